@@ -1,14 +1,26 @@
-#[cfg(test)]
-use crate::editor::BodyEditor;
-use crate::state::AppState;
+use crate::config::PluginCommand;
+use crate::datetime::{self, DateAdjust};
+use crate::diagnostics;
+use crate::discovery::DiscoveredSpec;
+use crate::links::ResponseLink;
+use crate::recording::{self, RecordedStep, StateSnapshot};
+use crate::script::{self, ConsoleEntry, Outcome};
+use crate::servers::{ServerForm, SpecServer};
+use crate::state::{AppState, RESPONSE_LINE_PAGE};
+use crate::table::TableSort;
 use crate::types::{
-    DetailTab, InputMode, PanelFocus, ParameterType, RequestEditMode, UrlInputField,
+    cycle_bool_value, cycle_enum_value, DetailTab, InputMode, PanelFocus, ParamForm, ParameterType,
+    RequestEditMode, UrlInputField, WorkspaceCommand,
 };
+use crate::viewer::ExternalViewer;
+use serde::{Deserialize, Serialize};
+use std::time::Instant;
 
 /// Represents all possible state-changing actions in the application
 /// This pattern separates input handling from state mutations, making the code
-/// more testable and enabling future features like undo/redo
-#[derive(Debug, Clone, PartialEq)]
+/// more testable. Actions editing a request config record it with
+/// `AppState::edit_config`, so `Undo`/`Redo` can take them back.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[allow(dead_code)] // Some actions defined for future use
 pub enum AppAction {
     // Navigation actions
@@ -23,6 +35,19 @@ pub enum AppAction {
 
     // View mode actions
     ToggleViewMode,
+    CycleGroupBy,
+    ToggleResponseVisual,
+    ExitResponseVisual,
+    ToggleResponseTable,
+    LoadMoreResponseLines,
+    ToggleResponseWrap,
+    ToggleThreePane,
+    CycleArrangement,
+    OpenResponseExternally(ExternalViewer),
+    CycleTableSort {
+        columns: usize,
+    },
+    ToggleTableSortOrder,
     ToggleGroupExpanded(String), // Toggle expand/collapse for a group
 
     // Input mode actions
@@ -33,13 +58,32 @@ pub enum AppAction {
     ExitUrlInputMode,
     EnterTokenInputMode,
     ExitTokenInputMode,
+    /// Offer these security schemes in the auth modal, by name, starting on the one in use
+    OfferAuthSchemes(Vec<String>),
+    /// Ask for the credential of the next offered scheme
+    NextAuthScheme,
     EnterSearchMode,
     ExitSearchMode,
+    EnterResponseSearchMode,
+    /// Leave response search, keeping the query (and its highlights) unless `clear`
+    ExitResponseSearchMode {
+        clear: bool,
+    },
+    AppendToResponseSearch(String),
+    BackspaceResponseSearch,
+    JumpToResponseMatch {
+        forward: bool,
+    },
     EnterBodyInputMode,
     ExitBodyInputMode,
     EnterConfirmClearTokenMode,
     ExitConfirmClearTokenMode,
+    EnterConfirmResetMode(String),
+    ExitConfirmResetMode,
     SetActiveUrlField(UrlInputField),
+    SetUrlError(UrlInputField, String),
+    /// Fill the base URL with the next (or previous) server of the loaded spec
+    CycleBaseUrlServer(bool),
 
     // Text input actions (for modals)
     AppendToUrlInput(String),
@@ -60,19 +104,197 @@ pub enum AppAction {
     DeleteWordUrlInput,
     DeleteWordBaseUrlInput,
     DeleteWordTokenInput,
+    AcceptUrlCompletion,
+    /// Show or hide the secrets in the token modal and parameter form
+    ToggleSecretReveal,
 
     // Parameter editing actions
     StartEditingParameter {
         param_name: String,
-        endpoint_path: String,
+        endpoint_key: String,
     },
     AppendToParamBuffer(String),
     BackspaceParamBuffer,
     ClearParamBuffer,
     ConfirmParameterEdit {
-        endpoint_path: String,
+        endpoint_key: String,
     },
     CancelParameterEdit,
+    FillSpecDefaults {
+        endpoint_key: String,
+    },
+    ResetRequestConfig {
+        endpoint_key: String,
+    },
+
+    // Endpoint note actions
+    EnterNoteMode(String),
+    ExitNoteMode,
+    AppendToNote(String),
+    BackspaceNote,
+    DeleteWordNote,
+    ClearNote,
+    SaveNote {
+        endpoint_key: String,
+    },
+
+    // Server form actions
+    OpenServerForm(ServerForm),
+    ServerFormNextField,
+    ServerFormPrevField,
+    CycleServerFormChoice {
+        forward: bool,
+    },
+    AppendToServerForm(String),
+    BackspaceServerForm,
+    SubmitServerForm,
+    CancelServerForm,
+
+    // Spec info actions
+    OpenSpecInfo,
+    CloseSpecInfo,
+
+    // Workspace tab actions
+    ChangeWorkspace(WorkspaceCommand),
+
+    // Usage stats actions
+    OpenStats,
+    CloseStats,
+    ToggleStatsSort,
+    ScrollStatsDown,
+    ScrollStatsUp,
+
+    // Spec changes actions
+    RefreshSpec,
+    ToggleWatch,
+    OpenSpecChanges,
+    CloseSpecChanges,
+    ScrollSpecChangesDown,
+    ScrollSpecChangesUp,
+
+    // Link picker actions
+    OpenLinkPicker(Vec<ResponseLink>),
+    CloseLinkPicker,
+    NextLink,
+    PrevLink,
+
+    // Spec discovery actions
+    StartSpecDiscovery,
+    SpecDiscoveryFinished(Vec<DiscoveredSpec>),
+    NextDiscoveredSpec,
+    PrevDiscoveredSpec,
+    PickDiscoveredSpec,
+    CloseSpecDiscovery,
+
+    // Plugin command picker actions
+    OpenPluginCommands(Vec<(String, PluginCommand)>),
+    ClosePluginCommands,
+    NextPluginCommand,
+    PrevPluginCommand,
+    SetPluginOutput(String),
+
+    // Spec switcher actions
+    OpenSpecSwitcher,
+    CloseSpecSwitcher,
+    NextListedSpec,
+    PrevListedSpec,
+
+    // Jobs popup actions
+    OpenJobs,
+    CloseJobs,
+    NextJob,
+    PrevJob,
+    CancelJob,
+    CancelAllJobs,
+
+    // Error banner
+    DismissError,
+
+    // Log panel and performance overlay
+    ToggleLogPanel,
+    ToggleMetrics,
+
+    // Undo and redo of request config edits
+    Undo,
+    Redo,
+
+    // Console actions
+    OpenConsole,
+    CloseConsole,
+    AppendToConsole(String),
+    BackspaceConsole,
+    SubmitConsole,
+
+    // CSV export actions
+    EnterCsvExportMode(String),
+    EnterSaveBodyMode(String),
+    EnterScriptExportMode(String),
+    EnterTrafficExportMode(String),
+    EnterDiagnosticsExportMode(String),
+    ToggleScriptScope,
+    ExitExportMode,
+    AppendToExportPath(String),
+    BackspaceExportPath,
+    ClearExportPath,
+    ExportFailed(String),
+
+    // Response snapshot actions
+    EnterSnapshotNameMode,
+    ExitSnapshotNameMode,
+    AppendToSnapshotName(String),
+    BackspaceSnapshotName,
+    SaveSnapshot {
+        endpoint_key: String,
+        name: String,
+    },
+    CycleSnapshotDiff {
+        endpoint_key: String,
+    },
+
+    // Request variant actions
+    EnterVariantNameMode,
+    ExitVariantNameMode,
+    AppendToVariantName(String),
+    BackspaceVariantName,
+    SaveVariant {
+        endpoint_key: String,
+        name: String,
+    },
+
+    // Parameter paste actions
+    EnterParamPasteMode(String),
+    ExitParamPasteMode,
+    AppendToParamPaste(String),
+    BackspaceParamPaste,
+    SubmitParamPaste,
+    CycleVariant {
+        endpoint_key: String,
+    },
+    CycleBoolParam {
+        endpoint_key: String,
+    },
+    CycleEnumParam {
+        endpoint_key: String,
+    },
+    AdjustDateParam {
+        endpoint_key: String,
+        adjust: DateAdjust,
+    },
+    NextParamSuggestion,
+    PrevParamSuggestion,
+    AcceptParamSuggestion,
+
+    // Bulk parameter form actions
+    OpenParamForm(ParamForm),
+    ParamFormNextField,
+    ParamFormPrevField,
+    AppendToParamForm(String),
+    CycleParamFormBool,
+    BackspaceParamForm,
+    DeleteWordParamForm,
+    ClearParamFormField,
+    SubmitParamForm,
+    CancelParamForm,
 
     // Authentication actions
     SetAuthToken(String),
@@ -87,12 +309,37 @@ pub enum AppAction {
 
     // Body section actions
     ToggleBodySection,
+    ToggleExtensionsSection,
+    CycleContentType {
+        endpoint_key: String,
+    },
 }
 
 /// Apply an action to the application state
 /// This is a pure state transformation function that mutates AppState based on the action
 /// All state mutations should go through this function to maintain consistency
+///
+/// While a session is being recorded, the action and the resulting state are recorded too.
+/// The action's name is kept for the diagnostic bundle either way.
 pub fn apply_action(action: AppAction, state: &mut AppState) {
+    diagnostics::record_action(&action);
+    let recorded = state
+        .ui
+        .recording
+        .is_some()
+        .then(|| recording::sanitize(&action));
+
+    update(action, state);
+
+    if let Some(action) = recorded {
+        let snapshot = StateSnapshot::capture(state);
+        if let Some(steps) = &mut state.ui.recording {
+            steps.push(RecordedStep { action, snapshot });
+        }
+    }
+}
+
+fn update(action: AppAction, state: &mut AppState) {
     match action {
         // Navigation
         AppAction::NavigateUp => {
@@ -105,10 +352,7 @@ pub fn apply_action(action: AppAction, state: &mut AppState) {
             state.ui.panel_focus = panel;
         }
         AppAction::NavigateToTab(tab) => {
-            state.ui.active_detail_tab = tab;
-            // Reset response scroll when navigating to/from Response tab
-            state.ui.response_scroll = 0;
-            state.ui.response_selected_line = 0;
+            state.switch_detail_tab(tab);
         }
         AppAction::NavigateTabForward => {
             use DetailTab::*;
@@ -118,20 +362,18 @@ pub fn apply_action(action: AppAction, state: &mut AppState) {
                     state.ui.panel_focus = PanelFocus::Details;
                 }
                 (PanelFocus::Details, Endpoint) => {
-                    state.ui.active_detail_tab = Request;
+                    state.switch_detail_tab(Request);
                     state.ui.selected_param_index = 0;
                 }
                 (PanelFocus::Details, Request) => {
-                    state.ui.active_detail_tab = Headers;
+                    state.switch_detail_tab(Headers);
                 }
                 (PanelFocus::Details, Headers) => {
-                    state.ui.active_detail_tab = Response;
-                    state.ui.response_scroll = 0;
-                    state.ui.response_selected_line = 0;
+                    state.switch_detail_tab(Response);
                 }
                 (PanelFocus::Details, Response) => {
                     state.ui.panel_focus = PanelFocus::EndpointsList;
-                    state.ui.active_detail_tab = Endpoint;
+                    state.switch_detail_tab(Endpoint);
                 }
             }
         }
@@ -143,16 +385,14 @@ pub fn apply_action(action: AppAction, state: &mut AppState) {
                     state.ui.panel_focus = PanelFocus::Details;
                 }
                 (PanelFocus::Details, Request) => {
-                    state.ui.active_detail_tab = Endpoint;
+                    state.switch_detail_tab(Endpoint);
                     state.ui.selected_param_index = 0;
                 }
                 (PanelFocus::Details, Response) => {
-                    state.ui.active_detail_tab = Headers;
-                    state.ui.response_scroll = 0;
-                    state.ui.response_selected_line = 0;
+                    state.switch_detail_tab(Headers);
                 }
                 (PanelFocus::Details, Headers) => {
-                    state.ui.active_detail_tab = Request;
+                    state.switch_detail_tab(Request);
                     state.ui.selected_param_index = 0;
                 }
                 (PanelFocus::Details, Endpoint) => {
@@ -175,6 +415,71 @@ pub fn apply_action(action: AppAction, state: &mut AppState) {
                 ViewMode::Grouped => ViewMode::Flat,
             };
         }
+        AppAction::ToggleResponseVisual => {
+            state.ui.response_visual_anchor = match state.ui.response_visual_anchor {
+                Some(_) => None,
+                None => Some(state.ui.response_selected_line),
+            };
+        }
+        AppAction::ExitResponseVisual => {
+            state.ui.response_visual_anchor = None;
+        }
+        AppAction::ToggleResponseTable => {
+            state.ui.response_table = !state.ui.response_table;
+            // Line and row indexes do not translate between the views
+            state.ui.response_scroll = 0;
+            state.ui.response_selected_line = 0;
+            state.ui.response_visual_anchor = None;
+        }
+        AppAction::LoadMoreResponseLines => {
+            state.ui.response_line_limit = state
+                .ui
+                .response_line_limit
+                .saturating_add(RESPONSE_LINE_PAGE);
+        }
+        AppAction::ToggleResponseWrap => {
+            state.ui.response_wrap = !state.ui.response_wrap;
+        }
+        AppAction::ToggleThreePane => {
+            state.ui.three_pane = !state.ui.three_pane;
+        }
+        AppAction::CycleArrangement => {
+            state.ui.arrangement = state.ui.arrangement.next();
+            state.ui.toast = Some((
+                format!("Layout: {}", state.ui.arrangement.label()),
+                Instant::now(),
+            ));
+        }
+        AppAction::OpenResponseExternally(viewer) => {
+            let has_body = state
+                .request
+                .current_response
+                .as_ref()
+                .is_some_and(|response| !response.is_error);
+            state.ui.open_externally = has_body.then_some(viewer);
+        }
+        AppAction::CycleTableSort { columns } => {
+            // Unsorted -> first column -> ... -> last column -> unsorted
+            state.ui.table_sort = match state.ui.table_sort {
+                None if columns > 0 => Some(TableSort {
+                    column: 0,
+                    descending: false,
+                }),
+                Some(sort) if sort.column + 1 < columns => Some(TableSort {
+                    column: sort.column + 1,
+                    ..sort
+                }),
+                _ => None,
+            };
+        }
+        AppAction::ToggleTableSortOrder => {
+            if let Some(sort) = &mut state.ui.table_sort {
+                sort.descending = !sort.descending;
+            }
+        }
+        AppAction::CycleGroupBy => {
+            state.ui.group_by = state.ui.group_by.next();
+        }
         AppAction::ToggleGroupExpanded(group_name) => {
             if state.ui.expanded_groups.contains(&group_name) {
                 state.ui.expanded_groups.remove(&group_name);
@@ -192,20 +497,47 @@ pub fn apply_action(action: AppAction, state: &mut AppState) {
             state.input.url_input = swagger_url.unwrap_or_default();
             state.input.base_url_input = base_url.unwrap_or_default();
             state.input.active_url_field = UrlInputField::SwaggerUrl;
+            state.input.url_error = None;
         }
         AppAction::ExitUrlInputMode => {
             state.input.mode = InputMode::Normal;
             state.input.url_input.clear();
             state.input.base_url_input.clear();
+            state.input.url_error = None;
         }
         AppAction::EnterTokenInputMode => {
             state.input.mode = InputMode::EnteringToken;
             state.input.token_input.clear();
+            state.input.reveal_secrets = false;
         }
         AppAction::ExitTokenInputMode => {
             state.input.mode = InputMode::Normal;
             state.input.token_input.clear();
         }
+        AppAction::OfferAuthSchemes(names) => {
+            state.input.auth_schemes = names
+                .into_iter()
+                .filter_map(|name| {
+                    let scheme = state.data.security_schemes.get(&name)?.clone();
+                    Some((name, scheme))
+                })
+                .collect();
+            let current = state.request.auth.scheme.as_ref().map(|(name, _)| name);
+            state.input.auth_scheme_index = state
+                .input
+                .auth_schemes
+                .iter()
+                .position(|(name, _)| Some(name) == current)
+                .unwrap_or(0);
+            prefill_token_input(state);
+        }
+        AppAction::NextAuthScheme => {
+            let count = state.input.auth_schemes.len();
+            if count > 1 {
+                state.input.auth_scheme_index = (state.input.auth_scheme_index + 1) % count;
+                prefill_token_input(state);
+            }
+        }
         AppAction::EnterSearchMode => {
             state.input.mode = InputMode::Searching;
             if state.search.query.is_empty() {
@@ -215,13 +547,31 @@ pub fn apply_action(action: AppAction, state: &mut AppState) {
         AppAction::ExitSearchMode => {
             state.input.mode = InputMode::Normal;
         }
+        AppAction::EnterResponseSearchMode => {
+            state.input.mode = InputMode::SearchingResponse;
+            state.search.response_query.clear();
+        }
+        AppAction::ExitResponseSearchMode { clear } => {
+            state.input.mode = InputMode::Normal;
+            if clear {
+                state.search.response_query.clear();
+            }
+        }
+        AppAction::AppendToResponseSearch(text) => {
+            state.search.response_query.push_str(&text);
+        }
+        AppAction::BackspaceResponseSearch => {
+            state.search.response_query.pop();
+        }
+        AppAction::JumpToResponseMatch { forward } => {
+            state.jump_to_response_match(forward);
+        }
         AppAction::EnterBodyInputMode => {
             state.input.mode = InputMode::EnteringBody;
             // Body input is pre-populated by caller
         }
         AppAction::ExitBodyInputMode => {
-            state.input.mode = InputMode::Normal;
-            state.input.body_editor.clear();
+            state.close_body_editor();
         }
         AppAction::EnterConfirmClearTokenMode => {
             state.input.mode = InputMode::ConfirmClearToken;
@@ -229,16 +579,50 @@ pub fn apply_action(action: AppAction, state: &mut AppState) {
         AppAction::ExitConfirmClearTokenMode => {
             state.input.mode = InputMode::Normal;
         }
+        AppAction::EnterConfirmResetMode(endpoint_key) => {
+            state.input.mode = InputMode::ConfirmResetRequest(endpoint_key);
+        }
+        AppAction::ExitConfirmResetMode => {
+            state.input.mode = InputMode::Normal;
+        }
         AppAction::SetActiveUrlField(field) => {
             state.input.active_url_field = field;
         }
+        AppAction::SetUrlError(field, message) => {
+            state.input.url_error = Some((field, message));
+        }
+        AppAction::CycleBaseUrlServer(forward) => {
+            let mut urls: Vec<String> = state
+                .data
+                .servers
+                .iter()
+                .map(SpecServer::default_url)
+                .collect();
+            urls.dedup();
+            if !urls.is_empty() {
+                let count = urls.len();
+                let next = match urls
+                    .iter()
+                    .position(|url| *url == state.input.base_url_input)
+                {
+                    Some(index) if forward => (index + 1) % count,
+                    Some(index) => (index + count - 1) % count,
+                    None if forward => 0,
+                    None => count - 1,
+                };
+                state.input.base_url_input = urls.swap_remove(next);
+                clear_url_error(state, UrlInputField::BaseUrl);
+            }
+        }
 
         // Text input for modals
         AppAction::AppendToUrlInput(text) => {
             state.input.url_input.push_str(&text);
+            clear_url_error(state, UrlInputField::SwaggerUrl);
         }
         AppAction::AppendToBaseUrlInput(text) => {
             state.input.base_url_input.push_str(&text);
+            clear_url_error(state, UrlInputField::BaseUrl);
         }
         AppAction::AppendToTokenInput(text) => {
             state.input.token_input.push_str(&text);
@@ -251,9 +635,11 @@ pub fn apply_action(action: AppAction, state: &mut AppState) {
         }
         AppAction::ClearUrlInput => {
             state.input.url_input.clear();
+            clear_url_error(state, UrlInputField::SwaggerUrl);
         }
         AppAction::ClearBaseUrlInput => {
             state.input.base_url_input.clear();
+            clear_url_error(state, UrlInputField::BaseUrl);
         }
         AppAction::ClearTokenInput => {
             state.input.token_input.clear();
@@ -266,9 +652,11 @@ pub fn apply_action(action: AppAction, state: &mut AppState) {
         }
         AppAction::BackspaceUrlInput => {
             state.input.url_input.pop();
+            clear_url_error(state, UrlInputField::SwaggerUrl);
         }
         AppAction::BackspaceBaseUrlInput => {
             state.input.base_url_input.pop();
+            clear_url_error(state, UrlInputField::BaseUrl);
         }
         AppAction::BackspaceTokenInput => {
             state.input.token_input.pop();
@@ -281,22 +669,38 @@ pub fn apply_action(action: AppAction, state: &mut AppState) {
         }
         AppAction::DeleteWordUrlInput => {
             delete_word(&mut state.input.url_input);
+            clear_url_error(state, UrlInputField::SwaggerUrl);
         }
         AppAction::DeleteWordBaseUrlInput => {
             delete_word(&mut state.input.base_url_input);
+            clear_url_error(state, UrlInputField::BaseUrl);
         }
         AppAction::DeleteWordTokenInput => {
             delete_word(&mut state.input.token_input);
         }
+        AppAction::AcceptUrlCompletion => {
+            if let Some(suffix) = state.input.url_completion().map(str::to_string) {
+                let field = state.input.active_url_field.clone();
+                match field {
+                    UrlInputField::SwaggerUrl => state.input.url_input.push_str(&suffix),
+                    UrlInputField::BaseUrl => state.input.base_url_input.push_str(&suffix),
+                }
+                clear_url_error(state, field);
+            }
+        }
+        AppAction::ToggleSecretReveal => {
+            state.input.reveal_secrets = !state.input.reveal_secrets;
+        }
 
         // Parameter editing
         AppAction::StartEditingParameter {
             param_name,
-            endpoint_path,
+            endpoint_key,
         } => {
             state.request.edit_mode = RequestEditMode::Editing(param_name.clone());
+            state.request.suggestion_index = 0;
             // Initialize buffer with current value if it exists
-            if let Some(config) = state.request.configs.get(&endpoint_path) {
+            if let Some(config) = state.request.configs.get(&endpoint_key) {
                 if let Some(value) = config.get_param_value(&param_name) {
                     state.request.param_edit_buffer = value.to_string();
                 } else {
@@ -305,41 +709,66 @@ pub fn apply_action(action: AppAction, state: &mut AppState) {
             } else {
                 state.request.param_edit_buffer.clear();
             }
+            // Enum parameters offer their values, the current one highlighted
+            state.request.param_choices = state
+                .get_endpoint_by_key(&endpoint_key)
+                .and_then(|endpoint| {
+                    endpoint
+                        .request_params()
+                        .into_iter()
+                        .find(|(param, _)| param.name == param_name)
+                        .map(|(param, _)| param.allowed_values())
+                })
+                .unwrap_or_default();
+            let current = &state.request.param_edit_buffer;
+            if let Some(index) = state
+                .request
+                .param_choices
+                .iter()
+                .position(|v| v == current)
+            {
+                state.request.suggestion_index = index;
+            }
         }
         AppAction::AppendToParamBuffer(text) => {
             state.request.param_edit_buffer.push_str(&text);
+            state.request.suggestion_index = 0;
         }
         AppAction::BackspaceParamBuffer => {
             state.request.param_edit_buffer.pop();
+            state.request.suggestion_index = 0;
         }
         AppAction::ClearParamBuffer => {
             state.request.param_edit_buffer.clear();
+            state.request.suggestion_index = 0;
         }
-        AppAction::ConfirmParameterEdit { endpoint_path } => {
+        AppAction::ConfirmParameterEdit { endpoint_key } => {
             if let RequestEditMode::Editing(param_name) = &state.request.edit_mode {
                 // Clone values we need before borrowing mutably
                 let buffer_value = state.request.param_edit_buffer.clone();
                 let param_name = param_name.clone();
 
                 // Determine if this is a path or query param
-                let is_path_param = state
-                    .get_selected_endpoint_by_path(&endpoint_path)
-                    .map(|endpoint| {
+                let param_type = state
+                    .get_endpoint_by_key(&endpoint_key)
+                    .and_then(|endpoint| {
                         endpoint
-                            .parameters
-                            .iter()
-                            .any(|p| p.name == param_name && p.location == "path")
+                            .request_params()
+                            .into_iter()
+                            .find(|(param, _)| param.name == param_name)
+                            .map(|(_, param_type)| param_type)
                     })
-                    .unwrap_or(false);
+                    .unwrap_or(ParameterType::Query);
 
-                // Get or create the config and insert the value
-                let config = state.get_or_create_request_config_by_path(&endpoint_path);
+                state.request.record_param_value(&param_name, &buffer_value);
 
-                if is_path_param {
-                    config.set_param(param_name, buffer_value, ParameterType::Path);
-                } else {
-                    config.set_param(param_name, buffer_value, ParameterType::Query);
-                }
+                let label = format!("edit of {param_name}");
+                state.edit_config(&endpoint_key, &label, |state| {
+                    // Get or create the config and insert the value
+                    let config = state.get_or_create_request_config_by_key(&endpoint_key);
+
+                    config.set_param(param_name, buffer_value, param_type);
+                });
             }
             state.request.edit_mode = RequestEditMode::Viewing;
             state.request.param_edit_buffer.clear();
@@ -348,9 +777,599 @@ pub fn apply_action(action: AppAction, state: &mut AppState) {
             state.request.edit_mode = RequestEditMode::Viewing;
             state.request.param_edit_buffer.clear();
         }
+        AppAction::FillSpecDefaults { endpoint_key } => {
+            state.edit_config(&endpoint_key, "fill from spec", |state| {
+                state.fill_spec_defaults(&endpoint_key);
+            });
+        }
+        AppAction::ResetRequestConfig { endpoint_key } => {
+            // Configs are recreated from spec defaults on next use
+            state.edit_config(&endpoint_key, "reset request", |state| {
+                state.request.configs.remove(&endpoint_key);
+            });
+            state.request.edit_mode = RequestEditMode::Viewing;
+            state.request.param_edit_buffer.clear();
+            state.ui.selected_param_index = 0;
+        }
+        AppAction::EnterNoteMode(current_note) => {
+            state.input.mode = InputMode::EditingNote;
+            state.input.note_input = current_note;
+        }
+        AppAction::ExitNoteMode => {
+            state.input.mode = InputMode::Normal;
+            state.input.note_input.clear();
+        }
+        AppAction::AppendToNote(text) => {
+            state.input.note_input.push_str(&text);
+        }
+        AppAction::BackspaceNote => {
+            state.input.note_input.pop();
+        }
+        AppAction::DeleteWordNote => {
+            delete_word(&mut state.input.note_input);
+        }
+        AppAction::ClearNote => {
+            state.input.note_input.clear();
+        }
+        AppAction::SaveNote { endpoint_key } => {
+            let note = std::mem::take(&mut state.input.note_input);
+            state.set_note(endpoint_key, &note);
+            state.update_filtered_endpoints();
+            state.input.mode = InputMode::Normal;
+        }
+        AppAction::OpenServerForm(form) => {
+            state.input.server_form = form;
+            state.input.mode = InputMode::ConfiguringServer;
+        }
+        AppAction::ServerFormNextField => {
+            state.input.server_form.next_field();
+        }
+        AppAction::ServerFormPrevField => {
+            state.input.server_form.prev_field();
+        }
+        AppAction::CycleServerFormChoice { forward } => {
+            state.input.server_form.cycle(forward);
+        }
+        AppAction::AppendToServerForm(text) => {
+            if let Some(field) = state.input.server_form.active_variable_mut() {
+                if field.choices.is_empty() {
+                    field.value.push_str(&text);
+                }
+            }
+        }
+        AppAction::BackspaceServerForm => {
+            if let Some(field) = state.input.server_form.active_variable_mut() {
+                if field.choices.is_empty() {
+                    field.value.pop();
+                }
+            }
+        }
+        AppAction::SubmitServerForm => {
+            let form = std::mem::take(&mut state.input.server_form);
+            state.input.server_base_url = Some(form.url());
+            state.input.mode = InputMode::Normal;
+        }
+        AppAction::CancelServerForm => {
+            state.input.server_form = ServerForm::default();
+            state.input.mode = InputMode::Normal;
+        }
+        AppAction::OpenSpecInfo => {
+            state.input.mode = InputMode::ViewingSpecInfo;
+        }
+        AppAction::CloseSpecInfo => {
+            state.input.mode = InputMode::Normal;
+        }
+        AppAction::ChangeWorkspace(command) => {
+            state.ui.workspace_command = Some(command);
+        }
+        AppAction::OpenStats => {
+            state.input.mode = InputMode::ViewingStats;
+            state.ui.stats_scroll = 0;
+        }
+        AppAction::CloseStats => {
+            state.input.mode = InputMode::Normal;
+        }
+        AppAction::ToggleStatsSort => {
+            state.ui.stats_sort = state.ui.stats_sort.toggle();
+            state.ui.stats_scroll = 0;
+        }
+        AppAction::ScrollStatsDown => {
+            if state.ui.stats_scroll + 1 < state.request.usage_stats.len() {
+                state.ui.stats_scroll += 1;
+            }
+        }
+        AppAction::ScrollStatsUp => {
+            state.ui.stats_scroll = state.ui.stats_scroll.saturating_sub(1);
+        }
+        AppAction::RefreshSpec => {
+            state.data.refresh_requested = true;
+        }
+        AppAction::ToggleWatch => {
+            state.data.watching = !state.data.watching;
+            let message = if state.data.watching {
+                format!(
+                    "Watching the spec: reloading every {}s",
+                    state.data.watch_interval.as_secs()
+                )
+            } else {
+                "Stopped watching the spec".to_string()
+            };
+            state.ui.toast = Some((message, Instant::now()));
+        }
+        AppAction::OpenSpecChanges => {
+            if state.data.spec_diff.is_some() {
+                state.input.mode = InputMode::ViewingSpecChanges;
+                state.ui.spec_diff_scroll = 0;
+            } else {
+                state.ui.toast = Some((
+                    "No spec changes found since the last load".to_string(),
+                    Instant::now(),
+                ));
+            }
+        }
+        AppAction::CloseSpecChanges => {
+            state.input.mode = InputMode::Normal;
+        }
+        AppAction::ScrollSpecChangesDown => {
+            let lines = state.data.spec_diff.as_ref().map_or(0, |d| d.lines().len());
+            if state.ui.spec_diff_scroll + 1 < lines {
+                state.ui.spec_diff_scroll += 1;
+            }
+        }
+        AppAction::ScrollSpecChangesUp => {
+            state.ui.spec_diff_scroll = state.ui.spec_diff_scroll.saturating_sub(1);
+        }
+        AppAction::OpenLinkPicker(links) => {
+            state.input.mode = InputMode::SelectingLink;
+            state.input.links = links;
+            state.input.link_index = 0;
+        }
+        AppAction::CloseLinkPicker => {
+            state.input.mode = InputMode::Normal;
+            state.input.links.clear();
+        }
+        AppAction::NextLink => {
+            if state.input.link_index + 1 < state.input.links.len() {
+                state.input.link_index += 1;
+            }
+        }
+        AppAction::PrevLink => {
+            state.input.link_index = state.input.link_index.saturating_sub(1);
+        }
+        AppAction::StartSpecDiscovery => {
+            state.input.mode = InputMode::SelectingDiscoveredSpec;
+            state.input.discovered_specs = None;
+            state.input.discovered_index = 0;
+        }
+        AppAction::SpecDiscoveryFinished(specs) => {
+            state.input.discovered_specs = Some(specs);
+            state.input.discovered_index = 0;
+        }
+        AppAction::NextDiscoveredSpec => {
+            let count = state.input.discovered_specs.as_ref().map_or(0, Vec::len);
+            if state.input.discovered_index + 1 < count {
+                state.input.discovered_index += 1;
+            }
+        }
+        AppAction::PrevDiscoveredSpec => {
+            state.input.discovered_index = state.input.discovered_index.saturating_sub(1);
+        }
+        AppAction::PickDiscoveredSpec => {
+            let picked = state
+                .input
+                .discovered_specs
+                .as_ref()
+                .and_then(|specs| specs.get(state.input.discovered_index))
+                .cloned();
+            if let Some(spec) = picked {
+                state.input.url_input = spec.spec_url;
+                state.input.base_url_input = spec.base_url.unwrap_or_default();
+                state.input.active_url_field = UrlInputField::SwaggerUrl;
+                state.input.url_error = None;
+            }
+            state.input.mode = InputMode::EnteringUrl;
+        }
+        AppAction::CloseSpecDiscovery => {
+            state.input.mode = InputMode::EnteringUrl;
+        }
+        AppAction::OpenPluginCommands(commands) => {
+            state.input.mode = InputMode::SelectingPluginCommand;
+            state.input.plugin_commands = commands;
+            state.input.plugin_command_index = 0;
+            state.input.plugin_output = None;
+        }
+        AppAction::ClosePluginCommands => {
+            state.input.mode = InputMode::Normal;
+            state.input.plugin_commands.clear();
+        }
+        AppAction::NextPluginCommand => {
+            if state.input.plugin_command_index + 1 < state.input.plugin_commands.len() {
+                state.input.plugin_command_index += 1;
+            }
+        }
+        AppAction::PrevPluginCommand => {
+            state.input.plugin_command_index = state.input.plugin_command_index.saturating_sub(1);
+        }
+        AppAction::SetPluginOutput(output) => {
+            state.input.plugin_output = Some(output);
+        }
+        AppAction::OpenSpecSwitcher => {
+            if state.input.spec_list.is_empty() {
+                state.ui.toast = Some((
+                    "No specs to switch to: add [[specs]] to config.toml".to_string(),
+                    Instant::now(),
+                ));
+            } else {
+                state.input.mode = InputMode::SwitchingSpec;
+                state.input.spec_list_index = 0;
+            }
+        }
+        AppAction::CloseSpecSwitcher => {
+            state.input.mode = InputMode::Normal;
+        }
+        AppAction::NextListedSpec => {
+            if state.input.spec_list_index + 1 < state.input.spec_list.len() {
+                state.input.spec_list_index += 1;
+            }
+        }
+        AppAction::PrevListedSpec => {
+            state.input.spec_list_index = state.input.spec_list_index.saturating_sub(1);
+        }
+        AppAction::OpenJobs => {
+            state.input.mode = InputMode::ViewingJobs;
+            state.input.job_index = 0;
+        }
+        AppAction::CloseJobs => {
+            state.input.mode = InputMode::Normal;
+        }
+        AppAction::NextJob => {
+            if state.input.job_index + 1 < state.request.jobs.len() {
+                state.input.job_index += 1;
+            }
+        }
+        AppAction::PrevJob => {
+            state.input.job_index = state.input.job_index.saturating_sub(1);
+        }
+        AppAction::CancelJob => {
+            let id = state
+                .request
+                .jobs
+                .get(state.input.job_index)
+                .map(|job| job.id);
+            if let Some(endpoint_key) = id.and_then(|id| state.request.cancel_job(id)) {
+                state.ui.toast = Some((format!("Cancelled {endpoint_key}"), Instant::now()));
+            }
+            state.input.job_index = state
+                .input
+                .job_index
+                .min(state.request.jobs.len().saturating_sub(1));
+        }
+        AppAction::DismissError => {
+            state.ui.error_banner = None;
+        }
+        AppAction::ToggleLogPanel => {
+            state.ui.show_log = !state.ui.show_log;
+        }
+        AppAction::ToggleMetrics => {
+            state.ui.show_metrics = !state.ui.show_metrics;
+        }
+        AppAction::Undo => {
+            let message = match state.undo() {
+                Some(label) => format!("Undid {label} (Ctrl+R: redo)"),
+                None => "Nothing to undo".to_string(),
+            };
+            state.ui.toast = Some((message, Instant::now()));
+        }
+        AppAction::Redo => {
+            let message = match state.redo() {
+                Some(label) => format!("Redid {label}"),
+                None => "Nothing to redo".to_string(),
+            };
+            state.ui.toast = Some((message, Instant::now()));
+        }
+        AppAction::CancelAllJobs => {
+            let cancelled = state.request.cancel_all_jobs();
+            if cancelled > 0 {
+                state.ui.toast = Some((format!("Cancelled {cancelled} requests"), Instant::now()));
+            }
+            state.input.job_index = 0;
+        }
+        AppAction::OpenConsole => {
+            state.input.mode = InputMode::Console;
+            state.input.console_input.clear();
+        }
+        AppAction::CloseConsole => {
+            state.input.mode = InputMode::Normal;
+        }
+        AppAction::AppendToConsole(text) => {
+            state.input.console_input.push_str(&text);
+        }
+        AppAction::BackspaceConsole => {
+            state.input.console_input.pop();
+        }
+        AppAction::SubmitConsole => {
+            let input = std::mem::take(&mut state.input.console_input);
+            if !input.trim().is_empty() {
+                let output = script::run(&input, &mut state.console_scope()).map(|outcome| {
+                    if let Outcome::Assigned(name, value) = &outcome {
+                        state.input.console_vars.insert(name.clone(), value.clone());
+                    }
+                    outcome.display()
+                });
+                state.input.console_log.push(ConsoleEntry { input, output });
+            }
+        }
+        AppAction::EnterCsvExportMode(default_path) => {
+            state.input.mode = InputMode::ExportingCsv;
+            state.input.export_path_input = default_path;
+            state.input.export_error = None;
+        }
+        AppAction::EnterSaveBodyMode(default_path) => {
+            state.input.mode = InputMode::SavingBody;
+            state.input.export_path_input = default_path;
+            state.input.export_error = None;
+        }
+        AppAction::EnterScriptExportMode(default_path) => {
+            state.input.mode = InputMode::ExportingScript;
+            state.input.export_path_input = default_path;
+            state.input.export_collection = false;
+            state.input.export_error = None;
+        }
+        AppAction::EnterTrafficExportMode(default_path) => {
+            state.input.mode = InputMode::ExportingTraffic;
+            state.input.export_path_input = default_path;
+            state.input.export_error = None;
+        }
+        AppAction::EnterDiagnosticsExportMode(default_path) => {
+            state.input.mode = InputMode::ExportingDiagnostics;
+            state.input.export_path_input = default_path;
+            state.input.export_error = None;
+        }
+        AppAction::ToggleScriptScope => {
+            state.input.export_collection = !state.input.export_collection;
+            state.input.export_error = None;
+        }
+        AppAction::ExitExportMode => {
+            state.input.mode = InputMode::Normal;
+            state.input.export_path_input.clear();
+            state.input.export_collection = false;
+            state.input.export_error = None;
+        }
+        AppAction::AppendToExportPath(text) => {
+            state.input.export_path_input.push_str(&text);
+            state.input.export_error = None;
+        }
+        AppAction::BackspaceExportPath => {
+            state.input.export_path_input.pop();
+            state.input.export_error = None;
+        }
+        AppAction::ClearExportPath => {
+            state.input.export_path_input.clear();
+            state.input.export_error = None;
+        }
+        AppAction::ExportFailed(error) => {
+            state.input.export_error = Some(error);
+        }
+        AppAction::EnterSnapshotNameMode => {
+            state.input.mode = InputMode::EnteringSnapshotName;
+            state.input.snapshot_name_input.clear();
+        }
+        AppAction::ExitSnapshotNameMode => {
+            state.input.mode = InputMode::Normal;
+            state.input.snapshot_name_input.clear();
+        }
+        AppAction::AppendToSnapshotName(text) => {
+            state.input.snapshot_name_input.push_str(&text);
+        }
+        AppAction::BackspaceSnapshotName => {
+            state.input.snapshot_name_input.pop();
+        }
+        AppAction::SaveSnapshot { endpoint_key, name } => {
+            state.save_snapshot(&endpoint_key, name);
+        }
+        AppAction::CycleSnapshotDiff { endpoint_key } => {
+            state.cycle_snapshot_diff(&endpoint_key);
+        }
+        AppAction::EnterVariantNameMode => {
+            state.input.mode = InputMode::EnteringVariantName;
+            state.input.variant_name_input.clear();
+        }
+        AppAction::ExitVariantNameMode => {
+            state.input.mode = InputMode::Normal;
+            state.input.variant_name_input.clear();
+        }
+        AppAction::AppendToVariantName(text) => {
+            state.input.variant_name_input.push_str(&text);
+        }
+        AppAction::BackspaceVariantName => {
+            state.input.variant_name_input.pop();
+        }
+        AppAction::SaveVariant { endpoint_key, name } => {
+            state.request.save_variant(&endpoint_key, name);
+        }
+        AppAction::EnterParamPasteMode(endpoint_key) => {
+            state.input.mode = InputMode::PastingParams(endpoint_key);
+            state.input.param_paste_input.clear();
+            state.input.param_paste_error = None;
+        }
+        AppAction::ExitParamPasteMode => {
+            state.input.mode = InputMode::Normal;
+            state.input.param_paste_input.clear();
+            state.input.param_paste_error = None;
+        }
+        AppAction::AppendToParamPaste(text) => {
+            state.input.param_paste_input.push_str(&text);
+            state.input.param_paste_error = None;
+        }
+        AppAction::BackspaceParamPaste => {
+            state.input.param_paste_input.pop();
+            state.input.param_paste_error = None;
+        }
+        AppAction::SubmitParamPaste => {
+            let InputMode::PastingParams(endpoint_key) = state.input.mode.clone() else {
+                return;
+            };
+            let text = state.input.param_paste_input.clone();
+            let mut result = Ok((0, Vec::new()));
+            state.edit_config(&endpoint_key, "parameter paste", |state| {
+                result = state.fill_pasted_params(&endpoint_key, &text);
+            });
+            match result {
+                Ok((filled, unknown)) => {
+                    let mut message = format!("Filled {filled} parameters");
+                    if !unknown.is_empty() {
+                        message.push_str(&format!(" (no parameter for {})", unknown.join(", ")));
+                    }
+                    state.ui.toast = Some((message, Instant::now()));
+                    state.input.mode = InputMode::Normal;
+                    state.input.param_paste_input.clear();
+                }
+                Err(error) => state.input.param_paste_error = Some(error),
+            }
+        }
+        AppAction::CycleVariant { endpoint_key } => {
+            state.edit_config(&endpoint_key, "variant load", |state| {
+                state.request.cycle_variant(&endpoint_key);
+            });
+        }
+        AppAction::CycleBoolParam { endpoint_key } => {
+            let selected = state
+                .get_endpoint_by_key(&endpoint_key)
+                .and_then(|endpoint| endpoint.param_at(state.ui.selected_param_index))
+                .filter(|(param, _)| param.is_boolean())
+                .map(|(param, param_type)| (param.name.clone(), param_type));
+
+            if let Some((param_name, param_type)) = selected {
+                let label = format!("toggle of {param_name}");
+                state.edit_config(&endpoint_key, &label, |state| {
+                    let config = state.get_or_create_request_config_by_key(&endpoint_key);
+                    let next = cycle_bool_value(config.get_param_value(&param_name).unwrap_or(""));
+                    config.set_param(param_name, next.to_string(), param_type);
+                });
+            }
+        }
+        AppAction::CycleEnumParam { endpoint_key } => {
+            let selected = state
+                .get_endpoint_by_key(&endpoint_key)
+                .and_then(|endpoint| endpoint.param_at(state.ui.selected_param_index))
+                .map(|(param, param_type)| (param.name.clone(), param_type, param.allowed_values()))
+                .filter(|(_, _, allowed)| !allowed.is_empty());
+
+            if let Some((param_name, param_type, allowed)) = selected {
+                let label = format!("change of {param_name}");
+                state.edit_config(&endpoint_key, &label, |state| {
+                    let config = state.get_or_create_request_config_by_key(&endpoint_key);
+                    let current = config.get_param_value(&param_name).unwrap_or("");
+                    let next = cycle_enum_value(&allowed, current);
+                    config.set_param(param_name, next, param_type);
+                });
+            }
+        }
+        AppAction::AdjustDateParam {
+            endpoint_key,
+            adjust,
+        } => {
+            let selected = state
+                .get_endpoint_by_key(&endpoint_key)
+                .and_then(|endpoint| endpoint.param_at(state.ui.selected_param_index))
+                .and_then(|(param, param_type)| {
+                    param
+                        .date_format()
+                        .map(|format| (param.name.clone(), param_type, format))
+                });
+
+            if let Some((param_name, param_type, format)) = selected {
+                let label = format!("change of {param_name}");
+                state.edit_config(&endpoint_key, &label, |state| {
+                    let config = state.get_or_create_request_config_by_key(&endpoint_key);
+                    let current = config.get_param_value(&param_name).unwrap_or("");
+                    let next = datetime::adjust(current, format, adjust);
+                    config.set_param(param_name, next, param_type);
+                });
+            }
+        }
+        AppAction::NextParamSuggestion => {
+            let count = state.request.param_suggestions().len();
+            if count > 0 {
+                state.request.suggestion_index = (state.request.suggestion_index + 1) % count;
+            }
+        }
+        AppAction::PrevParamSuggestion => {
+            let count = state.request.param_suggestions().len();
+            if count > 0 {
+                state.request.suggestion_index =
+                    (state.request.suggestion_index.min(count - 1) + count - 1) % count;
+            }
+        }
+        AppAction::AcceptParamSuggestion => {
+            if let Some(value) = state.request.selected_suggestion().map(str::to_string) {
+                state.request.param_edit_buffer = value;
+                state.request.suggestion_index = 0;
+            }
+        }
+
+        // Bulk parameter form
+        AppAction::OpenParamForm(form) => {
+            state.input.param_form = form;
+            state.input.mode = InputMode::EditingParamForm;
+            state.input.reveal_secrets = false;
+        }
+        AppAction::ParamFormNextField => {
+            state.input.param_form.next_field();
+        }
+        AppAction::ParamFormPrevField => {
+            state.input.param_form.prev_field();
+        }
+        AppAction::AppendToParamForm(text) => {
+            if let Some(field) = state.input.param_form.active_field_mut() {
+                if !field.is_boolean() {
+                    field.value.push_str(&text);
+                }
+            }
+        }
+        AppAction::CycleParamFormBool => {
+            if let Some(field) = state.input.param_form.active_field_mut() {
+                if field.is_boolean() {
+                    field.value = cycle_bool_value(&field.value).to_string();
+                }
+            }
+        }
+        AppAction::BackspaceParamForm => {
+            if let Some(field) = state.input.param_form.active_field_mut() {
+                field.value.pop();
+            }
+        }
+        AppAction::DeleteWordParamForm => {
+            if let Some(field) = state.input.param_form.active_field_mut() {
+                delete_word(&mut field.value);
+            }
+        }
+        AppAction::ClearParamFormField => {
+            if let Some(field) = state.input.param_form.active_field_mut() {
+                field.value.clear();
+            }
+        }
+        AppAction::SubmitParamForm => {
+            let form = std::mem::take(&mut state.input.param_form);
+            for field in &form.fields {
+                state.request.record_param_value(&field.name, &field.value);
+            }
+            state.edit_config(&form.endpoint_key, "parameter form", |state| {
+                let config = state.get_or_create_request_config_by_key(&form.endpoint_key);
+                for field in form.fields {
+                    config.set_param(field.name, field.value, field.param_type);
+                }
+            });
+            state.input.mode = InputMode::Normal;
+        }
+        AppAction::CancelParamForm => {
+            state.input.param_form = ParamForm::default();
+            state.input.mode = InputMode::Normal;
+        }
 
         // Authentication
         AppAction::SetAuthToken(token) => {
+            state.request.auth.scheme = state.input.selected_auth_scheme().cloned();
             state.request.auth.set_token(token);
         }
         AppAction::ClearAuthToken => {
@@ -359,10 +1378,10 @@ pub fn apply_action(action: AppAction, state: &mut AppState) {
 
         // Response
         AppAction::SetErrorResponse(error_msg) => {
-            state.request.current_response = Some(crate::types::ApiResponse::error(error_msg));
+            state.set_response(Some(crate::types::ApiResponse::error(error_msg)));
         }
         AppAction::ClearResponse => {
-            state.request.current_response = None;
+            state.set_response(None);
         }
 
         // State resets
@@ -374,9 +1393,53 @@ pub fn apply_action(action: AppAction, state: &mut AppState) {
         AppAction::ToggleBodySection => {
             state.ui.body_section_expanded = !state.ui.body_section_expanded;
         }
+        AppAction::ToggleExtensionsSection => {
+            state.ui.extensions_expanded = !state.ui.extensions_expanded;
+        }
+        AppAction::CycleContentType { endpoint_key } => {
+            let Some(endpoint) = state.get_endpoint_by_key(&endpoint_key) else {
+                return;
+            };
+            let content_types: Vec<String> = endpoint
+                .request_body
+                .iter()
+                .flat_map(|body| body.content_types())
+                .map(str::to_string)
+                .collect();
+            let current = endpoint.body_content_type(state.request.configs.get(&endpoint_key));
+
+            if content_types.len() > 1 {
+                let next = content_types
+                    .iter()
+                    .position(|media_type| *media_type == current)
+                    .map_or(0, |index| (index + 1) % content_types.len());
+                state.edit_config(&endpoint_key, "content type change", |state| {
+                    state
+                        .get_or_create_request_config_by_key(&endpoint_key)
+                        .content_type = Some(content_types[next].clone());
+                });
+            }
+        }
+    }
+}
+
+/// Clear the URL modal validation error if it belongs to the edited field
+fn clear_url_error(state: &mut AppState, field: UrlInputField) {
+    if matches!(&state.input.url_error, Some((error_field, _)) if *error_field == field) {
+        state.input.url_error = None;
     }
 }
 
+/// Start the auth modal's input with the current token when it is for the chosen scheme
+fn prefill_token_input(state: &mut AppState) {
+    let chosen = state.input.selected_auth_scheme().map(|(name, _)| name);
+    let current = state.request.auth.scheme.as_ref().map(|(name, _)| name);
+    state.input.token_input = match &state.request.auth.token {
+        Some(token) if chosen == current => token.clone(),
+        _ => String::new(),
+    };
+}
+
 /// Helper function to delete the last word from a string (Ctrl+W behavior)
 fn delete_word(s: &mut String) {
     // Trim trailing whitespace first
@@ -394,56 +1457,14 @@ fn delete_word(s: &mut String) {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{
-        state::AuthState,
-        types::{LoadingState, ViewMode},
-    };
-    use std::collections::{HashMap, HashSet};
+    use crate::config::SpecConfig;
+    use crate::types::{ApiEndpoint, ApiResponse, MediaTypeSpec, RequestBodySpec, ViewMode};
+    use std::collections::HashMap;
 
     fn create_test_state() -> AppState {
-        use crate::state::{DataState, InputState, RequestState, SearchState, UiState};
-
-        AppState {
-            data: DataState {
-                endpoints: vec![],
-                grouped_endpoints: HashMap::new(),
-                loading_state: LoadingState::Idle,
-                retry_count: 0,
-            },
-            ui: UiState {
-                view_mode: ViewMode::Flat,
-                expanded_groups: HashSet::new(),
-                panel_focus: PanelFocus::EndpointsList,
-                active_detail_tab: DetailTab::Endpoint,
-                selected_param_index: 0,
-                body_section_expanded: true,
-                response_scroll: 0,
-                response_selected_line: 0,
-                yank_flash: false,
-            },
-            input: InputState {
-                mode: InputMode::Normal,
-                token_input: String::new(),
-                url_input: String::new(),
-                base_url_input: String::new(),
-                active_url_field: UrlInputField::SwaggerUrl,
-                body_editor: BodyEditor::new(),
-                body_validation_error: None,
-            },
-            request: RequestState {
-                auth: AuthState::new(),
-                executing_endpoint: None,
-                current_response: None,
-                configs: HashMap::new(),
-                edit_mode: RequestEditMode::Viewing,
-                param_edit_buffer: String::new(),
-            },
-            search: SearchState {
-                query: String::new(),
-                filtered_endpoints: Vec::new(),
-                filtered_grouped_endpoints: HashMap::new(),
-            },
-        }
+        let mut state = AppState::default();
+        state.ui.view_mode = ViewMode::Flat;
+        state
     }
 
     #[test]
@@ -560,6 +1581,23 @@ mod tests {
         assert!(state.ui.expanded_groups.is_empty());
     }
 
+    #[test]
+    fn test_spec_switcher() {
+        let mut state = create_test_state();
+        apply_action(AppAction::OpenSpecSwitcher, &mut state);
+        assert_eq!(state.input.mode, InputMode::Normal);
+        assert!(state.ui.toast.is_some());
+
+        state.input.spec_list = vec![SpecConfig::default(), SpecConfig::default()];
+        apply_action(AppAction::OpenSpecSwitcher, &mut state);
+        assert_eq!(state.input.mode, InputMode::SwitchingSpec);
+        apply_action(AppAction::NextListedSpec, &mut state);
+        apply_action(AppAction::NextListedSpec, &mut state);
+        assert_eq!(state.input.spec_list_index, 1);
+        apply_action(AppAction::CloseSpecSwitcher, &mut state);
+        assert_eq!(state.input.mode, InputMode::Normal);
+    }
+
     #[test]
     fn test_enter_url_input_mode() {
         let mut state = create_test_state();
@@ -600,6 +1638,83 @@ mod tests {
         assert_eq!(state.input.url_input, "");
     }
 
+    #[test]
+    fn test_accept_url_completion() {
+        let mut state = create_test_state();
+        state.input.url_history = vec!["http://localhost:5000/swagger.json".to_string()];
+        state.input.base_url_history = vec!["http://localhost:5000".to_string()];
+
+        apply_action(
+            AppAction::AppendToUrlInput("http://local".to_string()),
+            &mut state,
+        );
+        apply_action(AppAction::AcceptUrlCompletion, &mut state);
+        assert_eq!(state.input.url_input, "http://localhost:5000/swagger.json");
+
+        // Completion follows the active field
+        apply_action(
+            AppAction::SetActiveUrlField(UrlInputField::BaseUrl),
+            &mut state,
+        );
+        apply_action(
+            AppAction::AppendToBaseUrlInput("http://".to_string()),
+            &mut state,
+        );
+        apply_action(AppAction::AcceptUrlCompletion, &mut state);
+        assert_eq!(state.input.base_url_input, "http://localhost:5000");
+    }
+
+    #[test]
+    fn test_cycle_base_url_server() {
+        use crate::servers::ServerVariable;
+
+        let mut state = create_test_state();
+        state.data.servers = vec![
+            SpecServer {
+                url: "https://{region}.example.com".to_string(),
+                variables: HashMap::from([(
+                    "region".to_string(),
+                    ServerVariable {
+                        default: Some("eu".to_string()),
+                        ..Default::default()
+                    },
+                )]),
+                ..Default::default()
+            },
+            SpecServer {
+                url: "http://localhost:8080".to_string(),
+                ..Default::default()
+            },
+        ];
+        state.input.base_url_input = "typed".to_string();
+
+        apply_action(AppAction::CycleBaseUrlServer(true), &mut state);
+        assert_eq!(state.input.base_url_input, "https://eu.example.com");
+        apply_action(AppAction::CycleBaseUrlServer(true), &mut state);
+        assert_eq!(state.input.base_url_input, "http://localhost:8080");
+        apply_action(AppAction::CycleBaseUrlServer(true), &mut state);
+        assert_eq!(state.input.base_url_input, "https://eu.example.com");
+        apply_action(AppAction::CycleBaseUrlServer(false), &mut state);
+        assert_eq!(state.input.base_url_input, "http://localhost:8080");
+    }
+
+    #[test]
+    fn test_url_error_cleared_when_field_edited() {
+        let mut state = create_test_state();
+
+        apply_action(
+            AppAction::SetUrlError(UrlInputField::BaseUrl, "bad".to_string()),
+            &mut state,
+        );
+
+        // Editing the other field keeps the error
+        apply_action(AppAction::AppendToUrlInput("h".to_string()), &mut state);
+        assert!(state.input.url_error.is_some());
+
+        apply_action(AppAction::BackspaceBaseUrlInput, &mut state);
+        assert!(state.input.url_error.is_none());
+    }
+
     #[test]
     fn test_delete_word() {
         let mut s = "hello world foo".to_string();
@@ -638,6 +1753,46 @@ mod tests {
         assert!(!state.request.auth.is_authenticated());
     }
 
+    #[test]
+    fn test_auth_scheme_choice() {
+        use crate::types::{Credential, SecurityScheme};
+
+        let mut state = create_test_state();
+        let api_key = SecurityScheme {
+            kind: "apiKey".to_string(),
+            name: Some("api_key".to_string()),
+            location: Some("query".to_string()),
+            ..Default::default()
+        };
+        state.data.security_schemes = HashMap::from([
+            ("bearerAuth".to_string(), SecurityScheme::default()),
+            ("keyAuth".to_string(), api_key),
+        ]);
+        state.request.auth.set_token("old".to_string());
+
+        // A bearer token set before schemes were known is only filled in without a scheme
+        let offer = vec!["bearerAuth".to_string(), "keyAuth".to_string()];
+        apply_action(AppAction::EnterTokenInputMode, &mut state);
+        apply_action(AppAction::OfferAuthSchemes(offer.clone()), &mut state);
+        assert_eq!(state.input.auth_scheme_index, 0);
+        assert_eq!(state.input.token_input, "");
+
+        apply_action(AppAction::NextAuthScheme, &mut state);
+        apply_action(AppAction::AppendToTokenInput("k3y".to_string()), &mut state);
+        apply_action(AppAction::SetAuthToken("k3y".to_string()), &mut state);
+        apply_action(AppAction::ExitTokenInputMode, &mut state);
+        assert_eq!(
+            state.request.auth.credential(),
+            Some(Credential::Query("api_key".to_string(), "k3y".to_string()))
+        );
+
+        // Reopening starts on the scheme in use, with its key
+        apply_action(AppAction::EnterTokenInputMode, &mut state);
+        apply_action(AppAction::OfferAuthSchemes(offer), &mut state);
+        assert_eq!(state.input.auth_scheme_index, 1);
+        assert_eq!(state.input.token_input, "k3y");
+    }
+
     #[test]
     fn test_parameter_buffer_actions() {
         let mut state = create_test_state();
@@ -666,6 +1821,175 @@ mod tests {
         assert_eq!(state.request.param_edit_buffer, "");
     }
 
+    #[test]
+    fn test_param_form_submit_saves_all_fields() {
+        use crate::types::ParamFormField;
+
+        let mut state = create_test_state();
+        let field = |name: &str, param_type: ParameterType| ParamFormField {
+            name: name.to_string(),
+            param_type,
+            value: String::new(),
+            required: false,
+            type_info: "string".to_string(),
+        };
+
+        apply_action(
+            AppAction::OpenParamForm(ParamForm {
+                endpoint_key: "GET /users/{id}".to_string(),
+                fields: vec![
+                    field("id", ParameterType::Path),
+                    field("limit", ParameterType::Query),
+                ],
+                active_field: 0,
+            }),
+            &mut state,
+        );
+        assert_eq!(state.input.mode, InputMode::EditingParamForm);
+
+        apply_action(AppAction::AppendToParamForm("42".to_string()), &mut state);
+        apply_action(AppAction::ParamFormNextField, &mut state);
+        apply_action(AppAction::AppendToParamForm("100".to_string()), &mut state);
+        apply_action(AppAction::BackspaceParamForm, &mut state);
+        apply_action(AppAction::SubmitParamForm, &mut state);
+
+        assert_eq!(state.input.mode, InputMode::Normal);
+        let config = &state.request.configs["GET /users/{id}"];
+        assert_eq!(config.get_param_value("id"), Some("42"));
+        assert_eq!(config.get_param_value("limit"), Some("10"));
+        assert_eq!(config.path_params().count(), 1);
+    }
+
+    #[test]
+    fn test_param_suggestions_from_history() {
+        let mut state = create_test_state();
+
+        for value in ["42", "7", "420"] {
+            apply_action(
+                AppAction::StartEditingParameter {
+                    param_name: "id".to_string(),
+                    endpoint_key: "GET /users/{id}".to_string(),
+                },
+                &mut state,
+            );
+            apply_action(AppAction::ClearParamBuffer, &mut state);
+            apply_action(
+                AppAction::AppendToParamBuffer(value.to_string()),
+                &mut state,
+            );
+            apply_action(
+                AppAction::ConfirmParameterEdit {
+                    endpoint_key: "GET /users/{id}".to_string(),
+                },
+                &mut state,
+            );
+        }
+        assert_eq!(state.request.param_history["id"], vec!["420", "7", "42"]);
+        assert!(state.request.param_history_dirty);
+
+        apply_action(
+            AppAction::StartEditingParameter {
+                param_name: "id".to_string(),
+                endpoint_key: "GET /users/{id}".to_string(),
+            },
+            &mut state,
+        );
+        apply_action(AppAction::ClearParamBuffer, &mut state);
+        apply_action(AppAction::AppendToParamBuffer("4".to_string()), &mut state);
+        assert_eq!(state.request.param_suggestions(), vec!["420", "42"]);
+
+        apply_action(AppAction::NextParamSuggestion, &mut state);
+        apply_action(AppAction::AcceptParamSuggestion, &mut state);
+        assert_eq!(state.request.param_edit_buffer, "42");
+    }
+
+    #[test]
+    fn test_reset_request_config() {
+        let mut state = create_test_state();
+        state.get_or_create_request_config_by_key("GET /users").body =
+            Some("{\"stale\": true}".to_string());
+        state.get_or_create_request_config_by_key("GET /posts").body = Some("{}".to_string());
+
+        apply_action(
+            AppAction::EnterConfirmResetMode("GET /users".to_string()),
+            &mut state,
+        );
+        assert_eq!(
+            state.input.mode,
+            InputMode::ConfirmResetRequest("GET /users".to_string())
+        );
+
+        apply_action(
+            AppAction::ResetRequestConfig {
+                endpoint_key: "GET /users".to_string(),
+            },
+            &mut state,
+        );
+        apply_action(AppAction::ExitConfirmResetMode, &mut state);
+
+        assert_eq!(state.input.mode, InputMode::Normal);
+        assert!(!state.request.configs.contains_key("GET /users"));
+        assert!(state.request.configs.contains_key("GET /posts"));
+    }
+
+    #[test]
+    fn test_undo_redo_config_edits() {
+        let mut state = create_test_state();
+        state.get_or_create_request_config_by_key("GET /users").body = Some("{}".to_string());
+
+        apply_action(
+            AppAction::ResetRequestConfig {
+                endpoint_key: "GET /users".to_string(),
+            },
+            &mut state,
+        );
+        // Resetting again changes nothing, so there is nothing more to undo
+        apply_action(
+            AppAction::ResetRequestConfig {
+                endpoint_key: "GET /users".to_string(),
+            },
+            &mut state,
+        );
+        assert_eq!(state.request.undo_stack.len(), 1);
+
+        apply_action(AppAction::Undo, &mut state);
+        assert_eq!(
+            state.request.configs["GET /users"].body.as_deref(),
+            Some("{}")
+        );
+        assert!(state
+            .ui
+            .toast
+            .as_ref()
+            .unwrap()
+            .0
+            .starts_with("Undid reset request"));
+
+        apply_action(AppAction::Redo, &mut state);
+        assert!(!state.request.configs.contains_key("GET /users"));
+
+        apply_action(AppAction::Undo, &mut state);
+        apply_action(AppAction::Undo, &mut state);
+        assert_eq!(state.ui.toast.as_ref().unwrap().0, "Nothing to undo");
+        assert_eq!(state.request.redo_stack.len(), 1);
+    }
+
+    #[test]
+    fn test_param_form_cancel_discards_values() {
+        let mut state = create_test_state();
+        apply_action(
+            AppAction::OpenParamForm(ParamForm {
+                endpoint_key: "GET /users".to_string(),
+                ..ParamForm::default()
+            }),
+            &mut state,
+        );
+        apply_action(AppAction::CancelParamForm, &mut state);
+
+        assert_eq!(state.input.mode, InputMode::Normal);
+        assert!(state.request.configs.is_empty());
+    }
+
     #[test]
     fn test_navigate_param_up_down() {
         let mut state = create_test_state();
@@ -683,6 +2007,48 @@ mod tests {
         assert_eq!(state.ui.selected_param_index, 0);
     }
 
+    #[test]
+    fn test_cycle_content_type() {
+        let mut state = create_test_state();
+        state.data.endpoints = vec![ApiEndpoint {
+            method: "POST".to_string(),
+            path: "/users".to_string(),
+            request_body: Some(RequestBodySpec {
+                content: HashMap::from([
+                    ("application/json".to_string(), MediaTypeSpec::default()),
+                    ("application/xml".to_string(), MediaTypeSpec::default()),
+                ]),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }];
+        let cycle = |state: &mut AppState| {
+            apply_action(
+                AppAction::CycleContentType {
+                    endpoint_key: "POST /users".to_string(),
+                },
+                state,
+            );
+            state.request.configs["POST /users"].content_type.clone()
+        };
+
+        assert_eq!(cycle(&mut state).as_deref(), Some("application/xml"));
+        assert_eq!(cycle(&mut state).as_deref(), Some("application/json"));
+    }
+
+    #[test]
+    fn test_error_banner_dismissed() {
+        let mut state = create_test_state();
+        state.show_error("Copy failed: no clipboard".to_string());
+        assert_eq!(
+            state.ui.error_banner.as_deref(),
+            Some("Copy failed: no clipboard")
+        );
+
+        apply_action(AppAction::DismissError, &mut state);
+        assert_eq!(state.ui.error_banner, None);
+    }
+
     #[test]
     fn test_search_actions() {
         let mut state = create_test_state();
@@ -705,4 +2071,35 @@ mod tests {
         apply_action(AppAction::ExitSearchMode, &mut state);
         assert_eq!(state.input.mode, InputMode::Normal);
     }
+
+    #[test]
+    fn test_console_evaluates_against_state() {
+        let mut state = create_test_state();
+        state.data.endpoints = vec![ApiEndpoint {
+            method: "GET".to_string(),
+            path: "/users".to_string(),
+            ..Default::default()
+        }];
+        let mut response = ApiResponse::error(String::new());
+        response.body = r#"{"id": 42}"#.into();
+        state.request.current_response = Some(response);
+
+        apply_action(AppAction::OpenConsole, &mut state);
+        for input in ["len(endpoints)", "user = response.body.id", "user", "nope"] {
+            apply_action(AppAction::AppendToConsole(input.to_string()), &mut state);
+            apply_action(AppAction::SubmitConsole, &mut state);
+        }
+
+        let outputs: Vec<&Result<String, String>> = state
+            .input
+            .console_log
+            .iter()
+            .map(|entry| &entry.output)
+            .collect();
+        assert_eq!(outputs[0], &Ok("1".to_string()));
+        assert_eq!(outputs[1], &Ok("user = 42".to_string()));
+        assert_eq!(outputs[2], &Ok("42".to_string()));
+        assert!(outputs[3].is_err());
+        assert!(state.input.console_input.is_empty());
+    }
 }
@@ -1,16 +1,81 @@
-use crate::swagger;
-use crate::types::InputMode;
+use crate::grouping::GroupingRules;
+use crate::metrics::Metrics;
+use crate::notify::{self, FinishedRequest};
+use crate::plugins::PluginRegistry;
+use crate::recording::{Recorder, Replay, SessionMode};
+use crate::snapshot::ResponseSnapshot;
+use crate::spec_diff::EndpointSignature;
+use crate::stats::EndpointStats;
+use crate::store;
+use crate::swagger::{self, ServiceSource};
+use crate::types::{
+    InputMode, LoadingState, PanelFocus, Reconnect, RequestVariant, WorkspaceCommand,
+    STACKED_ENDPOINTS_PERCENT,
+};
 use crate::ui;
 use crate::ui::draw;
-use crate::{config::Config, state::AppState};
+use crate::viewer::{self, ExternalViewer};
+use crate::{
+    config::{self, Config},
+    state::{AppState, LockRecover},
+};
 use color_eyre::Result;
+use crossterm::event::{Event, EventStream, KeyEvent};
+use futures_util::{FutureExt, StreamExt};
 use ratatui::{
     layout::{Constraint, Direction, Layout},
     widgets::ListState,
     DefaultTerminal, Frame,
 };
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
 use std::sync::{Arc, RwLock};
-use std::time::Instant;
+use std::time::{Duration, Instant};
+use tokio::sync::Semaphore;
+use tokio::time::MissedTickBehavior;
+
+/// File holding saved request variants
+pub const VARIANTS_FILE: &str = "variants.toml";
+
+/// File holding endpoint notes
+const NOTES_FILE: &str = "notes.toml";
+
+/// Endpoint notes per spec URL, then per endpoint key
+type NoteStore = HashMap<String, HashMap<String, String>>;
+
+/// File holding body edits not saved when the app last exited
+const BODY_DRAFTS_FILE: &str = "body_drafts.toml";
+
+/// Body drafts per spec URL, then per endpoint key
+type DraftStore = HashMap<String, HashMap<String, String>>;
+
+/// File holding saved response snapshots
+const SNAPSHOTS_FILE: &str = "snapshots.toml";
+
+/// Response snapshots per spec URL, then per endpoint key
+type SnapshotStore = HashMap<String, HashMap<String, Vec<ResponseSnapshot>>>;
+
+/// File holding local usage stats
+const STATS_FILE: &str = "stats.toml";
+
+/// Usage stats per spec URL, then per endpoint key
+type StatsStore = HashMap<String, HashMap<String, EndpointStats>>;
+
+/// File holding the endpoint signatures of each spec's last load
+const SPEC_SIGNATURES_FILE: &str = "spec_signatures.toml";
+
+/// Endpoint signatures per spec URL
+type SignatureStore = HashMap<String, Vec<EndpointSignature>>;
+
+/// Least time between two writes of the body drafts, which change with every key typed
+const BODY_DRAFTS_SAVE_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Time between two replayed steps, so a replay can be followed on screen
+const REPLAY_STEP_INTERVAL: Duration = Duration::from_millis(150);
+
+/// Saved request variants per spec URL, then per endpoint key
+pub type VariantStore = HashMap<String, HashMap<String, Vec<RequestVariant>>>;
 
 #[derive(Debug)]
 pub struct App {
@@ -19,9 +84,38 @@ pub struct App {
     swagger_url: Option<String>,
     base_url: Option<String>,
     spinner_index: usize,
-    last_tick: Instant,
     event_handler: ui::EventHandler,
     config: Config,
+    variants: VariantStore,
+    notes: NoteStore,
+    body_drafts: DraftStore,
+    snapshots: SnapshotStore,
+    stats: StatsStore,
+    spec_signatures: SignatureStore,
+    /// Data files that failed to load, with the error; left as they are instead of overwritten
+    unreadable_files: HashMap<&'static str, String>,
+    /// Body drafts changed since they were last written, and when that was
+    body_drafts_unsaved: bool,
+    body_drafts_saved_at: Instant,
+    /// Session file being recorded to (`--record`)
+    recorder: Option<Recorder>,
+    /// Session being played back (`--replay`) and when its last step was applied
+    replay: Option<(Replay, Instant)>,
+    /// Open workspace tabs in order; the active one is `None` and lives in the fields above
+    workspaces: Vec<Option<Workspace>>,
+    active_workspace: usize,
+    /// Render and input timings for the performance overlay
+    metrics: Metrics,
+}
+
+/// A spec open in its own tab, with its own state, auth and history
+#[derive(Debug)]
+struct Workspace {
+    state: Arc<RwLock<AppState>>,
+    list_state: ListState,
+    swagger_url: Option<String>,
+    base_url: Option<String>,
+    selected_index: usize,
 }
 
 impl Default for App {
@@ -31,93 +125,321 @@ impl Default for App {
 
         // Load config
         let config = Config::load().unwrap();
+        draw::set_appearance(&config.ui);
         let swagger_url = config.server.swagger_url.clone();
         let base_url = config.server.base_url.clone();
 
-        // Determine initial input mode
-        let initial_input_mode = if swagger_url.is_none() {
-            InputMode::EnteringUrl // Show URL modal if no config
-        } else {
-            InputMode::Normal
-        };
-
-        let mut state = AppState::default();
-        state.input.mode = initial_input_mode;
-
-        Self {
-            state: Arc::new(RwLock::new(state)),
+        let mut unreadable_files = HashMap::new();
+        let mut app = Self {
+            state: Arc::new(RwLock::new(AppState::default())),
             list_state,
             swagger_url,
             base_url,
             spinner_index: 0,
-            last_tick: Instant::now(),
             event_handler: ui::EventHandler::new(),
+            variants: load_store(VARIANTS_FILE, &mut unreadable_files),
+            notes: load_store(NOTES_FILE, &mut unreadable_files),
+            body_drafts: load_store(BODY_DRAFTS_FILE, &mut unreadable_files),
+            snapshots: load_store(SNAPSHOTS_FILE, &mut unreadable_files),
+            stats: load_store(STATS_FILE, &mut unreadable_files),
+            spec_signatures: load_store(SPEC_SIGNATURES_FILE, &mut unreadable_files),
+            unreadable_files,
+            body_drafts_unsaved: false,
+            body_drafts_saved_at: Instant::now(),
             config,
-        }
+            recorder: None,
+            replay: None,
+            workspaces: vec![None],
+            active_workspace: 0,
+            metrics: Metrics::default(),
+        };
+        let state = app.new_workspace_state(app.swagger_url.clone().as_deref());
+        app.state = Arc::new(RwLock::new(state));
+        app
     }
 }
 
 impl App {
-    pub async fn run(mut self, mut terminal: DefaultTerminal) -> Result<()> {
+    /// Open a spec or send requests to a base URL other than the configured ones
+    ///
+    /// Unlike URLs entered in the app, these are not saved to the config.
+    pub fn with_urls(mut self, swagger_url: Option<String>, base_url: Option<String>) -> Self {
+        if swagger_url.is_some() {
+            self.swagger_url = swagger_url.as_deref().map(config::normalize_spec_url);
+            let state = self.new_workspace_state(self.swagger_url.as_deref());
+            self.state = Arc::new(RwLock::new(state));
+        }
+        if base_url.is_some() {
+            self.base_url = base_url;
+        }
+        self
+    }
+
+    /// Record the session to a file or replay a recorded one
+    pub fn with_session(mut self, session: Option<SessionMode>) -> Result<Self> {
+        match session {
+            Some(SessionMode::Record(path)) => {
+                self.recorder = Some(Recorder::create(&path)?);
+                self.state.write_lock().ui.recording = Some(Vec::new());
+            }
+            Some(SessionMode::Replay(path)) => {
+                self.replay = Some((Replay::load(&path)?, Instant::now()));
+            }
+            None => {}
+        }
+        Ok(self)
+    }
+
+    /// Fresh state for a workspace, with the data saved for its spec
+    ///
+    /// Without a spec URL the workspace starts in the URL modal.
+    fn new_workspace_state(&self, swagger_url: Option<&str>) -> AppState {
+        let mut state = AppState::default();
+        state.input.mode = if swagger_url.is_none() {
+            InputMode::EnteringUrl
+        } else {
+            InputMode::Normal
+        };
+        state.input.url_history = self.config.history.swagger_urls.clone();
+        state.input.base_url_history = self.config.history.base_urls.clone();
+        state.input.spec_list = self.config.specs.clone();
+        state.request.param_history = self.config.history.param_values.clone();
+        state
+            .request
+            .auth
+            .set_refresh(self.config.auth.refresh.clone());
+        state.request.plugins = PluginRegistry::load(&self.config.plugins);
+        state.request.path_rewrites = self.config.path_rewrites.clone();
+        state.request.templates = self.config.templates.clone();
+        state.data.watching = self.config.watch.enabled;
+        state.data.watch_interval = self.config.watch.interval();
+        state.ui.three_pane = self.config.layout.three_pane;
+        state.ui.arrangement = self.config.layout.arrangement;
+        state.ui.osc52 = self.config.clipboard.osc52;
+        let max_concurrent = self.config.requests.max_concurrent.max(1);
+        state.request.slots = Arc::new(Semaphore::new(max_concurrent));
+        let (grouping, grouping_error) = GroupingRules::load(&self.config.grouping);
+        state.data.grouping = grouping;
+        if let Some(error) = grouping_error {
+            state.show_error(error);
+        }
+
+        if let Some(url) = swagger_url {
+            state.request.variants = self.variants.get(url).cloned().unwrap_or_default();
+            state.data.notes = self.notes.get(url).cloned().unwrap_or_default();
+            state.request.snapshots = self.snapshots.get(url).cloned().unwrap_or_default();
+            state.request.usage_stats = self.stats.get(url).cloned().unwrap_or_default();
+            state.data.spec_signatures = self.spec_signatures.get(url).cloned();
+            state.request.body_drafts = self.body_drafts.get(url).cloned().unwrap_or_default();
+            state.ui.toast = body_drafts_notice(&state.request.body_drafts);
+        }
+        state
+    }
+
+    /// Run until quit; returns the summary of the replay, if one ran
+    pub async fn run(mut self, mut terminal: DefaultTerminal) -> Result<Option<String>> {
+        let size = terminal.size()?;
+        self.state.write_lock().resize(size.width, size.height);
+        for (file_name, error) in &self.unreadable_files {
+            self.state.write_lock().show_error(format!(
+                "Could not read {file_name}, leaving it unchanged this session: {error}"
+            ));
+        }
+
         // Only fetch if we have a URL
         if self.swagger_url.is_some() {
             self.fetch_endpoints_background();
         }
 
-        // Main UI loop
+        // Main UI loop: redraw after every input event and every tick
+        let mut events = EventStream::new();
+        // Events read off the stream while batching typed characters, handled next
+        let mut pending: VecDeque<Event> = VecDeque::new();
+        let mut ticks = tokio::time::interval(self.config.ui.tick_rate());
+        ticks.set_missed_tick_behavior(MissedTickBehavior::Skip);
+        let mut input_received: Option<Instant> = None;
         while !self.event_handler.should_quit {
-            // Update spinner animation
-            if self.last_tick.elapsed().as_millis() > 100 {
-                self.spinner_index = (self.spinner_index + 1) % 4;
-                self.last_tick = Instant::now();
+            self.restore_pending_selection();
+            self.show_selected_response();
+
+            let render_start = Instant::now();
+            terminal.draw(|frame| {
+                self.draw(frame);
+                if draw::screen_reader() {
+                    draw::strip_decorations(frame.buffer_mut());
+                }
+            })?;
+            self.metrics.render = render_start.elapsed();
+            if let Some(received) = input_received.take() {
+                self.metrics.event = received.elapsed();
             }
+            self.metrics.tick();
 
-            terminal.draw(|frame| self.draw(frame))?;
+            let event = match pending.pop_front() {
+                Some(event) => Some(event),
+                None => tokio::select! {
+                    _ = ticks.tick() => {
+                        self.spinner_index = (self.spinner_index + 1) % 4;
+                        None
+                    }
+                    event = events.next() => match event {
+                        Some(event) => Some(event?),
+                        None => break,
+                    },
+                },
+            };
+            if let Some(Event::Key(key)) = &event {
+                if ui::is_paste_char(key) {
+                    ui::offer_paste_batch(paste_batch(&mut events, &mut pending)?);
+                }
+            }
 
-            let state = Arc::clone(&self.state);
-            let (should_fetch, url_submitted) = self.event_handler.handle_events(
-                state,
-                &mut self.list_state,
-                self.base_url.clone(),
-                self.swagger_url.clone(),
-            )?;
+            if event.is_some() {
+                input_received = Some(Instant::now());
+            }
+            let (should_fetch, url_submitted) = match event {
+                Some(event) => self.event_handler.handle_event(
+                    event,
+                    Arc::clone(&self.state),
+                    &mut self.list_state,
+                    self.base_url.clone(),
+                    self.swagger_url.clone(),
+                )?,
+                None => (false, None),
+            };
+            // Typed characters the handled key didn't take as a paste are keys of their own
+            for key in ui::take_unclaimed_batch().into_iter().rev() {
+                pending.push_front(Event::Key(key));
+            }
 
             // If URL was submitted, save it and start fetching
             if let Some(submission) = url_submitted {
+                self.switch_spec_selection(&submission.swagger_url);
                 self.swagger_url = Some(submission.swagger_url.clone());
                 self.base_url = submission.base_url.clone();
-                self.config
-                    .set_swagger_url(submission.swagger_url, submission.base_url)?;
+                if let Err(e) = self
+                    .config
+                    .set_swagger_url(submission.swagger_url, submission.base_url)
+                {
+                    self.state
+                        .write_lock()
+                        .show_error(format!("Could not save the config: {e}"));
+                }
+                {
+                    let mut s = self.state.write_lock();
+                    s.input.url_history = self.config.history.swagger_urls.clone();
+                    s.input.base_url_history = self.config.history.base_urls.clone();
+                }
                 self.fetch_endpoints_background();
             } else if should_fetch {
                 self.fetch_endpoints_background();
             }
+
+            if let Some(base_url) = self.take_server_base_url() {
+                self.base_url = Some(base_url.clone());
+                if let Err(e) = self.config.set_base_url(base_url) {
+                    self.state
+                        .write_lock()
+                        .show_error(format!("Could not save the config: {e}"));
+                }
+                self.state.write_lock().input.base_url_history =
+                    self.config.history.base_urls.clone();
+            }
+
+            self.notify_finished_requests();
+
+            if let Some(command) = self.take_workspace_command() {
+                self.change_workspace(command);
+            }
+
+            self.reconnect_if_due();
+            self.reload_if_watched();
+            self.replay_step();
+            self.persist();
+
+            if let Some(viewer) = self.take_external_view_request() {
+                terminal = self.open_response_externally(terminal, viewer)?;
+                // The viewer had the terminal: start over with a fresh input stream
+                events = EventStream::new();
+            }
         }
 
-        Ok(())
+        // Parked workspaces may have finished requests since they were last active
+        for index in 0..self.workspaces.len() {
+            self.switch_workspace(index);
+            self.persist();
+        }
+        if let Err(e) = self.write_body_drafts() {
+            self.state
+                .write_lock()
+                .show_error(format!("Could not save the body drafts: {e}"));
+        }
+
+        Ok(self.replay.map(|(replay, _)| replay.summary()))
     }
 
     fn draw(&mut self, frame: &mut Frame) {
         // Check if we need to initialize selection (do this before acquiring lock)
         let should_select = self.list_state.selected().is_none();
+        let workspace_labels = self.workspace_labels();
 
         // Single read lock for the entire draw - no more lock dance!
-        let state = self.state.read().unwrap();
+        let state = self.state.read_lock();
+
+        let screen = state.screen_layout();
+        if screen.too_small {
+            ui::render_too_small(frame);
+            return;
+        }
+
+        // In screen reader mode, the last line announces errors and messages instead of overlays
+        let screen_reader = draw::screen_reader();
+        let [app_area, status_area] = Layout::vertical([
+            Constraint::Min(0),
+            Constraint::Length(u16::from(screen_reader)),
+        ])
+        .areas(frame.area());
 
         // Create main layout: Header, Search Bar, Body, Footer
         let main_chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
-                Constraint::Length(3), // Header
-                Constraint::Length(3), // Search bar
-                Constraint::Min(0),    // Body
-                Constraint::Length(3), // Footer
+                Constraint::Length(3),                      // Header
+                Constraint::Length(3),                      // Search bar
+                Constraint::Min(0),                         // Body
+                Constraint::Length(screen.footer_height()), // Footer
             ])
-            .split(frame.area());
+            .split(app_area);
 
+        // Too narrow and short for both panels: the focused one gets the whole body
+        let both_panels = screen.side_by_side || screen.stacked;
+        let show_endpoints = both_panels || state.ui.panel_focus == PanelFocus::EndpointsList;
+        let show_details = both_panels || state.ui.panel_focus == PanelFocus::Details;
+        let body_constraints: &[Constraint] = if screen.stacked {
+            &[
+                Constraint::Percentage(STACKED_ENDPOINTS_PERCENT),
+                Constraint::Percentage(100 - STACKED_ENDPOINTS_PERCENT),
+            ]
+        } else if screen.three_pane {
+            &[
+                Constraint::Percentage(30),
+                Constraint::Percentage(35),
+                Constraint::Percentage(35),
+            ]
+        } else if screen.side_by_side {
+            &[Constraint::Percentage(40), Constraint::Percentage(60)]
+        } else if show_endpoints {
+            &[Constraint::Percentage(100), Constraint::Length(0)]
+        } else {
+            &[Constraint::Length(0), Constraint::Percentage(100)]
+        };
         let body_chunks = Layout::default()
-            .direction(Direction::Horizontal)
-            .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+            .direction(if screen.stacked {
+                Direction::Vertical
+            } else {
+                Direction::Horizontal
+            })
+            .constraints(body_constraints)
             .split(main_chunks[2]);
 
         let display_url = self.swagger_url.as_deref().unwrap_or("No URL configured");
@@ -127,10 +449,15 @@ impl App {
             frame,
             main_chunks[0],
             display_url,
-            &state.data.loading_state,
-            state.data.endpoints.len(),
+            &state.data,
             &state.request.auth,
         );
+        ui::render_workspace_tabs(
+            frame,
+            main_chunks[0],
+            &workspace_labels,
+            self.active_workspace,
+        );
 
         // Render search bar
         ui::render_search_bar(frame, main_chunks[1], &state);
@@ -148,25 +475,63 @@ impl App {
         }
 
         // Render left panel (endpoints list)
-        ui::render_endpoints_panel(
-            frame,
-            body_chunks[0],
-            &state,
-            self.spinner_index,
-            &mut self.list_state,
-        );
+        if show_endpoints {
+            ui::render_endpoints_panel(
+                frame,
+                body_chunks[0],
+                &state,
+                self.spinner_index,
+                &mut self.list_state,
+            );
+        }
 
-        // Render right panel (details)
-        ui::render_details_panel(
-            frame,
-            body_chunks[1],
-            &state,
-            self.event_handler.selected_index,
-        );
+        // Render right panel (details), and the response next to it in the three-pane layout
+        if show_details {
+            ui::render_details_panel(
+                frame,
+                body_chunks[1],
+                &state,
+                self.event_handler.selected_index,
+            );
+        }
+        if screen.three_pane {
+            ui::render_response_panel(
+                frame,
+                body_chunks[2],
+                &state,
+                self.event_handler.selected_index,
+            );
+        }
 
         // Render footer
         ui::render_footer(frame, main_chunks[3], &state.ui.view_mode, &state);
 
+        if state.ui.show_metrics {
+            ui::render_metrics_overlay(
+                frame,
+                main_chunks[2],
+                &self.metrics.lines(state.stored_response_bytes()),
+            );
+        }
+
+        if state.ui.show_log {
+            ui::render_log_panel(frame, main_chunks[2]);
+        }
+
+        if screen_reader {
+            ui::render_status_line(frame, status_area, &state.status_announcement());
+        } else {
+            if let Some(message) = &state.ui.error_banner {
+                ui::render_error_banner(frame, main_chunks[2], message);
+            }
+
+            if let Some((message, shown_at)) = &state.ui.toast {
+                if shown_at.elapsed() < notify::TOAST_DURATION {
+                    ui::render_toast(frame, message);
+                }
+            }
+        }
+
         // Render modals LAST - after everything else
         match state.input.mode {
             InputMode::EnteringUrl => {
@@ -178,17 +543,733 @@ impl App {
             InputMode::ConfirmClearToken => {
                 draw::render_clear_confirmation_modal(frame);
             }
+            InputMode::ExportingCsv => {
+                draw::render_csv_export_modal(frame, &state);
+            }
+            InputMode::SavingBody => {
+                draw::render_save_body_modal(frame, &state);
+            }
+            InputMode::ExportingScript => {
+                draw::render_script_export_modal(frame, &state);
+            }
+            InputMode::ExportingTraffic => {
+                draw::render_traffic_export_modal(frame, &state);
+            }
+            InputMode::ExportingDiagnostics => {
+                draw::render_diagnostics_export_modal(frame, &state);
+            }
+            InputMode::SelectingDiscoveredSpec => {
+                draw::render_url_input_modal(frame, &state);
+                draw::render_spec_discovery_modal(frame, &state);
+            }
+            InputMode::SelectingLink => {
+                draw::render_link_picker_modal(frame, &state);
+            }
+            InputMode::EditingNote => {
+                draw::render_note_modal(frame, &state);
+            }
+            InputMode::EnteringVariantName => {
+                draw::render_variant_name_modal(frame, &state);
+            }
+            InputMode::PastingParams(_) => {
+                draw::render_param_paste_modal(frame, &state);
+            }
+            InputMode::EnteringSnapshotName => {
+                draw::render_snapshot_name_modal(frame, &state);
+            }
+            InputMode::ConfirmResetRequest(ref endpoint_key) => {
+                draw::render_reset_confirmation_modal(frame, endpoint_key);
+            }
             InputMode::EnteringBody => {
                 draw::render_body_input_modal(frame, &state);
             }
-            InputMode::Normal | InputMode::Searching => {}
+            InputMode::EditingParamForm => {
+                draw::render_param_form_modal(frame, &state);
+            }
+            InputMode::ConfiguringServer => {
+                draw::render_server_form_modal(frame, &state);
+            }
+            InputMode::Console => {
+                draw::render_console_modal(frame, &state);
+            }
+            InputMode::SelectingPluginCommand => {
+                draw::render_plugin_commands_modal(frame, &state);
+            }
+            InputMode::SwitchingSpec => {
+                draw::render_spec_switcher_modal(frame, &state);
+            }
+            InputMode::ViewingJobs => {
+                draw::render_jobs_modal(frame, &state);
+            }
+            InputMode::ViewingSpecInfo => {
+                draw::render_spec_info_modal(frame, &state.data.spec_info);
+            }
+            InputMode::ViewingStats => {
+                draw::render_stats_modal(frame, &state);
+            }
+            InputMode::ViewingSpecChanges => {
+                draw::render_spec_changes_modal(frame, &state);
+            }
+            InputMode::Normal | InputMode::Searching | InputMode::SearchingResponse => {}
         }
         // state read lock is automatically dropped here
     }
 
+    /// Refetch the spec when the server was unreachable and the next attempt is due
+    fn reconnect_if_due(&self) {
+        {
+            let state = self.state.read_lock();
+            let due = state.data.reconnect.as_ref().is_some_and(Reconnect::is_due);
+            if !due
+                || matches!(
+                    state.data.loading_state,
+                    LoadingState::Fetching | LoadingState::Parsing
+                )
+            {
+                return;
+            }
+        }
+        self.fetch_endpoints_background();
+    }
+
+    /// Apply the next step of the replay once the spec has loaded and the interval passed
+    fn replay_step(&mut self) {
+        let Some((replay, last_step)) = &mut self.replay else {
+            return;
+        };
+        if replay.is_done() || last_step.elapsed() < REPLAY_STEP_INTERVAL {
+            return;
+        }
+
+        let mut state = self.state.write_lock();
+        if matches!(
+            state.data.loading_state,
+            LoadingState::Fetching | LoadingState::Parsing
+        ) {
+            return;
+        }
+        replay.step(&mut state);
+        *last_step = Instant::now();
+    }
+
+    /// Append the steps recorded since the last call to the session file
+    fn save_recording(&mut self) -> Result<()> {
+        let Some(recorder) = &mut self.recorder else {
+            return Ok(());
+        };
+        let steps = {
+            let mut state = self.state.write_lock();
+            match &mut state.ui.recording {
+                Some(steps) if !steps.is_empty() => std::mem::take(steps),
+                _ => return Ok(()),
+            }
+        };
+        recorder.write(&steps)
+    }
+
+    /// Save the recording and the data that changed, showing failures instead of ending the session
+    fn persist(&mut self) {
+        if let Err(e) = self.save_recording() {
+            self.state
+                .write_lock()
+                .show_error(format!("Could not save the recording: {e}"));
+        }
+        if let Err(e) = self.save_data() {
+            self.state
+                .write_lock()
+                .show_error(format!("Could not save data: {e}"));
+        }
+    }
+
+    /// Persist the active workspace's data that changed
+    ///
+    /// Each kind of data is saved even when another fails; the first failure is returned.
+    fn save_data(&mut self) -> Result<()> {
+        let results = [
+            self.save_param_history(),
+            self.save_variants(),
+            self.save_notes(),
+            self.save_body_drafts(),
+            self.save_snapshots(),
+            self.save_stats(),
+            self.save_spec_signatures(),
+        ];
+        results.into_iter().collect()
+    }
+
+    /// Persist parameter value history when it changed
+    fn save_param_history(&mut self) -> Result<()> {
+        let param_values = {
+            let mut state = self.state.write_lock();
+            if !state.request.param_history_dirty {
+                return Ok(());
+            }
+            state.request.param_history_dirty = false;
+            state.request.param_history.clone()
+        };
+        self.config.set_param_history(param_values)
+    }
+
+    /// Persist request variants of the current spec when they changed
+    fn save_variants(&mut self) -> Result<()> {
+        let Some(url) = self.swagger_url.clone() else {
+            return Ok(());
+        };
+        {
+            let mut state = self.state.write_lock();
+            if !state.request.variants_dirty {
+                return Ok(());
+            }
+            state.request.variants_dirty = false;
+            self.variants.insert(url, state.request.variants.clone());
+        }
+        self.save_store(VARIANTS_FILE, &self.variants)
+    }
+
+    /// Base URL submitted in the server form, if any
+    fn take_server_base_url(&self) -> Option<String> {
+        self.state.write_lock().input.server_base_url.take()
+    }
+
+    /// Show the response of the selected endpoint, whichever way it was selected
+    fn show_selected_response(&self) {
+        let mut state = self.state.write_lock();
+        let selected_key = state
+            .get_selected_endpoint(self.event_handler.selected_index)
+            .map(|endpoint| endpoint.key());
+        state.show_response_for(selected_key);
+    }
+
+    /// Tell the user about slow requests that finished out of sight, in any workspace
+    fn notify_finished_requests(&self) {
+        let mut state = self.state.write_lock();
+        let selected_key = state
+            .get_selected_endpoint(self.event_handler.selected_index)
+            .map(|endpoint| endpoint.key());
+
+        // Requests of parked workspaces finished out of sight by definition
+        let mut finished: Vec<(FinishedRequest, Option<String>)> = self
+            .workspaces
+            .iter()
+            .flatten()
+            .flat_map(|workspace| {
+                let requests =
+                    std::mem::take(&mut workspace.state.write_lock().request.finished_requests);
+                requests.into_iter().map(|request| (request, None))
+            })
+            .collect();
+        for request in std::mem::take(&mut state.request.finished_requests) {
+            finished.push((request, selected_key.clone()));
+        }
+
+        let config = &self.config.notifications;
+        for (request, selected_key) in finished {
+            let focused = state.ui.terminal_focused;
+            if request.needs_notification(config, selected_key.as_deref(), focused) {
+                notify::notify(config, &request);
+                state.ui.toast = Some((request.summary(), Instant::now()));
+            }
+        }
+    }
+
+    /// Change to the workspace tabs requested by a key, if any
+    fn take_workspace_command(&self) -> Option<WorkspaceCommand> {
+        self.state.write_lock().ui.workspace_command.take()
+    }
+
+    /// External viewer requested for the current response, if any
+    fn take_external_view_request(&self) -> Option<ExternalViewer> {
+        self.state.write_lock().ui.open_externally.take()
+    }
+
+    /// Dump the current response to a temp file and open it in the viewer
+    fn open_response_externally(
+        &self,
+        terminal: DefaultTerminal,
+        viewer: ExternalViewer,
+    ) -> Result<DefaultTerminal> {
+        let path = {
+            let state = self.state.read_lock();
+            match &state.request.current_response {
+                Some(response) => {
+                    viewer::write_response_file(response, &state.request.response_lines)?
+                }
+                None => return Ok(terminal),
+            }
+        };
+
+        match viewer {
+            ExternalViewer::Pager => viewer::open_in_pager(terminal, &path),
+            ExternalViewer::Browser => {
+                // No opener available is not worth ending the session over
+                let _ = viewer::open_in_browser(&path);
+                Ok(terminal)
+            }
+        }
+    }
+
+    /// Persist endpoint notes of the current spec when they changed
+    fn save_notes(&mut self) -> Result<()> {
+        let Some(url) = self.swagger_url.clone() else {
+            return Ok(());
+        };
+        {
+            let mut state = self.state.write_lock();
+            if !state.data.notes_dirty {
+                return Ok(());
+            }
+            state.data.notes_dirty = false;
+            self.notes.insert(url, state.data.notes.clone());
+        }
+        self.save_store(NOTES_FILE, &self.notes)
+    }
+
+    /// Persist the body being edited as a draft when it changes, so it survives a crash
+    ///
+    /// Writes are spaced by [`BODY_DRAFTS_SAVE_INTERVAL`] rather than made on every key.
+    fn save_body_drafts(&mut self) -> Result<()> {
+        let Some(url) = self.swagger_url.clone() else {
+            return Ok(());
+        };
+
+        {
+            let mut state = self.state.write_lock();
+            state.track_body_draft();
+            if !state.request.body_drafts_dirty {
+                return Ok(());
+            }
+            state.request.body_drafts_dirty = false;
+            let drafts = state.request.body_drafts.clone();
+            if drafts.is_empty() {
+                self.body_drafts.remove(&url);
+            } else {
+                self.body_drafts.insert(url, drafts);
+            }
+            self.body_drafts_unsaved = true;
+        }
+        if self.body_drafts_saved_at.elapsed() < BODY_DRAFTS_SAVE_INTERVAL {
+            return Ok(());
+        }
+        self.write_body_drafts()
+    }
+
+    /// Write the body drafts if they changed since they were last written
+    fn write_body_drafts(&mut self) -> Result<()> {
+        if !self.body_drafts_unsaved {
+            return Ok(());
+        }
+        self.body_drafts_unsaved = false;
+        self.body_drafts_saved_at = Instant::now();
+        self.save_store(BODY_DRAFTS_FILE, &self.body_drafts)
+    }
+
+    /// Write a data file, unless it failed to load: what it holds would be lost
+    fn save_store<T: Serialize>(&self, file_name: &'static str, data: &T) -> Result<()> {
+        if self.unreadable_files.contains_key(file_name) {
+            return Ok(());
+        }
+        store::save(file_name, data)
+    }
+
+    /// Persist response snapshots of the current spec when they changed
+    fn save_snapshots(&mut self) -> Result<()> {
+        let Some(url) = self.swagger_url.clone() else {
+            return Ok(());
+        };
+        {
+            let mut state = self.state.write_lock();
+            if !state.request.snapshots_dirty {
+                return Ok(());
+            }
+            state.request.snapshots_dirty = false;
+            self.snapshots.insert(url, state.request.snapshots.clone());
+        }
+        self.save_store(SNAPSHOTS_FILE, &self.snapshots)
+    }
+
+    /// Persist usage stats of the current spec when they changed
+    fn save_stats(&mut self) -> Result<()> {
+        let Some(url) = self.swagger_url.clone() else {
+            return Ok(());
+        };
+        {
+            let mut state = self.state.write_lock();
+            if !state.request.usage_stats_dirty {
+                return Ok(());
+            }
+            state.request.usage_stats_dirty = false;
+            self.stats.insert(url, state.request.usage_stats.clone());
+        }
+        self.save_store(STATS_FILE, &self.stats)
+    }
+
+    /// Persist the endpoint signatures of the current spec when it loaded with changes
+    fn save_spec_signatures(&mut self) -> Result<()> {
+        let Some(url) = self.swagger_url.clone() else {
+            return Ok(());
+        };
+        {
+            let mut state = self.state.write_lock();
+            if !state.data.spec_signatures_dirty {
+                return Ok(());
+            }
+            state.data.spec_signatures_dirty = false;
+            let Some(signatures) = state.data.spec_signatures.clone() else {
+                return Ok(());
+            };
+            self.spec_signatures.insert(url, signatures);
+        }
+        self.save_store(SPEC_SIGNATURES_FILE, &self.spec_signatures)
+    }
+
+    fn change_workspace(&mut self, command: WorkspaceCommand) {
+        let count = self.workspaces.len();
+        match command {
+            WorkspaceCommand::Open => {
+                let fresh = Workspace {
+                    state: Arc::new(RwLock::new(self.new_workspace_state(None))),
+                    list_state: ListState::default(),
+                    swagger_url: None,
+                    base_url: None,
+                    selected_index: 0,
+                };
+                let previous = self.swap_workspace(fresh);
+                self.workspaces[self.active_workspace] = Some(previous);
+                self.active_workspace += 1;
+                self.workspaces.insert(self.active_workspace, None);
+            }
+            WorkspaceCommand::Close if count > 1 => {
+                // Keep what changed in the closing workspace and stop its requests
+                self.persist();
+                let error = {
+                    let mut state = self.state.write_lock();
+                    state.request.cancel_all_jobs();
+                    state.ui.error_banner.take()
+                };
+                self.workspaces.remove(self.active_workspace);
+                self.active_workspace = self.active_workspace.min(count - 2);
+                if let Some(next) = self.workspaces[self.active_workspace].take() {
+                    self.swap_workspace(next);
+                }
+                // A failed save would otherwise be reported to the closed workspace
+                if let Some(error) = error {
+                    self.state.write_lock().show_error(error);
+                }
+            }
+            WorkspaceCommand::Close => {}
+            WorkspaceCommand::Next => self.switch_workspace((self.active_workspace + 1) % count),
+            WorkspaceCommand::Previous => {
+                self.switch_workspace((self.active_workspace + count - 1) % count)
+            }
+            WorkspaceCommand::OpenSpec(index) => self.open_listed_spec(index),
+        }
+    }
+
+    /// Show a spec of the config's `specs`: its tab when one has it open, else a new tab
+    /// (or the current one, while it has no spec) loading it with its base URL and token
+    fn open_listed_spec(&mut self, index: usize) {
+        let Some(spec) = self.config.specs.get(index).cloned() else {
+            return;
+        };
+        let url = config::normalize_spec_url(&spec.swagger_url);
+        if self.swagger_url.as_deref() == Some(url.as_str()) {
+            return;
+        }
+        let open = self.workspaces.iter().position(|workspace| {
+            workspace
+                .as_ref()
+                .is_some_and(|workspace| workspace.swagger_url.as_deref() == Some(url.as_str()))
+        });
+        if let Some(open) = open {
+            self.switch_workspace(open);
+            return;
+        }
+
+        if self.swagger_url.is_some() {
+            self.change_workspace(WorkspaceCommand::Open);
+        }
+        let mut state = self.new_workspace_state(Some(&url));
+        let (width, height) = self.state.read_lock().ui.terminal_size;
+        state.resize(width, height);
+        if let Some(token) = spec.token_value() {
+            state.request.auth.set_token(token);
+        }
+        self.state = Arc::new(RwLock::new(state));
+        self.list_state = ListState::default();
+        self.event_handler.selected_index = 0;
+        self.swagger_url = Some(url);
+        self.base_url = spec.base_url;
+        self.fetch_endpoints_background();
+    }
+
+    /// Make the workspace at `index` the active one, parking the current one
+    fn switch_workspace(&mut self, index: usize) {
+        if index == self.active_workspace {
+            return;
+        }
+        let Some(next) = self.workspaces.get_mut(index).and_then(Option::take) else {
+            return;
+        };
+        let previous = self.swap_workspace(next);
+        self.workspaces[self.active_workspace] = Some(previous);
+        self.active_workspace = index;
+    }
+
+    /// Put `workspace` in the App fields, returning the one that was there
+    fn swap_workspace(&mut self, workspace: Workspace) -> Workspace {
+        // Resizes only reached the active workspace
+        let (width, height) = self.state.read_lock().ui.terminal_size;
+        workspace.state.write_lock().resize(width, height);
+
+        Workspace {
+            state: std::mem::replace(&mut self.state, workspace.state),
+            list_state: std::mem::replace(&mut self.list_state, workspace.list_state),
+            swagger_url: std::mem::replace(&mut self.swagger_url, workspace.swagger_url),
+            base_url: std::mem::replace(&mut self.base_url, workspace.base_url),
+            selected_index: std::mem::replace(
+                &mut self.event_handler.selected_index,
+                workspace.selected_index,
+            ),
+        }
+    }
+
+    /// Tab label per workspace: the spec title, else its URL
+    fn workspace_labels(&self) -> Vec<String> {
+        let label = |state: &Arc<RwLock<AppState>>, swagger_url: &Option<String>| {
+            let title = state.read_lock().data.spec_info.title.clone();
+            title
+                .or_else(|| swagger_url.clone())
+                .unwrap_or_else(|| "New".to_string())
+        };
+        self.workspaces
+            .iter()
+            .map(|workspace| match workspace {
+                Some(workspace) => label(&workspace.state, &workspace.swagger_url),
+                None => label(&self.state, &self.swagger_url),
+            })
+            .collect()
+    }
+
+    /// Remember the selection of the current spec and queue the one last used for `next_url`
+    fn switch_spec_selection(&mut self, next_url: &str) {
+        let mut state = self.state.write_lock();
+
+        if let Some(current_url) = &self.swagger_url {
+            if let Some(endpoint) = state.get_selected_endpoint(self.event_handler.selected_index) {
+                state
+                    .ui
+                    .last_selected_by_spec
+                    .insert(current_url.clone(), endpoint.key());
+            }
+        }
+
+        state.ui.pending_selection = state.ui.last_selected_by_spec.get(next_url).cloned();
+
+        // Variants belong to a spec; swap in the ones saved for the next URL
+        state.request.variants = self.variants.get(next_url).cloned().unwrap_or_default();
+        state.request.active_variant.clear();
+        state.data.notes = self.notes.get(next_url).cloned().unwrap_or_default();
+        state.request.snapshots = self.snapshots.get(next_url).cloned().unwrap_or_default();
+        state.request.usage_stats = self.stats.get(next_url).cloned().unwrap_or_default();
+        state.data.spec_signatures = self.spec_signatures.get(next_url).cloned();
+        state.data.spec_diff = None;
+        state.request.body_drafts = self.body_drafts.get(next_url).cloned().unwrap_or_default();
+        state.ui.toast = body_drafts_notice(&state.request.body_drafts);
+        state.ui.snapshot_diff = None;
+        drop(state);
+
+        self.event_handler.selected_index = 0;
+        self.list_state.select(None);
+    }
+
+    /// Select the queued endpoint once the endpoint list has finished loading
+    fn restore_pending_selection(&mut self) {
+        let mut state = self.state.write_lock();
+
+        if !matches!(state.data.loading_state, LoadingState::Complete) {
+            return;
+        }
+
+        if let Some(key) = state.ui.pending_selection.take() {
+            if let Some(index) = state.locate_endpoint(&key) {
+                self.event_handler.selected_index = index;
+                self.list_state.select(Some(index));
+            }
+        }
+    }
+
     fn fetch_endpoints_background(&self) {
-        if let Some(url) = &self.swagger_url {
-            swagger::fetch_endpoints_background(Arc::clone(&self.state), url.clone());
+        self.load_endpoints(false);
+    }
+
+    /// Reload the watched spec when its interval passed since the last request
+    fn reload_if_watched(&self) {
+        {
+            let state = self.state.read_lock();
+            let due = state.data.watching
+                && !state.data.reloading
+                && matches!(state.data.loading_state, LoadingState::Complete)
+                && state
+                    .data
+                    .last_fetch
+                    .is_none_or(|at| at.elapsed() >= state.data.watch_interval);
+            if !due {
+                return;
+            }
+        }
+        self.load_endpoints(true);
+    }
+
+    /// Request the spec; a `quiet` reload keeps the endpoints on screen meanwhile
+    fn load_endpoints(&self, quiet: bool) {
+        let Some(url) = &self.swagger_url else {
+            return;
+        };
+        {
+            let mut state = self.state.write_lock();
+            state.data.last_fetch = Some(Instant::now());
+            state.data.spec_url = Some(url.clone());
+        }
+        if let Some(name) = config::aggregate_name(url) {
+            match self.aggregate_sources(url) {
+                Some(sources) => {
+                    swagger::fetch_aggregate_background(
+                        Arc::clone(&self.state),
+                        name.to_string(),
+                        sources,
+                        quiet,
+                    );
+                }
+                None => {
+                    self.state.write_lock().data.loading_state =
+                        LoadingState::Error(format!("No aggregate named {name} in config.toml"));
+                }
+            }
+        } else if quiet {
+            swagger::reload_endpoints_background(
+                Arc::clone(&self.state),
+                url.clone(),
+                self.config.spec_headers(url),
+            );
+        } else {
+            swagger::fetch_endpoints_background(
+                Arc::clone(&self.state),
+                url.clone(),
+                self.config.spec_headers(url),
+            );
+        }
+    }
+
+    /// The services of the aggregate a spec URL names, if configured
+    fn aggregate_sources(&self, url: &str) -> Option<Vec<ServiceSource>> {
+        let aggregate = self.config.aggregate(url)?;
+        Some(
+            aggregate
+                .services
+                .iter()
+                .map(|service| ServiceSource {
+                    name: service.name.clone(),
+                    url: service.swagger_url.clone(),
+                    base_url: service.base_url.clone(),
+                    headers: self.config.spec_headers(&service.swagger_url),
+                })
+                .collect(),
+        )
+    }
+}
+
+/// Character keys that follow the one just read, for paste support
+///
+/// Takes them from the pending events first, then from the stream as long as events are
+/// ready without waiting. The first other event ends the batch and stays pending.
+fn paste_batch(
+    events: &mut EventStream,
+    pending: &mut VecDeque<Event>,
+) -> std::io::Result<Vec<KeyEvent>> {
+    let mut batch = Vec::new();
+    loop {
+        let next = match pending.pop_front() {
+            Some(event) => event,
+            None => match events.next().now_or_never() {
+                Some(Some(event)) => event?,
+                _ => break,
+            },
+        };
+        match next {
+            Event::Key(key) if ui::is_paste_char(&key) => batch.push(key),
+            other => {
+                pending.push_front(other);
+                break;
+            }
         }
     }
+    Ok(batch)
+}
+
+/// A data file's contents, or the default when it failed to load, noting the error
+fn load_store<T: DeserializeOwned + Default>(
+    file_name: &'static str,
+    unreadable_files: &mut HashMap<&'static str, String>,
+) -> T {
+    store::load(file_name).unwrap_or_else(|e| {
+        unreadable_files.insert(file_name, e.to_string());
+        T::default()
+    })
+}
+
+/// Toast telling which endpoints have body drafts left from an earlier session
+fn body_drafts_notice(drafts: &HashMap<String, String>) -> Option<(String, Instant)> {
+    let mut keys: Vec<&str> = drafts.keys().map(String::as_str).collect();
+    keys.sort_unstable();
+    let first = keys.first()?;
+    let message = match keys.len() {
+        1 => format!("Unsaved body draft for {first}: b restores it"),
+        n => format!(
+            "Unsaved body drafts for {first} and {} more: b restores them",
+            n - 1
+        ),
+    };
+    Some((message, Instant::now()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::actions::{apply_action, AppAction};
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_failing_save_shows_error_and_keeps_running() {
+        // Writes to /dev/full fail like writes to a full disk
+        let mut app = App::default()
+            .with_session(Some(SessionMode::Record("/dev/full".into())))
+            .unwrap();
+        apply_action(AppAction::ToggleMetrics, &mut app.state.write_lock());
+
+        app.persist();
+
+        let state = app.state.read_lock();
+        let banner = state.ui.error_banner.as_deref().unwrap();
+        assert!(banner.starts_with("Could not save the recording"));
+        assert!(!app.event_handler.should_quit);
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_closing_a_workspace_saves_it_first() {
+        let mut app = App::default()
+            .with_session(Some(SessionMode::Record("/dev/full".into())))
+            .unwrap();
+        apply_action(AppAction::ToggleMetrics, &mut app.state.write_lock());
+        app.change_workspace(WorkspaceCommand::Open);
+        app.change_workspace(WorkspaceCommand::Previous);
+
+        app.change_workspace(WorkspaceCommand::Close);
+
+        // The unsaved step was written, and the failure reported in the remaining tab
+        assert_eq!(app.workspaces.len(), 1);
+        let state = app.state.read_lock();
+        let banner = state.ui.error_banner.as_deref().unwrap();
+        assert!(banner.starts_with("Could not save the recording"));
+    }
 }
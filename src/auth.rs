@@ -0,0 +1,114 @@
+//! Fetching fresh bearer tokens from an OAuth2 token endpoint
+//!
+//! When `[auth.refresh]` is configured, a 401 response triggers one token
+//! request (refresh token grant, or client credentials when no refresh token
+//! is set) and the original request is retried with the new token.
+
+use crate::config::TokenRefreshConfig;
+use serde_json::Value;
+
+/// Tokens returned by the token endpoint
+#[derive(Debug, Clone, PartialEq)]
+pub struct TokenGrant {
+    pub access_token: String,
+    /// Replacement refresh token, for servers that rotate them
+    pub refresh_token: Option<String>,
+}
+
+/// Request a new access token
+pub async fn refresh_token(config: &TokenRefreshConfig) -> Result<TokenGrant, String> {
+    let response = reqwest::Client::new()
+        .post(&config.token_url)
+        .header("Content-Type", "application/x-www-form-urlencoded")
+        .header("Accept", "application/json")
+        .body(token_request_body(config))
+        .send()
+        .await
+        .map_err(|e| format!("Token request failed: {e}"))?;
+
+    let status = response.status();
+    let body = response
+        .text()
+        .await
+        .map_err(|e| format!("Failed to read token response: {e}"))?;
+    if !status.is_success() {
+        return Err(format!("Token endpoint returned {status}"));
+    }
+
+    parse_token_response(&body)
+}
+
+/// Form body of the token request
+fn token_request_body(config: &TokenRefreshConfig) -> String {
+    let mut form = url::form_urlencoded::Serializer::new(String::new());
+    match &config.refresh_token {
+        Some(refresh_token) => form
+            .append_pair("grant_type", "refresh_token")
+            .append_pair("refresh_token", refresh_token),
+        None => form.append_pair("grant_type", "client_credentials"),
+    };
+    form.append_pair("client_id", &config.client_id);
+    if let Some(secret) = &config.client_secret {
+        form.append_pair("client_secret", secret);
+    }
+    if let Some(scope) = &config.scope {
+        form.append_pair("scope", scope);
+    }
+    form.finish()
+}
+
+fn parse_token_response(body: &str) -> Result<TokenGrant, String> {
+    let json: Value =
+        serde_json::from_str(body).map_err(|e| format!("Invalid token response: {e}"))?;
+    let access_token = json
+        .get("access_token")
+        .and_then(Value::as_str)
+        .ok_or("Token response has no access_token")?;
+
+    Ok(TokenGrant {
+        access_token: access_token.to_string(),
+        refresh_token: json
+            .get("refresh_token")
+            .and_then(Value::as_str)
+            .map(str::to_string),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(refresh_token: Option<&str>) -> TokenRefreshConfig {
+        TokenRefreshConfig {
+            token_url: "https://auth.example.com/token".to_string(),
+            client_id: "cli".to_string(),
+            client_secret: None,
+            refresh_token: refresh_token.map(str::to_string),
+            scope: Some("read write".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_token_request_body() {
+        assert_eq!(
+            token_request_body(&config(Some("r/1"))),
+            "grant_type=refresh_token&refresh_token=r%2F1&client_id=cli&scope=read+write"
+        );
+        assert_eq!(
+            token_request_body(&config(None)),
+            "grant_type=client_credentials&client_id=cli&scope=read+write"
+        );
+    }
+
+    #[test]
+    fn test_parse_token_response() {
+        assert_eq!(
+            parse_token_response(r#"{"access_token":"abc","refresh_token":"def"}"#),
+            Ok(TokenGrant {
+                access_token: "abc".to_string(),
+                refresh_token: Some("def".to_string()),
+            })
+        );
+        assert!(parse_token_response(r#"{"error":"invalid_grant"}"#).is_err());
+    }
+}
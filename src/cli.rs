@@ -0,0 +1,462 @@
+//! Command line interface
+//!
+//! Without a subcommand the interactive TUI starts as before. The other
+//! subcommands work on the configured spec (or `--spec`) without a terminal,
+//! for scripts and CI: list the endpoints, send a request, export saved
+//! requests as a shell script, or serve the response examples as a mock.
+
+use crate::app::{VariantStore, VARIANTS_FILE};
+use crate::codegen::{self, ScriptRequest};
+use crate::config::{self, Config};
+use crate::mock;
+use crate::plugins::PluginRegistry;
+use crate::recording::SessionMode;
+use crate::request;
+use crate::state::AppState;
+use crate::store;
+use crate::swagger::fetch::{self, ServiceSource};
+use crate::types::{ApiEndpoint, ParameterType, RequestConfig, RequestVariant, SecurityScheme};
+use crate::utils::write_script;
+use clap::{Args, CommandFactory, Parser, Subcommand};
+use clap_complete::Shell;
+use color_eyre::eyre::eyre;
+use color_eyre::Result;
+use std::collections::HashMap;
+use std::io::{stdout, Write};
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::sync::{Arc, RwLock};
+
+/// Name of the binary, for completions and the man page
+const BIN_NAME: &str = "lazy-swagger-tui";
+
+#[derive(Debug, Parser)]
+#[command(
+    name = BIN_NAME,
+    version,
+    about = "Explore and call Swagger/OpenAPI APIs from the terminal",
+    args_conflicts_with_subcommands = true
+)]
+pub struct Cli {
+    /// Spec URL to use instead of the configured one
+    #[arg(long, global = true, value_name = "URL")]
+    pub spec: Option<String>,
+
+    /// Base URL for requests instead of the configured one
+    #[arg(long, global = true, value_name = "URL")]
+    pub base_url: Option<String>,
+
+    #[command(flatten)]
+    pub tui: TuiArgs,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Args)]
+pub struct TuiArgs {
+    /// Record the session's actions to a file, e.g. for a bug report
+    #[arg(long, value_name = "FILE", conflicts_with = "replay")]
+    pub record: Option<PathBuf>,
+
+    /// Replay a recorded session and report where it diverged
+    #[arg(long, value_name = "FILE")]
+    pub replay: Option<PathBuf>,
+}
+
+impl TuiArgs {
+    pub fn session(self) -> Option<SessionMode> {
+        match (self.record, self.replay) {
+            (Some(path), _) => Some(SessionMode::Record(path)),
+            (None, Some(path)) => Some(SessionMode::Replay(path)),
+            (None, None) => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Subcommand)]
+pub enum Command {
+    /// Browse the API interactively (the default)
+    Tui(TuiArgs),
+
+    /// List the spec's endpoints, one `METHOD<tab>PATH<tab>SUMMARY` per line
+    List {
+        /// Only endpoints with this tag
+        #[arg(long)]
+        tag: Option<String>,
+    },
+
+    /// Send an endpoint's request and print the response body
+    ///
+    /// Exits with a failure status for network errors and 4xx/5xx responses.
+    Exec {
+        /// Endpoint as `METHOD /path`, e.g. "GET /users/{id}"
+        endpoint: String,
+
+        /// Start from a request variant saved in the app
+        #[arg(long, value_name = "NAME")]
+        variant: Option<String>,
+
+        /// Path or query parameter
+        #[arg(short, long = "param", value_name = "NAME=VALUE", value_parser = parse_param)]
+        params: Vec<(String, String)>,
+
+        /// Request body
+        #[arg(short, long)]
+        body: Option<String>,
+
+        /// Bearer token sent with the request
+        #[arg(long, env = "API_TOKEN", hide_env_values = true)]
+        token: Option<String>,
+
+        /// Print the status line and headers before the body
+        #[arg(short, long)]
+        include: bool,
+    },
+
+    /// Export the requests saved in the app as an executable curl script
+    Export {
+        /// Only saved requests of these endpoints (`METHOD /path`)
+        endpoints: Vec<String>,
+
+        /// Write the script to a file instead of stdout
+        #[arg(short, long, value_name = "FILE")]
+        output: Option<String>,
+    },
+
+    /// Serve the spec's response examples on localhost
+    Mock {
+        #[arg(short, long, default_value_t = 4010)]
+        port: u16,
+    },
+
+    /// Print shell completions
+    Completions { shell: Shell },
+
+    /// Print the man page
+    Man,
+}
+
+/// `NAME=VALUE` of `--param`
+fn parse_param(text: &str) -> Result<(String, String), String> {
+    text.split_once('=')
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .ok_or_else(|| format!("expected NAME=VALUE, got `{text}`"))
+}
+
+/// Run a subcommand other than `tui`
+pub async fn run(
+    command: Command,
+    spec: Option<String>,
+    base_url: Option<String>,
+) -> Result<ExitCode> {
+    match command {
+        Command::Tui(_) => unreachable!("the TUI is started by main"),
+        Command::Completions { shell } => {
+            clap_complete::generate(shell, &mut Cli::command(), BIN_NAME, &mut stdout());
+            Ok(ExitCode::SUCCESS)
+        }
+        Command::Man => {
+            clap_mangen::Man::new(Cli::command()).render(&mut stdout())?;
+            Ok(ExitCode::SUCCESS)
+        }
+        Command::List { tag } => {
+            let spec = SpecContext::load(spec, base_url).await?;
+            let listed = spec
+                .endpoints
+                .iter()
+                .filter(|endpoint| tag.as_ref().is_none_or(|tag| endpoint.tags.contains(tag)));
+            for endpoint in listed {
+                let service = endpoint
+                    .service
+                    .as_ref()
+                    .map(|service| format!("{service}: "))
+                    .unwrap_or_default();
+                println!(
+                    "{service}{}\t{}\t{}",
+                    endpoint.method.to_uppercase(),
+                    endpoint.path,
+                    endpoint.summary.as_deref().unwrap_or_default()
+                );
+            }
+            Ok(ExitCode::SUCCESS)
+        }
+        Command::Exec {
+            endpoint,
+            variant,
+            params,
+            body,
+            token,
+            include,
+        } => {
+            let spec = SpecContext::load(spec, base_url).await?;
+            let endpoint = find_endpoint(&spec.endpoints, &endpoint)?;
+            let mut config = match variant {
+                Some(name) => spec
+                    .variants()?
+                    .remove(&endpoint.key())
+                    .and_then(|variants| variants.into_iter().find(|v| v.name == name))
+                    .map(|variant| variant.config)
+                    .ok_or_else(|| eyre!("No saved variant `{name}` for {}", endpoint.key()))?,
+                None => RequestConfig::default(),
+            };
+            for (name, value) in params {
+                let param_type = endpoint
+                    .parameters
+                    .iter()
+                    .find(|p| p.name == name)
+                    .and_then(|p| ParameterType::from_location(&p.location))
+                    .unwrap_or(ParameterType::Query);
+                config.set_param(name, value, param_type);
+            }
+            if body.is_some() {
+                config.body = body;
+            }
+            exec(&spec, endpoint, &config, token, include).await
+        }
+        Command::Export { endpoints, output } => {
+            let spec = SpecContext::load(spec, base_url).await?;
+            export(&spec, &endpoints, output.as_deref())
+        }
+        Command::Mock { port } => {
+            let spec = SpecContext::load(spec, base_url).await?;
+            mock::serve(spec.endpoints, port).await?;
+            Ok(ExitCode::SUCCESS)
+        }
+    }
+}
+
+/// The spec a command works on, loaded as the app loads it on start
+struct SpecContext {
+    config: Config,
+    url: String,
+    base_url: Option<String>,
+    endpoints: Vec<ApiEndpoint>,
+    /// Security schemes of the spec, by name (none for aggregates)
+    security_schemes: HashMap<String, SecurityScheme>,
+}
+
+impl SpecContext {
+    /// Fetch the given spec or the configured one
+    async fn load(spec: Option<String>, base_url: Option<String>) -> Result<Self> {
+        let config = Config::load()?;
+        let url = spec
+            .or_else(|| config.server.swagger_url.clone())
+            .ok_or_else(|| eyre!("No spec URL configured; pass --spec <URL>"))?;
+        let base_url = base_url.or_else(|| config.server.base_url.clone());
+
+        let plugins = PluginRegistry::load(&config.plugins);
+        let (endpoints, security_schemes) = if config::aggregate_name(&url).is_some() {
+            (
+                Self::load_aggregate(&config, &url, &plugins).await?,
+                HashMap::new(),
+            )
+        } else {
+            let spec = fetch::load_spec(&url, &config.spec_headers(&url), &plugins)
+                .await
+                .map_err(|e| eyre!("Could not load the spec at {url}: {e}"))?;
+            (spec.endpoints, spec.security_schemes)
+        };
+        Ok(Self {
+            config,
+            url,
+            base_url,
+            endpoints,
+            security_schemes,
+        })
+    }
+
+    /// The merged endpoints of an aggregate; services that fail to load are reported and skipped
+    async fn load_aggregate(
+        config: &Config,
+        url: &str,
+        plugins: &PluginRegistry,
+    ) -> Result<Vec<ApiEndpoint>> {
+        let aggregate = config
+            .aggregate(url)
+            .ok_or_else(|| eyre!("No aggregate {url} in config.toml"))?;
+        let sources = aggregate
+            .services
+            .iter()
+            .map(|service| ServiceSource {
+                name: service.name.clone(),
+                url: service.swagger_url.clone(),
+                base_url: service.base_url.clone(),
+                headers: config.spec_headers(&service.swagger_url),
+            })
+            .collect();
+        let (endpoints, errors) = fetch::load_aggregate(sources, plugins).await;
+        for error in &errors {
+            eprintln!("{error}");
+        }
+        if endpoints.is_empty() && !errors.is_empty() {
+            return Err(eyre!("No service of {url} could be loaded"));
+        }
+        Ok(endpoints)
+    }
+
+    /// Request variants saved in the app for this spec, keyed by endpoint key
+    fn variants(&self) -> Result<HashMap<String, Vec<RequestVariant>>> {
+        let mut store: VariantStore = store::load(VARIANTS_FILE)?;
+        Ok(store.remove(&self.url).unwrap_or_default())
+    }
+}
+
+/// Send the request and print the response; fails for network errors and 4xx/5xx statuses
+async fn exec(
+    spec: &SpecContext,
+    endpoint: &ApiEndpoint,
+    config: &RequestConfig,
+    token: Option<String>,
+    include: bool,
+) -> Result<ExitCode> {
+    let mut state = AppState::default();
+    state.request.templates = spec.config.templates.clone();
+    let merged = state.request.merged_config(endpoint, Some(config));
+    if let Some(error) = endpoint.validation_errors(merged.as_ref()).first() {
+        return Err(eyre!("{error}"));
+    }
+    let base_url = endpoint
+        .request_base_url(spec.base_url.clone())
+        .ok_or_else(|| eyre!("No base URL configured; pass --base-url <URL>"))?;
+
+    state.request.auth.token = token;
+    state
+        .request
+        .auth
+        .set_refresh(spec.config.auth.refresh.clone());
+    state.request.plugins = PluginRegistry::load(&spec.config.plugins);
+    state.request.path_rewrites = spec.config.path_rewrites.clone();
+    let state = Arc::new(RwLock::new(state));
+    let (response, reauth_note) =
+        request::send_endpoint_request(&state, endpoint, Some(config), base_url).await;
+
+    if let Some(note) = reauth_note {
+        eprintln!("({note})");
+    }
+    if let Some(error) = &response.error_message {
+        eprintln!("{error}");
+        return Ok(ExitCode::FAILURE);
+    }
+    let mut out = stdout();
+    if include {
+        writeln!(out, "HTTP {} {}", response.status, response.status_text)?;
+        let mut headers: Vec<_> = response.headers.iter().collect();
+        headers.sort();
+        for (name, value) in headers {
+            writeln!(out, "{name}: {value}")?;
+        }
+        writeln!(out)?;
+    }
+    out.write_all(response.body.bytes())?;
+    out.flush()?;
+    Ok(if response.status >= 400 {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    })
+}
+
+/// Write the saved requests, optionally only those of some endpoints, as a shell script
+fn export(spec: &SpecContext, keys: &[String], output: Option<&str>) -> Result<ExitCode> {
+    let selected = keys
+        .iter()
+        .map(|key| find_endpoint(&spec.endpoints, key).map(ApiEndpoint::key))
+        .collect::<Result<Vec<_>>>()?;
+    let variants = spec.variants()?;
+    let requests: Vec<ScriptRequest> = codegen::saved_requests(&spec.endpoints, &variants)
+        .into_iter()
+        .filter(|request| selected.is_empty() || selected.contains(&request.endpoint.key()))
+        .collect();
+    if requests.is_empty() {
+        return Err(eyre!(
+            "No saved requests to export; save some with `S` in the app"
+        ));
+    }
+
+    let script = codegen::shell_script(
+        &requests,
+        spec.base_url.as_deref().unwrap_or_default(),
+        &spec.security_schemes,
+    );
+    match output {
+        Some(path) => {
+            let written = write_script(path, &script)?;
+            eprintln!(
+                "Exported {} requests to {}",
+                requests.len(),
+                written.display()
+            );
+        }
+        None => print!("{script}"),
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+/// The endpoint for `METHOD /path`, ignoring the method's case
+///
+/// Endpoints of aggregates are named with their service, as `service: METHOD /path`.
+fn find_endpoint<'a>(endpoints: &'a [ApiEndpoint], key: &str) -> Result<&'a ApiEndpoint> {
+    let (service, endpoint_key) = match key.trim().split_once(": ") {
+        Some((service, rest)) if !service.contains(char::is_whitespace) => (Some(service), rest),
+        _ => (None, key.trim()),
+    };
+    let (method, path) = endpoint_key
+        .trim()
+        .split_once(char::is_whitespace)
+        .ok_or_else(|| eyre!("Expected an endpoint as `METHOD /path`, got `{key}`"))?;
+    endpoints
+        .iter()
+        .filter(|endpoint| endpoint.event.is_none())
+        .filter(|endpoint| {
+            service.is_none_or(|service| endpoint.service.as_deref() == Some(service))
+        })
+        .find(|endpoint| {
+            endpoint.method.eq_ignore_ascii_case(method) && endpoint.path == path.trim()
+        })
+        .ok_or_else(|| eyre!("No endpoint {key} in the spec; see `{BIN_NAME} list`"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_args() {
+        let parse = |args: &[&str]| {
+            Cli::try_parse_from(std::iter::once(BIN_NAME).chain(args.iter().copied()))
+        };
+        Cli::command().debug_assert();
+
+        // The TUI stays the default and keeps its session flags
+        let cli = parse(&["--replay", "bug.jsonl"]).unwrap();
+        assert_eq!(cli.command, None);
+        assert_eq!(
+            cli.tui.session(),
+            Some(SessionMode::Replay(PathBuf::from("bug.jsonl")))
+        );
+        assert!(parse(&["--record"]).is_err());
+        assert!(parse(&["--verbose"]).is_err());
+
+        let cli = parse(&[
+            "exec",
+            "GET /users/{id}",
+            "-p",
+            "id=42",
+            "--spec",
+            "http://x/spec.json",
+        ])
+        .unwrap();
+        assert_eq!(cli.spec.as_deref(), Some("http://x/spec.json"));
+        match cli.command {
+            Some(Command::Exec {
+                endpoint, params, ..
+            }) => {
+                assert_eq!(endpoint, "GET /users/{id}");
+                assert_eq!(params, vec![("id".to_string(), "42".to_string())]);
+            }
+            other => panic!("expected exec, got {other:?}"),
+        }
+        assert!(parse(&["exec", "GET /users", "-p", "id"]).is_err());
+    }
+}
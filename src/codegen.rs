@@ -0,0 +1,893 @@
+//! Client code generated from an endpoint and its current request config
+//!
+//! Turns an exploratory request into a function to paste into an application:
+//! path, query and header parameters become typed arguments (from the parameter
+//! schema), the current body is embedded as is, and a commented call shows
+//! the values currently entered in the Request tab.
+//!
+//! Requests can also be exported as a standalone `curl` shell script to commit
+//! and run in CI; the base URL and the credential of the endpoint's security
+//! scheme come from environment variables, and so do the values of header
+//! parameters that hold secrets.
+
+use crate::redact::is_secret_name;
+use crate::request::RequestBody;
+use crate::types::{
+    ApiEndpoint, ApiParameter, ParameterType, RequestConfig, RequestVariant, SecurityScheme,
+};
+use std::collections::HashMap;
+use std::fmt::Write;
+
+/// Rust keywords a parameter name may collide with
+const RUST_KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern",
+    "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub",
+    "ref", "return", "self", "static", "struct", "super", "trait", "true", "type", "unsafe", "use",
+    "where", "while",
+];
+
+/// One request of an exported shell script
+#[derive(Debug, Clone, Copy)]
+pub struct ScriptRequest<'a> {
+    pub endpoint: &'a ApiEndpoint,
+    pub config: Option<&'a RequestConfig>,
+    /// Name of the saved variant the config comes from
+    pub variant: Option<&'a str>,
+}
+
+/// An async function sending the endpoint's request with reqwest
+///
+/// Endpoints with security requirements take the credential of their first scheme
+/// in `schemes` as arguments and send it the way the scheme says; a bearer token
+/// when none is known.
+pub fn rust_reqwest(
+    endpoint: &ApiEndpoint,
+    config: Option<&RequestConfig>,
+    base_url: &str,
+    schemes: &HashMap<String, SecurityScheme>,
+) -> String {
+    let name = function_name(endpoint);
+    let credential = needs_token(endpoint).then(|| {
+        let scheme = endpoint
+            .security_scheme_names()
+            .into_iter()
+            .find_map(|name| schemes.get(name));
+        RustCredential::for_scheme(scheme)
+    });
+    let credential_args = credential
+        .as_ref()
+        .map(RustCredential::arguments)
+        .unwrap_or_default();
+
+    // Arguments named after parameters, unique among themselves and the function's own names
+    let mut taken: Vec<String> = ["client", "base_url", "url", "request"]
+        .into_iter()
+        .chain(credential_args.iter().copied())
+        .map(str::to_string)
+        .collect();
+    let params: Vec<(&ApiParameter, ParameterType, String)> = endpoint
+        .request_params()
+        .into_iter()
+        .map(|(param, param_type)| {
+            let base_ident = identifier(&param.name);
+            let mut ident = base_ident.clone();
+            let mut n = 1;
+            while taken.contains(&ident) {
+                n += 1;
+                ident = format!("{base_ident}_{n}");
+            }
+            taken.push(ident.clone());
+            (param, param_type, ident)
+        })
+        .collect();
+
+    let mut code = String::new();
+    match &endpoint.summary {
+        Some(summary) => writeln!(code, "/// {}: {summary}", endpoint.key()),
+        None => writeln!(code, "/// {}", endpoint.key()),
+    }
+    .ok();
+    writeln!(code, "pub async fn {name}(").ok();
+    writeln!(code, "    client: &reqwest::Client,").ok();
+    writeln!(code, "    base_url: &str,").ok();
+    for arg in &credential_args {
+        writeln!(code, "    {arg}: &str,").ok();
+    }
+    for (param, _, ident) in &params {
+        writeln!(code, "    {ident}: {},", argument_type(param)).ok();
+    }
+    writeln!(code, ") -> reqwest::Result<reqwest::Response> {{").ok();
+
+    let path = params
+        .iter()
+        .filter(|(_, param_type, _)| *param_type == ParameterType::Path)
+        .fold(endpoint.path.clone(), |path, (param, _, ident)| {
+            path.replace(&format!("{{{}}}", param.name), &format!("{{{ident}}}"))
+        });
+    writeln!(code, "    let url = format!(\"{{base_url}}{path}\");").ok();
+    writeln!(
+        code,
+        "    let mut request = client.request(reqwest::Method::{}, url);",
+        endpoint.method.to_uppercase()
+    )
+    .ok();
+
+    for (param, param_type, ident) in &params {
+        let call = match param_type {
+            ParameterType::Path => continue,
+            ParameterType::Query => format!("query(&[(\"{}\", {ident}.to_string())])", param.name),
+            ParameterType::Header => format!("header(\"{}\", {ident}.to_string())", param.name),
+        };
+        if param.is_required() {
+            writeln!(code, "    request = request.{call};").ok();
+        } else {
+            writeln!(code, "    if let Some({ident}) = {ident} {{").ok();
+            writeln!(code, "        request = request.{call};").ok();
+            writeln!(code, "    }}").ok();
+        }
+    }
+    if let Some(credential) = &credential {
+        writeln!(code, "    request = request.{};", credential.call()).ok();
+    }
+
+    let body = config.and_then(|config| config.body.clone());
+    if endpoint.supports_body() && body.as_deref().is_some_and(|b| !b.trim().is_empty()) {
+        let body = RequestBody::encode(&endpoint.body_content_type(config), body);
+        writeln!(code, "    request = request").ok();
+        writeln!(
+            code,
+            "        .header(\"Content-Type\", {:?})",
+            body.content_type
+        )
+        .ok();
+        writeln!(code, "        .body({});", raw_string(&body.content)).ok();
+    }
+    writeln!(code, "    request.send().await").ok();
+    writeln!(code, "}}").ok();
+
+    // A call with the values entered in the Request tab
+    let mut args = vec!["&client".to_string(), format!("{base_url:?}")];
+    args.extend(credential_args.iter().map(|arg| arg.to_string()));
+    for (param, _, _) in &params {
+        let value = config
+            .and_then(|config| config.get_param_value(&param.name))
+            .filter(|value| !value.is_empty());
+        args.push(argument_value(param, value));
+    }
+    writeln!(code).ok();
+    writeln!(
+        code,
+        "// let response = {name}({}).await?;",
+        args.join(", ")
+    )
+    .ok();
+    code
+}
+
+/// How a generated Rust function sends the credential of a security scheme
+enum RustCredential {
+    /// An API key in the named header, query parameter or cookie
+    ApiKey {
+        name: String,
+        location: String,
+    },
+    Basic,
+    /// A token after another HTTP authorization scheme, e.g. `Digest`
+    Http(String),
+    Bearer,
+}
+
+impl RustCredential {
+    fn for_scheme(scheme: Option<&SecurityScheme>) -> Self {
+        match scheme {
+            Some(scheme) if scheme.kind == "apiKey" => Self::ApiKey {
+                name: scheme.name.clone().unwrap_or_else(|| "api_key".to_string()),
+                location: scheme.location.clone().unwrap_or_default(),
+            },
+            Some(scheme) if scheme.is_http("basic") => Self::Basic,
+            Some(scheme) if scheme.kind == "http" && !scheme.is_http("bearer") => {
+                Self::Http(scheme.scheme.clone().unwrap_or_default())
+            }
+            _ => Self::Bearer,
+        }
+    }
+
+    /// Names of the function arguments holding the credential
+    fn arguments(&self) -> Vec<&'static str> {
+        match self {
+            Self::ApiKey { .. } => vec!["api_key"],
+            Self::Basic => vec!["username", "password"],
+            Self::Http(_) | Self::Bearer => vec!["token"],
+        }
+    }
+
+    /// The request builder call sending the credential
+    fn call(&self) -> String {
+        match self {
+            Self::ApiKey { name, location } => match location.as_str() {
+                "query" => format!("query(&[({name:?}, api_key)])"),
+                "cookie" => format!("header(\"Cookie\", format!(\"{name}={{api_key}}\"))"),
+                _ => format!("header({name:?}, api_key)"),
+            },
+            Self::Basic => "basic_auth(username, Some(password))".to_string(),
+            Self::Http(scheme) => {
+                format!("header(\"Authorization\", format!(\"{scheme} {{token}}\"))")
+            }
+            Self::Bearer => "bearer_auth(token)".to_string(),
+        }
+    }
+}
+
+/// A `sh` script with one function per request, running the named ones or all
+///
+/// `BASE_URL` defaults to the given base URL. Endpoints with security requirements
+/// send the credential of their first scheme in `schemes` (a bearer token when none
+/// is known) from an environment variable, so no secret ends up in the file. For
+/// the same reason header parameters with secret names, or sent as the API key
+/// of one of `schemes`, are read from an environment variable named after them.
+pub fn shell_script(
+    requests: &[ScriptRequest],
+    base_url: &str,
+    schemes: &HashMap<String, SecurityScheme>,
+) -> String {
+    let mut functions = String::new();
+    // Environment variables the requests read, with what they hold
+    let mut variables: Vec<(String, String)> = Vec::new();
+    let mut use_variable = |name: &str, purpose: String| {
+        if !variables.iter().any(|(known, _)| known == name) {
+            variables.push((name.to_string(), purpose));
+        }
+        format!("${{{name}:?{name} is not set}}")
+    };
+
+    let mut names: Vec<String> = Vec::new();
+    for request in requests {
+        let endpoint = request.endpoint;
+        let mut name = function_name(endpoint);
+        if let Some(variant) = request.variant {
+            name = format!("{name}_{}", snake_case(variant));
+        }
+        let base_name = name.clone();
+        let mut n = 1;
+        while names.contains(&name) {
+            n += 1;
+            name = format!("{base_name}_{n}");
+        }
+
+        writeln!(functions).ok();
+        match request.variant {
+            Some(variant) => writeln!(functions, "# {} ({variant})", endpoint.key()),
+            None => writeln!(functions, "# {}", endpoint.key()),
+        }
+        .ok();
+        let path = script_path(endpoint, request.config);
+        let mut url = format!("\"$BASE_URL\"{}", shell_quote(&path));
+        let mut args = Vec::new();
+        // Header the credential goes in, so a parameter of the same name isn't sent twice
+        let mut auth_header = None;
+        let scheme = endpoint
+            .security_scheme_names()
+            .into_iter()
+            .find_map(|name| schemes.get(name));
+        match scheme {
+            _ if !needs_token(endpoint) => {}
+            Some(scheme) if scheme.kind == "apiKey" => {
+                let name = scheme.name.as_deref().unwrap_or("api_key");
+                let key = use_variable("API_KEY", format!("API key ({})", scheme.describe()));
+                match scheme.location.as_deref() {
+                    Some("query") => {
+                        let separator = if path.contains('?') { '&' } else { '?' };
+                        url.push_str(&format!("\"{separator}{name}={key}\""));
+                    }
+                    Some("cookie") => args.push(format!("-b \"{name}={key}\"")),
+                    _ => {
+                        args.push(format!("-H \"{name}: {key}\""));
+                        auth_header = Some(name);
+                    }
+                }
+            }
+            Some(scheme) if scheme.is_http("basic") => {
+                let credentials = use_variable(
+                    "API_CREDENTIALS",
+                    "user:password for basic auth".to_string(),
+                );
+                args.push(format!("-u \"{credentials}\""));
+            }
+            Some(scheme) if scheme.kind == "http" && !scheme.is_http("bearer") => {
+                let auth_scheme = scheme.scheme.as_deref().unwrap_or_default();
+                let token = use_variable(
+                    "API_TOKEN",
+                    format!("token sent as {auth_scheme} credential"),
+                );
+                args.push(format!("-H \"Authorization: {auth_scheme} {token}\""));
+            }
+            _ => {
+                let token = use_variable("API_TOKEN", "bearer token".to_string());
+                args.push(format!("-H \"Authorization: Bearer {token}\""));
+            }
+        }
+        args.insert(
+            0,
+            format!(
+                "curl -sS --fail-with-body -X {} {url}",
+                endpoint.method.to_uppercase()
+            ),
+        );
+        for (name, value) in request
+            .config
+            .map(RequestConfig::header_values)
+            .unwrap_or_default()
+        {
+            if auth_header.is_some_and(|header| header.eq_ignore_ascii_case(&name)) {
+                continue;
+            }
+            if is_secret_header(&name, schemes) {
+                let value = use_variable(
+                    &snake_case(&name).to_uppercase(),
+                    format!("value of the {name} header"),
+                );
+                args.push(format!("-H \"{name}: {value}\""));
+            } else {
+                args.push(format!("-H {}", shell_quote(&format!("{name}: {value}"))));
+            }
+        }
+        let body = request.config.and_then(|config| config.body.clone());
+        if endpoint.supports_body() && body.as_deref().is_some_and(|b| !b.trim().is_empty()) {
+            let body = RequestBody::encode(&endpoint.body_content_type(request.config), body);
+            args.push(format!(
+                "-H {}",
+                shell_quote(&format!("Content-Type: {}", body.content_type))
+            ));
+            args.push(format!("--data-raw {}", shell_quote(&body.content)));
+        }
+        writeln!(functions, "{name}() {{").ok();
+        writeln!(functions, "  {}", args.join(" \\\n    ")).ok();
+        writeln!(functions, "}}").ok();
+        names.push(name);
+    }
+
+    let mut script = String::new();
+    writeln!(script, "#!/bin/sh").ok();
+    writeln!(script, "# Requests exported from lazy-swagger-tui").ok();
+    writeln!(script, "#").ok();
+    writeln!(
+        script,
+        "# Usage: $0 [request...]  (runs every request by default)"
+    )
+    .ok();
+    writeln!(script, "# BASE_URL overrides the base URL.").ok();
+    for (name, purpose) in &variables {
+        writeln!(script, "# {name}: {purpose}").ok();
+    }
+    writeln!(script, "set -eu").ok();
+    writeln!(script).ok();
+    writeln!(script, "BASE_URL=\"${{BASE_URL:-{base_url}}}\"").ok();
+    script.push_str(&functions);
+
+    writeln!(script).ok();
+    writeln!(script, "if [ \"$#\" -eq 0 ]; then").ok();
+    writeln!(script, "  set -- {}", names.join(" ")).ok();
+    writeln!(script, "fi").ok();
+    writeln!(script, "for request in \"$@\"; do").ok();
+    writeln!(script, "  \"$request\"").ok();
+    writeln!(script, "done").ok();
+    script
+}
+
+/// Every saved request variant as a script request, in spec order
+pub fn saved_requests<'a>(
+    endpoints: &'a [ApiEndpoint],
+    variants: &'a HashMap<String, Vec<RequestVariant>>,
+) -> Vec<ScriptRequest<'a>> {
+    endpoints
+        .iter()
+        .flat_map(|endpoint| {
+            variants
+                .get(&endpoint.key())
+                .into_iter()
+                .flatten()
+                .map(move |variant| ScriptRequest {
+                    endpoint,
+                    config: Some(&variant.config),
+                    variant: Some(&variant.name),
+                })
+        })
+        .collect()
+}
+
+/// Default file name of an exported script, e.g. `get_users_by_id.sh`
+pub fn script_file_name(endpoint: &ApiEndpoint) -> String {
+    format!("{}.sh", function_name(endpoint))
+}
+
+/// Whether any of the endpoint's security requirements asks for credentials
+fn needs_token(endpoint: &ApiEndpoint) -> bool {
+    endpoint
+        .security
+        .as_ref()
+        .is_some_and(|security| security.iter().any(|requirement| !requirement.is_empty()))
+}
+
+/// A value percent-encoded to fit in one path segment, e.g. `a%2Fb` for `a/b`
+fn encode_path_segment(value: &str) -> String {
+    let mut url = url::Url::parse("http://localhost/").expect("valid URL");
+    url.path_segments_mut()
+        .expect("URL with a path")
+        .clear()
+        .push(value);
+    url.path()[1..].to_string()
+}
+
+/// Whether a header parameter holds a secret, by its name or as an API key header
+fn is_secret_header(name: &str, schemes: &HashMap<String, SecurityScheme>) -> bool {
+    is_secret_name(name)
+        || schemes.values().any(|scheme| {
+            scheme.kind == "apiKey"
+                && scheme.location.as_deref() == Some("header")
+                && scheme
+                    .name
+                    .as_deref()
+                    .is_some_and(|key| key.eq_ignore_ascii_case(name))
+        })
+}
+
+/// The path with the entered path parameters and the non-empty query parameters
+fn script_path(endpoint: &ApiEndpoint, config: Option<&RequestConfig>) -> String {
+    let params = config
+        .map(|config| config.parameters.as_slice())
+        .unwrap_or_default();
+    let mut path = endpoint.path.clone();
+    let mut query = url::form_urlencoded::Serializer::new(String::new());
+    for param in params.iter().filter(|param| !param.value.is_empty()) {
+        match param.param_type {
+            ParameterType::Path => {
+                path = path.replace(
+                    &format!("{{{}}}", param.name),
+                    &encode_path_segment(&param.value),
+                );
+            }
+            ParameterType::Query => {
+                query.append_pair(&param.name, &param.value);
+            }
+            // Sent with `-H` by `shell_script`
+            ParameterType::Header => {}
+        }
+    }
+    let query = query.finish();
+    if query.is_empty() {
+        path
+    } else {
+        format!("{path}?{query}")
+    }
+}
+
+/// Single-quoted for `sh`, with embedded quotes closed and escaped
+fn shell_quote(text: &str) -> String {
+    format!("'{}'", text.replace('\'', r"'\''"))
+}
+
+/// e.g. `get_users_by_id` for `GET /users/{id}`
+fn function_name(endpoint: &ApiEndpoint) -> String {
+    let mut words = vec![endpoint.method.to_lowercase()];
+    for segment in endpoint.path.split('/').filter(|s| !s.is_empty()) {
+        match segment.strip_prefix('{').and_then(|s| s.strip_suffix('}')) {
+            Some(param) => words.push(format!("by_{}", snake_case(param))),
+            None => words.push(snake_case(segment)),
+        }
+    }
+    words.retain(|word| !word.is_empty());
+    words.join("_")
+}
+
+/// Lowercase words joined by underscores, e.g. `userId` or `user-id` to `user_id`
+fn snake_case(text: &str) -> String {
+    let mut snake = String::new();
+    let mut prev = ' ';
+    for c in text.chars() {
+        if c.is_ascii_uppercase() {
+            if prev.is_ascii_lowercase() || prev.is_ascii_digit() {
+                snake.push('_');
+            }
+            snake.push(c.to_ascii_lowercase());
+        } else if c.is_ascii_alphanumeric() {
+            snake.push(c);
+        } else if !snake.is_empty() && !snake.ends_with('_') {
+            snake.push('_');
+        }
+        prev = c;
+    }
+    snake.trim_end_matches('_').to_string()
+}
+
+/// A parameter name usable as a Rust argument
+fn identifier(name: &str) -> String {
+    let ident = snake_case(name);
+    if ident.is_empty() || ident.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("param_{ident}")
+    } else if RUST_KEYWORDS.contains(&ident.as_str()) {
+        format!("{ident}_")
+    } else {
+        ident
+    }
+}
+
+fn base_type(param: &ApiParameter) -> &'static str {
+    let schema = param.schema.as_ref();
+    match (
+        schema.and_then(|s| s.param_type.as_deref()),
+        schema.and_then(|s| s.format.as_deref()),
+    ) {
+        (Some("integer"), Some("int32")) => "i32",
+        (Some("integer"), _) => "i64",
+        (Some("number"), Some("float")) => "f32",
+        (Some("number"), _) => "f64",
+        (Some("boolean"), _) => "bool",
+        _ => "&str",
+    }
+}
+
+/// Path parameters and required query parameters are plain, others optional
+fn argument_type(param: &ApiParameter) -> String {
+    if param.location == "path" || param.is_required() {
+        base_type(param).to_string()
+    } else {
+        format!("Option<{}>", base_type(param))
+    }
+}
+
+/// The entered value as a Rust expression of the argument's type
+fn argument_value(param: &ApiParameter, value: Option<&str>) -> String {
+    let optional = param.location != "path" && !param.is_required();
+    let Some(value) = value else {
+        return if optional { "None" } else { "todo!()" }.to_string();
+    };
+    let literal = match base_type(param) {
+        "&str" => format!("{value:?}"),
+        "bool" if value == "true" || value == "false" => value.to_string(),
+        "f32" | "f64" if value.parse::<f64>().is_ok() => value.to_string(),
+        "i32" | "i64" if value.parse::<i64>().is_ok() => value.to_string(),
+        _ => format!("todo!(/* {value:?} */)"),
+    };
+    if optional {
+        format!("Some({literal})")
+    } else {
+        literal
+    }
+}
+
+/// A raw string literal with enough `#`s for the text
+fn raw_string(text: &str) -> String {
+    let mut hashes = "#".to_string();
+    while text.contains(&format!("\"{hashes}")) {
+        hashes.push('#');
+    }
+    format!("r{hashes}\"{text}\"{hashes}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ParameterSchema;
+
+    fn param(name: &str, location: &str, required: bool, param_type: &str) -> ApiParameter {
+        ApiParameter {
+            name: name.to_string(),
+            location: location.to_string(),
+            required: Some(required),
+            schema: Some(ParameterSchema {
+                param_type: Some(param_type.to_string()),
+                ..Default::default()
+            }),
+            description: None,
+            example: None,
+        }
+    }
+
+    #[test]
+    fn test_rust_reqwest() {
+        let endpoint = ApiEndpoint {
+            method: "PUT".to_string(),
+            path: "/users/{userId}".to_string(),
+            summary: Some("Update a user".to_string()),
+            parameters: vec![
+                param("userId", "path", true, "integer"),
+                param("type", "query", false, "string"),
+            ],
+            security: Some(vec![HashMap::from([("bearer".to_string(), Vec::new())])]),
+            ..Default::default()
+        };
+        let mut config = RequestConfig::default();
+        config.set_param(
+            "userId".to_string(),
+            "42".to_string(),
+            crate::types::ParameterType::Path,
+        );
+        config.body = Some(r##"{"tag": "a"#b"}"##.to_string());
+
+        let code = rust_reqwest(
+            &endpoint,
+            Some(&config),
+            "http://localhost:5000",
+            &HashMap::new(),
+        );
+        let expected = r####"/// PUT /users/{userId}: Update a user
+pub async fn put_users_by_user_id(
+    client: &reqwest::Client,
+    base_url: &str,
+    token: &str,
+    user_id: i64,
+    type_: Option<&str>,
+) -> reqwest::Result<reqwest::Response> {
+    let url = format!("{base_url}/users/{user_id}");
+    let mut request = client.request(reqwest::Method::PUT, url);
+    if let Some(type_) = type_ {
+        request = request.query(&[("type", type_.to_string())]);
+    }
+    request = request.bearer_auth(token);
+    request = request
+        .header("Content-Type", "application/json")
+        .body(r##"{"tag": "a"#b"}"##);
+    request.send().await
+}
+
+// let response = put_users_by_user_id(&client, "http://localhost:5000", token, 42, None).await?;
+"####;
+        assert_eq!(code, expected);
+    }
+
+    #[test]
+    fn test_rust_reqwest_auth_from_scheme() {
+        let scheme =
+            |value: serde_json::Value| -> SecurityScheme { serde_json::from_value(value).unwrap() };
+        let schemes = HashMap::from([
+            (
+                "header_key".to_string(),
+                scheme(serde_json::json!({ "type": "apiKey", "name": "X-Key", "in": "header" })),
+            ),
+            (
+                "query_key".to_string(),
+                scheme(serde_json::json!({ "type": "apiKey", "name": "api_key", "in": "query" })),
+            ),
+            (
+                "basic".to_string(),
+                scheme(serde_json::json!({ "type": "http", "scheme": "basic" })),
+            ),
+            (
+                "bearer".to_string(),
+                scheme(serde_json::json!({ "type": "http", "scheme": "bearer" })),
+            ),
+        ]);
+        let code = |scheme: &str| {
+            let endpoint = ApiEndpoint {
+                method: "GET".to_string(),
+                path: "/reports".to_string(),
+                security: Some(vec![HashMap::from([(scheme.to_string(), Vec::new())])]),
+                ..Default::default()
+            };
+            rust_reqwest(&endpoint, None, "http://localhost:5000", &schemes)
+        };
+
+        let header_key = code("header_key");
+        assert!(header_key.contains("    api_key: &str,\n"));
+        assert!(header_key.contains(r#"request = request.header("X-Key", api_key);"#));
+        let query_key = code("query_key");
+        assert!(query_key.contains(r#"request = request.query(&[("api_key", api_key)]);"#));
+        let basic = code("basic");
+        assert!(basic.contains("    username: &str,\n    password: &str,\n"));
+        assert!(basic.contains("request = request.basic_auth(username, Some(password));"));
+        assert!(
+            basic.contains(r#"get_reports(&client, "http://localhost:5000", username, password)"#)
+        );
+        let bearer = code("bearer");
+        assert!(bearer.contains("request = request.bearer_auth(token);"));
+        for code in [header_key, query_key, basic] {
+            assert!(!code.contains("bearer_auth") && !code.contains("token"));
+        }
+    }
+
+    #[test]
+    fn test_rust_reqwest_unique_arguments() {
+        let endpoint = ApiEndpoint {
+            method: "GET".to_string(),
+            path: "/users".to_string(),
+            parameters: vec![
+                param("userId", "query", true, "string"),
+                param("user-id", "query", true, "string"),
+                param("url", "query", true, "string"),
+            ],
+            ..Default::default()
+        };
+
+        let code = rust_reqwest(&endpoint, None, "http://localhost:5000", &HashMap::new());
+        assert!(code.contains("    user_id: &str,\n    user_id_2: &str,\n    url_2: &str,\n"));
+        assert!(code.contains(r#"request = request.query(&[("user-id", user_id_2.to_string())]);"#));
+        assert!(code.contains(r#"request = request.query(&[("url", url_2.to_string())]);"#));
+    }
+
+    #[test]
+    fn test_shell_script() {
+        let endpoint = ApiEndpoint {
+            method: "POST".to_string(),
+            path: "/users/{userId}/notes".to_string(),
+            parameters: vec![
+                param("userId", "path", true, "integer"),
+                param("tag", "query", false, "string"),
+            ],
+            security: Some(vec![HashMap::from([("bearer".to_string(), Vec::new())])]),
+            ..Default::default()
+        };
+        let mut config = RequestConfig::default();
+        config.set_param(
+            "userId".to_string(),
+            "42".to_string(),
+            crate::types::ParameterType::Path,
+        );
+        config.set_param(
+            "tag".to_string(),
+            "a b".to_string(),
+            crate::types::ParameterType::Query,
+        );
+        config.body = Some(r#"{"text": "it's"}"#.to_string());
+        let public = ApiEndpoint {
+            method: "GET".to_string(),
+            path: "/health".to_string(),
+            ..Default::default()
+        };
+
+        let requests = [
+            ScriptRequest {
+                endpoint: &endpoint,
+                config: Some(&config),
+                variant: Some("Admin user"),
+            },
+            ScriptRequest {
+                endpoint: &public,
+                config: None,
+                variant: None,
+            },
+            ScriptRequest {
+                endpoint: &public,
+                config: None,
+                variant: None,
+            },
+        ];
+        let script = shell_script(&requests, "http://localhost:5000", &HashMap::new());
+        let expected = r#"#!/bin/sh
+# Requests exported from lazy-swagger-tui
+#
+# Usage: $0 [request...]  (runs every request by default)
+# BASE_URL overrides the base URL.
+# API_TOKEN: bearer token
+set -eu
+
+BASE_URL="${BASE_URL:-http://localhost:5000}"
+
+# POST /users/{userId}/notes (Admin user)
+post_users_by_user_id_notes_admin_user() {
+  curl -sS --fail-with-body -X POST "$BASE_URL"'/users/42/notes?tag=a+b' \
+    -H "Authorization: Bearer ${API_TOKEN:?API_TOKEN is not set}" \
+    -H 'Content-Type: application/json' \
+    --data-raw '{"text": "it'\''s"}'
+}
+
+# GET /health
+get_health() {
+  curl -sS --fail-with-body -X GET "$BASE_URL"'/health'
+}
+
+# GET /health
+get_health_2() {
+  curl -sS --fail-with-body -X GET "$BASE_URL"'/health'
+}
+
+if [ "$#" -eq 0 ]; then
+  set -- post_users_by_user_id_notes_admin_user get_health get_health_2
+fi
+for request in "$@"; do
+  "$request"
+done
+"#;
+        assert_eq!(script, expected);
+    }
+
+    #[test]
+    fn test_script_secret_headers() {
+        let endpoint = ApiEndpoint {
+            method: "GET".to_string(),
+            path: "/reports".to_string(),
+            parameters: vec![
+                param("X-Tenant", "header", false, "string"),
+                param("X-Api-Key", "header", false, "string"),
+                param("X-Client", "header", false, "string"),
+            ],
+            ..Default::default()
+        };
+        let mut config = RequestConfig::default();
+        for (name, value) in [
+            ("X-Tenant", "acme"),
+            ("X-Api-Key", "k1"),
+            ("X-Client", "c1"),
+        ] {
+            config.set_param(name.to_string(), value.to_string(), ParameterType::Header);
+        }
+        let client: SecurityScheme = serde_json::from_value(
+            serde_json::json!({ "type": "apiKey", "name": "x-client", "in": "header" }),
+        )
+        .unwrap();
+        let schemes = HashMap::from([("client".to_string(), client)]);
+
+        let script = shell_script(
+            &[ScriptRequest {
+                endpoint: &endpoint,
+                config: Some(&config),
+                variant: None,
+            }],
+            "http://localhost:5000",
+            &schemes,
+        );
+        assert!(script.contains("-H 'X-Tenant: acme'"));
+        assert!(script.contains(r#"-H "X-Api-Key: ${X_API_KEY:?X_API_KEY is not set}""#));
+        assert!(script.contains(r#"-H "X-Client: ${X_CLIENT:?X_CLIENT is not set}""#));
+        assert!(!script.contains("k1") && !script.contains("c1"));
+    }
+
+    #[test]
+    fn test_script_auth_from_scheme() {
+        let scheme =
+            |value: serde_json::Value| -> SecurityScheme { serde_json::from_value(value).unwrap() };
+        let schemes = HashMap::from([
+            (
+                "basic".to_string(),
+                scheme(serde_json::json!({ "type": "http", "scheme": "basic" })),
+            ),
+            (
+                "query_key".to_string(),
+                scheme(serde_json::json!({ "type": "apiKey", "name": "api_key", "in": "query" })),
+            ),
+            (
+                "session".to_string(),
+                scheme(serde_json::json!({ "type": "apiKey", "name": "sid", "in": "cookie" })),
+            ),
+        ]);
+        let secured = |path: &str, scheme: &str| ApiEndpoint {
+            method: "GET".to_string(),
+            path: path.to_string(),
+            parameters: vec![param("name", "path", true, "string")],
+            security: Some(vec![HashMap::from([(scheme.to_string(), Vec::new())])]),
+            ..Default::default()
+        };
+        let by_basic = secured("/files/{name}", "basic");
+        let by_query = secured("/reports/{name}", "query_key");
+        let by_cookie = secured("/sessions/{name}", "session");
+        let mut config = RequestConfig::default();
+        config.set_param("name".to_string(), "a b/c".to_string(), ParameterType::Path);
+        let requests: Vec<ScriptRequest> = [&by_basic, &by_query, &by_cookie]
+            .into_iter()
+            .map(|endpoint| ScriptRequest {
+                endpoint,
+                config: Some(&config),
+                variant: None,
+            })
+            .collect();
+
+        let script = shell_script(&requests, "http://localhost:5000", &schemes);
+        assert!(script.contains(
+            r#""$BASE_URL"'/files/a%20b%2Fc' \
+    -u "${API_CREDENTIALS:?API_CREDENTIALS is not set}""#
+        ));
+        assert!(script.contains(
+            r#""$BASE_URL"'/reports/a%20b%2Fc'"?api_key=${API_KEY:?API_KEY is not set}""#
+        ));
+        assert!(script.contains(r#"-b "sid=${API_KEY:?API_KEY is not set}""#));
+        assert!(script.contains("# API_CREDENTIALS: user:password for basic auth"));
+        assert!(!script.contains("API_TOKEN") && !script.contains("Bearer"));
+    }
+
+    #[test]
+    fn test_identifiers() {
+        assert_eq!(identifier("page-size"), "page_size");
+        assert_eq!(identifier("X-Request-ID"), "x_request_id");
+        assert_eq!(identifier("2fa"), "param_2fa");
+        assert_eq!(identifier("match"), "match_");
+    }
+}
@@ -1,11 +1,57 @@
+use crate::types::ApiEndpoint;
+use crate::utils::url_under;
 use color_eyre::Result;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::path::PathBuf;
+use std::time::Duration;
+
+/// Maximum number of URLs remembered per history list
+const MAX_URL_HISTORY: usize = 20;
+
+/// Maximum number of values remembered per parameter name
+pub const MAX_PARAM_HISTORY: usize = 10;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
     pub server: ServerConfig,
+    #[serde(default)]
+    pub history: HistoryConfig,
+    #[serde(default)]
+    pub grouping: GroupingConfig,
+    #[serde(default)]
+    pub auth: AuthConfig,
+    #[serde(default)]
+    pub layout: LayoutConfig,
+    #[serde(default)]
+    pub notifications: NotificationConfig,
+    #[serde(default)]
+    pub clipboard: ClipboardConfig,
+    #[serde(default)]
+    pub ui: UiConfig,
+    #[serde(default)]
+    pub requests: RequestsConfig,
+    #[serde(default)]
+    pub watch: WatchConfig,
+    /// External programs hooked into requests, see `plugins`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub plugins: Vec<PluginConfig>,
+    /// Extra headers for downloading specs, e.g. from private registries
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub spec_profiles: Vec<SpecProfileConfig>,
+    /// Specs of several services merged into one endpoint list, opened as `aggregate:<name>`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aggregates: Vec<AggregateConfig>,
+    /// Specs offered by the spec switcher (`W`), each opened in its own workspace tab
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub specs: Vec<SpecConfig>,
+    /// Path changes for requests to some base URLs, e.g. behind an API gateway
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub path_rewrites: Vec<PathRewriteConfig>,
+    /// Parameters and headers shared by many endpoints, merged into their requests
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub templates: Vec<RequestTemplateConfig>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -15,6 +61,476 @@ pub struct ServerConfig {
     pub base_url: Option<String>,
 }
 
+/// Previously submitted URLs, most recent first
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HistoryConfig {
+    #[serde(default)]
+    pub swagger_urls: Vec<String>,
+    #[serde(default)]
+    pub base_urls: Vec<String>,
+    /// Values used per parameter name, shared across endpoints
+    #[serde(default)]
+    pub param_values: HashMap<String, Vec<String>>,
+}
+
+/// User-defined grouping of endpoints in the grouped view
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GroupingConfig {
+    /// Rules mapping paths to group names, the first match wins
+    #[serde(default)]
+    pub rules: Vec<GroupRule>,
+    /// Groups listed here come first, in this order; the rest follow alphabetically
+    #[serde(default)]
+    pub order: Vec<String>,
+    /// Groups left out of the grouped view
+    #[serde(default)]
+    pub hidden: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GroupRule {
+    /// Regex matched against the endpoint path
+    pub pattern: String,
+    /// Group name, may reference captures (`$1`, `${name}`)
+    pub group: String,
+}
+
+/// How the main screen is split
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LayoutConfig {
+    /// Start with the response in its own pane next to the request (toggled with `|`)
+    #[serde(default)]
+    pub three_pane: bool,
+    /// Endpoints and details next to each other or stacked (toggled with `\`)
+    #[serde(default)]
+    pub arrangement: PanelArrangement,
+}
+
+/// How the endpoints and details panels share the screen
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum PanelArrangement {
+    /// Next to each other, stacked when the terminal is too narrow
+    #[default]
+    Auto,
+    /// Endpoints on top, details below
+    Stacked,
+    /// Next to each other, however narrow the terminal
+    SideBySide,
+}
+
+impl PanelArrangement {
+    /// The arrangement `\` switches to
+    pub fn next(self) -> Self {
+        match self {
+            Self::Auto => Self::Stacked,
+            Self::Stacked => Self::SideBySide,
+            Self::SideBySide => Self::Auto,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Auto => "auto",
+            Self::Stacked => "stacked",
+            Self::SideBySide => "side by side",
+        }
+    }
+}
+
+/// Pacing of the main loop and colors
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UiConfig {
+    /// Milliseconds between ticks, which animate the spinner and pick up finished requests
+    #[serde(default = "default_tick_rate_ms")]
+    pub tick_rate_ms: u64,
+    /// Colors of methods, statuses and borders
+    #[serde(default)]
+    pub palette: Palette,
+    /// Put a symbol before each method (`+ POST`, `× DELETE`), so they differ without color
+    #[serde(default)]
+    pub method_markers: bool,
+    /// Draw no box or spinner characters, and announce messages in one status line
+    #[serde(default)]
+    pub screen_reader: bool,
+}
+
+/// Colors of methods, statuses and borders
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Palette {
+    /// The terminal's green/blue/yellow/red/cyan
+    #[default]
+    Default,
+    /// Blue/orange/vermillion hues that stay apart with red-green color blindness
+    ColorBlind,
+    /// Bright colors and lighter muted text, for low-contrast screens
+    HighContrast,
+}
+
+/// Faster ticks than this only cost CPU: the screen is redrawn on input anyway
+const MIN_TICK_RATE_MS: u64 = 16;
+
+fn default_tick_rate_ms() -> u64 {
+    100
+}
+
+impl UiConfig {
+    pub fn tick_rate(&self) -> Duration {
+        Duration::from_millis(self.tick_rate_ms.max(MIN_TICK_RATE_MS))
+    }
+}
+
+impl Default for UiConfig {
+    fn default() -> Self {
+        Self {
+            tick_rate_ms: default_tick_rate_ms(),
+            palette: Palette::default(),
+            method_markers: false,
+            screen_reader: false,
+        }
+    }
+}
+
+/// How requests to endpoints are sent
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RequestsConfig {
+    /// Requests in flight at once; more wait in the queue, in the order they were sent
+    #[serde(default = "default_max_concurrent")]
+    pub max_concurrent: usize,
+}
+
+fn default_max_concurrent() -> usize {
+    4
+}
+
+impl Default for RequestsConfig {
+    fn default() -> Self {
+        Self {
+            max_concurrent: default_max_concurrent(),
+        }
+    }
+}
+
+/// Reloading the spec on an interval, e.g. while the backend hot-reloads
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatchConfig {
+    /// Watch from the start instead of after `F6`
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_watch_interval_secs")]
+    pub interval_secs: u64,
+}
+
+fn default_watch_interval_secs() -> u64 {
+    5
+}
+
+impl WatchConfig {
+    pub fn interval(&self) -> Duration {
+        Duration::from_secs(self.interval_secs.max(1))
+    }
+}
+
+impl Default for WatchConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval_secs: default_watch_interval_secs(),
+        }
+    }
+}
+
+/// Notifications for slow requests that finish while another endpoint or window has focus
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotificationConfig {
+    /// Ring the terminal bell
+    #[serde(default = "default_bell")]
+    pub bell: bool,
+    /// Send a desktop notification (notify-send on Linux, osascript on macOS)
+    #[serde(default)]
+    pub desktop: bool,
+    /// Requests finishing faster than this are not notified
+    #[serde(default = "default_min_duration_ms")]
+    pub min_duration_ms: u64,
+}
+
+fn default_bell() -> bool {
+    true
+}
+
+fn default_min_duration_ms() -> u64 {
+    2000
+}
+
+impl Default for NotificationConfig {
+    fn default() -> Self {
+        Self {
+            bell: default_bell(),
+            desktop: false,
+            min_duration_ms: default_min_duration_ms(),
+        }
+    }
+}
+
+/// How yanked text reaches the clipboard
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ClipboardConfig {
+    /// When to copy through the terminal with an OSC 52 escape sequence
+    #[serde(default)]
+    pub osc52: Osc52Mode,
+}
+
+/// When yanks go through the terminal instead of the system clipboard
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Osc52Mode {
+    /// Over SSH, or when the system clipboard is unavailable
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+/// Authentication settings
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AuthConfig {
+    /// Token endpoint used to re-authenticate when a request returns 401
+    pub refresh: Option<TokenRefreshConfig>,
+}
+
+/// OAuth2 token endpoint credentials
+///
+/// Uses the refresh token grant when `refresh_token` is set, client credentials otherwise.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenRefreshConfig {
+    pub token_url: String,
+    pub client_id: String,
+    pub client_secret: Option<String>,
+    pub refresh_token: Option<String>,
+    pub scope: Option<String>,
+}
+
+/// An external plugin: a program that receives hook events as JSON on stdin
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PluginConfig {
+    pub name: String,
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// Hooks the program is called for
+    #[serde(default)]
+    pub hooks: Vec<PluginHook>,
+    /// Commands the program offers in the plugin command picker
+    #[serde(default)]
+    pub commands: Vec<PluginCommand>,
+    /// How long a call may take before the program is killed
+    #[serde(default = "default_plugin_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+fn default_plugin_timeout_secs() -> u64 {
+    10
+}
+
+impl PluginConfig {
+    pub fn timeout(&self) -> Duration {
+        Duration::from_secs(self.timeout_secs.max(1))
+    }
+}
+
+/// Hooks an external plugin can be called for; loaded specs are only seen by compiled-in plugins
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PluginHook {
+    /// May change or cancel the request
+    Request,
+    /// Observes the response
+    Response,
+}
+
+/// A command offered by a plugin
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PluginCommand {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+}
+
+/// Headers sent when downloading specs whose URL is under `url_prefix`
+///
+/// The scheme, host and port must match, and the path lie under its path.
+///
+/// Only the spec download uses them, never the API requests. Values may
+/// reference environment variables as `${NAME}`, keeping secrets out of the file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpecProfileConfig {
+    pub name: String,
+    pub url_prefix: String,
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+}
+
+/// Rewrites the spec's paths for requests to an environment
+///
+/// Gateways often expose a service under another path than its spec declares:
+/// `strip_prefix = "/internal"` and `add_prefix = "/api/v2"` turn `/internal/users`
+/// into `/api/v2/users`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PathRewriteConfig {
+    /// Applies to requests to this scheme, host and port, under its path
+    ///
+    /// The first matching rule wins.
+    pub base_url: String,
+    pub strip_prefix: Option<String>,
+    pub add_prefix: Option<String>,
+}
+
+impl PathRewriteConfig {
+    /// The path as the environment exposes it
+    pub fn apply(&self, path: &str) -> String {
+        let path = match &self.strip_prefix {
+            Some(prefix) => path.strip_prefix(prefix.as_str()).unwrap_or(path),
+            None => path,
+        };
+        match &self.add_prefix {
+            Some(prefix) => format!("{}{path}", prefix.trim_end_matches('/')),
+            None => path.to_string(),
+        }
+    }
+}
+
+/// Parameters and headers shared by many endpoints, e.g. a tenant id or paging
+///
+/// Merged into requests as they are sent: `params` fill the endpoint's path and
+/// query parameters of that name left empty, `headers` are added. `endpoints`
+/// picks where the template applies, as `tag:Orders`, `GET /users/{id}` or a
+/// path ending in `*` for a prefix; every endpoint when empty. Values may
+/// reference environment variables as `${NAME}`. Later templates win.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RequestTemplateConfig {
+    pub name: String,
+    #[serde(default)]
+    pub endpoints: Vec<String>,
+    #[serde(default)]
+    pub params: BTreeMap<String, String>,
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+}
+
+impl RequestTemplateConfig {
+    /// Whether requests to the endpoint get the template
+    pub fn applies_to(&self, endpoint: &ApiEndpoint) -> bool {
+        self.endpoints.is_empty()
+            || self
+                .endpoints
+                .iter()
+                .any(|pattern| endpoint_matches(pattern.trim(), endpoint))
+    }
+
+    /// Parameter values with environment variables expanded
+    pub fn param_values(&self) -> impl Iterator<Item = (&String, String)> {
+        expand_values(&self.params)
+    }
+
+    /// Header values with environment variables expanded
+    pub fn header_values(&self) -> impl Iterator<Item = (&String, String)> {
+        expand_values(&self.headers)
+    }
+}
+
+fn expand_values(values: &BTreeMap<String, String>) -> impl Iterator<Item = (&String, String)> {
+    values
+        .iter()
+        .map(|(name, value)| (name, expand_env(value, |var| std::env::var(var).ok())))
+}
+
+/// Whether an endpoint matches `tag:NAME`, `METHOD /path`, or a path, with a trailing `*` for a prefix
+fn endpoint_matches(pattern: &str, endpoint: &ApiEndpoint) -> bool {
+    if let Some(tag) = pattern.strip_prefix("tag:") {
+        return endpoint.tags.iter().any(|t| t == tag.trim());
+    }
+    let (method, path) = match pattern.split_once(char::is_whitespace) {
+        Some((method, path)) => (Some(method), path.trim()),
+        None => (None, pattern),
+    };
+    let path_matches = match path.strip_suffix('*') {
+        Some(prefix) => endpoint.path.starts_with(prefix),
+        None => endpoint.path == path,
+    };
+    path_matches && method.is_none_or(|method| endpoint.method.eq_ignore_ascii_case(method))
+}
+
+/// A named set of service specs listed as one
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AggregateConfig {
+    pub name: String,
+    pub services: Vec<ServiceConfig>,
+}
+
+/// One service of an aggregate
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServiceConfig {
+    /// Prefixes the service's groups, e.g. `orders / Users`
+    pub name: String,
+    pub swagger_url: String,
+    /// Where the service's requests go; defaults to the first server in its spec
+    pub base_url: Option<String>,
+}
+
+/// A spec registered for the spec switcher, with where its requests go and their token
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SpecConfig {
+    pub name: String,
+    /// Spec URL or file, or `aggregate:<name>`
+    pub swagger_url: String,
+    /// Where the spec's requests go; defaults to the first server in the spec
+    pub base_url: Option<String>,
+    /// Token set when the spec is opened, may reference environment variables as `${NAME}`
+    pub token: Option<String>,
+}
+
+impl SpecConfig {
+    /// The token with environment variables expanded; `None` when it expands to nothing
+    pub fn token_value(&self) -> Option<String> {
+        self.token
+            .as_deref()
+            .map(|token| expand_env(token, |var| std::env::var(var).ok()))
+            .filter(|token| !token.is_empty())
+    }
+}
+
+/// Prefix of spec URLs naming an aggregate from the config
+pub const AGGREGATE_PREFIX: &str = "aggregate:";
+
+/// The aggregate name of a spec URL like `aggregate:shop`
+pub fn aggregate_name(spec_url: &str) -> Option<&str> {
+    spec_url.strip_prefix(AGGREGATE_PREFIX)
+}
+
+/// The file a spec URL names: a `file://` URL, a path starting with `/`, `.` or `~`,
+/// or any other existing file, e.g. `openapi.yaml` in the current directory
+pub fn spec_file_path(spec_url: &str) -> Option<PathBuf> {
+    if let Some(path) = spec_url.strip_prefix("file://") {
+        return Some(PathBuf::from(path));
+    }
+    if spec_url.contains("://") || aggregate_name(spec_url).is_some() {
+        return None;
+    }
+    if let Some(rest) = spec_url.strip_prefix("~/") {
+        return dirs::home_dir().map(|home| home.join(rest));
+    }
+    let path = PathBuf::from(spec_url);
+    (spec_url.starts_with(['/', '.']) || path.is_file()).then_some(path)
+}
+
+/// The spec URL to remember: files by their absolute path, so they load from any directory
+pub fn normalize_spec_url(spec_url: &str) -> String {
+    spec_file_path(spec_url)
+        .and_then(|path| path.canonicalize().ok())
+        .map_or_else(|| spec_url.to_string(), |path| path.display().to_string())
+}
+
 impl Default for Config {
     fn default() -> Self {
         Self {
@@ -22,13 +538,28 @@ impl Default for Config {
                 swagger_url: None,
                 base_url: None,
             },
+            history: HistoryConfig::default(),
+            grouping: GroupingConfig::default(),
+            auth: AuthConfig::default(),
+            layout: LayoutConfig::default(),
+            notifications: NotificationConfig::default(),
+            clipboard: ClipboardConfig::default(),
+            ui: UiConfig::default(),
+            requests: RequestsConfig::default(),
+            watch: WatchConfig::default(),
+            plugins: Vec::new(),
+            spec_profiles: Vec::new(),
+            aggregates: Vec::new(),
+            specs: Vec::new(),
+            path_rewrites: Vec::new(),
+            templates: Vec::new(),
         }
     }
 }
 
 impl Config {
-    /// Get the config file path
-    pub fn config_path() -> Result<PathBuf> {
+    /// Get the app directory (~/.config/lazy-swagger-tui), creating it if needed
+    pub fn config_dir() -> Result<PathBuf> {
         // Use ~/.config instead of platform-specific directory
         let home_dir = dirs::home_dir()
             .ok_or_else(|| color_eyre::eyre::eyre!("Could not find home directory"))?;
@@ -41,7 +572,12 @@ impl Config {
             fs::create_dir_all(&app_dir)?;
         }
 
-        Ok(app_dir.join("config.toml"))
+        Ok(app_dir)
+    }
+
+    /// Get the config file path
+    pub fn config_path() -> Result<PathBuf> {
+        Ok(Self::config_dir()?.join("config.toml"))
     }
 
     /// Load config from file, or return default if file doesn't exist
@@ -73,9 +609,102 @@ impl Config {
         // Use provided base_url as-is (no auto-extraction)
         self.server.base_url = base_url;
 
+        // Remember both URLs for completion in the URL modal
+        if let Some(url) = &self.server.swagger_url {
+            push_history(&mut self.history.swagger_urls, url, MAX_URL_HISTORY);
+        }
+        if let Some(url) = &self.server.base_url {
+            push_history(&mut self.history.base_urls, url, MAX_URL_HISTORY);
+        }
+
         self.save()?;
         Ok(())
     }
+
+    /// Switch the base URL of the current spec, then save
+    pub fn set_base_url(&mut self, base_url: String) -> Result<()> {
+        push_history(&mut self.history.base_urls, &base_url, MAX_URL_HISTORY);
+        self.server.base_url = Some(base_url);
+        self.save()
+    }
+
+    /// Headers for downloading the spec at `url`, from the first matching profile
+    pub fn spec_headers(&self, url: &str) -> Vec<(String, String)> {
+        self.spec_profiles
+            .iter()
+            .find(|profile| url_under(&profile.url_prefix, url))
+            .map(|profile| {
+                expand_values(&profile.headers)
+                    .map(|(name, value)| (name.clone(), value))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// The configured aggregate a spec URL like `aggregate:shop` refers to
+    pub fn aggregate(&self, spec_url: &str) -> Option<&AggregateConfig> {
+        let name = aggregate_name(spec_url)?;
+        self.aggregates
+            .iter()
+            .find(|aggregate| aggregate.name == name)
+    }
+
+    /// Replace the remembered parameter values, then save
+    pub fn set_param_history(&mut self, param_values: HashMap<String, Vec<String>>) -> Result<()> {
+        self.history.param_values = param_values;
+        self.save()
+    }
+}
+
+/// Move (or insert) a value to the front of a history list
+pub fn push_history(history: &mut Vec<String>, value: &str, limit: usize) {
+    history.retain(|existing| existing != value);
+    history.insert(0, value.to_string());
+    history.truncate(limit);
+}
+
+/// Find the most recent history entry that extends the typed input
+///
+/// Returns only the missing suffix, e.g. input "http://lo" with history entry
+/// "http://localhost:5000" yields "calhost:5000".
+pub fn complete_from_history<'a>(input: &str, history: &'a [String]) -> Option<&'a str> {
+    if input.is_empty() {
+        return None;
+    }
+
+    history
+        .iter()
+        .find(|url| url.len() > input.len() && url.starts_with(input))
+        .map(|url| &url[input.len()..])
+}
+
+/// Replace `${NAME}` references with the looked up value, or nothing when unset
+fn expand_env(text: &str, lookup: impl Fn(&str) -> Option<String>) -> String {
+    let mut expanded = String::new();
+    let mut rest = text;
+    while let Some(start) = rest.find("${") {
+        let Some(len) = rest[start + 2..].find('}') else {
+            break;
+        };
+        expanded.push_str(&rest[..start]);
+        expanded.push_str(&lookup(&rest[start + 2..start + 2 + len]).unwrap_or_default());
+        rest = &rest[start + 3 + len..];
+    }
+    expanded.push_str(rest);
+    expanded
+}
+
+/// Validate a spec URL, which may also name an aggregate
+pub fn validate_spec_url(url: &str) -> Result<(), String> {
+    match aggregate_name(url) {
+        Some("") => Err("Aggregate name cannot be empty".to_string()),
+        Some(_) => Ok(()),
+        None => match spec_file_path(url) {
+            Some(path) if path.is_file() => Ok(()),
+            Some(path) => Err(format!("No such file: {}", path.display())),
+            None => validate_url(url),
+        },
+    }
 }
 
 /// Simple URL validation
@@ -100,6 +729,185 @@ pub fn validate_url(url: &str) -> Result<(), String> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_push_history_dedupes_and_orders() {
+        let mut history = vec!["http://a".to_string(), "http://b".to_string()];
+        push_history(&mut history, "http://b", MAX_URL_HISTORY);
+        assert_eq!(history, vec!["http://b", "http://a"]);
+
+        for i in 0..30 {
+            push_history(&mut history, &format!("http://host{i}"), MAX_URL_HISTORY);
+        }
+        assert_eq!(history.len(), MAX_URL_HISTORY);
+        assert_eq!(history[0], "http://host29");
+    }
+
+    #[test]
+    fn test_complete_from_history() {
+        let history = vec![
+            "http://localhost:5000/swagger/v1/swagger.json".to_string(),
+            "http://localhost:8080".to_string(),
+        ];
+
+        assert_eq!(
+            complete_from_history("http://localhost:8", &history),
+            Some("080")
+        );
+        assert_eq!(
+            complete_from_history("http://lo", &history),
+            Some("calhost:5000/swagger/v1/swagger.json")
+        );
+        // Exact matches and empty input have nothing to complete
+        assert_eq!(
+            complete_from_history("http://localhost:8080", &history),
+            None
+        );
+        assert_eq!(complete_from_history("", &history), None);
+        assert_eq!(complete_from_history("https://", &history), None);
+    }
+
+    #[test]
+    fn test_spec_headers() {
+        let config: Config = toml::from_str(
+            r#"
+            [server]
+
+            [[spec_profiles]]
+            name = "registry"
+            url_prefix = "https://registry.example.com/"
+            headers = { "X-Api-Key" = "key-${REGISTRY_USER}-${MISSING}", Accept = "application/json" }
+            "#,
+        )
+        .unwrap();
+
+        assert!(config
+            .spec_headers("http://localhost:5000/swagger.json")
+            .is_empty());
+        let headers = config.spec_headers("https://registry.example.com/apis/orders.json");
+        assert_eq!(headers.len(), 2);
+        // Look-alike hosts and other ports don't get the registry's secrets
+        assert!(config
+            .spec_headers("https://registry.example.com.evil.net/apis/orders.json")
+            .is_empty());
+        assert!(config
+            .spec_headers("https://registry.example.com:8443/apis/orders.json")
+            .is_empty());
+        assert_eq!(
+            headers[0],
+            ("Accept".to_string(), "application/json".to_string())
+        );
+
+        let lookup = |var: &str| (var == "USER").then(|| "ann".to_string());
+        assert_eq!(expand_env("key-${USER}-${MISSING}", lookup), "key-ann-");
+        assert_eq!(expand_env("${USER", lookup), "${USER");
+    }
+
+    #[test]
+    fn test_aggregate_lookup() {
+        let config: Config = toml::from_str(
+            r#"
+            [server]
+
+            [[aggregates]]
+            name = "shop"
+
+            [[aggregates.services]]
+            name = "orders"
+            swagger_url = "http://localhost:8081/openapi.json"
+            base_url = "http://localhost:8081"
+
+            [[aggregates.services]]
+            name = "users"
+            swagger_url = "http://localhost:8082/openapi.json"
+            "#,
+        )
+        .unwrap();
+
+        let shop = config.aggregate("aggregate:shop").unwrap();
+        assert_eq!(shop.services.len(), 2);
+        assert_eq!(shop.services[1].base_url, None);
+        assert!(config.aggregate("aggregate:other").is_none());
+        assert!(config
+            .aggregate("http://localhost:8081/openapi.json")
+            .is_none());
+
+        assert!(validate_spec_url("aggregate:shop").is_ok());
+        assert!(validate_spec_url("aggregate:").is_err());
+        assert!(validate_spec_url("shop").is_err());
+    }
+
+    #[test]
+    fn test_watch_interval() {
+        let config: Config = toml::from_str("[server]\n[watch]\nenabled = true\n").unwrap();
+        assert!(config.watch.enabled);
+        assert_eq!(config.watch.interval(), Duration::from_secs(5));
+
+        let config: Config = toml::from_str("[server]\n[watch]\ninterval_secs = 0\n").unwrap();
+        assert!(!config.watch.enabled);
+        assert_eq!(config.watch.interval(), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_specs_list() {
+        let config: Config = toml::from_str(
+            r#"
+            [server]
+
+            [[specs]]
+            name = "orders"
+            swagger_url = "http://localhost:8081/openapi.json"
+            base_url = "http://localhost:8081"
+            token = "secret"
+
+            [[specs]]
+            name = "users"
+            swagger_url = "http://localhost:8082/openapi.json"
+            token = "${LAZY_SWAGGER_TUI_UNSET_TOKEN}"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.specs.len(), 2);
+        assert_eq!(config.specs[0].token_value().as_deref(), Some("secret"));
+        assert_eq!(config.specs[1].base_url, None);
+        assert_eq!(config.specs[1].token_value(), None);
+        assert!(toml::to_string(&Config::default())
+            .unwrap()
+            .find("specs")
+            .is_none());
+    }
+
+    #[test]
+    fn test_spec_file_path() {
+        let file = std::env::temp_dir().join("lazy-swagger-tui-spec-file-test.yaml");
+        fs::write(&file, "openapi: 3.0.0").unwrap();
+        let path = file.display().to_string();
+
+        assert_eq!(spec_file_path(&path), Some(file.clone()));
+        assert_eq!(
+            spec_file_path(&format!("file://{path}")),
+            Some(file.clone())
+        );
+        assert_eq!(
+            spec_file_path("./openapi.json"),
+            Some(PathBuf::from("./openapi.json"))
+        );
+        assert_eq!(spec_file_path("http://localhost/openapi.json"), None);
+        assert_eq!(spec_file_path("aggregate:shop"), None);
+        assert_eq!(spec_file_path("shop"), None);
+
+        assert!(validate_spec_url(&path).is_ok());
+        assert_eq!(
+            validate_spec_url("./missing-spec.json"),
+            Err("No such file: ./missing-spec.json".to_string())
+        );
+        assert_eq!(
+            normalize_spec_url("http://x/openapi.json"),
+            "http://x/openapi.json"
+        );
+        fs::remove_file(file).unwrap();
+    }
+
     #[test]
     fn test_validate_url_valid_http() {
         assert!(validate_url("http://localhost:5000").is_ok());
@@ -0,0 +1,286 @@
+//! Content-type aware formatting of response bodies
+//!
+//! The `Content-Type` header decides how a body is shown: JSON is
+//! pretty-printed (and highlighted when drawn), XML is indented, text is
+//! shown as-is and binary bodies become a hex dump that can be saved to a file.
+//! Bodies without a usable header are sniffed.
+
+use crate::types::ApiResponse;
+use crate::utils::{export_file_name, try_format_json};
+
+/// Bytes per hex dump line
+const HEX_DUMP_WIDTH: usize = 16;
+
+/// How a response body is rendered
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum BodyKind {
+    Json,
+    Xml,
+    #[default]
+    Text,
+    Binary,
+}
+
+impl BodyKind {
+    /// Pick the kind from the Content-Type header, sniffing the body when it doesn't say
+    pub fn detect(response: &ApiResponse) -> Self {
+        let content_type = response
+            .headers
+            .get("content-type")
+            .and_then(|value| value.split(';').next())
+            .map(|value| value.trim().to_lowercase())
+            .unwrap_or_default();
+
+        match content_type.as_str() {
+            "application/json" => return Self::Json,
+            t if t.ends_with("+json") => return Self::Json,
+            t if t.ends_with("/xml") || t.ends_with("+xml") => return Self::Xml,
+            t if t.starts_with("text/") => return Self::Text,
+            t if t.starts_with("image/")
+                || t.starts_with("audio/")
+                || t.starts_with("video/")
+                || t.starts_with("font/")
+                || matches!(
+                    t,
+                    "application/octet-stream"
+                        | "application/pdf"
+                        | "application/zip"
+                        | "application/gzip"
+                        | "application/x-protobuf"
+                ) =>
+            {
+                return Self::Binary
+            }
+            _ => {}
+        }
+
+        if std::str::from_utf8(response.body.bytes()).is_err() || response.body.bytes().contains(&0)
+        {
+            Self::Binary
+        } else if serde_json::from_str::<serde_json::Value>(&response.body).is_ok() {
+            Self::Json
+        } else if response.body.trim_start().starts_with("<?xml") {
+            Self::Xml
+        } else {
+            Self::Text
+        }
+    }
+
+    /// Short name shown next to the status
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Json => "JSON",
+            Self::Xml => "XML",
+            Self::Text => "text",
+            Self::Binary => "binary",
+        }
+    }
+
+    /// Body lines as displayed for this kind
+    pub fn format_lines(self, response: &ApiResponse) -> Vec<String> {
+        match self {
+            Self::Json => try_format_json(&response.body)
+                .lines()
+                .map(str::to_string)
+                .collect(),
+            Self::Xml => format_xml(&response.body),
+            Self::Text => response.body.lines().map(str::to_string).collect(),
+            Self::Binary => hex_dump(response.body.bytes()),
+        }
+    }
+}
+
+/// Indent XML one element per line, keeping text-only elements on a single line
+pub fn format_xml(body: &str) -> Vec<String> {
+    let tokens = xml_tokens(body);
+    let mut lines = Vec::new();
+    let mut depth = 0usize;
+    let mut i = 0;
+
+    while i < tokens.len() {
+        let token = tokens[i];
+        let indent = "  ".repeat(depth);
+
+        if token.starts_with("</") {
+            depth = depth.saturating_sub(1);
+            lines.push(format!("{}{token}", "  ".repeat(depth)));
+        } else if token.starts_with("<?") || token.starts_with("<!") || token.ends_with("/>") {
+            lines.push(format!("{indent}{token}"));
+        } else if token.starts_with('<') {
+            // <name>text</name> stays on one line
+            match (tokens.get(i + 1), tokens.get(i + 2)) {
+                (Some(text), Some(close)) if !text.starts_with('<') && close.starts_with("</") => {
+                    lines.push(format!("{indent}{token}{text}{close}"));
+                    i += 3;
+                    continue;
+                }
+                _ => {
+                    lines.push(format!("{indent}{token}"));
+                    depth += 1;
+                }
+            }
+        } else {
+            lines.push(format!("{indent}{token}"));
+        }
+        i += 1;
+    }
+    lines
+}
+
+/// Split XML into tags and trimmed, non-empty text runs
+fn xml_tokens(body: &str) -> Vec<&str> {
+    let mut tokens = Vec::new();
+    let mut rest = body;
+
+    while !rest.is_empty() {
+        if rest.starts_with('<') {
+            let end = if rest.starts_with("<!--") {
+                rest.find("-->").map(|i| i + 3)
+            } else {
+                tag_end(rest)
+            }
+            .unwrap_or(rest.len());
+            tokens.push(&rest[..end]);
+            rest = &rest[end..];
+        } else {
+            let end = rest.find('<').unwrap_or(rest.len());
+            let text = rest[..end].trim();
+            if !text.is_empty() {
+                tokens.push(text);
+            }
+            rest = &rest[end..];
+        }
+    }
+    tokens
+}
+
+/// Index just past the `>` closing a tag, ignoring `>` inside quoted attribute values
+fn tag_end(tag: &str) -> Option<usize> {
+    let mut quote = None;
+    for (i, c) in tag.char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), _) if c == q => quote = None,
+            (None, '>') => return Some(i + 1),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Classic offset / hex / ASCII dump
+pub fn hex_dump(bytes: &[u8]) -> Vec<String> {
+    bytes
+        .chunks(HEX_DUMP_WIDTH)
+        .enumerate()
+        .map(|(index, chunk)| {
+            let hex: Vec<String> = chunk.iter().map(|b| format!("{b:02x}")).collect();
+            let ascii: String = chunk
+                .iter()
+                .map(|&b| {
+                    if b.is_ascii_graphic() || b == b' ' {
+                        b as char
+                    } else {
+                        '.'
+                    }
+                })
+                .collect();
+            format!(
+                "{:08x}  {:<width$}  |{ascii}|",
+                index * HEX_DUMP_WIDTH,
+                hex.join(" "),
+                width = HEX_DUMP_WIDTH * 3 - 1
+            )
+        })
+        .collect()
+}
+
+/// Default file name for a saved body, from the endpoint path and content type
+pub fn body_file_name(endpoint_path: &str, response: &ApiResponse) -> String {
+    let extension = match response
+        .headers
+        .get("content-type")
+        .and_then(|value| value.split(';').next())
+        .map(str::trim)
+    {
+        Some("image/png") => "png",
+        Some("image/jpeg") => "jpg",
+        Some("image/gif") => "gif",
+        Some("application/pdf") => "pdf",
+        Some("application/zip") => "zip",
+        Some("application/gzip") => "gz",
+        _ => "bin",
+    };
+    export_file_name(endpoint_path, extension)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(content_type: &str, bytes: &[u8]) -> ApiResponse {
+        let mut response = ApiResponse::error(String::new());
+        response.is_error = false;
+        if !content_type.is_empty() {
+            response
+                .headers
+                .insert("content-type".to_string(), content_type.to_string());
+        }
+        response.body = bytes.to_vec().into();
+        response
+    }
+
+    #[test]
+    fn test_detect_body_kind() {
+        assert_eq!(
+            BodyKind::detect(&response("application/vnd.api+json; charset=utf-8", b"{}")),
+            BodyKind::Json
+        );
+        assert_eq!(
+            BodyKind::detect(&response("text/xml", b"<a/>")),
+            BodyKind::Xml
+        );
+        assert_eq!(
+            BodyKind::detect(&response("text/plain", b"{}")),
+            BodyKind::Text
+        );
+        assert_eq!(
+            BodyKind::detect(&response("image/png", b"PNG")),
+            BodyKind::Binary
+        );
+        assert_eq!(BodyKind::detect(&response("", b"[1, 2]")), BodyKind::Json);
+        assert_eq!(
+            BodyKind::detect(&response("", &[0xff, 0x00, 0x10])),
+            BodyKind::Binary
+        );
+    }
+
+    #[test]
+    fn test_format_xml() {
+        let xml = r#"<?xml version="1.0"?><users><user id="1" note="a>b"><name>Ada</name><admin/></user><!-- end --></users>"#;
+        assert_eq!(
+            format_xml(xml),
+            vec![
+                r#"<?xml version="1.0"?>"#,
+                "<users>",
+                r#"  <user id="1" note="a>b">"#,
+                "    <name>Ada</name>",
+                "    <admin/>",
+                "  </user>",
+                "  <!-- end -->",
+                "</users>",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_hex_dump() {
+        assert_eq!(
+            hex_dump(b"\x89PNG\r\n\x1a\n"),
+            vec![format!(
+                "00000000  {:<47}  |.PNG....|",
+                "89 50 4e 47 0d 0a 1a 0a"
+            )]
+        );
+    }
+}
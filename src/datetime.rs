@@ -0,0 +1,122 @@
+//! Helpers for `date` / `date-time` formatted parameters
+//!
+//! Values follow the OpenAPI formats: `date` is `YYYY-MM-DD` and `date-time`
+//! is RFC 3339 (e.g. `2025-01-31T09:30:00Z`).
+
+use chrono::{DateTime, Duration, FixedOffset, Local, NaiveDate, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+
+const DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// Date-like formats a parameter schema can declare
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DateFormat {
+    Date,
+    DateTime,
+}
+
+impl DateFormat {
+    /// Map an OpenAPI `format` string to a date format
+    pub fn from_schema_format(format: &str) -> Option<Self> {
+        match format {
+            "date" => Some(Self::Date),
+            "date-time" => Some(Self::DateTime),
+            _ => None,
+        }
+    }
+}
+
+/// Edits the date helper can apply to a value
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum DateAdjust {
+    Now,
+    Days(i64),
+    Hours(i64),
+}
+
+/// Current date/time rendered in the given format
+pub fn now(format: DateFormat) -> String {
+    match format {
+        DateFormat::Date => Local::now().date_naive().format(DATE_FORMAT).to_string(),
+        DateFormat::DateTime => Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true),
+    }
+}
+
+/// Apply an adjustment to a value, starting from "now" when the value is empty or invalid
+pub fn adjust(value: &str, format: DateFormat, adjust: DateAdjust) -> String {
+    let delta = match adjust {
+        DateAdjust::Now => return now(format),
+        DateAdjust::Days(days) => Duration::days(days),
+        DateAdjust::Hours(hours) => Duration::hours(hours),
+    };
+
+    match format {
+        DateFormat::Date => {
+            let date = NaiveDate::parse_from_str(value, DATE_FORMAT)
+                .unwrap_or_else(|_| Local::now().date_naive());
+            // Dates have no time of day, so hour steps are ignored
+            let date = if matches!(adjust, DateAdjust::Hours(_)) {
+                date
+            } else {
+                date + delta
+            };
+            date.format(DATE_FORMAT).to_string()
+        }
+        DateFormat::DateTime => {
+            let datetime = DateTime::parse_from_rfc3339(value)
+                .unwrap_or_else(|_| Utc::now().with_timezone(&FixedOffset::east_opt(0).unwrap()));
+            (datetime + delta).to_rfc3339_opts(SecondsFormat::Secs, true)
+        }
+    }
+}
+
+/// Check a non-empty value against the format
+pub fn validate(value: &str, format: DateFormat) -> Result<(), String> {
+    match format {
+        DateFormat::Date => NaiveDate::parse_from_str(value, DATE_FORMAT)
+            .map(|_| ())
+            .map_err(|_| "expected YYYY-MM-DD".to_string()),
+        DateFormat::DateTime => DateTime::parse_from_rfc3339(value)
+            .map(|_| ())
+            .map_err(|_| "expected RFC 3339, e.g. 2025-01-31T09:30:00Z".to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_adjust_date_and_datetime() {
+        assert_eq!(
+            adjust("2024-02-28", DateFormat::Date, DateAdjust::Days(1)),
+            "2024-02-29"
+        );
+        assert_eq!(
+            adjust("2024-03-01", DateFormat::Date, DateAdjust::Hours(5)),
+            "2024-03-01"
+        );
+        assert_eq!(
+            adjust(
+                "2024-12-31T23:30:00+02:00",
+                DateFormat::DateTime,
+                DateAdjust::Hours(1)
+            ),
+            "2025-01-01T00:30:00+02:00"
+        );
+        assert!(validate(
+            &adjust("", DateFormat::DateTime, DateAdjust::Days(-1)),
+            DateFormat::DateTime
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(validate("2024-01-31", DateFormat::Date).is_ok());
+        assert!(validate("31/01/2024", DateFormat::Date).is_err());
+        assert!(validate("2024-01-31T10:00:00Z", DateFormat::DateTime).is_ok());
+        assert!(validate("2024-01-31 10:00", DateFormat::DateTime).is_err());
+        assert!(validate(&now(DateFormat::Date), DateFormat::Date).is_ok());
+    }
+}
@@ -0,0 +1,301 @@
+//! Finding spec URLs of APIs running in Docker or Kubernetes
+//!
+//! Running containers and services are listed with the `docker` and `kubectl`
+//! command line tools, when installed:
+//!
+//! - A Docker container labelled `lazy-swagger-tui.spec` offers that URL, or
+//!   that path on its first published port. Other containers' published ports
+//!   are probed for a spec at the usual paths.
+//! - A Kubernetes service annotated with `lazy-swagger-tui/spec` offers that
+//!   URL; with `lazy-swagger-tui/spec-path` (and optionally
+//!   `lazy-swagger-tui/port`) the path on the service's cluster address.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::time::Duration;
+use tokio::process::Command;
+use url::Url;
+
+/// Docker label holding a spec URL, or a path on the container's published port
+const DOCKER_SPEC_LABEL: &str = "lazy-swagger-tui.spec";
+
+/// Kubernetes service annotations
+const K8S_SPEC_ANNOTATION: &str = "lazy-swagger-tui/spec";
+const K8S_SPEC_PATH_ANNOTATION: &str = "lazy-swagger-tui/spec-path";
+const K8S_PORT_ANNOTATION: &str = "lazy-swagger-tui/port";
+
+/// Where frameworks commonly serve their spec, tried on unlabelled ports
+const COMMON_SPEC_PATHS: &[&str] = &[
+    "/openapi.json",
+    "/swagger.json",
+    "/v3/api-docs",
+    "/swagger/v1/swagger.json",
+    "/api-docs",
+];
+
+/// How long `docker`/`kubectl` may take before their output is ignored
+const COMMAND_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How long a probed port may take to answer
+const PROBE_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// A spec URL found for a running API
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DiscoveredSpec {
+    /// Where it was found, e.g. `docker: orders-api`
+    pub source: String,
+    pub spec_url: String,
+    /// Scheme, host and port of the spec URL
+    pub base_url: Option<String>,
+}
+
+impl DiscoveredSpec {
+    fn new(source: String, spec_url: String) -> Self {
+        let base_url = Url::parse(&spec_url)
+            .ok()
+            .map(|url| url.origin().ascii_serialization())
+            .filter(|origin| origin != "null");
+        Self {
+            source,
+            spec_url,
+            base_url,
+        }
+    }
+}
+
+/// A container found by `docker ps`: a labelled spec, or a port to probe
+#[derive(Debug, Clone, PartialEq)]
+enum DockerCandidate {
+    Spec(DiscoveredSpec),
+    Port { source: String, port: u16 },
+}
+
+/// Look for specs in Docker containers and Kubernetes services
+pub async fn discover() -> Vec<DiscoveredSpec> {
+    let mut specs = Vec::new();
+
+    if let Some(output) = run("docker", &["ps", "--format", "{{json .}}"]).await {
+        let mut probes = Vec::new();
+        for candidate in docker_candidates(&output) {
+            match candidate {
+                DockerCandidate::Spec(spec) => specs.push(spec),
+                DockerCandidate::Port { source, port } => {
+                    probes.push(tokio::spawn(probe_port(source, port)));
+                }
+            }
+        }
+        for probe in probes {
+            if let Ok(Some(spec)) = probe.await {
+                specs.push(spec);
+            }
+        }
+    }
+
+    if let Some(output) = run(
+        "kubectl",
+        &["get", "services", "--all-namespaces", "-o", "json"],
+    )
+    .await
+    {
+        specs.extend(kubernetes_specs(&output));
+    }
+    specs
+}
+
+/// Stdout of a command that ran successfully in time; `None` when it is not installed
+async fn run(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).kill_on_drop(true).output();
+    let output = tokio::time::timeout(COMMAND_TIMEOUT, output)
+        .await
+        .ok()?
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// The first common path on the port that serves a Swagger/OpenAPI document
+async fn probe_port(source: String, port: u16) -> Option<DiscoveredSpec> {
+    let client = reqwest::Client::builder()
+        .timeout(PROBE_TIMEOUT)
+        .build()
+        .ok()?;
+    for path in COMMON_SPEC_PATHS {
+        let url = format!("http://localhost:{port}{path}");
+        let Ok(response) = client.get(&url).send().await else {
+            // Nothing listening, or not HTTP: no other path will do better
+            return None;
+        };
+        if !response.status().is_success() {
+            continue;
+        }
+        if let Ok(document) = response.json::<Value>().await {
+            if document.get("openapi").is_some() || document.get("swagger").is_some() {
+                return Some(DiscoveredSpec::new(source, url));
+            }
+        }
+    }
+    None
+}
+
+/// Containers from `docker ps --format '{{json .}}'`, one JSON object per line
+fn docker_candidates(output: &str) -> Vec<DockerCandidate> {
+    let mut candidates = Vec::new();
+    for container in output
+        .lines()
+        .filter_map(|line| serde_json::from_str::<Value>(line).ok())
+    {
+        let field = |name: &str| container.get(name).and_then(Value::as_str).unwrap_or("");
+        let source = format!("docker: {}", field("Names"));
+        let ports = published_ports(field("Ports"));
+
+        let label = field("Labels").split(',').find_map(|label| {
+            label
+                .split_once('=')
+                .filter(|(key, _)| *key == DOCKER_SPEC_LABEL)
+                .map(|(_, value)| value.to_string())
+        });
+        match label {
+            Some(url) if url.contains("://") => {
+                candidates.push(DockerCandidate::Spec(DiscoveredSpec::new(source, url)));
+            }
+            Some(path) => {
+                if let Some(port) = ports.first() {
+                    let url = format!("http://localhost:{port}{path}");
+                    candidates.push(DockerCandidate::Spec(DiscoveredSpec::new(source, url)));
+                }
+            }
+            None => candidates.extend(ports.into_iter().map(|port| DockerCandidate::Port {
+                source: source.clone(),
+                port,
+            })),
+        }
+    }
+    candidates
+}
+
+/// Host ports of TCP mappings like `0.0.0.0:8080->80/tcp, :::8080->80/tcp`, without duplicates
+fn published_ports(ports: &str) -> Vec<u16> {
+    let mut published: Vec<u16> = Vec::new();
+    for mapping in ports.split(',').map(str::trim) {
+        let Some((host, container)) = mapping.split_once("->") else {
+            continue;
+        };
+        if !container.ends_with("/tcp") {
+            continue;
+        }
+        let port = host.rsplit(':').next().and_then(|port| port.parse().ok());
+        if let Some(port) = port.filter(|port| !published.contains(port)) {
+            published.push(port);
+        }
+    }
+    published
+}
+
+/// Annotated services from `kubectl get services --all-namespaces -o json`
+fn kubernetes_specs(output: &str) -> Vec<DiscoveredSpec> {
+    let Ok(list) = serde_json::from_str::<Value>(output) else {
+        return Vec::new();
+    };
+    let services = list.get("items").and_then(Value::as_array);
+
+    services
+        .into_iter()
+        .flatten()
+        .filter_map(|service| {
+            let metadata = service.get("metadata")?;
+            let name = metadata.get("name")?.as_str()?;
+            let namespace = metadata
+                .get("namespace")
+                .and_then(Value::as_str)
+                .unwrap_or("default");
+            let annotations = metadata.get("annotations")?;
+            let annotation = |key: &str| annotations.get(key).and_then(Value::as_str);
+            let source = format!("k8s: {namespace}/{name}");
+
+            if let Some(url) = annotation(K8S_SPEC_ANNOTATION) {
+                return Some(DiscoveredSpec::new(source, url.to_string()));
+            }
+            let path = annotation(K8S_SPEC_PATH_ANNOTATION)?;
+            let port = annotation(K8S_PORT_ANNOTATION)
+                .and_then(|port| port.parse::<u64>().ok())
+                .or_else(|| service.pointer("/spec/ports/0/port")?.as_u64())?;
+            let url = format!("http://{name}.{namespace}.svc:{port}{path}");
+            Some(DiscoveredSpec::new(source, url))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_docker_candidates() {
+        let output = [
+            r#"{"Names":"orders-api","Labels":"com.docker.compose.service=orders,lazy-swagger-tui.spec=/docs/openapi.json","Ports":"0.0.0.0:8081->80/tcp, :::8081->80/tcp"}"#,
+            r#"{"Names":"users-api","Labels":"","Ports":"0.0.0.0:8082->8080/tcp, 0.0.0.0:9000->9000/udp"}"#,
+            r#"{"Names":"postgres","Labels":"","Ports":"5432/tcp"}"#,
+        ]
+        .join("\n");
+
+        assert_eq!(
+            docker_candidates(&output),
+            vec![
+                DockerCandidate::Spec(DiscoveredSpec {
+                    source: "docker: orders-api".to_string(),
+                    spec_url: "http://localhost:8081/docs/openapi.json".to_string(),
+                    base_url: Some("http://localhost:8081".to_string()),
+                }),
+                DockerCandidate::Port {
+                    source: "docker: users-api".to_string(),
+                    port: 8082,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_kubernetes_specs() {
+        let output = serde_json::json!({
+            "items": [
+                {
+                    "metadata": {
+                        "name": "billing",
+                        "namespace": "shop",
+                        "annotations": { "lazy-swagger-tui/spec-path": "/v3/api-docs" }
+                    },
+                    "spec": { "ports": [{ "port": 8080 }] }
+                },
+                {
+                    "metadata": {
+                        "name": "gateway",
+                        "namespace": "shop",
+                        "annotations": { "lazy-swagger-tui/spec": "https://api.example.com/openapi.json" }
+                    }
+                },
+                { "metadata": { "name": "redis", "namespace": "shop" } }
+            ]
+        })
+        .to_string();
+
+        let urls: Vec<(String, String)> = kubernetes_specs(&output)
+            .into_iter()
+            .map(|spec| (spec.source, spec.spec_url))
+            .collect();
+        assert_eq!(
+            urls,
+            vec![
+                (
+                    "k8s: shop/billing".to_string(),
+                    "http://billing.shop.svc:8080/v3/api-docs".to_string()
+                ),
+                (
+                    "k8s: shop/gateway".to_string(),
+                    "https://api.example.com/openapi.json".to_string()
+                ),
+            ]
+        );
+    }
+}
@@ -10,7 +10,7 @@
 //! - Bracket matching
 //! - Undo/redo functionality
 
-use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use serde_json::Value;
 
 /// A text editor for editing request bodies (primarily JSON)
@@ -37,11 +37,73 @@ pub struct BodyEditor {
 
 /// Content type for the editor (enables syntax-specific features)
 #[derive(Debug, Clone, PartialEq)]
-#[allow(dead_code)] // Some variants reserved for future use
 pub enum ContentType {
     Json,
     Xml,
     PlainText,
+    /// url-encoded form fields, edited as `name=value` lines
+    Form,
+}
+
+impl ContentType {
+    /// How a body sent as `media_type` is edited (multipart bodies are written as JSON)
+    pub fn for_media_type(media_type: &str) -> Self {
+        if media_type == "application/x-www-form-urlencoded" {
+            Self::Form
+        } else if crate::types::edited_as_json(media_type) {
+            Self::Json
+        } else if media_type.ends_with("/xml") || media_type.ends_with("+xml") {
+            Self::Xml
+        } else {
+            Self::PlainText
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Json => "JSON",
+            Self::Xml => "XML",
+            Self::PlainText => "Text",
+            Self::Form => "Form",
+        }
+    }
+}
+
+/// The `name=value` lines form mode shows for a body kept as a JSON object
+///
+/// Bodies that are not a JSON object are shown as written.
+pub fn form_lines(body: &str) -> String {
+    match serde_json::from_str::<Value>(body) {
+        Ok(Value::Object(fields)) => fields
+            .iter()
+            .map(|(name, value)| match value {
+                Value::String(text) => format!("{name}={text}"),
+                other => format!("{name}={other}"),
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+        _ => body.to_string(),
+    }
+}
+
+/// The JSON object a form body is kept as, from its `name=value` lines
+///
+/// Blank lines are skipped; a line without `=` is an error naming its line number.
+pub fn form_object(text: &str) -> Result<String, String> {
+    let mut fields = serde_json::Map::new();
+    for (index, line) in text.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let Some((name, value)) = line.split_once('=') else {
+            return Err(format!("Line {}: expected name=value", index + 1));
+        };
+        fields.insert(name.trim().to_string(), Value::String(value.to_string()));
+    }
+    if fields.is_empty() {
+        return Ok(String::new());
+    }
+    Ok(serde_json::to_string_pretty(&Value::Object(fields)).unwrap_or_default())
 }
 
 impl Default for BodyEditor {
@@ -132,14 +194,12 @@ impl BodyEditor {
         self.dirty
     }
 
-    /// Set content type (for future syntax highlighting)
-    #[allow(dead_code)] // Reserved for future syntax highlighting
+    /// Set content type (decides whether the body is validated as JSON)
     pub fn set_content_type(&mut self, content_type: ContentType) {
         self.content_type = content_type;
     }
 
     /// Get content type
-    #[allow(dead_code)] // Reserved for future syntax highlighting
     pub fn content_type(&self) -> &ContentType {
         &self.content_type
     }
@@ -408,39 +468,15 @@ impl BodyEditor {
         }
     }
 
-    /// Handle paste batching - collects multiple character events in quick succession
+    /// Insert a batch of characters that arrived in quick succession
     ///
     /// This is useful for terminal paste operations where characters arrive rapidly.
     /// Automatically normalizes smart quotes to regular quotes for JSON compatibility.
     /// Returns the number of characters inserted.
-    pub fn handle_paste_batch(&mut self, initial_char: char) -> usize {
-        let mut chars = vec![initial_char];
-
-        // Drain any immediately available character events
-        while let Ok(true) = crossterm::event::poll(std::time::Duration::from_millis(0)) {
-            if let Ok(Event::Key(next_key)) = crossterm::event::read() {
-                match next_key.code {
-                    KeyCode::Char(next_c)
-                        if !next_key.modifiers.contains(KeyModifiers::CONTROL) =>
-                    {
-                        chars.push(next_c);
-                    }
-                    _ => {
-                        // Non-character or control key, stop batching
-                        break;
-                    }
-                }
-            } else {
-                break;
-            }
-        }
-
-        let count = chars.len();
-        let batch_str: String = chars.into_iter().collect();
-
+    pub fn handle_paste_batch(&mut self, batch: &str) -> usize {
         // Use normalized insertion for paste to handle smart quotes
-        self.insert_str_normalized(&batch_str);
-        count
+        self.insert_str_normalized(batch);
+        batch.chars().count()
     }
 
     /// Move cursor up by one line
@@ -780,4 +816,25 @@ mod tests {
         let content = editor.content_with_cursor();
         assert_eq!(content, "he█llo\nworld");
     }
+
+    #[test]
+    fn test_form_fields() {
+        assert_eq!(
+            ContentType::for_media_type("application/x-www-form-urlencoded"),
+            ContentType::Form
+        );
+        assert_eq!(
+            form_lines(r#"{"grant_type": "password", "count": 3}"#),
+            "count=3\ngrant_type=password"
+        );
+
+        let body = form_object("user=ann\n\nnote=a=b\n").unwrap();
+        let fields: Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(fields, serde_json::json!({ "user": "ann", "note": "a=b" }));
+        assert_eq!(form_object("  \n").unwrap(), "");
+        assert_eq!(
+            form_object("user=ann\noops").unwrap_err(),
+            "Line 2: expected name=value"
+        );
+    }
 }
@@ -0,0 +1,276 @@
+//! Quick value generators for parameters and request bodies
+//!
+//! Generated values are inserted into the edit buffer with a single key
+//! (Ctrl+U, Ctrl+N, Ctrl+T) so common test inputs never need hand-typing.
+//! Request bodies can be filled with fake data derived from their JSON schema.
+
+use crate::datetime::{self, DateFormat};
+use crate::types::ApiParameter;
+use chrono::Utc;
+use serde_json::{json, Map, Value};
+
+/// Range used for random integers when the schema declares no bounds
+const DEFAULT_INT_RANGE: (i64, i64) = (1, 1000);
+
+/// Nesting limit for sample bodies (schemas are already `$ref`-resolved)
+const MAX_SAMPLE_DEPTH: usize = 6;
+
+const FIRST_NAMES: &[&str] = &[
+    "Alice", "Bob", "Carla", "Dmitri", "Emma", "Farid", "Grace", "Hiro", "Ines", "Jonas",
+];
+const LAST_NAMES: &[&str] = &[
+    "Smith", "Garcia", "Nakamura", "Okafor", "Novak", "Larsen", "Costa", "Schmidt",
+];
+const CITIES: &[&str] = &["Berlin", "Lisbon", "Osaka", "Toronto", "Nairobi", "Oslo"];
+const WORDS: &[&str] = &[
+    "alpha", "bravo", "delta", "echo", "lima", "nova", "orbit", "pixel", "sierra", "zen",
+];
+
+/// Kinds of values that can be generated
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Generator {
+    Uuid,
+    Int,
+    Timestamp,
+}
+
+/// Generate a value, honouring the parameter's schema where it matters
+pub fn generate(generator: Generator, param: Option<&ApiParameter>) -> String {
+    match generator {
+        Generator::Uuid => uuid_v4(),
+        Generator::Int => {
+            let (min, max) = param.map(int_range).unwrap_or(DEFAULT_INT_RANGE);
+            fastrand::i64(min..=max).to_string()
+        }
+        Generator::Timestamp => match param.and_then(|p| p.date_format()) {
+            Some(format) => datetime::now(format),
+            None => Utc::now().timestamp().to_string(),
+        },
+    }
+}
+
+/// Random version 4 UUID in its hyphenated form
+pub fn uuid_v4() -> String {
+    let mut bytes = fastrand::u128(..).to_be_bytes();
+    bytes[6] = (bytes[6] & 0x0f) | 0x40; // version 4
+    bytes[8] = (bytes[8] & 0x3f) | 0x80; // RFC 4122 variant
+
+    let hex: String = bytes.iter().map(|b| format!("{b:02x}")).collect();
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[0..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..32]
+    )
+}
+
+/// Build a JSON value matching the schema, filled with plausible fake data
+pub fn sample_from_schema(schema: &Value) -> Value {
+    sample_value(schema, None, 0)
+}
+
+fn sample_value(schema: &Value, key: Option<&str>, depth: usize) -> Value {
+    if depth > MAX_SAMPLE_DEPTH {
+        return Value::Null;
+    }
+    if let Some(example) = schema.get("example") {
+        return example.clone();
+    }
+    if let Some(Value::Array(options)) = schema.get("enum") {
+        if !options.is_empty() {
+            return options[fastrand::usize(..options.len())].clone();
+        }
+    }
+    if let Some(Value::Array(parts)) = schema.get("allOf") {
+        let mut merged = Map::new();
+        for part in parts {
+            if let Value::Object(fields) = sample_value(part, key, depth + 1) {
+                merged.extend(fields);
+            }
+        }
+        return Value::Object(merged);
+    }
+    if let Some(Value::Array(variants)) = schema.get("oneOf").or(schema.get("anyOf")) {
+        if let Some(first) = variants.first() {
+            return sample_value(first, key, depth + 1);
+        }
+    }
+
+    let schema_type = schema.get("type").and_then(Value::as_str).unwrap_or(
+        if schema.get("properties").is_some() {
+            "object"
+        } else {
+            "string"
+        },
+    );
+
+    match schema_type {
+        "object" => {
+            let properties = schema.get("properties").and_then(Value::as_object);
+            Value::Object(
+                properties
+                    .into_iter()
+                    .flatten()
+                    .map(|(name, prop)| (name.clone(), sample_value(prop, Some(name), depth + 1)))
+                    .collect(),
+            )
+        }
+        "array" => {
+            let items = schema.get("items").cloned().unwrap_or(json!({}));
+            let singular = key.map(|k| k.trim_end_matches('s'));
+            Value::Array(
+                (0..fastrand::usize(1..=2))
+                    .map(|_| sample_value(&items, singular, depth + 1))
+                    .collect(),
+            )
+        }
+        "integer" => {
+            let min = schema.get("minimum").and_then(Value::as_i64).unwrap_or(1);
+            let max = schema
+                .get("maximum")
+                .and_then(Value::as_i64)
+                .unwrap_or(min.max(0).saturating_add(100));
+            json!(fastrand::i64(min..=max.max(min)))
+        }
+        "number" => {
+            let min = schema.get("minimum").and_then(Value::as_f64).unwrap_or(0.0);
+            let max = schema
+                .get("maximum")
+                .and_then(Value::as_f64)
+                .unwrap_or(min + 100.0);
+            let value = min + fastrand::f64() * (max - min).max(0.0);
+            json!((value * 100.0).round() / 100.0)
+        }
+        "boolean" => json!(fastrand::bool()),
+        _ => json!(fake_string(
+            schema.get("format").and_then(Value::as_str),
+            key
+        )),
+    }
+}
+
+/// Pick a fake string based on the declared format, then on the property name
+fn fake_string(format: Option<&str>, key: Option<&str>) -> String {
+    let first = pick(FIRST_NAMES);
+    let last = pick(LAST_NAMES);
+
+    match format {
+        Some("email") => return format!("{}.{}@example.com", first, last).to_lowercase(),
+        Some("uuid") => return uuid_v4(),
+        Some("date") => return datetime::now(DateFormat::Date),
+        Some("date-time") => return datetime::now(DateFormat::DateTime),
+        Some("uri" | "url") => return format!("https://example.com/{}", pick(WORDS)),
+        _ => {}
+    }
+
+    let key = key
+        .unwrap_or_default()
+        .to_lowercase()
+        .replace(['_', '-'], "");
+    if key.contains("email") {
+        format!("{}.{}@example.com", first, last).to_lowercase()
+    } else if key.contains("firstname") || key == "givenname" {
+        first.to_string()
+    } else if key.contains("lastname") || key.contains("surname") || key == "familyname" {
+        last.to_string()
+    } else if key.contains("username") || key == "login" {
+        format!("{}{}", first.to_lowercase(), fastrand::u16(10..100))
+    } else if key.contains("name") {
+        format!("{first} {last}")
+    } else if key.contains("phone") {
+        format!("+1-555-01{:02}", fastrand::u8(..100))
+    } else if key.contains("city") {
+        pick(CITIES).to_string()
+    } else if key.contains("url") || key.contains("website") {
+        format!("https://example.com/{}", pick(WORDS))
+    } else if key.ends_with("id") {
+        uuid_v4()
+    } else {
+        format!("{} {}", pick(WORDS), pick(WORDS))
+    }
+}
+
+fn pick<'a>(options: &[&'a str]) -> &'a str {
+    options[fastrand::usize(..options.len())]
+}
+
+/// Integer bounds from the schema's minimum/maximum, falling back to the default range
+fn int_range(param: &ApiParameter) -> (i64, i64) {
+    let schema = param.schema.as_ref();
+    let min = schema
+        .and_then(|s| s.minimum)
+        .map(|v| v.ceil() as i64)
+        .unwrap_or(DEFAULT_INT_RANGE.0);
+    let max = schema
+        .and_then(|s| s.maximum)
+        .map(|v| v.floor() as i64)
+        .unwrap_or(min.max(0).saturating_add(DEFAULT_INT_RANGE.1));
+    (min, max.max(min))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ParameterSchema;
+
+    #[test]
+    fn test_uuid_v4_shape() {
+        let uuid = uuid_v4();
+        let parts: Vec<&str> = uuid.split('-').collect();
+        assert_eq!(
+            parts.iter().map(|p| p.len()).collect::<Vec<_>>(),
+            vec![8, 4, 4, 4, 12]
+        );
+        assert!(parts[2].starts_with('4'));
+        assert!(matches!(
+            parts[3].chars().next(),
+            Some('8' | '9' | 'a' | 'b')
+        ));
+    }
+
+    #[test]
+    fn test_sample_from_schema() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "email": { "type": "string" },
+                "age": { "type": "integer", "minimum": 18, "maximum": 18 },
+                "role": { "type": "string", "enum": ["admin"] },
+                "createdAt": { "type": "string", "format": "date-time" },
+                "tags": { "type": "array", "items": { "type": "string" } },
+                "nickname": { "type": "string", "example": "ace" }
+            }
+        });
+
+        let sample = sample_from_schema(&schema);
+        assert!(sample["email"].as_str().unwrap().ends_with("@example.com"));
+        assert_eq!(sample["age"], 18);
+        assert_eq!(sample["role"], "admin");
+        assert!(
+            datetime::validate(sample["createdAt"].as_str().unwrap(), DateFormat::DateTime).is_ok()
+        );
+        assert!(!sample["tags"].as_array().unwrap().is_empty());
+        assert_eq!(sample["nickname"], "ace");
+    }
+
+    #[test]
+    fn test_int_respects_schema_bounds() {
+        let param = ApiParameter {
+            name: "page".to_string(),
+            location: "query".to_string(),
+            schema: Some(ParameterSchema {
+                minimum: Some(5.0),
+                maximum: Some(7.0),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        for _ in 0..50 {
+            let value: i64 = generate(Generator::Int, Some(&param)).parse().unwrap();
+            assert!((5..=7).contains(&value));
+        }
+    }
+}
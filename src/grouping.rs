@@ -0,0 +1,223 @@
+//! Building endpoint groups for the grouped view
+//!
+//! Endpoints are grouped by tag or path prefix, unless a user-defined rule
+//! from the `[grouping]` config section maps their path to a group first.
+//! The same section controls group order and which groups are hidden.
+//! Callbacks and webhooks always get their own groups, listed last.
+
+use crate::config::GroupingConfig;
+use crate::types::{path_prefix_group, ApiEndpoint, GroupBy, CALLBACKS_GROUP, WEBHOOKS_GROUP};
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+
+/// Group for endpoints without tags
+const UNTAGGED_GROUP: &str = "Other";
+
+/// Compiled grouping rules
+#[derive(Debug, Clone, Default)]
+pub struct GroupingRules {
+    rules: Vec<(Regex, String)>,
+    order: Vec<String>,
+    hidden: HashSet<String>,
+}
+
+impl GroupingRules {
+    /// Compile the rules of the config, failing on the first invalid pattern
+    pub fn new(config: &GroupingConfig) -> Result<Self, regex::Error> {
+        let rules = config
+            .rules
+            .iter()
+            .map(|rule| Ok((Regex::new(&rule.pattern)?, rule.group.clone())))
+            .collect::<Result<_, regex::Error>>()?;
+
+        Ok(Self {
+            rules,
+            order: config.order.clone(),
+            hidden: config.hidden.iter().cloned().collect(),
+        })
+    }
+
+    /// Compile the rules of the config, or ignore them all when a pattern is invalid
+    ///
+    /// Returns the error to show alongside, so a typo in `config.toml` falls back
+    /// to grouping by tag or path prefix instead of keeping the app from starting.
+    pub fn load(config: &GroupingConfig) -> (Self, Option<String>) {
+        match Self::new(config) {
+            Ok(rules) => (rules, None),
+            Err(e) => {
+                let rules = Self {
+                    rules: Vec::new(),
+                    order: config.order.clone(),
+                    hidden: config.hidden.iter().cloned().collect(),
+                };
+                let error = format!("Grouping rules in config.toml ignored, invalid pattern: {e}");
+                (rules, Some(error))
+            }
+        }
+    }
+
+    /// Groups an endpoint belongs to: the first matching rule, otherwise the strategy
+    ///
+    /// Groups of merged specs are prefixed with the service, e.g. `orders / Users`.
+    pub fn groups_for(&self, endpoint: &ApiEndpoint, group_by: GroupBy) -> Vec<String> {
+        if let Some(event) = &endpoint.event {
+            return vec![event.group().to_string()];
+        }
+
+        let groups = self.own_groups(endpoint, group_by);
+        match &endpoint.service {
+            Some(service) => groups
+                .into_iter()
+                .map(|group| format!("{service} / {group}"))
+                .collect(),
+            None => groups,
+        }
+    }
+
+    fn own_groups(&self, endpoint: &ApiEndpoint, group_by: GroupBy) -> Vec<String> {
+        for (regex, group) in &self.rules {
+            if let Some(captures) = regex.captures(&endpoint.path) {
+                let mut name = String::new();
+                captures.expand(group, &mut name);
+                return vec![name];
+            }
+        }
+
+        match group_by {
+            GroupBy::Tag if endpoint.tags.is_empty() => vec![UNTAGGED_GROUP.to_string()],
+            GroupBy::Tag => endpoint.tags.clone(),
+            GroupBy::PathPrefix => vec![path_prefix_group(&endpoint.path)],
+        }
+    }
+
+    /// Group endpoints, leaving out hidden groups
+    pub fn build(
+        &self,
+        endpoints: &[ApiEndpoint],
+        group_by: GroupBy,
+    ) -> HashMap<String, Vec<ApiEndpoint>> {
+        let mut grouped: HashMap<String, Vec<ApiEndpoint>> = HashMap::new();
+        for endpoint in endpoints {
+            for group in self.groups_for(endpoint, group_by) {
+                if !self.hidden.contains(&group) {
+                    grouped.entry(group).or_default().push(endpoint.clone());
+                }
+            }
+        }
+        grouped
+    }
+
+    /// Sort group names: configured order first, then alphabetically, callbacks and webhooks last
+    pub fn sorted_names<'a>(&self, names: impl Iterator<Item = &'a String>) -> Vec<&'a String> {
+        let mut names: Vec<&String> = names.collect();
+        names.sort_by_key(|name| {
+            let rank = self
+                .order
+                .iter()
+                .position(|ordered| ordered == *name)
+                .unwrap_or(usize::MAX);
+            let is_events = [CALLBACKS_GROUP, WEBHOOKS_GROUP].contains(&name.as_str());
+            (rank, is_events, *name)
+        });
+        names
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::GroupRule;
+
+    fn endpoint(path: &str, tags: &[&str]) -> ApiEndpoint {
+        ApiEndpoint {
+            method: "GET".to_string(),
+            path: path.to_string(),
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    fn rules() -> GroupingRules {
+        GroupingRules::new(&GroupingConfig {
+            rules: vec![
+                GroupRule {
+                    pattern: "^/internal/".to_string(),
+                    group: "Internal".to_string(),
+                },
+                GroupRule {
+                    pattern: r"^/admin/(\w+)".to_string(),
+                    group: "Admin: $1".to_string(),
+                },
+            ],
+            order: vec!["Users".to_string(), "Admin: roles".to_string()],
+            hidden: vec!["Internal".to_string()],
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_rules_override_strategy_and_hide_groups() {
+        let endpoints = vec![
+            endpoint("/admin/roles/{id}", &["Roles"]),
+            endpoint("/internal/health", &["Ops"]),
+            endpoint("/users", &["Users"]),
+            endpoint("/misc", &[]),
+        ];
+
+        let grouped = rules().build(&endpoints, GroupBy::Tag);
+        assert_eq!(grouped["Admin: roles"][0].path, "/admin/roles/{id}");
+        assert_eq!(grouped["Other"][0].path, "/misc");
+        assert!(!grouped.contains_key("Internal"));
+        assert!(!grouped.contains_key("Ops"));
+
+        let by_path = rules().build(&endpoints, GroupBy::PathPrefix);
+        assert!(by_path.contains_key("/users"));
+        assert!(by_path.contains_key("Admin: roles"));
+    }
+
+    #[test]
+    fn test_service_endpoints_are_grouped_per_service() {
+        let orders = ApiEndpoint {
+            service: Some("orders".to_string()),
+            ..endpoint("/orders", &["Orders"])
+        };
+        let users = ApiEndpoint {
+            service: Some("users".to_string()),
+            ..endpoint("/users", &[])
+        };
+
+        let grouped = rules().build(&[orders.clone(), users], GroupBy::Tag);
+        assert_eq!(grouped["orders / Orders"][0].path, "/orders");
+        assert!(grouped.contains_key("users / Other"));
+        assert_eq!(orders.key(), "orders: GET /orders");
+    }
+
+    #[test]
+    fn test_sorted_names_follow_configured_order() {
+        let names = ["Zoo", "Webhooks", "Admin: roles", "Alpha", "Users"].map(String::from);
+        let sorted = rules().sorted_names(names.iter());
+        assert_eq!(
+            sorted,
+            ["Users", "Admin: roles", "Alpha", "Zoo", "Webhooks"]
+        );
+    }
+
+    #[test]
+    fn test_invalid_pattern_is_rejected() {
+        let config = GroupingConfig {
+            rules: vec![GroupRule {
+                pattern: "(".to_string(),
+                group: "Broken".to_string(),
+            }],
+            order: vec!["Users".to_string()],
+            ..Default::default()
+        };
+        assert!(GroupingRules::new(&config).is_err());
+
+        let (rules, error) = GroupingRules::load(&config);
+        assert!(error.unwrap().contains("invalid pattern"));
+        let grouped = rules.build(&[endpoint("/users", &["Users"])], GroupBy::Tag);
+        assert!(grouped.contains_key("Users"));
+        assert_eq!(rules.order, ["Users"]);
+    }
+}
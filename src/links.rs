@@ -0,0 +1,176 @@
+//! Hyperlinks found in responses
+//!
+//! Links come from the `Link` header (RFC 8288) and from the body: absolute
+//! URLs anywhere in JSON string values, plus relative `href` values as used
+//! by HAL and similar HATEOAS formats.
+
+use crate::types::ApiResponse;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use url::Url;
+
+/// Most links offered for one response
+const MAX_LINKS: usize = 50;
+
+/// A followable link and where it was found
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResponseLink {
+    pub label: String,
+    pub url: String,
+}
+
+/// Collect the links of a response, resolving relative ones against the base URL
+pub fn extract_links(response: &ApiResponse, base_url: Option<&str>) -> Vec<ResponseLink> {
+    let base =
+        base_url.and_then(|base| Url::parse(&format!("{}/", base.trim_end_matches('/'))).ok());
+    let mut links = Vec::new();
+
+    if let Some(header) = response.headers.get("link") {
+        for (target, rel) in parse_link_header(header) {
+            links.push((format!("Link: {}", rel.unwrap_or("link")), target));
+        }
+    }
+    if let Ok(body) = serde_json::from_str::<Value>(&response.body) {
+        collect_body_links(&body, "$", &mut links);
+    }
+
+    let mut resolved: Vec<ResponseLink> = Vec::new();
+    for (label, target) in links {
+        let url = match Url::parse(&target) {
+            Ok(url) => url,
+            Err(_) => match base.as_ref().and_then(|base| base.join(&target).ok()) {
+                Some(url) => url,
+                None => continue,
+            },
+        };
+        if !matches!(url.scheme(), "http" | "https") {
+            continue;
+        }
+        let url = url.to_string();
+        if resolved.iter().all(|link| link.url != url) {
+            resolved.push(ResponseLink { label, url });
+        }
+        if resolved.len() == MAX_LINKS {
+            break;
+        }
+    }
+    resolved
+}
+
+/// Split a `Link` header into targets and their `rel`
+fn parse_link_header(header: &str) -> Vec<(String, Option<&str>)> {
+    header
+        .split(',')
+        .filter_map(|entry| {
+            let mut parts = entry.split(';');
+            let target = parts
+                .next()?
+                .trim()
+                .strip_prefix('<')?
+                .strip_suffix('>')?
+                .to_string();
+            let rel = parts.find_map(|param| {
+                param
+                    .trim()
+                    .strip_prefix("rel=")
+                    .map(|rel| rel.trim_matches('"'))
+            });
+            Some((target, rel))
+        })
+        .collect()
+}
+
+fn collect_body_links(value: &Value, path: &str, links: &mut Vec<(String, String)>) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map {
+                let path = format!("{path}.{key}");
+                match value {
+                    Value::String(s) if key == "href" || is_absolute_url(s) => {
+                        links.push((path, s.clone()));
+                    }
+                    _ => collect_body_links(value, &path, links),
+                }
+            }
+        }
+        Value::Array(items) => {
+            for (idx, item) in items.iter().enumerate() {
+                collect_body_links(item, &format!("{path}[{idx}]"), links);
+            }
+        }
+        Value::String(s) if is_absolute_url(s) => links.push((path.to_string(), s.clone())),
+        _ => {}
+    }
+}
+
+fn is_absolute_url(s: &str) -> bool {
+    s.starts_with("http://") || s.starts_with("https://")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_links_from_header_and_body() {
+        let mut response = ApiResponse::error(String::new());
+        response.is_error = false;
+        response.headers.insert(
+            "link".to_string(),
+            r#"<https://api.example.com/users?page=2>; rel="next", </users?page=9>; rel="last""#
+                .to_string(),
+        );
+        response.body = r#"{
+            "_links": { "self": { "href": "/users/1" } },
+            "avatar": "https://cdn.example.com/a.png",
+            "name": "not a link",
+            "friends": ["https://api.example.com/users/2", "https://api.example.com/users/2"]
+        }"#
+        .into();
+
+        let links = extract_links(&response, Some("http://localhost:5000/api"));
+        let pairs: Vec<(&str, &str)> = links
+            .iter()
+            .map(|link| (link.label.as_str(), link.url.as_str()))
+            .collect();
+
+        assert_eq!(
+            pairs,
+            vec![
+                ("Link: next", "https://api.example.com/users?page=2"),
+                ("Link: last", "http://localhost:5000/users?page=9"),
+                ("$._links.self.href", "http://localhost:5000/users/1"),
+                ("$.avatar", "https://cdn.example.com/a.png"),
+                ("$.friends[0]", "https://api.example.com/users/2"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_resolve_relative_links_like_a_browser() {
+        let mut response = ApiResponse::error(String::new());
+        response.body = r#"{
+            "_links": {
+                "self": { "href": "/api/orders/1" },
+                "items": { "href": "orders/1/items" }
+            }
+        }"#
+        .into();
+
+        let urls: Vec<String> = extract_links(&response, Some("https://h/api"))
+            .into_iter()
+            .map(|link| link.url)
+            .collect();
+        assert_eq!(
+            urls,
+            vec!["https://h/api/orders/1/items", "https://h/api/orders/1"]
+        );
+    }
+
+    #[test]
+    fn test_relative_links_need_a_base_url() {
+        let mut response = ApiResponse::error(String::new());
+        response.body = r#"{"href": "/orders/7"}"#.into();
+        assert!(extract_links(&response, None).is_empty());
+    }
+}
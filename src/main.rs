@@ -1,22 +1,81 @@
 mod actions;
 mod app;
+mod auth;
+mod cli;
+mod codegen;
 mod config;
+mod content;
+mod datetime;
+mod diagnostics;
+mod discovery;
 mod editor;
+mod generators;
+mod grouping;
+mod links;
+mod metrics;
+mod mock;
+mod notify;
+mod plugins;
+mod problem;
+mod recording;
+mod redact;
 mod request;
+mod schema;
+mod script;
+mod servers;
+mod snapshot;
+mod spec_diff;
 mod state;
+mod stats;
+mod store;
 mod swagger;
+mod table;
+mod tasks;
+mod traffic;
 mod types;
 mod ui;
 mod utils;
+mod viewer;
 
 use app::App;
+use clap::Parser;
+use cli::{Cli, Command, TuiArgs};
 use color_eyre::Result;
+use crossterm::event::{DisableFocusChange, EnableFocusChange};
+use crossterm::execute;
+use std::io::stdout;
+use std::process::ExitCode;
 
 #[tokio::main]
-async fn main() -> Result<()> {
+async fn main() -> Result<ExitCode> {
     color_eyre::install()?;
+    let cli = Cli::parse();
+    match cli.command.unwrap_or(Command::Tui(cli.tui)) {
+        Command::Tui(args) => run_tui(args, cli.spec, cli.base_url).await,
+        command => cli::run(command, cli.spec, cli.base_url).await,
+    }
+}
+
+/// Run the interactive app until quit
+async fn run_tui(
+    args: TuiArgs,
+    swagger_url: Option<String>,
+    base_url: Option<String>,
+) -> Result<ExitCode> {
+    let app = App::default()
+        .with_urls(swagger_url, base_url)
+        .with_session(args.session())?;
+
     let terminal = ratatui::init();
-    let app_result = App::default().run(terminal).await;
+    tasks::log_background_panics();
+    // Focus events tell whether a finished request happened out of sight
+    execute!(stdout(), EnableFocusChange)?;
+    let app_result = app.run(terminal).await;
+    execute!(stdout(), DisableFocusChange)?;
     ratatui::restore();
-    app_result
+
+    if let Some(summary) = app_result? {
+        println!("{summary}");
+    }
+    Ok(ExitCode::SUCCESS)
 }
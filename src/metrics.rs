@@ -0,0 +1,114 @@
+//! Timings for the performance overlay
+//!
+//! `F12` shows how long the last frame took to render and the last input to
+//! handle, how often and how long the UI waited for the state lock held by a
+//! background task, and how much memory the kept responses take. Lock waits are
+//! counted over the last second; uncontended locking is not timed at all.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+/// Lock waits over the current window, in microseconds
+static LOCK_WAITS: AtomicU64 = AtomicU64::new(0);
+static LOCK_WAIT_TOTAL_US: AtomicU64 = AtomicU64::new(0);
+static LOCK_WAIT_MAX_US: AtomicU64 = AtomicU64::new(0);
+
+/// How often the lock wait figures start over
+const WINDOW: Duration = Duration::from_secs(1);
+
+/// Count a wait for a lock held elsewhere
+pub fn record_lock_wait(wait: Duration) {
+    let micros = wait.as_micros() as u64;
+    LOCK_WAITS.fetch_add(1, Ordering::Relaxed);
+    LOCK_WAIT_TOTAL_US.fetch_add(micros, Ordering::Relaxed);
+    LOCK_WAIT_MAX_US.fetch_max(micros, Ordering::Relaxed);
+}
+
+/// Waits for a contended lock during one window
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct LockWaits {
+    pub count: u64,
+    pub total: Duration,
+    pub max: Duration,
+}
+
+impl LockWaits {
+    /// The waits recorded since the last call, starting a new window
+    fn take() -> Self {
+        Self {
+            count: LOCK_WAITS.swap(0, Ordering::Relaxed),
+            total: Duration::from_micros(LOCK_WAIT_TOTAL_US.swap(0, Ordering::Relaxed)),
+            max: Duration::from_micros(LOCK_WAIT_MAX_US.swap(0, Ordering::Relaxed)),
+        }
+    }
+}
+
+/// Timings of the main loop, kept by the app
+#[derive(Debug, Clone)]
+pub struct Metrics {
+    /// Duration of the last frame's render
+    pub render: Duration,
+    /// Time from the last input arriving to its effect being drawn
+    pub event: Duration,
+    /// Lock waits of the last complete window
+    pub lock_waits: LockWaits,
+    window_start: Instant,
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self {
+            render: Duration::ZERO,
+            event: Duration::ZERO,
+            lock_waits: LockWaits::default(),
+            window_start: Instant::now(),
+        }
+    }
+}
+
+impl Metrics {
+    /// Start a new lock wait window once the current one is over
+    pub fn tick(&mut self) {
+        if self.window_start.elapsed() >= WINDOW {
+            self.lock_waits = LockWaits::take();
+            self.window_start = Instant::now();
+        }
+    }
+
+    /// The overlay's lines; `stored_bytes` is the size of the kept responses
+    pub fn lines(&self, stored_bytes: usize) -> Vec<String> {
+        let waits = &self.lock_waits;
+        vec![
+            format!("render    {:>8.2} ms", millis(self.render)),
+            format!("input     {:>8.2} ms", millis(self.event)),
+            format!(
+                "lock wait {:>5}/s max {:.2} ms",
+                waits.count,
+                millis(waits.max)
+            ),
+            format!("lock wait {:>8.2} ms total", millis(waits.total)),
+            format!("responses {:>8.1} KiB", stored_bytes as f64 / 1024.0),
+        ]
+    }
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lock_waits_window() {
+        record_lock_wait(Duration::from_millis(3));
+        record_lock_wait(Duration::from_millis(1));
+
+        // Other tests may wait for locks meanwhile, so only lower bounds hold
+        let waits = LockWaits::take();
+        assert!(waits.count >= 2);
+        assert!(waits.max >= Duration::from_millis(3));
+        assert!(waits.total >= Duration::from_millis(4));
+    }
+}
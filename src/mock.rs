@@ -0,0 +1,210 @@
+//! Mock server answering with the spec's response examples
+//!
+//! `lazy-swagger-tui mock` listens on a local port and answers every request
+//! matching an endpoint's method and path template with the endpoint's response
+//! example: its status, media type and body. Requests matching no endpoint get a
+//! 404, endpoints without an example a 501.
+
+use crate::types::ApiEndpoint;
+use color_eyre::Result;
+use std::sync::Arc;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+
+/// What the mock server answers to one request
+#[derive(Debug, Clone, PartialEq)]
+pub struct MockResponse {
+    pub status: u16,
+    pub content_type: String,
+    pub body: String,
+}
+
+impl MockResponse {
+    fn text(status: u16, body: String) -> Self {
+        Self {
+            status,
+            content_type: "text/plain".to_string(),
+            body,
+        }
+    }
+}
+
+/// The response to `method` on `target` (a path with an optional query)
+pub fn respond(endpoints: &[ApiEndpoint], method: &str, target: &str) -> MockResponse {
+    let path = target.split(['?', '#']).next().unwrap_or_default();
+    // A literal segment beats a template variable, e.g. `/users/me` over `/users/{id}`
+    let endpoint = endpoints
+        .iter()
+        .filter(|endpoint| endpoint.event.is_none())
+        .filter(|endpoint| endpoint.method.eq_ignore_ascii_case(method))
+        .filter(|endpoint| matches_template(&endpoint.path, path))
+        .min_by_key(|endpoint| endpoint.path.matches('{').count());
+
+    let Some(endpoint) = endpoint else {
+        return MockResponse::text(404, format!("No endpoint matches {method} {path}"));
+    };
+    let Some(example) = &endpoint.response_example else {
+        return MockResponse::text(
+            501,
+            format!("The spec has no response example for {}", endpoint.key()),
+        );
+    };
+
+    // `default` and ranges like `2XX` answer with the plain success status
+    let status = example.status.parse().unwrap_or(200);
+    let body = match &example.body {
+        serde_json::Value::String(text) => text.clone(),
+        body => serde_json::to_string_pretty(body).unwrap_or_default(),
+    };
+    MockResponse {
+        status,
+        content_type: example
+            .media_type
+            .clone()
+            .unwrap_or_else(|| "application/json".to_string()),
+        body,
+    }
+}
+
+/// Whether a path matches a template like `/users/{id}`, segment by segment
+fn matches_template(template: &str, path: &str) -> bool {
+    let template: Vec<&str> = template.trim_end_matches('/').split('/').collect();
+    let path: Vec<&str> = path.trim_end_matches('/').split('/').collect();
+    template.len() == path.len()
+        && template.iter().zip(&path).all(|(expected, actual)| {
+            expected == actual
+                || (expected.starts_with('{') && expected.ends_with('}') && !actual.is_empty())
+        })
+}
+
+/// Serve the endpoints' examples on localhost until interrupted
+pub async fn serve(endpoints: Vec<ApiEndpoint>, port: u16) -> Result<()> {
+    let listener = TcpListener::bind(("127.0.0.1", port)).await?;
+    eprintln!(
+        "Mocking {} endpoints on http://127.0.0.1:{port} (Ctrl+C to stop)",
+        endpoints.len()
+    );
+
+    let endpoints = Arc::new(endpoints);
+    loop {
+        let (stream, _) = listener.accept().await?;
+        let endpoints = Arc::clone(&endpoints);
+        tokio::spawn(async move {
+            if let Err(e) = handle_connection(stream, &endpoints).await {
+                eprintln!("Connection failed: {e}");
+            }
+        });
+    }
+}
+
+/// Read one HTTP/1.1 request, answer it and close the connection
+async fn handle_connection(mut stream: TcpStream, endpoints: &[ApiEndpoint]) -> Result<()> {
+    let (reader, mut writer) = stream.split();
+    let mut reader = BufReader::new(reader);
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line).await?;
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+        return Ok(());
+    };
+
+    // Read past the headers and body so the client sees a clean close
+    let mut content_length: u64 = 0;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header).await? == 0 || header.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().unwrap_or(0);
+            }
+        }
+    }
+    // The body is never used; it is discarded as it arrives instead of buffered whole
+    tokio::io::copy(
+        &mut (&mut reader).take(content_length),
+        &mut tokio::io::sink(),
+    )
+    .await?;
+
+    let response = respond(endpoints, method, target);
+    eprintln!("{method} {target} -> {}", response.status);
+
+    let reason = reqwest::StatusCode::from_u16(response.status)
+        .ok()
+        .and_then(|status| status.canonical_reason())
+        .unwrap_or("");
+    let head = format!(
+        "HTTP/1.1 {} {reason}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        response.status,
+        response.content_type,
+        response.body.len()
+    );
+    writer.write_all(head.as_bytes()).await?;
+    writer.write_all(response.body.as_bytes()).await?;
+    writer.flush().await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ResponseExample;
+
+    fn endpoint(method: &str, path: &str, example: Option<serde_json::Value>) -> ApiEndpoint {
+        ApiEndpoint {
+            method: method.to_string(),
+            path: path.to_string(),
+            response_example: example.map(|body| ResponseExample {
+                status: "200".to_string(),
+                media_type: Some("application/json".to_string()),
+                body,
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_respond() {
+        let endpoints = vec![
+            endpoint("GET", "/users/{id}", Some(serde_json::json!({"id": 1}))),
+            endpoint("GET", "/users/me", Some(serde_json::json!("me"))),
+            endpoint("DELETE", "/users/{id}", None),
+        ];
+
+        let response = respond(&endpoints, "GET", "/users/42?fields=id");
+        assert_eq!(response.status, 200);
+        assert_eq!(response.body, "{\n  \"id\": 1\n}");
+        assert_eq!(respond(&endpoints, "get", "/users/me").body, "me");
+
+        assert_eq!(respond(&endpoints, "DELETE", "/users/42").status, 501);
+        assert_eq!(respond(&endpoints, "GET", "/users").status, 404);
+        assert_eq!(respond(&endpoints, "GET", "/users/42/posts").status, 404);
+    }
+
+    #[tokio::test]
+    async fn test_claimed_body_length_is_not_allocated() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let endpoints = vec![endpoint("POST", "/users", None)];
+            handle_connection(stream, &endpoints).await
+        });
+
+        // A terabyte announced, two bytes sent
+        let mut client = TcpStream::connect(address).await.unwrap();
+        client
+            .write_all(b"POST /users HTTP/1.1\r\nContent-Length: 1099511627776\r\n\r\nhi")
+            .await
+            .unwrap();
+        client.shutdown().await.unwrap();
+        let mut response = String::new();
+        client.read_to_string(&mut response).await.unwrap();
+
+        assert!(response.starts_with("HTTP/1.1 501"));
+        server.await.unwrap().unwrap();
+    }
+}
@@ -0,0 +1,116 @@
+//! Notifications for requests that finish out of sight
+//!
+//! A request that takes a while may finish after the user moved on to another
+//! endpoint or another window. Then the terminal bell rings and/or a desktop
+//! notification is sent, as configured under `[notifications]`, and a toast
+//! with the status and duration is shown in the app.
+
+use crate::config::NotificationConfig;
+use crate::types::ApiResponse;
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+/// How long a toast stays on screen
+pub const TOAST_DURATION: Duration = Duration::from_secs(4);
+
+/// Outcome of a request, handed to the app when it finishes
+#[derive(Debug, Clone, PartialEq)]
+pub struct FinishedRequest {
+    pub endpoint_key: String,
+    /// HTTP status, 0 for network errors
+    pub status: u16,
+    pub duration: Duration,
+}
+
+impl FinishedRequest {
+    pub fn new(endpoint_key: String, response: &ApiResponse) -> Self {
+        Self {
+            endpoint_key,
+            status: response.status,
+            duration: response.duration,
+        }
+    }
+
+    /// One line for the toast and the notification, e.g. `GET /users: 200 in 2.4s`
+    pub fn summary(&self) -> String {
+        let status = match self.status {
+            0 => "failed".to_string(),
+            status => status.to_string(),
+        };
+        format!(
+            "{}: {status} in {:.1}s",
+            self.endpoint_key,
+            self.duration.as_secs_f64()
+        )
+    }
+
+    /// Whether the user should be told: the request was slow and finished out of sight
+    pub fn needs_notification(
+        &self,
+        config: &NotificationConfig,
+        selected_key: Option<&str>,
+        terminal_focused: bool,
+    ) -> bool {
+        let out_of_sight = !terminal_focused || selected_key != Some(self.endpoint_key.as_str());
+        out_of_sight && self.duration >= Duration::from_millis(config.min_duration_ms)
+    }
+}
+
+/// Ring the bell and/or send a desktop notification, as configured
+pub fn notify(config: &NotificationConfig, finished: &FinishedRequest) {
+    if config.bell {
+        let mut stdout = std::io::stdout();
+        let _ = stdout.write_all(b"\x07");
+        let _ = stdout.flush();
+    }
+    if config.desktop {
+        send_desktop_notification("Request finished", &finished.summary());
+    }
+}
+
+/// Best effort: notify-send on Linux, osascript on macOS; failures are ignored
+fn send_desktop_notification(title: &str, body: &str) {
+    let mut command = if cfg!(target_os = "macos") {
+        let script = format!("display notification {body:?} with title {title:?}");
+        let mut command = Command::new("osascript");
+        command.args(["-e", &script]);
+        command
+    } else {
+        let mut command = Command::new("notify-send");
+        command.args([title, body]);
+        command
+    };
+    let _ = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_needs_notification() {
+        let config = NotificationConfig::default();
+        let finished = FinishedRequest {
+            endpoint_key: "GET /reports".to_string(),
+            status: 200,
+            duration: Duration::from_secs(3),
+        };
+        assert_eq!(finished.summary(), "GET /reports: 200 in 3.0s");
+
+        // Watching it finish needs no notification
+        assert!(!finished.needs_notification(&config, Some("GET /reports"), true));
+        assert!(finished.needs_notification(&config, Some("GET /users"), true));
+        assert!(finished.needs_notification(&config, Some("GET /reports"), false));
+
+        let quick = FinishedRequest {
+            duration: Duration::from_millis(300),
+            ..finished
+        };
+        assert!(!quick.needs_notification(&config, None, false));
+    }
+}
@@ -0,0 +1,455 @@
+//! Plugins hooking into spec loading, request execution and custom commands
+//!
+//! A plugin implements [`Plugin`], where every hook defaults to doing nothing.
+//! Plugins are either compiled in (listed in `builtin_plugins`) or external
+//! programs configured under `[[plugins]]`, which get the request and response
+//! hooks as a JSON message on stdin and may answer a request with changes on
+//! stdout. Either way, things like org-specific request signing or audit
+//! logging need no fork.
+
+use crate::config::{PluginCommand, PluginConfig, PluginHook};
+use crate::request::RequestBody;
+use crate::types::{ApiEndpoint, ApiResponse};
+use serde_json::{json, Map, Value};
+use std::fmt;
+use std::io::{Read, Write};
+use std::process::{Command, Stdio};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How often a running plugin program is checked for having exited
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// A request about to be sent, as plugins see and may change it
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutgoingRequest {
+    pub method: String,
+    pub url: String,
+    /// Headers besides the body's Content-Type, including `Authorization` when a token is set
+    pub headers: Vec<(String, String)>,
+    pub body: Option<RequestBody>,
+}
+
+impl OutgoingRequest {
+    /// Set a header, replacing one with the same name (case-insensitive)
+    pub fn set_header(&mut self, name: &str, value: String) {
+        match self
+            .headers
+            .iter_mut()
+            .find(|(existing, _)| existing.eq_ignore_ascii_case(name))
+        {
+            Some((_, existing)) => *existing = value,
+            None => self.headers.push((name.to_string(), value)),
+        }
+    }
+
+    fn to_json(&self) -> Value {
+        json!({
+            "method": self.method,
+            "url": self.url,
+            "headers": self
+                .headers
+                .iter()
+                .map(|(name, value)| (name.clone(), Value::from(value.as_str())))
+                .collect::<Map<String, Value>>(),
+            "content_type": self.body.as_ref().map(|body| &body.content_type),
+            "body": self.body.as_ref().map(|body| &body.content),
+        })
+    }
+}
+
+pub trait Plugin: Send + Sync {
+    fn name(&self) -> &str;
+
+    /// Called with the parsed endpoints before they are listed
+    ///
+    /// Only compiled-in plugins get this hook; external programs are not called for it.
+    fn on_spec_loaded(&self, _endpoints: &mut Vec<ApiEndpoint>) {}
+
+    /// Called before a request is sent; an error cancels the request
+    fn on_request(&self, _request: &mut OutgoingRequest) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// Called with each response before it is shown
+    ///
+    /// External programs only observe responses; what they print is ignored.
+    fn on_response(&self, _request: &OutgoingRequest, _response: &mut ApiResponse) {}
+
+    /// Commands offered in the plugin command picker
+    fn commands(&self) -> Vec<PluginCommand> {
+        Vec::new()
+    }
+
+    /// Run one of the plugin's commands for the selected endpoint, returning its output
+    fn run_command(
+        &self,
+        command: &str,
+        _endpoint: Option<&ApiEndpoint>,
+    ) -> Result<String, String> {
+        Err(format!("Unknown command: {command}"))
+    }
+}
+
+/// Plugins compiled into the binary; add `Arc::new(YourPlugin)` here
+fn builtin_plugins() -> Vec<Arc<dyn Plugin>> {
+    Vec::new()
+}
+
+/// The active plugins, called in registration order
+#[derive(Clone, Default)]
+pub struct PluginRegistry {
+    plugins: Vec<Arc<dyn Plugin>>,
+}
+
+impl fmt::Debug for PluginRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.plugins.iter().map(|plugin| plugin.name()))
+            .finish()
+    }
+}
+
+impl PluginRegistry {
+    /// Built-in plugins followed by the external ones from the config
+    pub fn load(configs: &[PluginConfig]) -> Self {
+        let mut registry = Self::default();
+        for plugin in builtin_plugins() {
+            registry.register(plugin);
+        }
+        for config in configs {
+            registry.register(Arc::new(CommandPlugin {
+                config: config.clone(),
+            }));
+        }
+        registry
+    }
+
+    pub fn register(&mut self, plugin: Arc<dyn Plugin>) {
+        self.plugins.push(plugin);
+    }
+
+    pub fn on_spec_loaded(&self, endpoints: &mut Vec<ApiEndpoint>) {
+        for plugin in &self.plugins {
+            plugin.on_spec_loaded(endpoints);
+        }
+    }
+
+    /// Run the request hooks, stopping at the first plugin that cancels
+    pub fn on_request(&self, request: &mut OutgoingRequest) -> Result<(), String> {
+        for plugin in &self.plugins {
+            plugin
+                .on_request(request)
+                .map_err(|e| format!("Plugin {}: {e}", plugin.name()))?;
+        }
+        Ok(())
+    }
+
+    pub fn on_response(&self, request: &OutgoingRequest, response: &mut ApiResponse) {
+        for plugin in &self.plugins {
+            plugin.on_response(request, response);
+        }
+    }
+
+    /// Commands of all plugins, with the name of the plugin offering each
+    pub fn commands(&self) -> Vec<(String, PluginCommand)> {
+        self.plugins
+            .iter()
+            .flat_map(|plugin| {
+                plugin
+                    .commands()
+                    .into_iter()
+                    .map(|command| (plugin.name().to_string(), command))
+            })
+            .collect()
+    }
+
+    pub fn run_command(
+        &self,
+        plugin: &str,
+        command: &str,
+        endpoint: Option<&ApiEndpoint>,
+    ) -> Result<String, String> {
+        self.plugins
+            .iter()
+            .find(|candidate| candidate.name() == plugin)
+            .ok_or_else(|| format!("Unknown plugin: {plugin}"))?
+            .run_command(command, endpoint)
+    }
+}
+
+/// An external program configured under `[[plugins]]`
+///
+/// Each call starts the program with a JSON message on stdin, e.g.
+/// `{"hook": "request", "request": {...}}`. For the request hook it may print a
+/// JSON object with a new `url`, `headers` to set, a new `body`, or an `error`
+/// that cancels the request. The response hook only reports the response: its
+/// output and failures are ignored. There is no hook for loaded specs. A program
+/// still running after the configured `timeout_secs` is killed and the call fails.
+struct CommandPlugin {
+    config: PluginConfig,
+}
+
+impl CommandPlugin {
+    fn handles(&self, hook: PluginHook) -> bool {
+        self.config.hooks.contains(&hook)
+    }
+
+    /// Run the program with a message on stdin, returning what it printed
+    ///
+    /// Blocks until the program exits or the timeout passes, so async callers
+    /// run hooks on a blocking thread.
+    fn call(&self, message: &Value) -> Result<String, String> {
+        let command = &self.config.command;
+        let mut child = Command::new(command)
+            .args(&self.config.args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| format!("failed to start {command}: {e}"))?;
+
+        // Written and read on their own threads, so a program that doesn't read
+        // its input or fills a pipe can't block past the timeout
+        let stdin = child.stdin.take().map(|mut stdin| {
+            let message = message.to_string();
+            thread::spawn(move || stdin.write_all(message.as_bytes()))
+        });
+        let stdout = child.stdout.take().map(read_to_end);
+        let stderr = child.stderr.take().map(read_to_end);
+
+        let deadline = Instant::now() + self.config.timeout();
+        let status = loop {
+            match child.try_wait() {
+                Ok(Some(status)) => break status,
+                Ok(None) if Instant::now() < deadline => thread::sleep(POLL_INTERVAL),
+                Ok(None) => {
+                    let _ = child.kill();
+                    let _ = child.wait();
+                    return Err(format!(
+                        "{command} timed out after {}s",
+                        self.config.timeout().as_secs()
+                    ));
+                }
+                Err(e) => return Err(format!("failed to run {command}: {e}")),
+            }
+        };
+
+        if let Some(Ok(Err(e))) = stdin.map(thread::JoinHandle::join) {
+            // A program may exit without reading its input
+            if e.kind() != std::io::ErrorKind::BrokenPipe {
+                return Err(format!("failed to write to {command}: {e}"));
+            }
+        }
+        let collect = |output: Option<thread::JoinHandle<Vec<u8>>>| {
+            output
+                .and_then(|handle| handle.join().ok())
+                .unwrap_or_default()
+        };
+        let (stdout, stderr) = (collect(stdout), collect(stderr));
+        if !status.success() {
+            let stderr = String::from_utf8_lossy(&stderr).trim().to_string();
+            return Err(if stderr.is_empty() {
+                format!("{command} exited with {status}")
+            } else {
+                stderr
+            });
+        }
+        Ok(String::from_utf8_lossy(&stdout).into_owned())
+    }
+}
+
+impl Plugin for CommandPlugin {
+    fn name(&self) -> &str {
+        &self.config.name
+    }
+
+    fn on_request(&self, request: &mut OutgoingRequest) -> Result<(), String> {
+        if !self.handles(PluginHook::Request) {
+            return Ok(());
+        }
+        let output = self.call(&json!({ "hook": "request", "request": request.to_json() }))?;
+        apply_request_reply(request, &output)
+    }
+
+    fn on_response(&self, request: &OutgoingRequest, response: &mut ApiResponse) {
+        if !self.handles(PluginHook::Response) {
+            return;
+        }
+        let _ = self.call(&json!({
+            "hook": "response",
+            "request": request.to_json(),
+            "response": {
+                "status": response.status,
+                "headers": response.headers,
+                "body": response.body.text(),
+                "duration_ms": response.duration.as_millis() as u64,
+            },
+        }));
+    }
+
+    fn commands(&self) -> Vec<PluginCommand> {
+        self.config.commands.clone()
+    }
+
+    fn run_command(&self, command: &str, endpoint: Option<&ApiEndpoint>) -> Result<String, String> {
+        if !self.config.commands.iter().any(|c| c.name == command) {
+            return Err(format!("Unknown command: {command}"));
+        }
+        self.call(&json!({
+            "hook": "command",
+            "command": command,
+            "endpoint": endpoint.map(|endpoint| json!({
+                "method": endpoint.method,
+                "path": endpoint.path,
+            })),
+        }))
+    }
+}
+
+/// Read a pipe of a plugin program to its end on a separate thread
+fn read_to_end(mut pipe: impl Read + Send + 'static) -> thread::JoinHandle<Vec<u8>> {
+    thread::spawn(move || {
+        let mut output = Vec::new();
+        let _ = pipe.read_to_end(&mut output);
+        output
+    })
+}
+
+/// Apply the JSON a request hook printed; empty output leaves the request as is
+fn apply_request_reply(request: &mut OutgoingRequest, output: &str) -> Result<(), String> {
+    if output.trim().is_empty() {
+        return Ok(());
+    }
+    let reply: Map<String, Value> = serde_json::from_str(output)
+        .map_err(|e| format!("invalid reply (expected a JSON object): {e}"))?;
+
+    if let Some(error) = reply.get("error").and_then(Value::as_str) {
+        return Err(error.to_string());
+    }
+    if let Some(url) = reply.get("url").and_then(Value::as_str) {
+        request.url = url.to_string();
+    }
+    if let Some(headers) = reply.get("headers").and_then(Value::as_object) {
+        for (name, value) in headers {
+            let value = value
+                .as_str()
+                .map_or_else(|| value.to_string(), str::to_string);
+            request.set_header(name, value);
+        }
+    }
+    if let (Some(content), Some(body)) = (
+        reply.get("body").and_then(Value::as_str),
+        request.body.as_mut(),
+    ) {
+        body.content = content.to_string();
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request() -> OutgoingRequest {
+        OutgoingRequest {
+            method: "POST".to_string(),
+            url: "https://api.example.com/orders".to_string(),
+            headers: vec![("Authorization".to_string(), "Bearer abc".to_string())],
+            body: Some(RequestBody {
+                content_type: "application/json".to_string(),
+                content: "{}".to_string(),
+            }),
+        }
+    }
+
+    struct Signer;
+
+    impl Plugin for Signer {
+        fn name(&self) -> &str {
+            "signer"
+        }
+
+        fn on_request(&self, request: &mut OutgoingRequest) -> Result<(), String> {
+            if request.url.contains("forbidden") {
+                return Err("refusing to sign".to_string());
+            }
+            let length = request.body.as_ref().map_or(0, |body| body.content.len());
+            request.set_header("X-Signature", format!("len={length}"));
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_registry_runs_request_hooks() {
+        let mut registry = PluginRegistry::default();
+        registry.register(Arc::new(Signer));
+
+        let mut signed = request();
+        registry.on_request(&mut signed).unwrap();
+        assert_eq!(
+            signed.headers[1],
+            ("X-Signature".to_string(), "len=2".to_string())
+        );
+
+        let mut refused = request();
+        refused.url.push_str("/forbidden");
+        assert_eq!(
+            registry.on_request(&mut refused),
+            Err("Plugin signer: refusing to sign".to_string())
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_command_plugin_timeout() {
+        let plugin = |script: &str| CommandPlugin {
+            config: toml::from_str(&format!(
+                "name = \"test\"\ncommand = \"sh\"\nargs = [\"-c\", {script:?}]\ntimeout_secs = 1\n"
+            ))
+            .unwrap(),
+        };
+
+        let echo = plugin("cat");
+        assert_eq!(
+            echo.call(&json!({ "hook": "request" })).unwrap(),
+            r#"{"hook":"request"}"#
+        );
+
+        let started = Instant::now();
+        let hung = plugin("sleep 30");
+        assert_eq!(
+            hung.call(&json!({})),
+            Err("sh timed out after 1s".to_string())
+        );
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_apply_request_reply() {
+        let mut request = request();
+        apply_request_reply(&mut request, "  \n").unwrap();
+        assert_eq!(request, self::request());
+
+        apply_request_reply(
+            &mut request,
+            r#"{"url": "https://signed.example.com", "headers": {"authorization": "Sig xyz", "X-Nonce": 7}, "body": "{\"a\":1}"}"#,
+        )
+        .unwrap();
+        assert_eq!(request.url, "https://signed.example.com");
+        assert_eq!(
+            request.headers,
+            vec![
+                ("Authorization".to_string(), "Sig xyz".to_string()),
+                ("X-Nonce".to_string(), "7".to_string()),
+            ]
+        );
+        assert_eq!(request.body.unwrap().content, r#"{"a":1}"#);
+
+        assert_eq!(
+            apply_request_reply(&mut self::request(), r#"{"error": "expired key"}"#),
+            Err("expired key".to_string())
+        );
+    }
+}
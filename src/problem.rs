@@ -0,0 +1,104 @@
+//! RFC 7807 problem details (`application/problem+json`)
+//!
+//! Error responses in this format are shown as a structured block above the
+//! raw body instead of only as JSON.
+
+use crate::types::ApiResponse;
+use serde_json::Value;
+
+/// Members defined by RFC 7807, everything else is an extension
+const STANDARD_MEMBERS: [&str; 5] = ["type", "title", "status", "detail", "instance"];
+
+/// Parsed problem details of a response
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProblemDetails {
+    pub problem_type: Option<String>,
+    pub title: Option<String>,
+    pub status: Option<u64>,
+    pub detail: Option<String>,
+    pub instance: Option<String>,
+    /// Extension members (e.g. validation `errors`) as compact JSON
+    pub extensions: Vec<(String, String)>,
+}
+
+impl ProblemDetails {
+    /// Parse a response declared as `problem+json`, or one shaped like it
+    ///
+    /// Without the content type, a body needs `title` plus `type` or `detail`
+    /// so ordinary objects with a `title` field are not mistaken for problems.
+    pub fn from_response(response: &ApiResponse) -> Option<Self> {
+        let Ok(Value::Object(body)) = serde_json::from_str::<Value>(&response.body) else {
+            return None;
+        };
+
+        let declared = response
+            .headers
+            .get("content-type")
+            .is_some_and(|content_type| content_type.contains("problem+json"));
+        let shaped = body.get("title").is_some_and(Value::is_string)
+            && (body.contains_key("type") || body.contains_key("detail"));
+        if !declared && !shaped {
+            return None;
+        }
+
+        let text = |key: &str| body.get(key).and_then(Value::as_str).map(str::to_string);
+        Some(Self {
+            problem_type: text("type"),
+            title: text("title"),
+            status: body.get("status").and_then(Value::as_u64),
+            detail: text("detail"),
+            instance: text("instance"),
+            extensions: body
+                .iter()
+                .filter(|(key, _)| !STANDARD_MEMBERS.contains(&key.as_str()))
+                .map(|(key, value)| (key.clone(), value.to_string()))
+                .collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(content_type: &str, body: &str) -> ApiResponse {
+        let mut response = ApiResponse::error(String::new());
+        response.is_error = false;
+        response
+            .headers
+            .insert("content-type".to_string(), content_type.to_string());
+        response.body = body.into();
+        response
+    }
+
+    #[test]
+    fn test_parses_problem_json() {
+        let problem = ProblemDetails::from_response(&response(
+            "application/problem+json",
+            r#"{"type": "https://example.com/probs/out-of-credit", "title": "Out of credit",
+                "status": 403, "detail": "Balance is 30", "instance": "/account/12345",
+                "balance": 30}"#,
+        ))
+        .unwrap();
+
+        assert_eq!(problem.title.as_deref(), Some("Out of credit"));
+        assert_eq!(problem.status, Some(403));
+        assert_eq!(problem.instance.as_deref(), Some("/account/12345"));
+        assert_eq!(
+            problem.extensions,
+            vec![("balance".to_string(), "30".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_detects_problem_shape_without_content_type() {
+        let shaped = r#"{"title": "Not Found", "status": 404, "detail": "No user 7"}"#;
+        assert!(ProblemDetails::from_response(&response("application/json", shaped)).is_some());
+
+        let article = r#"{"id": 1, "title": "Hello"}"#;
+        assert!(ProblemDetails::from_response(&response("application/json", article)).is_none());
+        assert!(
+            ProblemDetails::from_response(&response("application/problem+json", "[]")).is_none()
+        );
+    }
+}
@@ -0,0 +1,210 @@
+//! Recording sessions for bug reports, and replaying them
+//!
+//! With `--record <file>` every applied [`AppAction`] is appended to the file as
+//! a JSON line, together with a sanitized snapshot of the state after it: modes,
+//! focus and counts, never tokens or response bodies. `--replay <file>` loads the
+//! spec as usual, then applies the recorded actions one by one and compares each
+//! resulting snapshot with the recorded one.
+//!
+//! Only state changes made through actions are recorded. Requests, file exports
+//! and list navigation happen in the key handlers and are not replayed.
+
+use crate::actions::{apply_action, AppAction};
+use crate::state::AppState;
+use crate::types::{DetailTab, PanelFocus};
+use color_eyre::Result;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::PathBuf;
+
+/// How the app was asked to record or replay a session
+#[derive(Debug, Clone, PartialEq)]
+pub enum SessionMode {
+    Record(PathBuf),
+    Replay(PathBuf),
+}
+
+/// One applied action and the state it led to
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedStep {
+    pub action: AppAction,
+    pub snapshot: StateSnapshot,
+}
+
+/// The parts of the state a UI bug usually shows in, without any user data
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StateSnapshot {
+    pub input_mode: String,
+    pub panel_focus: PanelFocus,
+    pub detail_tab: DetailTab,
+    pub view_mode: String,
+    pub expanded_groups: usize,
+    pub endpoints: usize,
+    pub response_status: Option<u16>,
+    pub response_scroll: usize,
+    pub search_query_len: usize,
+}
+
+impl StateSnapshot {
+    pub fn capture(state: &AppState) -> Self {
+        Self {
+            input_mode: format!("{:?}", state.input.mode),
+            panel_focus: state.ui.panel_focus.clone(),
+            detail_tab: state.ui.active_detail_tab.clone(),
+            view_mode: format!("{:?}", state.ui.view_mode),
+            expanded_groups: state.ui.expanded_groups.len(),
+            endpoints: state.data.endpoints.len(),
+            response_status: state
+                .request
+                .current_response
+                .as_ref()
+                .map(|response| response.status),
+            response_scroll: state.ui.response_scroll,
+            search_query_len: state.search.query.chars().count(),
+        }
+    }
+}
+
+/// Copy of an action that is safe to write to a file: token text is masked
+pub fn sanitize(action: &AppAction) -> AppAction {
+    let mask = |text: &str| "*".repeat(text.chars().count());
+    match action {
+        AppAction::SetAuthToken(token) => AppAction::SetAuthToken(mask(token)),
+        AppAction::AppendToTokenInput(text) => AppAction::AppendToTokenInput(mask(text)),
+        other => other.clone(),
+    }
+}
+
+/// Appends recorded steps to the session file
+#[derive(Debug)]
+pub struct Recorder {
+    file: File,
+}
+
+impl Recorder {
+    /// Start a new recording, replacing an existing file
+    pub fn create(path: &PathBuf) -> Result<Self> {
+        Ok(Self {
+            file: File::create(path)?,
+        })
+    }
+
+    pub fn write(&mut self, steps: &[RecordedStep]) -> Result<()> {
+        for step in steps {
+            writeln!(self.file, "{}", serde_json::to_string(step)?)?;
+        }
+        self.file.flush()?;
+        Ok(())
+    }
+}
+
+/// A recording being played back, with the steps whose outcome differed
+#[derive(Debug)]
+pub struct Replay {
+    steps: Vec<RecordedStep>,
+    next: usize,
+    divergences: Vec<String>,
+}
+
+impl Replay {
+    pub fn load(path: &PathBuf) -> Result<Self> {
+        let steps = fs::read_to_string(path)?
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(serde_json::from_str)
+            .collect::<Result<Vec<RecordedStep>, _>>()?;
+        Ok(Self::new(steps))
+    }
+
+    fn new(steps: Vec<RecordedStep>) -> Self {
+        Self {
+            steps,
+            next: 0,
+            divergences: Vec::new(),
+        }
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.next >= self.steps.len()
+    }
+
+    /// Apply the next recorded action, noting a divergence from the recorded snapshot
+    pub fn step(&mut self, state: &mut AppState) {
+        let Some(step) = self.steps.get(self.next) else {
+            return;
+        };
+        apply_action(step.action.clone(), state);
+
+        let snapshot = StateSnapshot::capture(state);
+        if snapshot != step.snapshot {
+            self.divergences.push(format!(
+                "step {} ({:?}): expected {:?}, got {:?}",
+                self.next + 1,
+                step.action,
+                step.snapshot,
+                snapshot
+            ));
+        }
+        self.next += 1;
+    }
+
+    /// What happened, for printing once the app has exited
+    pub fn summary(&self) -> String {
+        let mut summary = format!(
+            "Replayed {} of {} recorded steps, {} diverged",
+            self.next,
+            self.steps.len(),
+            self.divergences.len()
+        );
+        if let Some(first) = self.divergences.first() {
+            summary.push_str(&format!("\nFirst divergence at {first}"));
+        }
+        summary
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_and_replay() {
+        let mut state = AppState::default();
+        state.ui.recording = Some(Vec::new());
+        for action in [
+            AppAction::EnterTokenInputMode,
+            AppAction::AppendToTokenInput("secret".to_string()),
+            AppAction::NavigateToPanel(PanelFocus::Details),
+        ] {
+            apply_action(action, &mut state);
+        }
+        let steps = state.ui.recording.take().unwrap();
+        assert_eq!(steps.len(), 3);
+        assert_eq!(
+            steps[1].action,
+            AppAction::AppendToTokenInput("******".to_string())
+        );
+
+        // Steps survive the JSON lines round trip
+        let lines: Vec<String> = steps
+            .iter()
+            .map(|step| serde_json::to_string(step).unwrap())
+            .collect();
+        let loaded: Vec<RecordedStep> = lines
+            .iter()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+
+        let mut replay = Replay::new(loaded);
+        let mut fresh = AppState::default();
+        while !replay.is_done() {
+            replay.step(&mut fresh);
+        }
+        assert_eq!(fresh.ui.panel_focus, PanelFocus::Details);
+        assert_eq!(
+            replay.summary(),
+            "Replayed 3 of 3 recorded steps, 0 diverged"
+        );
+    }
+}
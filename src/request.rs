@@ -1,9 +1,92 @@
 use url::Url;
 
-use crate::state::AppState;
-use crate::types::{ApiEndpoint, ApiResponse};
+use crate::auth;
+use crate::config::PathRewriteConfig;
+use crate::notify::FinishedRequest;
+use crate::plugins::{OutgoingRequest, PluginRegistry};
+use crate::state::{AppState, EndpointResponse, LockRecover};
+use crate::tasks;
+use crate::traffic::TrafficEntry;
+use crate::types::{ApiEndpoint, ApiResponse, Credential, Reconnect, RequestConfig, ResponseBody};
+use crate::ui::events::log_debug;
+use crate::utils::url_under;
+use serde_json::Value;
 use std::collections::HashMap;
 use std::sync::{Arc, RwLock};
+use tokio::sync::OwnedSemaphorePermit;
+
+/// Encoded request body and the Content-Type header that goes with it
+#[derive(Debug, Clone, PartialEq)]
+pub struct RequestBody {
+    pub content_type: String,
+    pub content: String,
+}
+
+impl RequestBody {
+    /// Encode the editor's body for a media type
+    ///
+    /// Form media types take a JSON object and send its fields as form fields;
+    /// anything else is sent as written.
+    pub fn encode(media_type: &str, body: Option<String>) -> Self {
+        let fields = body
+            .as_deref()
+            .and_then(|body| serde_json::from_str::<Value>(body).ok())
+            .and_then(|value| match value {
+                Value::Object(fields) => Some(fields),
+                _ => None,
+            });
+
+        match (media_type, fields) {
+            ("application/x-www-form-urlencoded", Some(fields)) => {
+                let mut form = url::form_urlencoded::Serializer::new(String::new());
+                for (name, value) in &fields {
+                    form.append_pair(name, &form_value(value));
+                }
+                Self {
+                    content_type: media_type.to_string(),
+                    content: form.finish(),
+                }
+            }
+            ("multipart/form-data", Some(fields)) => {
+                let boundary = format!("lazy-swagger-{:016x}", fastrand::u64(..));
+                Self {
+                    content_type: format!("{media_type}; boundary={boundary}"),
+                    content: multipart_body(&fields, &boundary),
+                }
+            }
+            _ => Self {
+                content_type: media_type.to_string(),
+                content: body.unwrap_or_else(|| {
+                    if media_type.contains("json") {
+                        "{}".to_string()
+                    } else {
+                        String::new()
+                    }
+                }),
+            },
+        }
+    }
+}
+
+/// Form field value: strings as-is, other JSON values as their JSON text
+fn form_value(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn multipart_body(fields: &serde_json::Map<String, Value>, boundary: &str) -> String {
+    let mut body = String::new();
+    for (name, value) in fields {
+        body.push_str(&format!(
+            "--{boundary}\r\nContent-Disposition: form-data; name=\"{name}\"\r\n\r\n{}\r\n",
+            form_value(value)
+        ));
+    }
+    body.push_str(&format!("--{boundary}--\r\n"));
+    body
+}
 
 pub struct RequestUrlBuilder {
     base_url: String,
@@ -62,6 +145,26 @@ impl RequestUrlBuilder {
     }
 }
 
+/// The path as exposed at `base_url`, by the first rewrite rule for it
+pub fn rewrite_path(rewrites: &[PathRewriteConfig], base_url: &str, path: &str) -> String {
+    rewrites
+        .iter()
+        .find(|rewrite| url_under(&rewrite.base_url, base_url))
+        .map_or_else(|| path.to_string(), |rewrite| rewrite.apply(path))
+}
+
+/// Whether two URLs have the same scheme, host and port
+fn same_origin(url: &str, other: &str) -> bool {
+    match (Url::parse(url), Url::parse(other)) {
+        (Ok(url), Ok(other)) => {
+            url.scheme() == other.scheme()
+                && url.host_str() == other.host_str()
+                && url.port_or_known_default() == other.port_or_known_default()
+        }
+        _ => false,
+    }
+}
+
 /// Executes an HTTP request for the given endpoint in the background
 pub fn execute_request_background(
     state: Arc<RwLock<AppState>>,
@@ -69,112 +172,293 @@ pub fn execute_request_background(
     base_url: String,
 ) {
     // Mark this endpoint as executing
-    {
-        let mut s = state.write().unwrap();
-        s.request.executing_endpoint = Some(endpoint.path.clone());
-        s.request.current_response = None; // Clear any previous response
-    }
+    let job = {
+        let mut s = state.write_lock();
+        s.store_response(endpoint.key(), None); // Clear any previous response
+        s.request.last_executed = Some(endpoint.key());
+        s.request.start_job(endpoint.key())
+    };
 
-    // Spawn background task
-    tokio::spawn(async move {
-        // Get path, query parameters, and body from request config
-        let (path_params, query_params, body) = {
-            let s = state.read().unwrap();
-            s.request
-                .configs
-                .get(&endpoint.path)
-                .map(|config| {
-                    (
-                        config.path_params_map(),
-                        config.query_params_map(),
-                        config.body.clone(),
-                    )
-                })
-                .unwrap_or_default()
+    // Spawn background task, watched so a panic doesn't leave the endpoint executing
+    let failed = request_failed(job, endpoint.key());
+    let watched = Arc::clone(&state);
+    let task_state = Arc::clone(&state);
+    let task = async move {
+        let state = task_state;
+        let _slot = wait_for_slot(&state, job).await;
+        let endpoint_key = endpoint.key();
+        let config = state
+            .read_lock()
+            .request
+            .configs
+            .get(&endpoint_key)
+            .cloned();
+
+        let (response, reauth_note) =
+            send_endpoint_request(&state, &endpoint, config.as_ref(), base_url).await;
+
+        let outcome = match &response.error_message {
+            Some(e) => format!("✗ {e}"),
+            None => response.status.to_string(),
         };
+        log_debug(&format!(
+            "{endpoint_key}: {outcome} in {} ms",
+            response.duration.as_millis()
+        ));
 
-        let full_url = match RequestUrlBuilder::new(base_url)
-            .set_path(endpoint.path)
-            .set_path_params(path_params)
-            .set_query_params(query_params)
-            .build()
+        // Store response and finish the job, unless it was cancelled
         {
-            Ok(url) => url,
-            Err(e) => {
-                // Handle error and return early
-                let mut s = state.write().unwrap();
-                s.request.executing_endpoint = None;
-                s.request.current_response =
-                    Some(ApiResponse::error(format!("Failed to build URL: {e}")));
+            let mut s = state.write_lock();
+            if s.request.finish_job(job).is_none() {
                 return;
             }
-        };
+            s.request
+                .finished_requests
+                .push(FinishedRequest::new(endpoint_key.clone(), &response));
+            s.request.record_response(endpoint_key.clone(), &response);
+            let response = EndpointResponse {
+                reauth_note,
+                ..response.into()
+            };
+            s.store_response(endpoint_key, Some(response));
+        }
+    };
+    let abort = tasks::spawn(watched, task, failed);
+    state.write_lock().request.set_job_abort(job, abort);
+}
 
-        // Convert method string to reqwest::Method
-        let method = match endpoint.method.to_uppercase().as_str() {
-            "GET" => reqwest::Method::GET,
-            "POST" => reqwest::Method::POST,
-            "PUT" => reqwest::Method::PUT,
-            "PATCH" => reqwest::Method::PATCH,
-            "DELETE" => reqwest::Method::DELETE,
-            _ => reqwest::Method::GET, // Default to GET for unknown methods
+/// Wait in the queue until the request may be sent, then mark its job as sent
+async fn wait_for_slot(state: &Arc<RwLock<AppState>>, job: u64) -> Option<OwnedSemaphorePermit> {
+    let slots = Arc::clone(&state.read_lock().request.slots);
+    let slot = slots.acquire_owned().await.ok();
+    state.write_lock().request.set_job_started(job);
+    slot
+}
+
+/// Show a request whose task panicked as failed, instead of executing forever
+fn request_failed(job: u64, endpoint_key: String) -> impl FnOnce(&mut AppState, String) {
+    move |state, message| {
+        state.request.finish_job(job);
+        let response = ApiResponse::error(format!("Request failed: {message}"));
+        state.store_response(endpoint_key, Some(response.into()));
+    }
+}
+
+/// Sends an endpoint's request with the given parameters and body and waits for the response
+///
+/// Returns the response and a note when it was retried after re-authenticating.
+pub async fn send_endpoint_request(
+    state: &Arc<RwLock<AppState>>,
+    endpoint: &ApiEndpoint,
+    config: Option<&RequestConfig>,
+    base_url: String,
+) -> (ApiResponse, Option<String>) {
+    // Templates are merged in as the request goes out, never into the stored config
+    let (config, mut headers) = {
+        let s = state.read_lock();
+        (
+            s.request.merged_config(endpoint, config),
+            s.request.template_headers(endpoint),
+        )
+    };
+    let config = config.as_ref();
+    // Header parameters are set after the template headers, so they win
+    headers.extend(config.map(RequestConfig::header_values).unwrap_or_default());
+
+    // Get path, query parameters, and body from request config
+    let (path_params, query_params, body) = config
+        .map(|config| {
+            (
+                config.path_params_map(),
+                config.query_params_map(),
+                config.body.clone(),
+            )
+        })
+        .unwrap_or_default();
+    let body = endpoint
+        .supports_body()
+        .then(|| RequestBody::encode(&endpoint.body_content_type(config), body));
+
+    let path = {
+        let rewrites = &state.read_lock().request.path_rewrites;
+        rewrite_path(rewrites, &base_url, &endpoint.path)
+    };
+
+    let full_url = match RequestUrlBuilder::new(base_url)
+        .set_path(path)
+        .set_path_params(path_params)
+        .set_query_params(query_params)
+        .build()
+    {
+        Ok(url) => url,
+        Err(e) => {
+            return (
+                ApiResponse::error(format!("Failed to build URL: {e}")),
+                None,
+            )
+        }
+    };
+
+    // Convert method string to reqwest::Method
+    let method = match endpoint.method.to_uppercase().as_str() {
+        "GET" => reqwest::Method::GET,
+        "POST" => reqwest::Method::POST,
+        "PUT" => reqwest::Method::PUT,
+        "PATCH" => reqwest::Method::PATCH,
+        "DELETE" => reqwest::Method::DELETE,
+        _ => reqwest::Method::GET, // Default to GET for unknown methods
+    };
+
+    execute_with_reauth(&full_url, method, state, body.as_ref(), &headers, true).await
+}
+
+/// Follows a link from a response with a GET, using the same auth as endpoint requests
+///
+/// The response replaces the current one; `executing_key` is the endpoint it is shown under.
+/// Credentials and plugin headers only go to links on the scheme, host and port of
+/// `base_url`: a link elsewhere, e.g. to a CDN, is followed without them.
+pub fn follow_link_background(
+    state: Arc<RwLock<AppState>>,
+    executing_key: String,
+    url: String,
+    base_url: Option<String>,
+) {
+    let authenticated = base_url.is_some_and(|base_url| same_origin(&url, &base_url));
+    let job = {
+        let mut s = state.write_lock();
+        s.store_response(executing_key.clone(), None);
+        s.request.start_job(executing_key.clone())
+    };
+    let failed = request_failed(job, executing_key.clone());
+
+    let watched = Arc::clone(&state);
+    let task_state = Arc::clone(&state);
+    let task = async move {
+        let state = task_state;
+        let _slot = wait_for_slot(&state, job).await;
+        let (response, reauth_note) =
+            execute_with_reauth(&url, reqwest::Method::GET, &state, None, &[], authenticated).await;
+
+        let mut s = state.write_lock();
+        if s.request.finish_job(job).is_none() {
+            return;
+        }
+        let response = EndpointResponse {
+            response,
+            followed_link: Some(url),
+            reauth_note,
         };
+        s.store_response(executing_key, Some(response));
+    };
+    let abort = tasks::spawn(watched, task, failed);
+    state.write_lock().request.set_job_abort(job, abort);
+}
 
-        // Build and execute request
-        let response = execute_request(&full_url, method, &state, body).await;
+/// Executes a request, re-authenticating and retrying once if it returns 401
+///
+/// Only applies when a token endpoint is configured; the note tells what happened.
+/// Without `authenticated`, the request goes out without credentials or plugin hooks
+/// and is not retried.
+async fn execute_with_reauth(
+    url: &str,
+    method: reqwest::Method,
+    state: &Arc<RwLock<AppState>>,
+    body: Option<&RequestBody>,
+    headers: &[(String, String)],
+    authenticated: bool,
+) -> (ApiResponse, Option<String>) {
+    let response = execute_request(url, method.clone(), state, body, headers, authenticated).await;
+
+    let refresh = state.read_lock().request.auth.refresh.clone();
+    let Some(refresh) = refresh.filter(|_| authenticated && response.status == 401) else {
+        return (response, None);
+    };
 
-        // Store response and clear executing flag
-        {
-            let mut s = state.write().unwrap();
-            s.request.executing_endpoint = None;
-            s.request.current_response = Some(response);
+    match auth::refresh_token(&refresh).await {
+        Ok(grant) => {
+            state.write_lock().request.auth.apply_grant(grant);
+            let retried = execute_request(url, method, state, body, headers, true).await;
+            (retried, Some("retried after re-auth".to_string()))
         }
-    });
+        Err(e) => (response, Some(format!("re-auth failed: {e}"))),
+    }
 }
 
 async fn execute_request(
     url: &str,
     method: reqwest::Method,
     state: &Arc<RwLock<AppState>>,
-    body: Option<String>,
+    body: Option<&RequestBody>,
+    headers: &[(String, String)],
+    authenticated: bool,
 ) -> ApiResponse {
     use std::time::Instant;
 
-    // Get auth token if available
-    let token = {
-        let s = state.read().unwrap();
-        s.request.auth.token.clone()
+    let (credential, plugins) = {
+        let s = state.read_lock();
+        let credential = s.request.auth.credential().filter(|_| authenticated);
+        let plugins = if authenticated {
+            s.request.plugins.clone()
+        } else {
+            PluginRegistry::default()
+        };
+        (credential, plugins)
+    };
+
+    let mut outgoing = OutgoingRequest {
+        method: method.to_string(),
+        url: url.to_string(),
+        headers: Vec::new(),
+        body: body.cloned(),
+    };
+    for (name, value) in headers {
+        outgoing.set_header(name, value.clone());
+    }
+    match &credential {
+        Some(Credential::Header(name, value)) => outgoing.set_header(name, value.clone()),
+        Some(Credential::Query(name, value)) => {
+            if let Ok(mut with_key) = Url::parse(&outgoing.url) {
+                with_key.query_pairs_mut().append_pair(name, value);
+                outgoing.url = with_key.to_string();
+            }
+        }
+        None => {}
+    }
+    // Plugin programs block until they exit, so they run off the async threads
+    let hook_plugins = plugins.clone();
+    let hook = tokio::task::spawn_blocking(move || {
+        let result = hook_plugins.on_request(&mut outgoing);
+        (outgoing, result)
+    })
+    .await;
+    let outgoing = match hook {
+        Ok((outgoing, Ok(()))) => outgoing,
+        Ok((_, Err(e))) => return ApiResponse::error(format!("Request cancelled: {e}")),
+        Err(e) => return ApiResponse::error(format!("Request cancelled: plugin failed: {e}")),
     };
 
     // Build request with the appropriate HTTP method
     let client = reqwest::Client::new();
-    let mut request_builder = client.request(method.clone(), url);
+    let method = reqwest::Method::from_bytes(outgoing.method.as_bytes()).unwrap_or(method);
+    let mut request_builder = client.request(method, &outgoing.url);
 
     // Add body for methods that support it
-    if method == reqwest::Method::POST
-        || method == reqwest::Method::PUT
-        || method == reqwest::Method::PATCH
-    {
-        request_builder = request_builder.header("Content-Type", "application/json");
-
-        // Use provided body or default to empty object
-        if let Some(body_str) = body {
-            request_builder = request_builder.body(body_str);
-        } else {
-            request_builder = request_builder.body("{}");
-        }
+    if let Some(body) = &outgoing.body {
+        request_builder = request_builder
+            .header("Content-Type", &body.content_type)
+            .body(body.content.clone());
     }
 
-    // Add bearer token if available
-    if let Some(token) = token {
-        request_builder = request_builder.bearer_auth(token);
+    for (name, value) in &outgoing.headers {
+        request_builder = request_builder.header(name, value);
     }
 
     // Start timing the request
     let start = Instant::now();
+    let started = chrono::Utc::now();
 
     // Execute request
-    match request_builder.send().await {
+    let mut response = match request_builder.send().await {
         Ok(response) => {
             let duration = start.elapsed(); // Capture duration immediately
 
@@ -197,13 +481,13 @@ async fn execute_request(
                 })
                 .collect();
 
-            // Get response body as text
-            match response.text().await {
-                Ok(body) => ApiResponse {
+            // Keep the bytes as received; the text view of binary bodies is made when needed
+            match response.bytes().await {
+                Ok(bytes) => ApiResponse {
                     status,
                     status_text,
                     headers,
-                    body,
+                    body: bytes.to_vec().into(),
                     duration, // Use actual measured duration
                     is_error: false,
                     error_message: None,
@@ -212,7 +496,7 @@ async fn execute_request(
                     status: 0,
                     status_text: String::new(),
                     headers: HashMap::new(),
-                    body: String::new(),
+                    body: ResponseBody::default(),
                     duration, // Even on error, show how long we waited
                     is_error: true,
                     error_message: Some(format!("Failed to read response body: {e}")),
@@ -222,18 +506,44 @@ async fn execute_request(
         Err(e) => {
             let duration = start.elapsed(); // Capture duration for failed requests too
 
+            // The server went away; refetch the spec in the background until it is back
+            if e.is_connect() {
+                state
+                    .write_lock()
+                    .data
+                    .reconnect
+                    .get_or_insert_with(Reconnect::start);
+            }
+
             // Network error or connection failure (didn't get HTTP response)
             ApiResponse {
                 status: 0,
                 status_text: String::new(),
                 headers: HashMap::new(),
-                body: String::new(),
+                body: ResponseBody::default(),
                 duration,
                 is_error: true,
                 error_message: Some(format!("Request failed: {e}")),
             }
         }
-    }
+    };
+
+    let hook = tokio::task::spawn_blocking(move || {
+        plugins.on_response(&outgoing, &mut response);
+        (outgoing, response)
+    })
+    .await;
+    let (outgoing, response) = match hook {
+        Ok(hooked) => hooked,
+        Err(e) => return ApiResponse::error(format!("Response hook failed: {e}")),
+    };
+    state.write_lock().request.record_traffic(TrafficEntry::new(
+        &outgoing,
+        &response,
+        started,
+        credential.as_ref(),
+    ));
+    response
 }
 
 #[cfg(test)]
@@ -421,6 +731,104 @@ mod tests {
         assert_eq!(url.unwrap(), "http://localhost:5000/users/%7Bid%7D");
     }
 
+    #[test]
+    fn test_rewrite_path() {
+        let rewrites = vec![
+            PathRewriteConfig {
+                base_url: "https://gateway.example.com/".to_string(),
+                strip_prefix: Some("/internal".to_string()),
+                add_prefix: Some("/api/v2/".to_string()),
+            },
+            PathRewriteConfig {
+                base_url: "https://".to_string(),
+                strip_prefix: None,
+                add_prefix: Some("/public".to_string()),
+            },
+        ];
+
+        let gateway = "https://gateway.example.com";
+        assert_eq!(
+            rewrite_path(&rewrites, gateway, "/internal/users/{id}"),
+            "/api/v2/users/{id}"
+        );
+        assert_eq!(
+            rewrite_path(&rewrites, gateway, "/health"),
+            "/api/v2/health"
+        );
+        assert_eq!(
+            rewrite_path(&rewrites, "https://staging.example.com", "/users"),
+            "/public/users"
+        );
+        assert_eq!(
+            rewrite_path(&rewrites, "http://localhost:5000", "/internal/users"),
+            "/internal/users"
+        );
+    }
+
+    #[test]
+    fn test_rewrite_path_matches_whole_host_port_and_segments() {
+        let rewrite = |base_url: &str| PathRewriteConfig {
+            base_url: base_url.to_string(),
+            strip_prefix: None,
+            add_prefix: Some("/v2".to_string()),
+        };
+        let applies = |rule: &str, base_url: &str| {
+            rewrite_path(&[rewrite(rule)], base_url, "/users") == "/v2/users"
+        };
+
+        assert!(applies("http://host:80", "http://host"));
+        assert!(!applies("http://host:80", "http://host:8080"));
+        assert!(!applies("http://host", "http://host:8080"));
+        assert!(applies(
+            "https://API.example.com",
+            "https://api.example.com/"
+        ));
+        assert!(!applies(
+            "https://api.example.com",
+            "https://api.example.com.evil.io"
+        ));
+        assert!(!applies(
+            "https://api.example.com",
+            "http://api.example.com"
+        ));
+        assert!(applies(
+            "https://gw.example.com/svc",
+            "https://gw.example.com/svc/v1"
+        ));
+        assert!(!applies(
+            "https://gw.example.com/svc",
+            "https://gw.example.com/svc2"
+        ));
+    }
+
+    #[test]
+    fn test_encode_form_bodies() {
+        let fields = Some(r#"{"name": "Ada Lovelace", "age": 36}"#.to_string());
+
+        let form = RequestBody::encode("application/x-www-form-urlencoded", fields.clone());
+        assert_eq!(form.content, "age=36&name=Ada+Lovelace");
+
+        let multipart = RequestBody::encode("multipart/form-data", fields);
+        let boundary = multipart.content_type.split("boundary=").nth(1).unwrap();
+        assert_eq!(
+            multipart.content,
+            format!(
+                "--{boundary}\r\nContent-Disposition: form-data; name=\"age\"\r\n\r\n36\r\n\
+                 --{boundary}\r\nContent-Disposition: form-data; name=\"name\"\r\n\r\nAda Lovelace\r\n\
+                 --{boundary}--\r\n"
+            )
+        );
+
+        assert_eq!(RequestBody::encode("application/json", None).content, "{}");
+        assert_eq!(
+            RequestBody::encode("application/xml", Some("<a/>".to_string())),
+            RequestBody {
+                content_type: "application/xml".to_string(),
+                content: "<a/>".to_string(),
+            }
+        );
+    }
+
     #[test]
     fn test_build_url_invalid_base() {
         let url = build_url_with_params(
@@ -432,4 +840,104 @@ mod tests {
         assert!(url.is_err());
         assert!(url.unwrap_err().contains("Invalid URL"));
     }
+
+    #[tokio::test]
+    async fn test_cancel_all_aborts_queued_and_running_jobs() {
+        // A server that accepts connections but never answers keeps a request running
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let server = tokio::spawn(async move {
+            let mut connections = Vec::new();
+            while let Ok((connection, _)) = listener.accept().await {
+                connections.push(connection);
+            }
+        });
+
+        let mut state = AppState::default();
+        state.request.slots = Arc::new(tokio::sync::Semaphore::new(1));
+        let slots = Arc::clone(&state.request.slots);
+        let state = Arc::new(RwLock::new(state));
+        for path in ["/running", "/queued"] {
+            let endpoint = ApiEndpoint {
+                method: "GET".to_string(),
+                path: path.to_string(),
+                ..Default::default()
+            };
+            execute_request_background(state.clone(), endpoint, base_url.clone());
+        }
+        assert!(eventually(|| state.read_lock().request.queued_jobs() == 1).await);
+        assert_eq!(state.read_lock().request.jobs.len(), 2);
+        assert_eq!(slots.available_permits(), 0);
+
+        crate::actions::apply_action(
+            crate::actions::AppAction::CancelAllJobs,
+            &mut state.write_lock(),
+        );
+        {
+            let s = state.read_lock();
+            assert!(s.request.jobs.is_empty());
+            assert!(!s.request.is_executing("GET /running"));
+            assert!(!s.request.is_executing("GET /queued"));
+        }
+
+        // The aborted tasks give their slot back once they are dropped
+        assert!(eventually(|| slots.available_permits() == 1).await);
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn test_followed_links_get_credentials_only_on_the_api_origin() {
+        // A server answering every request with the request it got
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let api = format!("http://{}", listener.local_addr().unwrap());
+        let server = tokio::spawn(async move {
+            use tokio::io::{AsyncReadExt, AsyncWriteExt};
+            while let Ok((mut connection, _)) = listener.accept().await {
+                let mut head = vec![0; 4096];
+                let n = connection.read(&mut head).await.unwrap_or(0);
+                let head = String::from_utf8_lossy(&head[..n]).to_lowercase();
+                let reply = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{head}",
+                    head.len()
+                );
+                let _ = connection.write_all(reply.as_bytes()).await;
+            }
+        });
+
+        let mut state = AppState::default();
+        state.request.auth.set_token("secret-token".to_string());
+        let state = Arc::new(RwLock::new(state));
+        let follow = |base_url: &str| {
+            let state = Arc::clone(&state);
+            let url = format!("{api}/orders/1");
+            let base_url = Some(base_url.to_string());
+            async move {
+                let key = "GET /orders".to_string();
+                follow_link_background(Arc::clone(&state), key.clone(), url, base_url);
+                assert!(
+                    eventually(|| state.read_lock().request.responses.contains_key(&key)).await
+                );
+                let s = state.read_lock();
+                s.request.responses[&key].response.body.text().to_string()
+            }
+        };
+
+        let same_origin = follow(&format!("{api}/api")).await;
+        assert!(same_origin.contains("authorization: bearer secret-token"));
+        let elsewhere = follow("http://api.example.com").await;
+        assert!(elsewhere.starts_with("get /orders/1"));
+        assert!(!elsewhere.contains("secret-token"));
+        server.abort();
+    }
+
+    /// Whether `done` holds within a second, letting background tasks run meanwhile
+    async fn eventually(done: impl Fn() -> bool) -> bool {
+        for _ in 0..100 {
+            if done() {
+                return true;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        done()
+    }
 }
@@ -0,0 +1,303 @@
+//! Readable outlines of JSON schemas
+//!
+//! The Request tab shows the body an operation expects as an indented outline
+//! of its fields instead of the raw schema, e.g. `name: string (required)` or
+//! `tags: [string]`, so the shape is known before the body editor is opened.
+//!
+//! Composed schemas are spelled out: `oneOf`/`anyOf` as their alternatives
+//! (`one of: Cat | Dog`), each listed with its own fields, and `allOf` parts
+//! merged into one object. Outlines are drawn from the [`SchemaModel`] built
+//! when the spec is parsed, which names parts after the schemas they reference.
+
+use crate::types::{SchemaModel, SchemaShape};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// Nesting shown before an outline is cut, also guarding against huge schemas
+const MAX_OUTLINE_DEPTH: usize = 6;
+
+/// Enum values listed in a type summary before the rest are elided
+const MAX_ENUM_VALUES: usize = 5;
+
+/// Alternatives of a `oneOf`/`anyOf` named in a type summary before the rest are elided
+const MAX_VARIANTS: usize = 4;
+
+/// One field of a schema outline
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutlineField {
+    /// Nesting level, 0 for the top-level fields
+    pub depth: usize,
+    pub name: String,
+    /// Type as shown, e.g. `string/date-time`, `[object]` or `"asc" | "desc"`
+    pub summary: String,
+    pub required: bool,
+    pub description: Option<String>,
+    /// Heads one alternative of a `oneOf`/`anyOf`, its fields nested below; `summary`
+    /// is then the keyword's label, e.g. `one of`
+    pub variant: bool,
+}
+
+/// The fields of an object schema (or of an array's items), nested fields after their parent
+pub fn outline(schema: &SchemaModel) -> Vec<OutlineField> {
+    let mut fields = Vec::new();
+    push_fields(schema, 0, &mut fields);
+    fields
+}
+
+fn push_fields(schema: &SchemaModel, depth: usize, fields: &mut Vec<OutlineField>) {
+    if depth >= MAX_OUTLINE_DEPTH {
+        return;
+    }
+    let schema = element_schema(schema);
+    let (properties, required) = object_properties(schema);
+    for (name, property) in properties {
+        fields.push(OutlineField {
+            depth,
+            name: name.to_string(),
+            summary: type_summary(property),
+            required: required.contains(&name),
+            description: property.description.clone(),
+            variant: false,
+        });
+        push_fields(property, depth + 1, fields);
+    }
+    if let Some((label, variants)) = alternatives(schema) {
+        for variant in variants {
+            fields.push(OutlineField {
+                depth,
+                name: variant_name(variant),
+                summary: label.to_string(),
+                required: false,
+                description: variant.description.clone(),
+                variant: true,
+            });
+            push_fields(variant, depth + 1, fields);
+        }
+    }
+}
+
+/// The alternatives of a `oneOf` or `anyOf` schema (or `allOf` part), with the keyword's label
+fn alternatives(schema: &SchemaModel) -> Option<(&'static str, &[SchemaModel])> {
+    match &schema.shape {
+        SchemaShape::OneOf(variants) => Some(("one of", variants)),
+        SchemaShape::AnyOf(variants) => Some(("any of", variants)),
+        SchemaShape::AllOf(parts) => parts.iter().find_map(alternatives),
+        _ => None,
+    }
+}
+
+/// How a part of a composed schema is named: its title or the name of the schema it
+/// was referenced as, else its type
+fn variant_name(schema: &SchemaModel) -> String {
+    match schema.name() {
+        Some(name) => name.to_string(),
+        None => type_summary(schema),
+    }
+}
+
+/// Names joined by `separator`, elided after `MAX_VARIANTS`
+fn variant_names(parts: &[SchemaModel], separator: &str) -> String {
+    let mut names: Vec<String> = parts.iter().take(MAX_VARIANTS).map(variant_name).collect();
+    if parts.len() > MAX_VARIANTS {
+        names.push("…".to_string());
+    }
+    names.join(separator)
+}
+
+/// The innermost item schema of (nested) arrays
+fn element_schema(schema: &SchemaModel) -> &SchemaModel {
+    match &schema.shape {
+        SchemaShape::Array(items) => element_schema(items),
+        _ => schema,
+    }
+}
+
+/// Properties by name and required names of an object schema, merging `allOf` parts
+fn object_properties(schema: &SchemaModel) -> (BTreeMap<&str, &SchemaModel>, Vec<&str>) {
+    match &schema.shape {
+        SchemaShape::Object {
+            properties,
+            required,
+        } => (
+            properties
+                .iter()
+                .map(|(name, property)| (name.as_str(), property))
+                .collect(),
+            required.iter().map(String::as_str).collect(),
+        ),
+        SchemaShape::AllOf(parts) => {
+            let mut properties = BTreeMap::new();
+            let mut required = Vec::new();
+            for part in parts {
+                let (part_properties, part_required) = object_properties(part);
+                properties.extend(part_properties);
+                required.extend(part_required);
+            }
+            (properties, required)
+        }
+        _ => (BTreeMap::new(), Vec::new()),
+    }
+}
+
+/// A schema's type in a few characters, e.g. `integer/int64`, `[string]` or `"a" | "b"`
+pub fn type_summary(schema: &SchemaModel) -> String {
+    match &schema.shape {
+        SchemaShape::Enum(values) => enum_summary(values),
+        SchemaShape::OneOf(variants) => format!("one of: {}", variant_names(variants, " | ")),
+        SchemaShape::AnyOf(variants) => format!("any of: {}", variant_names(variants, " | ")),
+        // A lone part usually just wraps a reference, e.g. to add a description
+        SchemaShape::AllOf(parts) => match parts.as_slice() {
+            [part] => variant_name(part),
+            _ if parts.iter().any(|part| part.name().is_some()) => {
+                format!("all of: {}", variant_names(parts, " & "))
+            }
+            // Own properties next to a `oneOf`/`anyOf`
+            _ => parts
+                .iter()
+                .find(|part| alternatives(part).is_some())
+                .map_or_else(|| "object".to_string(), type_summary),
+        },
+        SchemaShape::Array(items) => format!("[{}]", type_summary(items)),
+        SchemaShape::Object { .. } => "object".to_string(),
+        SchemaShape::Scalar { kind, format } => match format {
+            Some(format) => format!("{kind}/{format}"),
+            None => kind.clone(),
+        },
+        SchemaShape::Any => "any".to_string(),
+    }
+}
+
+/// Allowed values as alternatives, e.g. `"asc" | "desc"`, elided after a few
+pub fn enum_summary(values: &[Value]) -> String {
+    let mut shown: Vec<String> = values
+        .iter()
+        .take(MAX_ENUM_VALUES)
+        .map(Value::to_string)
+        .collect();
+    if values.len() > MAX_ENUM_VALUES {
+        shown.push("…".to_string());
+    }
+    shown.join(" | ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::swagger::parse::schema_model;
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    fn model(schema: Value) -> SchemaModel {
+        schema_model(&schema, &HashMap::new())
+    }
+
+    #[test]
+    fn test_outline() {
+        let schema = model(json!({
+            "type": "object",
+            "required": ["name"],
+            "properties": {
+                "name": { "type": "string", "description": "Display name" },
+                "born": { "type": "string", "format": "date" },
+                "sort": { "type": "string", "enum": ["asc", "desc"] },
+                "tags": { "type": "array", "items": { "type": "string" } },
+                "pets": {
+                    "type": "array",
+                    "items": {
+                        "allOf": [
+                            { "properties": { "id": { "type": "integer" } }, "required": ["id"] },
+                            { "properties": { "kind": { "type": ["string", "null"] } } }
+                        ]
+                    }
+                }
+            }
+        }));
+
+        let lines: Vec<String> = outline(&schema)
+            .iter()
+            .map(|field| {
+                let required = if field.required { " *" } else { "" };
+                format!(
+                    "{}{}: {}{required}",
+                    "  ".repeat(field.depth),
+                    field.name,
+                    field.summary
+                )
+            })
+            .collect();
+        assert_eq!(
+            lines,
+            vec![
+                "born: string/date",
+                "name: string *",
+                "pets: [object]",
+                "  id: integer *",
+                "  kind: string",
+                "sort: \"asc\" | \"desc\"",
+                "tags: [string]",
+            ]
+        );
+        assert_eq!(
+            outline(&schema)[1].description.as_deref(),
+            Some("Display name")
+        );
+        assert!(outline(&model(json!({ "type": "string" }))).is_empty());
+    }
+
+    #[test]
+    fn test_composed_schemas() {
+        // Parts are named by their title, else by the schema they reference
+        let schemas = HashMap::from([(
+            "Cat".to_string(),
+            json!({ "properties": { "meows": { "type": "boolean" } } }),
+        )]);
+        let cat = json!({ "$ref": "#/components/schemas/Cat" });
+        let dog = json!({ "title": "Dog", "properties": { "barks": { "type": "boolean" } } });
+        let schema = schema_model(
+            &json!({
+                "properties": {
+                    "pet": { "oneOf": [cat, dog] },
+                    "id": { "anyOf": [{ "type": "integer" }, { "type": "string", "format": "uuid" }] }
+                }
+            }),
+            &schemas,
+        );
+
+        let lines: Vec<String> = outline(&schema)
+            .iter()
+            .map(|field| {
+                let indent = "  ".repeat(field.depth);
+                if field.variant {
+                    format!("{indent}{} {}", field.summary, field.name)
+                } else {
+                    format!("{indent}{}: {}", field.name, field.summary)
+                }
+            })
+            .collect();
+        assert_eq!(
+            lines,
+            vec![
+                "id: any of: integer | string/uuid",
+                "  any of integer",
+                "  any of string/uuid",
+                "pet: one of: Cat | Dog",
+                "  one of Cat",
+                "    meows: boolean",
+                "  one of Dog",
+                "    barks: boolean",
+            ]
+        );
+
+        let summary = |schema: Value| type_summary(&schema_model(&schema, &schemas));
+        assert_eq!(summary(json!({ "allOf": [cat] })), "Cat");
+        assert_eq!(
+            summary(json!({ "allOf": [cat, { "properties": {} }] })),
+            "all of: Cat & object"
+        );
+        assert_eq!(summary(json!({ "allOf": [{}, {}] })), "object");
+        assert_eq!(
+            summary(json!({ "oneOf": [cat, dog], "properties": { "id": {} } })),
+            "one of: Cat | Dog"
+        );
+    }
+}
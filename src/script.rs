@@ -0,0 +1,436 @@
+//! Small expression language for inspecting state from the console
+//!
+//! An input is either an expression or an assignment `name = expression`.
+//! Expressions are JSON literals (`1`, `"text"`, `true`, `null`), names of
+//! values in scope, field and index access (`response.body.items[0].id`,
+//! `headers["content-type"]`) and function calls:
+//!
+//! - `len(x)`: length of an array, object or string
+//! - `keys(x)`: field names of an object
+//! - `first(x)` / `last(x)`: first or last element of an array
+//! - `where(list, field, value)`: elements of a list whose `field` equals `value`
+//! - `map(list, field)`: the `field` of each element of a list
+
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Values an input can refer to by name
+#[derive(Debug, Clone, Default)]
+pub struct Scope {
+    pub values: HashMap<String, Value>,
+}
+
+impl Scope {
+    pub fn set(&mut self, name: &str, value: Value) {
+        self.values.insert(name.to_string(), value);
+    }
+}
+
+/// What an input did: produced a value or assigned one
+#[derive(Debug, Clone, PartialEq)]
+pub enum Outcome {
+    Value(Value),
+    Assigned(String, Value),
+}
+
+impl Outcome {
+    /// Text shown for the outcome: strings without quotes, lists and objects pretty-printed
+    pub fn display(&self) -> String {
+        let text = |value: &Value| match value {
+            Value::String(text) => text.clone(),
+            Value::Array(_) | Value::Object(_) => {
+                serde_json::to_string_pretty(value).unwrap_or_default()
+            }
+            other => other.to_string(),
+        };
+        match self {
+            Self::Value(value) => text(value),
+            Self::Assigned(name, value) => format!("{name} = {}", text(value)),
+        }
+    }
+}
+
+/// Parse and evaluate one input, assigning into `scope` when it is an assignment
+pub fn run(input: &str, scope: &mut Scope) -> Result<Outcome, String> {
+    let tokens = tokenize(input)?;
+    let mut parser = Parser { tokens, pos: 0 };
+
+    let assignment = match (parser.tokens.first(), parser.tokens.get(1)) {
+        (Some(Token::Ident(name)), Some(Token::Assign)) => Some(name.clone()),
+        _ => None,
+    };
+    if assignment.is_some() {
+        parser.pos = 2;
+    }
+
+    let expr = parser.expression()?;
+    if let Some(token) = parser.tokens.get(parser.pos) {
+        return Err(format!("Unexpected {token:?}"));
+    }
+    let value = eval(&expr, scope)?;
+
+    Ok(match assignment {
+        Some(name) => {
+            scope.set(&name, value.clone());
+            Outcome::Assigned(name, value)
+        }
+        None => Outcome::Value(value),
+    })
+}
+
+/// One evaluated console input with its output or error
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConsoleEntry {
+    pub input: String,
+    pub output: Result<String, String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Literal(Value),
+    Dot,
+    Comma,
+    Assign,
+    LParen,
+    RParen,
+    LBracket,
+    RBracket,
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = input.char_indices().peekable();
+
+    while let Some(&(start, c)) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '.' => {
+                chars.next();
+                tokens.push(Token::Dot);
+            }
+            ',' => {
+                chars.next();
+                tokens.push(Token::Comma);
+            }
+            '=' => {
+                chars.next();
+                tokens.push(Token::Assign);
+            }
+            '(' | ')' | '[' | ']' => {
+                chars.next();
+                tokens.push(match c {
+                    '(' => Token::LParen,
+                    ')' => Token::RParen,
+                    '[' => Token::LBracket,
+                    _ => Token::RBracket,
+                });
+            }
+            '"' => {
+                chars.next();
+                let mut text = String::new();
+                loop {
+                    match chars.next() {
+                        Some((_, '"')) => break,
+                        Some((_, '\\')) => match chars.next() {
+                            Some((_, 'n')) => text.push('\n'),
+                            Some((_, escaped)) => text.push(escaped),
+                            None => return Err("Unterminated string".to_string()),
+                        },
+                        Some((_, other)) => text.push(other),
+                        None => return Err("Unterminated string".to_string()),
+                    }
+                }
+                tokens.push(Token::Literal(Value::String(text)));
+            }
+            c if c.is_ascii_digit() || c == '-' => {
+                let mut end = start;
+                while let Some(&(i, c)) = chars.peek() {
+                    if !(c.is_ascii_digit() || (c == '-' && i == start) || (c == '.' && i > start))
+                    {
+                        break;
+                    }
+                    end = i + c.len_utf8();
+                    chars.next();
+                }
+                let number: serde_json::Number = input[start..end]
+                    .parse()
+                    .map_err(|_| format!("Invalid number: {}", &input[start..end]))?;
+                tokens.push(Token::Literal(Value::Number(number)));
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let mut end = start;
+                while let Some(&(i, c)) = chars.peek() {
+                    if !(c.is_alphanumeric() || c == '_') {
+                        break;
+                    }
+                    end = i + c.len_utf8();
+                    chars.next();
+                }
+                tokens.push(match &input[start..end] {
+                    "true" => Token::Literal(Value::Bool(true)),
+                    "false" => Token::Literal(Value::Bool(false)),
+                    "null" => Token::Literal(Value::Null),
+                    name => Token::Ident(name.to_string()),
+                });
+            }
+            other => return Err(format!("Unexpected character '{other}'")),
+        }
+    }
+    Ok(tokens)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    Literal(Value),
+    Name(String),
+    Field(Box<Expr>, String),
+    Index(Box<Expr>, Box<Expr>),
+    Call(String, Vec<Expr>),
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn expect(&mut self, expected: Token) -> Result<(), String> {
+        match self.next() {
+            Some(token) if token == expected => Ok(()),
+            Some(token) => Err(format!("Expected {expected:?}, found {token:?}")),
+            None => Err(format!("Expected {expected:?}")),
+        }
+    }
+
+    fn expression(&mut self) -> Result<Expr, String> {
+        let mut expr = match self.next() {
+            Some(Token::Literal(value)) => Expr::Literal(value),
+            Some(Token::Ident(name)) if self.peek() == Some(&Token::LParen) => {
+                self.pos += 1;
+                let mut args = Vec::new();
+                if self.peek() != Some(&Token::RParen) {
+                    args.push(self.expression()?);
+                    while self.peek() == Some(&Token::Comma) {
+                        self.pos += 1;
+                        args.push(self.expression()?);
+                    }
+                }
+                self.expect(Token::RParen)?;
+                Expr::Call(name, args)
+            }
+            Some(Token::Ident(name)) => Expr::Name(name),
+            Some(Token::LParen) => {
+                let inner = self.expression()?;
+                self.expect(Token::RParen)?;
+                inner
+            }
+            Some(token) => return Err(format!("Unexpected {token:?}")),
+            None => return Err("Expected an expression".to_string()),
+        };
+
+        loop {
+            match self.peek() {
+                Some(Token::Dot) => {
+                    self.pos += 1;
+                    match self.next() {
+                        Some(Token::Ident(field)) => expr = Expr::Field(Box::new(expr), field),
+                        _ => return Err("Expected a field name after '.'".to_string()),
+                    }
+                }
+                Some(Token::LBracket) => {
+                    self.pos += 1;
+                    let index = self.expression()?;
+                    self.expect(Token::RBracket)?;
+                    expr = Expr::Index(Box::new(expr), Box::new(index));
+                }
+                _ => return Ok(expr),
+            }
+        }
+    }
+}
+
+fn eval(expr: &Expr, scope: &Scope) -> Result<Value, String> {
+    match expr {
+        Expr::Literal(value) => Ok(value.clone()),
+        Expr::Name(name) => scope
+            .values
+            .get(name)
+            .cloned()
+            .ok_or_else(|| format!("Unknown name: {name}")),
+        Expr::Field(target, field) => Ok(eval(target, scope)?
+            .get(field)
+            .cloned()
+            .unwrap_or(Value::Null)),
+        Expr::Index(target, index) => {
+            let target = eval(target, scope)?;
+            Ok(match eval(index, scope)? {
+                Value::Number(n) => n
+                    .as_u64()
+                    .and_then(|i| target.get(i as usize))
+                    .cloned()
+                    .unwrap_or(Value::Null),
+                Value::String(key) => target.get(&key).cloned().unwrap_or(Value::Null),
+                other => return Err(format!("Cannot index with {other}")),
+            })
+        }
+        Expr::Call(name, args) => {
+            let args = args
+                .iter()
+                .map(|arg| eval(arg, scope))
+                .collect::<Result<Vec<_>, _>>()?;
+            call(name, &args)
+        }
+    }
+}
+
+fn call(name: &str, args: &[Value]) -> Result<Value, String> {
+    let arity = |n: usize| {
+        if args.len() == n {
+            Ok(())
+        } else {
+            Err(format!("{name}() takes {n} argument(s)"))
+        }
+    };
+
+    match name {
+        "len" => {
+            arity(1)?;
+            match &args[0] {
+                Value::Array(items) => Ok(items.len().into()),
+                Value::Object(fields) => Ok(fields.len().into()),
+                Value::String(text) => Ok(text.chars().count().into()),
+                other => Err(format!("len() of {other}")),
+            }
+        }
+        "keys" => {
+            arity(1)?;
+            match &args[0] {
+                Value::Object(fields) => Ok(fields.keys().cloned().collect::<Vec<_>>().into()),
+                other => Err(format!("keys() of {other}")),
+            }
+        }
+        "first" | "last" => {
+            arity(1)?;
+            let items = args[0]
+                .as_array()
+                .ok_or_else(|| format!("{name}() of {}", args[0]))?;
+            let item = if name == "first" {
+                items.first()
+            } else {
+                items.last()
+            };
+            Ok(item.cloned().unwrap_or(Value::Null))
+        }
+        "where" => {
+            arity(3)?;
+            let (items, field) = list_and_field(name, &args[0], &args[1])?;
+            Ok(Value::Array(
+                items
+                    .iter()
+                    .filter(|item| item.get(field) == Some(&args[2]))
+                    .cloned()
+                    .collect(),
+            ))
+        }
+        "map" => {
+            arity(2)?;
+            let (items, field) = list_and_field(name, &args[0], &args[1])?;
+            Ok(Value::Array(
+                items
+                    .iter()
+                    .map(|item| item.get(field).cloned().unwrap_or(Value::Null))
+                    .collect(),
+            ))
+        }
+        _ => Err(format!("Unknown function: {name}()")),
+    }
+}
+
+fn list_and_field<'a>(
+    name: &str,
+    list: &'a Value,
+    field: &'a Value,
+) -> Result<(&'a Vec<Value>, &'a str), String> {
+    let items = list
+        .as_array()
+        .ok_or_else(|| format!("{name}() expects a list, got {list}"))?;
+    let field = field
+        .as_str()
+        .ok_or_else(|| format!("{name}() expects a field name, got {field}"))?;
+    Ok((items, field))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn scope() -> Scope {
+        let mut scope = Scope::default();
+        scope.set(
+            "endpoints",
+            json!([
+                { "method": "GET", "path": "/users" },
+                { "method": "POST", "path": "/users" },
+                { "method": "GET", "path": "/orders" }
+            ]),
+        );
+        scope.set(
+            "response",
+            json!({ "status": 200, "body": { "items": [{ "id": 7 }] } }),
+        );
+        scope
+    }
+
+    fn value(input: &str, scope: &mut Scope) -> Value {
+        match run(input, scope).unwrap() {
+            Outcome::Value(value) | Outcome::Assigned(_, value) => value,
+        }
+    }
+
+    #[test]
+    fn test_expressions() {
+        let mut scope = scope();
+        assert_eq!(value("len(endpoints)", &mut scope), json!(3));
+        assert_eq!(value("response.body.items[0].id", &mut scope), json!(7));
+        assert_eq!(value("response[\"status\"]", &mut scope), json!(200));
+        assert_eq!(
+            value(
+                "map(where(endpoints, \"method\", \"GET\"), \"path\")",
+                &mut scope
+            ),
+            json!(["/users", "/orders"])
+        );
+        assert_eq!(value("last(endpoints).method", &mut scope), json!("GET"));
+        assert_eq!(value("response.missing.field", &mut scope), Value::Null);
+    }
+
+    #[test]
+    fn test_assignment_and_errors() {
+        let mut scope = scope();
+        assert_eq!(
+            run("id = response.body.items[0].id", &mut scope),
+            Ok(Outcome::Assigned("id".to_string(), json!(7)))
+        );
+        assert_eq!(value("id", &mut scope), json!(7));
+
+        assert!(run("nope", &mut scope).is_err());
+        assert!(run("len(1, 2)", &mut scope).is_err());
+        assert!(run("response.", &mut scope).is_err());
+        assert!(run("\"open", &mut scope).is_err());
+        assert!(run("a b", &mut scope).is_err());
+    }
+}
@@ -0,0 +1,247 @@
+//! Base URLs from the spec's `servers` list
+//!
+//! Server URLs may be templates like `https://{region}.api.example.com/{basePath}`.
+//! The server form lets the user pick a server and fill in its variables (from
+//! their enum choices or free text, starting at the defaults), and the built URL
+//! becomes the base URL for requests.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use url::Url;
+
+/// An entry of the spec's `servers` list
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SpecServer {
+    pub url: String,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub variables: HashMap<String, ServerVariable>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ServerVariable {
+    pub default: Option<String>,
+    #[serde(rename = "enum", default)]
+    pub choices: Vec<String>,
+    pub description: Option<String>,
+}
+
+impl SpecServer {
+    /// Variable names in the order they appear in the URL template
+    pub fn variable_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = Vec::new();
+        let mut rest = self.url.as_str();
+        while let Some(start) = rest.find('{') {
+            let Some(len) = rest[start..].find('}') else {
+                break;
+            };
+            let name = &rest[start + 1..start + len];
+            if !names.iter().any(|known| known == name) {
+                names.push(name.to_string());
+            }
+            rest = &rest[start + len + 1..];
+        }
+        names
+    }
+
+    /// The URL with each variable at its default
+    pub fn default_url(&self) -> String {
+        let defaults: HashMap<String, String> = self
+            .variables
+            .iter()
+            .filter_map(|(name, variable)| Some((name.clone(), variable.default.clone()?)))
+            .collect();
+        self.build_url(&defaults)
+    }
+
+    /// The URL with each `{variable}` replaced by its value
+    pub fn build_url(&self, values: &HashMap<String, String>) -> String {
+        values.iter().fold(self.url.clone(), |url, (name, value)| {
+            url.replace(&format!("{{{name}}}"), value)
+        })
+    }
+}
+
+/// Make relative server URLs (e.g. `/api/v1`) absolute against the spec URL
+pub fn resolve_servers(servers: Vec<SpecServer>, spec_url: &str) -> Vec<SpecServer> {
+    let Ok(spec_url) = Url::parse(spec_url) else {
+        return servers;
+    };
+    servers
+        .into_iter()
+        .map(|mut server| {
+            if !server.url.contains("://") {
+                if let Ok(absolute) = spec_url.join(&server.url) {
+                    // join percent-encodes the braces of template variables
+                    server.url = absolute.to_string().replace("%7B", "{").replace("%7D", "}");
+                }
+            }
+            server
+        })
+        .collect()
+}
+
+/// One variable input of the server form
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ServerFormField {
+    pub name: String,
+    pub value: String,
+    /// Allowed values; the field is cycled through them instead of typed into
+    pub choices: Vec<String>,
+    pub description: Option<String>,
+}
+
+/// State of the server form: the picked server and its variable values
+///
+/// Field 0 is the server itself, variables follow.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ServerForm {
+    pub servers: Vec<SpecServer>,
+    pub server_index: usize,
+    pub fields: Vec<ServerFormField>,
+    pub active_field: usize,
+}
+
+impl ServerForm {
+    pub fn new(servers: Vec<SpecServer>) -> Self {
+        let mut form = Self {
+            servers,
+            ..Self::default()
+        };
+        form.select_server(0);
+        form
+    }
+
+    pub fn server(&self) -> Option<&SpecServer> {
+        self.servers.get(self.server_index)
+    }
+
+    /// Switch to a server, resetting the variables to its defaults
+    fn select_server(&mut self, index: usize) {
+        self.server_index = index;
+        self.fields = self
+            .server()
+            .map(|server| {
+                server
+                    .variable_names()
+                    .into_iter()
+                    .map(|name| {
+                        let variable = server.variables.get(&name).cloned().unwrap_or_default();
+                        ServerFormField {
+                            value: variable
+                                .default
+                                .or_else(|| variable.choices.first().cloned())
+                                .unwrap_or_default(),
+                            name,
+                            choices: variable.choices,
+                            description: variable.description,
+                        }
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+    }
+
+    /// Variable field that has focus (none while the server row is focused)
+    pub fn active_variable_mut(&mut self) -> Option<&mut ServerFormField> {
+        let index = self.active_field.checked_sub(1)?;
+        self.fields.get_mut(index)
+    }
+
+    pub fn next_field(&mut self) {
+        self.active_field = (self.active_field + 1) % (self.fields.len() + 1);
+    }
+
+    pub fn prev_field(&mut self) {
+        self.active_field = (self.active_field + self.fields.len()) % (self.fields.len() + 1);
+    }
+
+    /// Step the focused row through its choices: the servers, or the variable's enum
+    pub fn cycle(&mut self, forward: bool) {
+        let step = |index: usize, len: usize| {
+            if forward {
+                (index + 1) % len
+            } else {
+                (index + len - 1) % len
+            }
+        };
+
+        if self.active_field == 0 {
+            if self.servers.len() > 1 {
+                self.select_server(step(self.server_index, self.servers.len()));
+            }
+        } else if let Some(field) = self.active_variable_mut() {
+            if !field.choices.is_empty() {
+                let current = field.choices.iter().position(|c| *c == field.value);
+                let next = current.map_or(0, |index| step(index, field.choices.len()));
+                field.value = field.choices[next].clone();
+            }
+        }
+    }
+
+    /// Base URL built from the server and the variable values
+    pub fn url(&self) -> String {
+        let values: HashMap<String, String> = self
+            .fields
+            .iter()
+            .map(|field| (field.name.clone(), field.value.clone()))
+            .collect();
+        self.server()
+            .map(|server| server.build_url(&values))
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn templated_server() -> SpecServer {
+        serde_json::from_value(serde_json::json!({
+            "url": "https://{region}.api.example.com/{basePath}",
+            "variables": {
+                "basePath": { "default": "v2" },
+                "region": { "default": "eu", "enum": ["us", "eu", "ap"] }
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_server_form_builds_url() {
+        let plain = SpecServer {
+            url: "https://staging.example.com".to_string(),
+            ..Default::default()
+        };
+        let mut form = ServerForm::new(vec![templated_server(), plain]);
+
+        let names: Vec<&str> = form.fields.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, vec!["region", "basePath"]);
+        assert_eq!(form.url(), "https://eu.api.example.com/v2");
+
+        form.next_field();
+        form.cycle(true);
+        form.cycle(true);
+        assert_eq!(form.url(), "https://us.api.example.com/v2");
+
+        form.next_field();
+        form.active_variable_mut().unwrap().value = "beta".to_string();
+        assert_eq!(form.url(), "https://us.api.example.com/beta");
+
+        form.next_field();
+        assert_eq!(form.active_field, 0);
+        form.cycle(false);
+        assert_eq!(form.url(), "https://staging.example.com");
+        assert!(form.fields.is_empty());
+    }
+
+    #[test]
+    fn test_resolve_relative_servers() {
+        let servers = vec![SpecServer {
+            url: "/{version}/api".to_string(),
+            ..Default::default()
+        }];
+        let resolved = resolve_servers(servers, "https://example.com/docs/openapi.json");
+        assert_eq!(resolved[0].url, "https://example.com/{version}/api");
+    }
+}
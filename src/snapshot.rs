@@ -0,0 +1,137 @@
+//! Named response snapshots and line diffs against them
+//!
+//! A snapshot keeps the formatted body of a response under a name (e.g.
+//! "before migration") so a later response of the same endpoint can be
+//! compared with it line by line.
+
+use crate::types::ApiResponse;
+use chrono::{Local, SecondsFormat};
+use serde::{Deserialize, Serialize};
+
+/// Above this many cells the diff stops searching for moved lines and
+/// reports the differing middle as removed + added
+const MAX_DIFF_CELLS: usize = 4_000_000;
+
+/// A saved response of an endpoint
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResponseSnapshot {
+    pub name: String,
+    pub status: u16,
+    pub saved_at: String,
+    pub lines: Vec<String>,
+}
+
+impl ResponseSnapshot {
+    /// Capture a response with its formatted body lines
+    pub fn capture(name: String, response: &ApiResponse, lines: &[String]) -> Self {
+        Self {
+            name,
+            status: response.status,
+            saved_at: Local::now().to_rfc3339_opts(SecondsFormat::Secs, false),
+            lines: lines.to_vec(),
+        }
+    }
+}
+
+/// One line of a diff between a snapshot and a fresh response
+#[derive(Debug, Clone, PartialEq)]
+pub enum DiffLine<'a> {
+    Same(&'a str),
+    Removed(&'a str),
+    Added(&'a str),
+}
+
+/// Line diff turning `old` into `new` (longest common subsequence)
+pub fn diff_lines<'a>(old: &'a [String], new: &'a [String]) -> Vec<DiffLine<'a>> {
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let old_mid = &old[prefix..old.len() - suffix];
+    let new_mid = &new[prefix..new.len() - suffix];
+
+    let mut diff: Vec<DiffLine> = old[..prefix].iter().map(|l| DiffLine::Same(l)).collect();
+
+    if (old_mid.len() + 1) * (new_mid.len() + 1) > MAX_DIFF_CELLS {
+        diff.extend(old_mid.iter().map(|l| DiffLine::Removed(l)));
+        diff.extend(new_mid.iter().map(|l| DiffLine::Added(l)));
+    } else {
+        // lcs[i][j] = common lines of old_mid[i..] and new_mid[j..]
+        let width = new_mid.len() + 1;
+        let mut lcs = vec![0usize; (old_mid.len() + 1) * width];
+        for i in (0..old_mid.len()).rev() {
+            for j in (0..new_mid.len()).rev() {
+                lcs[i * width + j] = if old_mid[i] == new_mid[j] {
+                    lcs[(i + 1) * width + j + 1] + 1
+                } else {
+                    lcs[(i + 1) * width + j].max(lcs[i * width + j + 1])
+                };
+            }
+        }
+
+        let (mut i, mut j) = (0, 0);
+        while i < old_mid.len() && j < new_mid.len() {
+            if old_mid[i] == new_mid[j] {
+                diff.push(DiffLine::Same(&old_mid[i]));
+                i += 1;
+                j += 1;
+            } else if lcs[(i + 1) * width + j] >= lcs[i * width + j + 1] {
+                diff.push(DiffLine::Removed(&old_mid[i]));
+                i += 1;
+            } else {
+                diff.push(DiffLine::Added(&new_mid[j]));
+                j += 1;
+            }
+        }
+        diff.extend(old_mid[i..].iter().map(|l| DiffLine::Removed(l)));
+        diff.extend(new_mid[j..].iter().map(|l| DiffLine::Added(l)));
+    }
+
+    diff.extend(old[old.len() - suffix..].iter().map(|l| DiffLine::Same(l)));
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(text: &str) -> Vec<String> {
+        text.lines().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn test_diff_lines() {
+        let old = lines("{\n  \"id\": 1,\n  \"name\": \"a\",\n  \"old\": true\n}");
+        let new = lines("{\n  \"id\": 1,\n  \"name\": \"b\",\n  \"old\": true,\n  \"new\": 2\n}");
+
+        assert_eq!(
+            diff_lines(&old, &new),
+            vec![
+                DiffLine::Same("{"),
+                DiffLine::Same("  \"id\": 1,"),
+                DiffLine::Removed("  \"name\": \"a\","),
+                DiffLine::Removed("  \"old\": true"),
+                DiffLine::Added("  \"name\": \"b\","),
+                DiffLine::Added("  \"old\": true,"),
+                DiffLine::Added("  \"new\": 2"),
+                DiffLine::Same("}"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_identical_and_empty() {
+        let same = lines("a\nb");
+        assert!(diff_lines(&same, &same)
+            .iter()
+            .all(|line| matches!(line, DiffLine::Same(_))));
+        assert_eq!(
+            diff_lines(&[], &same),
+            vec![DiffLine::Added("a"), DiffLine::Added("b")]
+        );
+    }
+}
@@ -0,0 +1,279 @@
+//! Changes to a spec between two loads
+//!
+//! Each load reduces the endpoints to their signatures (method and path,
+//! parameters, request body). The signatures are kept per spec URL, so
+//! the next load, in this session or a later one, can list the endpoints
+//! that were added, removed or changed in between.
+
+use crate::types::ApiEndpoint;
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// What requests to an endpoint depend on
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EndpointSignature {
+    /// Endpoint key (`METHOD path`)
+    pub key: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub params: Vec<ParamSignature>,
+    /// Media types of the request body and whether it is required
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ParamSignature {
+    pub name: String,
+    pub location: String,
+    pub required: bool,
+    /// Type as shown in the Request tab, e.g. `integer/int32`
+    pub kind: String,
+}
+
+impl ParamSignature {
+    fn label(&self) -> String {
+        format!("{} parameter {}", self.location, self.name)
+    }
+}
+
+impl EndpointSignature {
+    pub fn of(endpoint: &ApiEndpoint) -> Self {
+        let params = endpoint
+            .parameters
+            .iter()
+            .map(|param| ParamSignature {
+                name: param.name.clone(),
+                location: param.location.clone(),
+                required: param.required.unwrap_or(false),
+                kind: param.type_info(),
+            })
+            .collect();
+        let body = endpoint.request_body.as_ref().map(|body| {
+            let mut media_types: Vec<&str> = body.content.keys().map(String::as_str).collect();
+            media_types.sort_unstable();
+            let media_types = if media_types.is_empty() {
+                "any".to_string()
+            } else {
+                media_types.join(", ")
+            };
+            if body.required.unwrap_or(false) {
+                format!("{media_types}, required")
+            } else {
+                media_types
+            }
+        });
+        Self {
+            key: endpoint.key(),
+            params,
+            body,
+        }
+    }
+}
+
+/// Signatures of the executable endpoints of a spec
+pub fn signatures(endpoints: &[ApiEndpoint]) -> Vec<EndpointSignature> {
+    endpoints
+        .iter()
+        .filter(|endpoint| endpoint.event.is_none())
+        .map(EndpointSignature::of)
+        .collect()
+}
+
+/// Endpoints that differ between two loads of a spec
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SpecDiff {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    /// Endpoint keys with what changed about them
+    pub changed: Vec<(String, Vec<String>)>,
+    /// Local time the changes were found (`HH:MM`)
+    pub found_at: String,
+}
+
+impl SpecDiff {
+    /// Compare the signatures of the previous load with the current ones
+    pub fn between(old: &[EndpointSignature], new: &[EndpointSignature]) -> Self {
+        let old: BTreeMap<&str, &EndpointSignature> =
+            old.iter().map(|sig| (sig.key.as_str(), sig)).collect();
+        let new: BTreeMap<&str, &EndpointSignature> =
+            new.iter().map(|sig| (sig.key.as_str(), sig)).collect();
+
+        let mut diff = Self {
+            found_at: Local::now().format("%H:%M").to_string(),
+            ..Self::default()
+        };
+        for (key, sig) in &new {
+            match old.get(key) {
+                None => diff.added.push(key.to_string()),
+                Some(previous) => {
+                    let changes = endpoint_changes(previous, sig);
+                    if !changes.is_empty() {
+                        diff.changed.push((key.to_string(), changes));
+                    }
+                }
+            }
+        }
+        diff.removed = old
+            .keys()
+            .filter(|key| !new.contains_key(*key))
+            .map(|key| key.to_string())
+            .collect();
+        diff
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+
+    /// Counts for a status line, e.g. "2 added, 1 changed"
+    pub fn summary(&self) -> String {
+        [
+            (self.added.len(), "added"),
+            (self.removed.len(), "removed"),
+            (self.changed.len(), "changed"),
+        ]
+        .iter()
+        .filter(|(count, _)| *count > 0)
+        .map(|(count, what)| format!("{count} {what}"))
+        .collect::<Vec<_>>()
+        .join(", ")
+    }
+
+    /// One line per change, removed endpoints first
+    pub fn lines(&self) -> Vec<(DiffKind, String)> {
+        let mut lines: Vec<(DiffKind, String)> = self
+            .removed
+            .iter()
+            .map(|key| (DiffKind::Removed, key.clone()))
+            .collect();
+        lines.extend(self.added.iter().map(|key| (DiffKind::Added, key.clone())));
+        for (key, changes) in &self.changed {
+            lines.push((DiffKind::Changed, key.clone()));
+            lines.extend(
+                changes
+                    .iter()
+                    .map(|change| (DiffKind::Detail, change.clone())),
+            );
+        }
+        lines
+    }
+}
+
+/// How a line of the changes view is marked
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DiffKind {
+    Added,
+    Removed,
+    Changed,
+    Detail,
+}
+
+/// What changed about the parameters and body of one endpoint
+fn endpoint_changes(old: &EndpointSignature, new: &EndpointSignature) -> Vec<String> {
+    let find = |params: &[ParamSignature], param: &ParamSignature| {
+        params
+            .iter()
+            .find(|p| p.name == param.name && p.location == param.location)
+            .cloned()
+    };
+
+    let mut changes = Vec::new();
+    for param in &old.params {
+        if find(&new.params, param).is_none() {
+            changes.push(format!("- {}", param.label()));
+        }
+    }
+    for param in &new.params {
+        let Some(previous) = find(&old.params, param) else {
+            let required = if param.required { ", required" } else { "" };
+            changes.push(format!("+ {} ({}{required})", param.label(), param.kind));
+            continue;
+        };
+        if previous.kind != param.kind {
+            changes.push(format!(
+                "~ {}: {} → {}",
+                param.label(),
+                previous.kind,
+                param.kind
+            ));
+        }
+        if previous.required != param.required {
+            let now = if param.required {
+                "now required"
+            } else {
+                "now optional"
+            };
+            changes.push(format!("~ {}: {now}", param.label()));
+        }
+    }
+    if old.body != new.body {
+        let describe = |body: &Option<String>| body.clone().unwrap_or_else(|| "none".to_string());
+        changes.push(format!(
+            "~ request body: {} → {}",
+            describe(&old.body),
+            describe(&new.body)
+        ));
+    }
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn signature(key: &str, params: &[(&str, bool, &str)]) -> EndpointSignature {
+        EndpointSignature {
+            key: key.to_string(),
+            params: params
+                .iter()
+                .map(|(name, required, kind)| ParamSignature {
+                    name: name.to_string(),
+                    location: "query".to_string(),
+                    required: *required,
+                    kind: kind.to_string(),
+                })
+                .collect(),
+            body: None,
+        }
+    }
+
+    #[test]
+    fn test_spec_diff() {
+        let old = vec![
+            signature(
+                "GET /users",
+                &[("limit", false, "integer"), ("q", false, "string")],
+            ),
+            signature("DELETE /users/{id}", &[]),
+            signature("GET /health", &[]),
+        ];
+        let mut new = vec![
+            signature(
+                "GET /users",
+                &[("limit", true, "string"), ("page", false, "integer")],
+            ),
+            signature("GET /health", &[]),
+            signature("POST /users", &[]),
+        ];
+        new[2].body = Some("application/json, required".to_string());
+
+        let diff = SpecDiff::between(&old, &new);
+        assert_eq!(diff.added, vec!["POST /users"]);
+        assert_eq!(diff.removed, vec!["DELETE /users/{id}"]);
+        assert_eq!(
+            diff.changed,
+            vec![(
+                "GET /users".to_string(),
+                vec![
+                    "- query parameter q".to_string(),
+                    "~ query parameter limit: integer → string".to_string(),
+                    "~ query parameter limit: now required".to_string(),
+                    "+ query parameter page (integer)".to_string(),
+                ]
+            )]
+        );
+        assert_eq!(diff.summary(), "1 added, 1 removed, 1 changed");
+        assert!(SpecDiff::between(&new, &new).is_empty());
+    }
+}
@@ -1,32 +1,185 @@
+use crate::auth::TokenGrant;
+use crate::config::{complete_from_history, push_history, MAX_PARAM_HISTORY};
+use crate::config::{
+    Osc52Mode, PanelArrangement, PathRewriteConfig, PluginCommand, RequestTemplateConfig,
+    RequestsConfig, SpecConfig, TokenRefreshConfig, WatchConfig,
+};
+use crate::content::BodyKind;
+use crate::discovery::DiscoveredSpec;
 use crate::editor::BodyEditor;
+use crate::grouping::GroupingRules;
+use crate::links::ResponseLink;
+use crate::metrics;
+use crate::notify::{FinishedRequest, TOAST_DURATION};
+use crate::plugins::PluginRegistry;
+use crate::problem::ProblemDetails;
+use crate::recording::RecordedStep;
+use crate::redact::{mask_token, register_secret};
+use crate::script::{ConsoleEntry, Scope};
+use crate::servers::{ServerForm, SpecServer};
+use crate::snapshot::ResponseSnapshot;
+use crate::spec_diff::{self, EndpointSignature, SpecDiff};
+use crate::stats::{EndpointStats, StatsSort};
+use crate::table::{ResponseTable, TableSort};
+use crate::traffic::{TrafficEntry, MAX_TRAFFIC_ENTRIES};
 use crate::types::{
-    ApiEndpoint, ApiResponse, DetailTab, InputMode, LoadingState, PanelFocus, ParameterType,
-    RenderItem, RequestConfig, RequestEditMode, UrlInputField, ViewMode,
+    bearer, ApiEndpoint, ApiResponse, Credential, DetailTab, GroupBy, InputMode, LoadingState,
+    PanelFocus, ParamForm, Reconnect, RenderItem, RequestConfig, RequestEditMode, RequestVariant,
+    ResponseSample, ScreenLayout, SecurityScheme, SpecInfo, UrlInputField, ViewMode, ViewPosition,
+    WorkspaceCommand, STACKED_ENDPOINTS_PERCENT,
 };
-use crate::utils::mask_token;
+use crate::ui::events::log_debug;
+use crate::viewer::ExternalViewer;
+use std::borrow::Cow;
 use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard, TryLockError};
+use std::time::{Duration, Instant};
+use tokio::sync::Semaphore;
+use tokio::task::AbortHandle;
+
+/// Locking that survives a panic in another thread
+///
+/// A thread that panics while holding a lock poisons it. The state behind it is
+/// still usable, so locking takes it over instead of panicking in turn. Waits
+/// for a lock held elsewhere are timed for the performance overlay.
+pub trait LockRecover<T> {
+    fn read_lock(&self) -> RwLockReadGuard<'_, T>;
+    fn write_lock(&self) -> RwLockWriteGuard<'_, T>;
+}
+
+impl<T> LockRecover<T> for RwLock<T> {
+    fn read_lock(&self) -> RwLockReadGuard<'_, T> {
+        match self.try_read() {
+            Ok(guard) => guard,
+            Err(TryLockError::Poisoned(e)) => e.into_inner(),
+            Err(TryLockError::WouldBlock) => {
+                let start = Instant::now();
+                let guard = self.read().unwrap_or_else(PoisonError::into_inner);
+                metrics::record_lock_wait(start.elapsed());
+                guard
+            }
+        }
+    }
+
+    fn write_lock(&self) -> RwLockWriteGuard<'_, T> {
+        match self.try_write() {
+            Ok(guard) => guard,
+            Err(TryLockError::Poisoned(e)) => e.into_inner(),
+            Err(TryLockError::WouldBlock) => {
+                let start = Instant::now();
+                let guard = self.write().unwrap_or_else(PoisonError::into_inner);
+                metrics::record_lock_wait(start.elapsed());
+                guard
+            }
+        }
+    }
+}
 
 /// Data loaded from backend
 #[derive(Debug, Clone)]
 pub struct DataState {
     pub endpoints: Vec<ApiEndpoint>,
     pub grouped_endpoints: HashMap<String, Vec<ApiEndpoint>>,
+    /// User-defined grouping rules from the config
+    pub grouping: GroupingRules,
     pub loading_state: LoadingState,
     pub retry_count: u32,
+    /// Set while the server is unreachable and the spec is refetched in the background
+    pub reconnect: Option<Reconnect>,
+    /// Free-text notes of the current spec, keyed by endpoint key
+    pub notes: HashMap<String, String>,
+    /// Set when `notes` changed and should be persisted
+    pub notes_dirty: bool,
+    /// `info` block of the loaded spec
+    pub spec_info: SpecInfo,
+    /// `servers` of the loaded spec, relative URLs made absolute
+    pub servers: Vec<SpecServer>,
+    /// `components.securitySchemes` of the loaded spec, by name
+    pub security_schemes: HashMap<String, SecurityScheme>,
+    /// Endpoint signatures of the previous load of this spec, possibly from an earlier session
+    pub spec_signatures: Option<Vec<EndpointSignature>>,
+    /// Set when `spec_signatures` changed and should be persisted
+    pub spec_signatures_dirty: bool,
+    /// Latest changes found when the spec loaded
+    pub spec_diff: Option<SpecDiff>,
+    /// Set by an explicit refresh, so a load without changes is reported too
+    pub refresh_requested: bool,
+    /// Reload the spec every `watch_interval` while the endpoints stay on screen
+    pub watching: bool,
+    pub watch_interval: Duration,
+    /// When the spec was last requested
+    pub last_fetch: Option<Instant>,
+    /// Set while a watched reload is in flight
+    pub reloading: bool,
+    /// Spec URL of the latest load; loads that finish for another URL are dropped
+    pub spec_url: Option<String>,
 }
 
 /// UI display and navigation state
 #[derive(Debug, Clone)]
 pub struct UiState {
     pub view_mode: ViewMode,
+    pub group_by: GroupBy,
     pub expanded_groups: HashSet<String>,
     pub panel_focus: PanelFocus,
     pub active_detail_tab: DetailTab,
     pub selected_param_index: usize,
     pub body_section_expanded: bool,
+    pub extensions_expanded: bool,
     pub response_scroll: usize,
+    pub headers_scroll: usize,
+    /// Remembered positions of the Response and Headers tabs per endpoint key
+    pub view_positions: HashMap<(String, DetailTab), ViewPosition>,
+    /// Endpoint key the current scroll positions belong to
+    pub position_endpoint: Option<String>,
     pub response_selected_line: usize,
+    /// Line where the visual selection in the Response tab started
+    pub response_visual_anchor: Option<usize>,
+    /// Number of body lines shown before the "load more" notice
+    pub response_line_limit: usize,
+    /// Set to open the response in an external pager or browser
+    pub open_externally: Option<ExternalViewer>,
+    /// Wrap long response lines (off keeps one screen row per line)
+    pub response_wrap: bool,
+    /// Show array responses as a table instead of raw JSON
+    pub response_table: bool,
+    pub table_sort: Option<TableSort>,
+    /// Endpoint key and index of the snapshot the response is diffed against
+    pub snapshot_diff: Option<(String, usize)>,
     pub yank_flash: bool,
+    /// When yanks are copied with OSC 52, from the config
+    pub osc52: Osc52Mode,
+    /// Last selected endpoint key per group (grouped view)
+    pub last_selected_by_group: HashMap<String, String>,
+    /// Last selected endpoint key per swagger URL
+    pub last_selected_by_spec: HashMap<String, String>,
+    /// Endpoint key to select once the endpoint list has loaded
+    pub pending_selection: Option<String>,
+    /// Steps not yet written to the session file, while recording a session
+    pub recording: Option<Vec<RecordedStep>>,
+    pub stats_sort: StatsSort,
+    pub stats_scroll: usize,
+    pub spec_diff_scroll: usize,
+    /// Show the response in its own pane next to the request
+    pub three_pane: bool,
+    /// Endpoints and details next to each other or stacked
+    pub arrangement: PanelArrangement,
+    /// Terminal width and height, kept up to date on resize
+    pub terminal_size: (u16, u16),
+    /// False while the terminal window is in the background
+    pub terminal_focused: bool,
+    /// Message shown briefly in the corner, with when it was shown
+    pub toast: Option<(String, Instant)>,
+    /// Latest recoverable error, shown in a banner until dismissed with Esc
+    pub error_banner: Option<String>,
+    /// Latest recoverable error, kept after the banner is dismissed for the diagnostic bundle
+    pub last_error: Option<String>,
+    /// Show the recent log entries in a panel over the bottom of the screen
+    pub show_log: bool,
+    /// Show render, input and lock timings in a corner
+    pub show_metrics: bool,
+    /// Set to open, close or switch workspace tabs
+    pub workspace_command: Option<WorkspaceCommand>,
 }
 
 /// Modal/form input state
@@ -37,29 +190,484 @@ pub struct InputState {
     pub url_input: String,
     pub base_url_input: String,
     pub active_url_field: UrlInputField,
+    /// Validation error shown under the offending field of the URL modal
+    pub url_error: Option<(UrlInputField, String)>,
     pub body_editor: BodyEditor,
     pub body_validation_error: Option<String>,
+    /// Key of the endpoint whose body is in the editor
+    pub body_endpoint: Option<String>,
+    /// Body the editor opened with, before any edits or restored draft
+    pub body_saved: String,
+    /// Set when the editor opened with a draft left from an earlier session
+    pub body_draft_restored: bool,
+    pub url_history: Vec<String>,
+    pub base_url_history: Vec<String>,
+    pub param_form: ParamForm,
+    pub server_form: ServerForm,
+    /// Base URL built in the server form, picked up and saved by the app loop
+    pub server_base_url: Option<String>,
+    pub variant_name_input: String,
+    pub param_paste_input: String,
+    /// Security schemes the auth modal offers, the endpoint's own first, and the chosen one
+    pub auth_schemes: Vec<(String, SecurityScheme)>,
+    pub auth_scheme_index: usize,
+    /// Why the pasted parameters could not be read, shown in the paste modal
+    pub param_paste_error: Option<String>,
+    pub snapshot_name_input: String,
+    pub note_input: String,
+    pub export_path_input: String,
+    /// Whether the script export covers all saved requests instead of the selected one
+    pub export_collection: bool,
+    /// Links offered by the link picker and the highlighted one
+    pub links: Vec<ResponseLink>,
+    pub link_index: usize,
+    /// Specs found in Docker/Kubernetes for the URL modal; `None` while still looking
+    pub discovered_specs: Option<Vec<DiscoveredSpec>>,
+    pub discovered_index: usize,
+    /// Commands of the plugins with the plugin offering each, and the highlighted one
+    pub plugin_commands: Vec<(String, PluginCommand)>,
+    pub plugin_command_index: usize,
+    /// Specs of the config offered by the spec switcher, and the highlighted one
+    pub spec_list: Vec<SpecConfig>,
+    pub spec_list_index: usize,
+    /// Highlighted request in the jobs popup
+    pub job_index: usize,
+    /// Output of the last plugin command run from the picker
+    pub plugin_output: Option<String>,
+    pub console_input: String,
+    /// Inputs evaluated in the console this session, oldest first
+    pub console_log: Vec<ConsoleEntry>,
+    /// Variables assigned in the console
+    pub console_vars: HashMap<String, serde_json::Value>,
+    /// Error from the last failed export, shown in the export modal
+    pub export_error: Option<String>,
+    /// Show secrets typed into the token modal and parameter form instead of dots
+    pub reveal_secrets: bool,
+}
+
+impl InputState {
+    /// Completion suffix for the active URL field, taken from the URL history
+    pub fn url_completion(&self) -> Option<&str> {
+        match self.active_url_field {
+            UrlInputField::SwaggerUrl => complete_from_history(&self.url_input, &self.url_history),
+            UrlInputField::BaseUrl => {
+                complete_from_history(&self.base_url_input, &self.base_url_history)
+            }
+        }
+    }
+
+    /// Security scheme chosen in the auth modal; `None` when the spec declares none
+    pub fn selected_auth_scheme(&self) -> Option<&(String, SecurityScheme)> {
+        self.auth_schemes.get(self.auth_scheme_index)
+    }
 }
 
 /// HTTP request and authentication state
 #[derive(Debug, Clone)]
 pub struct RequestState {
     pub auth: AuthState,
-    pub executing_endpoint: Option<String>,
+    /// Requests queued or in flight, in the order they were sent
+    pub jobs: Vec<RequestJob>,
+    /// Permits for requests in flight; queued requests wait for one
+    pub slots: Arc<Semaphore>,
+    /// Id given to the next request started
+    pub next_job_id: u64,
+    /// Key of the endpoint last sent a request, for `.` to send it again
+    pub last_executed: Option<String>,
+    /// Last response per endpoint key, with how it was obtained
+    pub responses: HashMap<String, EndpointResponse>,
+    /// Key of the endpoint whose response is shown as `current_response`
+    pub response_endpoint: Option<String>,
+    /// Response of the selected endpoint, as shown
     pub current_response: Option<ApiResponse>,
+    /// Formatted body lines of `current_response`, computed once per response
+    pub response_lines: Vec<String>,
+    /// How the body of `current_response` is rendered, from its content type
+    pub body_kind: BodyKind,
+    /// URL of the link the current response was fetched from, if it was followed
+    pub followed_link: Option<String>,
+    /// Outcome of an automatic re-auth for `current_response` (e.g. "retried after re-auth")
+    pub reauth_note: Option<String>,
+    /// RFC 7807 details of `current_response`, if it is a problem response
+    pub response_problem: Option<ProblemDetails>,
+    /// Parameter values and body per endpoint key
     pub configs: HashMap<String, RequestConfig>,
     pub edit_mode: RequestEditMode,
     pub param_edit_buffer: String,
+    /// Previously used values per parameter name, most recent first
+    pub param_history: HashMap<String, Vec<String>>,
+    /// Set when `param_history` changed and should be persisted
+    pub param_history_dirty: bool,
+    /// Allowed values of the parameter being edited, offered instead of its history
+    pub param_choices: Vec<String>,
+    pub suggestion_index: usize,
+    /// Saved request variants of the current spec, keyed by endpoint key
+    pub variants: HashMap<String, Vec<RequestVariant>>,
+    /// Index of the variant last saved or loaded, keyed by endpoint key
+    pub active_variant: HashMap<String, usize>,
+    /// Set when `variants` changed and should be persisted
+    pub variants_dirty: bool,
+    /// Saved response snapshots of the current spec, keyed by endpoint key
+    pub snapshots: HashMap<String, Vec<ResponseSnapshot>>,
+    /// Set when `snapshots` changed and should be persisted
+    pub snapshots_dirty: bool,
+    /// Body edits not saved yet, kept in case the app exits, keyed by endpoint key
+    pub body_drafts: HashMap<String, String>,
+    /// Set when `body_drafts` changed and should be persisted
+    pub body_drafts_dirty: bool,
+    /// Request config edits that `u` takes back, most recent last
+    pub undo_stack: Vec<ConfigEdit>,
+    /// Edits taken back, for Ctrl+R to apply again, most recent last
+    pub redo_stack: Vec<ConfigEdit>,
+    /// Recent responses per endpoint key this session, oldest first
+    pub response_history: HashMap<String, Vec<ResponseSample>>,
+    /// Requests sent this session with their responses, oldest first
+    pub traffic: Vec<TrafficEntry>,
+    /// Usage of the current spec's endpoints across sessions, keyed by endpoint key
+    pub usage_stats: HashMap<String, EndpointStats>,
+    /// Set when `usage_stats` changed and should be persisted
+    pub usage_stats_dirty: bool,
+    /// Plugins hooked into spec loading and requests
+    pub plugins: PluginRegistry,
+    /// Path rewrites per base URL, from the config
+    pub path_rewrites: Vec<PathRewriteConfig>,
+    /// Shared parameter and header sets, from the config
+    pub templates: Vec<RequestTemplateConfig>,
+    /// Requests that just finished, for the app to notify about
+    pub finished_requests: Vec<FinishedRequest>,
+}
+
+/// The last response to an endpoint
+#[derive(Debug, Clone)]
+pub struct EndpointResponse {
+    pub response: ApiResponse,
+    /// URL of the link the response was fetched from, if it was followed
+    pub followed_link: Option<String>,
+    /// Outcome of an automatic re-auth (e.g. "retried after re-auth")
+    pub reauth_note: Option<String>,
+}
+
+impl From<ApiResponse> for EndpointResponse {
+    fn from(response: ApiResponse) -> Self {
+        Self {
+            response,
+            followed_link: None,
+            reauth_note: None,
+        }
+    }
+}
+
+/// An endpoint's request config as it was before an edit, for undo and redo
+#[derive(Debug, Clone)]
+pub struct ConfigEdit {
+    pub endpoint_key: String,
+    /// What was edited, e.g. "reset request"
+    pub label: String,
+    /// The config before the edit; `None` when the endpoint had none
+    pub config: Option<RequestConfig>,
+}
+
+/// A request queued or in flight, listed in the jobs popup
+#[derive(Debug, Clone)]
+pub struct RequestJob {
+    pub id: u64,
+    /// Key of the endpoint the response is shown under
+    pub endpoint_key: String,
+    /// When the request was sent; `None` while it waits in the queue
+    pub started: Option<Instant>,
+    /// Stops the request's task when the job is cancelled
+    pub abort: Option<AbortHandle>,
+}
+
+/// Response body lines shown at first, and added per "load more"
+pub const RESPONSE_LINE_PAGE: usize = 2_000;
+
+/// Responses remembered per endpoint
+pub const MAX_RESPONSE_HISTORY: usize = 20;
+
+/// Maximum number of suggestions shown below a parameter being edited
+const MAX_PARAM_SUGGESTIONS: usize = 5;
+
+/// Maximum number of allowed values listed below an enum parameter being edited
+const MAX_PARAM_CHOICES: usize = 12;
+
+/// Request config edits that can be undone; the oldest are dropped first
+const MAX_UNDO_STEPS: usize = 100;
+
+impl RequestState {
+    /// The config as sent: template parameters fill the endpoint's parameters left empty
+    pub fn merged_config(
+        &self,
+        endpoint: &ApiEndpoint,
+        config: Option<&RequestConfig>,
+    ) -> Option<RequestConfig> {
+        let values: HashMap<&String, String> = self
+            .templates
+            .iter()
+            .filter(|template| template.applies_to(endpoint))
+            .flat_map(RequestTemplateConfig::param_values)
+            .collect();
+        if values.is_empty() {
+            return config.cloned();
+        }
+
+        let mut merged = config.cloned().unwrap_or_default();
+        for (param, param_type) in endpoint.request_params() {
+            let is_empty = merged
+                .get_param_value(&param.name)
+                .is_none_or(str::is_empty);
+            if let (true, Some(value)) = (is_empty, values.get(&param.name)) {
+                merged.set_param(param.name.clone(), value.clone(), param_type);
+            }
+        }
+        Some(merged)
+    }
+
+    /// Headers of the templates applying to the endpoint, in config order
+    pub fn template_headers(&self, endpoint: &ApiEndpoint) -> Vec<(String, String)> {
+        self.templates
+            .iter()
+            .filter(|template| template.applies_to(endpoint))
+            .flat_map(|template| {
+                template
+                    .header_values()
+                    .map(|(name, value)| (name.clone(), value))
+            })
+            .collect()
+    }
+
+    /// Names of the templates applying to the endpoint
+    pub fn template_names(&self, endpoint: &ApiEndpoint) -> Vec<&str> {
+        self.templates
+            .iter()
+            .filter(|template| template.applies_to(endpoint))
+            .map(|template| template.name.as_str())
+            .collect()
+    }
+
+    /// Queue a request to an endpoint, returning its job id
+    pub fn start_job(&mut self, endpoint_key: String) -> u64 {
+        let id = self.next_job_id;
+        self.next_job_id += 1;
+        self.jobs.push(RequestJob {
+            id,
+            endpoint_key,
+            started: None,
+            abort: None,
+        });
+        id
+    }
+
+    /// Attach the handle cancelling a job's task, once it is spawned
+    pub fn set_job_abort(&mut self, id: u64, abort: AbortHandle) {
+        if let Some(job) = self.jobs.iter_mut().find(|job| job.id == id) {
+            job.abort = Some(abort);
+        }
+    }
+
+    /// Mark a queued job as sent
+    pub fn set_job_started(&mut self, id: u64) {
+        if let Some(job) = self.jobs.iter_mut().find(|job| job.id == id) {
+            job.started = Some(Instant::now());
+        }
+    }
+
+    /// Remove a finished job; `None` when it was cancelled meanwhile
+    pub fn finish_job(&mut self, id: u64) -> Option<RequestJob> {
+        let index = self.jobs.iter().position(|job| job.id == id)?;
+        Some(self.jobs.remove(index))
+    }
+
+    /// Stop a job's request and forget it, returning its endpoint key
+    pub fn cancel_job(&mut self, id: u64) -> Option<String> {
+        let job = self.finish_job(id)?;
+        if let Some(abort) = job.abort {
+            abort.abort();
+        }
+        Some(job.endpoint_key)
+    }
+
+    /// Stop all queued and running requests, returning how many there were
+    pub fn cancel_all_jobs(&mut self) -> usize {
+        let jobs = std::mem::take(&mut self.jobs);
+        for abort in jobs.iter().filter_map(|job| job.abort.as_ref()) {
+            abort.abort();
+        }
+        jobs.len()
+    }
+
+    /// Jobs waiting for a slot
+    pub fn queued_jobs(&self) -> usize {
+        self.jobs.iter().filter(|job| job.started.is_none()).count()
+    }
+
+    /// Whether a request to the endpoint is in flight
+    pub fn is_executing(&self, endpoint_key: &str) -> bool {
+        self.jobs.iter().any(|job| job.endpoint_key == endpoint_key)
+    }
+
+    /// Remember a value used for a parameter
+    pub fn record_param_value(&mut self, name: &str, value: &str) {
+        if value.is_empty() {
+            return;
+        }
+        let history = self.param_history.entry(name.to_string()).or_default();
+        if history.first().map(String::as_str) != Some(value) {
+            push_history(history, value, MAX_PARAM_HISTORY);
+            self.param_history_dirty = true;
+        }
+    }
+
+    /// Add an exchange to the session's traffic log, dropping the oldest beyond the limit
+    pub fn record_traffic(&mut self, entry: TrafficEntry) {
+        self.traffic.push(entry);
+        if self.traffic.len() > MAX_TRAFFIC_ENTRIES {
+            self.traffic.remove(0);
+        }
+    }
+
+    /// Remember the status and duration of a response to an endpoint, and count it in its stats
+    pub fn record_response(&mut self, endpoint_key: String, response: &ApiResponse) {
+        self.usage_stats
+            .entry(endpoint_key.clone())
+            .or_default()
+            .record(response.status, response.duration);
+        self.usage_stats_dirty = true;
+
+        let history = self.response_history.entry(endpoint_key).or_default();
+        history.push(ResponseSample {
+            status: response.status,
+            duration: response.duration,
+        });
+        if history.len() > MAX_RESPONSE_HISTORY {
+            history.remove(0);
+        }
+    }
+
+    /// Save an endpoint's current config as a named variant (replacing one with the same name)
+    pub fn save_variant(&mut self, endpoint_key: &str, name: String) {
+        let config = self.configs.get(endpoint_key).cloned().unwrap_or_default();
+        let variants = self.variants.entry(endpoint_key.to_string()).or_default();
+
+        let index = match variants.iter().position(|v| v.name == name) {
+            Some(index) => {
+                variants[index].config = config;
+                index
+            }
+            None => {
+                variants.push(RequestVariant { name, config });
+                variants.len() - 1
+            }
+        };
+
+        self.active_variant.insert(endpoint_key.to_string(), index);
+        self.variants_dirty = true;
+    }
+
+    /// Load the next saved variant of an endpoint into its config
+    pub fn cycle_variant(&mut self, endpoint_key: &str) {
+        let Some(variants) = self.variants.get(endpoint_key).filter(|v| !v.is_empty()) else {
+            return;
+        };
+
+        let next = self
+            .active_variant
+            .get(endpoint_key)
+            .map(|index| (index + 1) % variants.len())
+            .unwrap_or(0);
+
+        self.configs
+            .insert(endpoint_key.to_string(), variants[next].config.clone());
+        self.active_variant.insert(endpoint_key.to_string(), next);
+    }
+
+    /// Allowed values or history values matching the buffer of the parameter being edited
+    ///
+    /// All allowed values are offered while the buffer holds one of them.
+    pub fn param_suggestions(&self) -> Vec<&str> {
+        let RequestEditMode::Editing(name) = &self.edit_mode else {
+            return Vec::new();
+        };
+        let input = self.param_edit_buffer.to_lowercase();
+
+        if !self.param_choices.is_empty() {
+            let is_choice = self.param_choices.contains(&self.param_edit_buffer);
+            return self
+                .param_choices
+                .iter()
+                .filter(|value| is_choice || value.to_lowercase().starts_with(&input))
+                .take(MAX_PARAM_CHOICES)
+                .map(String::as_str)
+                .collect();
+        }
+
+        self.param_history
+            .get(name)
+            .into_iter()
+            .flatten()
+            .filter(|value| *value != &self.param_edit_buffer)
+            .filter(|value| value.to_lowercase().starts_with(&input))
+            .take(MAX_PARAM_SUGGESTIONS)
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// The suggestion currently highlighted in the dropdown
+    pub fn selected_suggestion(&self) -> Option<&str> {
+        let suggestions = self.param_suggestions();
+        let last = suggestions.len().checked_sub(1)?;
+        suggestions.get(self.suggestion_index.min(last)).copied()
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct AuthState {
     pub token: Option<String>,
+    /// Security scheme the token was entered for, by name; without one it is a bearer token
+    pub scheme: Option<(String, SecurityScheme)>,
+    /// Token endpoint to re-authenticate with on 401, from config
+    pub refresh: Option<TokenRefreshConfig>,
 }
 
 impl AuthState {
     pub fn new() -> Self {
-        Self { token: None }
+        Self {
+            token: None,
+            scheme: None,
+            refresh: None,
+        }
+    }
+
+    /// Where the token goes on a request, as its scheme says
+    pub fn credential(&self) -> Option<Credential> {
+        let token = self.token.as_deref()?;
+        Some(match &self.scheme {
+            Some((_, scheme)) => scheme.credential(token),
+            None => bearer(token),
+        })
+    }
+
+    /// Use the configured token endpoint, masking its credentials in the debug log
+    pub fn set_refresh(&mut self, refresh: Option<TokenRefreshConfig>) {
+        if let Some(config) = &refresh {
+            for secret in [&config.client_secret, &config.refresh_token]
+                .into_iter()
+                .flatten()
+            {
+                register_secret(secret);
+            }
+        }
+        self.refresh = refresh;
+    }
+
+    /// Use a freshly issued token, keeping a rotated refresh token for the next refresh
+    pub fn apply_grant(&mut self, grant: TokenGrant) {
+        register_secret(&grant.access_token);
+        self.token = Some(grant.access_token);
+        if let (Some(refresh), Some(refresh_token)) = (&mut self.refresh, grant.refresh_token) {
+            register_secret(&refresh_token);
+            refresh.refresh_token = Some(refresh_token);
+        }
     }
 
     pub fn is_authenticated(&self) -> bool {
@@ -67,11 +675,13 @@ impl AuthState {
     }
 
     pub fn set_token(&mut self, token: String) {
+        register_secret(&token);
         self.token = Some(token);
     }
 
     pub fn clear_token(&mut self) {
         self.token = None;
+        self.scheme = None;
     }
 
     pub fn get_masked_display(&self) -> String {
@@ -84,8 +694,11 @@ impl AuthState {
     /// Get authentication status text for display in the UI header
     pub fn get_status_text(&self) -> String {
         if self.is_authenticated() {
-            let display = self.get_masked_display();
-            format!("🔒 {display} | 'a':edit 'A':clear")
+            let display = match &self.scheme {
+                Some((name, _)) => format!("{name}: {}", self.get_masked_display()),
+                None => self.get_masked_display(),
+            };
+            format!("🔒 {display} | 'a':edit")
         } else {
             "🔓 Not authenticated | 'a':set token".to_string()
         }
@@ -96,8 +709,21 @@ impl AuthState {
 #[derive(Debug, Clone)]
 pub struct SearchState {
     pub query: String,
+    /// Text searched for in the response body (Response tab)
+    pub response_query: String,
     pub filtered_endpoints: Vec<ApiEndpoint>,
     pub filtered_grouped_endpoints: HashMap<String, Vec<ApiEndpoint>>,
+    /// Group the search is limited to (`F` in the grouped view)
+    pub scope: Option<String>,
+    /// Leave deprecated endpoints out of the list (`h`)
+    pub hide_deprecated: bool,
+}
+
+impl SearchState {
+    /// Whether the list shows `filtered_endpoints` rather than every endpoint
+    pub fn is_filtering(&self) -> bool {
+        !self.query.is_empty() || self.hide_deprecated
+    }
 }
 
 /// Main application state - composed of logical sub-states
@@ -116,19 +742,65 @@ impl Default for AppState {
             data: DataState {
                 endpoints: Vec::new(),
                 grouped_endpoints: HashMap::new(),
+                grouping: GroupingRules::default(),
                 loading_state: LoadingState::Idle,
                 retry_count: 0,
+                reconnect: None,
+                notes: HashMap::new(),
+                notes_dirty: false,
+                spec_info: SpecInfo::default(),
+                servers: Vec::new(),
+                security_schemes: HashMap::new(),
+                spec_signatures: None,
+                spec_signatures_dirty: false,
+                spec_diff: None,
+                refresh_requested: false,
+                watching: false,
+                watch_interval: WatchConfig::default().interval(),
+                last_fetch: None,
+                reloading: false,
+                spec_url: None,
             },
             ui: UiState {
                 view_mode: ViewMode::Grouped,
+                group_by: GroupBy::Tag,
                 expanded_groups: HashSet::new(),
                 panel_focus: PanelFocus::EndpointsList,
                 active_detail_tab: DetailTab::Endpoint,
                 selected_param_index: 0,
                 body_section_expanded: true,
+                extensions_expanded: false,
                 response_scroll: 0,
+                headers_scroll: 0,
+                view_positions: HashMap::new(),
+                position_endpoint: None,
                 response_selected_line: 0,
+                response_visual_anchor: None,
+                response_line_limit: RESPONSE_LINE_PAGE,
+                open_externally: None,
+                response_wrap: true,
+                response_table: false,
+                table_sort: None,
+                snapshot_diff: None,
                 yank_flash: false,
+                osc52: Osc52Mode::default(),
+                last_selected_by_group: HashMap::new(),
+                last_selected_by_spec: HashMap::new(),
+                pending_selection: None,
+                recording: None,
+                stats_sort: StatsSort::default(),
+                stats_scroll: 0,
+                spec_diff_scroll: 0,
+                three_pane: false,
+                arrangement: PanelArrangement::default(),
+                terminal_size: (80, 24),
+                terminal_focused: true,
+                toast: None,
+                error_banner: None,
+                last_error: None,
+                show_log: false,
+                show_metrics: false,
+                workspace_command: None,
             },
             input: InputState {
                 mode: InputMode::Normal,
@@ -136,34 +808,543 @@ impl Default for AppState {
                 url_input: String::new(),
                 base_url_input: String::new(),
                 active_url_field: UrlInputField::SwaggerUrl,
+                url_error: None,
                 body_editor: BodyEditor::new(),
                 body_validation_error: None,
+                body_endpoint: None,
+                body_saved: String::new(),
+                body_draft_restored: false,
+                url_history: Vec::new(),
+                base_url_history: Vec::new(),
+                param_form: ParamForm::default(),
+                server_form: ServerForm::default(),
+                server_base_url: None,
+                variant_name_input: String::new(),
+                param_paste_input: String::new(),
+                param_paste_error: None,
+                auth_schemes: Vec::new(),
+                auth_scheme_index: 0,
+                snapshot_name_input: String::new(),
+                note_input: String::new(),
+                export_path_input: String::new(),
+                export_collection: false,
+                links: Vec::new(),
+                link_index: 0,
+                discovered_specs: None,
+                discovered_index: 0,
+                plugin_commands: Vec::new(),
+                plugin_command_index: 0,
+                spec_list: Vec::new(),
+                spec_list_index: 0,
+                job_index: 0,
+                plugin_output: None,
+                console_input: String::new(),
+                console_log: Vec::new(),
+                console_vars: HashMap::new(),
+                export_error: None,
+                reveal_secrets: false,
             },
             request: RequestState {
                 auth: AuthState::new(),
-                executing_endpoint: None,
+                jobs: Vec::new(),
+                slots: Arc::new(Semaphore::new(RequestsConfig::default().max_concurrent)),
+                next_job_id: 0,
+                last_executed: None,
+                responses: HashMap::new(),
+                response_endpoint: None,
                 current_response: None,
+                response_lines: Vec::new(),
+                body_kind: BodyKind::default(),
+                response_problem: None,
+                followed_link: None,
+                reauth_note: None,
                 configs: HashMap::new(),
                 edit_mode: RequestEditMode::Viewing,
                 param_edit_buffer: String::new(),
+                param_history: HashMap::new(),
+                param_history_dirty: false,
+                param_choices: Vec::new(),
+                suggestion_index: 0,
+                variants: HashMap::new(),
+                active_variant: HashMap::new(),
+                variants_dirty: false,
+                snapshots: HashMap::new(),
+                snapshots_dirty: false,
+                body_drafts: HashMap::new(),
+                body_drafts_dirty: false,
+                undo_stack: Vec::new(),
+                redo_stack: Vec::new(),
+                response_history: HashMap::new(),
+                traffic: Vec::new(),
+                usage_stats: HashMap::new(),
+                usage_stats_dirty: false,
+                plugins: PluginRegistry::default(),
+                path_rewrites: Vec::new(),
+                templates: Vec::new(),
+                finished_requests: Vec::new(),
             },
             search: SearchState {
                 query: String::new(),
+                response_query: String::new(),
                 filtered_endpoints: Vec::new(),
                 filtered_grouped_endpoints: HashMap::new(),
+                hide_deprecated: false,
+                scope: None,
             },
         }
     }
 }
 
 impl AppState {
+    /// Store a new response, formatting its body once and resetting the viewer
+    pub fn set_response(&mut self, response: Option<ApiResponse>) {
+        self.request.body_kind = response.as_ref().map(BodyKind::detect).unwrap_or_default();
+        self.request.response_lines = match &response {
+            Some(response) if !response.is_error => self.request.body_kind.format_lines(response),
+            _ => Vec::new(),
+        };
+        self.request.response_problem = response
+            .as_ref()
+            .filter(|response| !response.is_error)
+            .and_then(ProblemDetails::from_response);
+        self.request.current_response = response;
+        self.request.followed_link = None;
+        self.request.reauth_note = None;
+        self.ui.response_line_limit = RESPONSE_LINE_PAGE;
+        self.ui.response_visual_anchor = None;
+    }
+
+    /// Show an error the user can recover from in the banner, and log it
+    pub fn show_error(&mut self, message: String) {
+        log_debug(&message);
+        self.ui.last_error = Some(message.clone());
+        self.ui.error_banner = Some(message);
+    }
+
+    /// Store an endpoint's response (`None` clears it), showing it if the endpoint is shown
+    pub fn store_response(&mut self, endpoint_key: String, response: Option<EndpointResponse>) {
+        let shown = self.request.response_endpoint.as_ref() == Some(&endpoint_key);
+        match response {
+            Some(response) => {
+                if shown {
+                    self.show_stored_response(Some(&response));
+                }
+                self.request.responses.insert(endpoint_key, response);
+            }
+            None => {
+                if shown {
+                    self.set_response(None);
+                }
+                self.request.responses.remove(&endpoint_key);
+            }
+        }
+    }
+
+    /// Keep the body in the editor as a draft while it differs from the saved body
+    pub fn track_body_draft(&mut self) {
+        if self.input.mode != InputMode::EnteringBody {
+            return;
+        }
+        let Some(endpoint_key) = &self.input.body_endpoint else {
+            return;
+        };
+        let content = self.input.body_editor.content();
+        let drafts = &mut self.request.body_drafts;
+
+        if content.trim() == self.input.body_saved.trim() {
+            if drafts.remove(endpoint_key).is_some() {
+                self.request.body_drafts_dirty = true;
+            }
+        } else if drafts.get(endpoint_key) != Some(&content) {
+            drafts.insert(endpoint_key.clone(), content);
+            self.request.body_drafts_dirty = true;
+        }
+    }
+
+    /// Close the body editor, dropping the draft of its endpoint: the body was saved or discarded
+    pub fn close_body_editor(&mut self) {
+        if let Some(endpoint_key) = self.input.body_endpoint.take() {
+            if self.request.body_drafts.remove(&endpoint_key).is_some() {
+                self.request.body_drafts_dirty = true;
+            }
+        }
+        self.input.mode = InputMode::Normal;
+        self.input.body_editor.clear();
+        self.input.body_validation_error = None;
+        self.input.body_draft_restored = false;
+    }
+
+    /// Apply an edit to an endpoint's request config so that it can be undone
+    ///
+    /// Nothing is recorded when the edit leaves the config as it was.
+    pub fn edit_config(&mut self, endpoint_key: &str, label: &str, edit: impl FnOnce(&mut Self)) {
+        let before = self.request.configs.get(endpoint_key).cloned();
+        edit(self);
+        if self.request.configs.get(endpoint_key) == before.as_ref() {
+            return;
+        }
+
+        let undo_stack = &mut self.request.undo_stack;
+        undo_stack.push(ConfigEdit {
+            endpoint_key: endpoint_key.to_string(),
+            label: label.to_string(),
+            config: before,
+        });
+        if undo_stack.len() > MAX_UNDO_STEPS {
+            undo_stack.remove(0);
+        }
+        self.request.redo_stack.clear();
+    }
+
+    /// Take back the last config edit, returning its label
+    pub fn undo(&mut self) -> Option<String> {
+        let edit = self.request.undo_stack.pop()?;
+        let label = edit.label.clone();
+        let redo = self.restore_config(edit);
+        self.request.redo_stack.push(redo);
+        Some(label)
+    }
+
+    /// Apply the last undone config edit again, returning its label
+    pub fn redo(&mut self) -> Option<String> {
+        let edit = self.request.redo_stack.pop()?;
+        let label = edit.label.clone();
+        let undo = self.restore_config(edit);
+        self.request.undo_stack.push(undo);
+        Some(label)
+    }
+
+    /// Put back a recorded config, returning the one it replaced
+    fn restore_config(&mut self, edit: ConfigEdit) -> ConfigEdit {
+        let configs = &mut self.request.configs;
+        let replaced = match edit.config {
+            Some(config) => configs.insert(edit.endpoint_key.clone(), config),
+            None => configs.remove(&edit.endpoint_key),
+        };
+        ConfigEdit {
+            config: replaced,
+            ..edit
+        }
+    }
+
+    /// The one line read out in screen reader mode: the latest error or message, else the status
+    pub fn status_announcement(&self) -> String {
+        if let Some(error) = &self.ui.error_banner {
+            return format!("Error: {error}. Esc dismisses.");
+        }
+        if let Some((message, _)) = self
+            .ui
+            .toast
+            .as_ref()
+            .filter(|(_, shown_at)| shown_at.elapsed() < TOAST_DURATION)
+        {
+            // Drop leading symbols like `✓`, which are read out as their names
+            return message
+                .trim_start_matches(|c: char| !c.is_alphanumeric())
+                .to_string();
+        }
+
+        let status = match &self.data.loading_state {
+            LoadingState::Idle => "No spec loaded".to_string(),
+            LoadingState::Fetching => "Loading spec".to_string(),
+            LoadingState::Parsing => "Parsing spec".to_string(),
+            LoadingState::Error(error) => format!("Spec failed to load: {error}. Ctrl+R retries."),
+            LoadingState::Complete => format!("{} endpoints", self.data.endpoints.len()),
+        };
+        let running = self.request.jobs.len();
+        let detail = if running > 0 {
+            Some(format!("{running} requests running"))
+        } else {
+            self.request
+                .current_response
+                .as_ref()
+                .map(|response| format!("response {} {}", response.status, response.status_text))
+        };
+        match detail {
+            Some(detail) => format!("{status}. {detail}."),
+            None => format!("{status}."),
+        }
+    }
+
+    /// Approximate memory taken by the kept responses and traffic log, in bytes
+    ///
+    /// Response bodies shared between them are counted once.
+    pub fn stored_response_bytes(&self) -> usize {
+        let bodies = self
+            .request
+            .responses
+            .values()
+            .map(|stored| &stored.response.body)
+            .chain(self.request.current_response.iter().map(|r| &r.body))
+            .chain(
+                self.request
+                    .traffic
+                    .iter()
+                    .map(|entry| &entry.response_body),
+            );
+        let mut seen = HashSet::new();
+        let body_bytes: usize = bodies
+            .filter(|body| seen.insert(body.bytes().as_ptr()))
+            .map(|body| body.bytes().len())
+            .sum();
+        let lines: usize = self.request.response_lines.iter().map(String::len).sum();
+        let request_bodies: usize = self
+            .request
+            .traffic
+            .iter()
+            .map(|entry| entry.request_body.as_ref().map_or(0, String::len))
+            .sum();
+        body_bytes + lines + request_bodies
+    }
+
+    /// Show the response of an endpoint, when it isn't shown already
+    pub fn show_response_for(&mut self, endpoint_key: Option<String>) {
+        if self.request.response_endpoint == endpoint_key {
+            return;
+        }
+        let response = endpoint_key
+            .as_ref()
+            .and_then(|key| self.request.responses.get(key))
+            .cloned();
+        self.show_stored_response(response.as_ref());
+        self.request.response_endpoint = endpoint_key;
+    }
+
+    fn show_stored_response(&mut self, response: Option<&EndpointResponse>) {
+        self.set_response(response.map(|response| response.response.clone()));
+        if let Some(response) = response {
+            self.request.followed_link = response.followed_link.clone();
+            self.request.reauth_note = response.reauth_note.clone();
+        }
+    }
+
+    /// Tab shown in the details panel; with three panes the response has its own
+    /// pane, so the Request tab stays in view while the Response tab is active
+    pub fn details_pane_tab(&self) -> DetailTab {
+        match &self.ui.active_detail_tab {
+            DetailTab::Response if self.screen_layout().three_pane => DetailTab::Request,
+            tab => tab.clone(),
+        }
+    }
+
+    /// Which parts of the main screen fit the terminal
+    pub fn screen_layout(&self) -> ScreenLayout {
+        let (width, height) = self.ui.terminal_size;
+        ScreenLayout::new(width, height, self.ui.three_pane, self.ui.arrangement)
+    }
+
+    /// Adapt to a new terminal size, keeping the selected response line in view
+    pub fn resize(&mut self, width: u16, height: u16) {
+        self.ui.terminal_size = (width, height);
+        let viewport_height = self.response_viewport_height();
+        if self.ui.response_selected_line >= self.ui.response_scroll + viewport_height {
+            self.ui.response_scroll = self.ui.response_selected_line + 1 - viewport_height;
+        }
+    }
+
+    /// Lines of the response shown at once, for keeping the selected line in view
+    pub fn response_viewport_height(&self) -> usize {
+        let layout = self.screen_layout();
+        // Header, search bar, footer, panel borders and, unless in its own pane, the tab bar
+        let tab_bar = if layout.three_pane { 0 } else { 1 };
+        let body = self
+            .ui
+            .terminal_size
+            .1
+            .saturating_sub(3 + 3 + layout.footer_height());
+        let pane = if layout.stacked {
+            body - body * STACKED_ENDPOINTS_PERCENT / 100
+        } else {
+            body
+        };
+        usize::from(pane.saturating_sub(2 + tab_bar)).max(1)
+    }
+
+    /// Values the console can refer to: `endpoints`, `response`, `spec` and assigned variables
+    pub fn console_scope(&self) -> Scope {
+        let mut scope = Scope::default();
+        scope.set(
+            "endpoints",
+            self.data
+                .endpoints
+                .iter()
+                .map(|endpoint| {
+                    serde_json::json!({
+                        "method": endpoint.method,
+                        "path": endpoint.path,
+                        "summary": endpoint.summary,
+                        "tags": endpoint.tags,
+                    })
+                })
+                .collect(),
+        );
+        let response = self.request.current_response.as_ref().map(|response| {
+            serde_json::json!({
+                "status": response.status,
+                "status_text": response.status_text,
+                "headers": response.headers,
+                "body": serde_json::from_str::<serde_json::Value>(&response.body)
+                    .unwrap_or_else(|_| response.body.text().into()),
+                "duration_ms": response.duration.as_millis() as u64,
+            })
+        });
+        scope.set("response", response.unwrap_or_default());
+        scope.set(
+            "spec",
+            serde_json::json!({
+                "title": self.data.spec_info.title,
+                "version": self.data.spec_info.version,
+            }),
+        );
+        for (name, value) in &self.input.console_vars {
+            scope.set(name, value.clone());
+        }
+        scope
+    }
+
+    /// Formatted response body lines within the current display limit
+    pub fn visible_response_lines(&self) -> &[String] {
+        let lines = &self.request.response_lines;
+        &lines[..lines.len().min(self.ui.response_line_limit)]
+    }
+
+    /// Indices of response body lines containing the response search query (case-insensitive)
+    pub fn response_matches(&self) -> Vec<usize> {
+        let query = self.search.response_query.to_ascii_lowercase();
+        if query.is_empty() {
+            return Vec::new();
+        }
+        self.request
+            .response_lines
+            .iter()
+            .enumerate()
+            .filter(|(_, line)| line.to_ascii_lowercase().contains(&query))
+            .map(|(idx, _)| idx)
+            .collect()
+    }
+
+    /// Select the next (or previous) matching response line, wrapping around
+    ///
+    /// Loads more lines when the match lies past the display limit.
+    pub fn jump_to_response_match(&mut self, forward: bool) {
+        let matches = self.response_matches();
+        // Body lines start after the status and history lines
+        let current = self.ui.response_selected_line.saturating_sub(2);
+        let at_body = self.ui.response_selected_line >= 2;
+
+        let target = if forward {
+            matches
+                .iter()
+                .find(|&&idx| idx > current || (!at_body && idx == current))
+                .or(matches.first())
+        } else {
+            matches
+                .iter()
+                .rev()
+                .find(|&&idx| idx < current)
+                .or(matches.last())
+        };
+        let Some(&target) = target else {
+            return;
+        };
+
+        while self.ui.response_line_limit <= target {
+            self.ui.response_line_limit += RESPONSE_LINE_PAGE;
+        }
+        self.ui.response_selected_line = target + 2;
+
+        // Keep the match on screen
+        let viewport_height = self.response_viewport_height();
+        if self.ui.response_selected_line < self.ui.response_scroll
+            || self.ui.response_selected_line >= self.ui.response_scroll + viewport_height
+        {
+            self.ui.response_scroll = self
+                .ui
+                .response_selected_line
+                .saturating_sub(viewport_height / 2);
+        }
+    }
+
+    /// Inclusive range of response lines in the visual selection, if active
+    pub fn response_visual_range(&self) -> Option<(usize, usize)> {
+        self.ui.response_visual_anchor.map(|anchor| {
+            let cursor = self.ui.response_selected_line;
+            (anchor.min(cursor), anchor.max(cursor))
+        })
+    }
+
+    /// Table of the current response when the table view is on and the body is tabular
+    pub fn response_table(&self) -> Option<ResponseTable> {
+        if !self.ui.response_table {
+            return None;
+        }
+        let response = self.request.current_response.as_ref()?;
+        if response.is_error {
+            return None;
+        }
+
+        let mut table = ResponseTable::from_body(&response.body)?;
+        if let Some(sort) = self.ui.table_sort {
+            table.sort(sort);
+        }
+        Some(table)
+    }
+
+    /// Save the current response as a named snapshot, replacing one with the same name
+    pub fn save_snapshot(&mut self, endpoint_key: &str, name: String) {
+        let Some(response) = self
+            .request
+            .current_response
+            .as_ref()
+            .filter(|r| !r.is_error)
+        else {
+            return;
+        };
+        let snapshot = ResponseSnapshot::capture(name, response, &self.request.response_lines);
+        let snapshots = self
+            .request
+            .snapshots
+            .entry(endpoint_key.to_string())
+            .or_default();
+
+        match snapshots.iter().position(|s| s.name == snapshot.name) {
+            Some(index) => snapshots[index] = snapshot,
+            None => snapshots.push(snapshot),
+        }
+        self.request.snapshots_dirty = true;
+    }
+
+    /// Diff against the next snapshot of the endpoint, turning the diff off after the last
+    pub fn cycle_snapshot_diff(&mut self, endpoint_key: &str) {
+        let count = self.request.snapshots.get(endpoint_key).map_or(0, Vec::len);
+
+        self.ui.snapshot_diff = match &self.ui.snapshot_diff {
+            Some((key, index)) if key == endpoint_key && index + 1 < count => {
+                Some((key.clone(), index + 1))
+            }
+            Some((key, _)) if key == endpoint_key => None,
+            _ if count > 0 => Some((endpoint_key.to_string(), 0)),
+            _ => None,
+        };
+        self.ui.response_selected_line = 0;
+        self.ui.response_scroll = 0;
+    }
+
+    /// Snapshot the response of the endpoint is being diffed against, if any
+    pub fn active_snapshot(&self, endpoint_key: &str) -> Option<&ResponseSnapshot> {
+        let (key, index) = self.ui.snapshot_diff.as_ref()?;
+        if key != endpoint_key {
+            return None;
+        }
+        self.request.snapshots.get(key)?.get(*index)
+    }
+
     /// Compute render items for grouped view on-demand
     /// This builds the flattened list of group headers and endpoints
     pub fn get_render_items(&self) -> Vec<RenderItem> {
         let mut render_items = Vec::new();
-        let grouped = self.active_grouped_endpoints();
-        let mut group_names: Vec<&String> = grouped.keys().collect();
-        group_names.sort();
+        let grouped = self.active_groups();
+        let group_names = self.data.grouping.sorted_names(grouped.keys());
 
         for group_name in group_names {
             let group_endpoints = &grouped[group_name];
@@ -178,7 +1359,7 @@ impl AppState {
             if is_expanded {
                 for endpoint in group_endpoints {
                     render_items.push(RenderItem::Endpoint {
-                        endpoint: endpoint.clone(),
+                        endpoint: Box::new(endpoint.clone()),
                     });
                 }
             }
@@ -190,54 +1371,240 @@ impl AppState {
     /// Get the selected endpoint based on the current view mode and selected index
     pub fn get_selected_endpoint(&self, selected_index: usize) -> Option<ApiEndpoint> {
         match self.ui.view_mode {
-            ViewMode::Flat => self.data.endpoints.get(selected_index).cloned(),
+            ViewMode::Flat => self.active_endpoints().get(selected_index).cloned(),
             ViewMode::Grouped => {
                 let render_items = self.get_render_items();
                 render_items
                     .get(selected_index)
                     .and_then(|item| match item {
-                        RenderItem::Endpoint { endpoint } => Some(endpoint.clone()),
+                        RenderItem::Endpoint { endpoint } => Some(endpoint.as_ref().clone()),
                         RenderItem::GroupHeader { .. } => None,
                     })
             }
         }
     }
 
+    /// Security schemes to authenticate with: those the endpoint accepts first, then the rest by name
+    pub fn auth_schemes_for(
+        &self,
+        endpoint: Option<&ApiEndpoint>,
+    ) -> Vec<(String, SecurityScheme)> {
+        let schemes = &self.data.security_schemes;
+        let mut names: Vec<&str> = endpoint
+            .map(ApiEndpoint::security_scheme_names)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|name| schemes.contains_key(*name))
+            .collect();
+        let mut others: Vec<&str> = schemes
+            .keys()
+            .map(String::as_str)
+            .filter(|name| !names.contains(name))
+            .collect();
+        others.sort();
+        names.extend(others);
+        names
+            .into_iter()
+            .map(|name| (name.to_string(), schemes[name].clone()))
+            .collect()
+    }
+
+    /// Find the name of the group that contains the item at `index` in grouped view
+    pub fn group_for_index(&self, index: usize) -> Option<String> {
+        self.get_render_items()
+            .iter()
+            .take(index + 1)
+            .rev()
+            .find_map(|item| match item {
+                RenderItem::GroupHeader { name, .. } => Some(name.clone()),
+                RenderItem::Endpoint { .. } => None,
+            })
+    }
+
+    /// Remember the endpoint at `index` as the last selection of its group
+    pub fn remember_selection(&mut self, index: usize) {
+        if self.ui.view_mode != ViewMode::Grouped {
+            return;
+        }
+
+        if let (Some(endpoint), Some(group)) = (
+            self.get_selected_endpoint(index),
+            self.group_for_index(index),
+        ) {
+            self.ui.last_selected_by_group.insert(group, endpoint.key());
+        }
+    }
+
+    /// Remember the scroll position of the active tab for the endpoint it belongs to
+    pub fn store_view_position(&mut self) {
+        let Some(endpoint_key) = self.ui.position_endpoint.clone() else {
+            return;
+        };
+        let position = match self.ui.active_detail_tab {
+            DetailTab::Response => ViewPosition {
+                scroll: self.ui.response_scroll,
+                selected_line: self.ui.response_selected_line,
+            },
+            DetailTab::Headers => ViewPosition {
+                scroll: self.ui.headers_scroll,
+                selected_line: 0,
+            },
+            _ => return,
+        };
+        self.ui
+            .view_positions
+            .insert((endpoint_key, self.ui.active_detail_tab.clone()), position);
+    }
+
+    /// Restore the remembered position of the active tab for an endpoint (top if none)
+    pub fn load_view_position(&mut self, endpoint_key: Option<String>) {
+        let position = endpoint_key
+            .as_ref()
+            .and_then(|key| {
+                self.ui
+                    .view_positions
+                    .get(&(key.clone(), self.ui.active_detail_tab.clone()))
+            })
+            .copied()
+            .unwrap_or_default();
+
+        match self.ui.active_detail_tab {
+            DetailTab::Headers => self.ui.headers_scroll = position.scroll,
+            _ => {
+                self.ui.response_scroll = position.scroll;
+                self.ui.response_selected_line = position.selected_line;
+            }
+        }
+        self.ui.response_visual_anchor = None;
+        self.ui.position_endpoint = endpoint_key;
+    }
+
+    /// Change the details tab; each tab keeps its own scroll position per endpoint
+    pub fn switch_detail_tab(&mut self, tab: DetailTab) {
+        self.store_view_position();
+        self.ui.active_detail_tab = tab;
+        self.load_view_position(self.ui.position_endpoint.clone());
+    }
+
+    /// Find the render index of an endpoint inside a specific (expanded) group
+    pub fn find_in_group(&self, group: &str, key: &str) -> Option<usize> {
+        let mut in_group = false;
+        for (idx, item) in self.get_render_items().iter().enumerate() {
+            match item {
+                RenderItem::GroupHeader { name, .. } => in_group = name == group,
+                RenderItem::Endpoint { endpoint } if in_group && endpoint.key() == key => {
+                    return Some(idx);
+                }
+                RenderItem::Endpoint { .. } => {}
+            }
+        }
+        None
+    }
+
+    /// Locate an endpoint by key in the current view, expanding its group if needed
+    pub fn locate_endpoint(&mut self, key: &str) -> Option<usize> {
+        match self.ui.view_mode {
+            ViewMode::Flat => self
+                .active_endpoints()
+                .iter()
+                .position(|endpoint| endpoint.key() == key),
+            ViewMode::Grouped => {
+                let grouped = self.active_groups();
+                let candidates = self.data.grouping.sorted_names(
+                    grouped
+                        .iter()
+                        .filter(|(_, endpoints)| endpoints.iter().any(|ep| ep.key() == key))
+                        .map(|(name, _)| name),
+                );
+
+                // Prefer a group that is already open
+                let group = candidates
+                    .iter()
+                    .find(|name| self.ui.expanded_groups.contains(**name))
+                    .or(candidates.first())
+                    .map(|name| (*name).clone())?;
+
+                self.ui.expanded_groups.insert(group.clone());
+                self.find_in_group(&group, key)
+            }
+        }
+    }
+
     /// Get or create request config for an endpoint, initializing with Swagger defaults
     pub fn get_or_create_request_config(&mut self, endpoint: &ApiEndpoint) -> &mut RequestConfig {
         self.request
             .configs
-            .entry(endpoint.path.clone())
-            .or_insert_with(|| {
-                let mut config = RequestConfig::default();
-
-                // Initialize parameters from Swagger spec
-                for param in &endpoint.parameters {
-                    let param_type = match param.location.as_str() {
-                        "path" => Some(ParameterType::Path),
-                        "query" => Some(ParameterType::Query),
-                        _ => None, // header, cookie, etc. - we don't support yet
-                    };
-
-                    if let Some(param_type) = param_type {
-                        let value = param
-                            .schema
-                            .as_ref()
-                            .and_then(|schema| schema.default.as_ref())
-                            .map(json_value_to_string)
-                            .unwrap_or_default();
-
-                        config.set_param(param.name.clone(), value, param_type);
-                    }
+            .entry(endpoint.key())
+            .or_insert_with(|| new_request_config(endpoint))
+    }
+
+    /// Fill every empty path, query and header parameter of an endpoint from its spec default/example
+    ///
+    /// Returns the number of parameters that were filled.
+    pub fn fill_spec_defaults(&mut self, endpoint_key: &str) -> usize {
+        let Some(endpoint) = self.get_endpoint_by_key(endpoint_key).cloned() else {
+            return 0;
+        };
+        let config = self.get_or_create_request_config(&endpoint);
+        let mut filled = 0;
+
+        for (param, param_type) in endpoint.request_params() {
+            let is_empty = config
+                .get_param_value(&param.name)
+                .map(str::is_empty)
+                .unwrap_or(true);
+            if let (true, Some(value)) = (is_empty, param.spec_value()) {
+                config.set_param(param.name.clone(), json_value_to_string(value), param_type);
+                filled += 1;
+            }
+        }
+
+        filled
+    }
+
+    /// Set path, query and header parameters from a pasted JSON object or query string
+    ///
+    /// Keys match parameter names exactly, or else ignoring case. Returns how many
+    /// parameters were set and the keys that match none.
+    pub fn fill_pasted_params(
+        &mut self,
+        endpoint_key: &str,
+        text: &str,
+    ) -> Result<(usize, Vec<String>), String> {
+        let pairs = parse_pasted_params(text)?;
+        let Some(endpoint) = self.get_endpoint_by_key(endpoint_key).cloned() else {
+            return Ok((0, pairs.into_iter().map(|(name, _)| name).collect()));
+        };
+        let params: Vec<_> = endpoint
+            .request_params()
+            .into_iter()
+            .map(|(param, param_type)| (param.name.clone(), param_type))
+            .collect();
+        let config = self.get_or_create_request_config(&endpoint);
+        let mut filled = 0;
+        let mut unknown = Vec::new();
+
+        for (name, value) in pairs {
+            let matched = params.iter().find(|(param, _)| *param == name).or_else(|| {
+                params
+                    .iter()
+                    .find(|(param, _)| param.eq_ignore_ascii_case(&name))
+            });
+            match matched {
+                Some((param, param_type)) => {
+                    config.set_param(param.clone(), value, param_type.clone());
+                    filled += 1;
                 }
+                None => unknown.push(name),
+            }
+        }
 
-                config
-            })
+        Ok((filled, unknown))
     }
 
     /// Get the active endpoints list (filtered or full)
     pub fn active_endpoints(&self) -> &[ApiEndpoint] {
-        if self.search.query.is_empty() {
+        if !self.search.is_filtering() {
             &self.data.endpoints
         } else {
             &self.search.filtered_endpoints
@@ -246,26 +1613,156 @@ impl AppState {
 
     /// Get the active grouped endpoints (filtered or full)
     pub fn active_grouped_endpoints(&self) -> &HashMap<String, Vec<ApiEndpoint>> {
-        if self.search.query.is_empty() {
+        if !self.search.is_filtering() {
             &self.data.grouped_endpoints
         } else {
             &self.search.filtered_grouped_endpoints
         }
     }
 
-    /// Get an endpoint by its path
-    pub fn get_selected_endpoint_by_path(&self, path: &str) -> Option<&ApiEndpoint> {
-        self.data.endpoints.iter().find(|ep| ep.path == path)
+    /// Groups shown in grouped view, following the selected grouping strategy
+    pub fn active_groups(&self) -> Cow<'_, HashMap<String, Vec<ApiEndpoint>>> {
+        match self.ui.group_by {
+            GroupBy::Tag => Cow::Borrowed(self.active_grouped_endpoints()),
+            GroupBy::PathPrefix => Cow::Owned(
+                self.data
+                    .grouping
+                    .build(self.active_endpoints(), GroupBy::PathPrefix),
+            ),
+        }
+    }
+
+    /// Store freshly parsed endpoints and rebuild their tag groups and the filtered list
+    ///
+    /// When endpoints were loaded before, the selected one stays selected.
+    pub fn set_endpoints(&mut self, endpoints: Vec<ApiEndpoint>) {
+        if !self.data.endpoints.is_empty() && self.ui.pending_selection.is_none() {
+            self.ui.pending_selection = self.request.response_endpoint.clone();
+        }
+        self.data.grouped_endpoints = self.data.grouping.build(&endpoints, GroupBy::Tag);
+        self.data.endpoints = endpoints;
+        self.update_filtered_endpoints();
+    }
+
+    /// Compare the loaded endpoints with the previous load and remember them for the next one
+    ///
+    /// Changes are announced with a toast and kept for the changes view.
+    pub fn record_spec_changes(&mut self) {
+        let signatures = spec_diff::signatures(&self.data.endpoints);
+        let refreshed = std::mem::take(&mut self.data.refresh_requested);
+        if let Some(previous) = &self.data.spec_signatures {
+            let diff = SpecDiff::between(previous, &signatures);
+            if !diff.is_empty() {
+                self.ui.toast = Some((
+                    format!("Spec changed: {} (A shows the changes)", diff.summary()),
+                    Instant::now(),
+                ));
+                self.data.spec_diff = Some(diff);
+            } else if refreshed {
+                self.ui.toast = Some(("Spec reloaded: no changes".to_string(), Instant::now()));
+            }
+        }
+        if self.data.spec_signatures.as_ref() != Some(&signatures) {
+            self.data.spec_signatures = Some(signatures);
+            self.data.spec_signatures_dirty = true;
+        }
+    }
+
+    /// Get an endpoint by its key (`METHOD path`, see `ApiEndpoint::key`)
+    pub fn get_endpoint_by_key(&self, key: &str) -> Option<&ApiEndpoint> {
+        self.data.endpoints.iter().find(|ep| ep.key() == key)
+    }
+
+    /// Get or create request config by endpoint key, initializing with Swagger defaults
+    /// when the endpoint is loaded
+    pub fn get_or_create_request_config_by_key(&mut self, key: &str) -> &mut RequestConfig {
+        if !self.request.configs.contains_key(key) {
+            let config = self
+                .get_endpoint_by_key(key)
+                .map(new_request_config)
+                .unwrap_or_default();
+            self.request.configs.insert(key.to_string(), config);
+        }
+        self.request.configs.entry(key.to_string()).or_default()
+    }
+
+    /// Set or (when empty) remove the note of an endpoint
+    pub fn set_note(&mut self, endpoint_key: String, note: &str) {
+        let note = note.trim();
+        let changed = if note.is_empty() {
+            self.data.notes.remove(&endpoint_key).is_some()
+        } else {
+            self.data
+                .notes
+                .insert(endpoint_key, note.to_string())
+                .as_deref()
+                != Some(note)
+        };
+        self.data.notes_dirty |= changed;
+    }
+
+    /// Change the search query; returns the new index of the endpoint at `selected_index`,
+    /// or the top when the filter no longer matches it
+    pub fn set_search_query(&mut self, query: String, selected_index: usize) -> usize {
+        let selected_key = self
+            .get_selected_endpoint(selected_index)
+            .map(|endpoint| endpoint.key());
+        self.search.query = query;
+        self.update_filtered_endpoints();
+        selected_key
+            .and_then(|key| self.locate_endpoint(&key))
+            .unwrap_or(0)
+    }
+
+    /// Limit the search to a group, or (`None`) search all endpoints again; returns the new
+    /// index of the endpoint at `selected_index`, as `set_search_query` does
+    pub fn set_search_scope(&mut self, scope: Option<String>, selected_index: usize) -> usize {
+        if self.search.scope == scope {
+            return selected_index;
+        }
+        if let Some(group) = &scope {
+            self.ui.expanded_groups.insert(group.clone());
+        }
+        self.search.scope = scope;
+        let query = self.search.query.clone();
+        self.set_search_query(query, selected_index)
+    }
+
+    /// Show or hide deprecated endpoints; returns the new index of the endpoint at
+    /// `selected_index`, as `set_search_query` does
+    pub fn set_hide_deprecated(&mut self, hide: bool, selected_index: usize) -> usize {
+        if self.search.hide_deprecated == hide {
+            return selected_index;
+        }
+        let selected_key = self
+            .get_selected_endpoint(selected_index)
+            .map(|endpoint| endpoint.key());
+        self.search.hide_deprecated = hide;
+        self.update_filtered_endpoints();
+        selected_key
+            .and_then(|key| self.locate_endpoint(&key))
+            .unwrap_or(0)
     }
 
-    /// Get or create request config by endpoint path
-    pub fn get_or_create_request_config_by_path(&mut self, path: &str) -> &mut RequestConfig {
-        self.request.configs.entry(path.to_string()).or_default()
+    /// The group of the selected header or endpoint in the grouped view
+    pub fn selected_group(&self, selected_index: usize) -> Option<String> {
+        if self.ui.view_mode != ViewMode::Grouped {
+            return None;
+        }
+        let items = self.get_render_items();
+        items
+            .get(..=selected_index)?
+            .iter()
+            .rev()
+            .find_map(|item| match item {
+                RenderItem::GroupHeader { name, .. } => Some(name.clone()),
+                RenderItem::Endpoint { .. } => None,
+            })
     }
 
     /// Filter endpoints based on search query
     pub fn update_filtered_endpoints(&mut self) {
-        if self.search.query.is_empty() {
+        if !self.search.is_filtering() {
             self.search.filtered_endpoints.clear();
             self.search.filtered_grouped_endpoints.clear();
             return;
@@ -273,11 +1770,27 @@ impl AppState {
 
         let query = self.search.query.to_lowercase();
 
-        // Filter endpoints by path, method, summary, or tags
-        self.search.filtered_endpoints = self
-            .data
-            .endpoints
+        // Only the endpoints of the scope group, when searching within one
+        let scoped = self.search.scope.as_ref().map(|group| {
+            let groups = match self.ui.group_by {
+                GroupBy::Tag => Cow::Borrowed(&self.data.grouped_endpoints),
+                GroupBy::PathPrefix => Cow::Owned(
+                    self.data
+                        .grouping
+                        .build(&self.data.endpoints, GroupBy::PathPrefix),
+                ),
+            };
+            groups.get(group).cloned().unwrap_or_default()
+        });
+
+        // Filter endpoints by path, method, summary, tags, or notes
+        let notes = &self.data.notes;
+        let hide_deprecated = self.search.hide_deprecated;
+        self.search.filtered_endpoints = scoped
+            .as_deref()
+            .unwrap_or(&self.data.endpoints)
             .iter()
+            .filter(|ep| !(hide_deprecated && ep.deprecated))
             .filter(|ep| {
                 ep.path.to_lowercase().contains(&query)
                     || ep.method.to_lowercase().contains(&query)
@@ -290,33 +1803,44 @@ impl AppState {
                         .tags
                         .iter()
                         .any(|tag| tag.to_lowercase().contains(&query))
+                    || notes
+                        .get(&ep.key())
+                        .is_some_and(|note| note.to_lowercase().contains(&query))
             })
             .cloned()
             .collect();
 
         // Rebuild grouped endpoints from filtered list
-        self.search.filtered_grouped_endpoints.clear();
-        for endpoint in &self.search.filtered_endpoints {
-            for tag in &endpoint.tags {
-                self.search
-                    .filtered_grouped_endpoints
-                    .entry(tag.clone())
-                    .or_default()
-                    .push(endpoint.clone());
-            }
-
-            // Handle endpoints without tags
-            if endpoint.tags.is_empty() {
-                self.search
-                    .filtered_grouped_endpoints
-                    .entry("Other".to_string())
-                    .or_default()
-                    .push(endpoint.clone());
-            }
+        self.search.filtered_grouped_endpoints = self
+            .data
+            .grouping
+            .build(&self.search.filtered_endpoints, GroupBy::Tag);
+        // Endpoints with several tags would show up in their other groups too
+        if let Some(group) = &self.search.scope {
+            self.search
+                .filtered_grouped_endpoints
+                .retain(|name, _| name == group);
         }
     }
 }
 
+/// A new endpoint's config: parameters set to their schema defaults, the others empty
+/// (cookie parameters are not supported)
+fn new_request_config(endpoint: &ApiEndpoint) -> RequestConfig {
+    let mut config = RequestConfig::default();
+    for (param, param_type) in endpoint.request_params() {
+        let value = param
+            .schema
+            .as_ref()
+            .and_then(|schema| schema.default.as_ref())
+            .map(json_value_to_string)
+            .unwrap_or_default();
+
+        config.set_param(param.name.clone(), value, param_type);
+    }
+    config
+}
+
 fn json_value_to_string(value: &serde_json::Value) -> String {
     match value {
         serde_json::Value::String(s) => s.clone(),
@@ -326,15 +1850,43 @@ fn json_value_to_string(value: &serde_json::Value) -> String {
     }
 }
 
+/// Whether pasted text is the start of a JSON object that continues on the next line
+pub fn is_partial_json_object(text: &str) -> bool {
+    text.trim_start().starts_with('{')
+        && serde_json::from_str::<serde_json::Value>(text).is_err_and(|e| e.is_eof())
+}
+
+/// Name/value pairs of a JSON object, or of a query string given as `a=1&b=2`, `?a=1` or a URL
+fn parse_pasted_params(text: &str) -> Result<Vec<(String, String)>, String> {
+    let text = text.trim();
+    if text.starts_with('{') {
+        let object: serde_json::Map<String, serde_json::Value> =
+            serde_json::from_str(text).map_err(|e| format!("Invalid JSON: {e}"))?;
+        return Ok(object
+            .iter()
+            .map(|(name, value)| (name.clone(), json_value_to_string(value)))
+            .collect());
+    }
+
+    let query = text.split_once('?').map_or(text, |(_, query)| query);
+    let query = query.split('#').next().unwrap_or_default();
+    if !query.contains('=') {
+        return Err("Expected a JSON object or a query string like a=1&b=2".to_string());
+    }
+    Ok(url::form_urlencoded::parse(query.as_bytes())
+        .filter(|(name, _)| !name.is_empty())
+        .map(|(name, value)| (name.into_owned(), value.into_owned()))
+        .collect())
+}
+
 /// Helper function to count visible items in current view mode
 pub fn count_visible_items(state: &AppState) -> usize {
     match state.ui.view_mode {
         ViewMode::Flat => state.active_endpoints().len(),
         ViewMode::Grouped => {
             let mut count = 0;
-            let grouped = state.active_grouped_endpoints();
-            let mut group_names: Vec<&String> = grouped.keys().collect();
-            group_names.sort();
+            let grouped = state.active_groups();
+            let group_names = state.data.grouping.sorted_names(grouped.keys());
 
             for group_name in group_names {
                 count += 1; // Group header
@@ -351,6 +1903,498 @@ pub fn count_visible_items(state: &AppState) -> usize {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::types::{ApiParameter, ParameterSchema, ParameterType};
+
+    fn create_endpoint(method: &str, path: &str, tags: &[&str]) -> ApiEndpoint {
+        ApiEndpoint {
+            method: method.to_string(),
+            request_body: None,
+            path: path.to_string(),
+            summary: None,
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            parameters: vec![],
+            response_example: None,
+            responses: vec![],
+            deprecated: false,
+            description: None,
+            security: None,
+            extensions: vec![],
+            event: None,
+            service: None,
+            base_url: None,
+        }
+    }
+
+    fn create_grouped_state() -> AppState {
+        let mut state = AppState::default();
+        state.data.endpoints = vec![
+            create_endpoint("GET", "/users", &["Users"]),
+            create_endpoint("POST", "/users", &["Users"]),
+            create_endpoint("GET", "/posts", &["Posts"]),
+        ];
+        for endpoint in &state.data.endpoints {
+            state
+                .data
+                .grouped_endpoints
+                .entry(endpoint.tags[0].clone())
+                .or_default()
+                .push(endpoint.clone());
+        }
+        state
+    }
+
+    #[test]
+    fn test_notes_are_searchable() {
+        let mut state = create_grouped_state();
+        state.set_note("GET /posts".to_string(), "  returns 500 if tenant missing ");
+        assert_eq!(
+            state.data.notes["GET /posts"],
+            "returns 500 if tenant missing"
+        );
+        assert!(state.data.notes_dirty);
+
+        state.search.query = "TENANT".to_string();
+        state.update_filtered_endpoints();
+        assert_eq!(state.search.filtered_endpoints.len(), 1);
+        assert_eq!(state.search.filtered_endpoints[0].path, "/posts");
+
+        state.set_note("GET /posts".to_string(), "");
+        assert!(state.data.notes.is_empty());
+    }
+
+    #[test]
+    fn test_hide_deprecated() {
+        let mut state = create_grouped_state();
+        state.ui.view_mode = ViewMode::Flat;
+        state.data.endpoints[1].deprecated = true;
+        state.data.grouped_endpoints.get_mut("Users").unwrap()[1].deprecated = true;
+
+        // The selected GET /posts keeps its selection as POST /users disappears
+        assert_eq!(state.set_hide_deprecated(true, 2), 1);
+        assert!(state.search.is_filtering());
+        let paths: Vec<_> = state.active_endpoints().iter().map(|ep| ep.key()).collect();
+        assert_eq!(paths, vec!["GET /users", "GET /posts"]);
+        assert_eq!(state.active_grouped_endpoints()["Users"].len(), 1);
+
+        state.search.query = "users".to_string();
+        state.update_filtered_endpoints();
+        assert_eq!(state.active_endpoints().len(), 1);
+
+        state.search.query.clear();
+        state.set_hide_deprecated(false, 0);
+        assert!(!state.search.is_filtering());
+        assert_eq!(state.active_endpoints().len(), 3);
+    }
+
+    #[test]
+    fn test_record_spec_changes() {
+        let mut state = AppState::default();
+        state.set_endpoints(vec![create_endpoint("GET", "/users", &["Users"])]);
+        state.record_spec_changes();
+        assert!(state.data.spec_signatures_dirty);
+        assert!(state.data.spec_diff.is_none());
+
+        state.data.spec_signatures_dirty = false;
+        state.data.refresh_requested = true;
+        state.record_spec_changes();
+        assert!(!state.data.spec_signatures_dirty);
+        assert_eq!(
+            state.ui.toast.as_ref().unwrap().0,
+            "Spec reloaded: no changes"
+        );
+
+        state.set_endpoints(vec![
+            create_endpoint("GET", "/users", &["Users"]),
+            create_endpoint("POST", "/users", &["Users"]),
+        ]);
+        state.record_spec_changes();
+        assert!(state.data.spec_signatures_dirty);
+        assert_eq!(state.data.spec_diff.unwrap().added, vec!["POST /users"]);
+    }
+
+    #[test]
+    fn test_reload_keeps_selection() {
+        let mut state = AppState::default();
+        state.set_endpoints(vec![create_endpoint("GET", "/users", &["Users"])]);
+        assert_eq!(state.ui.pending_selection, None);
+
+        state.request.response_endpoint = Some("GET /users".to_string());
+        state.set_endpoints(vec![
+            create_endpoint("GET", "/accounts", &["Accounts"]),
+            create_endpoint("GET", "/users", &["Users"]),
+        ]);
+        assert_eq!(state.ui.pending_selection.as_deref(), Some("GET /users"));
+    }
+
+    #[test]
+    fn test_search_scoped_to_group() {
+        let mut state = create_grouped_state();
+        state.ui.view_mode = ViewMode::Grouped;
+        assert_eq!(state.selected_group(1).as_deref(), Some("Users"));
+
+        state.set_search_scope(Some("Users".to_string()), 1);
+        assert!(state.ui.expanded_groups.contains("Users"));
+        state.set_search_query("get".to_string(), 0);
+        let keys: Vec<String> = state.active_endpoints().iter().map(|ep| ep.key()).collect();
+        assert_eq!(keys, vec!["GET /users"]);
+        assert_eq!(state.active_groups().len(), 1);
+
+        state.set_search_scope(None, 0);
+        assert_eq!(state.active_endpoints().len(), 2);
+    }
+
+    #[test]
+    fn test_refilter_keeps_selection() {
+        let mut state = create_grouped_state();
+        state.ui.view_mode = ViewMode::Flat;
+
+        // Refining the query keeps POST /users selected at its new index
+        let index = state.set_search_query("u".to_string(), 1);
+        assert_eq!(index, 1);
+        let index = state.set_search_query("post".to_string(), index);
+        assert_eq!(state.active_endpoints()[index].key(), "POST /users");
+
+        // Once it no longer matches, the selection goes back to the top
+        assert_eq!(state.set_search_query("get".to_string(), index), 0);
+    }
+
+    #[test]
+    fn test_save_and_cycle_variants() {
+        let mut request = AppState::default().request;
+        let key = "GET /users";
+        let set_limit = |request: &mut RequestState, value: &str| {
+            request
+                .configs
+                .entry(key.to_string())
+                .or_default()
+                .set_param("limit".to_string(), value.to_string(), ParameterType::Query);
+        };
+
+        set_limit(&mut request, "1");
+        request.save_variant(key, "small".to_string());
+        set_limit(&mut request, "500");
+        request.save_variant(key, "large".to_string());
+        set_limit(&mut request, "2");
+        request.save_variant(key, "small".to_string());
+
+        assert_eq!(request.variants[key].len(), 2);
+        assert_eq!(request.active_variant[key], 0);
+        assert!(request.variants_dirty);
+
+        request.cycle_variant(key);
+        assert_eq!(request.configs[key].get_param_value("limit"), Some("500"));
+        request.cycle_variant(key);
+        assert_eq!(request.configs[key].get_param_value("limit"), Some("2"));
+    }
+
+    #[test]
+    fn test_fill_spec_defaults_only_fills_empty_params() {
+        let mut state = AppState::default();
+        let mut endpoint = create_endpoint("GET", "/users/{id}", &[]);
+        endpoint.parameters = vec![
+            ApiParameter {
+                name: "id".to_string(),
+                location: "path".to_string(),
+                example: Some(serde_json::json!(42)),
+                ..Default::default()
+            },
+            ApiParameter {
+                name: "limit".to_string(),
+                location: "query".to_string(),
+                schema: Some(ParameterSchema {
+                    default: Some(serde_json::json!(10)),
+                    example: Some(serde_json::json!(99)),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+            ApiParameter {
+                name: "sort".to_string(),
+                location: "query".to_string(),
+                example: Some(serde_json::json!("name")),
+                ..Default::default()
+            },
+        ];
+        state.data.endpoints = vec![endpoint];
+        let config = state.get_or_create_request_config_by_key("GET /users/{id}");
+        // A new config starts from the schema defaults, not the examples
+        assert_eq!(config.get_param_value("limit"), Some("10"));
+        assert_eq!(config.get_param_value("id"), Some(""));
+        config.set_param("sort".to_string(), "age".to_string(), ParameterType::Query);
+
+        assert_eq!(state.fill_spec_defaults("GET /users/{id}"), 1);
+
+        let config = &state.request.configs["GET /users/{id}"];
+        assert_eq!(config.get_param_value("id"), Some("42"));
+        assert_eq!(config.get_param_value("limit"), Some("10"));
+        assert_eq!(config.get_param_value("sort"), Some("age"));
+    }
+
+    #[test]
+    fn test_fill_pasted_params() {
+        let mut state = AppState::default();
+        let mut endpoint = create_endpoint("GET", "/users/{id}", &[]);
+        endpoint.parameters = ["id:path", "pageSize:query", "q:query"]
+            .iter()
+            .map(|spec| {
+                let (name, location) = spec.split_once(':').unwrap();
+                ApiParameter {
+                    name: name.to_string(),
+                    location: location.to_string(),
+                    ..Default::default()
+                }
+            })
+            .collect();
+        state.data.endpoints = vec![endpoint];
+        let key = "GET /users/{id}";
+
+        let json = r#"{"id": 7, "pagesize": 50, "extra": true}"#;
+        assert!(!is_partial_json_object(json));
+        assert!(is_partial_json_object(r#"{"id": 7,"#));
+        assert_eq!(
+            state.fill_pasted_params(key, json),
+            Ok((2, vec!["extra".to_string()]))
+        );
+        let config = &state.request.configs[key];
+        assert_eq!(config.get_param_value("id"), Some("7"));
+        assert_eq!(config.get_param_value("pageSize"), Some("50"));
+
+        let url = "https://api.example.com/users/7?q=a%20b&pageSize=5#top";
+        assert_eq!(state.fill_pasted_params(key, url), Ok((2, vec![])));
+        let config = &state.request.configs[key];
+        assert_eq!(config.get_param_value("q"), Some("a b"));
+        assert_eq!(config.get_param_value("pageSize"), Some("5"));
+
+        assert!(state.fill_pasted_params(key, "not params").is_err());
+        assert!(state.fill_pasted_params(key, "{\"id\": }").is_err());
+    }
+
+    #[test]
+    fn test_templates_merge_into_sent_config() {
+        let mut state = AppState::default();
+        let template =
+            |name: &str, endpoints: &[&str], params: &[(&str, &str)]| RequestTemplateConfig {
+                name: name.to_string(),
+                endpoints: endpoints.iter().map(|e| e.to_string()).collect(),
+                params: params
+                    .iter()
+                    .map(|(n, v)| (n.to_string(), v.to_string()))
+                    .collect(),
+                headers: [("Accept-Language".to_string(), format!("{name}-lang"))].into(),
+            };
+        let request = &mut state.request;
+        request.templates = vec![
+            template("tenant", &[], &[("tenantId", "acme"), ("pageSize", "20")]),
+            template(
+                "paging",
+                &["tag:Orders", "GET /users*"],
+                &[("pageSize", "50")],
+            ),
+        ];
+
+        let mut users = create_endpoint("GET", "/users", &[]);
+        users.parameters = ["tenantId", "pageSize", "q"]
+            .iter()
+            .map(|name| ApiParameter {
+                name: name.to_string(),
+                location: "query".to_string(),
+                ..Default::default()
+            })
+            .collect();
+        let mut own = RequestConfig::default();
+        own.set_param(
+            "tenantId".to_string(),
+            "mine".to_string(),
+            ParameterType::Query,
+        );
+
+        let merged = request.merged_config(&users, Some(&own)).unwrap();
+        assert_eq!(merged.get_param_value("tenantId"), Some("mine"));
+        assert_eq!(merged.get_param_value("pageSize"), Some("50"));
+        assert_eq!(merged.get_param_value("q"), None);
+        assert_eq!(request.template_names(&users), vec!["tenant", "paging"]);
+        assert_eq!(request.template_headers(&users).len(), 2);
+
+        // Only parameters the endpoint declares are filled
+        let delete = create_endpoint("DELETE", "/users/{id}", &[]);
+        assert_eq!(request.template_names(&delete), vec!["tenant"]);
+        assert_eq!(
+            request.merged_config(&delete, None),
+            Some(RequestConfig::default())
+        );
+    }
+
+    #[test]
+    fn test_request_configs_are_per_method() {
+        let mut state = AppState::default();
+        let get = create_endpoint("GET", "/users/{id}", &[]);
+        let delete = create_endpoint("DELETE", "/users/{id}", &[]);
+        state.data.endpoints = vec![get.clone(), delete.clone()];
+
+        state.get_or_create_request_config(&get).set_param(
+            "id".to_string(),
+            "1".to_string(),
+            ParameterType::Path,
+        );
+        assert_eq!(
+            state
+                .get_or_create_request_config(&delete)
+                .get_param_value("id"),
+            None
+        );
+        assert_eq!(
+            state
+                .get_endpoint_by_key("DELETE /users/{id}")
+                .map(|ep| ep.method.as_str()),
+            Some("DELETE")
+        );
+    }
+
+    #[test]
+    fn test_group_by_path_prefix() {
+        let mut state = create_grouped_state();
+        state
+            .data
+            .endpoints
+            .push(create_endpoint("GET", "/users/{id}/posts", &[]));
+        state.ui.group_by = GroupBy::PathPrefix;
+
+        let groups = state.active_groups();
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups["/users"].len(), 3);
+        assert_eq!(groups["/posts"].len(), 1);
+        drop(groups);
+
+        state.search.query = "posts".to_string();
+        state.update_filtered_endpoints();
+        assert_eq!(state.active_groups()["/users"].len(), 1);
+    }
+
+    #[test]
+    fn test_large_response_is_limited_until_more_is_loaded() {
+        let mut state = AppState::default();
+        let items: Vec<u32> = (0..RESPONSE_LINE_PAGE as u32).collect();
+        let mut response = ApiResponse::error(String::new());
+        response.is_error = false;
+        response.body = serde_json::to_string(&items).unwrap().into();
+
+        state.ui.response_line_limit = 10;
+        state.set_response(Some(response));
+        // Pretty-printed array: brackets plus one line per item
+        assert_eq!(state.request.response_lines.len(), RESPONSE_LINE_PAGE + 2);
+        assert_eq!(state.visible_response_lines().len(), RESPONSE_LINE_PAGE);
+
+        crate::actions::apply_action(crate::actions::AppAction::LoadMoreResponseLines, &mut state);
+        assert_eq!(state.visible_response_lines().len(), RESPONSE_LINE_PAGE + 2);
+
+        state.set_response(None);
+        assert!(state.request.response_lines.is_empty());
+    }
+
+    #[test]
+    fn test_response_history_keeps_latest() {
+        let mut request = AppState::default().request;
+        let mut response = ApiResponse::error(String::new());
+        for status in 0..(MAX_RESPONSE_HISTORY as u16 + 3) {
+            response.status = 200 + status;
+            request.record_response("GET /users".to_string(), &response);
+        }
+
+        let history = &request.response_history["GET /users"];
+        assert_eq!(history.len(), MAX_RESPONSE_HISTORY);
+        assert_eq!(history.first().map(|s| s.status), Some(203));
+        assert_eq!(
+            history.last().map(|s| s.status),
+            Some(202 + MAX_RESPONSE_HISTORY as u16)
+        );
+
+        // Usage stats keep counting past the history limit
+        assert_eq!(
+            request.usage_stats["GET /users"].runs,
+            MAX_RESPONSE_HISTORY as u32 + 3
+        );
+        assert!(request.usage_stats_dirty);
+    }
+
+    #[test]
+    fn test_details_pane_tab() {
+        let mut state = AppState::default();
+        state.ui.active_detail_tab = DetailTab::Response;
+        assert_eq!(state.details_pane_tab(), DetailTab::Response);
+
+        state.ui.three_pane = true;
+        state.resize(160, 40);
+        assert_eq!(state.details_pane_tab(), DetailTab::Request);
+        state.ui.active_detail_tab = DetailTab::Headers;
+        assert_eq!(state.details_pane_tab(), DetailTab::Headers);
+
+        // Too narrow for three panes: the response is back on its tab
+        state.ui.active_detail_tab = DetailTab::Response;
+        state.resize(100, 40);
+        assert_eq!(state.details_pane_tab(), DetailTab::Response);
+    }
+
+    #[test]
+    fn test_screen_layout_degrades_with_size() {
+        let full = ScreenLayout::new(160, 40, true, PanelArrangement::Auto);
+        assert!(full.side_by_side && full.three_pane && !full.compact_footer);
+
+        let split = ScreenLayout::new(70, 20, true, PanelArrangement::Auto);
+        assert!(!split.side_by_side && !split.stacked && !split.three_pane && split.compact_footer);
+        assert!(!split.too_small);
+        assert!(ScreenLayout::new(30, 20, false, PanelArrangement::Auto).too_small);
+
+        // Narrow but tall: the panels are stacked, unless forced next to each other
+        let tall = ScreenLayout::new(70, 40, false, PanelArrangement::Auto);
+        assert!(tall.stacked && !tall.side_by_side);
+        let forced = ScreenLayout::new(70, 40, true, PanelArrangement::SideBySide);
+        assert!(forced.side_by_side && !forced.stacked && !forced.three_pane);
+        assert!(ScreenLayout::new(160, 40, false, PanelArrangement::Stacked).stacked);
+
+        // The selected response line stays in view when the terminal shrinks
+        let mut state = AppState::default();
+        state.resize(120, 60);
+        state.ui.response_selected_line = 40;
+        state.resize(120, 30);
+        assert_eq!(state.response_viewport_height(), 18);
+        assert_eq!(state.ui.response_scroll, 23);
+    }
+
+    #[test]
+    fn test_remember_selection_per_group() {
+        let mut state = create_grouped_state();
+        state.ui.expanded_groups.insert("Users".to_string());
+
+        // Render items: [Posts, Users, GET /users, POST /users]
+        state.remember_selection(3);
+        assert_eq!(
+            state.ui.last_selected_by_group.get("Users"),
+            Some(&"POST /users".to_string())
+        );
+        assert_eq!(state.find_in_group("Users", "POST /users"), Some(3));
+
+        // Group headers are not remembered
+        state.remember_selection(0);
+        assert!(!state.ui.last_selected_by_group.contains_key("Posts"));
+    }
+
+    #[test]
+    fn test_locate_endpoint_expands_group() {
+        let mut state = create_grouped_state();
+        assert!(state.ui.expanded_groups.is_empty());
+
+        // Render items after expanding: [Posts, Users, GET /users, POST /users]
+        assert_eq!(state.locate_endpoint("POST /users"), Some(3));
+        assert!(state.ui.expanded_groups.contains("Users"));
+        assert_eq!(state.locate_endpoint("DELETE /users"), None);
+    }
+
+    #[test]
+    fn test_locate_endpoint_flat() {
+        let mut state = create_grouped_state();
+        state.ui.view_mode = ViewMode::Flat;
+        assert_eq!(state.locate_endpoint("GET /posts"), Some(2));
+    }
 
     // AuthState tests
     #[test]
@@ -434,6 +2478,202 @@ mod tests {
         assert!(status.contains("🔒"));
         assert!(status.contains("eyJhbGc...pXVCJ9"));
         assert!(status.contains("'a':edit"));
-        assert!(status.contains("'A':clear"));
+        // 'A' opens the spec changes, it has nothing to do with the token
+        assert!(!status.contains("'A'"));
+    }
+
+    #[test]
+    fn test_jump_to_response_match_wraps() {
+        let mut state = AppState::default();
+        state.request.response_lines = [
+            "{",
+            "  \"Name\": \"a\",",
+            "  \"id\": 1,",
+            "  \"name\": \"b\"",
+            "}",
+        ]
+        .iter()
+        .map(|line| line.to_string())
+        .collect();
+        state.search.response_query = "name".to_string();
+        assert_eq!(state.response_matches(), vec![1, 3]);
+
+        state.jump_to_response_match(true);
+        assert_eq!(state.ui.response_selected_line, 3);
+        state.jump_to_response_match(true);
+        assert_eq!(state.ui.response_selected_line, 5);
+        state.jump_to_response_match(true);
+        assert_eq!(state.ui.response_selected_line, 3);
+        state.jump_to_response_match(false);
+        assert_eq!(state.ui.response_selected_line, 5);
+    }
+
+    #[test]
+    fn test_view_positions_per_endpoint_and_tab() {
+        let mut state = AppState::default();
+        state.ui.active_detail_tab = DetailTab::Response;
+        state.load_view_position(Some("GET /users".to_string()));
+        state.ui.response_scroll = 40;
+        state.ui.response_selected_line = 45;
+
+        // Another endpoint starts at the top
+        state.store_view_position();
+        state.load_view_position(Some("GET /posts".to_string()));
+        assert_eq!(state.ui.response_scroll, 0);
+        assert_eq!(state.ui.response_selected_line, 0);
+
+        // Headers keep their own position
+        state.ui.active_detail_tab = DetailTab::Headers;
+        state.load_view_position(Some("GET /users".to_string()));
+        assert_eq!(state.ui.headers_scroll, 0);
+
+        state.ui.active_detail_tab = DetailTab::Response;
+        state.load_view_position(Some("GET /users".to_string()));
+        assert_eq!(state.ui.response_scroll, 40);
+        assert_eq!(state.ui.response_selected_line, 45);
+    }
+
+    #[test]
+    fn test_lock_recovers_from_poisoning() {
+        let state = std::sync::Arc::new(RwLock::new(AppState::default()));
+        let poisoner = std::sync::Arc::clone(&state);
+        let _ = std::thread::spawn(move || {
+            let _guard = poisoner.write_lock();
+            panic!("panicked while holding the lock");
+        })
+        .join();
+
+        assert!(state.is_poisoned());
+        state.write_lock().data.retry_count = 1;
+        assert_eq!(state.read_lock().data.retry_count, 1);
+    }
+
+    #[tokio::test]
+    async fn test_request_jobs() {
+        let mut request = AppState::default().request;
+        let users = request.start_job("GET /users".to_string());
+        let posts = request.start_job("POST /posts".to_string());
+        assert!(request.is_executing("GET /users"));
+        assert!(request.is_executing("POST /posts"));
+        assert_eq!(request.queued_jobs(), 2);
+        request.set_job_started(users);
+        assert_eq!(request.queued_jobs(), 1);
+
+        let task = tokio::spawn(std::future::pending::<()>());
+        request.set_job_abort(posts, task.abort_handle());
+        assert_eq!(request.cancel_job(posts), Some("POST /posts".to_string()));
+        assert!(task.await.unwrap_err().is_cancelled());
+
+        // A cancelled job's response is dropped when its task finishes anyway
+        assert!(request.finish_job(posts).is_none());
+        assert_eq!(
+            request.finish_job(users).map(|job| job.endpoint_key),
+            Some("GET /users".to_string())
+        );
+        assert!(request.jobs.is_empty());
+
+        request.start_job("GET /users".to_string());
+        request.start_job("GET /posts".to_string());
+        assert_eq!(request.cancel_all_jobs(), 2);
+        assert!(!request.is_executing("GET /users"));
+    }
+
+    #[test]
+    fn test_responses_per_endpoint() {
+        let mut state = AppState::default();
+        state.show_response_for(Some("GET /users".to_string()));
+        state.store_response(
+            "GET /users".to_string(),
+            Some(ApiResponse::error("users down".to_string()).into()),
+        );
+        // A response to an endpoint not shown is kept for when it is selected
+        state.store_response(
+            "POST /users".to_string(),
+            Some(ApiResponse::error("bad body".to_string()).into()),
+        );
+        let shown = |state: &AppState| {
+            state
+                .request
+                .current_response
+                .as_ref()
+                .and_then(|response| response.error_message.clone())
+        };
+        assert_eq!(shown(&state), Some("users down".to_string()));
+
+        state.show_response_for(Some("POST /users".to_string()));
+        assert_eq!(shown(&state), Some("bad body".to_string()));
+        state.show_response_for(Some("GET /posts".to_string()));
+        assert_eq!(shown(&state), None);
+        state.show_response_for(Some("GET /users".to_string()));
+        assert_eq!(shown(&state), Some("users down".to_string()));
+
+        state.store_response("GET /users".to_string(), None);
+        assert_eq!(shown(&state), None);
+    }
+
+    #[test]
+    fn test_status_announcement() {
+        let mut state = AppState::default();
+        assert_eq!(state.status_announcement(), "No spec loaded.");
+
+        state.data.loading_state = LoadingState::Complete;
+        state.ui.toast = Some(("✓ Copied URL".to_string(), Instant::now()));
+        assert_eq!(state.status_announcement(), "Copied URL");
+
+        state.show_error("No base URL".to_string());
+        assert_eq!(
+            state.status_announcement(),
+            "Error: No base URL. Esc dismisses."
+        );
+
+        state.ui.error_banner = None;
+        state.ui.toast = None;
+        assert_eq!(state.status_announcement(), "0 endpoints.");
+    }
+
+    #[test]
+    fn test_param_choices_as_suggestions() {
+        let mut request = AppState::default().request;
+        request.edit_mode = RequestEditMode::Editing("sort".to_string());
+        request
+            .param_history
+            .insert("sort".to_string(), vec!["name".to_string()]);
+        request.param_choices = vec!["asc".to_string(), "desc".to_string(), "date".to_string()];
+
+        assert_eq!(request.param_suggestions(), vec!["asc", "desc", "date"]);
+        request.param_edit_buffer = "D".to_string();
+        assert_eq!(request.param_suggestions(), vec!["desc", "date"]);
+        // The current value still offers the others
+        request.param_edit_buffer = "desc".to_string();
+        assert_eq!(request.param_suggestions().len(), 3);
+    }
+
+    #[test]
+    fn test_body_drafts() {
+        let mut state = AppState::default();
+        state.input.mode = InputMode::EnteringBody;
+        state.input.body_endpoint = Some("POST /users".to_string());
+        state.input.body_saved = "{}".to_string();
+        state.input.body_editor.set_content("{}".to_string());
+        state.track_body_draft();
+        assert!(state.request.body_drafts.is_empty());
+
+        state
+            .input
+            .body_editor
+            .set_content(r#"{"name": "Ann"}"#.to_string());
+        state.track_body_draft();
+        assert_eq!(
+            state.request.body_drafts["POST /users"],
+            r#"{"name": "Ann"}"#
+        );
+        assert!(state.request.body_drafts_dirty);
+
+        // Saving or discarding the edit drops the draft
+        state.request.body_drafts_dirty = false;
+        state.close_body_editor();
+        assert!(state.request.body_drafts.is_empty());
+        assert!(state.request.body_drafts_dirty);
+        assert_eq!(state.input.mode, InputMode::Normal);
     }
 }
@@ -0,0 +1,153 @@
+//! Local usage statistics per endpoint
+//!
+//! Every executed request counts towards its endpoint: how often it ran, how
+//! many of those runs failed (network errors and 4xx/5xx statuses) and the
+//! latency of the most recent runs. The numbers are kept per spec URL in
+//! `stats.toml` next to the config and never leave the machine.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Latencies kept per endpoint for the median
+const MAX_LATENCY_SAMPLES: usize = 100;
+
+/// Usage of one endpoint
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EndpointStats {
+    pub runs: u32,
+    pub failures: u32,
+    /// Latencies of the most recent runs in milliseconds, oldest first
+    pub latencies_ms: Vec<u64>,
+}
+
+impl EndpointStats {
+    /// Count a response; status 0 is a network error
+    pub fn record(&mut self, status: u16, duration: Duration) {
+        self.runs += 1;
+        if status == 0 || status >= 400 {
+            self.failures += 1;
+        }
+        self.latencies_ms.push(duration.as_millis() as u64);
+        if self.latencies_ms.len() > MAX_LATENCY_SAMPLES {
+            self.latencies_ms.remove(0);
+        }
+    }
+
+    /// Share of runs that succeeded, from 0.0 to 1.0
+    pub fn success_ratio(&self) -> f64 {
+        if self.runs == 0 {
+            return 0.0;
+        }
+        f64::from(self.runs - self.failures) / f64::from(self.runs)
+    }
+
+    pub fn median_latency(&self) -> Option<Duration> {
+        let mut sorted = self.latencies_ms.clone();
+        sorted.sort_unstable();
+        let mid = sorted.len() / 2;
+        let median = match sorted.len() {
+            0 => return None,
+            len if len % 2 == 0 => (sorted[mid - 1] + sorted[mid]) / 2,
+            _ => sorted[mid],
+        };
+        Some(Duration::from_millis(median))
+    }
+}
+
+/// Order of the stats view
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum StatsSort {
+    #[default]
+    MostUsed,
+    MostFailing,
+}
+
+impl StatsSort {
+    pub fn toggle(self) -> Self {
+        match self {
+            Self::MostUsed => Self::MostFailing,
+            Self::MostFailing => Self::MostUsed,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::MostUsed => "most used",
+            Self::MostFailing => "most failing",
+        }
+    }
+}
+
+/// Endpoints with their stats in the given order; ties are broken by endpoint key
+pub fn ranked(
+    stats: &HashMap<String, EndpointStats>,
+    sort: StatsSort,
+) -> Vec<(&String, &EndpointStats)> {
+    let mut ranked: Vec<(&String, &EndpointStats)> = stats.iter().collect();
+    match sort {
+        StatsSort::MostUsed => ranked
+            .sort_by(|(a_key, a), (b_key, b)| b.runs.cmp(&a.runs).then_with(|| a_key.cmp(b_key))),
+        StatsSort::MostFailing => ranked.sort_by(|(a_key, a), (b_key, b)| {
+            b.failures
+                .cmp(&a.failures)
+                .then_with(|| a.success_ratio().total_cmp(&b.success_ratio()))
+                .then_with(|| a_key.cmp(b_key))
+        }),
+    }
+    ranked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_endpoint_stats_record() {
+        let mut stats = EndpointStats::default();
+        assert_eq!(stats.median_latency(), None);
+
+        for (status, ms) in [(200, 40), (404, 10), (0, 5000), (201, 30)] {
+            stats.record(status, Duration::from_millis(ms));
+        }
+        assert_eq!(stats.runs, 4);
+        assert_eq!(stats.failures, 2);
+        assert_eq!(stats.success_ratio(), 0.5);
+        assert_eq!(stats.median_latency(), Some(Duration::from_millis(35)));
+
+        for _ in 0..MAX_LATENCY_SAMPLES {
+            stats.record(200, Duration::from_millis(1));
+        }
+        assert_eq!(stats.latencies_ms.len(), MAX_LATENCY_SAMPLES);
+        assert_eq!(stats.median_latency(), Some(Duration::from_millis(1)));
+    }
+
+    #[test]
+    fn test_ranked() {
+        let stats_with = |runs: u32, failures: u32| EndpointStats {
+            runs,
+            failures,
+            latencies_ms: Vec::new(),
+        };
+        let stats = HashMap::from([
+            ("GET /users".to_string(), stats_with(10, 1)),
+            ("POST /orders".to_string(), stats_with(3, 3)),
+            ("DELETE /users/{id}".to_string(), stats_with(10, 0)),
+        ]);
+
+        let keys = |sort| -> Vec<&str> {
+            ranked(&stats, sort)
+                .into_iter()
+                .map(|(key, _)| key.as_str())
+                .collect()
+        };
+        assert_eq!(
+            keys(StatsSort::MostUsed),
+            vec!["DELETE /users/{id}", "GET /users", "POST /orders"]
+        );
+        assert_eq!(
+            keys(StatsSort::MostFailing),
+            vec!["POST /orders", "GET /users", "DELETE /users/{id}"]
+        );
+    }
+}
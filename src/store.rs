@@ -0,0 +1,34 @@
+//! Persistence for user data that doesn't belong in config.toml
+//!
+//! Each feature keeps its own TOML file next to the config
+//! (e.g. `~/.config/lazy-swagger-tui/variants.toml`).
+
+use crate::config::Config;
+use color_eyre::Result;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fs;
+
+/// Load a data file, returning the default value if it doesn't exist yet
+pub fn load<T: DeserializeOwned + Default>(file_name: &str) -> Result<T> {
+    let path = Config::config_dir()?.join(file_name);
+
+    if !path.exists() {
+        return Ok(T::default());
+    }
+
+    let contents = fs::read_to_string(&path)?;
+    Ok(toml::from_str(&contents)?)
+}
+
+/// Write a data file, replacing its previous contents
+///
+/// The data is written to a file next to it that is then renamed over it, so a crash
+/// mid-write leaves the previous contents instead of a truncated file.
+pub fn save<T: Serialize>(file_name: &str, data: &T) -> Result<()> {
+    let path = Config::config_dir()?.join(file_name);
+    let temp_path = path.with_file_name(format!("{file_name}.tmp"));
+    fs::write(&temp_path, toml::to_string_pretty(data)?)?;
+    fs::rename(&temp_path, &path)?;
+    Ok(())
+}
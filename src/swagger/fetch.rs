@@ -1,64 +1,551 @@
-use crate::state::AppState;
+use crate::config;
+use crate::plugins::PluginRegistry;
+use crate::servers::{self, SpecServer};
+use crate::state::{AppState, LockRecover};
 use crate::swagger::parse::parse_swagger_spec;
-use crate::types::{ApiEndpoint, LoadingState, SwaggerSpec};
+use crate::swagger::{refs, v2};
+use crate::tasks;
+use crate::types::{ApiEndpoint, LoadingState, Reconnect, SecurityScheme, SpecInfo, SwaggerSpec};
+use crate::ui::events::log_debug;
+use reqwest::StatusCode;
+use serde_json::Value;
 use std::collections::HashMap;
+use std::path::Path;
 use std::sync::{Arc, RwLock};
+use std::time::Instant;
+
+/// A fetched spec, parsed into endpoints
+#[derive(Debug)]
+pub struct LoadedSpec {
+    pub endpoints: Vec<ApiEndpoint>,
+    pub info: SpecInfo,
+    pub servers: Vec<SpecServer>,
+    pub security_schemes: HashMap<String, SecurityScheme>,
+}
+
+/// Parse the response of a spec request, running the plugins' spec hooks
+pub async fn parse_spec_response(
+    response: reqwest::Response,
+    url: &str,
+    plugins: &PluginRegistry,
+) -> Result<LoadedSpec, String> {
+    let status = response.status();
+    let content_type = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+    let body = response.bytes().await.map_err(|e| e.to_string())?;
+    parse_spec_blocking(body.to_vec(), url, content_type.clone(), plugins)
+        .await
+        .map_err(|e| parse_failure(e, content_type.as_deref(), status))
+}
+
+/// Why a downloaded spec didn't parse; a response that is no spec at all, e.g. an HTML
+/// error or login page, is reported by its content type and status instead
+fn parse_failure(error: String, content_type: Option<&str>, status: StatusCode) -> String {
+    match content_type {
+        Some(content_type) if !is_spec_media_type(content_type) => {
+            format!("server returned {content_type} (HTTP {status}), not a JSON or YAML spec")
+        }
+        _ if !status.is_success() => format!("server returned HTTP {status}: {error}"),
+        _ => error,
+    }
+}
+
+/// Whether a content type can hold a spec: JSON, YAML or unspecific text
+fn is_spec_media_type(content_type: &str) -> bool {
+    let media_type = content_type.to_lowercase();
+    ["json", "yaml", "text/plain", "octet-stream"]
+        .iter()
+        .any(|kind| media_type.contains(kind))
+}
+
+/// [`parse_spec`] on a blocking thread, as plugin programs block until they exit
+async fn parse_spec_blocking(
+    body: Vec<u8>,
+    url: &str,
+    content_type: Option<String>,
+    plugins: &PluginRegistry,
+) -> Result<LoadedSpec, String> {
+    let url = url.to_string();
+    let plugins = plugins.clone();
+    tokio::task::spawn_blocking(move || parse_spec(&body, &url, content_type.as_deref(), &plugins))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+/// Parse a spec document, OpenAPI 3 or Swagger 2.0, running the plugins' spec hooks
+pub fn parse_spec(
+    body: &[u8],
+    url: &str,
+    content_type: Option<&str>,
+    plugins: &PluginRegistry,
+) -> Result<LoadedSpec, String> {
+    let mut doc = if is_yaml(url, content_type) {
+        let doc: serde_yaml::Value = serde_yaml::from_slice(body).map_err(|e| e.to_string())?;
+        yaml_to_json(doc)
+    } else {
+        serde_json::from_slice(body).map_err(|e| e.to_string())?
+    };
+    if v2::is_swagger2(&doc) {
+        doc = v2::upgrade(doc);
+    }
+    let doc = refs::inline_refs(doc);
+    let mut spec: SwaggerSpec = serde_json::from_value(doc).map_err(|e| e.to_string())?;
+    let info = std::mem::take(&mut spec.info);
+    let servers = servers::resolve_servers(std::mem::take(&mut spec.servers), url);
+    let security_schemes = spec
+        .components
+        .as_mut()
+        .map(|components| std::mem::take(&mut components.security_schemes))
+        .unwrap_or_default();
+    let mut endpoints = parse_swagger_spec(spec);
+    plugins.on_spec_loaded(&mut endpoints);
+    Ok(LoadedSpec {
+        endpoints,
+        info,
+        servers,
+        security_schemes,
+    })
+}
+
+/// Whether a spec is YAML, by its content type or else its extension; JSON otherwise
+fn is_yaml(url: &str, content_type: Option<&str>) -> bool {
+    let path = url
+        .split(['?', '#'])
+        .next()
+        .unwrap_or_default()
+        .to_lowercase();
+    match content_type {
+        Some(content_type) if content_type.contains("yaml") => true,
+        Some(content_type) if content_type.contains("json") => false,
+        _ => path.ends_with(".yaml") || path.ends_with(".yml"),
+    }
+}
+
+/// A YAML document as JSON; keys that are not strings, like `200:` of responses, become strings
+fn yaml_to_json(value: serde_yaml::Value) -> Value {
+    use serde_yaml::Value as Yaml;
+    match value {
+        Yaml::Null => Value::Null,
+        Yaml::Bool(b) => Value::Bool(b),
+        Yaml::Number(n) => serde_json::to_value(&n).unwrap_or(Value::Null),
+        Yaml::String(s) => Value::String(s),
+        Yaml::Sequence(items) => Value::Array(items.into_iter().map(yaml_to_json).collect()),
+        Yaml::Mapping(map) => Value::Object(
+            map.into_iter()
+                .map(|(key, value)| {
+                    let key = match yaml_to_json(key) {
+                        Value::String(key) => key,
+                        other => other.to_string(),
+                    };
+                    (key, yaml_to_json(value))
+                })
+                .collect(),
+        ),
+        Yaml::Tagged(tagged) => yaml_to_json(tagged.value),
+    }
+}
+
+/// Read a spec from disk
+pub async fn read_spec_file(path: &Path, plugins: &PluginRegistry) -> Result<LoadedSpec, String> {
+    let body = tokio::fs::read(path)
+        .await
+        .map_err(|e| format!("Could not read {}: {e}", path.display()))?;
+    // Relative servers stay as written, there is no host to resolve them against
+    parse_spec_blocking(body, &path.to_string_lossy(), None, plugins)
+        .await
+        .map_err(|e| format!("Parse error: {e}"))
+}
+
+/// Fetch a spec, or read it when the URL names a file
+pub async fn load_spec(
+    url: &str,
+    headers: &[(String, String)],
+    plugins: &PluginRegistry,
+) -> Result<LoadedSpec, String> {
+    if let Some(path) = config::spec_file_path(url) {
+        return read_spec_file(&path, plugins).await;
+    }
+    let response = request_spec(url, headers)
+        .await
+        .map_err(|e| format!("Network error: {e}"))?;
+    parse_spec_response(response, url, plugins)
+        .await
+        .map_err(|e| format!("Parse error: {e}"))
+}
+
+/// Download a spec, sending the given extra headers
+pub async fn request_spec(
+    url: &str,
+    headers: &[(String, String)],
+) -> reqwest::Result<reqwest::Response> {
+    let mut request = reqwest::Client::new().get(url);
+    for (name, value) in headers {
+        request = request.header(name, value);
+    }
+    request.send().await
+}
 
 /// Spawns a background task to fetch endpoints
-pub fn fetch_endpoints_background(state: Arc<RwLock<AppState>>, url: String) {
+pub fn fetch_endpoints_background(
+    state: Arc<RwLock<AppState>>,
+    url: String,
+    headers: Vec<(String, String)>,
+) {
     // Set loading state
-    if let Ok(mut s) = state.write() {
-        s.data.loading_state = LoadingState::Fetching;
+    state.write_lock().data.loading_state = LoadingState::Fetching;
+
+    let watched = Arc::clone(&state);
+    tasks::spawn(
+        watched,
+        fetch_endpoints(state, url, headers),
+        loading_failed,
+    );
+}
+
+async fn fetch_endpoints(
+    state: Arc<RwLock<AppState>>,
+    url: String,
+    headers: Vec<(String, String)>,
+) {
+    let plugins = state.read_lock().request.plugins.clone();
+    if let Some(path) = config::spec_file_path(&url) {
+        log_debug(&format!("Reading spec from {}", path.display()));
+        match read_spec_file(&path, &plugins).await {
+            Ok(spec) => spec_loaded(&state, spec, &url),
+            Err(e) => {
+                log_debug(&format!("✗ {e}"));
+                let mut s = state.write_lock();
+                if !is_current(&s, &url) {
+                    return;
+                }
+                s.data.loading_state = LoadingState::Error(e);
+                s.data.reconnect = None;
+            }
+        }
+        return;
     }
 
-    tokio::spawn(async move {
-        match reqwest::get(&url).await {
-            Ok(response) => {
-                if let Ok(mut s) = state.write() {
-                    s.data.loading_state = LoadingState::Parsing;
+    log_debug(&format!("Fetching spec from {url}"));
+    match request_spec(&url, &headers).await {
+        Ok(response) => {
+            {
+                let mut s = state.write_lock();
+                if !is_current(&s, &url) {
+                    return;
                 }
+                s.data.loading_state = LoadingState::Parsing;
+            }
 
-                match response.json::<SwaggerSpec>().await {
-                    Ok(spec) => {
-                        let endpoints = parse_swagger_spec(spec);
-
-                        // Group endpoints
-                        let mut grouped: HashMap<String, Vec<ApiEndpoint>> = HashMap::new();
-                        for endpoint in &endpoints {
-                            if endpoint.tags.is_empty() {
-                                grouped
-                                    .entry("Other".to_string())
-                                    .or_default()
-                                    .push(endpoint.clone());
-                            } else {
-                                for tag in &endpoint.tags {
-                                    grouped
-                                        .entry(tag.clone())
-                                        .or_default()
-                                        .push(endpoint.clone());
-                                }
-                            }
-                        }
-
-                        if let Ok(mut s) = state.write() {
-                            s.data.endpoints = endpoints;
-                            s.data.grouped_endpoints = grouped;
-                            s.data.loading_state = LoadingState::Complete;
-                            s.data.retry_count = 0;
-                        }
-                    }
-                    Err(e) => {
-                        if let Ok(mut s) = state.write() {
-                            s.data.loading_state = LoadingState::Error(format!("Parse error: {e}"));
-                        }
+            match parse_spec_response(response, &url, &plugins).await {
+                Ok(spec) => spec_loaded(&state, spec, &url),
+                Err(e) => {
+                    log_debug(&format!("✗ Could not parse the spec from {url}: {e}"));
+                    let mut s = state.write_lock();
+                    if !is_current(&s, &url) {
+                        return;
                     }
+                    s.data.loading_state = LoadingState::Error(format!("Parse error: {e}"));
+                    // The server is back, just not serving a usable spec
+                    s.data.reconnect = None;
                 }
             }
-            Err(e) => {
-                if let Ok(mut s) = state.write() {
-                    s.data.loading_state = LoadingState::Error(format!("Network error: {e}"));
-                }
+        }
+        Err(e) => {
+            log_debug(&format!("✗ Could not fetch the spec from {url}: {e}"));
+            let mut s = state.write_lock();
+            if !is_current(&s, &url) {
+                return;
+            }
+            s.data.loading_state = LoadingState::Error(format!("Network error: {e}"));
+            // Keep trying while the server refuses connections, e.g. during a restart
+            match &mut s.data.reconnect {
+                Some(reconnect) => reconnect.failed(),
+                None if e.is_connect() => s.data.reconnect = Some(Reconnect::start()),
+                None => {}
+            }
+        }
+    }
+}
+
+/// Spawns a background task to reload the endpoints while they stay on screen
+///
+/// For watching the spec: a failed reload keeps the loaded endpoints and is only logged,
+/// so a backend restarting for a hot reload doesn't interrupt the session.
+pub fn reload_endpoints_background(
+    state: Arc<RwLock<AppState>>,
+    url: String,
+    headers: Vec<(String, String)>,
+) {
+    state.write_lock().data.reloading = true;
+
+    let watched = Arc::clone(&state);
+    let task = async move {
+        let plugins = state.read_lock().request.plugins.clone();
+        match load_spec(&url, &headers, &plugins).await {
+            Ok(spec) => spec_loaded(&state, spec, &url),
+            Err(e) => log_debug(&format!("✗ Could not reload the spec from {url}: {e}")),
+        }
+        state.write_lock().data.reloading = false;
+    };
+    tasks::spawn(watched, task, reload_failed);
+}
+
+/// Show the endpoints of a spec that loaded
+fn spec_loaded(state: &RwLock<AppState>, spec: LoadedSpec, url: &str) {
+    log_debug(&format!(
+        "Loaded {} endpoints from {url}",
+        spec.endpoints.len()
+    ));
+    let mut s = state.write_lock();
+    if !is_current(&s, url) {
+        log_debug(&format!(
+            "Dropped the spec from {url}, another spec was opened meanwhile"
+        ));
+        return;
+    }
+    s.set_endpoints(spec.endpoints);
+    s.data.spec_info = spec.info;
+    s.data.servers = spec.servers;
+    s.data.security_schemes = spec.security_schemes;
+    s.data.loading_state = LoadingState::Complete;
+    s.data.retry_count = 0;
+    s.data.reconnect = None;
+    s.record_spec_changes();
+}
+
+/// Whether a load of `url` is still for the spec on screen, not one the user switched away from
+fn is_current(state: &AppState, url: &str) -> bool {
+    state.data.spec_url.as_deref() == Some(url)
+}
+
+/// Log a reload whose task panicked and let the next one start
+fn reload_failed(state: &mut AppState, message: String) {
+    log_debug(&format!("✗ Reloading the spec failed: {message}"));
+    state.data.reloading = false;
+}
+
+/// Show a spec load whose task panicked as failed, instead of loading forever
+fn loading_failed(state: &mut AppState, message: String) {
+    state.data.loading_state = LoadingState::Error(format!("Loading failed: {message}"));
+    state.data.reconnect = None;
+}
+
+/// One service's spec of an aggregate
+#[derive(Debug, Clone)]
+pub struct ServiceSource {
+    pub name: String,
+    pub url: String,
+    pub base_url: Option<String>,
+    /// Extra headers for downloading the spec
+    pub headers: Vec<(String, String)>,
+}
+
+/// Fetch a service's spec, tagging its endpoints with the service and its base URL
+async fn load_service(
+    source: ServiceSource,
+    plugins: PluginRegistry,
+) -> Result<Vec<ApiEndpoint>, String> {
+    let spec = load_spec(&source.url, &source.headers, &plugins)
+        .await
+        .map_err(|e| format!("{}: {e}", source.name))?;
+
+    // Without a configured base URL, the spec's first server is the best guess
+    let base_url = source
+        .base_url
+        .or_else(|| spec.servers.first().map(SpecServer::default_url));
+    Ok(spec
+        .endpoints
+        .into_iter()
+        .map(|endpoint| ApiEndpoint {
+            service: Some(source.name.clone()),
+            base_url: base_url.clone(),
+            ..endpoint
+        })
+        .collect())
+}
+
+/// Fetch the services' specs concurrently and merge their endpoints in the given order
+///
+/// Returns the endpoints of the services that loaded, and an error per service that did not.
+pub async fn load_aggregate(
+    sources: Vec<ServiceSource>,
+    plugins: &PluginRegistry,
+) -> (Vec<ApiEndpoint>, Vec<String>) {
+    let tasks: Vec<_> = sources
+        .into_iter()
+        .map(|source| tokio::spawn(load_service(source, plugins.clone())))
+        .collect();
+
+    let mut endpoints = Vec::new();
+    let mut errors = Vec::new();
+    for task in tasks {
+        match task.await {
+            Ok(Ok(loaded)) => endpoints.extend(loaded),
+            Ok(Err(e)) => errors.push(e),
+            Err(e) => errors.push(format!("Loading failed: {e}")),
+        }
+    }
+    (endpoints, errors)
+}
+
+/// Spawns a background task to fetch and merge the specs of an aggregate
+///
+/// Services that fail to load are reported in a toast; the load only fails when all do.
+/// A `quiet` reload keeps the loaded endpoints on screen, and all of them when a service fails.
+pub fn fetch_aggregate_background(
+    state: Arc<RwLock<AppState>>,
+    name: String,
+    sources: Vec<ServiceSource>,
+    quiet: bool,
+) {
+    if quiet {
+        state.write_lock().data.reloading = true;
+    } else {
+        state.write_lock().data.loading_state = LoadingState::Fetching;
+    }
+
+    let watched = Arc::clone(&state);
+    let task = async move {
+        let plugins = state.read_lock().request.plugins.clone();
+        let (endpoints, errors) = load_aggregate(sources, &plugins).await;
+
+        let mut s = state.write_lock();
+        s.data.reloading = false;
+        if s.data.spec_url.as_deref().and_then(config::aggregate_name) != Some(name.as_str()) {
+            log_debug(&format!(
+                "Dropped {name}, another spec was opened meanwhile"
+            ));
+            return;
+        }
+        s.data.reconnect = None;
+        if !errors.is_empty() && (quiet || endpoints.is_empty()) {
+            if quiet {
+                log_debug(&format!("✗ Could not reload {name}: {}", errors.join("; ")));
+            } else {
+                s.data.loading_state = LoadingState::Error(errors.join("; "));
             }
+            return;
         }
-    });
+        s.set_endpoints(endpoints);
+        s.data.spec_info = SpecInfo {
+            title: Some(name),
+            ..Default::default()
+        };
+        s.data.servers = Vec::new();
+        s.data.security_schemes = HashMap::new();
+        s.data.loading_state = LoadingState::Complete;
+        s.data.retry_count = 0;
+        if errors.is_empty() {
+            s.record_spec_changes();
+        } else {
+            // A service missing from the list is not a change of the aggregate
+            s.ui.toast = Some((format!("Not loaded: {}", errors.join("; ")), Instant::now()));
+        }
+    };
+    tasks::spawn(
+        watched,
+        task,
+        if quiet { reload_failed } else { loading_failed },
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_yaml_spec() {
+        let yaml = b"openapi: 3.0.0
+info:
+  title: Pets
+  version: '1.0'
+paths:
+  /pets/{id}:
+    get:
+      parameters:
+        - name: id
+          in: path
+          required: true
+          schema: { type: integer }
+      responses:
+        200:
+          description: A pet
+          content:
+            application/json:
+              example: { name: Rex, age: 3 }
+";
+        assert!(is_yaml("http://x/openapi", Some("application/yaml")));
+        assert!(is_yaml("http://x/openapi.yml?v=2", None));
+        assert!(!is_yaml("http://x/openapi.yml", Some("application/json")));
+        // Anything else is read as JSON, so an HTML page doesn't fail as YAML
+        assert!(!is_yaml("http://x/openapi", None));
+        assert!(!is_yaml("http://x/openapi", Some("text/html")));
+
+        let spec = parse_spec(
+            yaml,
+            "http://x/openapi",
+            Some("application/yaml"),
+            &PluginRegistry::default(),
+        )
+        .unwrap();
+        assert_eq!(spec.info.title.as_deref(), Some("Pets"));
+        let endpoint = &spec.endpoints[0];
+        assert_eq!(endpoint.key(), "GET /pets/{id}");
+        assert_eq!(endpoint.parameters[0].type_info(), "integer");
+        let example = endpoint.response_example.as_ref().unwrap();
+        assert_eq!(example.status, "200");
+        assert_eq!(example.body["age"], 3);
+
+        assert_eq!(
+            parse_failure(
+                "expected value".to_string(),
+                Some("text/html; charset=utf-8"),
+                StatusCode::UNAUTHORIZED
+            ),
+            "server returned text/html; charset=utf-8 (HTTP 401 Unauthorized), not a JSON or YAML spec"
+        );
+        assert_eq!(
+            parse_failure(
+                "expected value".to_string(),
+                Some("application/json"),
+                StatusCode::OK
+            ),
+            "expected value"
+        );
+
+        assert!(parse_spec(
+            b"paths: [",
+            "http://x/openapi.yaml",
+            None,
+            &PluginRegistry::default()
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_load_of_a_spec_switched_away_from_is_dropped() {
+        let spec = |path: &str| {
+            let doc = format!(r#"{{"openapi":"3.0.0","paths":{{"{path}":{{"get":{{}}}}}}}}"#);
+            parse_spec(
+                doc.as_bytes(),
+                "http://x/",
+                None,
+                &PluginRegistry::default(),
+            )
+            .unwrap()
+        };
+        let state = RwLock::new(AppState::default());
+        state.write_lock().data.spec_url = Some("http://b/openapi.json".to_string());
+
+        spec_loaded(&state, spec("/a"), "http://a/openapi.json");
+        assert!(state.read_lock().data.endpoints.is_empty());
+
+        spec_loaded(&state, spec("/b"), "http://b/openapi.json");
+        let s = state.read_lock();
+        assert_eq!(s.data.endpoints[0].key(), "GET /b");
+        assert!(s.data.spec_diff.is_none());
+    }
 }
@@ -1,4 +1,6 @@
 pub mod fetch;
 pub mod parse;
+pub mod refs;
+pub mod v2;
 
 pub use fetch::*;
@@ -1,59 +1,287 @@
-use crate::types::{ApiEndpoint, SwaggerSpec};
+use crate::types::{
+    ApiEndpoint, ApiParameter, EventSource, Operation, PathItem, SchemaModel, SchemaShape,
+    SecurityRequirement, SwaggerSpec,
+};
+use serde_json::Value;
+use std::collections::HashMap;
 
 pub fn parse_swagger_spec(spec: SwaggerSpec) -> Vec<ApiEndpoint> {
     let mut endpoints: Vec<ApiEndpoint> = Vec::new();
+    let mut events: Vec<ApiEndpoint> = Vec::new();
 
-    for (path, path_item) in spec.paths {
-        if let Some(op) = &path_item.get {
-            endpoints.push(ApiEndpoint {
-                method: "GET".to_string(),
-                path: path.clone(),
-                summary: op.summary.clone(),
-                tags: op.tags.clone().unwrap_or_default(),
-                parameters: op.parameters.clone().unwrap_or_default(),
-            });
-        }
-        if let Some(op) = &path_item.post {
-            endpoints.push(ApiEndpoint {
-                method: "POST".to_string(),
-                path: path.clone(),
-                summary: op.summary.clone(),
-                tags: op.tags.clone().unwrap_or_default(),
-                parameters: op.parameters.clone().unwrap_or_default(),
-            });
+    // Named schemas from both OpenAPI 3 and Swagger 2 layouts
+    let mut schemas = spec.definitions;
+    if let Some(components) = spec.components {
+        schemas.extend(components.schemas);
+    }
+
+    for (path, path_item) in &spec.paths {
+        for (method, op) in path_item.operations() {
+            let endpoint = operation_endpoint(method, path, path_item, op, spec.security.as_ref());
+
+            for (name, expressions) in &op.callbacks {
+                for (expression, callback_item) in expressions {
+                    for (callback_method, callback_op) in callback_item.operations() {
+                        let mut callback = operation_endpoint(
+                            callback_method,
+                            expression,
+                            callback_item,
+                            callback_op,
+                            None,
+                        );
+                        callback.event = Some(EventSource::Callback {
+                            name: name.clone(),
+                            operation: endpoint.key(),
+                        });
+                        events.push(callback);
+                    }
+                }
+            }
+
+            endpoints.push(endpoint);
         }
-        if let Some(op) = &path_item.put {
-            endpoints.push(ApiEndpoint {
-                method: "PUT".to_string(),
-                path: path.clone(),
-                summary: op.summary.clone(),
-                tags: op.tags.clone().unwrap_or_default(),
-                parameters: op.parameters.clone().unwrap_or_default(),
-            });
+    }
+
+    for (name, path_item) in &spec.webhooks {
+        for (method, op) in path_item.operations() {
+            let mut webhook = operation_endpoint(method, name, path_item, op, None);
+            webhook.event = Some(EventSource::Webhook { name: name.clone() });
+            events.push(webhook);
         }
-        if let Some(op) = &path_item.delete {
-            endpoints.push(ApiEndpoint {
-                method: "DELETE".to_string(),
-                path: path.clone(),
-                summary: op.summary.clone(),
-                tags: op.tags.clone().unwrap_or_default(),
-                parameters: op.parameters.clone().unwrap_or_default(),
-            });
+    }
+
+    // Callbacks and webhooks form their own section after the regular endpoints
+    events.sort_by_key(|event| (event.event.as_ref().map(EventSource::label), event.key()));
+    endpoints.extend(events);
+
+    // Inline referenced schemas so request bodies and responses are self-contained,
+    // and model them for the outlines, which name the schemas they reference
+    for endpoint in &mut endpoints {
+        if let Some(body) = &mut endpoint.request_body {
+            for media in body.content.values_mut() {
+                if let Some(schema) = &mut media.schema {
+                    media.model = Some(schema_model(schema, &schemas));
+                    *schema = resolve_refs(schema, &schemas, &[]);
+                }
+            }
         }
-        if let Some(op) = &path_item.patch {
-            endpoints.push(ApiEndpoint {
-                method: "PATCH".to_string(),
-                path: path.clone(),
-                summary: op.summary.clone(),
-                tags: op.tags.clone().unwrap_or_default(),
-                parameters: op.parameters.clone().unwrap_or_default(),
-            });
+        for response in &mut endpoint.responses {
+            if let Some(schema) = &mut response.schema {
+                response.model = Some(schema_model(schema, &schemas));
+                *schema = resolve_refs(schema, &schemas, &[]);
+            }
         }
     }
 
     endpoints
 }
 
+/// Endpoint for one operation of a path item
+///
+/// `default_security` applies when the operation declares no requirements of its own.
+fn operation_endpoint(
+    method: &str,
+    path: &str,
+    path_item: &PathItem,
+    op: &Operation,
+    default_security: Option<&Vec<SecurityRequirement>>,
+) -> ApiEndpoint {
+    ApiEndpoint {
+        method: method.to_string(),
+        path: path.to_string(),
+        summary: op.summary.clone(),
+        description: op.description.clone(),
+        tags: op.tags.clone().unwrap_or_default(),
+        parameters: merge_parameters(&path_item.parameters, op.parameters.as_deref()),
+        request_body: op.body_spec(),
+        response_example: op.response_example(),
+        responses: op.declared_responses(),
+        deprecated: op.deprecated,
+        security: op.security.clone().or_else(|| default_security.cloned()),
+        extensions: op.extensions(),
+        event: None,
+        service: None,
+        base_url: None,
+    }
+}
+
+/// Path-level parameters followed by the operation's own
+///
+/// An operation parameter overrides a path-level one with the same name and location.
+fn merge_parameters(
+    path_params: &[ApiParameter],
+    op_params: Option<&[ApiParameter]>,
+) -> Vec<ApiParameter> {
+    let op_params = op_params.unwrap_or_default();
+    path_params
+        .iter()
+        .filter(|shared| {
+            !op_params
+                .iter()
+                .any(|own| own.name == shared.name && own.location == shared.location)
+        })
+        .chain(op_params)
+        .cloned()
+        .collect()
+}
+
+/// Name of the schema a `$ref` points to, e.g. `Pet` for `#/components/schemas/Pet`
+fn ref_name(reference: &str) -> &str {
+    reference.rsplit('/').next().unwrap_or_default()
+}
+
+/// Replace `$ref` objects with the schemas they point to
+///
+/// `path` holds the schemas being inlined around `schema`. A reference back to one of
+/// them is left as it is, naming the schema, so recursive schemas are inlined once.
+fn resolve_refs(schema: &Value, schemas: &HashMap<String, Value>, path: &[String]) -> Value {
+    match schema {
+        Value::Object(map) => {
+            if let Some(Value::String(reference)) = map.get("$ref") {
+                let name = ref_name(reference);
+                if path.iter().any(|seen| seen == name) {
+                    return schema.clone();
+                }
+                return match schemas.get(name) {
+                    Some(target) => resolve_refs(target, schemas, &with_ref(path, name)),
+                    None => Value::Object(Default::default()),
+                };
+            }
+            Value::Object(
+                map.iter()
+                    .map(|(key, value)| (key.clone(), resolve_refs(value, schemas, path)))
+                    .collect(),
+            )
+        }
+        Value::Array(items) => Value::Array(
+            items
+                .iter()
+                .map(|item| resolve_refs(item, schemas, path))
+                .collect(),
+        ),
+        other => other.clone(),
+    }
+}
+
+/// `path` followed by the schema `name`
+fn with_ref(path: &[String], name: &str) -> Vec<String> {
+    let mut path = path.to_vec();
+    path.push(name.to_string());
+    path
+}
+
+/// Model a schema for the outlines, following `$ref`s into the named `schemas`
+pub fn schema_model(schema: &Value, schemas: &HashMap<String, Value>) -> SchemaModel {
+    model_in(schema, schemas, &[])
+}
+
+/// Model of `schema` inside the schemas of `path`; a reference back to one of them
+/// is modeled by its name alone, like [`resolve_refs`] leaves it
+fn model_in(schema: &Value, schemas: &HashMap<String, Value>, path: &[String]) -> SchemaModel {
+    let Value::Object(map) = schema else {
+        return SchemaModel::default();
+    };
+    if let Some(Value::String(reference)) = map.get("$ref") {
+        let name = ref_name(reference);
+        let mut model = match schemas.get(name) {
+            Some(target) if !path.iter().any(|seen| seen == name) => {
+                model_in(target, schemas, &with_ref(path, name))
+            }
+            _ => SchemaModel::default(),
+        };
+        model.ref_name = Some(name.to_string());
+        return model;
+    }
+
+    let model = |schema: &Value| model_in(schema, schemas, path);
+    let parts = |keyword: &str| -> Option<Vec<SchemaModel>> {
+        match map.get(keyword) {
+            Some(Value::Array(parts)) if !parts.is_empty() => {
+                Some(parts.iter().map(model).collect())
+            }
+            _ => None,
+        }
+    };
+    let own_properties = map
+        .get("properties")
+        .and_then(Value::as_object)
+        .map(|properties| SchemaModel {
+            shape: SchemaShape::Object {
+                properties: properties
+                    .iter()
+                    .map(|(name, property)| (name.clone(), model(property)))
+                    .collect(),
+                required: map
+                    .get("required")
+                    .and_then(Value::as_array)
+                    .into_iter()
+                    .flatten()
+                    .filter_map(Value::as_str)
+                    .map(str::to_string)
+                    .collect(),
+            },
+            ..SchemaModel::default()
+        });
+    // Properties next to a composition are one more part that applies
+    let with_own_properties = |composed: SchemaShape| match own_properties.clone() {
+        Some(object) => SchemaShape::AllOf(vec![
+            object,
+            SchemaModel {
+                shape: composed,
+                ..SchemaModel::default()
+            },
+        ]),
+        None => composed,
+    };
+
+    let shape = if let Some(Value::Array(values)) = map.get("enum") {
+        SchemaShape::Enum(values.clone())
+    } else if let Some(variants) = parts("oneOf") {
+        with_own_properties(SchemaShape::OneOf(variants))
+    } else if let Some(variants) = parts("anyOf") {
+        with_own_properties(SchemaShape::AnyOf(variants))
+    } else if let Some(mut parts) = parts("allOf") {
+        parts.extend(own_properties);
+        SchemaShape::AllOf(parts)
+    } else {
+        let kind = match map.get("type") {
+            Some(Value::String(kind)) => Some(kind.as_str()),
+            // OpenAPI 3.1 writes nullable types as `["string", "null"]`
+            Some(Value::Array(kinds)) => kinds
+                .iter()
+                .filter_map(Value::as_str)
+                .find(|kind| *kind != "null"),
+            _ => None,
+        };
+        match (kind, own_properties) {
+            (_, Some(object)) => object.shape,
+            (Some("array"), None) => {
+                SchemaShape::Array(Box::new(map.get("items").map(model).unwrap_or_default()))
+            }
+            (Some("object"), None) => SchemaShape::Object {
+                properties: Vec::new(),
+                required: Vec::new(),
+            },
+            (Some(kind), None) => SchemaShape::Scalar {
+                kind: kind.to_string(),
+                format: map
+                    .get("format")
+                    .and_then(Value::as_str)
+                    .map(str::to_string),
+            },
+            (None, None) => SchemaShape::Any,
+        }
+    };
+
+    let text = |key: &str| map.get(key).and_then(Value::as_str).map(str::to_string);
+    SchemaModel {
+        ref_name: None,
+        title: text("title"),
+        description: text("description"),
+        shape,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -65,14 +293,224 @@ mod tests {
             summary: Some(summary.to_string()),
             tags: Some(tags),
             parameters: None,
+            request_body: None,
+            responses: HashMap::new(),
+            description: None,
+            security: None,
+            deprecated: false,
+            callbacks: HashMap::new(),
+            other: HashMap::new(),
         }
     }
 
     #[test]
-    fn test_parse_empty_spec() {
-        let spec = SwaggerSpec {
-            paths: HashMap::new(),
+    fn test_parse_resolves_request_body_refs() {
+        let spec: SwaggerSpec = serde_json::from_value(serde_json::json!({
+            "paths": {
+                "/users": {
+                    "post": {
+                        "requestBody": {
+                            "required": true,
+                            "content": {
+                                "application/json": {
+                                    "schema": { "$ref": "#/components/schemas/User" }
+                                }
+                            }
+                        }
+                    }
+                }
+            },
+            "components": {
+                "schemas": {
+                    "User": {
+                        "type": "object",
+                        "properties": {
+                            "name": { "type": "string" },
+                            "manager": { "$ref": "#/components/schemas/User" }
+                        }
+                    }
+                }
+            }
+        }))
+        .unwrap();
+
+        let endpoints = parse_swagger_spec(spec);
+        let body = endpoints[0].request_body.as_ref().unwrap();
+        let schema = body.json_schema().unwrap();
+
+        assert!(endpoints[0].body_required());
+        assert_eq!(schema["properties"]["name"]["type"], "string");
+        // The outline model names referenced schemas; the schema itself is left as written
+        assert!(schema.get("title").is_none());
+        let model = body.model_for("application/json").unwrap();
+        assert_eq!(model.ref_name.as_deref(), Some("User"));
+        // A self-reference is left as a reference naming the schema
+        assert_eq!(
+            schema["properties"]["manager"],
+            serde_json::json!({ "$ref": "#/components/schemas/User" })
+        );
+    }
+
+    #[test]
+    fn test_parse_stops_at_recursive_refs() {
+        let node = serde_json::json!({ "$ref": "#/components/schemas/Node" });
+        let spec: SwaggerSpec = serde_json::from_value(serde_json::json!({
+            "paths": {
+                "/nodes": {
+                    "post": {
+                        "requestBody": {
+                            "content": { "application/json": { "schema": node } }
+                        },
+                        "responses": {
+                            "200": { "content": { "application/json": { "schema": node } } }
+                        }
+                    }
+                }
+            },
+            "components": {
+                "schemas": {
+                    "Node": {
+                        "type": "object",
+                        "properties": {
+                            "parent": node,
+                            "left": node,
+                            "right": node,
+                            "children": { "type": "array", "items": node },
+                            "next": { "$ref": "#/components/schemas/Edge" },
+                            "value": { "type": "string" }
+                        }
+                    },
+                    "Edge": {
+                        "type": "object",
+                        "properties": { "to": node, "weight": { "type": "number" } }
+                    }
+                }
+            }
+        }))
+        .unwrap();
+
+        let endpoints = parse_swagger_spec(spec);
+        let schema = endpoints[0]
+            .request_body
+            .as_ref()
+            .unwrap()
+            .json_schema()
+            .unwrap();
+        let properties = &schema["properties"];
+        assert_eq!(properties["value"]["type"], "string");
+        for name in ["parent", "left", "right"] {
+            assert_eq!(properties[name], node);
+        }
+        assert_eq!(properties["children"]["items"], node);
+        // Mutual recursion stops at the schema that is already being inlined
+        assert_eq!(properties["next"]["properties"]["weight"]["type"], "number");
+        assert_eq!(properties["next"]["properties"]["to"], node);
+        let response = endpoints[0].responses[0].schema.as_ref().unwrap();
+        assert_eq!(response["properties"]["left"], node);
+    }
+
+    #[test]
+    fn test_parse_merges_path_level_parameters() {
+        let spec: SwaggerSpec = serde_json::from_value(serde_json::json!({
+            "paths": {
+                "/users/{id}": {
+                    "parameters": [
+                        { "name": "id", "in": "path", "required": true },
+                        { "name": "verbose", "in": "query", "description": "shared" }
+                    ],
+                    "get": {
+                        "parameters": [
+                            { "name": "verbose", "in": "query", "description": "own" },
+                            { "name": "fields", "in": "query" }
+                        ]
+                    },
+                    "delete": {}
+                }
+            }
+        }))
+        .unwrap();
+
+        let mut endpoints = parse_swagger_spec(spec);
+        endpoints.sort_by(|a, b| a.method.cmp(&b.method));
+        let names = |endpoint: &ApiEndpoint| -> Vec<String> {
+            endpoint.parameters.iter().map(|p| p.name.clone()).collect()
         };
+
+        assert_eq!(names(&endpoints[0]), vec!["id", "verbose"]);
+        assert_eq!(names(&endpoints[1]), vec!["id", "verbose", "fields"]);
+        assert_eq!(
+            endpoints[1].parameters[1].description.as_deref(),
+            Some("own")
+        );
+    }
+
+    #[test]
+    fn test_parse_applies_spec_security() {
+        let spec: SwaggerSpec = serde_json::from_value(serde_json::json!({
+            "security": [{ "bearer": [] }],
+            "paths": {
+                "/users": {
+                    "get": {},
+                    "post": { "security": [] }
+                }
+            }
+        }))
+        .unwrap();
+
+        let mut endpoints = parse_swagger_spec(spec);
+        endpoints.sort_by(|a, b| a.method.cmp(&b.method));
+
+        assert_eq!(endpoints[0].security_label(false), "bearer");
+        assert_eq!(endpoints[1].allows_anonymous(), Some(true));
+    }
+
+    #[test]
+    fn test_parse_callbacks_and_webhooks_after_endpoints() {
+        let spec: SwaggerSpec = serde_json::from_value(serde_json::json!({
+            "security": [{ "bearer": [] }],
+            "paths": {
+                "/orders": {
+                    "post": {
+                        "callbacks": {
+                            "onPaid": {
+                                "{$request.body#/callbackUrl}": { "post": { "summary": "Paid" } }
+                            }
+                        }
+                    }
+                }
+            },
+            "webhooks": {
+                "newPet": { "post": {} }
+            }
+        }))
+        .unwrap();
+
+        let endpoints = parse_swagger_spec(spec);
+        let keys: Vec<String> = endpoints.iter().map(ApiEndpoint::key).collect();
+        assert_eq!(
+            keys,
+            vec![
+                "POST /orders",
+                "POST {$request.body#/callbackUrl}",
+                "POST newPet"
+            ]
+        );
+
+        assert_eq!(endpoints[0].event, None);
+        assert_eq!(
+            endpoints[1].event,
+            Some(EventSource::Callback {
+                name: "onPaid".to_string(),
+                operation: "POST /orders".to_string(),
+            })
+        );
+        assert_eq!(endpoints[1].security, None);
+        assert_eq!(endpoints[2].event.as_ref().unwrap().group(), "Webhooks");
+    }
+
+    #[test]
+    fn test_parse_empty_spec() {
+        let spec = SwaggerSpec::default();
         let endpoints = parse_swagger_spec(spec);
         assert_eq!(endpoints.len(), 0);
     }
@@ -91,10 +529,14 @@ mod tests {
                 put: None,
                 delete: None,
                 patch: None,
+                parameters: vec![],
             },
         );
 
-        let spec = SwaggerSpec { paths };
+        let spec = SwaggerSpec {
+            paths,
+            ..Default::default()
+        };
         let endpoints = parse_swagger_spec(spec);
 
         assert_eq!(endpoints.len(), 1);
@@ -115,10 +557,14 @@ mod tests {
                 put: Some(create_test_operation("Update user", vec![])),
                 delete: Some(create_test_operation("Delete user", vec![])),
                 patch: Some(create_test_operation("Patch user", vec![])),
+                parameters: vec![],
             },
         );
 
-        let spec = SwaggerSpec { paths };
+        let spec = SwaggerSpec {
+            paths,
+            ..Default::default()
+        };
         let endpoints = parse_swagger_spec(spec);
 
         assert_eq!(endpoints.len(), 5);
@@ -145,6 +591,7 @@ mod tests {
                 put: None,
                 delete: None,
                 patch: None,
+                parameters: vec![],
             },
         );
         paths.insert(
@@ -161,10 +608,14 @@ mod tests {
                 put: None,
                 delete: None,
                 patch: None,
+                parameters: vec![],
             },
         );
 
-        let spec = SwaggerSpec { paths };
+        let spec = SwaggerSpec {
+            paths,
+            ..Default::default()
+        };
         let endpoints = parse_swagger_spec(spec);
 
         assert_eq!(endpoints.len(), 3);
@@ -184,19 +635,31 @@ mod tests {
                     summary: None,
                     tags: Some(vec!["Test".to_string()]),
                     parameters: None,
+                    request_body: None,
+                    responses: HashMap::new(),
+                    description: None,
+                    security: None,
+                    deprecated: true,
+                    callbacks: HashMap::new(),
+                    other: HashMap::new(),
                 }),
                 post: None,
                 put: None,
                 delete: None,
                 patch: None,
+                parameters: vec![],
             },
         );
 
-        let spec = SwaggerSpec { paths };
+        let spec = SwaggerSpec {
+            paths,
+            ..Default::default()
+        };
         let endpoints = parse_swagger_spec(spec);
 
         assert_eq!(endpoints.len(), 1);
         assert_eq!(endpoints[0].summary, None);
+        assert!(endpoints[0].deprecated);
     }
 
     #[test]
@@ -209,15 +672,26 @@ mod tests {
                     summary: Some("Test endpoint".to_string()),
                     tags: None,
                     parameters: None,
+                    request_body: None,
+                    responses: HashMap::new(),
+                    description: None,
+                    security: None,
+                    deprecated: false,
+                    callbacks: HashMap::new(),
+                    other: HashMap::new(),
                 }),
                 post: None,
                 put: None,
                 delete: None,
                 patch: None,
+                parameters: vec![],
             },
         );
 
-        let spec = SwaggerSpec { paths };
+        let spec = SwaggerSpec {
+            paths,
+            ..Default::default()
+        };
         let endpoints = parse_swagger_spec(spec);
 
         assert_eq!(endpoints.len(), 1);
@@ -241,16 +715,28 @@ mod tests {
                         required: Some(true),
                         schema: None,
                         description: Some("User ID".to_string()),
+                        example: None,
                     }]),
+                    request_body: None,
+                    responses: HashMap::new(),
+                    description: None,
+                    security: None,
+                    deprecated: false,
+                    callbacks: HashMap::new(),
+                    other: HashMap::new(),
                 }),
                 post: None,
                 put: None,
                 delete: None,
                 patch: None,
+                parameters: vec![],
             },
         );
 
-        let spec = SwaggerSpec { paths };
+        let spec = SwaggerSpec {
+            paths,
+            ..Default::default()
+        };
         let endpoints = parse_swagger_spec(spec);
 
         assert_eq!(endpoints.len(), 1);
@@ -270,10 +756,14 @@ mod tests {
                 put: None,
                 delete: None,
                 patch: None,
+                parameters: vec![],
             },
         );
 
-        let spec = SwaggerSpec { paths };
+        let spec = SwaggerSpec {
+            paths,
+            ..Default::default()
+        };
         let endpoints = parse_swagger_spec(spec);
 
         // No operations defined, so no endpoints should be created
@@ -294,10 +784,14 @@ mod tests {
                 put: None,
                 delete: None,
                 patch: None,
+                parameters: vec![],
             },
         );
 
-        let spec = SwaggerSpec { paths };
+        let spec = SwaggerSpec {
+            paths,
+            ..Default::default()
+        };
         let endpoints = parse_swagger_spec(spec);
 
         assert_eq!(endpoints.len(), 1);
@@ -0,0 +1,168 @@
+//! OpenAPI 3 references to reusable parameters and callbacks
+//!
+//! Parameters and callbacks are read into typed fields, which a `$ref` in
+//! their place would not fit. The references are replaced by the components
+//! they point to before the document is read; ones that don't resolve are
+//! dropped, so a dangling reference costs a parameter or callback, not the spec.
+
+use serde_json::{Map, Value};
+
+/// Methods whose operations are searched for references
+const METHODS: [&str; 8] = [
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+/// Maximum callback nesting followed (guards against callbacks referencing themselves)
+const MAX_CALLBACK_DEPTH: usize = 4;
+
+/// The document with its parameter and callback references inlined
+pub fn inline_refs(mut doc: Value) -> Value {
+    let components = doc.get("components").cloned().unwrap_or_default();
+    for section in ["paths", "webhooks"] {
+        if let Some(Value::Object(items)) = doc.get_mut(section) {
+            for path_item in items.values_mut().filter_map(Value::as_object_mut) {
+                inline_path_item(path_item, &components, 0);
+            }
+        }
+    }
+    doc
+}
+
+fn inline_path_item(path_item: &mut Map<String, Value>, components: &Value, depth: usize) {
+    inline_parameters(path_item, components);
+    for method in METHODS {
+        let Some(Value::Object(op)) = path_item.get_mut(method) else {
+            continue;
+        };
+        inline_parameters(op, components);
+        if let Some(Value::Object(callbacks)) = op.get_mut("callbacks") {
+            inline_callbacks(callbacks, components, depth);
+        }
+    }
+}
+
+fn inline_parameters(item: &mut Map<String, Value>, components: &Value) {
+    if let Some(Value::Array(params)) = item.get_mut("parameters") {
+        let resolved = params
+            .drain(..)
+            .filter_map(|param| resolve(param, components, "parameters"))
+            .collect();
+        *params = resolved;
+    }
+}
+
+fn inline_callbacks(callbacks: &mut Map<String, Value>, components: &Value, depth: usize) {
+    let names: Vec<String> = callbacks.keys().cloned().collect();
+    for name in names {
+        let resolved = callbacks
+            .remove(&name)
+            .filter(|_| depth < MAX_CALLBACK_DEPTH)
+            .and_then(|callback| resolve(callback, components, "callbacks"));
+        let Some(mut callback) = resolved else {
+            continue;
+        };
+        if let Some(expressions) = callback.as_object_mut() {
+            for path_item in expressions.values_mut().filter_map(Value::as_object_mut) {
+                inline_path_item(path_item, components, depth + 1);
+            }
+        }
+        callbacks.insert(name, callback);
+    }
+}
+
+/// The component a `#/components/<section>/...` reference points to, the value itself
+/// when it is no reference, or nothing when the reference doesn't resolve
+fn resolve(value: Value, components: &Value, section: &str) -> Option<Value> {
+    let Some(reference) = value.get("$ref").and_then(Value::as_str) else {
+        return Some(value);
+    };
+    let name = reference
+        .strip_prefix("#/components/")?
+        .strip_prefix(section)?
+        .strip_prefix('/')?;
+    components.get(section)?.get(name).cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::plugins::PluginRegistry;
+    use crate::swagger::parse_spec;
+    use crate::types::EventSource;
+    use serde_json::json;
+
+    fn parse(doc: serde_json::Value) -> Result<crate::swagger::LoadedSpec, String> {
+        let body = serde_json::to_vec(&doc).unwrap();
+        parse_spec(
+            &body,
+            "http://x/openapi.json",
+            None,
+            &PluginRegistry::default(),
+        )
+    }
+
+    #[test]
+    fn test_inline_parameter_refs() {
+        let spec = parse(json!({
+            "openapi": "3.0.0",
+            "paths": {
+                "/pets/{id}": {
+                    "parameters": [
+                        { "$ref": "#/components/parameters/Id" },
+                        { "$ref": "#/components/parameters/Missing" }
+                    ],
+                    "get": {
+                        "parameters": [{ "$ref": "#/components/parameters/Page" }]
+                    }
+                }
+            },
+            "components": {
+                "parameters": {
+                    "Id": { "name": "id", "in": "path", "required": true },
+                    "Page": { "name": "page", "in": "query" }
+                }
+            }
+        }))
+        .unwrap();
+
+        let names: Vec<&str> = spec.endpoints[0]
+            .parameters
+            .iter()
+            .map(|p| p.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["id", "page"]);
+    }
+
+    #[test]
+    fn test_inline_callback_refs() {
+        let spec = parse(json!({
+            "openapi": "3.0.0",
+            "paths": {
+                "/subscriptions": {
+                    "post": {
+                        "callbacks": {
+                            "onEvent": { "$ref": "#/components/callbacks/onEv" },
+                            "onGone": { "$ref": "#/components/callbacks/missing" },
+                            "onOdd": { "{$request.body#/url}": "not a path item" }
+                        }
+                    }
+                }
+            },
+            "components": {
+                "callbacks": {
+                    "onEv": {
+                        "{$request.body#/url}": { "post": { "summary": "Event" } }
+                    }
+                }
+            }
+        }))
+        .unwrap();
+
+        assert_eq!(spec.endpoints.len(), 2);
+        let callback = &spec.endpoints[1];
+        assert_eq!(callback.summary.as_deref(), Some("Event"));
+        assert!(matches!(
+            &callback.event,
+            Some(EventSource::Callback { name, .. }) if name == "onEvent"
+        ));
+    }
+}
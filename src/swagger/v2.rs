@@ -0,0 +1,401 @@
+//! Swagger 2.0 (OpenAPI v2) documents
+//!
+//! Many services still serve Swagger 2.0, which keeps the server in `host`,
+//! `basePath` and `schemes`, parameter types next to the parameter instead of
+//! in a `schema`, the body as an `in: body` parameter, form fields as
+//! `in: formData` parameters and media types in `consumes`/`produces`. Such a
+//! document is rewritten into the OpenAPI 3 layout before it is read, so the
+//! rest of the app only knows one format.
+
+use serde_json::{json, Map, Value};
+
+/// Methods whose operations are rewritten
+const METHODS: [&str; 7] = ["get", "put", "post", "delete", "options", "head", "patch"];
+
+/// Parameter fields that OpenAPI 3 keeps in the parameter's `schema`
+const SCHEMA_FIELDS: [&str; 16] = [
+    "type",
+    "format",
+    "items",
+    "enum",
+    "default",
+    "minimum",
+    "maximum",
+    "exclusiveMinimum",
+    "exclusiveMaximum",
+    "minLength",
+    "maxLength",
+    "pattern",
+    "minItems",
+    "maxItems",
+    "uniqueItems",
+    "multipleOf",
+];
+
+/// Media type assumed when neither the operation nor the document declares one
+const DEFAULT_MEDIA_TYPE: &str = "application/json";
+
+/// Whether the document declares `swagger: "2.0"`
+pub fn is_swagger2(doc: &Value) -> bool {
+    doc.get("swagger")
+        .and_then(Value::as_str)
+        .is_some_and(|version| version.starts_with('2'))
+}
+
+/// The document in the OpenAPI 3 layout
+pub fn upgrade(mut doc: Value) -> Value {
+    let Some(root) = doc.as_object_mut() else {
+        return doc;
+    };
+    let shared = Shared {
+        consumes: media_types(root.get("consumes")),
+        produces: media_types(root.get("produces")),
+        parameters: root.remove("parameters").unwrap_or_default(),
+        responses: root.remove("responses").unwrap_or_default(),
+    };
+
+    if let Some(server) = server_url(root) {
+        root.insert("servers".to_string(), json!([{ "url": server }]));
+    }
+    if let Some(Value::Object(definitions)) = root.remove("securityDefinitions") {
+        let schemes: Map<String, Value> = definitions
+            .into_iter()
+            .map(|(name, definition)| (name, upgrade_security_scheme(definition)))
+            .collect();
+        root.entry("components").or_insert_with(|| json!({}))["securitySchemes"] =
+            Value::Object(schemes);
+    }
+    if let Some(Value::Object(paths)) = root.get_mut("paths") {
+        for path_item in paths.values_mut().filter_map(Value::as_object_mut) {
+            upgrade_path_item(path_item, &shared);
+        }
+    }
+    doc
+}
+
+/// Document-wide defaults and the reusable parameters and responses `$ref`s point to
+struct Shared {
+    consumes: Vec<String>,
+    produces: Vec<String>,
+    parameters: Value,
+    responses: Value,
+}
+
+impl Shared {
+    /// The reused definition for a `#/parameters/...` or `#/responses/...` reference
+    fn resolve(&self, value: &Value) -> Value {
+        let reference = value.get("$ref").and_then(Value::as_str);
+        let target = reference.and_then(|reference| {
+            let (section, name) = reference.strip_prefix("#/")?.split_once('/')?;
+            match section {
+                "parameters" => self.parameters.get(name),
+                "responses" => self.responses.get(name),
+                _ => None,
+            }
+        });
+        target.unwrap_or(value).clone()
+    }
+}
+
+/// `scheme://host/basePath` of the first scheme, or the base path alone when no
+/// host is given, which is then resolved against the spec's URL
+fn server_url(root: &Map<String, Value>) -> Option<String> {
+    let base_path = root
+        .get("basePath")
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .trim_end_matches('/');
+    let Some(host) = root.get("host").and_then(Value::as_str) else {
+        return (!base_path.is_empty()).then(|| base_path.to_string());
+    };
+    let scheme = root
+        .get("schemes")
+        .and_then(|schemes| schemes.get(0))
+        .and_then(Value::as_str)
+        .unwrap_or("https");
+    Some(format!("{scheme}://{host}{base_path}"))
+}
+
+/// `type: basic` is an `http` scheme in OpenAPI 3; `apiKey` and `oauth2` keep their fields
+fn upgrade_security_scheme(mut definition: Value) -> Value {
+    if definition["type"] == "basic" {
+        definition["type"] = json!("http");
+        definition["scheme"] = json!("basic");
+    }
+    definition
+}
+
+fn media_types(value: Option<&Value>) -> Vec<String> {
+    value
+        .and_then(Value::as_array)
+        .map(|types| {
+            types
+                .iter()
+                .filter_map(Value::as_str)
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn upgrade_path_item(path_item: &mut Map<String, Value>, shared: &Shared) {
+    if let Some(Value::Array(params)) = path_item.get_mut("parameters") {
+        for param in params.iter_mut() {
+            *param = upgrade_parameter(shared.resolve(param));
+        }
+    }
+    for method in METHODS {
+        if let Some(Value::Object(op)) = path_item.get_mut(method) {
+            upgrade_operation(op, shared);
+        }
+    }
+}
+
+/// Move the body and form fields into a `requestBody` and the media types into `content`
+fn upgrade_operation(op: &mut Map<String, Value>, shared: &Shared) {
+    let consumes = Some(media_types(op.get("consumes")))
+        .filter(|types| !types.is_empty())
+        .unwrap_or_else(|| shared.consumes.clone());
+    let produces = Some(media_types(op.get("produces")))
+        .filter(|types| !types.is_empty())
+        .unwrap_or_else(|| shared.produces.clone());
+
+    let params: Vec<Value> = match op.remove("parameters") {
+        Some(Value::Array(params)) => params.iter().map(|p| shared.resolve(p)).collect(),
+        _ => Vec::new(),
+    };
+    let (body, params): (Vec<Value>, Vec<Value>) =
+        params.into_iter().partition(|param| param["in"] == "body");
+    let (form_fields, params): (Vec<Value>, Vec<Value>) = params
+        .into_iter()
+        .partition(|param| param["in"] == "formData");
+
+    if let Some(body) = body.into_iter().next() {
+        let schema = body.get("schema").cloned().unwrap_or_else(|| json!({}));
+        op.insert(
+            "requestBody".to_string(),
+            request_body(&body, &with_default(consumes.clone()), schema),
+        );
+    } else if !form_fields.is_empty() {
+        let media_type = if consumes.iter().any(|t| t == "multipart/form-data") {
+            "multipart/form-data"
+        } else {
+            "application/x-www-form-urlencoded"
+        };
+        let schema = form_schema(&form_fields);
+        let required = form_fields.iter().any(|field| field["required"] == true);
+        op.insert(
+            "requestBody".to_string(),
+            request_body(
+                &json!({ "required": required }),
+                &[media_type.to_string()],
+                schema,
+            ),
+        );
+    }
+    if !params.is_empty() {
+        let params = params.into_iter().map(upgrade_parameter).collect();
+        op.insert("parameters".to_string(), Value::Array(params));
+    }
+
+    if let Some(Value::Object(responses)) = op.get_mut("responses") {
+        let produces = with_default(produces);
+        for response in responses.values_mut() {
+            *response = upgrade_response(shared.resolve(response), &produces);
+        }
+    }
+}
+
+fn with_default(media_types: Vec<String>) -> Vec<String> {
+    if media_types.is_empty() {
+        vec![DEFAULT_MEDIA_TYPE.to_string()]
+    } else {
+        media_types
+    }
+}
+
+fn request_body(param: &Value, media_types: &[String], schema: Value) -> Value {
+    let content: Map<String, Value> = media_types
+        .iter()
+        .map(|media_type| (media_type.clone(), json!({ "schema": schema })))
+        .collect();
+    let mut body = json!({ "content": content });
+    for field in ["required", "description"] {
+        if let Some(value) = param.get(field) {
+            body[field] = value.clone();
+        }
+    }
+    body
+}
+
+/// An object schema with a property per form field; `file` fields become binary strings
+fn form_schema(fields: &[Value]) -> Value {
+    let mut properties = Map::new();
+    let mut required = Vec::new();
+    for field in fields {
+        let Some(name) = field.get("name").and_then(Value::as_str) else {
+            continue;
+        };
+        let mut schema = upgrade_parameter(field.clone())["schema"].take();
+        if schema["type"] == "file" {
+            schema = json!({ "type": "string", "format": "binary" });
+        }
+        if let Some(description) = field.get("description") {
+            schema["description"] = description.clone();
+        }
+        if field["required"] == true {
+            required.push(json!(name));
+        }
+        properties.insert(name.to_string(), schema);
+    }
+    let mut schema = json!({ "type": "object", "properties": properties });
+    if !required.is_empty() {
+        schema["required"] = Value::Array(required);
+    }
+    schema
+}
+
+/// Gather the type fields of a parameter into its `schema`; `x-example` becomes its example
+fn upgrade_parameter(mut param: Value) -> Value {
+    let Some(fields) = param.as_object_mut() else {
+        return param;
+    };
+    if !fields.contains_key("schema") {
+        let schema: Map<String, Value> = SCHEMA_FIELDS
+            .iter()
+            .filter_map(|field| Some((field.to_string(), fields.remove(*field)?)))
+            .collect();
+        if !schema.is_empty() {
+            fields.insert("schema".to_string(), Value::Object(schema));
+        }
+    }
+    if let Some(example) = fields.remove("x-example") {
+        fields.entry("example").or_insert(example);
+    }
+    param
+}
+
+/// Move the response's `schema` and `examples` into `content` per produced media type
+fn upgrade_response(mut response: Value, produces: &[String]) -> Value {
+    let Some(fields) = response.as_object_mut() else {
+        return response;
+    };
+    let schema = fields.remove("schema");
+    let examples = match fields.remove("examples") {
+        Some(Value::Object(examples)) => examples,
+        _ => Map::new(),
+    };
+    if schema.is_none() && examples.is_empty() {
+        return response;
+    }
+
+    let mut media_types = produces.to_vec();
+    media_types.extend(examples.keys().filter(|t| !produces.contains(t)).cloned());
+    let content: Map<String, Value> = media_types
+        .into_iter()
+        .map(|media_type| {
+            let mut media = json!({});
+            if let Some(schema) = &schema {
+                media["schema"] = schema.clone();
+            }
+            if let Some(example) = examples.get(&media_type) {
+                media["example"] = example.clone();
+            }
+            (media_type, media)
+        })
+        .collect();
+    fields.insert("content".to_string(), Value::Object(content));
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::swagger::parse::parse_swagger_spec;
+    use crate::types::SwaggerSpec;
+
+    #[test]
+    fn test_upgrade_swagger2() {
+        let doc = json!({
+            "swagger": "2.0",
+            "host": "api.example.com",
+            "basePath": "/v1/",
+            "schemes": ["https", "http"],
+            "produces": ["application/json"],
+            "securityDefinitions": {
+                "basicAuth": { "type": "basic" },
+                "key": { "type": "apiKey", "name": "X-Key", "in": "header" }
+            },
+            "parameters": {
+                "Page": { "name": "page", "in": "query", "type": "integer", "default": 1 }
+            },
+            "paths": {
+                "/pets/{petId}": {
+                    "parameters": [
+                        { "name": "petId", "in": "path", "required": true, "type": "string" }
+                    ],
+                    "get": {
+                        "parameters": [{ "$ref": "#/parameters/Page" }],
+                        "responses": {
+                            "200": {
+                                "description": "A pet",
+                                "schema": { "$ref": "#/definitions/Pet" },
+                                "examples": { "application/json": { "name": "Rex" } }
+                            }
+                        }
+                    },
+                    "put": {
+                        "parameters": [{
+                            "name": "pet", "in": "body", "required": true,
+                            "schema": { "$ref": "#/definitions/Pet" }
+                        }]
+                    },
+                    "post": {
+                        "consumes": ["multipart/form-data"],
+                        "parameters": [
+                            { "name": "photo", "in": "formData", "type": "file", "required": true },
+                            { "name": "caption", "in": "formData", "type": "string" }
+                        ]
+                    }
+                }
+            },
+            "definitions": {
+                "Pet": { "type": "object", "properties": { "name": { "type": "string" } } }
+            }
+        });
+        assert!(is_swagger2(&doc));
+        let doc = upgrade(doc);
+        assert_eq!(doc["servers"][0]["url"], "https://api.example.com/v1");
+        let schemes = &doc["components"]["securitySchemes"];
+        assert_eq!(
+            schemes["basicAuth"],
+            json!({ "type": "http", "scheme": "basic" })
+        );
+        assert_eq!(schemes["key"]["name"], "X-Key");
+
+        let spec: SwaggerSpec = serde_json::from_value(doc).unwrap();
+        let endpoints = parse_swagger_spec(spec);
+        let get = endpoints.iter().find(|e| e.method == "GET").unwrap();
+        assert_eq!(get.parameters.len(), 2);
+        assert_eq!(get.parameters[0].type_info(), "string");
+        let page = &get.parameters[1];
+        assert_eq!(
+            (page.name.as_str(), page.location.as_str()),
+            ("page", "query")
+        );
+        assert_eq!(page.type_info(), "integer");
+        assert_eq!(page.spec_value(), Some(&json!(1)));
+        assert_eq!(get.response_example.as_ref().unwrap().status, "200");
+
+        let put = endpoints.iter().find(|e| e.method == "PUT").unwrap();
+        assert!(put.body_required());
+        let schema = put.request_body.as_ref().unwrap().json_schema().unwrap();
+        assert_eq!(schema["properties"]["name"]["type"], "string");
+
+        let post = endpoints.iter().find(|e| e.method == "POST").unwrap();
+        let form = &post.request_body.as_ref().unwrap().content["multipart/form-data"];
+        let schema = form.schema.as_ref().unwrap();
+        assert_eq!(schema["properties"]["photo"]["format"], "binary");
+        assert_eq!(schema["required"], json!(["photo"]));
+    }
+}
@@ -0,0 +1,190 @@
+//! Table view of JSON array responses
+//!
+//! A response body that is an array of objects can be shown as a table:
+//! columns come from the object keys (in first-seen order) and rows can be
+//! sorted by any column. Tables can be exported to CSV files.
+
+use crate::utils::{export_file_name, write_export};
+use serde_json::{Map, Value};
+use std::cmp::Ordering;
+use std::path::PathBuf;
+
+/// Sort applied to a response table
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TableSort {
+    pub column: usize,
+    pub descending: bool,
+}
+
+/// Rows of an array response, keyed by column
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResponseTable {
+    pub columns: Vec<String>,
+    pub rows: Vec<Map<String, Value>>,
+}
+
+impl ResponseTable {
+    /// Build a table from a response body that is a non-empty array of objects
+    pub fn from_body(body: &str) -> Option<Self> {
+        let Value::Array(items) = serde_json::from_str::<Value>(body).ok()? else {
+            return None;
+        };
+        if items.is_empty() {
+            return None;
+        }
+
+        let rows = items
+            .into_iter()
+            .map(|item| match item {
+                Value::Object(row) => Some(row),
+                _ => None,
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        let mut columns: Vec<String> = Vec::new();
+        for row in &rows {
+            for key in row.keys() {
+                if !columns.contains(key) {
+                    columns.push(key.clone());
+                }
+            }
+        }
+
+        Some(Self { columns, rows })
+    }
+
+    /// Text of a cell: strings unquoted, missing/null empty, nested values as compact JSON
+    pub fn cell(&self, row: usize, column: usize) -> String {
+        match self.rows[row].get(&self.columns[column]) {
+            None | Some(Value::Null) => String::new(),
+            Some(Value::String(s)) => s.clone(),
+            Some(value) => value.to_string(),
+        }
+    }
+
+    /// Sort rows by a column, numbers numerically and everything else as text
+    pub fn sort(&mut self, sort: TableSort) {
+        let Some(column) = self.columns.get(sort.column) else {
+            return;
+        };
+
+        self.rows.sort_by(|a, b| {
+            let ordering = compare_values(a.get(column), b.get(column));
+            if sort.descending {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        });
+    }
+}
+
+impl ResponseTable {
+    /// Render the table as CSV with a header row (RFC 4180 quoting)
+    pub fn to_csv(&self) -> String {
+        let mut csv = csv_record(self.columns.iter().map(String::as_str));
+        for row in 0..self.rows.len() {
+            let cells: Vec<String> = (0..self.columns.len())
+                .map(|column| self.cell(row, column))
+                .collect();
+            csv.push_str(&csv_record(cells.iter().map(String::as_str)));
+        }
+        csv
+    }
+
+    /// Write the table as CSV, expanding a leading `~/`; returns the written path
+    pub fn write_csv(&self, path: &str) -> std::io::Result<PathBuf> {
+        write_export(path, self.to_csv().as_bytes())
+    }
+}
+
+/// Suggested export file name for an endpoint path, e.g. `users-id-posts.csv`
+pub fn csv_file_name(endpoint_path: &str) -> String {
+    export_file_name(endpoint_path, "csv")
+}
+
+fn csv_record<'a>(fields: impl Iterator<Item = &'a str>) -> String {
+    let fields: Vec<String> = fields
+        .map(|field| {
+            if field.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", field.replace('"', "\"\""))
+            } else {
+                field.to_string()
+            }
+        })
+        .collect();
+    format!("{}\r\n", fields.join(","))
+}
+
+fn compare_values(a: Option<&Value>, b: Option<&Value>) -> Ordering {
+    match (a, b) {
+        (Some(Value::Number(a)), Some(Value::Number(b))) => a
+            .as_f64()
+            .partial_cmp(&b.as_f64())
+            .unwrap_or(Ordering::Equal),
+        // Missing and null values sort after everything else (in ascending order)
+        (None | Some(Value::Null), None | Some(Value::Null)) => Ordering::Equal,
+        (None | Some(Value::Null), _) => Ordering::Greater,
+        (_, None | Some(Value::Null)) => Ordering::Less,
+        (Some(Value::String(a)), Some(Value::String(b))) => a.cmp(b),
+        (Some(a), Some(b)) => a.to_string().cmp(&b.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_body_collects_columns() {
+        let table = ResponseTable::from_body(
+            r#"[{"id": 2, "name": "b"}, {"id": 10, "email": null, "tags": ["x"]}]"#,
+        )
+        .unwrap();
+
+        assert_eq!(table.columns, ["id", "name", "email", "tags"]);
+        assert_eq!(table.cell(0, 1), "b");
+        assert_eq!(table.cell(1, 1), "");
+        assert_eq!(table.cell(1, 3), r#"["x"]"#);
+
+        assert!(ResponseTable::from_body(r#"{"id": 1}"#).is_none());
+        assert!(ResponseTable::from_body("[1, 2]").is_none());
+        assert!(ResponseTable::from_body("[]").is_none());
+    }
+
+    #[test]
+    fn test_to_csv_quotes_fields() {
+        let table = ResponseTable::from_body(
+            r#"[{"id": 1, "name": "Smith, Jo"}, {"id": 2, "name": "say \"hi\""}]"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            table.to_csv(),
+            "id,name\r\n1,\"Smith, Jo\"\r\n2,\"say \"\"hi\"\"\"\r\n"
+        );
+        assert_eq!(csv_file_name("/users/{id}/posts"), "users-id-posts.csv");
+        assert_eq!(csv_file_name("/"), "response.csv");
+    }
+
+    #[test]
+    fn test_sort_numeric_and_missing_last() {
+        let mut table =
+            ResponseTable::from_body(r#"[{"id": 2}, {"id": 10}, {"name": "x"}, {"id": 1}]"#)
+                .unwrap();
+
+        table.sort(TableSort {
+            column: 0,
+            descending: false,
+        });
+        let ids: Vec<String> = (0..4).map(|row| table.cell(row, 0)).collect();
+        assert_eq!(ids, ["1", "2", "10", ""]);
+
+        table.sort(TableSort {
+            column: 0,
+            descending: true,
+        });
+        assert_eq!(table.cell(0, 1), "x");
+        assert_eq!(table.cell(1, 0), "10");
+    }
+}
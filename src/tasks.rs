@@ -0,0 +1,85 @@
+//! Background tasks whose failure shows in the UI
+//!
+//! A spawned task that panics would otherwise vanish silently, leaving e.g. the
+//! request spinner running forever. Tasks started with `spawn` are watched:
+//! when one panics, its `on_panic` callback puts the state back in order and
+//! shows the error. The panic itself goes to the debug log, not over the UI.
+
+use crate::state::{AppState, LockRecover};
+use crate::ui::events::log_debug;
+use std::any::Any;
+use std::future::Future;
+use std::sync::{Arc, RwLock};
+use tokio::task::AbortHandle;
+
+/// Run a task in the background, calling `on_panic` with the panic message if it panics
+///
+/// The returned handle cancels the task; a cancelled task is not reported.
+pub fn spawn<F, P>(state: Arc<RwLock<AppState>>, task: F, on_panic: P) -> AbortHandle
+where
+    F: Future<Output = ()> + Send + 'static,
+    P: FnOnce(&mut AppState, String) + Send + 'static,
+{
+    let handle = tokio::spawn(task);
+    let abort = handle.abort_handle();
+    tokio::spawn(async move {
+        if let Err(e) = handle.await {
+            if e.is_panic() {
+                let message = panic_message(e.into_panic());
+                on_panic(&mut state.write_lock(), message);
+            }
+        }
+    });
+    abort
+}
+
+/// The message a panic was raised with
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown error".to_string())
+}
+
+/// Write panics of background threads to the debug log instead of the terminal
+///
+/// Panics of the main thread still go to the previous hook, which restores the terminal.
+pub fn log_background_panics() {
+    let previous = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        if std::thread::current().name() == Some("main") {
+            previous(info);
+        } else {
+            log_debug(&format!("Background task panicked: {info}"));
+        }
+    }));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::LoadingState;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_spawn_reports_panics() {
+        let state = Arc::new(RwLock::new(AppState::default()));
+        spawn(
+            Arc::clone(&state),
+            async { panic!("spec parser blew up") },
+            |state, message| state.data.loading_state = LoadingState::Error(message),
+        );
+
+        for _ in 0..100 {
+            if matches!(state.read_lock().data.loading_state, LoadingState::Error(_)) {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert!(matches!(
+            &state.read_lock().data.loading_state,
+            LoadingState::Error(message) if message == "spec parser blew up"
+        ));
+    }
+}
@@ -0,0 +1,510 @@
+//! The session's HTTP traffic, for export to other tools
+//!
+//! Every request sent this session is kept with its response, including
+//! followed links and retries after re-auth. `H` writes the log as a HAR file
+//! (read by mitmproxy, browsers' dev tools and most HTTP tooling) when the path
+//! ends in `.har`, as a Markdown report to attach to tickets and PRs when it
+//! ends in `.md`, or as JSON Lines, one exchange per line, otherwise.
+//! Credential header values are masked, as in recorded sessions, and so is the
+//! API key of the spec's security scheme, in its header or query parameter;
+//! the report also masks credentials in URLs and bodies. Response bodies share
+//! the buffer of the response, and only their first `MAX_TRAFFIC_BODY_BYTES`
+//! are kept.
+
+use crate::plugins::OutgoingRequest;
+use crate::redact::{mask_header, redact, MASK};
+use crate::types::{ApiResponse, Credential, ResponseBody};
+use crate::utils::write_export;
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde_json::{json, Value};
+use std::path::PathBuf;
+use std::time::Duration;
+use url::Url;
+
+/// Exchanges kept per session; the oldest are dropped first
+pub const MAX_TRAFFIC_ENTRIES: usize = 500;
+
+/// Bytes of a response body kept in the log; the rest is dropped
+pub const MAX_TRAFFIC_BODY_BYTES: usize = 64 * 1024;
+
+/// Lines of a body shown in the Markdown report; the rest is cut
+const REPORT_BODY_LINES: usize = 40;
+
+/// Characters of a body shown in the Markdown report, for bodies of few long lines
+const REPORT_BODY_CHARS: usize = 4000;
+
+/// One request sent this session and what came back
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrafficEntry {
+    pub started: DateTime<Utc>,
+    pub method: String,
+    pub url: String,
+    pub request_headers: Vec<(String, String)>,
+    pub request_body: Option<String>,
+    /// HTTP status, 0 when no response arrived
+    pub status: u16,
+    pub status_text: String,
+    pub response_headers: Vec<(String, String)>,
+    /// At most `MAX_TRAFFIC_BODY_BYTES` of the body
+    pub response_body: ResponseBody,
+    /// Size of the whole response body in bytes
+    pub response_size: usize,
+    pub duration: Duration,
+    pub error: Option<String>,
+}
+
+impl TrafficEntry {
+    /// Record an exchange; `credential` is the one the request was authenticated with
+    pub fn new(
+        request: &OutgoingRequest,
+        response: &ApiResponse,
+        started: DateTime<Utc>,
+        credential: Option<&Credential>,
+    ) -> Self {
+        let mut request_headers: Vec<(String, String)> = request
+            .headers
+            .iter()
+            .map(|(name, value)| match credential {
+                Some(Credential::Header(key_name, _)) if key_name.eq_ignore_ascii_case(name) => {
+                    (name.clone(), MASK.to_string())
+                }
+                _ => (name.clone(), mask_header(name, value)),
+            })
+            .collect();
+        let url = match credential {
+            Some(Credential::Query(key_name, _)) => mask_query_param(&request.url, key_name),
+            _ => request.url.clone(),
+        };
+        if let Some(body) = &request.body {
+            request_headers.push(("Content-Type".to_string(), body.content_type.clone()));
+        }
+        let mut response_headers: Vec<(String, String)> = response
+            .headers
+            .iter()
+            .map(|(name, value)| (name.clone(), value.clone()))
+            .collect();
+        response_headers.sort();
+
+        Self {
+            started,
+            method: request.method.clone(),
+            url,
+            request_headers,
+            request_body: request.body.as_ref().map(|body| body.content.clone()),
+            status: response.status,
+            status_text: response.status_text.clone(),
+            response_headers,
+            response_body: response.body.truncated(MAX_TRAFFIC_BODY_BYTES),
+            response_size: response.body.bytes().len(),
+            duration: response.duration,
+            error: response.error_message.clone(),
+        }
+    }
+
+    fn header(headers: &[(String, String)], name: &str) -> Option<String> {
+        headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.clone())
+    }
+
+    fn duration_ms(&self) -> f64 {
+        self.duration.as_secs_f64() * 1000.0
+    }
+
+    fn is_truncated(&self) -> bool {
+        self.response_body.bytes().len() < self.response_size
+    }
+
+    /// The response body as HAR `content`, noting when it was cut
+    fn har_content(&self, mime_type: Option<String>) -> Value {
+        let mut content = json!({
+            "size": self.response_size,
+            "mimeType": mime_type.unwrap_or_default(),
+            "text": self.response_body.text(),
+        });
+        if self.is_truncated() {
+            content["comment"] = json!(format!(
+                "truncated to the first {} bytes",
+                self.response_body.bytes().len()
+            ));
+        }
+        content
+    }
+
+    /// The exchange as a HAR 1.2 entry
+    fn to_har(&self) -> Value {
+        let headers = |headers: &[(String, String)]| -> Vec<Value> {
+            headers
+                .iter()
+                .map(|(name, value)| json!({ "name": name, "value": value }))
+                .collect()
+        };
+        let query: Vec<Value> = Url::parse(&self.url)
+            .map(|url| {
+                url.query_pairs()
+                    .map(|(name, value)| json!({ "name": name, "value": value }))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let mut request = json!({
+            "method": self.method,
+            "url": self.url,
+            "httpVersion": "HTTP/1.1",
+            "cookies": [],
+            "headers": headers(&self.request_headers),
+            "queryString": query,
+            "headersSize": -1,
+            "bodySize": self.request_body.as_ref().map_or(0, String::len),
+        });
+        if let Some(body) = &self.request_body {
+            let mime_type = Self::header(&self.request_headers, "content-type");
+            request["postData"] =
+                json!({ "mimeType": mime_type.unwrap_or_default(), "text": body });
+        }
+        let mime_type = Self::header(&self.response_headers, "content-type");
+
+        let mut entry = json!({
+            "startedDateTime": self.started.to_rfc3339_opts(SecondsFormat::Millis, true),
+            "time": self.duration_ms(),
+            "request": request,
+            "response": {
+                "status": self.status,
+                "statusText": self.status_text,
+                "httpVersion": "HTTP/1.1",
+                "cookies": [],
+                "headers": headers(&self.response_headers),
+                "content": self.har_content(mime_type),
+                "redirectURL": "",
+                "headersSize": -1,
+                "bodySize": self.response_size,
+            },
+            "cache": {},
+            "timings": { "send": 0, "wait": self.duration_ms(), "receive": 0 },
+        });
+        if let Some(error) = &self.error {
+            entry["comment"] = json!(error);
+        }
+        entry
+    }
+
+    /// The exchange as a section of the Markdown report
+    fn to_markdown(&self, number: usize) -> String {
+        let url = redact(&self.url);
+        let parsed = Url::parse(&self.url).ok();
+        let path = parsed.as_ref().map_or(url.as_str(), Url::path);
+        let outcome = match &self.error {
+            Some(_) if self.status == 0 => "failed".to_string(),
+            _ => format!("{} {}", self.status, self.status_text)
+                .trim()
+                .to_string(),
+        };
+        let mut section = format!(
+            "## {number}. {} {path} — {outcome} ({:.0} ms)\n\n- URL: `{url}`\n- Sent: {}\n",
+            self.method,
+            self.duration_ms(),
+            self.started.format("%Y-%m-%d %H:%M:%S UTC"),
+        );
+        if let Some(error) = &self.error {
+            section.push_str(&format!("- Error: {}\n", redact(error)));
+        }
+
+        let params: Vec<(String, String)> = parsed
+            .map(|url| url.query_pairs().into_owned().collect())
+            .unwrap_or_default();
+        if !params.is_empty() {
+            section.push_str("\n**Query parameters**\n\n| Name | Value |\n| --- | --- |\n");
+            for (name, value) in params {
+                let cell = |text: &str| redact(text).replace('|', "\\|");
+                section.push_str(&format!("| {} | {} |\n", cell(&name), cell(&value)));
+            }
+        }
+
+        if let Some(body) = self.request_body.as_deref().filter(|body| !body.is_empty()) {
+            let content_type = Self::header(&self.request_headers, "content-type");
+            section.push_str("\n**Request body**\n\n");
+            section.push_str(&markdown_code(body, content_type.as_deref()));
+        }
+        if !self.response_body.is_empty() {
+            let content_type = Self::header(&self.response_headers, "content-type");
+            section.push_str("\n**Response body**\n\n");
+            section.push_str(&markdown_code(&self.response_body, content_type.as_deref()));
+        }
+        section
+    }
+
+    /// The exchange as one JSON Lines record
+    fn to_json_line(&self) -> Value {
+        let headers = |headers: &[(String, String)]| -> serde_json::Map<String, Value> {
+            headers
+                .iter()
+                .map(|(name, value)| (name.clone(), json!(value)))
+                .collect()
+        };
+        json!({
+            "time": self.started.to_rfc3339_opts(SecondsFormat::Millis, true),
+            "method": self.method,
+            "url": self.url,
+            "request_headers": headers(&self.request_headers),
+            "request_body": self.request_body,
+            "status": self.status,
+            "response_headers": headers(&self.response_headers),
+            "response_body": self.response_body.text(),
+            "response_size": self.response_size,
+            "duration_ms": self.duration.as_millis() as u64,
+            "error": self.error,
+        })
+    }
+}
+
+/// A URL with the value of query parameter `name` masked
+fn mask_query_param(url: &str, name: &str) -> String {
+    let Ok(mut masked) = Url::parse(url) else {
+        return url.to_string();
+    };
+    let pairs: Vec<(String, String)> = masked
+        .query_pairs()
+        .map(|(key, value)| {
+            let value = if key == name {
+                MASK.to_string()
+            } else {
+                value.into_owned()
+            };
+            (key.into_owned(), value)
+        })
+        .collect();
+    if pairs.is_empty() {
+        return url.to_string();
+    }
+    masked.query_pairs_mut().clear().extend_pairs(pairs);
+    masked.to_string()
+}
+
+/// The entries as a HAR document
+pub fn to_har(entries: &[TrafficEntry]) -> Value {
+    json!({
+        "log": {
+            "version": "1.2",
+            "creator": { "name": "lazy-swagger-tui", "version": env!("CARGO_PKG_VERSION") },
+            "entries": entries.iter().map(TrafficEntry::to_har).collect::<Vec<_>>(),
+        }
+    })
+}
+
+/// The entries as JSON Lines
+pub fn to_json_lines(entries: &[TrafficEntry]) -> String {
+    entries
+        .iter()
+        .map(|entry| format!("{}\n", entry.to_json_line()))
+        .collect()
+}
+
+/// The entries as a Markdown report, one section per exchange
+pub fn to_markdown(entries: &[TrafficEntry]) -> String {
+    let total: Duration = entries.iter().map(|entry| entry.duration).sum();
+    let failed = entries
+        .iter()
+        .filter(|entry| entry.error.is_some() || entry.status >= 400)
+        .count();
+    let mut report = format!(
+        "# API session report\n\n- Requests: {} ({failed} failed)\n- Total time: {:.0} ms\n",
+        entries.len(),
+        total.as_secs_f64() * 1000.0
+    );
+    if let (Some(first), Some(last)) = (entries.first(), entries.last()) {
+        report.push_str(&format!(
+            "- Sent: {} to {}\n",
+            first.started.format("%Y-%m-%d %H:%M:%S"),
+            last.started.format("%H:%M:%S UTC")
+        ));
+    }
+    for (index, entry) in entries.iter().enumerate() {
+        report.push('\n');
+        report.push_str(&entry.to_markdown(index + 1));
+    }
+    report
+}
+
+/// A body as a fenced code block, cut after a few dozen lines
+fn markdown_code(body: &str, content_type: Option<&str>) -> String {
+    let body = redact(body);
+    let line_count = body.lines().count();
+    let mut shown: String = body
+        .lines()
+        .take(REPORT_BODY_LINES)
+        .collect::<Vec<_>>()
+        .join("\n");
+    let mut cut = line_count > REPORT_BODY_LINES;
+    if shown.chars().count() > REPORT_BODY_CHARS {
+        shown = shown.chars().take(REPORT_BODY_CHARS).collect();
+        cut = true;
+    }
+    let language = match content_type {
+        Some(content_type) if content_type.contains("json") => "json",
+        Some(content_type) if content_type.contains("xml") => "xml",
+        _ => "",
+    };
+    // A longer fence than any in the body keeps the block closed
+    let fence = if body.contains("```") { "````" } else { "```" };
+    let mut block = format!("{fence}{language}\n{shown}\n{fence}\n");
+    if cut {
+        block.push_str(&format!(
+            "\n(cut; the whole body has {line_count} lines, {} bytes)\n",
+            body.len()
+        ));
+    }
+    block
+}
+
+/// Write the log as HAR for a `.har` path, a Markdown report for `.md`, else as
+/// JSON Lines; returns the written path
+pub fn export(entries: &[TrafficEntry], path: &str) -> std::io::Result<PathBuf> {
+    let lower = path.to_lowercase();
+    let contents = if lower.ends_with(".har") {
+        serde_json::to_string_pretty(&to_har(entries)).unwrap_or_default()
+    } else if lower.ends_with(".md") {
+        to_markdown(entries)
+    } else {
+        to_json_lines(entries)
+    };
+    write_export(path, contents.as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::request::RequestBody;
+    use crate::types::SecurityScheme;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_traffic_entry_export() {
+        let request = OutgoingRequest {
+            method: "POST".to_string(),
+            url: "http://localhost:5000/users?notify=true".to_string(),
+            headers: vec![("Authorization".to_string(), "Bearer secret".to_string())],
+            body: Some(RequestBody {
+                content_type: "application/json".to_string(),
+                content: r#"{"name":"Ann"}"#.to_string(),
+            }),
+        };
+        let response = ApiResponse {
+            status: 201,
+            status_text: "Created".to_string(),
+            headers: HashMap::from([("content-type".to_string(), "application/json".to_string())]),
+            body: r#"{"id":1}"#.into(),
+            duration: Duration::from_millis(42),
+            is_error: false,
+            error_message: None,
+        };
+        let started = DateTime::parse_from_rfc3339("2024-05-01T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let entry = TrafficEntry::new(&request, &response, started, None);
+        assert_eq!(
+            entry.request_headers[0],
+            ("Authorization".to_string(), "Bearer ***".to_string())
+        );
+
+        let har = to_har(std::slice::from_ref(&entry));
+        let har_entry = &har["log"]["entries"][0];
+        assert_eq!(har_entry["startedDateTime"], "2024-05-01T12:00:00.000Z");
+        assert_eq!(har_entry["request"]["queryString"][0]["name"], "notify");
+        assert_eq!(
+            har_entry["request"]["postData"]["mimeType"],
+            "application/json"
+        );
+        assert_eq!(har_entry["response"]["status"], 201);
+        assert_eq!(har_entry["response"]["content"]["text"], r#"{"id":1}"#);
+
+        let report = to_markdown(std::slice::from_ref(&entry));
+        assert!(report.contains("- Requests: 1 (0 failed)\n- Total time: 42 ms"));
+        assert!(report.contains("## 1. POST /users — 201 Created (42 ms)"));
+        assert!(report.contains("| notify | true |"));
+        assert!(report.contains("**Response body**\n\n```json\n{\"id\":1}\n```"));
+
+        let long = (0..100)
+            .map(|n| n.to_string())
+            .collect::<Vec<_>>()
+            .join("\n");
+        let block = markdown_code(&long, None);
+        assert!(block.contains("\n39\n```"));
+        assert!(block.ends_with("(cut; the whole body has 100 lines, 289 bytes)\n"));
+
+        let lines = to_json_lines(&[entry.clone(), entry]);
+        assert_eq!(lines.lines().count(), 2);
+        let line: Value = serde_json::from_str(lines.lines().next().unwrap()).unwrap();
+        assert_eq!(line["duration_ms"], 42);
+        assert_eq!(line["request_headers"]["Authorization"], "Bearer ***");
+    }
+
+    #[test]
+    fn test_api_key_masked_in_har() {
+        let scheme: SecurityScheme =
+            serde_json::from_value(json!({ "type": "apiKey", "name": "key", "in": "query" }))
+                .unwrap();
+        let credential = scheme.credential("q-key-4f9a81c2");
+        let request = OutgoingRequest {
+            method: "GET".to_string(),
+            url: "http://localhost:5000/items?limit=5&key=q-key-4f9a81c2".to_string(),
+            headers: vec![("X-Custom-Key".to_string(), "h-key-77d0e5b3".to_string())],
+            body: None,
+        };
+        let entry = TrafficEntry::new(
+            &request,
+            &ApiResponse::error("timeout".to_string()),
+            Utc::now(),
+            Some(&credential),
+        );
+
+        let har = serde_json::to_string(&to_har(&[entry])).unwrap();
+        assert!(!har.contains("q-key-4f9a81c2"));
+        assert!(har.contains(r#"{"name":"key","value":"***"}"#));
+        assert!(har.contains(r#"{"name":"limit","value":"5"}"#));
+
+        let header_scheme: SecurityScheme = serde_json::from_value(
+            json!({ "type": "apiKey", "name": "X-Custom-Key", "in": "header" }),
+        )
+        .unwrap();
+        let entry = TrafficEntry::new(
+            &request,
+            &ApiResponse::error("timeout".to_string()),
+            Utc::now(),
+            Some(&header_scheme.credential("h-key-77d0e5b3")),
+        );
+        assert_eq!(entry.request_headers[0].1, "***");
+    }
+
+    #[test]
+    fn test_response_body_shared_and_capped() {
+        let request = OutgoingRequest {
+            method: "GET".to_string(),
+            url: "http://localhost:5000/files".to_string(),
+            headers: Vec::new(),
+            body: None,
+        };
+        let mut response = ApiResponse::error(String::new());
+        response.body = "small".into();
+        let entry = TrafficEntry::new(&request, &response, Utc::now(), None);
+        assert!(std::ptr::eq(
+            entry.response_body.bytes(),
+            response.body.bytes()
+        ));
+
+        // Cut before the character that crosses the limit
+        response.body = format!("a{}", "é".repeat(MAX_TRAFFIC_BODY_BYTES)).into();
+        let entry = TrafficEntry::new(&request, &response, Utc::now(), None);
+        assert_eq!(entry.response_size, 2 * MAX_TRAFFIC_BODY_BYTES + 1);
+        assert_eq!(entry.response_body.len(), MAX_TRAFFIC_BODY_BYTES - 1);
+        let content = &to_har(&[entry])["log"]["entries"][0]["response"]["content"];
+        assert_eq!(content["size"], 2 * MAX_TRAFFIC_BODY_BYTES + 1);
+        assert_eq!(
+            content["comment"],
+            format!(
+                "truncated to the first {} bytes",
+                MAX_TRAFFIC_BODY_BYTES - 1
+            )
+        );
+    }
+}
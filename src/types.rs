@@ -1,17 +1,91 @@
-use serde::Deserialize;
+use crate::config::PanelArrangement;
+use crate::datetime::{self, DateFormat};
+use crate::redact::register_secret;
+use crate::schema;
+use crate::servers::SpecServer;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::time::Duration;
+use std::fmt;
+use std::ops::Deref;
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct ApiEndpoint {
     pub method: String,
     pub path: String,
     pub summary: Option<String>,
+    /// Longer explanation, often markdown
+    pub description: Option<String>,
     pub tags: Vec<String>,
     pub parameters: Vec<ApiParameter>,
+    pub request_body: Option<RequestBodySpec>,
+    pub response_example: Option<ResponseExample>,
+    /// Responses the spec declares, by status code
+    pub responses: Vec<DeclaredResponse>,
+    /// Marked `deprecated` by the spec: still callable, but on its way out
+    pub deprecated: bool,
+    /// Security requirements of the operation, else the spec-wide ones; `None` when the spec is silent
+    pub security: Option<Vec<SecurityRequirement>>,
+    /// Vendor extensions (`x-*` fields) of the operation, sorted by name
+    pub extensions: Vec<(String, serde_json::Value)>,
+    /// Set for callbacks and webhooks: requests the API sends, listed but not executable
+    pub event: Option<EventSource>,
+    /// Service the endpoint comes from when several specs are merged
+    pub service: Option<String>,
+    /// Base URL of that service, used instead of the configured one
+    pub base_url: Option<String>,
+}
+
+/// Group of the endpoints panel listing callbacks
+pub const CALLBACKS_GROUP: &str = "Callbacks";
+
+/// Group of the endpoints panel listing webhooks
+pub const WEBHOOKS_GROUP: &str = "Webhooks";
+
+/// Where a callback or webhook operation is declared
+#[derive(Debug, Clone, PartialEq)]
+pub enum EventSource {
+    /// Callback `name` of the operation with key `operation`
+    Callback { name: String, operation: String },
+    /// Top-level webhook `name` (OpenAPI 3.1)
+    Webhook { name: String },
+}
+
+impl EventSource {
+    /// Section of the endpoints panel this event is listed in
+    pub fn group(&self) -> &'static str {
+        match self {
+            Self::Callback { .. } => CALLBACKS_GROUP,
+            Self::Webhook { .. } => WEBHOOKS_GROUP,
+        }
+    }
+
+    /// Short description, e.g. "callback onPaid of POST /orders"
+    pub fn label(&self) -> String {
+        match self {
+            Self::Callback { name, operation } => format!("callback {name} of {operation}"),
+            Self::Webhook { name } => format!("webhook {name}"),
+        }
+    }
 }
 
 impl ApiEndpoint {
+    /// Stable identity of this endpoint (method + path), e.g. "GET /users/{id}"
+    ///
+    /// Endpoints of merged specs are prefixed with their service, e.g. "orders: GET /orders".
+    pub fn key(&self) -> String {
+        match &self.service {
+            Some(service) => format!("{service}: {} {}", self.method, self.path),
+            None => format!("{} {}", self.method, self.path),
+        }
+    }
+
+    /// Base URL this endpoint's requests go to: its service's, else `default`
+    pub fn request_base_url(&self, default: Option<String>) -> Option<String> {
+        self.base_url.clone().or(default)
+    }
+
     /// Get all path parameters for this endpoint
     pub fn path_params(&self) -> Vec<&ApiParameter> {
         self.parameters
@@ -28,6 +102,31 @@ impl ApiEndpoint {
             .collect()
     }
 
+    /// Get all header parameters for this endpoint
+    pub fn header_params(&self) -> Vec<&ApiParameter> {
+        self.parameters
+            .iter()
+            .filter(|p| p.location == "header")
+            .collect()
+    }
+
+    /// Parameters edited in the Request tab, in its order: path, then query, then header
+    pub fn request_params(&self) -> Vec<(&ApiParameter, ParameterType)> {
+        let path = self
+            .path_params()
+            .into_iter()
+            .map(|p| (p, ParameterType::Path));
+        let query = self
+            .query_params()
+            .into_iter()
+            .map(|p| (p, ParameterType::Query));
+        let header = self
+            .header_params()
+            .into_iter()
+            .map(|p| (p, ParameterType::Header));
+        path.chain(query).chain(header).collect()
+    }
+
     /// Check if all required path parameters have values in the given config
     pub fn has_all_required_path_params(&self, config: &RequestConfig) -> bool {
         self.path_params().iter().all(|param| {
@@ -54,6 +153,168 @@ impl ApiEndpoint {
             .collect()
     }
 
+    /// Get list of required query parameter names without a value
+    pub fn missing_required_query_params(&self, config: Option<&RequestConfig>) -> Vec<String> {
+        Self::missing_required(self.query_params(), config)
+    }
+
+    /// Get list of required header parameter names without a value
+    pub fn missing_required_header_params(&self, config: Option<&RequestConfig>) -> Vec<String> {
+        Self::missing_required(self.header_params(), config)
+    }
+
+    fn missing_required(params: Vec<&ApiParameter>, config: Option<&RequestConfig>) -> Vec<String> {
+        params
+            .iter()
+            .filter(|param| param.is_required())
+            .filter(|param| {
+                config
+                    .and_then(|c| c.get_param_value(&param.name))
+                    .map(|v| v.is_empty())
+                    .unwrap_or(true)
+            })
+            .map(|param| param.name.clone())
+            .collect()
+    }
+
+    /// Media type the body is sent as: the one picked in the Request tab, else the spec's first
+    pub fn body_content_type(&self, config: Option<&RequestConfig>) -> String {
+        let declared = self
+            .request_body
+            .as_ref()
+            .map(RequestBodySpec::content_types)
+            .unwrap_or_default();
+        config
+            .and_then(|c| c.content_type.as_deref())
+            .filter(|picked| declared.contains(picked))
+            .or(declared.first().copied())
+            .unwrap_or("application/json")
+            .to_string()
+    }
+
+    /// Whether the spec marks the request body as required
+    pub fn body_required(&self) -> bool {
+        self.request_body
+            .as_ref()
+            .and_then(|body| body.required)
+            .unwrap_or(false)
+    }
+
+    /// Collect every reason the request can't be sent yet (empty if it can)
+    pub fn validation_errors(&self, config: Option<&RequestConfig>) -> Vec<String> {
+        let mut errors = Vec::new();
+
+        let missing_path: Vec<String> = match config {
+            Some(config) if self.has_all_required_path_params(config) => Vec::new(),
+            Some(config) => self.missing_path_params(config),
+            None => self.path_params().iter().map(|p| p.name.clone()).collect(),
+        };
+        if !missing_path.is_empty() {
+            errors.push(format!(
+                "Missing required path parameter(s): {}",
+                missing_path.join(", ")
+            ));
+        }
+
+        let missing_query = self.missing_required_query_params(config);
+        if !missing_query.is_empty() {
+            errors.push(format!(
+                "Missing required query parameter(s): {}",
+                missing_query.join(", ")
+            ));
+        }
+
+        let missing_headers = self.missing_required_header_params(config);
+        if !missing_headers.is_empty() {
+            errors.push(format!(
+                "Missing required header(s): {}",
+                missing_headers.join(", ")
+            ));
+        }
+
+        for (param, _) in self.request_params() {
+            let value = config
+                .and_then(|c| c.get_param_value(&param.name))
+                .unwrap_or("");
+            if let Some(e) = param.value_error(value) {
+                errors.push(format!("Invalid value for '{}': {e}", param.name));
+            }
+        }
+
+        if self.supports_body() {
+            let body = config
+                .and_then(|c| c.body.as_deref())
+                .filter(|body| !body.trim().is_empty());
+            match body {
+                None if self.body_required() => {
+                    errors.push("Missing required request body (press 'b' to edit)".to_string());
+                }
+                Some(body) if edited_as_json(&self.body_content_type(config)) => {
+                    if let Err(e) = serde_json::from_str::<serde_json::Value>(body) {
+                        errors.push(format!("Request body is not valid JSON: {e}"));
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        errors
+    }
+
+    /// Whether the endpoint can be called without auth, `None` when the spec doesn't say
+    pub fn allows_anonymous(&self) -> Option<bool> {
+        self.security.as_ref().map(|requirements| {
+            requirements.is_empty() || requirements.iter().any(|r| r.is_empty())
+        })
+    }
+
+    /// Accepted ways to authenticate, e.g. "oauth2 (read, write) or apiKey"
+    pub fn security_label(&self, with_scopes: bool) -> String {
+        let mut alternatives: Vec<String> = self
+            .security
+            .iter()
+            .flatten()
+            .filter(|requirement| !requirement.is_empty())
+            .map(|requirement| {
+                let mut schemes: Vec<(&String, &Vec<String>)> = requirement.iter().collect();
+                schemes.sort();
+                schemes
+                    .into_iter()
+                    .map(|(scheme, scopes)| {
+                        if with_scopes && !scopes.is_empty() {
+                            format!("{scheme} ({})", scopes.join(", "))
+                        } else {
+                            scheme.clone()
+                        }
+                    })
+                    .collect::<Vec<_>>()
+                    .join(" + ")
+            })
+            .collect();
+        alternatives.dedup();
+        alternatives.join(" or ")
+    }
+
+    /// Names of the security schemes the endpoint accepts, in the order of its requirements
+    pub fn security_scheme_names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = Vec::new();
+        for requirement in self.security.iter().flatten() {
+            let mut schemes: Vec<&str> = requirement.keys().map(String::as_str).collect();
+            schemes.sort();
+            for scheme in schemes {
+                if !names.contains(&scheme) {
+                    names.push(scheme);
+                }
+            }
+        }
+        names
+    }
+
+    /// Get the parameter at a Request tab index (path params first, then query and header params)
+    pub fn param_at(&self, index: usize) -> Option<(&ApiParameter, ParameterType)> {
+        self.request_params().into_iter().nth(index)
+    }
+
     /// Check if this endpoint supports request body (POST/PUT/PATCH)
     pub fn supports_body(&self) -> bool {
         matches!(
@@ -63,7 +324,7 @@ impl ApiEndpoint {
     }
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Default, Deserialize)]
 pub struct ApiParameter {
     pub name: String,
 
@@ -76,9 +337,122 @@ pub struct ApiParameter {
 
     #[allow(dead_code)]
     pub description: Option<String>,
+
+    pub example: Option<serde_json::Value>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+impl ApiParameter {
+    /// Type info string for display (e.g., "integer/int32" or "boolean")
+    pub fn type_info(&self) -> String {
+        if let Some(schema) = self.schema.as_ref().filter(|s| !s.enum_values.is_empty()) {
+            schema::enum_summary(&schema.enum_values)
+        } else if let Some(schema) = &self.schema {
+            let type_str = schema.param_type.as_deref().unwrap_or("unknown");
+            if let Some(format) = &schema.format {
+                format!("{type_str}/{format}")
+            } else {
+                type_str.to_string()
+            }
+        } else {
+            "unknown".to_string()
+        }
+    }
+
+    /// Whether the spec marks this parameter as required
+    pub fn is_required(&self) -> bool {
+        self.required.unwrap_or(false)
+    }
+
+    /// Date format declared by the schema (`date` or `date-time`)
+    pub fn date_format(&self) -> Option<DateFormat> {
+        self.schema
+            .as_ref()
+            .and_then(|schema| schema.format.as_deref())
+            .and_then(DateFormat::from_schema_format)
+    }
+
+    /// Whether the schema types this parameter as a boolean
+    pub fn is_boolean(&self) -> bool {
+        self.schema
+            .as_ref()
+            .and_then(|schema| schema.param_type.as_deref())
+            == Some("boolean")
+    }
+
+    /// The schema's `enum` values as they are sent, empty when any value goes
+    pub fn allowed_values(&self) -> Vec<String> {
+        self.schema
+            .iter()
+            .flat_map(|schema| &schema.enum_values)
+            .map(|value| match value {
+                serde_json::Value::String(text) => text.clone(),
+                other => other.to_string(),
+            })
+            .collect()
+    }
+
+    /// Why a value doesn't fit the schema: not an allowed value, its type, a uuid or
+    /// date format, or the bounds
+    ///
+    /// Empty values pass; missing required values are reported separately.
+    pub fn value_error(&self, value: &str) -> Option<String> {
+        let schema = self.schema.as_ref()?;
+        if value.is_empty() {
+            return None;
+        }
+        let allowed = self.allowed_values();
+        if !allowed.is_empty() && !allowed.iter().any(|allowed| allowed == value) {
+            return Some(format!("expected one of {}", allowed.join(", ")));
+        }
+        if let Some(format) = self.date_format() {
+            return datetime::validate(value, format).err();
+        }
+
+        let number = match schema.param_type.as_deref() {
+            Some("integer") => {
+                let Ok(number) = value.parse::<i64>() else {
+                    return Some("expected an integer".to_string());
+                };
+                if schema.format.as_deref() == Some("int32") && i32::try_from(number).is_err() {
+                    return Some("out of range for int32".to_string());
+                }
+                Some(number as f64)
+            }
+            Some("number") => match value.parse::<f64>() {
+                Ok(number) if number.is_finite() => Some(number),
+                _ => return Some("expected a number".to_string()),
+            },
+            Some("boolean") if value != "true" && value != "false" => {
+                return Some("expected true or false".to_string());
+            }
+            _ => None,
+        };
+        if let Some(number) = number {
+            if let Some(minimum) = schema.minimum.filter(|minimum| number < *minimum) {
+                return Some(format!("must be at least {minimum}"));
+            }
+            if let Some(maximum) = schema.maximum.filter(|maximum| number > *maximum) {
+                return Some(format!("must be at most {maximum}"));
+            }
+        }
+
+        if schema.format.as_deref() == Some("uuid") && !is_uuid(value) {
+            return Some("expected a UUID like 123e4567-e89b-12d3-a456-426614174000".to_string());
+        }
+        None
+    }
+
+    /// Value suggested by the spec: schema default, then parameter or schema example
+    pub fn spec_value(&self) -> Option<&serde_json::Value> {
+        let schema = self.schema.as_ref();
+        schema
+            .and_then(|schema| schema.default.as_ref())
+            .or(self.example.as_ref())
+            .or(schema.and_then(|schema| schema.example.as_ref()))
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
 pub struct ParameterSchema {
     #[serde(rename = "type")]
     pub param_type: Option<String>, // "string", "integer", "boolean"
@@ -86,27 +460,159 @@ pub struct ParameterSchema {
     pub format: Option<String>, // "int32", "int64", "date-time", etc.
 
     pub default: Option<serde_json::Value>,
+
+    pub example: Option<serde_json::Value>,
+
+    pub minimum: Option<f64>,
+
+    pub maximum: Option<f64>,
+
+    /// Allowed values, picked from a list instead of typed
+    #[serde(default, rename = "enum")]
+    pub enum_values: Vec<serde_json::Value>,
 }
 
-/// Distinguishes between path and query parameters
-#[derive(Debug, Clone, PartialEq)]
+/// Distinguishes between path, query and header parameters
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum ParameterType {
     Path,
     Query,
+    Header,
+}
+
+impl ParameterType {
+    /// The type of a spec parameter's `in`; cookie parameters are not edited
+    pub fn from_location(location: &str) -> Option<Self> {
+        match location {
+            "path" => Some(Self::Path),
+            "query" => Some(Self::Query),
+            "header" => Some(Self::Header),
+            _ => None,
+        }
+    }
 }
 
 /// Represents a parameter value configured by the user
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Parameter {
     pub name: String,
     pub value: String,
     pub param_type: ParameterType,
 }
 
-#[derive(Debug, Clone, Default)]
+/// A single input of the bulk parameter edit form
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ParamFormField {
+    pub name: String,
+    pub param_type: ParameterType,
+    pub value: String,
+    pub required: bool,
+    pub type_info: String,
+}
+
+impl ParamFormField {
+    /// Boolean fields are cycled rather than typed into
+    pub fn is_boolean(&self) -> bool {
+        self.type_info == "boolean"
+    }
+}
+
+/// Whether text is a UUID in its canonical hyphenated form
+fn is_uuid(text: &str) -> bool {
+    text.len() == 36
+        && text.char_indices().all(|(idx, c)| match idx {
+            8 | 13 | 18 | 23 => c == '-',
+            _ => c.is_ascii_hexdigit(),
+        })
+}
+
+/// Next value when cycling a boolean parameter: unset → true → false → unset
+pub fn cycle_bool_value(current: &str) -> &'static str {
+    match current {
+        "true" => "false",
+        "false" => "",
+        _ => "true",
+    }
+}
+
+/// Next value when cycling an enum parameter: unset → each allowed value → unset
+pub fn cycle_enum_value(allowed: &[String], current: &str) -> String {
+    if current.is_empty() {
+        return allowed.first().cloned().unwrap_or_default();
+    }
+    match allowed.iter().position(|value| value == current) {
+        Some(index) => allowed.get(index + 1).cloned().unwrap_or_default(),
+        None => allowed.first().cloned().unwrap_or_default(),
+    }
+}
+
+/// State of the bulk parameter edit form for one endpoint
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ParamForm {
+    pub endpoint_key: String,
+    pub fields: Vec<ParamFormField>,
+    pub active_field: usize,
+}
+
+impl ParamForm {
+    /// Build a form for every path, query and header parameter of an endpoint
+    pub fn for_endpoint(endpoint: &ApiEndpoint, config: Option<&RequestConfig>) -> Self {
+        let fields = endpoint
+            .request_params()
+            .into_iter()
+            .map(|(param, param_type)| ParamFormField {
+                name: param.name.clone(),
+                param_type,
+                value: config
+                    .and_then(|c| c.get_param_value(&param.name))
+                    .unwrap_or_default()
+                    .to_string(),
+                required: param.is_required(),
+                type_info: param.type_info(),
+            })
+            .collect();
+
+        Self {
+            endpoint_key: endpoint.key(),
+            fields,
+            active_field: 0,
+        }
+    }
+
+    /// Get the field that currently has focus
+    pub fn active_field_mut(&mut self) -> Option<&mut ParamFormField> {
+        self.fields.get_mut(self.active_field)
+    }
+
+    /// Move focus to the next field (wrapping)
+    pub fn next_field(&mut self) {
+        if !self.fields.is_empty() {
+            self.active_field = (self.active_field + 1) % self.fields.len();
+        }
+    }
+
+    /// Move focus to the previous field (wrapping)
+    pub fn prev_field(&mut self) {
+        if !self.fields.is_empty() {
+            self.active_field = (self.active_field + self.fields.len() - 1) % self.fields.len();
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct RequestConfig {
     pub parameters: Vec<Parameter>,
     pub body: Option<String>,
+    /// Media type picked for the body; the spec's first one when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+}
+
+/// A named snapshot of an endpoint's request config (e.g. "admin user")
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RequestVariant {
+    pub name: String,
+    pub config: RequestConfig,
 }
 
 impl RequestConfig {
@@ -145,6 +651,15 @@ impl RequestConfig {
             .filter(|p| p.param_type == ParameterType::Query)
     }
 
+    /// Header parameters with a value, to send along with the request
+    pub fn header_values(&self) -> Vec<(String, String)> {
+        self.parameters
+            .iter()
+            .filter(|p| p.param_type == ParameterType::Header && !p.value.is_empty())
+            .map(|p| (p.name.clone(), p.value.clone()))
+            .collect()
+    }
+
     /// Convert path parameters to HashMap for URL building
     pub fn path_params_map(&self) -> HashMap<String, String> {
         self.path_params()
@@ -172,8 +687,8 @@ pub struct ApiResponse {
     /// Response headers as key-value pairs (keys normalized to lowercase)
     pub headers: HashMap<String, String>,
 
-    /// Raw response body (could be JSON, HTML, plain text, etc.)
-    pub body: String,
+    /// Response body as received, read as text (JSON, HTML, ...) through `Deref`
+    pub body: ResponseBody,
 
     /// Time taken to complete the request
     pub duration: Duration,
@@ -186,6 +701,14 @@ pub struct ApiResponse {
     pub error_message: Option<String>,
 }
 
+/// Status and duration of a past response, kept for per-endpoint history
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ResponseSample {
+    /// HTTP status code (0 for network errors)
+    pub status: u16,
+    pub duration: Duration,
+}
+
 impl ApiResponse {
     /// Creates an error response with the given error message
     pub fn error(error_message: String) -> Self {
@@ -193,7 +716,7 @@ impl ApiResponse {
             status: 0,
             status_text: String::new(),
             headers: HashMap::new(),
-            body: String::new(),
+            body: ResponseBody::default(),
             duration: Duration::from_secs(0),
             is_error: true,
             error_message: Some(error_message),
@@ -201,9 +724,286 @@ impl ApiResponse {
     }
 }
 
-#[derive(Deserialize)]
+/// Bytes of a response body, shared by copies of the response and the traffic log
+///
+/// Derefs to the body as text. A body that isn't valid UTF-8, e.g. an image,
+/// is only converted (lossily) when its text is first asked for.
+#[derive(Clone, Default)]
+pub struct ResponseBody(Arc<BodyBuffer>);
+
+#[derive(Default)]
+struct BodyBuffer {
+    bytes: Vec<u8>,
+    utf8: bool,
+    lossy: OnceLock<String>,
+}
+
+impl ResponseBody {
+    /// The body as received
+    pub fn bytes(&self) -> &[u8] {
+        &self.0.bytes
+    }
+
+    pub fn text(&self) -> &str {
+        if self.0.utf8 {
+            // Checked when the buffer was created
+            std::str::from_utf8(&self.0.bytes).unwrap_or_default()
+        } else {
+            self.0
+                .lossy
+                .get_or_init(|| String::from_utf8_lossy(&self.0.bytes).into_owned())
+        }
+    }
+
+    /// The first `max_len` bytes, sharing the buffer when the body is no longer
+    pub fn truncated(&self, max_len: usize) -> Self {
+        if self.0.bytes.len() <= max_len {
+            return self.clone();
+        }
+        let mut end = max_len;
+        if self.0.utf8 {
+            while !self.text().is_char_boundary(end) {
+                end -= 1;
+            }
+        }
+        Self::from(self.0.bytes[..end].to_vec())
+    }
+}
+
+impl From<Vec<u8>> for ResponseBody {
+    fn from(bytes: Vec<u8>) -> Self {
+        let utf8 = std::str::from_utf8(&bytes).is_ok();
+        Self(Arc::new(BodyBuffer {
+            bytes,
+            utf8,
+            lossy: OnceLock::new(),
+        }))
+    }
+}
+
+impl From<String> for ResponseBody {
+    fn from(text: String) -> Self {
+        Self(Arc::new(BodyBuffer {
+            bytes: text.into_bytes(),
+            utf8: true,
+            lossy: OnceLock::new(),
+        }))
+    }
+}
+
+impl From<&str> for ResponseBody {
+    fn from(text: &str) -> Self {
+        Self::from(text.to_string())
+    }
+}
+
+impl Deref for ResponseBody {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.text()
+    }
+}
+
+impl fmt::Debug for ResponseBody {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.text(), f)
+    }
+}
+
+impl PartialEq for ResponseBody {
+    fn eq(&self, other: &Self) -> bool {
+        self.bytes() == other.bytes()
+    }
+}
+
+impl PartialEq<&str> for ResponseBody {
+    fn eq(&self, other: &&str) -> bool {
+        self.text() == *other
+    }
+}
+
+#[derive(Default, Deserialize)]
 pub struct SwaggerSpec {
+    #[serde(default)]
+    pub info: SpecInfo,
+
     pub paths: HashMap<String, PathItem>,
+
+    /// OpenAPI 3.1 webhooks: requests the API sends, keyed by name
+    #[serde(default)]
+    pub webhooks: HashMap<String, PathItem>,
+
+    /// Base URLs the API is served from, possibly templated
+    #[serde(default)]
+    pub servers: Vec<SpecServer>,
+
+    /// OpenAPI 3 reusable components (`#/components/schemas/...`)
+    pub components: Option<Components>,
+
+    /// Swagger 2 schema definitions (`#/definitions/...`)
+    #[serde(default)]
+    pub definitions: HashMap<String, serde_json::Value>,
+
+    /// Security requirements for operations that don't declare their own
+    pub security: Option<Vec<SecurityRequirement>>,
+}
+
+/// The spec's `info` block: which API and version is loaded
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct SpecInfo {
+    pub title: Option<String>,
+    pub version: Option<String>,
+    pub description: Option<String>,
+    #[serde(rename = "termsOfService")]
+    pub terms_of_service: Option<String>,
+    pub contact: Option<SpecContact>,
+    pub license: Option<SpecLicense>,
+}
+
+impl SpecInfo {
+    /// Title with version, e.g. "Petstore v1.0.2"
+    pub fn heading(&self) -> Option<String> {
+        let title = self.title.as_deref().filter(|t| !t.trim().is_empty())?;
+        Some(match &self.version {
+            Some(version) if version.starts_with(['v', 'V']) => format!("{title} {version}"),
+            Some(version) => format!("{title} v{version}"),
+            None => title.to_string(),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct SpecContact {
+    pub name: Option<String>,
+    pub email: Option<String>,
+    pub url: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct SpecLicense {
+    pub name: Option<String>,
+    pub url: Option<String>,
+}
+
+/// Schemes that must all be satisfied, each with the scopes it needs
+///
+/// An endpoint accepts any one of its requirements; an empty one means no auth.
+pub type SecurityRequirement = HashMap<String, Vec<String>>;
+
+#[derive(Default, Deserialize)]
+pub struct Components {
+    #[serde(default)]
+    pub schemas: HashMap<String, serde_json::Value>,
+
+    /// Ways to authenticate, by the names security requirements use
+    #[serde(default, rename = "securitySchemes")]
+    pub security_schemes: HashMap<String, SecurityScheme>,
+}
+
+/// How an API expects to be authenticated, from `components.securitySchemes`
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct SecurityScheme {
+    /// `http`, `apiKey`, `oauth2`, `openIdConnect` or `mutualTLS`
+    #[serde(rename = "type", default)]
+    pub kind: String,
+    /// HTTP authorization scheme of `http` schemes, e.g. `bearer` or `basic`
+    pub scheme: Option<String>,
+    /// Header, query parameter or cookie an `apiKey` is sent in
+    pub name: Option<String>,
+    #[serde(rename = "in")]
+    pub location: Option<String>,
+    pub description: Option<String>,
+}
+
+/// Where a credential goes on a request
+#[derive(Debug, Clone, PartialEq)]
+pub enum Credential {
+    Header(String, String),
+    Query(String, String),
+}
+
+impl SecurityScheme {
+    /// An `http` scheme of the given authorization scheme, e.g. `basic` (case-insensitive)
+    pub fn is_http(&self, scheme: &str) -> bool {
+        self.kind == "http"
+            && self
+                .scheme
+                .as_deref()
+                .is_some_and(|s| s.eq_ignore_ascii_case(scheme))
+    }
+
+    /// Short description, e.g. "API key in header X-API-Key" or "HTTP basic auth"
+    pub fn describe(&self) -> String {
+        match self.kind.as_str() {
+            "apiKey" => format!(
+                "API key in {} {}",
+                self.location.as_deref().unwrap_or("header"),
+                self.name.as_deref().unwrap_or("?")
+            ),
+            "http" => format!(
+                "HTTP {} auth",
+                self.scheme.as_deref().unwrap_or("bearer").to_lowercase()
+            ),
+            "oauth2" => "OAuth2 access token".to_string(),
+            "openIdConnect" => "OpenID Connect token".to_string(),
+            "mutualTLS" => "client certificate (not supported)".to_string(),
+            other => format!("{other} (not supported)"),
+        }
+    }
+
+    /// Label of the auth modal's input for this scheme
+    pub fn prompt(&self) -> &'static str {
+        match self.kind.as_str() {
+            "apiKey" => "API key:",
+            "http" if self.is_http("basic") => "Username and password, as user:password:",
+            "http" if !self.is_http("bearer") => "Credentials:",
+            _ => "Token:",
+        }
+    }
+
+    /// Where `secret` goes on a request; tokens of OAuth2 and unknown kinds are sent as bearer tokens
+    ///
+    /// The secret and the value sent are registered for redaction, wherever they end up.
+    pub fn credential(&self, secret: &str) -> Credential {
+        let credential = self.place_secret(secret);
+        register_secret(secret);
+        match &credential {
+            Credential::Header(_, value) | Credential::Query(_, value) => register_secret(value),
+        }
+        credential
+    }
+
+    fn place_secret(&self, secret: &str) -> Credential {
+        use base64::Engine;
+
+        match (self.kind.as_str(), self.location.as_deref()) {
+            ("apiKey", location) => {
+                let name = self.name.clone().unwrap_or_default();
+                match location {
+                    Some("query") => Credential::Query(name, secret.to_string()),
+                    Some("cookie") => {
+                        Credential::Header("Cookie".to_string(), format!("{name}={secret}"))
+                    }
+                    _ => Credential::Header(name, secret.to_string()),
+                }
+            }
+            ("http", _) if self.is_http("basic") => {
+                let encoded = base64::engine::general_purpose::STANDARD.encode(secret);
+                Credential::Header("Authorization".to_string(), format!("Basic {encoded}"))
+            }
+            ("http", _) if !self.is_http("bearer") => {
+                let scheme = self.scheme.clone().unwrap_or_default();
+                Credential::Header("Authorization".to_string(), format!("{scheme} {secret}"))
+            }
+            _ => bearer(secret),
+        }
+    }
+}
+
+/// An `Authorization: Bearer` header, how a token is sent when no scheme says otherwise
+pub fn bearer(token: &str) -> Credential {
+    Credential::Header("Authorization".to_string(), format!("Bearer {token}"))
 }
 
 #[derive(Deserialize)]
@@ -213,13 +1013,340 @@ pub struct PathItem {
     pub put: Option<Operation>,
     pub delete: Option<Operation>,
     pub patch: Option<Operation>,
+    /// Parameters shared by all operations of the path
+    #[serde(default)]
+    pub parameters: Vec<ApiParameter>,
+}
+
+impl PathItem {
+    /// Operations of the path with their HTTP methods
+    pub fn operations(&self) -> Vec<(&'static str, &Operation)> {
+        [
+            ("GET", &self.get),
+            ("POST", &self.post),
+            ("PUT", &self.put),
+            ("DELETE", &self.delete),
+            ("PATCH", &self.patch),
+        ]
+        .into_iter()
+        .filter_map(|(method, op)| Some((method, op.as_ref()?)))
+        .collect()
+    }
 }
 
 #[derive(Deserialize)]
 pub struct Operation {
     pub summary: Option<String>,
+    pub description: Option<String>,
     pub tags: Option<Vec<String>>,
     pub parameters: Option<Vec<ApiParameter>>,
+    #[serde(rename = "requestBody")]
+    pub request_body: Option<RequestBodySpec>,
+    #[serde(default)]
+    pub responses: HashMap<String, ResponseSpec>,
+    pub security: Option<Vec<SecurityRequirement>>,
+    #[serde(default)]
+    pub deprecated: bool,
+    /// Requests the API makes back to the client: callback name → URL expression → path item
+    ///
+    /// Callbacks that don't read as path items are skipped; they only add to what is listed.
+    #[serde(default, deserialize_with = "lenient_callbacks")]
+    pub callbacks: HashMap<String, HashMap<String, PathItem>>,
+
+    /// Fields not modeled above, including vendor extensions
+    #[serde(flatten)]
+    pub other: HashMap<String, serde_json::Value>,
+}
+
+/// Callbacks that read as path items, skipping the rest instead of failing the spec
+fn lenient_callbacks<'de, D>(
+    deserializer: D,
+) -> Result<HashMap<String, HashMap<String, PathItem>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let callbacks = HashMap::<String, serde_json::Value>::deserialize(deserializer)?;
+    Ok(callbacks
+        .into_iter()
+        .filter_map(|(name, callback)| Some((name, serde_json::from_value(callback).ok()?)))
+        .collect())
+}
+
+impl Operation {
+    /// Request body of this operation, from OpenAPI 3 `requestBody` or a Swagger 2 `in: body` parameter
+    pub fn body_spec(&self) -> Option<RequestBodySpec> {
+        self.request_body.clone().or_else(|| {
+            self.parameters
+                .iter()
+                .flatten()
+                .find(|param| param.location == "body")
+                .map(|param| RequestBodySpec {
+                    required: param.required,
+                    description: param.description.clone(),
+                    content: HashMap::new(),
+                })
+        })
+    }
+
+    /// Vendor extensions (`x-*` fields), sorted by name
+    pub fn extensions(&self) -> Vec<(String, serde_json::Value)> {
+        let mut extensions: Vec<(String, serde_json::Value)> = self
+            .other
+            .iter()
+            .filter(|(name, _)| name.starts_with("x-"))
+            .map(|(name, value)| (name.clone(), value.clone()))
+            .collect();
+        extensions.sort_by(|a, b| a.0.cmp(&b.0));
+        extensions
+    }
+
+    /// First example response in the spec, success statuses first
+    pub fn response_example(&self) -> Option<ResponseExample> {
+        let mut statuses: Vec<&String> = self.responses.keys().collect();
+        statuses.sort_by_key(|status| {
+            (
+                !status.starts_with('2'),
+                status.as_str() == "default",
+                *status,
+            )
+        });
+
+        statuses.into_iter().find_map(|status| {
+            let response = &self.responses[status];
+            let mut media_types: Vec<&String> = response.content.keys().collect();
+            media_types.sort_by_key(|media_type| (!is_json_media_type(media_type), *media_type));
+
+            let openapi3 = media_types.into_iter().find_map(|media_type| {
+                response.content[media_type]
+                    .example_value()
+                    .map(|body| (Some(media_type.clone()), body))
+            });
+            // Swagger 2 keys examples by mime type directly on the response
+            let swagger2 = || {
+                response
+                    .examples
+                    .iter()
+                    .min_by_key(|(media_type, _)| (!is_json_media_type(media_type), *media_type))
+                    .map(|(media_type, body)| (Some(media_type.clone()), body.clone()))
+            };
+
+            openapi3
+                .or_else(swagger2)
+                .map(|(media_type, body)| ResponseExample {
+                    status: status.clone(),
+                    media_type,
+                    body,
+                })
+        })
+    }
+
+    /// Declared responses in status order, `default` last, each in its preferred media type
+    pub fn declared_responses(&self) -> Vec<DeclaredResponse> {
+        let mut responses: Vec<DeclaredResponse> = self
+            .responses
+            .iter()
+            .map(|(status, response)| {
+                let mut media_types: Vec<&String> = response.content.keys().collect();
+                media_types
+                    .sort_by_key(|media_type| (!is_json_media_type(media_type), *media_type));
+                let media_type = media_types.first().copied();
+                let media = media_type.map(|media_type| &response.content[media_type]);
+                DeclaredResponse {
+                    status: status.clone(),
+                    description: response.description.clone().filter(|d| !d.is_empty()),
+                    media_type: media_type.cloned(),
+                    schema: media.and_then(|media| media.schema.clone()),
+                    model: None,
+                    example: media.and_then(MediaTypeSpec::example_value),
+                }
+            })
+            .collect();
+        responses.sort_by(|a, b| {
+            (a.status == "default", &a.status).cmp(&(b.status == "default", &b.status))
+        });
+        responses
+    }
+}
+
+/// A response declared by the spec for one status code
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ResponseSpec {
+    pub description: Option<String>,
+
+    #[serde(default)]
+    pub content: HashMap<String, MediaTypeSpec>,
+
+    /// Swagger 2 examples per mime type
+    #[serde(default)]
+    pub examples: HashMap<String, serde_json::Value>,
+}
+
+/// A response the spec declares for an operation, shown in the Endpoint tab
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeclaredResponse {
+    /// Status code, a range such as `4XX`, or `default`
+    pub status: String,
+    pub description: Option<String>,
+    /// Media type the schema and example are taken from, JSON preferred
+    pub media_type: Option<String>,
+    /// Body schema, `$ref`s resolved after parsing
+    pub schema: Option<serde_json::Value>,
+    /// The schema as outlined, built after parsing
+    pub model: Option<SchemaModel>,
+    pub example: Option<serde_json::Value>,
+}
+
+/// A body or response schema as the Request and Endpoint tabs outline it
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SchemaModel {
+    /// Name of the component schema it was referenced as, e.g. `Pet`
+    pub ref_name: Option<String>,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub shape: SchemaShape,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum SchemaShape {
+    /// Properties by name
+    Object {
+        properties: Vec<(String, SchemaModel)>,
+        required: Vec<String>,
+    },
+    Array(Box<SchemaModel>),
+    /// Allowed values
+    Enum(Vec<serde_json::Value>),
+    /// A string, number, integer or boolean, with the format if any
+    Scalar {
+        kind: String,
+        format: Option<String>,
+    },
+    OneOf(Vec<SchemaModel>),
+    AnyOf(Vec<SchemaModel>),
+    /// Parts that all apply, including an object of the schema's own properties
+    AllOf(Vec<SchemaModel>),
+    /// No type given
+    #[default]
+    Any,
+}
+
+impl SchemaModel {
+    /// How the schema is named: its title, else the name it was referenced by
+    pub fn name(&self) -> Option<&str> {
+        self.title.as_deref().or(self.ref_name.as_deref())
+    }
+}
+
+/// Example response body taken from the spec, shown before a request is made
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResponseExample {
+    pub status: String,
+    pub media_type: Option<String>,
+    pub body: serde_json::Value,
+}
+
+impl ResponseExample {
+    /// Body lines as displayed: strings as written, anything else pretty-printed
+    pub fn lines(&self) -> Vec<String> {
+        match &self.body {
+            serde_json::Value::String(text) => text.lines().map(str::to_string).collect(),
+            other => serde_json::to_string_pretty(other)
+                .unwrap_or_default()
+                .lines()
+                .map(str::to_string)
+                .collect(),
+        }
+    }
+}
+
+/// Request body declared by the spec for an operation
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct RequestBodySpec {
+    pub required: Option<bool>,
+
+    #[allow(dead_code)]
+    pub description: Option<String>,
+
+    /// Schemas per media type (e.g. "application/json"), `$ref`s resolved after parsing
+    #[serde(default)]
+    pub content: HashMap<String, MediaTypeSpec>,
+}
+
+impl RequestBodySpec {
+    /// Schema of the JSON body, if the spec declares one
+    pub fn json_schema(&self) -> Option<&serde_json::Value> {
+        self.json_media().and_then(|media| media.schema.as_ref())
+    }
+
+    fn json_media(&self) -> Option<&MediaTypeSpec> {
+        self.content.get("application/json").or_else(|| {
+            self.content
+                .iter()
+                .find(|(media_type, _)| media_type.contains("json"))
+                .map(|(_, media)| media)
+        })
+    }
+
+    /// Schema of the body in the given media type, else of the JSON body
+    pub fn schema_for(&self, media_type: &str) -> Option<&serde_json::Value> {
+        self.content
+            .get(media_type)
+            .and_then(|media| media.schema.as_ref())
+            .or_else(|| self.json_schema())
+    }
+
+    /// Outline model of [`Self::schema_for`]
+    pub fn model_for(&self, media_type: &str) -> Option<&SchemaModel> {
+        self.content
+            .get(media_type)
+            .and_then(|media| media.model.as_ref())
+            .or_else(|| self.json_media().and_then(|media| media.model.as_ref()))
+    }
+
+    /// Declared media types, JSON ones first
+    pub fn content_types(&self) -> Vec<&str> {
+        let mut types: Vec<&str> = self.content.keys().map(String::as_str).collect();
+        types.sort_by_key(|media_type| (!is_json_media_type(media_type), *media_type));
+        types
+    }
+}
+
+fn is_json_media_type(media_type: &str) -> bool {
+    media_type == "application/json" || media_type.ends_with("+json")
+}
+
+/// Whether bodies of this media type are written as JSON in the body editor
+///
+/// Form bodies are written as a JSON object whose fields become the form fields.
+pub fn edited_as_json(media_type: &str) -> bool {
+    is_json_media_type(media_type)
+        || media_type == "application/x-www-form-urlencoded"
+        || media_type == "multipart/form-data"
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct MediaTypeSpec {
+    pub schema: Option<serde_json::Value>,
+    /// The schema as outlined, built after parsing
+    #[serde(skip)]
+    pub model: Option<SchemaModel>,
+    pub example: Option<serde_json::Value>,
+
+    /// Named examples, each with a `value`
+    #[serde(default)]
+    pub examples: HashMap<String, serde_json::Value>,
+}
+
+impl MediaTypeSpec {
+    /// The `example`, else the first named example's value (by name)
+    fn example_value(&self) -> Option<serde_json::Value> {
+        self.example.clone().or_else(|| {
+            self.examples
+                .iter()
+                .min_by_key(|(name, _)| *name)
+                .and_then(|(_, example)| example.get("value").cloned())
+        })
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -228,6 +1355,112 @@ pub enum ViewMode {
     Grouped,
 }
 
+/// Smallest terminal the main screen is drawn in; below it only a notice is shown
+pub const MIN_SCREEN_SIZE: (u16, u16) = (40, 12);
+
+/// Narrower terminals stack the panels, or show only the focused one, instead of both side by side
+const SIDE_BY_SIDE_MIN_WIDTH: u16 = 80;
+
+/// Narrow terminals at least this tall stack the panels instead of showing only the focused one
+const STACKED_MIN_HEIGHT: u16 = 30;
+
+/// Share of the body height the endpoints get when the panels are stacked
+pub const STACKED_ENDPOINTS_PERCENT: u16 = 40;
+
+/// Narrower terminals fall back to two panes from the three-pane layout
+const THREE_PANE_MIN_WIDTH: u16 = 120;
+
+/// Shorter terminals get a one-line footer without its border
+const FULL_FOOTER_MIN_HEIGHT: u16 = 24;
+
+/// Which parts of the main screen fit the terminal
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScreenLayout {
+    /// Below the minimum size, nothing but a notice is drawn
+    pub too_small: bool,
+    /// Endpoints and details next to each other
+    pub side_by_side: bool,
+    /// Endpoints above details; with neither this nor `side_by_side`, only the focused one
+    pub stacked: bool,
+    /// The response in a pane of its own, when enabled and wide enough
+    pub three_pane: bool,
+    pub compact_footer: bool,
+}
+
+impl ScreenLayout {
+    pub fn new(width: u16, height: u16, three_pane: bool, arrangement: PanelArrangement) -> Self {
+        let (side_by_side, stacked) = match arrangement {
+            PanelArrangement::Auto if width >= SIDE_BY_SIDE_MIN_WIDTH => (true, false),
+            PanelArrangement::Auto => (false, height >= STACKED_MIN_HEIGHT),
+            PanelArrangement::Stacked => (false, true),
+            PanelArrangement::SideBySide => (true, false),
+        };
+        Self {
+            too_small: width < MIN_SCREEN_SIZE.0 || height < MIN_SCREEN_SIZE.1,
+            side_by_side,
+            stacked,
+            three_pane: three_pane && side_by_side && width >= THREE_PANE_MIN_WIDTH,
+            compact_footer: height < FULL_FOOTER_MIN_HEIGHT,
+        }
+    }
+
+    pub fn footer_height(&self) -> u16 {
+        if self.compact_footer {
+            1
+        } else {
+            3
+        }
+    }
+}
+
+/// How endpoints are grouped in the grouped view
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum GroupBy {
+    #[default]
+    Tag,
+    PathPrefix,
+}
+
+impl GroupBy {
+    pub fn next(self) -> Self {
+        match self {
+            Self::Tag => Self::PathPrefix,
+            Self::PathPrefix => Self::Tag,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Tag => "tag",
+            Self::PathPrefix => "path",
+        }
+    }
+}
+
+/// Group name for an endpoint path: leading `api`/version segments plus the first resource segment
+///
+/// `/api/v1/users/{id}` groups under `/api/v1/users`, `/pets` under `/pets`.
+pub fn path_prefix_group(path: &str) -> String {
+    let mut prefix = String::new();
+    for segment in path.split('/').filter(|s| !s.is_empty()) {
+        prefix.push('/');
+        prefix.push_str(segment);
+
+        let is_version = segment.len() > 1
+            && segment.starts_with(['v', 'V'])
+            && segment[1..].chars().all(|c| c.is_ascii_digit() || c == '.');
+        if !(segment.eq_ignore_ascii_case("api") || is_version) {
+            break;
+        }
+    }
+
+    if prefix.is_empty() {
+        "/".to_string()
+    } else {
+        prefix
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum LoadingState {
     Idle,
@@ -237,6 +1470,43 @@ pub enum LoadingState {
     Error(String),
 }
 
+/// Longest wait between two attempts to reach a server that went away
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(30);
+
+/// Background attempts to refetch the spec after the server refused connections
+///
+/// The delay doubles with each failed attempt, from one second up to
+/// `MAX_RECONNECT_DELAY`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reconnect {
+    /// Failed attempts so far
+    pub attempt: u32,
+    pub next_attempt: Instant,
+}
+
+impl Reconnect {
+    pub fn start() -> Self {
+        Self {
+            attempt: 0,
+            next_attempt: Instant::now() + Self::delay(0),
+        }
+    }
+
+    fn delay(attempt: u32) -> Duration {
+        Duration::from_secs(1u64 << attempt.min(5)).min(MAX_RECONNECT_DELAY)
+    }
+
+    /// Schedule the next attempt after one failed
+    pub fn failed(&mut self) {
+        self.attempt += 1;
+        self.next_attempt = Instant::now() + Self::delay(self.attempt);
+    }
+
+    pub fn is_due(&self) -> bool {
+        Instant::now() >= self.next_attempt
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum RenderItem {
     GroupHeader {
@@ -245,7 +1515,7 @@ pub enum RenderItem {
         expanded: bool,
     },
     Endpoint {
-        endpoint: ApiEndpoint,
+        endpoint: Box<ApiEndpoint>,
     },
 }
 
@@ -254,17 +1524,52 @@ pub enum InputMode {
     Normal,
     EnteringToken,
     ConfirmClearToken,
+    /// Confirm clearing the request config of the endpoint at this path
+    ConfirmResetRequest(String),
+    EnteringVariantName,
+    /// Paste a JSON object or query string onto the parameters of the endpoint at this path
+    PastingParams(String),
+    EnteringSnapshotName,
+    EditingNote,
+    ExportingCsv,
+    SavingBody,
+    ExportingScript,
+    ExportingTraffic,
+    ExportingDiagnostics,
+    SelectingLink,
+    SelectingDiscoveredSpec,
     EnteringUrl,
     Searching,
+    SearchingResponse,
     EnteringBody,
+    EditingParamForm,
+    ViewingSpecInfo,
+    ViewingStats,
+    ViewingSpecChanges,
+    ConfiguringServer,
+    SelectingPluginCommand,
+    SwitchingSpec,
+    ViewingJobs,
+    Console,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum UrlInputField {
     SwaggerUrl,
     BaseUrl,
 }
 
+/// Change to the open workspace tabs, carried out by the app
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum WorkspaceCommand {
+    Open,
+    Close,
+    Next,
+    Previous,
+    /// Show the spec at this index of the config's `specs`, opening a tab for it if needed
+    OpenSpec(usize),
+}
+
 #[derive(Debug, Clone)]
 pub struct UrlSubmission {
     pub swagger_url: String,
@@ -272,14 +1577,14 @@ pub struct UrlSubmission {
 }
 
 /// Tracks which main panel has focus
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum PanelFocus {
     EndpointsList, // Left panel
     Details,       // Right panel
 }
 
 /// Tracks which tab is active in the Details panel
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum DetailTab {
     Endpoint,
     Request,
@@ -287,6 +1592,13 @@ pub enum DetailTab {
     Response,
 }
 
+/// Scroll offset and selected line of a scrollable tab
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ViewPosition {
+    pub scroll: usize,
+    pub selected_line: usize,
+}
+
 // For tracking UI state in Request tab
 #[derive(Debug, Clone, PartialEq)]
 pub enum RequestEditMode {
@@ -309,13 +1621,26 @@ mod tests {
             required: Some(required),
             schema: None,
             description: None,
+            example: None,
         }
     }
 
+    #[test]
+    fn test_path_prefix_group() {
+        assert_eq!(path_prefix_group("/pets/{petId}"), "/pets");
+        assert_eq!(
+            path_prefix_group("/api/v1/users/{id}/roles"),
+            "/api/v1/users"
+        );
+        assert_eq!(path_prefix_group("/v2.1/orders"), "/v2.1/orders");
+        assert_eq!(path_prefix_group("/"), "/");
+    }
+
     #[test]
     fn test_path_params_filter() {
         let endpoint = ApiEndpoint {
             method: "GET".to_string(),
+            request_body: None,
             path: "/users/{id}".to_string(),
             summary: None,
             tags: vec![],
@@ -323,6 +1648,15 @@ mod tests {
                 create_param("id", "path", true),
                 create_param("limit", "query", false),
             ],
+            response_example: None,
+            responses: vec![],
+            deprecated: false,
+            description: None,
+            security: None,
+            extensions: vec![],
+            event: None,
+            service: None,
+            base_url: None,
         };
 
         let path_params = endpoint.path_params();
@@ -335,6 +1669,7 @@ mod tests {
     fn test_query_params_filter() {
         let endpoint = ApiEndpoint {
             method: "GET".to_string(),
+            request_body: None,
             path: "/users".to_string(),
             summary: None,
             tags: vec![],
@@ -343,6 +1678,15 @@ mod tests {
                 create_param("limit", "query", false),
                 create_param("skip", "query", false),
             ],
+            response_example: None,
+            responses: vec![],
+            deprecated: false,
+            description: None,
+            security: None,
+            extensions: vec![],
+            event: None,
+            service: None,
+            base_url: None,
         };
 
         let query_params = endpoint.query_params();
@@ -355,10 +1699,20 @@ mod tests {
     fn test_has_all_required_path_params_success() {
         let endpoint = ApiEndpoint {
             method: "GET".to_string(),
+            request_body: None,
             path: "/users/{id}".to_string(),
             summary: None,
             tags: vec![],
             parameters: vec![create_param("id", "path", true)],
+            response_example: None,
+            responses: vec![],
+            deprecated: false,
+            description: None,
+            security: None,
+            extensions: vec![],
+            event: None,
+            service: None,
+            base_url: None,
         };
 
         let mut config = RequestConfig::default();
@@ -371,10 +1725,20 @@ mod tests {
     fn test_has_all_required_path_params_missing() {
         let endpoint = ApiEndpoint {
             method: "GET".to_string(),
+            request_body: None,
             path: "/users/{id}".to_string(),
             summary: None,
             tags: vec![],
             parameters: vec![create_param("id", "path", true)],
+            response_example: None,
+            responses: vec![],
+            deprecated: false,
+            description: None,
+            security: None,
+            extensions: vec![],
+            event: None,
+            service: None,
+            base_url: None,
         };
 
         let config = RequestConfig::default(); // Empty config
@@ -386,10 +1750,20 @@ mod tests {
     fn test_has_all_required_path_params_empty_value() {
         let endpoint = ApiEndpoint {
             method: "GET".to_string(),
+            request_body: None,
             path: "/users/{id}".to_string(),
             summary: None,
             tags: vec![],
             parameters: vec![create_param("id", "path", true)],
+            response_example: None,
+            responses: vec![],
+            deprecated: false,
+            description: None,
+            security: None,
+            extensions: vec![],
+            event: None,
+            service: None,
+            base_url: None,
         };
 
         let mut config = RequestConfig::default();
@@ -402,6 +1776,7 @@ mod tests {
     fn test_has_all_required_path_params_multiple() {
         let endpoint = ApiEndpoint {
             method: "GET".to_string(),
+            request_body: None,
             path: "/users/{userId}/posts/{postId}".to_string(),
             summary: None,
             tags: vec![],
@@ -409,6 +1784,15 @@ mod tests {
                 create_param("userId", "path", true),
                 create_param("postId", "path", true),
             ],
+            response_example: None,
+            responses: vec![],
+            deprecated: false,
+            description: None,
+            security: None,
+            extensions: vec![],
+            event: None,
+            service: None,
+            base_url: None,
         };
 
         let mut config = RequestConfig::default();
@@ -422,10 +1806,20 @@ mod tests {
     fn test_missing_path_params_empty() {
         let endpoint = ApiEndpoint {
             method: "GET".to_string(),
+            request_body: None,
             path: "/users/{id}".to_string(),
             summary: None,
             tags: vec![],
             parameters: vec![create_param("id", "path", true)],
+            response_example: None,
+            responses: vec![],
+            deprecated: false,
+            description: None,
+            security: None,
+            extensions: vec![],
+            event: None,
+            service: None,
+            base_url: None,
         };
 
         let mut config = RequestConfig::default();
@@ -439,10 +1833,20 @@ mod tests {
     fn test_missing_path_params_single() {
         let endpoint = ApiEndpoint {
             method: "GET".to_string(),
+            request_body: None,
             path: "/users/{id}".to_string(),
             summary: None,
             tags: vec![],
             parameters: vec![create_param("id", "path", true)],
+            response_example: None,
+            responses: vec![],
+            deprecated: false,
+            description: None,
+            security: None,
+            extensions: vec![],
+            event: None,
+            service: None,
+            base_url: None,
         };
 
         let config = RequestConfig::default(); // Empty config
@@ -456,6 +1860,7 @@ mod tests {
     fn test_missing_path_params_multiple() {
         let endpoint = ApiEndpoint {
             method: "GET".to_string(),
+            request_body: None,
             path: "/users/{userId}/posts/{postId}".to_string(),
             summary: None,
             tags: vec![],
@@ -463,6 +1868,15 @@ mod tests {
                 create_param("userId", "path", true),
                 create_param("postId", "path", true),
             ],
+            response_example: None,
+            responses: vec![],
+            deprecated: false,
+            description: None,
+            security: None,
+            extensions: vec![],
+            event: None,
+            service: None,
+            base_url: None,
         };
 
         let mut config = RequestConfig::default();
@@ -478,10 +1892,20 @@ mod tests {
     fn test_missing_path_params_empty_value() {
         let endpoint = ApiEndpoint {
             method: "GET".to_string(),
+            request_body: None,
             path: "/users/{id}".to_string(),
             summary: None,
             tags: vec![],
             parameters: vec![create_param("id", "path", true)],
+            response_example: None,
+            responses: vec![],
+            deprecated: false,
+            description: None,
+            security: None,
+            extensions: vec![],
+            event: None,
+            service: None,
+            base_url: None,
         };
 
         let mut config = RequestConfig::default();
@@ -492,6 +1916,471 @@ mod tests {
         assert_eq!(missing[0], "id");
     }
 
+    #[test]
+    fn test_validation_errors_lists_everything_missing() {
+        let endpoint = ApiEndpoint {
+            method: "POST".to_string(),
+            request_body: Some(RequestBodySpec {
+                required: Some(true),
+                ..Default::default()
+            }),
+            path: "/users/{id}".to_string(),
+            summary: None,
+            tags: vec![],
+            parameters: vec![
+                create_param("id", "path", true),
+                create_param("tenant", "query", true),
+                create_param("verbose", "query", false),
+                create_param("X-Request-Id", "header", true),
+            ],
+            response_example: None,
+            responses: vec![],
+            deprecated: false,
+            description: None,
+            security: None,
+            extensions: vec![],
+            event: None,
+            service: None,
+            base_url: None,
+        };
+
+        let errors = endpoint.validation_errors(None);
+        assert_eq!(errors.len(), 4);
+        assert!(errors[0].contains("id"));
+        assert!(errors[1].contains("tenant"));
+        assert!(!errors[1].contains("verbose"));
+        assert_eq!(errors[2], "Missing required header(s): X-Request-Id");
+        assert!(errors[3].contains("request body"));
+
+        let mut config = RequestConfig::default();
+        config.set_param("id".to_string(), "1".to_string(), ParameterType::Path);
+        config.set_param("tenant".to_string(), "a".to_string(), ParameterType::Query);
+        config.set_param(
+            "X-Request-Id".to_string(),
+            "r1".to_string(),
+            ParameterType::Header,
+        );
+        assert_eq!(
+            config.header_values(),
+            vec![("X-Request-Id".to_string(), "r1".to_string())]
+        );
+        config.body = Some("{\"name\": \"x\"}".to_string());
+        assert!(endpoint.validation_errors(Some(&config)).is_empty());
+    }
+
+    #[test]
+    fn test_param_value_error() {
+        let param = |param_type: &str, format: Option<&str>| ApiParameter {
+            schema: Some(ParameterSchema {
+                param_type: Some(param_type.to_string()),
+                format: format.map(str::to_string),
+                minimum: Some(1.0),
+                maximum: Some(100.0),
+                ..Default::default()
+            }),
+            ..create_param("id", "query", false)
+        };
+
+        let int32 = param("integer", Some("int32"));
+        assert_eq!(int32.value_error("42"), None);
+
+        let mut sort = param("string", None);
+        if let Some(schema) = sort.schema.as_mut() {
+            schema.enum_values = vec![serde_json::json!("asc"), serde_json::json!("desc")];
+        }
+        assert_eq!(sort.value_error("desc"), None);
+        assert_eq!(
+            sort.value_error("up").as_deref(),
+            Some("expected one of asc, desc")
+        );
+        assert_eq!(sort.type_info(), r#""asc" | "desc""#);
+        let allowed = sort.allowed_values();
+        assert_eq!(cycle_enum_value(&allowed, ""), "asc");
+        assert_eq!(cycle_enum_value(&allowed, "asc"), "desc");
+        assert_eq!(cycle_enum_value(&allowed, "desc"), "");
+        assert_eq!(int32.value_error(""), None);
+        assert_eq!(int32.value_error("abc").unwrap(), "expected an integer");
+        assert_eq!(int32.value_error("0").unwrap(), "must be at least 1");
+        assert_eq!(
+            int32.value_error("3000000000").unwrap(),
+            "out of range for int32"
+        );
+        assert_eq!(
+            param("number", None).value_error("1e9").unwrap(),
+            "must be at most 100"
+        );
+        assert!(param("boolean", None).value_error("yes").is_some());
+
+        let uuid = param("string", Some("uuid"));
+        assert_eq!(
+            uuid.value_error("123e4567-e89b-12d3-a456-426614174000"),
+            None
+        );
+        assert!(uuid.value_error("123e4567").is_some());
+
+        let endpoint = ApiEndpoint {
+            parameters: vec![int32],
+            ..Default::default()
+        };
+        let mut config = RequestConfig::default();
+        config.set_param("id".to_string(), "abc".to_string(), ParameterType::Query);
+        assert_eq!(
+            endpoint.validation_errors(Some(&config)),
+            vec!["Invalid value for 'id': expected an integer"]
+        );
+    }
+
+    #[test]
+    fn test_validation_errors_invalid_body() {
+        let endpoint = ApiEndpoint {
+            method: "PUT".to_string(),
+            request_body: None,
+            path: "/users".to_string(),
+            summary: None,
+            tags: vec![],
+            parameters: vec![],
+            response_example: None,
+            responses: vec![],
+            deprecated: false,
+            description: None,
+            security: None,
+            extensions: vec![],
+            event: None,
+            service: None,
+            base_url: None,
+        };
+        let config = RequestConfig {
+            body: Some("{not json".to_string()),
+            ..Default::default()
+        };
+
+        let errors = endpoint.validation_errors(Some(&config));
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].starts_with("Request body is not valid JSON"));
+    }
+
+    #[test]
+    fn test_validation_errors_without_config_or_parameters() {
+        let endpoint = ApiEndpoint {
+            method: "GET".to_string(),
+            path: "/health".to_string(),
+            ..Default::default()
+        };
+
+        assert!(endpoint.validation_errors(None).is_empty());
+    }
+
+    #[test]
+    fn test_body_content_type() {
+        let media = |name: &str| (name.to_string(), MediaTypeSpec::default());
+        let endpoint = ApiEndpoint {
+            method: "POST".to_string(),
+            path: "/upload".to_string(),
+            request_body: Some(RequestBodySpec {
+                content: HashMap::from([
+                    media("text/plain"),
+                    media("multipart/form-data"),
+                    media("application/json"),
+                ]),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        assert_eq!(
+            endpoint.request_body.as_ref().unwrap().content_types(),
+            vec!["application/json", "multipart/form-data", "text/plain"]
+        );
+        assert_eq!(endpoint.body_content_type(None), "application/json");
+
+        let mut config = RequestConfig {
+            body: Some("plain words".to_string()),
+            content_type: Some("text/plain".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(endpoint.body_content_type(Some(&config)), "text/plain");
+        assert!(endpoint.validation_errors(Some(&config)).is_empty());
+
+        config.content_type = Some("application/xml".to_string());
+        assert_eq!(
+            endpoint.body_content_type(Some(&config)),
+            "application/json"
+        );
+        assert_eq!(endpoint.validation_errors(Some(&config)).len(), 1);
+    }
+
+    #[test]
+    fn test_response_example() {
+        let operation: Operation = serde_json::from_value(serde_json::json!({
+            "responses": {
+                "404": {
+                    "content": { "application/json": { "example": { "error": "not found" } } }
+                },
+                "200": {
+                    "content": {
+                        "text/plain": { "example": "ok" },
+                        "application/json": {
+                            "examples": { "b": { "value": 2 }, "a": { "value": { "id": 1 } } }
+                        }
+                    }
+                }
+            }
+        }))
+        .unwrap();
+
+        let example = operation.response_example().unwrap();
+        assert_eq!(example.status, "200");
+        assert_eq!(example.media_type.as_deref(), Some("application/json"));
+        assert_eq!(example.lines(), vec!["{", "  \"id\": 1", "}"]);
+
+        let swagger2: Operation = serde_json::from_value(serde_json::json!({
+            "responses": { "201": { "examples": { "application/json": [1, 2] } } }
+        }))
+        .unwrap();
+        assert_eq!(swagger2.response_example().unwrap().status, "201");
+
+        let none: Operation =
+            serde_json::from_value(serde_json::json!({ "responses": { "204": {} } })).unwrap();
+        assert!(none.response_example().is_none());
+    }
+
+    #[test]
+    fn test_declared_responses() {
+        let operation: Operation = serde_json::from_value(serde_json::json!({
+            "responses": {
+                "default": { "description": "Unexpected error" },
+                "404": { "description": "" },
+                "200": {
+                    "description": "The user",
+                    "content": {
+                        "application/xml": {},
+                        "application/json": {
+                            "schema": { "type": "object" },
+                            "example": { "id": 1 }
+                        }
+                    }
+                }
+            }
+        }))
+        .unwrap();
+
+        let responses = operation.declared_responses();
+        let statuses: Vec<&str> = responses.iter().map(|r| r.status.as_str()).collect();
+        assert_eq!(statuses, vec!["200", "404", "default"]);
+        assert_eq!(responses[0].description.as_deref(), Some("The user"));
+        assert_eq!(responses[0].media_type.as_deref(), Some("application/json"));
+        assert_eq!(
+            responses[0].schema,
+            Some(serde_json::json!({ "type": "object" }))
+        );
+        assert_eq!(responses[0].example, Some(serde_json::json!({ "id": 1 })));
+        assert_eq!(responses[1].description, None);
+        assert_eq!(responses[2].schema, None);
+    }
+
+    #[test]
+    fn test_security_scheme_credential() {
+        let scheme =
+            |value: serde_json::Value| -> SecurityScheme { serde_json::from_value(value).unwrap() };
+        let header = |name: &str, value: &str| Credential::Header(name.into(), value.into());
+
+        let basic = scheme(serde_json::json!({ "type": "http", "scheme": "Basic" }));
+        assert_eq!(
+            basic.credential("ann:pw"),
+            header("Authorization", "Basic YW5uOnB3")
+        );
+        assert_eq!(basic.describe(), "HTTP basic auth");
+
+        let key = scheme(serde_json::json!({ "type": "apiKey", "name": "X-Key", "in": "header" }));
+        assert_eq!(key.credential("k"), header("X-Key", "k"));
+        assert_eq!(key.describe(), "API key in header X-Key");
+        let cookie = scheme(serde_json::json!({ "type": "apiKey", "name": "sid", "in": "cookie" }));
+        assert_eq!(cookie.credential("k"), header("Cookie", "sid=k"));
+
+        let oauth = scheme(serde_json::json!({ "type": "oauth2", "flows": {} }));
+        assert_eq!(oauth.credential("t"), bearer("t"));
+        assert_eq!(oauth.credential("t"), header("Authorization", "Bearer t"));
+    }
+
+    #[test]
+    fn test_security_label() {
+        let requirement = |schemes: &[(&str, &[&str])]| -> SecurityRequirement {
+            schemes
+                .iter()
+                .map(|(name, scopes)| {
+                    (
+                        name.to_string(),
+                        scopes.iter().map(|s| s.to_string()).collect(),
+                    )
+                })
+                .collect()
+        };
+        let mut endpoint = ApiEndpoint {
+            security: Some(vec![
+                requirement(&[("oauth2", &["read", "write"])]),
+                requirement(&[("apiKey", &[]), ("appId", &[])]),
+            ]),
+            ..Default::default()
+        };
+
+        assert_eq!(endpoint.allows_anonymous(), Some(false));
+        assert_eq!(
+            endpoint.security_label(true),
+            "oauth2 (read, write) or apiKey + appId"
+        );
+        assert_eq!(endpoint.security_label(false), "oauth2 or apiKey + appId");
+
+        endpoint.security = Some(vec![requirement(&[]), requirement(&[("bearer", &[])])]);
+        assert_eq!(endpoint.allows_anonymous(), Some(true));
+        endpoint.security = None;
+        assert_eq!(endpoint.allows_anonymous(), None);
+    }
+
+    #[test]
+    fn test_reconnect_backoff() {
+        let mut reconnect = Reconnect::start();
+        assert!(!reconnect.is_due());
+
+        let delays: Vec<u64> = (0..8)
+            .map(|attempt| Reconnect::delay(attempt).as_secs())
+            .collect();
+        assert_eq!(delays, vec![1, 2, 4, 8, 16, 30, 30, 30]);
+
+        reconnect.failed();
+        assert_eq!(reconnect.attempt, 1);
+        assert!(reconnect.next_attempt > Instant::now() + Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_spec_info_heading() {
+        let info = |title: Option<&str>, version: Option<&str>| SpecInfo {
+            title: title.map(str::to_string),
+            version: version.map(str::to_string),
+            ..Default::default()
+        };
+        assert_eq!(
+            info(Some("Petstore"), Some("1.0.2")).heading().as_deref(),
+            Some("Petstore v1.0.2")
+        );
+        assert_eq!(
+            info(Some("Billing"), Some("v2")).heading().as_deref(),
+            Some("Billing v2")
+        );
+        assert_eq!(
+            info(Some("Users"), None).heading().as_deref(),
+            Some("Users")
+        );
+        assert_eq!(info(None, Some("1.0")).heading(), None);
+    }
+
+    #[test]
+    fn test_operation_extensions() {
+        let operation: Operation = serde_json::from_value(serde_json::json!({
+            "summary": "List users",
+            "operationId": "listUsers",
+            "x-ratelimit": { "limit": 100 },
+            "x-owner": "identity-team"
+        }))
+        .unwrap();
+
+        assert_eq!(
+            operation.extensions(),
+            vec![
+                ("x-owner".to_string(), serde_json::json!("identity-team")),
+                (
+                    "x-ratelimit".to_string(),
+                    serde_json::json!({ "limit": 100 })
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_param_at_and_bool_cycle() {
+        let mut flag = create_param("active", "query", false);
+        flag.schema = Some(ParameterSchema {
+            param_type: Some("boolean".to_string()),
+            ..Default::default()
+        });
+        let endpoint = ApiEndpoint {
+            method: "GET".to_string(),
+            request_body: None,
+            path: "/users/{id}".to_string(),
+            summary: None,
+            tags: vec![],
+            parameters: vec![
+                create_param("X-Trace", "header", false),
+                flag,
+                create_param("id", "path", true),
+            ],
+            response_example: None,
+            responses: vec![],
+            deprecated: false,
+            description: None,
+            security: None,
+            extensions: vec![],
+            event: None,
+            service: None,
+            base_url: None,
+        };
+
+        let (param, param_type) = endpoint.param_at(1).unwrap();
+        assert_eq!(param.name, "active");
+        assert_eq!(param_type, ParameterType::Query);
+        assert!(param.is_boolean());
+        assert!(!endpoint.param_at(0).unwrap().0.is_boolean());
+        assert_eq!(endpoint.param_at(2).unwrap().1, ParameterType::Header);
+        assert!(endpoint.param_at(3).is_none());
+
+        assert_eq!(cycle_bool_value(""), "true");
+        assert_eq!(cycle_bool_value("true"), "false");
+        assert_eq!(cycle_bool_value("false"), "");
+        assert_eq!(cycle_bool_value("True"), "true");
+    }
+
+    #[test]
+    fn test_param_form_for_endpoint() {
+        let endpoint = ApiEndpoint {
+            method: "GET".to_string(),
+            request_body: None,
+            path: "/users/{id}".to_string(),
+            summary: None,
+            tags: vec![],
+            parameters: vec![
+                create_param("limit", "query", false),
+                create_param("id", "path", true),
+            ],
+            response_example: None,
+            responses: vec![],
+            deprecated: false,
+            description: None,
+            security: None,
+            extensions: vec![],
+            event: None,
+            service: None,
+            base_url: None,
+        };
+
+        let mut config = RequestConfig::default();
+        config.set_param("limit".to_string(), "10".to_string(), ParameterType::Query);
+
+        let mut form = ParamForm::for_endpoint(&endpoint, Some(&config));
+
+        // Path params come first, like in the Request tab
+        assert_eq!(form.fields.len(), 2);
+        assert_eq!(form.fields[0].name, "id");
+        assert!(form.fields[0].required);
+        assert_eq!(form.fields[0].value, "");
+        assert_eq!(form.fields[1].name, "limit");
+        assert_eq!(form.fields[1].value, "10");
+
+        form.next_field();
+        assert_eq!(form.active_field, 1);
+        form.next_field();
+        assert_eq!(form.active_field, 0);
+        form.prev_field();
+        assert_eq!(form.active_field, 1);
+    }
+
     #[test]
     fn test_request_config_default() {
         let config = RequestConfig::default();
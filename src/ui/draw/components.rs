@@ -1,41 +1,56 @@
 //! Reusable UI components
 //!
 //! This module contains shared UI components used throughout the application:
-//! - Header (title, status, auth)
-//! - Footer (command help)
+//! - Header (title, status, auth, workspace tabs)
+//! - Toasts, the error banner and the log panel
+//! - Footer (command help), a single line in short terminals
+//! - Notice for terminals below the minimum size
 //! - Search bar
 //! - Loading spinners
 //! - Error/empty state messages
 
-use crate::state::{AppState, AuthState};
-use crate::types::{InputMode, LoadingState, ViewMode};
+use super::styling;
+use crate::state::{AppState, AuthState, DataState};
+use crate::types::{
+    DetailTab, InputMode, LoadingState, PanelFocus, Reconnect, ViewMode, MIN_SCREEN_SIZE,
+};
+use crate::ui::events::recent_log;
 use ratatui::{
-    layout::Rect,
-    style::{Color, Style},
-    widgets::{Block, Borders, Paragraph},
+    layout::{Alignment, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
     Frame,
 };
+use std::time::Instant;
 
 /// Render the application header with status and auth info
 pub fn render_header(
     frame: &mut Frame,
     area: Rect,
     swagger_url: &str,
-    loading_state: &LoadingState,
-    endpoints_count: usize,
+    data: &DataState,
     auth_state: &AuthState,
 ) {
-    let status_text = match loading_state {
+    let status_text = match &data.loading_state {
         LoadingState::Idle => "Idle".to_string(),
         LoadingState::Fetching => "Fetching...".to_string(),
         LoadingState::Parsing => "Parsing...".to_string(),
-        LoadingState::Complete => format!("{endpoints_count} endpoints loaded"),
+        LoadingState::Complete if data.watching => {
+            format!("{} endpoints loaded, watching", data.endpoints.len())
+        }
+        LoadingState::Complete => format!("{} endpoints loaded", data.endpoints.len()),
         LoadingState::Error(_) => "Error".to_string(),
     };
 
     let auth_status = auth_state.get_status_text();
 
-    let header_text = format!("lazy swagger tui - {swagger_url} [{status_text}] | {auth_status}",);
+    let header_text = match data.spec_info.heading() {
+        Some(heading) => {
+            format!("lazy swagger tui - {heading} ({swagger_url}) [{status_text}] | {auth_status}")
+        }
+        None => format!("lazy swagger tui - {swagger_url} [{status_text}] | {auth_status}"),
+    };
 
     let header = Paragraph::new(header_text)
         .style(Style::default().fg(Color::Cyan))
@@ -44,8 +59,169 @@ pub fn render_header(
     frame.render_widget(header, area);
 }
 
+/// Render a short message in the top right corner, over the header
+/// Notice shown instead of the main screen when the terminal is below the minimum size
+pub fn render_too_small(frame: &mut Frame) {
+    let area = frame.area();
+    let (min_width, min_height) = MIN_SCREEN_SIZE;
+    let notice = Paragraph::new(format!(
+        "Terminal too small\n{}x{}, need {min_width}x{min_height}",
+        area.width, area.height
+    ))
+    .style(Style::default().fg(Color::Yellow))
+    .alignment(Alignment::Center)
+    .wrap(Wrap { trim: true });
+    let y = area.height.saturating_sub(2) / 2;
+    let notice_area = Rect {
+        y,
+        height: area.height - y,
+        ..area
+    };
+    frame.render_widget(notice, notice_area);
+}
+
+pub fn render_toast(frame: &mut Frame, message: &str) {
+    let area = frame.area();
+    let width = (message.chars().count() as u16 + 4).min(area.width);
+    let toast_area = Rect {
+        x: area.width.saturating_sub(width + 1),
+        y: 1,
+        width,
+        height: 3.min(area.height),
+    };
+
+    frame.render_widget(Clear, toast_area);
+    let toast = Paragraph::new(message.to_string())
+        .style(Style::default().fg(Color::Yellow))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Yellow)),
+        );
+    frame.render_widget(toast, toast_area);
+}
+
+/// Render the performance overlay in the top right corner of `area`
+pub fn render_metrics_overlay(frame: &mut Frame, area: Rect, lines: &[String]) {
+    let width = lines
+        .iter()
+        .map(|line| line.chars().count() as u16 + 2)
+        .max()
+        .unwrap_or(0)
+        .min(area.width);
+    let overlay_area = Rect {
+        x: area.x + area.width - width,
+        y: area.y,
+        width,
+        height: (lines.len() as u16 + 2).min(area.height),
+    };
+
+    let block = Block::default()
+        .title(" F12 ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Magenta));
+    let text: Vec<Line> = lines.iter().map(|line| Line::from(line.as_str())).collect();
+
+    frame.render_widget(Clear, overlay_area);
+    frame.render_widget(
+        Paragraph::new(text)
+            .block(block)
+            .style(Style::default().fg(Color::Magenta)),
+        overlay_area,
+    );
+}
+
+/// Render the most recent log entries over the bottom third of `area`
+pub fn render_log_panel(frame: &mut Frame, area: Rect) {
+    let height = (area.height / 3).max(5).min(area.height);
+    let panel_area = Rect {
+        y: area.y + area.height - height,
+        height,
+        ..area
+    };
+
+    let block = Block::default()
+        .title(" Log (Ctrl+O: close) ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::DarkGray))
+        .style(
+            Style::default()
+                .bg(styling::default_bg())
+                .fg(styling::default_fg()),
+        );
+    let lines: Vec<Line> = recent_log(height.saturating_sub(2) as usize)
+        .into_iter()
+        .map(Line::from)
+        .collect();
+
+    frame.render_widget(Clear, panel_area);
+    frame.render_widget(Paragraph::new(lines).block(block), panel_area);
+}
+
+/// Render the latest error over the bottom line of `area`, until dismissed
+pub fn render_error_banner(frame: &mut Frame, area: Rect, message: &str) {
+    if area.height == 0 {
+        return;
+    }
+    let banner_area = Rect {
+        y: area.y + area.height - 1,
+        height: 1,
+        ..area
+    };
+
+    frame.render_widget(Clear, banner_area);
+    let banner = Paragraph::new(Line::from(vec![
+        Span::styled(
+            format!(" ✗ {message} "),
+            Style::default().add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(" Esc: dismiss"),
+    ]))
+    .style(Style::default().fg(Color::White).bg(Color::Red));
+    frame.render_widget(banner, banner_area);
+}
+
+/// Render the screen reader status line, where errors and messages are announced
+pub fn render_status_line(frame: &mut Frame, area: Rect, announcement: &str) {
+    frame.render_widget(Clear, area);
+    frame.render_widget(Paragraph::new(announcement.to_string()), area);
+}
+
+/// Render the workspace tabs over the header's top border, once more than one is open
+pub fn render_workspace_tabs(frame: &mut Frame, area: Rect, labels: &[String], active: usize) {
+    if labels.len() < 2 {
+        return;
+    }
+
+    let mut tabs = vec![Span::raw(" ")];
+    for (index, label) in labels.iter().enumerate() {
+        let style = if index == active {
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::DarkGray)
+        };
+        tabs.push(Span::styled(format!("{}:{label}", index + 1), style));
+        tabs.push(Span::raw(" "));
+    }
+
+    frame.render_widget(Block::default().title(Line::from(tabs)), area);
+}
+
 /// Render the search bar with active filter indication
+///
+/// On the Response tab it shows the text searched for in the response instead.
 pub fn render_search_bar(frame: &mut Frame, area: Rect, state: &AppState) {
+    if matches!(state.input.mode, InputMode::SearchingResponse)
+        || (!state.search.response_query.is_empty()
+            && state.ui.panel_focus == PanelFocus::Details
+            && state.ui.active_detail_tab == DetailTab::Response)
+    {
+        render_response_search_bar(frame, area, state);
+        return;
+    }
+
     let is_active = matches!(state.input.mode, InputMode::Searching);
 
     let border_style = if is_active {
@@ -57,12 +233,23 @@ pub fn render_search_bar(frame: &mut Frame, area: Rect, state: &AppState) {
     };
 
     // Show match count if filtering
-    let title = if !state.search.query.is_empty() {
-        let count = state.search.filtered_endpoints.len();
-        let total = state.data.endpoints.len();
-        format!(" Search [{count}/{total}] ")
+    let title = match (&state.search.scope, state.search.query.is_empty()) {
+        (Some(group), true) => format!(" Search in {group} "),
+        (Some(group), false) => format!(
+            " Search in {group} [{}] ",
+            state.search.filtered_endpoints.len()
+        ),
+        (None, false) => {
+            let count = state.search.filtered_endpoints.len();
+            let total = state.data.endpoints.len();
+            format!(" Search [{count}/{total}] ")
+        }
+        (None, true) => " Search (/) ".to_string(),
+    };
+    let title = if state.search.hide_deprecated {
+        format!("{title}· deprecated hidden ")
     } else {
-        " Search (/) ".to_string()
+        title
     };
 
     let block = Block::default()
@@ -81,6 +268,46 @@ pub fn render_search_bar(frame: &mut Frame, area: Rect, state: &AppState) {
     frame.render_widget(paragraph, area);
 }
 
+/// Search bar for finding text in the response body, with the match count
+fn render_response_search_bar(frame: &mut Frame, area: Rect, state: &AppState) {
+    let is_active = matches!(state.input.mode, InputMode::SearchingResponse);
+    let matches = state.response_matches();
+
+    let title = if state.search.response_query.is_empty() {
+        " Find in response ".to_string()
+    } else {
+        let current = state.ui.response_selected_line.checked_sub(2);
+        match matches.iter().position(|&idx| Some(idx) == current) {
+            Some(position) => format!(
+                " Find in response [{}/{}] n/N ",
+                position + 1,
+                matches.len()
+            ),
+            None => format!(" Find in response [{} matches] n/N ", matches.len()),
+        }
+    };
+
+    let border_style = if is_active {
+        Style::default().fg(Color::Cyan)
+    } else if matches.is_empty() {
+        Style::default().fg(Color::Red)
+    } else {
+        Style::default().fg(Color::Green)
+    };
+
+    let search_text = if is_active {
+        format!("{}_", state.search.response_query)
+    } else {
+        state.search.response_query.clone()
+    };
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(border_style)
+        .title(title);
+    frame.render_widget(Paragraph::new(search_text).block(block), area);
+}
+
 /// Render the footer with command help
 pub fn render_footer(
     frame: &mut Frame,
@@ -88,14 +315,12 @@ pub fn render_footer(
     view_mode: &ViewMode,
     state: &crate::state::AppState,
 ) {
-    use crate::types::{DetailTab, PanelFocus};
-
     let base_text = match view_mode {
         ViewMode::Flat => {
-            "Tab:Panel j/k/↑/↓:Nav Space:Execute/Toggle | g:Group ,:URL a:Auth q:Quit"
+            "Tab:Panel j/k/↑/↓:Nav Space:Execute/Toggle | g:Group ,:URL a:Auth i:Info q:Quit"
         }
         ViewMode::Grouped => {
-            "Tab:Panel j/k/↑/↓:Nav Space:Execute/Toggle | g:Ungroup ,:URL a:Auth q:Quit"
+            "Tab:Panel j/k/↑/↓:Nav Space:Execute/Toggle | g:Ungroup G:Group by ,:URL a:Auth i:Info q:Quit"
         }
     };
 
@@ -104,14 +329,30 @@ pub fn render_footer(
         && state.ui.active_detail_tab == DetailTab::Response
         && state.request.current_response.is_some()
     {
-        format!("{base_text} | y:Yank")
+        if state.ui.snapshot_diff.is_some() {
+            format!("{base_text} | DIFF D:Next snapshot/off M:Save snapshot w:Wrap")
+        } else if state.ui.response_table {
+            format!("{base_text} | t:JSON s:Sort o:Order y:Yank row X:CSV")
+        } else if state.ui.response_visual_anchor.is_some() {
+            format!("{base_text} | VISUAL y:Yank selection Esc:Cancel")
+        } else if state.visible_response_lines().len() < state.request.response_lines.len() {
+            format!("{base_text} | y:Yank v:Visual w:Wrap L:More f:Links O:Pager B:Browser")
+        } else {
+            format!("{base_text} | /:Find y:Yank v:Visual p:Path t:Table w:Wrap f:Links M/D:Snapshot O:Pager B:Browser")
+        }
     } else {
         base_text.to_string()
     };
 
+    // A short terminal gets a single line without the border
+    let block = if area.height >= 3 {
+        Block::default().borders(Borders::ALL).title("Commands")
+    } else {
+        Block::default()
+    };
     let footer = Paragraph::new(footer_text)
         .style(Style::default().fg(Color::Yellow))
-        .block(Block::default().borders(Borders::ALL).title("Commands"));
+        .block(block);
 
     frame.render_widget(footer, area);
 }
@@ -147,11 +388,26 @@ pub fn render_loading_spinner(
 }
 
 /// Render error message with retry instructions
-pub fn render_error_message(frame: &mut Frame, area: Rect, error: &str, retry_count: u32) {
-    let retry_text = if retry_count > 0 {
-        format!("\n\nRetry attempt: {retry_count}")
-    } else {
-        String::new()
+pub fn render_error_message(
+    frame: &mut Frame,
+    area: Rect,
+    error: &str,
+    retry_count: u32,
+    reconnect: Option<&Reconnect>,
+) {
+    let retry_text = match reconnect {
+        Some(reconnect) => {
+            let wait = reconnect
+                .next_attempt
+                .saturating_duration_since(Instant::now());
+            format!(
+                "\n\nServer unreachable, retrying in {}s (attempt {})",
+                wait.as_secs() + 1,
+                reconnect.attempt + 1
+            )
+        }
+        None if retry_count > 0 => format!("\n\nRetry attempt: {retry_count}"),
+        None => String::new(),
     };
 
     let error_msg = format!("❌ {error}{retry_text}\n\nPress [R] to retry\nPress [F5] to refresh",);
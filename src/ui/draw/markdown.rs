@@ -0,0 +1,198 @@
+//! Basic markdown rendering for spec descriptions
+//!
+//! Covers what API descriptions commonly use: headings, bullet and numbered
+//! lists, fenced code blocks, `code` spans, **bold**, *emphasis* and
+//! [links](url). Anything else is shown as written.
+
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+};
+
+/// Render markdown text as styled lines
+pub fn markdown_lines(text: &str) -> Vec<Line<'static>> {
+    let code_style = Style::default().fg(Color::Yellow);
+    let mut lines = Vec::new();
+    let mut in_code_block = false;
+
+    for raw in text.lines() {
+        let trimmed = raw.trim_start();
+        let indent = &raw[..raw.len() - trimmed.len()];
+
+        if trimmed.starts_with("```") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+        if in_code_block {
+            lines.push(Line::from(Span::styled(format!("  {raw}"), code_style)));
+            continue;
+        }
+
+        let level = trimmed.chars().take_while(|c| *c == '#').count();
+        if (1..=6).contains(&level) && trimmed[level..].starts_with(' ') {
+            let mut style = Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD);
+            if level == 1 {
+                style = style.add_modifier(Modifier::UNDERLINED);
+            }
+            lines.push(Line::from(inline_spans(trimmed[level..].trim(), style)));
+            continue;
+        }
+
+        let (marker, rest) = list_marker(trimmed);
+        let mut spans = Vec::new();
+        if let Some(marker) = marker {
+            spans.push(Span::raw(format!("{indent}{marker} ")));
+        } else if !indent.is_empty() {
+            spans.push(Span::raw(indent.to_string()));
+        }
+        spans.extend(inline_spans(rest, Style::default()));
+        lines.push(Line::from(spans));
+    }
+    lines
+}
+
+/// Split a list item into its display marker and text
+fn list_marker(line: &str) -> (Option<String>, &str) {
+    if let Some(rest) = ["- ", "* ", "+ "]
+        .iter()
+        .find_map(|bullet| line.strip_prefix(bullet))
+    {
+        return (Some("•".to_string()), rest);
+    }
+
+    let digits = line.chars().take_while(char::is_ascii_digit).count();
+    match line[digits..].strip_prefix(". ") {
+        Some(rest) if digits > 0 => (Some(line[..digits + 1].to_string()), rest),
+        _ => (None, line),
+    }
+}
+
+/// Style `code`, **bold**, *emphasis* and [links](url) within a line
+fn inline_spans(text: &str, base: Style) -> Vec<Span<'static>> {
+    let mut spans = Vec::new();
+    let mut plain = String::new();
+    let mut rest = text;
+
+    while let Some(c) = rest.chars().next() {
+        let styled = match c {
+            '`' => delimited(rest, "`").map(|(inner, len)| {
+                (
+                    vec![Span::styled(inner.to_string(), base.fg(Color::Yellow))],
+                    len,
+                )
+            }),
+            '*' if rest.starts_with("**") => delimited(rest, "**")
+                .map(|(inner, len)| (inline_spans(inner, base.add_modifier(Modifier::BOLD)), len)),
+            '*' => delimited(rest, "*").map(|(inner, len)| {
+                (
+                    inline_spans(inner, base.add_modifier(Modifier::ITALIC)),
+                    len,
+                )
+            }),
+            '[' => link(rest).map(|(label, url, len)| {
+                let mut link_spans = inline_spans(label, base.add_modifier(Modifier::UNDERLINED));
+                link_spans.push(Span::styled(format!(" ({url})"), base.fg(Color::DarkGray)));
+                (link_spans, len)
+            }),
+            _ => None,
+        };
+
+        match styled {
+            Some((styled_spans, len)) => {
+                if !plain.is_empty() {
+                    spans.push(Span::styled(std::mem::take(&mut plain), base));
+                }
+                spans.extend(styled_spans);
+                rest = &rest[len..];
+            }
+            None => {
+                plain.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+    }
+
+    if !plain.is_empty() {
+        spans.push(Span::styled(plain, base));
+    }
+    spans
+}
+
+/// Text between a leading delimiter and its closing one, with the total length consumed
+fn delimited<'a>(text: &'a str, delimiter: &str) -> Option<(&'a str, usize)> {
+    let after = &text[delimiter.len()..];
+    let end = after.find(delimiter).filter(|end| *end > 0)?;
+    Some((&after[..end], delimiter.len() * 2 + end))
+}
+
+/// `[label](url)` at the start of the text, with the total length consumed
+fn link(text: &str) -> Option<(&str, &str, usize)> {
+    let label_end = text.find("](")?;
+    let url_end = text[label_end + 2..].find(')')? + label_end + 2;
+    Some((
+        &text[1..label_end],
+        &text[label_end + 2..url_end],
+        url_end + 1,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texts(line: &Line) -> Vec<String> {
+        line.spans
+            .iter()
+            .map(|span| span.content.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_inline_spans() {
+        let spans = inline_spans(
+            "Use `id` or **both** [docs](https://x.io) *now*",
+            Style::default(),
+        );
+        let contents: Vec<&str> = spans.iter().map(|span| span.content.as_ref()).collect();
+        assert_eq!(
+            contents,
+            vec![
+                "Use ",
+                "id",
+                " or ",
+                "both",
+                " ",
+                "docs",
+                " (https://x.io)",
+                " ",
+                "now"
+            ]
+        );
+        assert_eq!(spans[1].style.fg, Some(Color::Yellow));
+        assert!(spans[3].style.add_modifier.contains(Modifier::BOLD));
+        assert!(spans[5].style.add_modifier.contains(Modifier::UNDERLINED));
+        assert!(spans[8].style.add_modifier.contains(Modifier::ITALIC));
+
+        // Unclosed markers stay literal
+        let spans = inline_spans("2 * 3 and `tick", Style::default());
+        assert_eq!(spans.len(), 1);
+        assert_eq!(spans[0].content, "2 * 3 and `tick");
+    }
+
+    #[test]
+    fn test_markdown_blocks() {
+        let lines = markdown_lines("# Title\n- one\n  2. two\n```\n# not a heading\n```\ntext");
+
+        assert_eq!(texts(&lines[0]), vec!["Title"]);
+        assert!(lines[0].spans[0]
+            .style
+            .add_modifier
+            .contains(Modifier::UNDERLINED));
+        assert_eq!(texts(&lines[1]), vec!["• ", "one"]);
+        assert_eq!(texts(&lines[2]), vec!["  2. ", "two"]);
+        assert_eq!(texts(&lines[3]), vec!["  # not a heading"]);
+        assert_eq!(texts(&lines[4]), vec!["text"]);
+    }
+}
@@ -6,18 +6,29 @@
 //! - `panels`: Main panels (endpoints list, details panel)
 //! - `tabs`: Detail tabs (endpoint, request, headers, response)
 //! - `styling`: Color schemes and style constants
+//! - `markdown`: Basic markdown rendering for spec descriptions
 
 mod components;
+mod markdown;
 mod modals;
 mod panels;
 mod styling;
 mod tabs;
 
 // Re-export public API to maintain compatibility
-pub use components::{render_footer, render_header, render_search_bar};
+pub use components::{
+    render_error_banner, render_footer, render_header, render_log_panel, render_metrics_overlay,
+    render_search_bar, render_status_line, render_toast, render_too_small, render_workspace_tabs,
+};
 pub use modals::{
-    render_body_input_modal, render_clear_confirmation_modal, render_token_input_modal,
-    render_url_input_modal,
+    render_body_input_modal, render_clear_confirmation_modal, render_console_modal,
+    render_csv_export_modal, render_diagnostics_export_modal, render_jobs_modal,
+    render_link_picker_modal, render_note_modal, render_param_form_modal, render_param_paste_modal,
+    render_plugin_commands_modal, render_reset_confirmation_modal, render_save_body_modal,
+    render_script_export_modal, render_server_form_modal, render_snapshot_name_modal,
+    render_spec_changes_modal, render_spec_discovery_modal, render_spec_info_modal,
+    render_spec_switcher_modal, render_stats_modal, render_token_input_modal,
+    render_traffic_export_modal, render_url_input_modal, render_variant_name_modal,
 };
-pub use panels::{render_details_panel, render_endpoints_panel};
-pub use tabs::try_format_json;
+pub use panels::{render_details_panel, render_endpoints_panel, render_response_panel};
+pub use styling::{screen_reader, set_appearance, strip_decorations};
@@ -4,18 +4,27 @@
 //! - URL configuration modal (Swagger URL + Base URL)
 //! - Token input modal
 //! - Clear confirmation modal
+//! - Bulk parameter form modal
+//! - Variant name, snapshot name, endpoint note and CSV export modals
+//! - Link picker, plugin command picker and console
+//! - Usage stats view
 
-use super::styling;
+use super::{markdown, styling};
+use crate::editor::ContentType;
+use crate::redact::{is_secret_name, mask_input};
+use crate::spec_diff::DiffKind;
 use crate::state::AppState;
-use crate::types::UrlInputField;
+use crate::stats;
+use crate::types::{ParameterType, SpecInfo, UrlInputField};
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
+    text::{Line, Span},
     widgets::{Block, Borders, Clear, Paragraph, Wrap},
     Frame,
 };
 
-/// Render the token input modal for bearer authentication
+/// Render the token input modal, asking for the credential of the chosen security scheme
 pub fn render_token_input_modal(frame: &mut Frame, state: &AppState) {
     let area = frame.area();
 
@@ -34,9 +43,805 @@ pub fn render_token_input_modal(frame: &mut Frame, state: &AppState) {
     // Clear the background behind the modal
     frame.render_widget(Clear, modal_area);
 
+    let schemes = &state.input.auth_schemes;
+    let (title, label) = match state.input.selected_auth_scheme() {
+        Some((name, scheme)) if schemes.len() > 1 => (
+            format!(
+                " {name}: {} ({}/{}) ",
+                scheme.describe(),
+                state.input.auth_scheme_index + 1,
+                schemes.len()
+            ),
+            scheme.prompt(),
+        ),
+        Some((name, scheme)) => (format!(" {name}: {} ", scheme.describe()), scheme.prompt()),
+        None => (" Enter Bearer Token ".to_string(), "Token:"),
+    };
+
     // Create modal block
     let block = Block::default()
-        .title(" Enter Bearer Token ")
+        .title(title)
+        .borders(Borders::ALL)
+        .border_style(
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )
+        .style(
+            Style::default()
+                .bg(styling::default_bg())
+                .fg(styling::default_fg()),
+        );
+
+    let inner = block.inner(modal_area);
+    frame.render_widget(block, modal_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .split(inner);
+
+    // Label
+    let label = Paragraph::new(label).style(Style::default().fg(Color::LightCyan));
+    frame.render_widget(label, chunks[0]);
+
+    // Input field - dots unless revealed
+    let token = if state.input.reveal_secrets {
+        state.input.token_input.clone()
+    } else {
+        mask_input(&state.input.token_input)
+    };
+    let input = Paragraph::new(token).style(
+        Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD),
+    );
+    frame.render_widget(input, chunks[1]);
+
+    // Help text
+    let reveal = if state.input.reveal_secrets {
+        "Hide"
+    } else {
+        "Reveal"
+    };
+    // With several schemes, switching them takes the place of the clear hint
+    let help = if schemes.len() > 1 {
+        format!("Enter: Save  |  Tab: Scheme  |  Ctrl+R: {reveal}  |  Esc: Cancel")
+    } else {
+        format!("Enter: Save  |  Ctrl+R: {reveal}  |  Ctrl+L: Clear  |  Esc: Cancel")
+    };
+    let help = Paragraph::new(help)
+        .style(Style::default().fg(styling::muted_fg()))
+        .alignment(Alignment::Center);
+    frame.render_widget(help, chunks[3]);
+}
+
+/// Render the modal asking for the name of a request variant
+pub fn render_variant_name_modal(frame: &mut Frame, state: &AppState) {
+    render_text_input_modal(
+        frame,
+        " Save Variant ",
+        "Name (an existing name is overwritten):",
+        &state.input.variant_name_input,
+        None,
+        "Enter: Save  |  Esc: Cancel",
+    );
+}
+
+/// Render the modal taking a JSON object or query string for the parameters
+pub fn render_param_paste_modal(frame: &mut Frame, state: &AppState) {
+    render_text_input_modal(
+        frame,
+        " Paste Parameters ",
+        "JSON object or query string, e.g. {\"id\": 7} or id=7&page=2:",
+        &state.input.param_paste_input,
+        state.input.param_paste_error.as_deref(),
+        "Enter: Fill  |  Esc: Cancel",
+    );
+}
+
+/// Render the modal asking for the name of a response snapshot
+pub fn render_snapshot_name_modal(frame: &mut Frame, state: &AppState) {
+    render_text_input_modal(
+        frame,
+        " Save Response Snapshot ",
+        "Name, e.g. \"before migration\" (an existing name is overwritten):",
+        &state.input.snapshot_name_input,
+        None,
+        "Enter: Save  |  Esc: Cancel",
+    );
+}
+
+/// Render the modal editing the note of the selected endpoint
+pub fn render_note_modal(frame: &mut Frame, state: &AppState) {
+    render_text_input_modal(
+        frame,
+        " Endpoint Note ",
+        "Note (leave empty to remove):",
+        &state.input.note_input,
+        None,
+        "Enter: Save  |  Ctrl+L: Clear  |  Esc: Cancel",
+    );
+}
+
+/// Render the modal asking where to export the response table as CSV
+pub fn render_csv_export_modal(frame: &mut Frame, state: &AppState) {
+    let rows = state
+        .response_table()
+        .map(|table| table.rows.len())
+        .unwrap_or(0);
+    render_text_input_modal(
+        frame,
+        " Export CSV ",
+        &format!("File path ({rows} rows, overwritten if it exists):"),
+        &state.input.export_path_input,
+        state.input.export_error.as_deref(),
+        "Enter: Export  |  Ctrl+L: Clear  |  Esc: Cancel",
+    );
+}
+
+/// Render the modal asking where to save a binary response body
+pub fn render_save_body_modal(frame: &mut Frame, state: &AppState) {
+    let size = state
+        .request
+        .current_response
+        .as_ref()
+        .map(|response| response.body.bytes().len())
+        .unwrap_or(0);
+    render_text_input_modal(
+        frame,
+        " Save Response Body ",
+        &format!("File path ({size} bytes, overwritten if it exists):"),
+        &state.input.export_path_input,
+        state.input.export_error.as_deref(),
+        "Enter: Save  |  Ctrl+L: Clear  |  Esc: Cancel",
+    );
+}
+
+/// Render the modal asking where to write a request, or all saved ones, as a shell script
+pub fn render_script_export_modal(frame: &mut Frame, state: &AppState) {
+    let scope = if state.input.export_collection {
+        let count: usize = state.request.variants.values().map(Vec::len).sum();
+        format!("all {count} saved requests")
+    } else {
+        "the selected request".to_string()
+    };
+    render_text_input_modal(
+        frame,
+        " Export Shell Script ",
+        &format!("File path for {scope} (overwritten if it exists):"),
+        &state.input.export_path_input,
+        state.input.export_error.as_deref(),
+        "Enter: Export  |  Tab: Request/Saved requests  |  Ctrl+L: Clear  |  Esc: Cancel",
+    );
+}
+
+/// Render the modal asking where to write the session's traffic log
+pub fn render_traffic_export_modal(frame: &mut Frame, state: &AppState) {
+    render_text_input_modal(
+        frame,
+        " Export Traffic ",
+        &format!(
+            "File path ({} requests; .har for HAR, .md for a report, else JSON Lines):",
+            state.request.traffic.len()
+        ),
+        &state.input.export_path_input,
+        state.input.export_error.as_deref(),
+        "Enter: Export  |  Ctrl+L: Clear  |  Esc: Cancel",
+    );
+}
+
+pub fn render_diagnostics_export_modal(frame: &mut Frame, state: &AppState) {
+    render_text_input_modal(
+        frame,
+        " Diagnostic Bundle ",
+        "File path (version, masked config, spec, recent actions and log):",
+        &state.input.export_path_input,
+        state.input.export_error.as_deref(),
+        "Enter: Write  |  Ctrl+L: Clear  |  Esc: Cancel",
+    );
+}
+
+/// Shared layout for single-line text input modals
+fn render_text_input_modal(
+    frame: &mut Frame,
+    title: &str,
+    label: &str,
+    value: &str,
+    error: Option<&str>,
+    help: &str,
+) {
+    let area = frame.area();
+
+    let modal_width = (area.width as f32 * 0.6).min(80.0) as u16;
+    let modal_height = 7;
+    let modal_x = (area.width.saturating_sub(modal_width)) / 2;
+    let modal_y = (area.height.saturating_sub(modal_height)) / 2;
+
+    let modal_area = Rect {
+        x: modal_x,
+        y: modal_y,
+        width: modal_width,
+        height: modal_height,
+    };
+
+    frame.render_widget(Clear, modal_area);
+
+    let block = Block::default()
+        .title(title.to_string())
+        .borders(Borders::ALL)
+        .border_style(
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )
+        .style(
+            Style::default()
+                .bg(styling::default_bg())
+                .fg(styling::default_fg()),
+        );
+
+    let inner = block.inner(modal_area);
+    frame.render_widget(block, modal_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .split(inner);
+
+    let label = Paragraph::new(label.to_string()).style(Style::default().fg(Color::LightCyan));
+    frame.render_widget(label, chunks[0]);
+
+    // Keep the end of long input (where the cursor is) visible
+    let width = chunks[1].width.saturating_sub(1) as usize;
+    let skip = value.chars().count().saturating_sub(width);
+    let visible: String = value.chars().skip(skip).collect();
+    let input = Paragraph::new(format!("{visible}█")).style(
+        Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD),
+    );
+    frame.render_widget(input, chunks[1]);
+
+    if let Some(error) = error {
+        let error = Paragraph::new(error.to_string()).style(Style::default().fg(Color::Red));
+        frame.render_widget(error, chunks[2]);
+    }
+
+    let help = Paragraph::new(help.to_string())
+        .style(Style::default().fg(styling::muted_fg()))
+        .alignment(Alignment::Center);
+    frame.render_widget(help, chunks[3]);
+}
+
+/// Render the bulk parameter form listing every path and query parameter
+pub fn render_param_form_modal(frame: &mut Frame, state: &AppState) {
+    let area = frame.area();
+    let form = &state.input.param_form;
+
+    let modal_width = (area.width as f32 * 0.7).min(100.0) as u16;
+    // One line per field plus borders, a spacer and the help line
+    let modal_height = (form.fields.len() as u16 + 4).min(area.height);
+    let modal_x = (area.width.saturating_sub(modal_width)) / 2;
+    let modal_y = (area.height.saturating_sub(modal_height)) / 2;
+
+    let modal_area = Rect {
+        x: modal_x,
+        y: modal_y,
+        width: modal_width,
+        height: modal_height,
+    };
+
+    frame.render_widget(Clear, modal_area);
+
+    let block = Block::default()
+        .title(format!(" Edit Parameters: {} ", form.endpoint_key))
+        .borders(Borders::ALL)
+        .border_style(
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )
+        .style(
+            Style::default()
+                .bg(styling::default_bg())
+                .fg(styling::default_fg()),
+        );
+
+    let inner = block.inner(modal_area);
+    frame.render_widget(block, modal_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .split(inner);
+
+    // Keep the active field visible when the form is taller than the modal
+    let visible = chunks[0].height as usize;
+    let skip = (form.active_field + 1).saturating_sub(visible);
+    let endpoint = state.get_endpoint_by_key(&form.endpoint_key);
+
+    let lines: Vec<Line> = form
+        .fields
+        .iter()
+        .enumerate()
+        .skip(skip)
+        .map(|(idx, field)| {
+            let is_active = idx == form.active_field;
+            let location = match field.param_type {
+                ParameterType::Path => "path",
+                ParameterType::Query => "query",
+                ParameterType::Header => "header",
+            };
+            let name_style = if is_active {
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::LightCyan)
+            };
+
+            let mut spans = vec![
+                Span::styled(if is_active { "▶ " } else { "  " }, name_style),
+                Span::styled(field.name.clone(), name_style),
+            ];
+            if field.required {
+                spans.push(Span::styled("*", Style::default().fg(Color::Red)));
+            }
+            spans.push(Span::styled(
+                format!(" ({}, {location}): ", field.type_info),
+                Style::default().fg(styling::muted_fg()),
+            ));
+            let value = if is_secret_name(&field.name) && !state.input.reveal_secrets {
+                mask_input(&field.value)
+            } else {
+                field.value.clone()
+            };
+            spans.push(Span::styled(value, Style::default().fg(Color::White)));
+            if is_active {
+                spans.push(Span::styled("█", Style::default().fg(Color::Yellow)));
+            } else if let Some(e) = endpoint
+                .as_ref()
+                .and_then(|ep| ep.parameters.iter().find(|p| p.name == field.name))
+                .and_then(|param| param.value_error(&field.value))
+            {
+                spans.push(Span::styled(
+                    format!("  ⚠ {e}"),
+                    Style::default().fg(Color::Red),
+                ));
+            }
+            Line::from(spans)
+        })
+        .collect();
+
+    frame.render_widget(Paragraph::new(lines), chunks[0]);
+
+    let has_secrets = form.fields.iter().any(|field| is_secret_name(&field.name));
+    let reveal = match (has_secrets, state.input.reveal_secrets) {
+        (false, _) => "",
+        (true, false) => "  |  Ctrl+R: Reveal",
+        (true, true) => "  |  Ctrl+R: Hide",
+    };
+    let help = Paragraph::new(format!(
+        "Tab/↑↓: Next field  |  Space: Toggle boolean{reveal}  |  Enter: Save all  |  Esc: Cancel"
+    ))
+    .style(Style::default().fg(styling::muted_fg()))
+    .alignment(Alignment::Center);
+    frame.render_widget(help, chunks[2]);
+}
+
+/// Render the server form: the picked server, its variables and the resulting base URL
+pub fn render_server_form_modal(frame: &mut Frame, state: &AppState) {
+    let area = frame.area();
+    let form = &state.input.server_form;
+
+    let modal_width = (area.width as f32 * 0.7).min(100.0) as u16;
+    // Server row, one line per variable, a spacer, the URL preview, a spacer, help and borders
+    let modal_height = (form.fields.len() as u16 + 7).min(area.height);
+    let modal_area = Rect {
+        x: (area.width.saturating_sub(modal_width)) / 2,
+        y: (area.height.saturating_sub(modal_height)) / 2,
+        width: modal_width,
+        height: modal_height,
+    };
+
+    frame.render_widget(Clear, modal_area);
+
+    let block = Block::default()
+        .title(format!(
+            " Server ({}/{}) ",
+            form.server_index + 1,
+            form.servers.len()
+        ))
+        .borders(Borders::ALL)
+        .border_style(
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )
+        .style(
+            Style::default()
+                .bg(styling::default_bg())
+                .fg(styling::default_fg()),
+        );
+
+    let inner = block.inner(modal_area);
+    frame.render_widget(block, modal_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .split(inner);
+
+    let row_style = |is_active: bool| {
+        if is_active {
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::LightCyan)
+        }
+    };
+    let marker = |is_active: bool| if is_active { "▶ " } else { "  " };
+
+    let mut lines: Vec<Line> = Vec::new();
+    if let Some(server) = form.server() {
+        let is_active = form.active_field == 0;
+        let mut spans = vec![
+            Span::styled(marker(is_active), row_style(is_active)),
+            Span::styled("server: ", row_style(is_active)),
+            Span::styled(server.url.clone(), Style::default().fg(Color::White)),
+        ];
+        if let Some(description) = &server.description {
+            spans.push(Span::styled(
+                format!("  {description}"),
+                Style::default().fg(styling::muted_fg()),
+            ));
+        }
+        lines.push(Line::from(spans));
+    }
+
+    for (idx, field) in form.fields.iter().enumerate() {
+        let is_active = form.active_field == idx + 1;
+        let mut spans = vec![
+            Span::styled(marker(is_active), row_style(is_active)),
+            Span::styled(format!("{}: ", field.name), row_style(is_active)),
+        ];
+        if field.choices.is_empty() {
+            spans.push(Span::styled(
+                field.value.clone(),
+                Style::default().fg(Color::White),
+            ));
+            if is_active {
+                spans.push(Span::styled("█", Style::default().fg(Color::Yellow)));
+            }
+        } else {
+            spans.push(Span::styled(
+                format!("◀ {} ▶", field.value),
+                Style::default().fg(Color::White),
+            ));
+            spans.push(Span::styled(
+                format!("  ({})", field.choices.join(", ")),
+                Style::default().fg(styling::muted_fg()),
+            ));
+        }
+        if let Some(description) = &field.description {
+            spans.push(Span::styled(
+                format!("  {description}"),
+                Style::default().fg(styling::muted_fg()),
+            ));
+        }
+        lines.push(Line::from(spans));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(vec![
+        Span::styled("Base URL: ", Style::default().fg(Color::Cyan)),
+        Span::styled(
+            form.url(),
+            Style::default()
+                .fg(Color::Green)
+                .add_modifier(Modifier::BOLD),
+        ),
+    ]));
+
+    frame.render_widget(Paragraph::new(lines), chunks[0]);
+
+    let help = Paragraph::new(
+        "Tab/↑↓: Next field  |  ←→: Switch server / value  |  Enter: Use as base URL  |  Esc: Cancel",
+    )
+    .style(Style::default().fg(styling::muted_fg()))
+    .alignment(Alignment::Center);
+    frame.render_widget(help, chunks[2]);
+}
+
+/// Render the list of links found in the current response
+pub fn render_link_picker_modal(frame: &mut Frame, state: &AppState) {
+    let area = frame.area();
+    let links = &state.input.links;
+
+    let modal_width = (area.width as f32 * 0.7).min(100.0) as u16;
+    // One line per link plus borders, a spacer and the help line
+    let modal_height = (links.len() as u16 + 4).min(area.height);
+    let modal_x = (area.width.saturating_sub(modal_width)) / 2;
+    let modal_y = (area.height.saturating_sub(modal_height)) / 2;
+
+    let modal_area = Rect {
+        x: modal_x,
+        y: modal_y,
+        width: modal_width,
+        height: modal_height,
+    };
+
+    frame.render_widget(Clear, modal_area);
+
+    let block = Block::default()
+        .title(format!(" Follow Link ({}) ", links.len()))
+        .borders(Borders::ALL)
+        .border_style(
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )
+        .style(
+            Style::default()
+                .bg(styling::default_bg())
+                .fg(styling::default_fg()),
+        );
+
+    let inner = block.inner(modal_area);
+    frame.render_widget(block, modal_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .split(inner);
+
+    // Keep the highlighted link visible when the list is taller than the modal
+    let visible = chunks[0].height as usize;
+    let skip = (state.input.link_index + 1).saturating_sub(visible);
+
+    let lines: Vec<Line> = links
+        .iter()
+        .enumerate()
+        .skip(skip)
+        .map(|(idx, link)| {
+            let is_active = idx == state.input.link_index;
+            let label_style = if is_active {
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::LightCyan)
+            };
+
+            Line::from(vec![
+                Span::styled(if is_active { "▶ " } else { "  " }, label_style),
+                Span::styled(format!("{}: ", link.label), label_style),
+                Span::styled(link.url.clone(), Style::default().fg(Color::White)),
+            ])
+        })
+        .collect();
+
+    frame.render_widget(Paragraph::new(lines), chunks[0]);
+
+    let help = Paragraph::new("↑↓/j/k: Select  |  Enter: GET with current auth  |  Esc: Cancel")
+        .style(Style::default().fg(styling::muted_fg()))
+        .alignment(Alignment::Center);
+    frame.render_widget(help, chunks[2]);
+}
+
+/// Render the picker of specs found in Docker containers and Kubernetes services
+pub fn render_spec_discovery_modal(frame: &mut Frame, state: &AppState) {
+    let area = frame.area();
+    let specs = state.input.discovered_specs.as_deref();
+
+    let modal_width = (area.width as f32 * 0.7).min(100.0) as u16;
+    // One line per spec (or the status line) plus borders, a spacer and the help line
+    let rows = specs.map_or(1, |specs| specs.len().max(1)) as u16;
+    let modal_height = (rows + 4).min(area.height);
+    let modal_x = (area.width.saturating_sub(modal_width)) / 2;
+    let modal_y = (area.height.saturating_sub(modal_height)) / 2;
+
+    let modal_area = Rect {
+        x: modal_x,
+        y: modal_y,
+        width: modal_width,
+        height: modal_height,
+    };
+
+    frame.render_widget(Clear, modal_area);
+
+    let block = Block::default()
+        .title(" Discovered Specs ")
+        .borders(Borders::ALL)
+        .border_style(
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )
+        .style(
+            Style::default()
+                .bg(styling::default_bg())
+                .fg(styling::default_fg()),
+        );
+
+    let inner = block.inner(modal_area);
+    frame.render_widget(block, modal_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .split(inner);
+
+    let lines: Vec<Line> = match specs {
+        None => vec![Line::styled(
+            "Looking for specs in Docker containers and Kubernetes services...",
+            Style::default().fg(Color::Gray),
+        )],
+        Some([]) => vec![Line::styled(
+            "No specs found. Label containers with lazy-swagger-tui.spec=<url or path>",
+            Style::default().fg(Color::Gray),
+        )],
+        Some(specs) => {
+            // Keep the highlighted spec visible when the list is taller than the modal
+            let visible = chunks[0].height as usize;
+            let skip = (state.input.discovered_index + 1).saturating_sub(visible);
+            specs
+                .iter()
+                .enumerate()
+                .skip(skip)
+                .map(|(idx, spec)| {
+                    let is_active = idx == state.input.discovered_index;
+                    let label_style = if is_active {
+                        Style::default()
+                            .fg(Color::Yellow)
+                            .add_modifier(Modifier::BOLD)
+                    } else {
+                        Style::default().fg(Color::LightCyan)
+                    };
+                    Line::from(vec![
+                        Span::styled(if is_active { "▶ " } else { "  " }, label_style),
+                        Span::styled(format!("{}: ", spec.source), label_style),
+                        Span::styled(spec.spec_url.clone(), Style::default().fg(Color::White)),
+                    ])
+                })
+                .collect()
+        }
+    };
+    frame.render_widget(Paragraph::new(lines), chunks[0]);
+
+    let help = Paragraph::new("↑↓/j/k: Select  |  Enter: Use  |  r: Search again  |  Esc: Back")
+        .style(Style::default().fg(styling::muted_fg()))
+        .alignment(Alignment::Center);
+    frame.render_widget(help, chunks[2]);
+}
+
+/// Render the console: earlier inputs with their output, newest at the bottom, and the input line
+pub fn render_console_modal(frame: &mut Frame, state: &AppState) {
+    let area = frame.area();
+
+    let modal_width = (area.width as f32 * 0.8).min(120.0) as u16;
+    let modal_height = (area.height as f32 * 0.7) as u16;
+    let modal_x = (area.width.saturating_sub(modal_width)) / 2;
+    let modal_y = (area.height.saturating_sub(modal_height)) / 2;
+
+    let modal_area = Rect {
+        x: modal_x,
+        y: modal_y,
+        width: modal_width,
+        height: modal_height,
+    };
+
+    frame.render_widget(Clear, modal_area);
+
+    let block = Block::default()
+        .title(" Console ")
+        .borders(Borders::ALL)
+        .border_style(
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )
+        .style(
+            Style::default()
+                .bg(styling::default_bg())
+                .fg(styling::default_fg()),
+        );
+
+    let inner = block.inner(modal_area);
+    frame.render_widget(block, modal_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .split(inner);
+
+    let mut lines: Vec<Line> = Vec::new();
+    for entry in &state.input.console_log {
+        lines.push(Line::from(Span::styled(
+            format!(": {}", entry.input),
+            Style::default().fg(styling::muted_fg()),
+        )));
+        match &entry.output {
+            Ok(output) => lines.extend(output.lines().map(|line| Line::from(line.to_string()))),
+            Err(error) => lines.push(Line::from(Span::styled(
+                error.clone(),
+                Style::default().fg(Color::Red),
+            ))),
+        }
+    }
+    // Show the newest output when the log is taller than the area
+    let skip = lines.len().saturating_sub(chunks[0].height as usize);
+    let log: Vec<Line> = lines.into_iter().skip(skip).collect();
+    frame.render_widget(Paragraph::new(log), chunks[0]);
+
+    let input = Paragraph::new(Line::from(vec![
+        Span::styled(": ", Style::default().fg(Color::Yellow)),
+        Span::raw(state.input.console_input.as_str()),
+        Span::styled("█", Style::default().fg(Color::Yellow)),
+    ]));
+    frame.render_widget(input, chunks[1]);
+
+    let help = Paragraph::new(
+        "endpoints, response, spec  |  len keys first last where map  |  name = expr  |  Esc: Close",
+    )
+    .style(Style::default().fg(styling::muted_fg()))
+    .alignment(Alignment::Center);
+    frame.render_widget(help, chunks[2]);
+}
+
+/// Render the plugin command picker with the output of the last command
+pub fn render_plugin_commands_modal(frame: &mut Frame, state: &AppState) {
+    let area = frame.area();
+    let commands = &state.input.plugin_commands;
+
+    let modal_width = (area.width as f32 * 0.7).min(100.0) as u16;
+    let modal_height = (area.height as f32 * 0.7) as u16;
+    let modal_x = (area.width.saturating_sub(modal_width)) / 2;
+    let modal_y = (area.height.saturating_sub(modal_height)) / 2;
+
+    let modal_area = Rect {
+        x: modal_x,
+        y: modal_y,
+        width: modal_width,
+        height: modal_height,
+    };
+
+    frame.render_widget(Clear, modal_area);
+
+    let block = Block::default()
+        .title(format!(" Plugin Commands ({}) ", commands.len()))
         .borders(Borders::ALL)
         .border_style(
             Style::default()
@@ -55,34 +860,434 @@ pub fn render_token_input_modal(frame: &mut Frame, state: &AppState) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
+            Constraint::Length((commands.len() as u16).min(inner.height / 2)),
             Constraint::Length(1),
+            Constraint::Min(1),
             Constraint::Length(1),
+        ])
+        .split(inner);
+
+    // Keep the highlighted command visible when the list is taller than its area
+    let visible = chunks[0].height as usize;
+    let skip = (state.input.plugin_command_index + 1).saturating_sub(visible);
+
+    let lines: Vec<Line> = commands
+        .iter()
+        .enumerate()
+        .skip(skip)
+        .map(|(idx, (plugin, command))| {
+            let is_active = idx == state.input.plugin_command_index;
+            let label_style = if is_active {
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::LightCyan)
+            };
+
+            Line::from(vec![
+                Span::styled(if is_active { "▶ " } else { "  " }, label_style),
+                Span::styled(format!("{plugin}: {}", command.name), label_style),
+                Span::styled(
+                    format!("  {}", command.description),
+                    Style::default().fg(styling::muted_fg()),
+                ),
+            ])
+        })
+        .collect();
+
+    frame.render_widget(Paragraph::new(lines), chunks[0]);
+
+    if let Some(output) = &state.input.plugin_output {
+        let output = Paragraph::new(output.as_str()).wrap(Wrap { trim: false });
+        frame.render_widget(output, chunks[2]);
+    }
+
+    let help = Paragraph::new("↑↓/j/k: Select  |  Enter: Run for selected endpoint  |  Esc: Close")
+        .style(Style::default().fg(styling::muted_fg()))
+        .alignment(Alignment::Center);
+    frame.render_widget(help, chunks[3]);
+}
+
+/// Render the specs of the config to switch to, with their URLs and base URLs
+pub fn render_spec_switcher_modal(frame: &mut Frame, state: &AppState) {
+    let area = frame.area();
+    let specs = &state.input.spec_list;
+
+    let modal_width = (area.width as f32 * 0.7).min(100.0) as u16;
+    let modal_height = (specs.len() as u16 * 2 + 4).clamp(6, area.height);
+    let modal_area = Rect {
+        x: (area.width.saturating_sub(modal_width)) / 2,
+        y: (area.height.saturating_sub(modal_height)) / 2,
+        width: modal_width,
+        height: modal_height,
+    };
+
+    frame.render_widget(Clear, modal_area);
+
+    let block = Block::default()
+        .title(format!(" Switch Spec ({}) ", specs.len()))
+        .borders(Borders::ALL)
+        .border_style(
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )
+        .style(
+            Style::default()
+                .bg(styling::default_bg())
+                .fg(styling::default_fg()),
+        );
+
+    let inner = block.inner(modal_area);
+    frame.render_widget(block, modal_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1)])
+        .split(inner);
+
+    // Two lines per spec; keep the highlighted one visible
+    let visible = (chunks[0].height / 2) as usize;
+    let skip = (state.input.spec_list_index + 1).saturating_sub(visible);
+
+    let lines: Vec<Line> = specs
+        .iter()
+        .enumerate()
+        .skip(skip)
+        .flat_map(|(idx, spec)| {
+            let is_active = idx == state.input.spec_list_index;
+            let name_style = if is_active {
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::LightCyan)
+            };
+            let auth = if spec.token.is_some() {
+                "  · token"
+            } else {
+                ""
+            };
+
+            [
+                Line::from(vec![
+                    Span::styled(if is_active { "▶ " } else { "  " }, name_style),
+                    Span::styled(spec.name.clone(), name_style),
+                ]),
+                Line::from(Span::styled(
+                    format!(
+                        "    {} → {}{auth}",
+                        spec.swagger_url,
+                        spec.base_url.as_deref().unwrap_or("spec's server")
+                    ),
+                    Style::default().fg(styling::muted_fg()),
+                )),
+            ]
+        })
+        .collect();
+
+    frame.render_widget(Paragraph::new(lines), chunks[0]);
+
+    let help = Paragraph::new("↑↓/j/k: Select  |  Enter: Open in its workspace tab  |  Esc: Close")
+        .style(Style::default().fg(styling::muted_fg()))
+        .alignment(Alignment::Center);
+    frame.render_widget(help, chunks[1]);
+}
+
+/// Render the queued and running requests in the order they were sent
+pub fn render_jobs_modal(frame: &mut Frame, state: &AppState) {
+    let area = frame.area();
+    let jobs = &state.request.jobs;
+
+    let modal_width = (area.width as f32 * 0.6).min(80.0) as u16;
+    let modal_height = (jobs.len() as u16 + 4).clamp(6, area.height);
+    let modal_area = Rect {
+        x: (area.width.saturating_sub(modal_width)) / 2,
+        y: (area.height.saturating_sub(modal_height)) / 2,
+        width: modal_width,
+        height: modal_height,
+    };
+
+    frame.render_widget(Clear, modal_area);
+
+    let block = Block::default()
+        .title(format!(
+            " Requests ({} running, {} queued) ",
+            jobs.len() - state.request.queued_jobs(),
+            state.request.queued_jobs()
+        ))
+        .borders(Borders::ALL)
+        .border_style(
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )
+        .style(
+            Style::default()
+                .bg(styling::default_bg())
+                .fg(styling::default_fg()),
+        );
+
+    let inner = block.inner(modal_area);
+    frame.render_widget(block, modal_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1)])
+        .split(inner);
+
+    let lines: Vec<Line> = if jobs.is_empty() {
+        vec![Line::from(Span::styled(
+            "No requests queued or running",
+            Style::default().fg(styling::muted_fg()),
+        ))]
+    } else {
+        // Keep the highlighted job visible when the list is taller than its area
+        let visible = chunks[0].height as usize;
+        let skip = (state.input.job_index + 1).saturating_sub(visible);
+
+        let mut queue_position = 0;
+        jobs.iter()
+            .enumerate()
+            .map(|(idx, job)| {
+                let status = match job.started {
+                    Some(started) => format!("  {:.1}s", started.elapsed().as_secs_f64()),
+                    None => {
+                        queue_position += 1;
+                        format!("  queued #{queue_position}")
+                    }
+                };
+                (idx, job, status)
+            })
+            .skip(skip)
+            .map(|(idx, job, status)| {
+                let is_active = idx == state.input.job_index;
+                let label_style = if is_active {
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(Color::LightCyan)
+                };
+
+                Line::from(vec![
+                    Span::styled(if is_active { "▶ " } else { "  " }, label_style),
+                    Span::styled(job.endpoint_key.clone(), label_style),
+                    Span::styled(status, Style::default().fg(styling::muted_fg())),
+                ])
+            })
+            .collect()
+    };
+
+    frame.render_widget(Paragraph::new(lines), chunks[0]);
+
+    let help = Paragraph::new("↑↓/j/k: Select  |  x: Cancel  |  X: Cancel all  |  Esc: Close")
+        .style(Style::default().fg(styling::muted_fg()))
+        .alignment(Alignment::Center);
+    frame.render_widget(help, chunks[1]);
+}
+
+/// Render the usage stats of the current spec's endpoints as a table
+pub fn render_stats_modal(frame: &mut Frame, state: &AppState) {
+    let area = frame.area();
+    let sort = state.ui.stats_sort;
+    let ranked = stats::ranked(&state.request.usage_stats, sort);
+
+    let modal_width = (area.width as f32 * 0.8).min(110.0) as u16;
+    let modal_height = (area.height as f32 * 0.7) as u16;
+    let modal_area = Rect {
+        x: (area.width.saturating_sub(modal_width)) / 2,
+        y: (area.height.saturating_sub(modal_height)) / 2,
+        width: modal_width,
+        height: modal_height,
+    };
+
+    frame.render_widget(Clear, modal_area);
+
+    let block = Block::default()
+        .title(format!(
+            " Usage Stats: {} ({}) ",
+            sort.label(),
+            ranked.len()
+        ))
+        .borders(Borders::ALL)
+        .border_style(
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )
+        .style(
+            Style::default()
+                .bg(styling::default_bg())
+                .fg(styling::default_fg()),
+        );
+
+    let inner = block.inner(modal_area);
+    frame.render_widget(block, modal_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
             Constraint::Length(1),
+            Constraint::Min(1),
             Constraint::Length(1),
         ])
         .split(inner);
 
-    // Label
-    let label = Paragraph::new("Token:").style(Style::default().fg(Color::LightCyan));
-    frame.render_widget(label, chunks[0]);
-
-    // Input field - show full token while editing
-    let input = Paragraph::new(state.input.token_input.clone()).style(
+    // Endpoint column takes what the four numeric columns leave
+    let key_width = (inner.width as usize).saturating_sub(40).max(10);
+    let header = Paragraph::new(format!(
+        "{:<key_width$} {:>6} {:>8} {:>8} {:>12}",
+        "Endpoint", "Runs", "Success", "Failed", "Median"
+    ))
+    .style(
         Style::default()
-            .fg(Color::Yellow)
+            .fg(Color::Cyan)
             .add_modifier(Modifier::BOLD),
     );
-    frame.render_widget(input, chunks[1]);
+    frame.render_widget(header, chunks[0]);
 
-    // Help text
-    let help = Paragraph::new("Enter: Save  |  Ctrl+L: Clear  |  Esc: Cancel")
+    let lines: Vec<Line> = if ranked.is_empty() {
+        vec![Line::from(Span::styled(
+            "No requests recorded for this spec yet",
+            Style::default().fg(styling::muted_fg()),
+        ))]
+    } else {
+        ranked
+            .iter()
+            .skip(state.ui.stats_scroll)
+            .map(|(key, endpoint)| {
+                let ratio = endpoint.success_ratio();
+                let ratio_color = if ratio >= 0.9 {
+                    Color::Green
+                } else if ratio >= 0.5 {
+                    Color::Yellow
+                } else {
+                    Color::Red
+                };
+                let median = endpoint
+                    .median_latency()
+                    .map(|median| format!("{}ms", median.as_millis()))
+                    .unwrap_or_else(|| "-".to_string());
+                let key: String = key.chars().take(key_width).collect();
+
+                Line::from(vec![
+                    Span::raw(format!("{key:<key_width$} {:>6} ", endpoint.runs)),
+                    Span::styled(
+                        format!("{:>7.0}%", ratio * 100.0),
+                        Style::default().fg(ratio_color),
+                    ),
+                    Span::raw(format!(" {:>8} {median:>12}", endpoint.failures)),
+                ])
+            })
+            .collect()
+    };
+    frame.render_widget(Paragraph::new(lines), chunks[1]);
+
+    let help = Paragraph::new("↑↓/j/k: Scroll  |  Tab: Most used / most failing  |  Esc: Close")
         .style(Style::default().fg(styling::muted_fg()))
         .alignment(Alignment::Center);
-    frame.render_widget(help, chunks[3]);
+    frame.render_widget(help, chunks[2]);
+}
+
+/// Render the endpoints the spec added, removed or changed since the previous load
+pub fn render_spec_changes_modal(frame: &mut Frame, state: &AppState) {
+    let Some(diff) = &state.data.spec_diff else {
+        return;
+    };
+    let area = frame.area();
+
+    let modal_width = (area.width as f32 * 0.8).min(110.0) as u16;
+    let modal_height = (area.height as f32 * 0.7) as u16;
+    let modal_area = Rect {
+        x: (area.width.saturating_sub(modal_width)) / 2,
+        y: (area.height.saturating_sub(modal_height)) / 2,
+        width: modal_width,
+        height: modal_height,
+    };
+
+    frame.render_widget(Clear, modal_area);
+
+    let block = Block::default()
+        .title(format!(
+            " Spec Changes: {} (found {}) ",
+            diff.summary(),
+            diff.found_at
+        ))
+        .borders(Borders::ALL)
+        .border_style(
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )
+        .style(
+            Style::default()
+                .bg(styling::default_bg())
+                .fg(styling::default_fg()),
+        );
+
+    let inner = block.inner(modal_area);
+    frame.render_widget(block, modal_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1)])
+        .split(inner);
+
+    let lines: Vec<Line> = diff
+        .lines()
+        .into_iter()
+        .skip(state.ui.spec_diff_scroll)
+        .map(|(kind, text)| match kind {
+            DiffKind::Added => Line::from(Span::styled(
+                format!("+ {text}"),
+                Style::default().fg(Color::Green),
+            )),
+            DiffKind::Removed => Line::from(Span::styled(
+                format!("- {text}"),
+                Style::default().fg(Color::Red),
+            )),
+            DiffKind::Changed => Line::from(Span::styled(
+                format!("~ {text}"),
+                Style::default().fg(Color::Yellow),
+            )),
+            DiffKind::Detail => Line::from(Span::styled(
+                format!("  {text}"),
+                Style::default().fg(styling::muted_fg()),
+            )),
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(lines), chunks[0]);
+
+    let help = Paragraph::new("↑↓/j/k: Scroll  |  Esc: Close")
+        .style(Style::default().fg(styling::muted_fg()))
+        .alignment(Alignment::Center);
+    frame.render_widget(help, chunks[1]);
 }
 
 /// Render the clear token confirmation modal
 pub fn render_clear_confirmation_modal(frame: &mut Frame) {
+    render_confirmation_modal(
+        frame,
+        " Clear Token? ",
+        "This will remove your authentication token.\nYou will need to re-enter it to make authenticated requests.",
+        "[Y] Yes, clear it  |  [N] Cancel",
+    );
+}
+
+/// Render the reset request config confirmation modal
+pub fn render_reset_confirmation_modal(frame: &mut Frame, endpoint_key: &str) {
+    render_confirmation_modal(
+        frame,
+        " Reset Request? ",
+        &format!("Clear all parameter values and the body of {endpoint_key}?\nSpec defaults will be applied again."),
+        "[Y] Yes, reset it  |  [N] Cancel",
+    );
+}
+
+/// Shared layout for yes/no confirmation modals
+fn render_confirmation_modal(frame: &mut Frame, title: &str, message: &str, actions: &str) {
     let area = frame.area();
 
     let modal_width = (area.width as f32 * 0.5).min(60.0) as u16;
@@ -102,7 +1307,7 @@ pub fn render_clear_confirmation_modal(frame: &mut Frame) {
 
     // Create modal block
     let block = Block::default()
-        .title(" Clear Token? ")
+        .title(title.to_string())
         .borders(Borders::ALL)
         .border_style(Style::default().fg(Color::Red).add_modifier(Modifier::BOLD))
         .style(
@@ -124,14 +1329,14 @@ pub fn render_clear_confirmation_modal(frame: &mut Frame) {
         .split(inner);
 
     // Message
-    let message = Paragraph::new("This will remove your authentication token.\nYou will need to re-enter it to make authenticated requests.")
+    let message = Paragraph::new(message.to_string())
         .style(Style::default().fg(styling::default_fg()))
         .alignment(Alignment::Center)
         .wrap(Wrap { trim: true });
     frame.render_widget(message, chunks[0]);
 
     // Actions
-    let actions = Paragraph::new("[Y] Yes, clear it  |  [N] Cancel")
+    let actions = Paragraph::new(actions.to_string())
         .style(
             Style::default()
                 .fg(Color::Yellow)
@@ -182,10 +1387,10 @@ pub fn render_url_input_modal(frame: &mut Frame, state: &AppState) {
             Constraint::Length(2), // Description
             Constraint::Length(1), // Swagger label
             Constraint::Length(1), // Swagger input
-            Constraint::Length(1), // Spacer
+            Constraint::Length(1), // Spacer / Swagger URL error
             Constraint::Length(1), // Base URL label
             Constraint::Length(1), // Base URL input
-            Constraint::Length(1), // Spacer
+            Constraint::Length(1), // Spacer / Base URL error
             Constraint::Length(1), // Help
         ])
         .split(inner);
@@ -214,8 +1419,15 @@ pub fn render_url_input_modal(frame: &mut Frame, state: &AppState) {
         }));
     frame.render_widget(swagger_label, chunks[1]);
 
+    // Inline completion from URL history (only for the active field)
+    let completion = state.input.url_completion().unwrap_or("");
+
     // Swagger URL input (highlighted if active)
-    let swagger_input = Paragraph::new(state.input.url_input.clone()).style(
+    let swagger_input = Paragraph::new(url_input_line(
+        &state.input.url_input,
+        if swagger_active { completion } else { "" },
+    ))
+    .style(
         Style::default()
             .fg(if swagger_active {
                 Color::Yellow
@@ -244,7 +1456,11 @@ pub fn render_url_input_modal(frame: &mut Frame, state: &AppState) {
     frame.render_widget(base_label, chunks[4]);
 
     // Base URL input (highlighted if active)
-    let base_input = Paragraph::new(state.input.base_url_input.clone()).style(
+    let base_input = Paragraph::new(url_input_line(
+        &state.input.base_url_input,
+        if base_active { completion } else { "" },
+    ))
+    .style(
         Style::default()
             .fg(if base_active {
                 Color::Yellow
@@ -259,16 +1475,160 @@ pub fn render_url_input_modal(frame: &mut Frame, state: &AppState) {
     );
     frame.render_widget(base_input, chunks[5]);
 
+    // Servers of the loaded spec to fill the base URL from
+    let servers = state.data.servers.len();
+    if base_active && servers > 0 && state.input.url_error.is_none() {
+        let noun = if servers == 1 { "server" } else { "servers" };
+        let hint = Paragraph::new(format!("↑/↓: use the loaded spec's {noun} ({servers})"))
+            .style(Style::default().fg(styling::muted_fg()));
+        frame.render_widget(hint, chunks[6]);
+    }
+
+    // Validation error under the offending field
+    if let Some((field, message)) = &state.input.url_error {
+        let error_chunk = match field {
+            UrlInputField::SwaggerUrl => chunks[3],
+            UrlInputField::BaseUrl => chunks[6],
+        };
+        let error = Paragraph::new(format!("⚠ {message}"))
+            .style(Style::default().fg(Color::Red).add_modifier(Modifier::BOLD));
+        frame.render_widget(error, error_chunk);
+    }
+
     // Help text
     let help = Paragraph::new(
-        "Tab: Switch fields  |  Ctrl+L: Clear field  |  Enter: Confirm  |  Esc: Cancel",
+        "Tab: Switch field  |  →: Accept suggestion  |  Ctrl+D: Discover  |  Ctrl+L: Clear  |  Enter: Confirm  |  Esc: Cancel",
     )
     .style(Style::default().fg(styling::muted_fg()))
     .alignment(Alignment::Center);
     frame.render_widget(help, chunks[7]);
 }
 
-/// Render the JSON body input modal for POST/PUT/PATCH requests
+/// Build a URL input line with a dimmed completion suffix after the typed text
+fn url_input_line(input: &str, completion: &str) -> Line<'static> {
+    Line::from(vec![
+        Span::raw(input.to_string()),
+        Span::styled(
+            completion.to_string(),
+            Style::default()
+                .fg(styling::muted_fg())
+                .remove_modifier(Modifier::BOLD),
+        ),
+    ])
+}
+
+/// Render the spec's `info` block: title, version, description, contact and license
+pub fn render_spec_info_modal(frame: &mut Frame, info: &SpecInfo) {
+    let area = frame.area();
+
+    let modal_width = (area.width as f32 * 0.8).min(100.0) as u16;
+    let modal_height = (area.height as f32 * 0.7).min(30.0) as u16;
+    let modal_area = Rect {
+        x: (area.width.saturating_sub(modal_width)) / 2,
+        y: (area.height.saturating_sub(modal_height)) / 2,
+        width: modal_width,
+        height: modal_height,
+    };
+
+    frame.render_widget(Clear, modal_area);
+
+    let block = Block::default()
+        .title(" API Info ")
+        .borders(Borders::ALL)
+        .border_style(
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )
+        .style(
+            Style::default()
+                .bg(styling::default_bg())
+                .fg(styling::default_fg()),
+        );
+
+    let inner = block.inner(modal_area);
+    frame.render_widget(block, modal_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .split(inner);
+
+    let label = |text: &'static str| Span::styled(text, Style::default().fg(Color::Cyan));
+    let mut lines: Vec<Line> = Vec::new();
+
+    match &info.title {
+        Some(title) => lines.push(Line::from(Span::styled(
+            title.clone(),
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ))),
+        None => lines.push(Line::from(Span::styled(
+            "The spec has no title",
+            Style::default().fg(styling::muted_fg()),
+        ))),
+    }
+    if let Some(version) = &info.version {
+        lines.push(Line::from(vec![
+            label("Version: "),
+            Span::raw(version.clone()),
+        ]));
+    }
+
+    if let Some(description) = info.description.as_deref().filter(|d| !d.trim().is_empty()) {
+        lines.push(Line::from(""));
+        lines.extend(markdown::markdown_lines(description));
+    }
+
+    let mut details = Vec::new();
+    if let Some(contact) = &info.contact {
+        let parts: Vec<String> = [
+            contact.name.clone(),
+            contact.email.as_ref().map(|email| format!("<{email}>")),
+            contact.url.clone(),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        if !parts.is_empty() {
+            details.push(Line::from(vec![
+                label("Contact: "),
+                Span::raw(parts.join(" ")),
+            ]));
+        }
+    }
+    if let Some(license) = &info.license {
+        let text = match (&license.name, &license.url) {
+            (Some(name), Some(url)) => format!("{name} ({url})"),
+            (Some(text), None) | (None, Some(text)) => text.clone(),
+            (None, None) => String::new(),
+        };
+        if !text.is_empty() {
+            details.push(Line::from(vec![label("License: "), Span::raw(text)]));
+        }
+    }
+    if let Some(terms) = &info.terms_of_service {
+        details.push(Line::from(vec![label("Terms: "), Span::raw(terms.clone())]));
+    }
+    if !details.is_empty() {
+        lines.push(Line::from(""));
+        lines.extend(details);
+    }
+
+    frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), chunks[0]);
+
+    let help = Paragraph::new("Esc/i: Close")
+        .style(Style::default().fg(styling::muted_fg()))
+        .alignment(Alignment::Center);
+    frame.render_widget(help, chunks[2]);
+}
+
+/// Render the body input modal for POST/PUT/PATCH requests
 pub fn render_body_input_modal(frame: &mut Frame, state: &AppState) {
     let area = frame.area();
 
@@ -287,8 +1647,9 @@ pub fn render_body_input_modal(frame: &mut Frame, state: &AppState) {
 
     frame.render_widget(Clear, modal_area);
 
+    let kind = state.input.body_editor.content_type().label();
     let block = Block::default()
-        .title(" Edit Request Body (JSON) ")
+        .title(format!(" Edit Request Body ({kind}) "))
         .borders(Borders::ALL)
         .border_style(
             Style::default()
@@ -330,7 +1691,16 @@ pub fn render_body_input_modal(frame: &mut Frame, state: &AppState) {
 
     // Label with cursor position
     let cursor_pos = state.input.body_editor.cursor_position_display();
-    let label = Paragraph::new(format!("JSON Body: {cursor_pos}"))
+    let draft_note = if state.input.body_draft_restored {
+        "  ·  restored unsaved draft (Ctrl+R: revert to saved body)"
+    } else {
+        ""
+    };
+    let form_note = match state.input.body_editor.content_type() {
+        ContentType::Form => "  ·  one name=value field per line",
+        _ => "",
+    };
+    let label = Paragraph::new(format!("{kind} Body: {cursor_pos}{form_note}{draft_note}"))
         .style(Style::default().fg(Color::LightGreen));
     frame.render_widget(label, chunks[0]);
 
@@ -358,7 +1728,7 @@ pub fn render_body_input_modal(frame: &mut Frame, state: &AppState) {
     // Help text (position depends on whether error is shown)
     let help_index = if has_error { 4 } else { 3 };
     let help = Paragraph::new(
-        "Enter: Save  |  Ctrl+N: New Line  |  Esc: Cancel  |  Ctrl+L: Clear  |  ↑↓←→: Navigate",
+        "Enter: Save  |  Ctrl+N: New Line  |  Ctrl+G: Sample data  |  Esc: Cancel  |  Ctrl+L: Clear  |  ↑↓←→: Navigate",
     )
     .style(Style::default().fg(styling::muted_fg()))
     .alignment(Alignment::Center);
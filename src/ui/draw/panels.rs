@@ -3,13 +3,15 @@
 //! This module contains rendering functions for the two main panels:
 //! - Endpoints panel (left side) - flat or grouped list
 //! - Details panel (right side) - tabs with endpoint details
+//! - Response panel (far right) - only in the three-pane layout
 
 use super::components::{
     render_empty_message, render_error_message, render_loading_spinner, render_no_search_results,
 };
 use super::{styling, tabs::*};
 use crate::state::AppState;
-use crate::types::{DetailTab, LoadingState, PanelFocus, RenderItem, ViewMode};
+use crate::types::{ApiEndpoint, DetailTab, LoadingState, PanelFocus, RenderItem, ViewMode};
+use crate::utils::truncate_middle;
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
@@ -32,12 +34,18 @@ pub fn render_endpoints_panel(
             render_loading_spinner(frame, area, &state.data.loading_state, spinner_index);
         }
         LoadingState::Error(error) => {
-            render_error_message(frame, area, error, state.data.retry_count);
+            render_error_message(
+                frame,
+                area,
+                error,
+                state.data.retry_count,
+                state.data.reconnect.as_ref(),
+            );
         }
         LoadingState::Complete | LoadingState::Idle => {
             if state.active_endpoints().is_empty() {
-                if !state.search.query.is_empty() {
-                    // Searching but no results
+                if state.search.is_filtering() {
+                    // Searching (or hiding deprecated endpoints) but no results
                     render_no_search_results(frame, area);
                 } else {
                     // No endpoints loaded
@@ -67,16 +75,24 @@ pub fn render_details_panel(
     // Get the selected endpoint
     let selected_endpoint = state.get_selected_endpoint(selected_index);
 
+    let shown_tab = state.details_pane_tab();
+
     // Determine border color based on panel focus
-    let border_color = if state.ui.panel_focus == PanelFocus::Details {
-        styling::focused_border()
-    } else {
-        styling::unfocused_border()
-    };
+    let border_color =
+        if state.ui.panel_focus == PanelFocus::Details && shown_tab == state.ui.active_detail_tab {
+            styling::focused_border()
+        } else {
+            styling::unfocused_border()
+        };
 
     // Create the main block
+    let title = if state.ui.three_pane {
+        "[2] Details"
+    } else {
+        "[2] Details & Response"
+    };
     let block = Block::default()
-        .title("[2] Details & Response")
+        .title(title)
         .borders(Borders::ALL)
         .border_style(Style::default().fg(border_color));
 
@@ -114,8 +130,8 @@ pub fn render_details_panel(
 
     // Render active tab content
     if let Some(endpoint) = selected_endpoint {
-        match state.ui.active_detail_tab {
-            DetailTab::Endpoint => render_endpoint_tab(frame, chunks[1], &endpoint),
+        match shown_tab {
+            DetailTab::Endpoint => render_endpoint_tab(frame, chunks[1], &endpoint, state),
             DetailTab::Request => render_request_tab(frame, chunks[1], &endpoint, state),
             DetailTab::Headers => render_headers_tab(frame, chunks[1], state),
             DetailTab::Response => render_response_tab(frame, chunks[1], &endpoint, state),
@@ -128,31 +144,107 @@ pub fn render_details_panel(
     }
 }
 
+/// Render the response pane of the three-pane layout
+///
+/// It has focus while the Response tab is active, so response keys work as on the tab.
+pub fn render_response_panel(
+    frame: &mut Frame,
+    area: Rect,
+    state: &AppState,
+    selected_index: usize,
+) {
+    let border_color = if state.ui.panel_focus == PanelFocus::Details
+        && state.ui.active_detail_tab == DetailTab::Response
+    {
+        styling::focused_border()
+    } else {
+        styling::unfocused_border()
+    };
+
+    let block = Block::default()
+        .title("Response")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(border_color));
+
+    let inner_area = block.inner(area);
+    frame.render_widget(block, area);
+
+    if !matches!(state.data.loading_state, LoadingState::Complete) {
+        return;
+    }
+
+    match state.get_selected_endpoint(selected_index) {
+        Some(endpoint) => render_response_tab(frame, inner_area, &endpoint, state),
+        None => {
+            let empty =
+                Paragraph::new("No endpoint selected").style(Style::default().fg(Color::DarkGray));
+            frame.render_widget(empty, inner_area);
+        }
+    }
+}
+
 // ============================================================================
 // Private Helper Functions
 // ============================================================================
 
+/// Columns left for a list entry inside the borders and the highlight symbol
+fn list_item_width(area: Rect) -> usize {
+    usize::from(area.width.saturating_sub(2 + 3))
+}
+
+/// List entry of an endpoint, its path shortened in the middle to fit `width` columns
+fn endpoint_line<'a>(
+    endpoint: &'a ApiEndpoint,
+    indent: &'static str,
+    show_service: bool,
+    width: usize,
+) -> Line<'a> {
+    let method_color = get_method_color(&endpoint.method);
+    // Deprecated endpoints are dimmed and struck through
+    let (method_modifier, path_style) = if endpoint.deprecated {
+        (
+            Modifier::DIM,
+            Style::default()
+                .fg(styling::muted_fg())
+                .add_modifier(Modifier::CROSSED_OUT),
+        )
+    } else {
+        (Modifier::BOLD, Style::default())
+    };
+    let mut spans = vec![
+        Span::raw(indent),
+        Span::styled(
+            styling::method_column(&endpoint.method),
+            Style::default()
+                .fg(method_color)
+                .add_modifier(method_modifier),
+        ),
+        Span::raw(" "),
+    ];
+    if let Some(service) = endpoint.service.as_ref().filter(|_| show_service) {
+        spans.push(Span::styled(
+            format!("{service} "),
+            Style::default().fg(styling::muted_fg()),
+        ));
+    }
+    let badge = styling::endpoint_badge(endpoint);
+
+    let used: usize = spans.iter().chain(&badge).map(Span::width).sum();
+    spans.push(Span::styled(
+        truncate_middle(&endpoint.path, width.saturating_sub(used)),
+        path_style,
+    ));
+    spans.extend(badge);
+    Line::from(spans)
+}
+
 /// Render flat endpoint list
 fn render_flat_list(frame: &mut Frame, area: Rect, state: &AppState, list_state: &mut ListState) {
+    let width = list_item_width(area);
     let items: Vec<ListItem> = state
         .active_endpoints()
         .iter()
-        .map(|endpoint| {
-            let method_color = get_method_color(&endpoint.method);
-
-            let line = Line::from(vec![
-                Span::styled(
-                    format!("{:7}", endpoint.method),
-                    Style::default()
-                        .fg(method_color)
-                        .add_modifier(Modifier::BOLD),
-                ),
-                Span::raw(" "),
-                Span::raw(&endpoint.path),
-            ]);
-
-            ListItem::new(line)
-        })
+        .map(|endpoint| ListItem::new(endpoint_line(endpoint, "", true, width)))
         .collect();
 
     // Determine border color based on panel focus
@@ -209,20 +301,8 @@ fn render_grouped_list(
                 items.push(ListItem::new(line));
             }
             RenderItem::Endpoint { endpoint } => {
-                let method_color = get_method_color(&endpoint.method);
-
-                let line = Line::from(vec![
-                    Span::raw("  "),
-                    Span::styled(
-                        format!("{:7}", endpoint.method),
-                        Style::default()
-                            .fg(method_color)
-                            .add_modifier(Modifier::BOLD),
-                    ),
-                    Span::raw(" "),
-                    Span::raw(&endpoint.path),
-                ]);
-
+                // The group header already names the service
+                let line = endpoint_line(endpoint, "  ", false, list_item_width(area));
                 items.push(ListItem::new(line));
             }
         }
@@ -239,8 +319,9 @@ fn render_grouped_list(
         .block(
             Block::default()
                 .title(format!(
-                    "[1] Endpoints - {} groups",
-                    state.active_grouped_endpoints().len()
+                    "[1] Endpoints - {} groups by {}",
+                    state.active_groups().len(),
+                    state.ui.group_by.label()
                 ))
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(border_color)),
@@ -259,8 +340,8 @@ fn render_grouped_list(
 fn render_tab_bar(frame: &mut Frame, area: Rect, state: &AppState) {
     let active_tab = &state.ui.active_detail_tab;
 
-    // Check if request is executing
-    let is_executing = state.request.executing_endpoint.is_some();
+    // Requests in flight, in any endpoint
+    let running = state.request.jobs.len();
 
     // Build tab labels with highlighting
     let endpoint_style = if *active_tab == DetailTab::Endpoint {
@@ -287,10 +368,10 @@ fn render_tab_bar(frame: &mut Frame, area: Rect, state: &AppState) {
         Style::default().fg(styling::default_fg())
     };
 
-    let response_label = if is_executing {
-        "Response (...)"
-    } else {
-        "Response"
+    let response_label = match running {
+        0 => "Response".to_string(),
+        1 => "Response (...)".to_string(),
+        running => format!("Response (... {running})"),
     };
 
     let response_style = if *active_tab == DetailTab::Response {
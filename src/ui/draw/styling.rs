@@ -5,21 +5,117 @@
 //! The app uses the terminal's default colors (Color::Reset) for text and backgrounds
 //! to respect the user's terminal theme, while using semantic colors (Green, Red, etc.)
 //! for syntax highlighting and status indicators.
+//!
+//! Methods, statuses and borders follow the palette picked in the config;
+//! method markers add a symbol per method for when colors can't be told apart.
+//! In screen reader mode, box and spinner characters are blanked out of every
+//! frame, so only text is left to read.
+
+use crate::config::{Palette, UiConfig};
+use crate::types::ApiEndpoint;
+use ratatui::buffer::Buffer;
+use ratatui::style::{Color, Style};
+use ratatui::text::Span;
+use std::sync::OnceLock;
+
+/// The `[ui]` settings drawing depends on, from the config at startup
+static APPEARANCE: OnceLock<UiConfig> = OnceLock::new();
 
-use ratatui::style::Color;
+/// Okabe-Ito colors, told apart with the common kinds of color blindness
+const ORANGE: Color = Color::Rgb(230, 159, 0);
+const SKY_BLUE: Color = Color::Rgb(86, 180, 233);
+const BLUISH_GREEN: Color = Color::Rgb(0, 158, 115);
+const YELLOW: Color = Color::Rgb(240, 228, 66);
+const VERMILLION: Color = Color::Rgb(213, 94, 0);
+const REDDISH_PURPLE: Color = Color::Rgb(204, 121, 167);
+
+/// Use the palette, method markers and screen reader mode of the config; only the first call counts
+pub fn set_appearance(config: &UiConfig) {
+    let _ = APPEARANCE.set(config.clone());
+}
+
+fn palette() -> Palette {
+    APPEARANCE.get().map(|ui| ui.palette).unwrap_or_default()
+}
+
+/// Whether screen reader mode is on
+pub fn screen_reader() -> bool {
+    APPEARANCE.get().is_some_and(|ui| ui.screen_reader)
+}
+
+/// Blank out box-drawing and braille spinner characters, which screen readers read out
+pub fn strip_decorations(buffer: &mut Buffer) {
+    for cell in buffer.content.iter_mut() {
+        let decoration = cell
+            .symbol()
+            .chars()
+            .next()
+            .is_some_and(|c| matches!(c, '\u{2500}'..='\u{257F}' | '\u{2800}'..='\u{28FF}'));
+        if decoration {
+            cell.set_symbol(" ");
+        }
+    }
+}
 
 /// Get the color for an HTTP method
 pub fn get_method_color(method: &str) -> Color {
-    match method {
-        "GET" => Color::Green,
-        "POST" => Color::Blue,
-        "PUT" => Color::Yellow,
-        "DELETE" => Color::Red,
-        "PATCH" => Color::Cyan,
+    method_color(palette(), method)
+}
+
+fn method_color(palette: Palette, method: &str) -> Color {
+    match (palette, method) {
+        (Palette::Default, "GET") => Color::Green,
+        (Palette::Default, "POST") => Color::Blue,
+        (Palette::Default, "PUT") => Color::Yellow,
+        (Palette::Default, "DELETE") => Color::Red,
+        (Palette::Default, "PATCH") => Color::Cyan,
+        (Palette::ColorBlind, "GET") => SKY_BLUE,
+        (Palette::ColorBlind, "POST") => YELLOW,
+        (Palette::ColorBlind, "PUT") => ORANGE,
+        (Palette::ColorBlind, "DELETE") => REDDISH_PURPLE,
+        (Palette::ColorBlind, "PATCH") => BLUISH_GREEN,
+        (Palette::HighContrast, "GET") => Color::LightGreen,
+        (Palette::HighContrast, "POST") => Color::LightBlue,
+        (Palette::HighContrast, "PUT") => Color::LightYellow,
+        (Palette::HighContrast, "DELETE") => Color::LightRed,
+        (Palette::HighContrast, "PATCH") => Color::LightCyan,
+        (Palette::HighContrast, _) => Color::White,
         _ => Color::Reset, // Use terminal default
     }
 }
 
+/// Symbol telling a method apart without its color
+fn method_marker(method: &str) -> &'static str {
+    match method {
+        "GET" => "↓",
+        "POST" => "+",
+        "PUT" => "=",
+        "PATCH" => "~",
+        "DELETE" => "×",
+        _ => "·",
+    }
+}
+
+fn method_markers() -> bool {
+    APPEARANCE.get().is_some_and(|ui| ui.method_markers)
+}
+
+/// A method as shown, with its marker when markers are on (e.g. `+ POST`)
+pub fn method_label(method: &str) -> String {
+    if method_markers() {
+        format!("{} {method}", method_marker(method))
+    } else {
+        method.to_string()
+    }
+}
+
+/// A method label padded so the paths after it line up in the endpoint list
+pub fn method_column(method: &str) -> String {
+    // Wide enough for `OPTIONS`, and its marker
+    let width = if method_markers() { 9 } else { 7 };
+    format!("{:width$}", method_label(method))
+}
+
 /// Get the default foreground color (uses terminal theme)
 pub fn default_fg() -> Color {
     Color::Reset
@@ -32,15 +128,251 @@ pub fn default_bg() -> Color {
 
 /// Get a dimmed/muted text color for help text and labels
 pub fn muted_fg() -> Color {
-    Color::DarkGray
+    match palette() {
+        Palette::HighContrast => Color::Gray,
+        _ => Color::DarkGray,
+    }
 }
 
 /// Get the border color for focused panels
 pub fn focused_border() -> Color {
-    Color::Cyan
+    match palette() {
+        Palette::Default => Color::Cyan,
+        Palette::ColorBlind => SKY_BLUE,
+        Palette::HighContrast => Color::LightYellow,
+    }
 }
 
 /// Get the border color for unfocused panels
 pub fn unfocused_border() -> Color {
-    Color::DarkGray
+    muted_fg()
+}
+
+/// Get the color for an HTTP status code by class (0 means no response)
+pub fn status_color(status: u16) -> Color {
+    status_class_color(palette(), status)
+}
+
+fn status_class_color(palette: Palette, status: u16) -> Color {
+    match (palette, status) {
+        (Palette::Default, 200..=299) => Color::Green,
+        (Palette::Default, 300..=399) => Color::Cyan,
+        (Palette::Default, 400..=499) => Color::Yellow,
+        (Palette::Default, _) => Color::Red,
+        (Palette::ColorBlind, 200..=299) => SKY_BLUE,
+        (Palette::ColorBlind, 300..=399) => BLUISH_GREEN,
+        (Palette::ColorBlind, 400..=499) => ORANGE,
+        (Palette::ColorBlind, _) => VERMILLION,
+        (Palette::HighContrast, 200..=299) => Color::LightGreen,
+        (Palette::HighContrast, 300..=399) => Color::LightCyan,
+        (Palette::HighContrast, 400..=499) => Color::LightYellow,
+        (Palette::HighContrast, _) => Color::LightRed,
+    }
+}
+
+/// Lock badge for the endpoint list: locked with the scheme names, or open for anonymous access
+fn security_badge(endpoint: &ApiEndpoint) -> Vec<Span<'static>> {
+    match endpoint.allows_anonymous() {
+        Some(false) => vec![
+            Span::styled(" 🔒 ", Style::default().fg(Color::Yellow)),
+            Span::styled(
+                endpoint.security_label(false),
+                Style::default().fg(muted_fg()),
+            ),
+        ],
+        Some(true) => vec![Span::styled(" 🔓", Style::default().fg(muted_fg()))],
+        None => Vec::new(),
+    }
+}
+
+/// Badge after a list entry: the event kind for callbacks and webhooks, else the security badge
+pub fn endpoint_badge(endpoint: &ApiEndpoint) -> Vec<Span<'static>> {
+    match &endpoint.event {
+        Some(event) => vec![Span::styled(
+            format!(" ⇠ {}", event.label()),
+            Style::default().fg(muted_fg()),
+        )],
+        None => security_badge(endpoint),
+    }
+}
+
+/// Syntax-highlight one line of pretty-printed JSON
+///
+/// Keys, strings, numbers and literals get their own colors; anything else
+/// (braces, commas, indentation) keeps the default color.
+pub fn json_line_spans(line: &str) -> Vec<Span<'static>> {
+    let mut spans = Vec::new();
+    let mut rest = line;
+
+    while let Some(c) = rest.chars().next() {
+        let (len, color) = if c == '"' {
+            let len = json_string_len(rest);
+            let is_key = rest[len..].trim_start().starts_with(':');
+            (len, if is_key { Color::Cyan } else { Color::Green })
+        } else if c == '-' || c.is_ascii_digit() {
+            let len = rest
+                .find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '+' | '.')))
+                .unwrap_or(rest.len());
+            (len, Color::Yellow)
+        } else if let Some(word) = ["true", "false", "null"]
+            .iter()
+            .find(|word| rest.starts_with(*word))
+        {
+            (word.len(), Color::Magenta)
+        } else {
+            let len = rest
+                .find(|c: char| c == '"' || c == '-' || c.is_ascii_alphanumeric())
+                .filter(|&len| len > 0)
+                .unwrap_or(c.len_utf8());
+            (len, default_fg())
+        };
+
+        spans.push(Span::styled(
+            rest[..len].to_string(),
+            Style::default().fg(color),
+        ));
+        rest = &rest[len..];
+    }
+    spans
+}
+
+/// Mark case-insensitive (ASCII) occurrences of `query` across a line's spans
+pub fn highlight_matches(spans: Vec<Span<'static>>, query: &str) -> Vec<Span<'static>> {
+    if query.is_empty() {
+        return spans;
+    }
+    let line: String = spans.iter().map(|span| span.content.as_ref()).collect();
+    let needle = query.to_ascii_lowercase();
+    let ranges: Vec<(usize, usize)> = line
+        .to_ascii_lowercase()
+        .match_indices(&needle)
+        .map(|(start, _)| (start, start + needle.len()))
+        .collect();
+    if ranges.is_empty() {
+        return spans;
+    }
+
+    let match_style = Style::default().bg(Color::Yellow).fg(Color::Black);
+    let mut highlighted = Vec::new();
+    let mut offset = 0;
+    for span in spans {
+        let (start, end) = (offset, offset + span.content.len());
+        offset = end;
+
+        let mut pos = start;
+        for &(match_start, match_end) in &ranges {
+            let (from, to) = (match_start.max(start), match_end.min(end));
+            if from >= to {
+                continue;
+            }
+            if pos < from {
+                highlighted.push(Span::styled(line[pos..from].to_string(), span.style));
+            }
+            highlighted.push(Span::styled(
+                line[from..to].to_string(),
+                span.style.patch(match_style),
+            ));
+            pos = to;
+        }
+        if pos < end {
+            highlighted.push(Span::styled(line[pos..end].to_string(), span.style));
+        }
+    }
+    highlighted
+}
+
+/// Length of the string literal at the start of `text`, including both quotes
+fn json_string_len(text: &str) -> usize {
+    let mut escaped = false;
+    for (i, c) in text.char_indices().skip(1) {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '"' => return i + 1,
+            _ => {}
+        }
+    }
+    text.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_line_spans() {
+        let spans = json_line_spans(r#"  "na\"me": "x", "n": -1.5e3, "ok": null"#);
+        let tokens: Vec<(&str, Color)> = spans
+            .iter()
+            .map(|span| (span.content.as_ref(), span.style.fg.unwrap()))
+            .collect();
+
+        assert_eq!(
+            tokens,
+            vec![
+                ("  ", default_fg()),
+                (r#""na\"me""#, Color::Cyan),
+                (": ", default_fg()),
+                (r#""x""#, Color::Green),
+                (", ", default_fg()),
+                (r#""n""#, Color::Cyan),
+                (": ", default_fg()),
+                ("-1.5e3", Color::Yellow),
+                (", ", default_fg()),
+                (r#""ok""#, Color::Cyan),
+                (": ", default_fg()),
+                ("null", Color::Magenta),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_palettes_keep_methods_and_statuses_apart() {
+        let methods = ["GET", "POST", "PUT", "DELETE", "PATCH"];
+        for palette in [Palette::Default, Palette::ColorBlind, Palette::HighContrast] {
+            let colors: Vec<Color> = methods
+                .iter()
+                .map(|method| method_color(palette, method))
+                .collect();
+            for (i, color) in colors.iter().enumerate() {
+                assert!(!colors[i + 1..].contains(color), "{palette:?}");
+            }
+            assert_ne!(
+                status_class_color(palette, 200),
+                status_class_color(palette, 500)
+            );
+        }
+
+        let mut markers: Vec<&str> = methods.iter().map(|m| method_marker(m)).collect();
+        markers.sort_unstable();
+        markers.dedup();
+        assert_eq!(markers.len(), methods.len());
+    }
+
+    #[test]
+    fn test_strip_decorations() {
+        let mut buffer = Buffer::with_lines(["┌─ Spec ─┐", "│⠋ GET   │"]);
+        strip_decorations(&mut buffer);
+        assert_eq!(buffer, Buffer::with_lines(["   Spec   ", "   GET    "]));
+    }
+
+    #[test]
+    fn test_highlight_matches_across_spans() {
+        let spans = vec![Span::raw("\"user"), Span::raw("Name\": 1")];
+        let highlighted = highlight_matches(spans, "username");
+        let parts: Vec<(&str, Option<Color>)> = highlighted
+            .iter()
+            .map(|span| (span.content.as_ref(), span.style.bg))
+            .collect();
+
+        assert_eq!(
+            parts,
+            vec![
+                ("\"", None),
+                ("user", Some(Color::Yellow)),
+                ("Name", Some(Color::Yellow)),
+                ("\": 1", None),
+            ]
+        );
+    }
 }
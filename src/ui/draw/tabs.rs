@@ -4,30 +4,58 @@
 //! - Endpoint tab (method, path, summary, tags)
 //! - Request tab (parameters with inline editing)
 //! - Headers tab (response headers)
-//! - Response tab (response body with JSON formatting)
-
-use super::styling;
+//! - Response tab (response body with JSON formatting, or a table for arrays)
+
+use super::{markdown, styling};
+use crate::content::BodyKind;
+use crate::datetime::DateFormat;
+use crate::editor::{self, ContentType};
+use crate::problem::ProblemDetails;
+use crate::schema;
+use crate::snapshot::{diff_lines, DiffLine, ResponseSnapshot};
 use crate::state::AppState;
-use crate::types::{ApiEndpoint, ApiParameter, DetailTab, RequestEditMode};
+use crate::table::ResponseTable;
+use crate::types::{
+    ApiEndpoint, ApiParameter, ApiResponse, DeclaredResponse, DetailTab, ParameterType,
+    RequestConfig, RequestEditMode, ResponseExample, SchemaModel,
+};
+use crate::utils::{sparkline, truncate_middle};
 use ratatui::{
-    layout::Rect,
+    layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Paragraph, Wrap},
+    widgets::{Block, Borders, Paragraph, Row, Table, TableState, Wrap},
     Frame,
 };
 use std::collections::HashMap;
 use styling::get_method_color;
 
+/// Number of recent status codes shown in the Response tab
+const STATUS_STRIP_LEN: usize = 10;
+
+/// Widest a response table column may get
+const MAX_TABLE_COLUMN_WIDTH: usize = 30;
+
+/// Fields of a body schema outlined in the Request tab before the rest are counted
+const MAX_SCHEMA_FIELDS: usize = 30;
+
+/// Top-level fields outlined per declared response in the Endpoint tab
+const MAX_RESPONSE_FIELDS: usize = 8;
+
 /// Render the Endpoint tab content
-pub fn render_endpoint_tab(frame: &mut Frame, area: Rect, endpoint: &ApiEndpoint) {
+pub fn render_endpoint_tab(
+    frame: &mut Frame,
+    area: Rect,
+    endpoint: &ApiEndpoint,
+    state: &AppState,
+) {
     let mut lines: Vec<Line> = Vec::new();
 
     let method_color = get_method_color(&endpoint.method);
 
     lines.push(Line::from(vec![
         Span::styled(
-            &endpoint.method,
+            styling::method_label(&endpoint.method),
             Style::default()
                 .fg(method_color)
                 .add_modifier(Modifier::BOLD),
@@ -38,6 +66,30 @@ pub fn render_endpoint_tab(frame: &mut Frame, area: Rect, endpoint: &ApiEndpoint
 
     lines.push(Line::from("")); // Empty line
 
+    if endpoint.deprecated {
+        lines.push(Line::from(Span::styled(
+            "⚠ Deprecated: may be removed in a future version of the API",
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )));
+    }
+
+    if let Some(event) = &endpoint.event {
+        lines.push(Line::from(vec![
+            Span::styled("Sent by the API: ", Style::default().fg(Color::Cyan)),
+            Span::raw(event.label()),
+        ]));
+    }
+
+    if let Some(service) = &endpoint.service {
+        let base_url = endpoint.base_url.as_deref().unwrap_or("no base URL");
+        lines.push(Line::from(vec![
+            Span::styled("Service: ", Style::default().fg(Color::Cyan)),
+            Span::raw(format!("{service} ({base_url})")),
+        ]));
+    }
+
     if let Some(summary) = &endpoint.summary {
         lines.push(Line::from(vec![
             Span::styled("Summary: ", Style::default().fg(Color::Cyan)),
@@ -52,6 +104,72 @@ pub fn render_endpoint_tab(frame: &mut Frame, area: Rect, endpoint: &ApiEndpoint
         ]));
     }
 
+    if let Some(anonymous) = endpoint.allows_anonymous() {
+        let schemes = endpoint.security_label(true);
+        let auth = match (anonymous, schemes.is_empty()) {
+            (false, _) => Span::styled(format!("🔒 {schemes}"), Style::default().fg(Color::Yellow)),
+            (true, true) => Span::raw("🔓 none required"),
+            (true, false) => Span::raw(format!("🔓 optional: {schemes}")),
+        };
+        lines.push(Line::from(vec![
+            Span::styled("Auth: ", Style::default().fg(Color::Cyan)),
+            auth,
+        ]));
+        for name in endpoint.security_scheme_names() {
+            if let Some(scheme) = state.data.security_schemes.get(name) {
+                lines.push(Line::from(Span::styled(
+                    format!("  {name}: {}", scheme.describe()),
+                    Style::default().fg(Color::DarkGray),
+                )));
+            }
+        }
+    }
+
+    if let Some(description) = endpoint
+        .description
+        .as_deref()
+        .filter(|d| !d.trim().is_empty())
+    {
+        lines.push(Line::from("")); // Empty line
+        lines.push(Line::from(Span::styled(
+            "Description:",
+            Style::default().fg(Color::Cyan),
+        )));
+        lines.extend(markdown::markdown_lines(description));
+    }
+
+    if !endpoint.responses.is_empty() {
+        lines.push(Line::from("")); // Empty line
+        push_response_lines(&mut lines, &endpoint.responses, area.width);
+    }
+
+    if !endpoint.extensions.is_empty() {
+        lines.push(Line::from("")); // Empty line
+        push_extension_lines(
+            &mut lines,
+            &endpoint.extensions,
+            state.ui.extensions_expanded,
+        );
+    }
+
+    lines.push(Line::from("")); // Empty line
+    match state.data.notes.get(&endpoint.key()) {
+        Some(note) => {
+            lines.push(Line::from(vec![
+                Span::styled("Notes: ", Style::default().fg(Color::Cyan)),
+                Span::styled(note.clone(), Style::default().fg(Color::Yellow)),
+            ]));
+            lines.push(Line::from(Span::styled(
+                "[Press 'N' to edit the note]",
+                Style::default().fg(Color::DarkGray),
+            )));
+        }
+        None => lines.push(Line::from(Span::styled(
+            "[Press 'N' to add a note]",
+            Style::default().fg(Color::DarkGray),
+        ))),
+    }
+
     let content = Paragraph::new(lines)
         .wrap(Wrap { trim: false })
         .style(Style::default().fg(styling::default_fg()));
@@ -59,16 +177,118 @@ pub fn render_endpoint_tab(frame: &mut Frame, area: Rect, endpoint: &ApiEndpoint
     frame.render_widget(content, area);
 }
 
+/// Declared responses: status and description, then the body's type, top-level fields and example
+fn push_response_lines(lines: &mut Vec<Line>, responses: &[DeclaredResponse], width: u16) {
+    lines.push(Line::from(Span::styled(
+        "Responses:",
+        Style::default().fg(Color::Cyan),
+    )));
+
+    for response in responses {
+        // Ranges such as `4XX` take the color of their class, `default` that of errors
+        let class = response.status.chars().next().and_then(|c| c.to_digit(10));
+        let color = styling::status_color(class.map_or(0, |class| class as u16 * 100));
+        let mut spans = vec![
+            Span::raw("  "),
+            Span::styled(
+                response.status.clone(),
+                Style::default().fg(color).add_modifier(Modifier::BOLD),
+            ),
+        ];
+        if let Some(description) = response
+            .description
+            .as_deref()
+            .and_then(|d| d.lines().next())
+        {
+            spans.push(Span::raw(format!("  {description}")));
+        }
+        lines.push(Line::from(spans));
+
+        if let Some(schema) = &response.model {
+            let media_type = response.media_type.as_deref().unwrap_or("body");
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("    {media_type}: "),
+                    Style::default().fg(Color::DarkGray),
+                ),
+                Span::styled(
+                    schema::type_summary(schema),
+                    Style::default().fg(Color::Yellow),
+                ),
+            ]));
+            // Top-level fields only, and those of each alternative of a composed response
+            let outline = schema::outline(schema);
+            let max_depth = usize::from(outline.iter().any(|f| f.variant && f.depth == 0));
+            let fields: Vec<schema::OutlineField> = outline
+                .into_iter()
+                .filter(|field| field.depth <= max_depth)
+                .map(|field| schema::OutlineField {
+                    depth: field.depth + 1,
+                    ..field
+                })
+                .collect();
+            push_outline_lines(lines, &fields, MAX_RESPONSE_FIELDS);
+        }
+        if let Some(example) = &response.example {
+            let label = "    Example: ";
+            let budget = (width as usize).saturating_sub(label.len() + 2);
+            lines.push(Line::from(vec![
+                Span::styled(label, Style::default().fg(Color::DarkGray)),
+                Span::raw(truncate_middle(&example.to_string(), budget).into_owned()),
+            ]));
+        }
+    }
+}
+
+/// Collapsible list of vendor extensions; objects and arrays are pretty-printed below their name
+fn push_extension_lines(
+    lines: &mut Vec<Line>,
+    extensions: &[(String, serde_json::Value)],
+    expanded: bool,
+) {
+    let icon = if expanded { "▼" } else { "▶" };
+    lines.push(Line::from(vec![
+        Span::styled(
+            format!("{icon} Extensions ({})", extensions.len()),
+            Style::default().fg(Color::Cyan),
+        ),
+        Span::styled("  [x: toggle]", Style::default().fg(Color::DarkGray)),
+    ]));
+    if !expanded {
+        return;
+    }
+
+    for (name, value) in extensions {
+        let name_span = Span::styled(format!("  {name}: "), Style::default().fg(Color::Magenta));
+        match value {
+            serde_json::Value::Object(_) | serde_json::Value::Array(_) => {
+                lines.push(Line::from(name_span));
+                let pretty = serde_json::to_string_pretty(value).unwrap_or_default();
+                for line in pretty.lines() {
+                    let mut spans = vec![Span::raw("    ")];
+                    spans.extend(styling::json_line_spans(line));
+                    lines.push(Line::from(spans));
+                }
+            }
+            serde_json::Value::String(text) => {
+                lines.push(Line::from(vec![name_span, Span::raw(text.clone())]));
+            }
+            other => {
+                lines.push(Line::from(vec![name_span, Span::raw(other.to_string())]));
+            }
+        }
+    }
+}
+
 /// Render the Request tab content (parameters, etc.)
 pub fn render_request_tab(frame: &mut Frame, area: Rect, endpoint: &ApiEndpoint, state: &AppState) {
     let mut lines: Vec<Line> = Vec::new();
 
-    // Get path and query parameters for this endpoint
-    let path_params: Vec<&ApiParameter> = endpoint.path_params();
-    let query_params: Vec<&ApiParameter> = endpoint.query_params();
+    // Get path, query and header parameters for this endpoint
+    let params = endpoint.request_params();
 
     // Check if there are ANY parameters or body support
-    if path_params.is_empty() && query_params.is_empty() && !endpoint.supports_body() {
+    if params.is_empty() && !endpoint.supports_body() {
         lines.push(Line::from(Span::styled(
             "No parameters defined for this endpoint",
             Style::default().fg(Color::DarkGray),
@@ -80,10 +300,42 @@ pub fn render_request_tab(frame: &mut Frame, area: Rect, endpoint: &ApiEndpoint,
     }
 
     // Get request config for this endpoint
-    let config = state.request.configs.get(&endpoint.path);
+    let config = state.request.configs.get(&endpoint.key());
+
+    // Saved variants of this endpoint
+    if let Some(variants) = state
+        .request
+        .variants
+        .get(&endpoint.key())
+        .filter(|v| !v.is_empty())
+    {
+        let active = state.request.active_variant.get(&endpoint.key());
+        let label = match active.and_then(|index| variants.get(*index)) {
+            Some(variant) => format!(
+                "Variant: {} ({}/{})",
+                variant.name,
+                active.unwrap() + 1,
+                variants.len()
+            ),
+            None => format!("Variants: {} saved", variants.len()),
+        };
+        lines.push(Line::from(vec![
+            Span::styled(
+                label,
+                Style::default()
+                    .fg(Color::LightBlue)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                "  [S: save, V: switch]",
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]));
+        lines.push(Line::from(""));
+    }
 
     // Show helpful message if no parameters but has body support
-    if path_params.is_empty() && query_params.is_empty() && endpoint.supports_body() {
+    if params.is_empty() && endpoint.supports_body() {
         lines.push(Line::from(Span::styled(
             "No parameters defined for this endpoint",
             Style::default().fg(Color::DarkGray),
@@ -91,105 +343,25 @@ pub fn render_request_tab(frame: &mut Frame, area: Rect, endpoint: &ApiEndpoint,
         lines.push(Line::from("")); // Empty line
     }
 
-    let total_path_params = path_params.len();
-
-    // ===== SECTION 1: Path Parameters =====
-    if !path_params.is_empty() {
-        lines.push(Line::from(Span::styled(
-            "Path Parameters:",
-            Style::default()
-                .fg(Color::Magenta)
-                .add_modifier(Modifier::BOLD),
-        )));
-        lines.push(Line::from("")); // Empty line
-
-        // Display each path parameter
-        for (idx, param) in path_params.iter().enumerate() {
-            let global_idx = idx; // Path params come first
-            let is_selected = state.ui.selected_param_index == global_idx;
-
-            let current_value =
-                if let RequestEditMode::Editing(editing_param_name) = &state.request.edit_mode {
-                    if editing_param_name == &param.name {
-                        state.request.param_edit_buffer.as_str()
-                    } else {
-                        config
-                            .and_then(|c| c.get_param_value(&param.name))
-                            .unwrap_or("")
-                    }
-                } else {
-                    config
-                        .and_then(|c| c.get_param_value(&param.name))
-                        .unwrap_or("")
-                };
-
-            let is_editing = matches!(
-                &state.request.edit_mode,
-                RequestEditMode::Editing(name) if name == &param.name
-            );
-
-            let line = build_param_line(
-                param,
-                current_value,
-                is_selected,
-                is_editing,
-                true, // is_path_param
-            );
-            lines.push(line);
-        }
-
-        lines.push(Line::from("")); // Empty line after path params
-    }
-
-    // ===== SECTION 2: Query Parameters =====
-    if !query_params.is_empty() {
-        lines.push(Line::from(Span::styled(
-            "Query Parameters:",
-            Style::default()
-                .fg(Color::Cyan)
-                .add_modifier(Modifier::BOLD),
-        )));
-        lines.push(Line::from("")); // Empty line
-
-        // Display each query parameter
-        for (idx, param) in query_params.iter().enumerate() {
-            let global_idx = total_path_params + idx; // Offset by path param count
-            let is_selected = state.ui.selected_param_index == global_idx;
-
-            let current_value =
-                if let RequestEditMode::Editing(editing_param_name) = &state.request.edit_mode {
-                    if editing_param_name == &param.name {
-                        state.request.param_edit_buffer.as_str()
-                    } else {
-                        config
-                            .and_then(|c| c.get_param_value(&param.name))
-                            .unwrap_or("")
-                    }
-                } else {
-                    config
-                        .and_then(|c| c.get_param_value(&param.name))
-                        .unwrap_or("")
-                };
-
-            let is_editing = matches!(
-                &state.request.edit_mode,
-                RequestEditMode::Editing(name) if name == &param.name
-            );
-
-            let line = build_param_line(
-                param,
-                current_value,
-                is_selected,
-                is_editing,
-                false, // is_path_param
-            );
-            lines.push(line);
+    // ===== SECTIONS 1-3: Path, Query and Header Parameters =====
+    let mut offset = 0;
+    for param_type in [
+        ParameterType::Path,
+        ParameterType::Query,
+        ParameterType::Header,
+    ] {
+        let section: Vec<&ApiParameter> = params
+            .iter()
+            .filter(|(_, t)| *t == param_type)
+            .map(|(param, _)| *param)
+            .collect();
+        if !section.is_empty() {
+            push_param_section(&mut lines, &section, &param_type, offset, config, state);
+            offset += section.len();
         }
-
-        lines.push(Line::from("")); // Empty line after query params
     }
 
-    // ===== SECTION 3: Request Body (for POST/PUT/PATCH) =====
+    // ===== SECTION 4: Request Body (for POST/PUT/PATCH) =====
     if endpoint.supports_body() {
         lines.push(Line::from("")); // Empty line
 
@@ -199,7 +371,12 @@ pub fn render_request_tab(frame: &mut Frame, area: Rect, endpoint: &ApiEndpoint,
         } else {
             "▶"
         };
-        let header_text = format!("{expand_icon} Request Body:");
+        let required_marker = if endpoint.body_required() {
+            " (required)"
+        } else {
+            ""
+        };
+        let header_text = format!("{expand_icon} Request Body{required_marker}:");
 
         lines.push(Line::from(vec![
             Span::styled(
@@ -215,14 +392,59 @@ pub fn render_request_tab(frame: &mut Frame, area: Rect, endpoint: &ApiEndpoint,
             ),
         ]));
 
+        let content_types = endpoint
+            .request_body
+            .as_ref()
+            .map(|body| body.content_types())
+            .unwrap_or_default();
+        if !content_types.is_empty() {
+            let current = endpoint.body_content_type(config);
+            let mut spans = vec![Span::raw("  Content-Type: ")];
+            for media_type in &content_types {
+                spans.push(if *media_type == current {
+                    Span::styled(
+                        format!("● {media_type}  "),
+                        Style::default()
+                            .fg(Color::Green)
+                            .add_modifier(Modifier::BOLD),
+                    )
+                } else {
+                    Span::styled(
+                        format!("○ {media_type}  "),
+                        Style::default().fg(Color::DarkGray),
+                    )
+                });
+            }
+            if content_types.len() > 1 {
+                spans.push(Span::styled(
+                    "[c: switch]",
+                    Style::default().fg(Color::DarkGray),
+                ));
+            }
+            lines.push(Line::from(spans));
+        }
+
+        let schema = endpoint
+            .request_body
+            .as_ref()
+            .and_then(|body| body.model_for(&endpoint.body_content_type(config)));
+        if let (true, Some(schema)) = (state.ui.body_section_expanded, schema) {
+            push_schema_lines(&mut lines, schema);
+        }
+
         if state.ui.body_section_expanded {
             lines.push(Line::from("")); // Empty line
 
-            // Get current body value
+            // Get current body value, form fields shown as in the editor
             let body_value = config
                 .and_then(|c| c.body.as_ref())
                 .map(|s| s.as_str())
                 .unwrap_or("{}");
+            let body_value = match ContentType::for_media_type(&endpoint.body_content_type(config))
+            {
+                ContentType::Form => editor::form_lines(body_value),
+                _ => body_value.to_string(),
+            };
 
             // Display body (truncate if too long)
             let body_lines: Vec<&str> = body_value.lines().collect();
@@ -245,7 +467,7 @@ pub fn render_request_tab(frame: &mut Frame, area: Rect, endpoint: &ApiEndpoint,
         lines.push(Line::from("")); // Empty line after body
     }
 
-    // ===== SECTION 4: URL Preview =====
+    // ===== SECTION 5: URL Preview =====
     lines.push(Line::from(Span::styled(
         "Preview URL:",
         Style::default()
@@ -253,8 +475,8 @@ pub fn render_request_tab(frame: &mut Frame, area: Rect, endpoint: &ApiEndpoint,
             .add_modifier(Modifier::BOLD),
     )));
 
-    // Build preview URL with both path and query params
-    let preview_url = if let Some(config) = config {
+    // Build preview URL with both path and query params, as sent with the templates
+    let preview_url = if let Some(config) = state.request.merged_config(endpoint, config) {
         let path_params = config.path_params_map();
         let query_params = config.query_params_map();
         build_preview_url(&endpoint.path, &path_params, &query_params)
@@ -267,18 +489,31 @@ pub fn render_request_tab(frame: &mut Frame, area: Rect, endpoint: &ApiEndpoint,
         Style::default().fg(Color::Yellow),
     )));
 
-    // ===== SECTION 5: Help Text =====
+    let templates = state.request.template_names(endpoint);
+    if !templates.is_empty() {
+        lines.push(Line::from(Span::styled(
+            format!("Templates: {}", templates.join(", ")),
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+
+    // ===== SECTION 6: Help Text =====
     lines.push(Line::from("")); // Empty line
 
     let help_text = match &state.request.edit_mode {
         RequestEditMode::Viewing => {
             if endpoint.supports_body() {
-                "j/k/↑/↓: Navigate  |  e: Edit param  |  b: Edit body  |  x: Toggle body  |  Space: Execute"
+                "j/k/↑/↓: Navigate  |  e: Edit param  |  E: Edit all  |  d: Defaults  |  R: Reset  |  S/V: Variants  |  b: Edit body  |  c: Content type  |  x: Toggle body  |  Space: Execute"
             } else {
-                "j/k/↑/↓: Navigate  |  e: Edit parameter  |  Space: Execute"
+                "j/k/↑/↓: Navigate  |  e: Edit parameter  |  E: Edit all  |  d: Fill defaults  |  R: Reset  |  S/V: Variants  |  Space: Execute"
             }
         }
-        RequestEditMode::Editing(_) => "Type to edit  |  Enter: Confirm  |  Esc: Cancel",
+        RequestEditMode::Editing(_) if !state.request.param_suggestions().is_empty() => {
+            "Type to edit  |  ↑/↓: Pick suggestion  |  Tab: Accept  |  Enter: Confirm  |  Esc: Cancel"
+        }
+        RequestEditMode::Editing(_) => {
+            "Type to edit  |  Ctrl+U/N/T: UUID/number/timestamp  |  Enter: Confirm  |  Esc: Cancel"
+        }
     };
 
     lines.push(Line::from(Span::styled(
@@ -290,6 +525,142 @@ pub fn render_request_tab(frame: &mut Frame, area: Rect, endpoint: &ApiEndpoint,
     frame.render_widget(content, area);
 }
 
+/// A titled section of one type of parameters; `offset` is the Request tab index of its first
+fn push_param_section(
+    lines: &mut Vec<Line>,
+    params: &[&ApiParameter],
+    param_type: &ParameterType,
+    offset: usize,
+    config: Option<&RequestConfig>,
+    state: &AppState,
+) {
+    let title = match param_type {
+        ParameterType::Path => "Path Parameters:",
+        ParameterType::Query => "Query Parameters:",
+        ParameterType::Header => "Header Parameters:",
+    };
+    lines.push(Line::from(Span::styled(
+        title,
+        Style::default()
+            .fg(param_color(param_type))
+            .add_modifier(Modifier::BOLD),
+    )));
+    lines.push(Line::from("")); // Empty line
+
+    for (idx, param) in params.iter().enumerate() {
+        let is_selected = state.ui.selected_param_index == offset + idx;
+        let is_editing = matches!(
+            &state.request.edit_mode,
+            RequestEditMode::Editing(name) if name == &param.name
+        );
+        let current_value = if is_editing {
+            state.request.param_edit_buffer.as_str()
+        } else {
+            config
+                .and_then(|c| c.get_param_value(&param.name))
+                .unwrap_or("")
+        };
+
+        lines.push(build_param_line(
+            param,
+            current_value,
+            is_selected,
+            is_editing,
+            param_type,
+        ));
+        if is_editing {
+            push_suggestion_lines(lines, state);
+        }
+    }
+
+    lines.push(Line::from("")); // Empty line after the section
+}
+
+/// Color of a parameter section's title and of its selected parameter
+fn param_color(param_type: &ParameterType) -> Color {
+    match param_type {
+        ParameterType::Path => Color::Magenta,
+        ParameterType::Query => Color::Cyan,
+        ParameterType::Header => Color::LightBlue,
+    }
+}
+
+/// Outline of the expected body: one line per field, nested fields indented
+fn push_schema_lines(lines: &mut Vec<Line>, schema: &SchemaModel) {
+    lines.push(Line::from(vec![
+        Span::raw("  Expected: "),
+        Span::styled(
+            schema::type_summary(schema),
+            Style::default().fg(Color::Yellow),
+        ),
+    ]));
+
+    push_outline_lines(lines, &schema::outline(schema), MAX_SCHEMA_FIELDS);
+}
+
+/// One line per outlined field below a schema's summary, at most `max_fields`
+fn push_outline_lines(lines: &mut Vec<Line>, fields: &[schema::OutlineField], max_fields: usize) {
+    for field in fields.iter().take(max_fields) {
+        if field.variant {
+            lines.push(Line::from(vec![
+                Span::raw(format!("    {}", "  ".repeat(field.depth))),
+                Span::styled(
+                    format!("{} ", field.summary),
+                    Style::default().fg(Color::DarkGray),
+                ),
+                Span::styled(field.name.clone(), Style::default().fg(Color::Magenta)),
+            ]));
+            continue;
+        }
+        let mut spans = vec![
+            Span::raw(format!("    {}", "  ".repeat(field.depth))),
+            Span::styled(field.name.clone(), Style::default().fg(Color::Cyan)),
+        ];
+        if field.required {
+            spans.push(Span::styled("*", Style::default().fg(Color::Red)));
+        }
+        spans.push(Span::raw(": "));
+        spans.push(Span::styled(
+            field.summary.clone(),
+            Style::default().fg(Color::Yellow),
+        ));
+        if let Some(description) = field.description.as_deref().and_then(|d| d.lines().next()) {
+            spans.push(Span::styled(
+                format!("  {description}"),
+                Style::default().fg(Color::DarkGray),
+            ));
+        }
+        lines.push(Line::from(spans));
+    }
+    if fields.len() > max_fields {
+        lines.push(Line::from(Span::styled(
+            format!("    … {} more fields", fields.len() - max_fields),
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+}
+
+/// Render the value history dropdown below the parameter being edited
+fn push_suggestion_lines(lines: &mut Vec<Line>, state: &AppState) {
+    let suggestions = state.request.param_suggestions();
+    let selected = state
+        .request
+        .suggestion_index
+        .min(suggestions.len().saturating_sub(1));
+
+    for (idx, value) in suggestions.iter().enumerate() {
+        let style = if idx == selected {
+            Style::default().fg(Color::Black).bg(Color::Yellow)
+        } else {
+            Style::default().fg(Color::DarkGray)
+        };
+        lines.push(Line::from(vec![
+            Span::raw("      "),
+            Span::styled(format!(" {value} "), style),
+        ]));
+    }
+}
+
 /// Render the Headers tab content
 pub fn render_headers_tab(frame: &mut Frame, area: Rect, state: &AppState) {
     let mut lines: Vec<Line> = Vec::new();
@@ -300,9 +671,12 @@ pub fn render_headers_tab(frame: &mut Frame, area: Rect, state: &AppState) {
             header_vec.sort_by_key(|(k, _)| k.as_str());
 
             for (key, value) in header_vec {
+                // Long values (cookies, tokens) are shortened to stay on one line
+                let name = Span::styled(format!("{key}: "), Style::default().fg(Color::Cyan));
+                let width = usize::from(area.width).saturating_sub(name.width());
                 lines.push(Line::from(vec![
-                    Span::styled(format!("{key}: "), Style::default().fg(Color::Cyan)),
-                    Span::raw(value.to_string()),
+                    name,
+                    Span::raw(truncate_middle(value, width).into_owned()),
                 ]));
             }
         } else {
@@ -318,7 +692,9 @@ pub fn render_headers_tab(frame: &mut Frame, area: Rect, state: &AppState) {
         )));
     }
 
-    let content = Paragraph::new(lines).wrap(Wrap { trim: false });
+    let content = Paragraph::new(lines)
+        .wrap(Wrap { trim: false })
+        .scroll((state.ui.headers_scroll as u16, 0));
 
     frame.render_widget(content, area);
 }
@@ -332,11 +708,21 @@ pub fn render_response_tab(
 ) {
     let mut lines: Vec<Line> = Vec::new();
 
-    let is_executing = state.request.executing_endpoint.as_ref() == Some(&endpoint.path);
+    let endpoint_key = endpoint.key();
+    let job = state
+        .request
+        .jobs
+        .iter()
+        .find(|job| job.endpoint_key == endpoint_key);
 
-    if is_executing {
+    if let Some(job) = job {
+        let status = if job.started.is_some() {
+            "⏳ Executing request..."
+        } else {
+            "⏳ Queued behind other requests (J: show queue)"
+        };
         lines.push(Line::from(vec![Span::styled(
-            "⏳ Executing request...",
+            status,
             Style::default().fg(Color::Cyan),
         )]));
     } else if let Some(ref response) = state.request.current_response {
@@ -355,42 +741,82 @@ pub fn render_response_tab(
                     )));
                 }
             }
+        } else if let Some(snapshot) = state.active_snapshot(&endpoint.key()) {
+            render_snapshot_diff(frame, area, response, snapshot, endpoint, state);
+            return;
+        } else if let Some(table) = state.response_table() {
+            render_response_table(frame, area, response, &table, endpoint, state);
+            return;
         } else {
-            // Show status line
-            lines.push(Line::from(vec![
-                Span::styled("Status: ", Style::default().fg(Color::Cyan)),
-                Span::styled(
-                    format!("{} {}", response.status, response.status_text),
-                    Style::default().fg(Color::Green),
-                ),
-                Span::raw("  "),
-                Span::styled("Duration: ", Style::default().fg(Color::Cyan)),
-                Span::raw(format!("{}ms", response.duration.as_millis())),
-            ]));
-            lines.push(Line::from("")); // Empty line
+            // Problem details get a structured block above the raw body
+            let area = match &state.request.response_problem {
+                Some(problem) => render_problem_block(frame, area, problem),
+                None => area,
+            };
 
-            // Show formatted body
-            let formatted_body = try_format_json(&response.body);
-            for (idx, line) in formatted_body.lines().enumerate() {
-                // Highlight selected line when in Response tab
+            // Only build the lines inside the viewport, bodies can be huge
+            let first = state.ui.response_scroll;
+            let last = first + area.height as usize;
+            let body_lines = state.visible_response_lines();
+
+            for total_line_idx in first..(body_lines.len() + 2).min(last) {
                 // response_selected_line counts from 0 including header (status=0, empty=1, body starts at 2)
-                let total_line_idx = idx + 2; // Add 2 for status and empty line
-                let line_style = if state.ui.active_detail_tab == DetailTab::Response
-                    && state.ui.response_selected_line == total_line_idx
+                let line = match total_line_idx {
+                    0 => {
+                        let mut status = response_status_line(response);
+                        status.spans.extend(latency_spans(state, endpoint));
+                        status.spans.extend(reauth_spans(state));
+                        status.spans.push(body_kind_span(state.request.body_kind));
+                        lines.push(status);
+                        continue;
+                    }
+                    1 => {
+                        lines.push(status_history_line(state, endpoint));
+                        continue;
+                    }
+                    _ => &body_lines[total_line_idx - 2],
+                };
+
+                // Highlight selected line when in Response tab
+                let in_visual = state
+                    .response_visual_range()
+                    .is_some_and(|(start, end)| (start..=end).contains(&total_line_idx));
+                let spans = if state.ui.active_detail_tab == DetailTab::Response
+                    && (state.ui.response_selected_line == total_line_idx || in_visual)
                 {
                     // Flash green if yank just happened, otherwise gray
-                    if state.ui.yank_flash {
+                    let line_style = if state.ui.yank_flash {
                         Style::default()
                             .bg(Color::Green)
                             .fg(Color::Black)
                             .add_modifier(Modifier::BOLD)
+                    } else if in_visual {
+                        Style::default().bg(Color::Blue).fg(Color::White)
                     } else {
                         Style::default().bg(Color::DarkGray)
-                    }
+                    };
+                    vec![Span::styled(line.clone(), line_style)]
+                } else if state.request.body_kind == BodyKind::Json {
+                    styling::json_line_spans(line)
                 } else {
-                    Style::default()
+                    vec![Span::raw(line.clone())]
                 };
-                lines.push(Line::from(Span::styled(line.to_string(), line_style)));
+                lines.push(Line::from(styling::highlight_matches(
+                    spans,
+                    &state.search.response_query,
+                )));
+            }
+
+            let hidden = state.request.response_lines.len() - body_lines.len();
+            if hidden > 0 && body_lines.len() + 2 < last {
+                lines.push(Line::from(Span::styled(
+                    format!(
+                        "… showing first {} of {} lines, press L to load more or O to open in pager",
+                        body_lines.len(),
+                        state.request.response_lines.len()
+                    ),
+                    Style::default().fg(Color::Yellow),
+                )));
             }
         }
     } else {
@@ -398,11 +824,17 @@ pub fn render_response_tab(
             "Press [Space] to execute request",
             Style::default().fg(Color::DarkGray),
         )));
+
+        if let Some(example) = &endpoint.response_example {
+            lines.push(Line::from(""));
+            push_example_lines(&mut lines, example);
+        }
     }
 
-    let content = Paragraph::new(lines)
-        .wrap(Wrap { trim: false })
-        .scroll((state.ui.response_scroll as u16, 0));
+    let mut content = Paragraph::new(lines);
+    if state.ui.response_wrap {
+        content = content.wrap(Wrap { trim: false });
+    }
 
     frame.render_widget(content, area);
 }
@@ -411,6 +843,32 @@ pub fn render_response_tab(
 // Helper Functions
 // ============================================================================
 
+/// Spec example under a label making clear it is not a real response
+fn push_example_lines(lines: &mut Vec<Line>, example: &ResponseExample) {
+    let mut label = format!("Example from spec · {}", example.status);
+    if let Some(media_type) = &example.media_type {
+        label.push_str(&format!(" · {media_type}"));
+    }
+    lines.push(Line::from(Span::styled(
+        label,
+        Style::default()
+            .fg(Color::Magenta)
+            .add_modifier(Modifier::BOLD | Modifier::ITALIC),
+    )));
+
+    let is_json = example
+        .media_type
+        .as_deref()
+        .is_none_or(|media_type| media_type.contains("json"));
+    for line in example.lines() {
+        lines.push(Line::from(if is_json {
+            styling::json_line_spans(&line)
+        } else {
+            vec![Span::raw(line)]
+        }));
+    }
+}
+
 /// Build URL preview with path and query parameters
 fn build_preview_url(
     path_template: &str,
@@ -452,26 +910,12 @@ fn build_param_line(
     current_value: &str,
     is_selected: bool,
     is_editing: bool,
-    is_path_param: bool,
+    param_type: &ParameterType,
 ) -> Line<'static> {
-    // Build type info string (e.g., "integer/int32" or "boolean")
-    let type_info = if let Some(schema) = &param.schema {
-        let type_str = schema.param_type.as_deref().unwrap_or("unknown");
-        if let Some(format) = &schema.format {
-            format!("{type_str}/{format}")
-        } else {
-            type_str.to_string()
-        }
-    } else {
-        "unknown".to_string()
-    };
+    let type_info = param.type_info();
 
     // Build required indicator
-    let required_str = if param.required.unwrap_or(false) {
-        "*"
-    } else {
-        ""
-    };
+    let required_str = if param.is_required() { "*" } else { "" };
 
     // Selection indicator
     let indicator = if is_selected { "→ " } else { "  " };
@@ -479,6 +923,14 @@ fn build_param_line(
     // Value display - show cursor if editing
     let value_display = if is_editing {
         format!("[{current_value}▊]") // Show cursor
+    } else if param.is_boolean() {
+        // Booleans are toggled, not typed
+        match current_value {
+            "true" => "[✓ true]".to_string(),
+            "false" => "[✗ false]".to_string(),
+            "" => "[- unset]".to_string(),
+            other => format!("[{other}]"),
+        }
     } else if current_value.is_empty() {
         "[_____]".to_string() // Empty placeholder
     } else {
@@ -492,19 +944,15 @@ fn build_param_line(
         Style::default().fg(Color::DarkGray)
     };
 
+    // Path parameters keep their color, the others only show it when selected
     let name_style = if is_selected {
         Style::default()
-            .fg(if is_path_param {
-                Color::Magenta
-            } else {
-                Color::Cyan
-            })
+            .fg(param_color(param_type))
             .add_modifier(Modifier::BOLD)
     } else {
-        Style::default().fg(if is_path_param {
-            Color::Magenta
-        } else {
-            styling::default_fg()
+        Style::default().fg(match param_type {
+            ParameterType::Path => Color::Magenta,
+            _ => styling::default_fg(),
         })
     };
 
@@ -520,26 +968,373 @@ fn build_param_line(
 
     let meta_style = Style::default().fg(Color::DarkGray);
 
-    Line::from(vec![
+    let mut spans = vec![
         Span::styled(indicator, indicator_style),
         Span::styled(format!("{}{}: ", param.name, required_str), name_style),
         Span::styled(value_display, value_style),
         Span::raw("  "),
         Span::styled(format!("({type_info})"), meta_style),
+    ];
+
+    // Flag values that don't fit the schema once the edit is confirmed
+    if !is_editing {
+        if let Some(e) = param.value_error(current_value) {
+            spans.push(Span::styled(
+                format!("  ⚠ {e}"),
+                Style::default().fg(Color::Red),
+            ));
+        }
+    }
+
+    // Date helper: show the adjust keys on the selected row
+    if let Some(format) = param.date_format() {
+        if is_selected && !is_editing {
+            let hint = match format {
+                DateFormat::Date => "  n: today  +/-: ±1 day",
+                DateFormat::DateTime => "  n: now  +/-: ±1 day  ]/[: ±1 hour",
+            };
+            spans.push(Span::styled(hint, meta_style));
+        }
+    }
+
+    Line::from(spans)
+}
+
+/// Status and duration line shown above the response body
+fn response_status_line(response: &ApiResponse) -> Line<'static> {
+    Line::from(vec![
+        Span::styled("Status: ", Style::default().fg(Color::Cyan)),
+        Span::styled(
+            format!("{} {}", response.status, response.status_text),
+            Style::default()
+                .fg(styling::status_color(response.status))
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::raw("  "),
+        Span::styled("Duration: ", Style::default().fg(Color::Cyan)),
+        Span::raw(format!("{}ms", response.duration.as_millis())),
     ])
 }
 
-/// Attempts to pretty-print JSON, returns original string if not valid JSON
-pub fn try_format_json(body: &str) -> String {
-    // Try to parse as JSON
-    match serde_json::from_str::<serde_json::Value>(body) {
-        Ok(json) => {
-            // Successfully parsed, pretty-print it
-            serde_json::to_string_pretty(&json).unwrap_or_else(|_| body.to_string())
-        }
-        Err(_) => {
-            // Not valid JSON, return as-is
-            body.to_string()
+/// Content kind the body is rendered as, with the save hint for binary bodies
+fn body_kind_span(kind: BodyKind) -> Span<'static> {
+    let text = match kind {
+        BodyKind::Binary => "  binary (hex), X: save to file".to_string(),
+        kind => format!("  {}", kind.label()),
+    };
+    Span::styled(text, Style::default().fg(Color::DarkGray))
+}
+
+/// Marks a response that went through automatic re-authentication
+fn reauth_spans(state: &AppState) -> Vec<Span<'static>> {
+    match &state.request.reauth_note {
+        Some(note) => vec![Span::styled(
+            format!("  ↻ {note}"),
+            Style::default().fg(Color::Yellow),
+        )],
+        None => Vec::new(),
+    }
+}
+
+/// Latency trend of the endpoint's successful responses, shown after the duration
+fn latency_spans(state: &AppState, endpoint: &ApiEndpoint) -> Vec<Span<'static>> {
+    let latencies: Vec<u128> = state
+        .request
+        .response_history
+        .get(&endpoint.key())
+        .into_iter()
+        .flatten()
+        .filter(|sample| sample.status != 0)
+        .map(|sample| sample.duration.as_millis())
+        .collect();
+    if latencies.len() < 2 {
+        return Vec::new();
+    }
+
+    let average = latencies.iter().sum::<u128>() / latencies.len() as u128;
+    vec![
+        Span::raw("  "),
+        Span::styled(sparkline(&latencies), Style::default().fg(Color::Magenta)),
+        Span::styled(
+            format!(" avg {average}ms"),
+            Style::default().fg(Color::DarkGray),
+        ),
+    ]
+}
+
+/// Recent status codes of the endpoint, oldest first (empty when there is no history yet)
+///
+/// A response fetched by following a link shows the followed URL instead.
+fn status_history_line(state: &AppState, endpoint: &ApiEndpoint) -> Line<'static> {
+    if let Some(url) = &state.request.followed_link {
+        return Line::from(vec![
+            Span::styled("Followed: ", Style::default().fg(Color::Cyan)),
+            Span::raw(url.clone()),
+        ]);
+    }
+
+    let Some(history) = state
+        .request
+        .response_history
+        .get(&endpoint.key())
+        .filter(|history| history.len() > 1)
+    else {
+        return Line::from("");
+    };
+
+    let mut spans = vec![Span::styled("History: ", Style::default().fg(Color::Cyan))];
+    for sample in &history[history.len().saturating_sub(STATUS_STRIP_LEN)..] {
+        let label = match sample.status {
+            0 => "ERR".to_string(),
+            status => status.to_string(),
+        };
+        spans.push(Span::styled(
+            label,
+            Style::default().fg(styling::status_color(sample.status)),
+        ));
+        spans.push(Span::raw(" "));
+    }
+    Line::from(spans)
+}
+
+/// Render the response body as a line diff against a saved snapshot
+fn render_snapshot_diff(
+    frame: &mut Frame,
+    area: Rect,
+    response: &ApiResponse,
+    snapshot: &ResponseSnapshot,
+    endpoint: &ApiEndpoint,
+    state: &AppState,
+) {
+    let diff = diff_lines(&snapshot.lines, &state.request.response_lines);
+    let added = diff
+        .iter()
+        .filter(|line| matches!(line, DiffLine::Added(_)))
+        .count();
+    let removed = diff
+        .iter()
+        .filter(|line| matches!(line, DiffLine::Removed(_)))
+        .count();
+
+    let mut status = response_status_line(response);
+    status.spans.extend(latency_spans(state, endpoint));
+    status.spans.extend(reauth_spans(state));
+
+    let mut summary = vec![
+        Span::styled("Diff vs ", Style::default().fg(Color::Cyan)),
+        Span::styled(
+            format!("\"{}\"", snapshot.name),
+            Style::default().add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            format!(" ({}, status {})  ", snapshot.saved_at, snapshot.status),
+            Style::default().fg(Color::DarkGray),
+        ),
+    ];
+    if added + removed == 0 {
+        summary.push(Span::styled("identical", Style::default().fg(Color::Green)));
+    } else {
+        summary.push(Span::styled(
+            format!("+{added} "),
+            Style::default().fg(Color::Green),
+        ));
+        summary.push(Span::styled(
+            format!("-{removed}"),
+            Style::default().fg(Color::Red),
+        ));
+    }
+
+    let first = state.ui.response_scroll;
+    let last = first + area.height as usize;
+    let mut lines = Vec::new();
+
+    for total_line_idx in first..(diff.len() + 2).min(last) {
+        let (marker, text, color) = match total_line_idx {
+            0 => {
+                lines.push(status.clone());
+                continue;
+            }
+            1 => {
+                lines.push(Line::from(summary.clone()));
+                continue;
+            }
+            _ => match diff[total_line_idx - 2] {
+                DiffLine::Same(text) => (' ', text, styling::default_fg()),
+                DiffLine::Removed(text) => ('-', text, Color::Red),
+                DiffLine::Added(text) => ('+', text, Color::Green),
+            },
+        };
+
+        let mut style = Style::default().fg(color);
+        if state.ui.active_detail_tab == DetailTab::Response
+            && state.ui.response_selected_line == total_line_idx
+        {
+            style = style.bg(Color::DarkGray);
         }
+        lines.push(Line::from(Span::styled(format!("{marker} {text}"), style)));
+    }
+
+    let mut content = Paragraph::new(lines);
+    if state.ui.response_wrap {
+        content = content.wrap(Wrap { trim: false });
     }
+    frame.render_widget(content, area);
+}
+
+/// Render RFC 7807 problem details as a bordered block; returns the area left below it
+fn render_problem_block(frame: &mut Frame, area: Rect, problem: &ProblemDetails) -> Rect {
+    let label = |name: &str| Span::styled(format!("{name}: "), Style::default().fg(Color::Cyan));
+    let mut lines: Vec<Line> = Vec::new();
+
+    let status_color = problem
+        .status
+        .map(|status| styling::status_color(status as u16))
+        .unwrap_or(Color::Red);
+    let mut heading = vec![Span::styled(
+        problem
+            .title
+            .clone()
+            .unwrap_or_else(|| "Problem".to_string()),
+        Style::default()
+            .fg(status_color)
+            .add_modifier(Modifier::BOLD),
+    )];
+    if let Some(status) = problem.status {
+        heading.push(Span::styled(
+            format!("  ({status})"),
+            Style::default().fg(status_color),
+        ));
+    }
+    lines.push(Line::from(heading));
+
+    if let Some(detail) = &problem.detail {
+        lines.push(Line::from(vec![label("Detail"), Span::raw(detail.clone())]));
+    }
+    if let Some(instance) = &problem.instance {
+        lines.push(Line::from(vec![
+            label("Instance"),
+            Span::raw(instance.clone()),
+        ]));
+    }
+    if let Some(problem_type) = &problem.problem_type {
+        lines.push(Line::from(vec![
+            label("Type"),
+            Span::styled(problem_type.clone(), Style::default().fg(Color::DarkGray)),
+        ]));
+    }
+    for (key, value) in &problem.extensions {
+        lines.push(Line::from(vec![
+            label(key),
+            Span::styled(value.clone(), Style::default().fg(Color::Yellow)),
+        ]));
+    }
+
+    // Keep at least half of the area for the raw body
+    let height = (lines.len() as u16 + 2).min(area.height / 2);
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(height), Constraint::Min(0)])
+        .split(area);
+
+    let block = Block::default()
+        .title(" Problem ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(status_color));
+    frame.render_widget(
+        Paragraph::new(lines)
+            .block(block)
+            .wrap(Wrap { trim: false }),
+        chunks[0],
+    );
+
+    chunks[1]
+}
+
+/// Render an array response as a table with sortable columns
+fn render_response_table(
+    frame: &mut Frame,
+    area: Rect,
+    response: &ApiResponse,
+    table: &ResponseTable,
+    endpoint: &ApiEndpoint,
+    state: &AppState,
+) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(2), Constraint::Min(0)])
+        .split(area);
+
+    let mut status = response_status_line(response);
+    status.spans.extend(latency_spans(state, endpoint));
+    status.spans.extend(reauth_spans(state));
+    status.spans.push(Span::styled(
+        format!(
+            "  {} rows × {} columns",
+            table.rows.len(),
+            table.columns.len()
+        ),
+        Style::default().fg(Color::DarkGray),
+    ));
+    frame.render_widget(
+        Paragraph::new(vec![status, status_history_line(state, endpoint)]),
+        chunks[0],
+    );
+
+    let sort = state.ui.table_sort;
+    let header = Row::new(table.columns.iter().enumerate().map(|(idx, name)| {
+        let marker = match sort {
+            Some(sort) if sort.column == idx && sort.descending => " ▼",
+            Some(sort) if sort.column == idx => " ▲",
+            _ => "",
+        };
+        format!("{name}{marker}")
+    }))
+    .style(
+        Style::default()
+            .fg(Color::Cyan)
+            .add_modifier(Modifier::BOLD),
+    );
+
+    let cells: Vec<Vec<String>> = (0..table.rows.len())
+        .map(|row| {
+            (0..table.columns.len())
+                .map(|column| table.cell(row, column))
+                .collect()
+        })
+        .collect();
+
+    // Size columns to their content, capped so one long value cannot hide the rest
+    let widths: Vec<Constraint> = table
+        .columns
+        .iter()
+        .enumerate()
+        .map(|(column, name)| {
+            let content = cells
+                .iter()
+                .map(|row| row[column].chars().count())
+                .max()
+                .unwrap_or(0);
+            let width = content.max(name.chars().count() + 2);
+            Constraint::Length(width.min(MAX_TABLE_COLUMN_WIDTH) as u16)
+        })
+        .collect();
+
+    let highlight = if state.ui.yank_flash {
+        Style::default()
+            .bg(Color::Green)
+            .fg(Color::Black)
+            .add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().bg(Color::DarkGray)
+    };
+
+    let rows = cells.into_iter().map(Row::new);
+    let widget = Table::new(rows, widths)
+        .header(header)
+        .column_spacing(2)
+        .row_highlight_style(highlight);
+
+    let mut table_state =
+        TableState::default().with_selected(Some(state.ui.response_selected_line));
+    frame.render_stateful_widget(widget, chunks[1], &mut table_state);
 }
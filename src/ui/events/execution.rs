@@ -1,16 +1,18 @@
 //! Request execution handlers
 //!
 //! This module handles:
-//! - Executing HTTP requests (Space/Enter key)
+//! - Executing HTTP requests (Space/Enter key), and the last one again (`.`)
 //! - Expanding/collapsing groups in grouped mode
 //! - Retry logic for failed requests
 
 use super::helpers::{can_execute_endpoint, log_debug};
+use super::modals;
 use crate::request::execute_request_background;
-use crate::state::{count_visible_items, AppState};
-use crate::types::{ApiResponse, RenderItem, ViewMode};
+use crate::state::{count_visible_items, AppState, LockRecover};
+use crate::types::{ApiEndpoint, ApiResponse, RenderItem, ViewMode};
 use ratatui::widgets::ListState;
 use std::sync::{Arc, RwLock};
+use std::time::Instant;
 
 /// Handle Enter/Space key - execute request or expand/collapse group
 pub fn handle_enter(
@@ -19,43 +21,14 @@ pub fn handle_enter(
     list_state: &mut ListState,
     base_url: Option<String>,
 ) {
-    let state_read = state.read().unwrap();
+    let state_read = state.read_lock();
 
     // Check what view mode we're in
     if state_read.ui.view_mode == ViewMode::Flat {
         // In flat mode: Execute request
-        if let Some(endpoint) = state_read.data.endpoints.get(*selected_index) {
-            let endpoint = endpoint.clone();
-
-            // Check if we have base_url configured
-            if let Some(base_url) = base_url {
-                // Check if this endpoint is already executing
-                if let Some(ref executing) = state_read.request.executing_endpoint {
-                    if executing == &endpoint.path {
-                        log_debug("Request already in progress for this endpoint");
-                        return;
-                    }
-                }
-
-                // Validate that all required path params are filled
-                let config = state_read.request.configs.get(&endpoint.path);
-                if let Err(err_msg) = can_execute_endpoint(&endpoint, config) {
-                    log_debug(&format!("Cannot execute: {err_msg}"));
-                    drop(state_read);
-
-                    // Store error in response so user can see it
-                    let mut s = state.write().unwrap();
-                    s.request.current_response = Some(ApiResponse::error(err_msg));
-                    return;
-                }
-
-                drop(state_read); // Release lock before spawning task
-
-                log_debug(&format!("Executing: {} {}", endpoint.method, endpoint.path));
-                execute_request_background(state.clone(), endpoint, base_url);
-            } else {
-                log_debug("Cannot execute: Base URL not configured");
-            }
+        if let Some(endpoint) = state_read.get_selected_endpoint(*selected_index) {
+            drop(state_read);
+            execute_endpoint(state, endpoint, base_url);
         }
     } else {
         // In grouped mode: Check if we're on a group header or endpoint
@@ -66,7 +39,7 @@ pub fn handle_enter(
                     let group_name = name.clone();
 
                     drop(state_read);
-                    let mut state_write = state.write().unwrap();
+                    let mut state_write = state.write_lock();
 
                     if state_write.ui.expanded_groups.contains(&group_name) {
                         state_write.ui.expanded_groups.remove(&group_name);
@@ -74,6 +47,17 @@ pub fn handle_enter(
                     } else {
                         state_write.ui.expanded_groups.insert(group_name.clone());
                         log_debug(&format!("Expanded group: {group_name}"));
+
+                        // Jump back to the endpoint last selected in this group
+                        let remembered = state_write
+                            .ui
+                            .last_selected_by_group
+                            .get(&group_name)
+                            .and_then(|key| state_write.find_in_group(&group_name, key));
+                        if let Some(index) = remembered {
+                            *selected_index = index;
+                            list_state.select(Some(index));
+                        }
                     }
 
                     let visible_count = count_visible_items(&state_write);
@@ -83,46 +67,77 @@ pub fn handle_enter(
                     }
                 }
                 RenderItem::Endpoint { endpoint } => {
-                    let endpoint = endpoint.clone();
-
-                    // Check if we have base_url configured
-                    if let Some(base_url) = base_url {
-                        // Check if this endpoint is already executing
-                        if let Some(ref executing) = state_read.request.executing_endpoint {
-                            if executing == &endpoint.path {
-                                log_debug("Request already in progress for this endpoint");
-                                return;
-                            }
-                        }
-
-                        // Validate that all required path params are filled
-                        let config = state_read.request.configs.get(&endpoint.path);
-                        if let Err(err_msg) = can_execute_endpoint(&endpoint, config) {
-                            log_debug(&format!("Cannot execute: {err_msg}"));
-                            drop(state_read);
+                    let endpoint = endpoint.as_ref().clone();
+                    drop(state_read);
+                    execute_endpoint(state, endpoint, base_url);
+                }
+            }
+        }
+    }
+}
 
-                            // Store error in response so user can see it
-                            let mut s = state.write().unwrap();
-                            s.request.current_response = Some(ApiResponse::error(err_msg));
-                            return;
-                        }
+/// Send the last executed request again, whatever is selected (`.`)
+pub fn handle_repeat_last(state: Arc<RwLock<AppState>>, base_url: Option<String>) {
+    let endpoint = {
+        let state_read = state.read_lock();
+        state_read
+            .request
+            .last_executed
+            .as_ref()
+            .and_then(|key| state_read.get_endpoint_by_key(key))
+            .cloned()
+    };
+
+    match endpoint {
+        Some(endpoint) => execute_endpoint(state, endpoint, base_url),
+        None => {
+            state.write_lock().ui.toast = Some(("No request sent yet".to_string(), Instant::now()));
+        }
+    }
+}
 
-                        drop(state_read);
+/// Send a request to an endpoint, unless one is in flight or its parameters are incomplete
+fn execute_endpoint(state: Arc<RwLock<AppState>>, endpoint: ApiEndpoint, base_url: Option<String>) {
+    // An endpoint sent before its parameters were touched goes with the spec defaults
+    state.write_lock().get_or_create_request_config(&endpoint);
+    let state_read = state.read_lock();
+
+    // Check if we have base_url configured
+    if let Some(base_url) = endpoint.request_base_url(base_url) {
+        // Check if this endpoint is already executing
+        if state_read.request.is_executing(&endpoint.key()) {
+            log_debug("Request already in progress for this endpoint");
+            return;
+        }
 
-                        log_debug(&format!("Executing: {} {}", endpoint.method, endpoint.path));
-                        execute_request_background(state.clone(), endpoint, base_url);
-                    } else {
-                        log_debug("Cannot execute: Base URL not configured");
-                    }
-                }
-            }
+        // Validate that all required path params are filled
+        let config = state_read
+            .request
+            .merged_config(&endpoint, state_read.request.configs.get(&endpoint.key()));
+        if let Err(err_msg) = can_execute_endpoint(&endpoint, config.as_ref()) {
+            log_debug(&format!("Cannot execute: {err_msg}"));
+            drop(state_read);
+
+            // Store error in response so user can see it
+            let mut s = state.write_lock();
+            s.store_response(endpoint.key(), Some(ApiResponse::error(err_msg).into()));
+            return;
         }
+
+        drop(state_read); // Release lock before spawning task
+
+        log_debug(&format!("Executing: {} {}", endpoint.method, endpoint.path));
+        execute_request_background(state.clone(), endpoint, base_url);
+    } else {
+        log_debug("Cannot execute: Base URL not configured");
+        drop(state_read);
+        modals::handle_server_form_open(state.clone());
     }
 }
 
 /// Handle retry after error (Ctrl+R)
 pub fn handle_retry(state: Arc<RwLock<AppState>>) -> bool {
-    let state_read = state.read().unwrap();
+    let state_read = state.read_lock();
     if matches!(
         state_read.data.loading_state,
         crate::types::LoadingState::Error(_)
@@ -130,11 +145,54 @@ pub fn handle_retry(state: Arc<RwLock<AppState>>) -> bool {
         drop(state_read);
 
         // Increment retry count
-        if let Ok(mut s) = state.write() {
-            s.data.retry_count += 1;
-        }
+        state.write_lock().data.retry_count += 1;
 
         return true; // Signal that we should fetch
     }
     false // Don't fetch if not in error state
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{DetailTab, PanelFocus};
+    use tokio::sync::Semaphore;
+
+    fn endpoint(path: &str) -> ApiEndpoint {
+        ApiEndpoint {
+            method: "GET".to_string(),
+            path: path.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_repeat_last_sends_last_executed_endpoint() {
+        let mut state = AppState::default();
+        state.data.endpoints = vec![endpoint("/health"), endpoint("/users")];
+        // No free slot, so the repeated request stays queued instead of going out
+        state.request.slots = Arc::new(Semaphore::new(0));
+        let state = Arc::new(RwLock::new(state));
+        let base_url = Some("http://localhost:5000".to_string());
+
+        handle_repeat_last(state.clone(), base_url.clone());
+        {
+            let s = state.read_lock();
+            assert!(s.request.jobs.is_empty());
+            assert_eq!(s.ui.toast.as_ref().unwrap().0, "No request sent yet");
+        }
+
+        // The panel and tab moved on since `GET /users` was sent
+        {
+            let mut s = state.write_lock();
+            s.request.last_executed = Some("GET /users".to_string());
+            s.ui.panel_focus = PanelFocus::Details;
+            s.ui.active_detail_tab = DetailTab::Endpoint;
+        }
+        handle_repeat_last(state.clone(), base_url);
+        let mut s = state.write_lock();
+        assert!(s.request.is_executing("GET /users"));
+        assert!(!s.request.is_executing("GET /health"));
+        s.request.cancel_all_jobs();
+    }
+}
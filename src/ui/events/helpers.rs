@@ -5,19 +5,21 @@
 //! - Edit mode checking
 //! - Validation functions
 //! - Paste batching
-//! - Debug logging
+//! - Debug logging, kept in memory for the log panel too
 
 use crate::actions::{apply_action, AppAction};
-use crate::state::AppState;
+use crate::redact::redact;
+use crate::state::{AppState, LockRecover};
 use crate::types::{ApiEndpoint, RequestConfig, RequestEditMode};
-use crossterm::event::{self, Event, KeyCode, KeyModifiers};
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use std::collections::VecDeque;
 use std::fs::OpenOptions;
 use std::io::Write;
 use std::sync::{Arc, RwLock};
 
 /// Check if currently editing a parameter
 pub fn is_editing(state: &Arc<RwLock<AppState>>) -> bool {
-    let state_read = state.read().unwrap();
+    let state_read = state.read_lock();
     matches!(state_read.request.edit_mode, RequestEditMode::Editing(_))
 }
 
@@ -25,103 +27,144 @@ pub fn is_editing(state: &Arc<RwLock<AppState>>) -> bool {
 /// If editing, treat as character input, otherwise apply the action
 pub fn apply_or_char(state: Arc<RwLock<AppState>>, ch: char, action: AppAction) {
     if is_editing(&state) {
-        let mut s = state.write().unwrap();
+        let mut s = state.write_lock();
         apply_action(AppAction::AppendToParamBuffer(ch.to_string()), &mut s);
     } else {
-        let mut s = state.write().unwrap();
+        let mut s = state.write_lock();
         apply_action(action, &mut s);
     }
 }
 
 /// Apply a single action to state
 pub fn apply(state: Arc<RwLock<AppState>>, action: AppAction) {
-    let mut s = state.write().unwrap();
+    let mut s = state.write_lock();
     apply_action(action, &mut s);
 }
 
 /// Apply multiple actions to state
 pub fn apply_many(state: Arc<RwLock<AppState>>, actions: Vec<AppAction>) {
-    let mut s = state.write().unwrap();
+    let mut s = state.write_lock();
     for action in actions {
         apply_action(action, &mut s);
     }
 }
 
-/// Check if endpoint can be executed (all required path params are filled)
+/// Check if endpoint can be executed (required params filled, body present and valid)
 pub fn can_execute_endpoint(
     endpoint: &ApiEndpoint,
     config: Option<&RequestConfig>,
 ) -> Result<(), String> {
-    // If endpoint has no path parameters, it can always be executed
-    let path_params = endpoint.path_params();
-    if path_params.is_empty() {
-        return Ok(());
-    }
-
-    // If we have path params, we need a config
-    let config = match config {
-        Some(c) => c,
-        None => {
-            return Err(format!(
-                "Please configure path parameter(s): {}",
-                path_params
-                    .iter()
-                    .map(|p| p.name.as_str())
-                    .collect::<Vec<_>>()
-                    .join(", ")
-            ));
-        }
-    };
-
-    // Check if all path params are filled
-    if !endpoint.has_all_required_path_params(config) {
-        let missing = endpoint.missing_path_params(config);
+    if let Some(event) = &endpoint.event {
         return Err(format!(
-            "Missing required path parameter(s): {}",
-            missing.join(", ")
+            "This is a {}: the API sends it, so it can only be inspected",
+            event.label()
         ));
     }
 
-    Ok(())
+    let errors = endpoint.validation_errors(config);
+    match errors.as_slice() {
+        [] => Ok(()),
+        [error] => Err(error.clone()),
+        _ => Err(format!(
+            "Cannot send request:\n{}",
+            errors
+                .iter()
+                .map(|e| format!("  • {e}"))
+                .collect::<Vec<_>>()
+                .join("\n")
+        )),
+    }
+}
+
+/// Character keys that arrived right after the key being handled, for paste support
+static PASTE_BATCH: RwLock<Vec<KeyEvent>> = RwLock::new(Vec::new());
+
+/// Whether a key can be part of a paste batch: a typed character, not a control chord
+pub fn is_paste_char(key: &KeyEvent) -> bool {
+    matches!(key.code, KeyCode::Char(_))
+        && key.kind == KeyEventKind::Press
+        && !key.modifiers.contains(KeyModifiers::CONTROL)
+}
+
+/// Offer the character keys that arrived right after the key about to be handled
+///
+/// The main loop reads them off the event stream; a handler taking text claims them
+/// with [`collect_paste_batch`], and the loop handles the rest as keys of their own.
+pub fn offer_paste_batch(keys: Vec<KeyEvent>) {
+    *PASTE_BATCH.write_lock() = keys;
+}
+
+/// Keys of the offered batch that no handler claimed, in the order they arrived
+pub fn take_unclaimed_batch() -> Vec<KeyEvent> {
+    std::mem::take(&mut *PASTE_BATCH.write_lock())
 }
 
 /// Collect a batch of characters for paste support
 ///
-/// When a character is typed, this function checks for any immediately available
-/// character events and batches them together. This enables fast paste operations
+/// When a character is typed, this function claims the character keys that arrived
+/// right after it and batches them together. This enables fast paste operations
 /// in terminals.
 ///
 /// Returns a tuple of (batched_string, character_count)
 pub fn collect_paste_batch(initial_char: char) -> (String, usize) {
     let mut chars = vec![initial_char];
-
-    // Drain any immediately available character events
-    while let Ok(true) = event::poll(std::time::Duration::from_millis(0)) {
-        if let Ok(Event::Key(next_key)) = event::read() {
-            match next_key.code {
-                KeyCode::Char(next_c) if !next_key.modifiers.contains(KeyModifiers::CONTROL) => {
-                    chars.push(next_c);
-                }
-                _ => {
-                    // Non-character or control key, stop batching
-                    break;
-                }
-            }
-        } else {
-            break;
-        }
-    }
+    chars.extend(
+        take_unclaimed_batch()
+            .iter()
+            .filter_map(|key| match key.code {
+                KeyCode::Char(c) => Some(c),
+                _ => None,
+            }),
+    );
 
     let count = chars.len();
     let batch_str: String = chars.into_iter().collect();
     (batch_str, count)
 }
 
-/// Log debug message to /tmp/lazy-swagger-tui.log
+/// Log entries kept for the log panel; the oldest are dropped first
+const MAX_LOG_ENTRIES: usize = 500;
+
+/// Recent log entries with the time they were logged, oldest first
+static RECENT_LOG: RwLock<VecDeque<String>> = RwLock::new(VecDeque::new());
+
+/// Log debug message to /tmp/lazy-swagger-tui.log and the log panel, with secrets masked
 pub fn log_debug(msg: &str) {
+    let msg = redact(msg);
     let _ = OpenOptions::new()
         .create(true)
         .append(true)
         .open("/tmp/lazy-swagger-tui.log")
         .and_then(|mut f| writeln!(f, "{msg}"));
+
+    let mut log = RECENT_LOG.write_lock();
+    log.push_back(format!("{} {msg}", chrono::Local::now().format("%H:%M:%S")));
+    if log.len() > MAX_LOG_ENTRIES {
+        log.pop_front();
+    }
+}
+
+/// The last `count` log entries, oldest first
+pub fn recent_log(count: usize) -> Vec<String> {
+    let log = RECENT_LOG.read_lock();
+    log.iter()
+        .skip(log.len().saturating_sub(count))
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recent_log() {
+        log_debug("Loaded 12 endpoints, token=abc123");
+        let entries = recent_log(MAX_LOG_ENTRIES);
+        // Other tests log too, so look for the entry rather than at the end
+        assert!(entries
+            .iter()
+            .any(|entry| entry.ends_with(" Loaded 12 endpoints, token=***")));
+        assert_eq!(recent_log(1).len(), 1);
+    }
 }
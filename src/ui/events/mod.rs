@@ -7,6 +7,7 @@
 //! - EnteringToken: Modal for bearer token authentication
 //! - Searching: Filtering endpoints by query
 //! - Parameter editing: Inline editing of request parameters
+//! - EditingParamForm: Editing all parameters of an endpoint at once
 //!
 //! # Architecture
 //!
@@ -16,7 +17,7 @@
 //! # Lock Management
 //!
 //! This module frequently acquires locks on Arc<RwLock<AppState>>. Care must be
-//! taken to minimize lock duration and avoid deadlocks. See handle_events for
+//! taken to minimize lock duration and avoid deadlocks. See handle_event for
 //! the main event loop.
 
 mod execution;
@@ -28,13 +29,22 @@ mod search;
 mod yank;
 
 // Re-export public items
-pub use helpers::{apply, apply_or_char, is_editing, log_debug};
+pub use helpers::{
+    apply, apply_or_char, is_editing, is_paste_char, log_debug, offer_paste_batch, recent_log,
+    take_unclaimed_batch,
+};
 
 use crate::actions::AppAction;
-use crate::state::AppState;
-use crate::types::{DetailTab, InputMode, PanelFocus, RequestEditMode, UrlSubmission};
+use crate::datetime::DateAdjust;
+use crate::generators::Generator;
+use crate::state::{AppState, LockRecover};
+use crate::types::{
+    DetailTab, InputMode, LoadingState, PanelFocus, RequestEditMode, UrlSubmission, ViewMode,
+    WorkspaceCommand,
+};
+use crate::viewer::ExternalViewer;
 use color_eyre::Result;
-use crossterm::event::{self, Event, KeyCode, KeyModifiers};
+use crossterm::event::{Event, KeyCode, KeyModifiers};
 use ratatui::widgets::ListState;
 use std::sync::{Arc, RwLock};
 
@@ -53,9 +63,10 @@ impl EventHandler {
         }
     }
 
-    /// Main event handling loop - dispatches to appropriate handlers based on input mode
-    pub fn handle_events(
+    /// Handle one terminal event - dispatches to appropriate handlers based on input mode
+    pub fn handle_event(
         &mut self,
+        event: Event,
         state: Arc<RwLock<AppState>>,
         list_state: &mut ListState,
         base_url: Option<String>,
@@ -64,449 +75,1189 @@ impl EventHandler {
         let mut should_fetch = false;
         let mut url_submitted = None;
 
-        if event::poll(std::time::Duration::from_millis(50))? {
-            if let Event::Key(key) = event::read()? {
-                let input_mode = state.read().unwrap().input.mode.clone();
+        if let Event::FocusGained | Event::FocusLost = event {
+            state.write_lock().ui.terminal_focused = event == Event::FocusGained;
+        }
+        if let Event::Resize(width, height) = event {
+            state.write_lock().resize(width, height);
+        }
+        // Ctrl+O toggles the log panel and F12 the performance overlay, in any mode
+        let toggles_log = matches!(
+            event,
+            Event::Key(key) if key.code == KeyCode::Char('o') && key.modifiers.contains(KeyModifiers::CONTROL)
+        );
+        let toggles_metrics = matches!(event, Event::Key(key) if key.code == KeyCode::F(12));
+        if toggles_log {
+            apply(state.clone(), AppAction::ToggleLogPanel);
+        } else if toggles_metrics {
+            apply(state.clone(), AppAction::ToggleMetrics);
+        } else if let Event::Key(key) = event {
+            let input_mode = state.read_lock().input.mode.clone();
 
-                match input_mode {
-                    InputMode::EnteringUrl => {
-                        url_submitted = modals::handle_url_input(key, state.clone())?;
-                    }
+            match input_mode {
+                InputMode::EnteringUrl => {
+                    url_submitted = modals::handle_url_input(key, state.clone())?;
+                }
 
-                    InputMode::EnteringToken => {
-                        modals::handle_token_input(key, state.clone())?;
-                    }
+                InputMode::EnteringToken => {
+                    modals::handle_token_input(key, state.clone())?;
+                }
 
-                    InputMode::ConfirmClearToken => {
-                        modals::handle_clear_confirmation(key, state.clone())?;
-                    }
+                InputMode::ConfirmClearToken => {
+                    modals::handle_clear_confirmation(key, state.clone())?;
+                }
 
-                    InputMode::Searching => {
-                        search::handle_search_input(
-                            &mut self.selected_index,
-                            key,
-                            state.clone(),
-                            list_state,
-                        )?;
-                    }
+                InputMode::EditingNote => {
+                    modals::handle_note_input(key, state.clone(), self.selected_index)?;
+                }
 
-                    InputMode::EnteringBody => {
-                        modals::handle_body_input(key, state.clone(), self.selected_index)?;
-                    }
+                InputMode::ExportingCsv
+                | InputMode::SavingBody
+                | InputMode::ExportingScript
+                | InputMode::ExportingTraffic
+                | InputMode::ExportingDiagnostics => {
+                    modals::handle_export_input(
+                        key,
+                        state.clone(),
+                        self.selected_index,
+                        base_url.as_deref(),
+                    )?;
+                }
 
-                    InputMode::Normal => match key.code {
-                        // QUIT
-                        KeyCode::Char('q') => {
-                            // Don't quit if we're editing a parameter
-                            if !is_editing(&state) {
-                                // Not editing - quit the app
-                                self.should_quit = true;
-                            } else {
-                                // We're editing - treat 'q' as character input
-                                let mut s = state.write().unwrap();
-                                s.request.param_edit_buffer.push('q');
-                            }
+                InputMode::SelectingDiscoveredSpec => {
+                    modals::handle_spec_discovery_input(key, state.clone());
+                }
+
+                InputMode::SelectingLink => {
+                    modals::handle_link_picker_input(
+                        key,
+                        state.clone(),
+                        self.selected_index,
+                        base_url.clone(),
+                    )?;
+                }
+
+                InputMode::EnteringVariantName => {
+                    modals::handle_variant_name_input(key, state.clone(), self.selected_index)?;
+                }
+
+                InputMode::PastingParams(_) => {
+                    modals::handle_param_paste_input(key, state.clone())?;
+                }
+
+                InputMode::EnteringSnapshotName => {
+                    modals::handle_snapshot_name_input(key, state.clone(), self.selected_index)?;
+                }
+
+                InputMode::ConfirmResetRequest(endpoint_key) => {
+                    modals::handle_reset_confirmation(key, state.clone(), endpoint_key)?;
+                }
+
+                InputMode::Searching => {
+                    search::handle_search_input(
+                        &mut self.selected_index,
+                        key,
+                        state.clone(),
+                        list_state,
+                    )?;
+                }
+
+                InputMode::SearchingResponse => {
+                    search::handle_response_search_input(key, state.clone())?;
+                }
+
+                InputMode::EnteringBody => {
+                    modals::handle_body_input(key, state.clone(), self.selected_index)?;
+                }
+
+                InputMode::EditingParamForm => {
+                    modals::handle_param_form_input(key, state.clone())?;
+                }
+
+                InputMode::ConfiguringServer => {
+                    modals::handle_server_form_input(key, state.clone());
+                }
+
+                InputMode::Console => {
+                    modals::handle_console_input(key, state.clone());
+                }
+
+                InputMode::SelectingPluginCommand => {
+                    modals::handle_plugin_commands_input(key, state.clone(), self.selected_index);
+                }
+
+                InputMode::SwitchingSpec => {
+                    modals::handle_spec_switcher_input(key, state.clone());
+                }
+
+                InputMode::ViewingJobs => {
+                    modals::handle_jobs_input(key, state.clone());
+                }
+
+                InputMode::ViewingSpecInfo => {
+                    modals::handle_spec_info_input(key, state.clone());
+                }
+
+                InputMode::ViewingStats => {
+                    modals::handle_stats_input(key, state.clone());
+                }
+
+                InputMode::ViewingSpecChanges => {
+                    modals::handle_spec_changes_input(key, state.clone());
+                }
+
+                InputMode::Normal => match key.code {
+                    // QUIT
+                    KeyCode::Char('q') => {
+                        // Don't quit if we're editing a parameter
+                        if !is_editing(&state) {
+                            // Not editing - quit the app
+                            self.should_quit = true;
+                        } else {
+                            // We're editing - treat 'q' as character input
+                            let mut s = state.write_lock();
+                            s.request.param_edit_buffer.push('q');
                         }
-                        // nav down
-                        KeyCode::Char('j') => {
-                            if is_editing(&state) {
-                                let mut s = state.write().unwrap();
-                                s.request.param_edit_buffer.push('j');
-                            } else {
-                                let state_read = state.read().unwrap();
-                                let panel = state_read.ui.panel_focus.clone();
-                                let active_tab = state_read.ui.active_detail_tab.clone();
-                                drop(state_read);
-
-                                // Handle navigation
-                                match panel {
-                                    PanelFocus::EndpointsList => {
-                                        // Navigate down in endpoints list
-                                        navigation::handle_down(
-                                            &mut self.selected_index,
+                    }
+                    // nav down
+                    KeyCode::Char('j') => {
+                        if is_editing(&state) {
+                            let mut s = state.write_lock();
+                            s.request.param_edit_buffer.push('j');
+                        } else {
+                            let state_read = state.read_lock();
+                            let panel = state_read.ui.panel_focus.clone();
+                            let active_tab = state_read.ui.active_detail_tab.clone();
+                            drop(state_read);
+
+                            // Handle navigation
+                            match panel {
+                                PanelFocus::EndpointsList => {
+                                    // Navigate down in endpoints list
+                                    navigation::handle_down(
+                                        &mut self.selected_index,
+                                        state.clone(),
+                                        list_state,
+                                    );
+                                }
+                                PanelFocus::Details => {
+                                    // If on Request tab and in Viewing mode, navigate params
+                                    if active_tab == DetailTab::Request {
+                                        navigation::handle_request_param_down(
+                                            self.selected_index,
                                             state.clone(),
-                                            list_state,
                                         );
+                                    } else if active_tab == DetailTab::Response {
+                                        navigation::handle_response_line_down(state.clone());
+                                    } else if active_tab == DetailTab::Headers {
+                                        navigation::handle_headers_scroll(state.clone(), true);
                                     }
-                                    PanelFocus::Details => {
-                                        // If on Request tab and in Viewing mode, navigate params
-                                        if active_tab == DetailTab::Request {
-                                            navigation::handle_request_param_down(
-                                                self.selected_index,
-                                                state.clone(),
-                                            );
-                                        } else if active_tab == DetailTab::Response {
-                                            navigation::handle_response_line_down(state.clone());
-                                        }
-                                        // For other tabs, j/k do nothing
-                                    }
+                                    // On the Endpoint tab, j/k do nothing
                                 }
                             }
                         }
-                        // nav up
-                        KeyCode::Char('k') => {
-                            if is_editing(&state) {
-                                let mut s = state.write().unwrap();
-                                s.request.param_edit_buffer.push('k');
-                            } else {
-                                let state_read = state.read().unwrap();
-                                let panel = state_read.ui.panel_focus.clone();
-                                let active_tab = state_read.ui.active_detail_tab.clone();
-                                drop(state_read);
-
-                                // Handle navigation
-                                match panel {
-                                    PanelFocus::EndpointsList => {
-                                        // Navigate up in endpoints list
-                                        navigation::handle_up(
-                                            &mut self.selected_index,
-                                            state.clone(),
-                                            list_state,
-                                        );
-                                    }
-                                    PanelFocus::Details => {
-                                        // If on Request tab and in Viewing mode, navigate params
-                                        if active_tab == DetailTab::Request {
-                                            navigation::handle_request_param_up(state.clone());
-                                        } else if active_tab == DetailTab::Response {
-                                            navigation::handle_response_line_up(state.clone());
-                                        }
-                                        // For other tabs, j/k do nothing
+                    }
+                    // nav up
+                    KeyCode::Char('k') => {
+                        if is_editing(&state) {
+                            let mut s = state.write_lock();
+                            s.request.param_edit_buffer.push('k');
+                        } else {
+                            let state_read = state.read_lock();
+                            let panel = state_read.ui.panel_focus.clone();
+                            let active_tab = state_read.ui.active_detail_tab.clone();
+                            drop(state_read);
+
+                            // Handle navigation
+                            match panel {
+                                PanelFocus::EndpointsList => {
+                                    // Navigate up in endpoints list
+                                    navigation::handle_up(
+                                        &mut self.selected_index,
+                                        state.clone(),
+                                        list_state,
+                                    );
+                                }
+                                PanelFocus::Details => {
+                                    // If on Request tab and in Viewing mode, navigate params
+                                    if active_tab == DetailTab::Request {
+                                        navigation::handle_request_param_up(state.clone());
+                                    } else if active_tab == DetailTab::Response {
+                                        navigation::handle_response_line_up(state.clone());
+                                    } else if active_tab == DetailTab::Headers {
+                                        navigation::handle_headers_scroll(state.clone(), false);
                                     }
+                                    // On the Endpoint tab, j/k do nothing
                                 }
                             }
                         }
-                        // handle auth dialog
-                        KeyCode::Char('a') => {
-                            if is_editing(&state) {
-                                let mut s = state.write().unwrap();
-                                s.request.param_edit_buffer.push('a');
-                            } else {
-                                modals::handle_auth_dialog(state.clone());
-                            }
+                    }
+                    // handle auth dialog
+                    KeyCode::Char('a') => {
+                        if is_editing(&state) {
+                            let mut s = state.write_lock();
+                            s.request.param_edit_buffer.push('a');
+                        } else {
+                            modals::handle_auth_dialog(state.clone(), self.selected_index);
                         }
-                        // handle body editor
-                        KeyCode::Char('b') => {
-                            if is_editing(&state) {
-                                let mut s = state.write().unwrap();
-                                s.request.param_edit_buffer.push('b');
-                            } else {
-                                let state_read = state.read().unwrap();
-                                let panel = state_read.ui.panel_focus.clone();
-                                let active_tab = state_read.ui.active_detail_tab.clone();
-                                let supports_body = state_read
-                                    .get_selected_endpoint(self.selected_index)
-                                    .map(|ep| ep.supports_body())
-                                    .unwrap_or(false);
-                                drop(state_read);
-
-                                if panel == PanelFocus::Details
-                                    && active_tab == DetailTab::Request
-                                    && supports_body
-                                {
-                                    modals::handle_body_dialog(state.clone(), self.selected_index);
-                                }
+                    }
+                    // write a diagnostic bundle for bug reports
+                    KeyCode::Char('b')
+                        if key.modifiers.contains(KeyModifiers::CONTROL) && !is_editing(&state) =>
+                    {
+                        apply(
+                            state.clone(),
+                            AppAction::EnterDiagnosticsExportMode(
+                                "lazy-swagger-tui-diagnostics.txt".to_string(),
+                            ),
+                        );
+                    }
+                    // handle body editor
+                    KeyCode::Char('b') => {
+                        if is_editing(&state) {
+                            let mut s = state.write_lock();
+                            s.request.param_edit_buffer.push('b');
+                        } else {
+                            let state_read = state.read_lock();
+                            let panel = state_read.ui.panel_focus.clone();
+                            let active_tab = state_read.ui.active_detail_tab.clone();
+                            let supports_body = state_read
+                                .get_selected_endpoint(self.selected_index)
+                                .map(|ep| ep.supports_body())
+                                .unwrap_or(false);
+                            drop(state_read);
+
+                            if panel == PanelFocus::Details
+                                && active_tab == DetailTab::Request
+                                && supports_body
+                            {
+                                modals::handle_body_dialog(state.clone(), self.selected_index);
                             }
                         }
-                        // edit param
-                        KeyCode::Char('e') => {
-                            if is_editing(&state) {
-                                let mut s = state.write().unwrap();
-                                s.request.param_edit_buffer.push('e');
-                            } else {
-                                let state_read = state.read().unwrap();
-                                let panel = state_read.ui.panel_focus.clone();
-                                let active_tab = state_read.ui.active_detail_tab.clone();
-                                drop(state_read);
-
-                                if panel == PanelFocus::Details && active_tab == DetailTab::Request
-                                {
-                                    parameters::handle_request_param_edit(
-                                        self.selected_index,
-                                        state.clone(),
-                                    );
-                                }
+                    }
+                    // edit param
+                    KeyCode::Char('e') => {
+                        if is_editing(&state) {
+                            let mut s = state.write_lock();
+                            s.request.param_edit_buffer.push('e');
+                        } else {
+                            let state_read = state.read_lock();
+                            let panel = state_read.ui.panel_focus.clone();
+                            let active_tab = state_read.ui.active_detail_tab.clone();
+                            drop(state_read);
+
+                            if panel == PanelFocus::Details
+                                && active_tab == DetailTab::Request
+                                && !parameters::handle_bool_toggle(
+                                    self.selected_index,
+                                    state.clone(),
+                                )
+                            {
+                                parameters::handle_request_param_edit(
+                                    self.selected_index,
+                                    state.clone(),
+                                );
                             }
                         }
-                        // toggle view - list <-> grouped
-                        KeyCode::Char('g') => {
-                            if is_editing(&state) {
-                                let mut s = state.write().unwrap();
-                                s.request.param_edit_buffer.push('g');
-                            } else {
-                                navigation::handle_toggle_view(
-                                    &mut self.selected_index,
+                    }
+                    // edit all params in a form
+                    KeyCode::Char('E') => {
+                        if is_editing(&state) {
+                            let mut s = state.write_lock();
+                            s.request.param_edit_buffer.push('E');
+                        } else {
+                            let state_read = state.read_lock();
+                            let panel = state_read.ui.panel_focus.clone();
+                            let active_tab = state_read.ui.active_detail_tab.clone();
+                            drop(state_read);
+
+                            if panel == PanelFocus::Details && active_tab == DetailTab::Request {
+                                parameters::handle_param_form_open(
+                                    self.selected_index,
                                     state.clone(),
-                                    list_state,
                                 );
                             }
                         }
-                        // config url
-                        KeyCode::Char(',') => {
-                            if is_editing(&state) {
-                                let mut s = state.write().unwrap();
-                                s.request.param_edit_buffer.push(',');
-                            } else {
-                                modals::handle_url_dialog(
+                    }
+                    // paste a JSON object or query string onto the params
+                    KeyCode::Char('I') => {
+                        if is_editing(&state) {
+                            let mut s = state.write_lock();
+                            s.request.param_edit_buffer.push('I');
+                        } else {
+                            let state_read = state.read_lock();
+                            let panel = state_read.ui.panel_focus.clone();
+                            let active_tab = state_read.ui.active_detail_tab.clone();
+                            drop(state_read);
+
+                            if panel == PanelFocus::Details && active_tab == DetailTab::Request {
+                                parameters::handle_param_paste_open(
+                                    self.selected_index,
                                     state.clone(),
-                                    swagger_url.clone(),
-                                    base_url.clone(),
                                 );
                             }
                         }
-                        // search endpoints
-                        KeyCode::Char('/') => {
-                            if is_editing(&state) {
-                                let mut s = state.write().unwrap();
-                                s.request.param_edit_buffer.push('/');
-                            } else {
-                                search::handle_search_activate(state.clone());
+                    }
+                    // fill empty params from spec defaults/examples
+                    KeyCode::Char('d') => {
+                        if is_editing(&state) {
+                            let mut s = state.write_lock();
+                            s.request.param_edit_buffer.push('d');
+                        } else {
+                            let state_read = state.read_lock();
+                            let panel = state_read.ui.panel_focus.clone();
+                            let active_tab = state_read.ui.active_detail_tab.clone();
+                            drop(state_read);
+
+                            if panel == PanelFocus::Details && active_tab == DetailTab::Request {
+                                parameters::handle_fill_spec_defaults(
+                                    self.selected_index,
+                                    state.clone(),
+                                );
                             }
                         }
-                        // toggle body section
-                        KeyCode::Char('x') => {
-                            if is_editing(&state) {
-                                let mut s = state.write().unwrap();
-                                s.request.param_edit_buffer.push('x');
-                            } else {
-                                let state_read = state.read().unwrap();
-                                let panel = state_read.ui.panel_focus.clone();
-                                let active_tab = state_read.ui.active_detail_tab.clone();
-                                let supports_body = state_read
-                                    .get_selected_endpoint(self.selected_index)
-                                    .map(|ep| ep.supports_body())
-                                    .unwrap_or(false);
-                                drop(state_read);
-
-                                if panel == PanelFocus::Details
-                                    && active_tab == DetailTab::Request
-                                    && supports_body
-                                {
-                                    apply(state.clone(), AppAction::ToggleBodySection);
-                                }
+                    }
+                    // reset params and body of the selected endpoint (asks first)
+                    KeyCode::Char('R') => {
+                        if is_editing(&state) {
+                            let mut s = state.write_lock();
+                            s.request.param_edit_buffer.push('R');
+                        } else {
+                            let state_read = state.read_lock();
+                            let on_request_tab = state_read.ui.panel_focus == PanelFocus::Details
+                                && state_read.ui.active_detail_tab == DetailTab::Request;
+                            let endpoint_key = state_read
+                                .get_selected_endpoint(self.selected_index)
+                                .map(|ep| ep.key());
+                            drop(state_read);
+
+                            if let (true, Some(endpoint_key)) = (on_request_tab, endpoint_key) {
+                                apply(
+                                    state.clone(),
+                                    AppAction::EnterConfirmResetMode(endpoint_key),
+                                );
                             }
                         }
-                        // yank (copy) current line
-                        KeyCode::Char('y') => {
-                            if is_editing(&state) {
-                                let mut s = state.write().unwrap();
-                                s.request.param_edit_buffer.push('y');
-                            } else {
-                                let state_read = state.read().unwrap();
-                                let panel = state_read.ui.panel_focus.clone();
-                                let active_tab = state_read.ui.active_detail_tab.clone();
-                                drop(state_read);
+                    }
+                    // edit the note of the selected endpoint
+                    // (previous response search match on the Response tab)
+                    KeyCode::Char('N') => {
+                        if is_editing(&state) {
+                            let mut s = state.write_lock();
+                            s.request.param_edit_buffer.push('N');
+                        } else if navigation::on_response_tab(&state) {
+                            apply(
+                                state.clone(),
+                                AppAction::JumpToResponseMatch { forward: false },
+                            );
+                        } else {
+                            let state_read = state.read_lock();
+                            let on_endpoint_tab = state_read.ui.panel_focus == PanelFocus::Details
+                                && state_read.ui.active_detail_tab == DetailTab::Endpoint;
+                            let current_note = state_read
+                                .get_selected_endpoint(self.selected_index)
+                                .map(|ep| {
+                                    state_read
+                                        .data
+                                        .notes
+                                        .get(&ep.key())
+                                        .cloned()
+                                        .unwrap_or_default()
+                                });
+                            drop(state_read);
 
-                                if panel == PanelFocus::Details && active_tab == DetailTab::Response
-                                {
-                                    yank::handle_yank_response_line(state.clone());
-                                }
+                            if let (true, Some(note)) = (on_endpoint_tab, current_note) {
+                                apply(state.clone(), AppAction::EnterNoteMode(note));
+                            }
+                        }
+                    }
+                    // save current params/body as a named variant
+                    KeyCode::Char('S') => {
+                        if is_editing(&state) {
+                            let mut s = state.write_lock();
+                            s.request.param_edit_buffer.push('S');
+                        } else if parameters::on_request_tab(&state) {
+                            apply(state.clone(), AppAction::EnterVariantNameMode);
+                        }
+                    }
+                    // switch to the next saved variant
+                    KeyCode::Char('V') => {
+                        if is_editing(&state) {
+                            let mut s = state.write_lock();
+                            s.request.param_edit_buffer.push('V');
+                        } else if parameters::on_request_tab(&state) {
+                            let endpoint =
+                                state.read_lock().get_selected_endpoint(self.selected_index);
+                            if let Some(endpoint) = endpoint {
+                                apply(
+                                    state.clone(),
+                                    AppAction::CycleVariant {
+                                        endpoint_key: endpoint.key(),
+                                    },
+                                );
                             }
                         }
-                        // switch to endpoints panel
-                        KeyCode::Char('1') => {
-                            apply_or_char(
+                    }
+                    // grouping strategy - tag <-> path prefix
+                    KeyCode::Char('G') => {
+                        if is_editing(&state) {
+                            let mut s = state.write_lock();
+                            s.request.param_edit_buffer.push('G');
+                        } else if state.read_lock().ui.view_mode == ViewMode::Grouped {
+                            navigation::handle_cycle_group_by(
+                                &mut self.selected_index,
                                 state.clone(),
-                                '1',
-                                AppAction::NavigateToPanel(PanelFocus::EndpointsList),
+                                list_state,
                             );
                         }
-                        // switch to details panel
-                        KeyCode::Char('2') => {
-                            apply_or_char(
+                    }
+                    // toggle view - list <-> grouped
+                    KeyCode::Char('g') => {
+                        if is_editing(&state) {
+                            let mut s = state.write_lock();
+                            s.request.param_edit_buffer.push('g');
+                        } else {
+                            navigation::handle_toggle_view(
+                                &mut self.selected_index,
                                 state.clone(),
-                                '2',
-                                AppAction::NavigateToPanel(PanelFocus::Details),
+                                list_state,
                             );
                         }
-
-                        // ctrl + modifiers
-                        // retry
-                        KeyCode::Char('r')
-                            if key
-                                .modifiers
-                                .contains(crossterm::event::KeyModifiers::CONTROL) =>
-                        {
-                            should_fetch = execution::handle_retry(state.clone());
+                    }
+                    // switch to a spec registered in the config
+                    KeyCode::Char('W') => {
+                        if is_editing(&state) {
+                            let mut s = state.write_lock();
+                            s.request.param_edit_buffer.push('W');
+                        } else {
+                            apply(state.clone(), AppAction::OpenSpecSwitcher);
                         }
-
-                        // Ctrl+l: Clear search filter
-                        KeyCode::Char('l')
-                            if key
-                                .modifiers
-                                .contains(crossterm::event::KeyModifiers::CONTROL) =>
-                        {
-                            search::handle_search_clear(
+                    }
+                    // hide deprecated endpoints
+                    KeyCode::Char('h') => {
+                        if is_editing(&state) {
+                            let mut s = state.write_lock();
+                            s.request.param_edit_buffer.push('h');
+                        } else {
+                            search::handle_toggle_deprecated(
                                 &mut self.selected_index,
                                 state.clone(),
                                 list_state,
                             );
                         }
-
-                        // Special keys --
-                        // tab navigation
-                        KeyCode::Tab => {
-                            apply(state.clone(), AppAction::NavigateTabForward);
+                    }
+                    // config url
+                    KeyCode::Char(',') => {
+                        if is_editing(&state) {
+                            let mut s = state.write_lock();
+                            s.request.param_edit_buffer.push(',');
+                        } else {
+                            modals::handle_url_dialog(
+                                state.clone(),
+                                swagger_url.clone(),
+                                base_url.clone(),
+                            );
                         }
-                        // Shift+Tab (BackTab) - move left
-                        KeyCode::BackTab => {
-                            apply(state.clone(), AppAction::NavigateTabBackward);
+                    }
+                    // search endpoints
+                    KeyCode::Char('/') => {
+                        if is_editing(&state) {
+                            let mut s = state.write_lock();
+                            s.request.param_edit_buffer.push('/');
+                        } else if navigation::on_response_tab(&state) {
+                            apply(state.clone(), AppAction::EnterResponseSearchMode);
+                        } else {
+                            search::handle_search_activate(
+                                &mut self.selected_index,
+                                state.clone(),
+                                list_state,
+                            );
                         }
-                        // space  - execute & expand
-                        KeyCode::Char(' ') => {
-                            if is_editing(&state) {
-                                let mut s = state.write().unwrap();
-                                s.request.param_edit_buffer.push(' ');
-                            } else {
-                                execution::handle_enter(
-                                    &mut self.selected_index,
-                                    state.clone(),
-                                    list_state,
-                                    base_url.clone(),
-                                );
-                            }
+                    }
+                    // send the last executed request again
+                    KeyCode::Char('.') => {
+                        if is_editing(&state) {
+                            let mut s = state.write_lock();
+                            s.request.param_edit_buffer.push('.');
+                        } else {
+                            execution::handle_repeat_last(state.clone(), base_url.clone());
+                        }
+                    }
+                    // search within the selected group
+                    KeyCode::Char('F') => {
+                        if is_editing(&state) {
+                            let mut s = state.write_lock();
+                            s.request.param_edit_buffer.push('F');
+                        } else {
+                            search::handle_scoped_search_activate(
+                                &mut self.selected_index,
+                                state.clone(),
+                                list_state,
+                            );
                         }
-                        // enter - param confirm
-                        KeyCode::Enter => {
-                            let state_read = state.read().unwrap();
+                    }
+                    // toggle body section
+                    KeyCode::Char('x') => {
+                        if is_editing(&state) {
+                            let mut s = state.write_lock();
+                            s.request.param_edit_buffer.push('x');
+                        } else {
+                            let state_read = state.read_lock();
                             let panel = state_read.ui.panel_focus.clone();
                             let active_tab = state_read.ui.active_detail_tab.clone();
-                            let edit_mode = state_read.request.edit_mode.clone();
+                            let supports_body = state_read
+                                .get_selected_endpoint(self.selected_index)
+                                .map(|ep| ep.supports_body())
+                                .unwrap_or(false);
                             drop(state_read);
 
-                            use crate::types::PanelFocus;
-
-                            // ONLY handle if on Request tab and in Editing mode
                             if panel == PanelFocus::Details
                                 && active_tab == DetailTab::Request
-                                && matches!(edit_mode, RequestEditMode::Editing(_))
+                                && supports_body
                             {
-                                parameters::handle_request_param_confirm(
-                                    self.selected_index,
+                                apply(state.clone(), AppAction::ToggleBodySection);
+                            } else if panel == PanelFocus::Details
+                                && active_tab == DetailTab::Endpoint
+                            {
+                                apply(state.clone(), AppAction::ToggleExtensionsSection);
+                            }
+                        }
+                    }
+                    // pick a server from the spec and fill in its variables
+                    KeyCode::Char('U') => {
+                        if is_editing(&state) {
+                            let mut s = state.write_lock();
+                            s.request.param_edit_buffer.push('U');
+                        } else {
+                            modals::handle_server_form_open(state.clone());
+                        }
+                    }
+                    // console
+                    KeyCode::Char(':') => {
+                        if is_editing(&state) {
+                            let mut s = state.write_lock();
+                            s.request.param_edit_buffer.push(':');
+                        } else {
+                            apply(state.clone(), AppAction::OpenConsole);
+                        }
+                    }
+                    // plugin commands
+                    KeyCode::Char('P') => {
+                        if is_editing(&state) {
+                            let mut s = state.write_lock();
+                            s.request.param_edit_buffer.push('P');
+                        } else {
+                            modals::handle_plugin_commands_open(state.clone());
+                        }
+                    }
+                    // requests in flight
+                    KeyCode::Char('J') => {
+                        if is_editing(&state) {
+                            let mut s = state.write_lock();
+                            s.request.param_edit_buffer.push('J');
+                        } else {
+                            apply(state.clone(), AppAction::OpenJobs);
+                        }
+                    }
+                    // undo the last request edit
+                    KeyCode::Char('u') if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        if is_editing(&state) {
+                            let mut s = state.write_lock();
+                            s.request.param_edit_buffer.push('u');
+                        } else {
+                            apply(state.clone(), AppAction::Undo);
+                        }
+                    }
+                    // spec info view
+                    KeyCode::Char('i') => {
+                        if is_editing(&state) {
+                            let mut s = state.write_lock();
+                            s.request.param_edit_buffer.push('i');
+                        } else {
+                            apply(state.clone(), AppAction::OpenSpecInfo);
+                        }
+                    }
+                    // previous / next workspace tab
+                    KeyCode::Char(c @ ('{' | '}')) => {
+                        if is_editing(&state) {
+                            let mut s = state.write_lock();
+                            s.request.param_edit_buffer.push(c);
+                        } else {
+                            let command = if c == '{' {
+                                WorkspaceCommand::Previous
+                            } else {
+                                WorkspaceCommand::Next
+                            };
+                            apply(state.clone(), AppAction::ChangeWorkspace(command));
+                        }
+                    }
+                    // copy a Rust client function for the selected endpoint
+                    KeyCode::Char('C') => {
+                        if is_editing(&state) {
+                            let mut s = state.write_lock();
+                            s.request.param_edit_buffer.push('C');
+                        } else {
+                            yank::handle_yank_rust_client(
+                                self.selected_index,
+                                state.clone(),
+                                base_url.clone(),
+                            );
+                        }
+                    }
+                    // usage stats view
+                    KeyCode::Char('T') => {
+                        if is_editing(&state) {
+                            let mut s = state.write_lock();
+                            s.request.param_edit_buffer.push('T');
+                        } else {
+                            apply(state.clone(), AppAction::OpenStats);
+                        }
+                    }
+                    // changes the spec went through since the last load
+                    KeyCode::Char('A') => {
+                        if is_editing(&state) {
+                            let mut s = state.write_lock();
+                            s.request.param_edit_buffer.push('A');
+                        } else {
+                            apply(state.clone(), AppAction::OpenSpecChanges);
+                        }
+                    }
+                    // fetch the spec again and report what changed
+                    KeyCode::F(5) if !is_editing(&state) && swagger_url.is_some() => {
+                        let loading = matches!(
+                            state.read_lock().data.loading_state,
+                            LoadingState::Fetching | LoadingState::Parsing
+                        );
+                        if !loading {
+                            apply(state.clone(), AppAction::RefreshSpec);
+                            should_fetch = true;
+                        }
+                    }
+                    // reload the spec on an interval
+                    KeyCode::F(6) if !is_editing(&state) => {
+                        apply(state.clone(), AppAction::ToggleWatch);
+                    }
+                    // export the session's traffic as HAR or JSON Lines
+                    KeyCode::Char('H') => {
+                        if is_editing(&state) {
+                            let mut s = state.write_lock();
+                            s.request.param_edit_buffer.push('H');
+                        } else {
+                            modals::handle_traffic_export_dialog(state.clone());
+                        }
+                    }
+                    // switch the body content type
+                    KeyCode::Char('c') if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        if is_editing(&state) {
+                            let mut s = state.write_lock();
+                            s.request.param_edit_buffer.push('c');
+                        } else {
+                            let state_read = state.read_lock();
+                            let on_request_tab = state_read.ui.panel_focus == PanelFocus::Details
+                                && state_read.ui.active_detail_tab == DetailTab::Request;
+                            let endpoint_key = state_read
+                                .get_selected_endpoint(self.selected_index)
+                                .filter(|ep| ep.supports_body())
+                                .map(|ep| ep.key());
+                            drop(state_read);
+
+                            if let (true, Some(endpoint_key)) = (on_request_tab, endpoint_key) {
+                                apply(state.clone(), AppAction::CycleContentType { endpoint_key });
+                            }
+                        }
+                    }
+                    // table view of array responses
+                    KeyCode::Char('t') if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        if is_editing(&state) {
+                            let mut s = state.write_lock();
+                            s.request.param_edit_buffer.push('t');
+                        } else if navigation::on_response_tab(&state) {
+                            navigation::handle_toggle_response_table(state.clone());
+                        }
+                    }
+                    // switch between the two- and three-pane layout
+                    KeyCode::Char('|') => {
+                        apply_or_char(state.clone(), '|', AppAction::ToggleThreePane);
+                    }
+                    // panels next to each other, stacked, or by terminal width
+                    KeyCode::Char('\\') => {
+                        apply_or_char(state.clone(), '\\', AppAction::CycleArrangement);
+                    }
+                    // wrap long response lines
+                    KeyCode::Char('w') if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        if is_editing(&state) {
+                            let mut s = state.write_lock();
+                            s.request.param_edit_buffer.push('w');
+                        } else if navigation::on_response_tab(&state) {
+                            apply(state.clone(), AppAction::ToggleResponseWrap);
+                        }
+                    }
+                    // large responses: show more lines; open the body in a pager / browser
+                    KeyCode::Char('L') => {
+                        if is_editing(&state) {
+                            let mut s = state.write_lock();
+                            s.request.param_edit_buffer.push('L');
+                        } else if navigation::on_response_tab(&state) {
+                            apply(state.clone(), AppAction::LoadMoreResponseLines);
+                        }
+                    }
+                    KeyCode::Char('O') => {
+                        if is_editing(&state) {
+                            let mut s = state.write_lock();
+                            s.request.param_edit_buffer.push('O');
+                        } else if navigation::on_response_tab(&state) {
+                            apply(
+                                state.clone(),
+                                AppAction::OpenResponseExternally(ExternalViewer::Pager),
+                            );
+                        }
+                    }
+                    KeyCode::Char('B') => {
+                        if is_editing(&state) {
+                            let mut s = state.write_lock();
+                            s.request.param_edit_buffer.push('B');
+                        } else if navigation::on_response_tab(&state) {
+                            apply(
+                                state.clone(),
+                                AppAction::OpenResponseExternally(ExternalViewer::Browser),
+                            );
+                        }
+                    }
+                    // save the response as a named snapshot
+                    KeyCode::Char('M') => {
+                        if is_editing(&state) {
+                            let mut s = state.write_lock();
+                            s.request.param_edit_buffer.push('M');
+                        } else if navigation::on_response_tab(&state) {
+                            let has_response = state
+                                .read_lock()
+                                .request
+                                .current_response
+                                .as_ref()
+                                .is_some_and(|response| !response.is_error);
+                            if has_response {
+                                apply(state.clone(), AppAction::EnterSnapshotNameMode);
+                            }
+                        }
+                    }
+                    // diff the response against the next saved snapshot
+                    KeyCode::Char('D') => {
+                        if is_editing(&state) {
+                            let mut s = state.write_lock();
+                            s.request.param_edit_buffer.push('D');
+                        } else if navigation::on_response_tab(&state) {
+                            let endpoint =
+                                state.read_lock().get_selected_endpoint(self.selected_index);
+                            if let Some(endpoint) = endpoint {
+                                apply(
                                     state.clone(),
+                                    AppAction::CycleSnapshotDiff {
+                                        endpoint_key: endpoint.key(),
+                                    },
                                 );
                             }
                         }
-                        // backspace - param edit
-                        KeyCode::Backspace => {
-                            let state_read = state.read().unwrap();
-                            let panel = state_read.ui.panel_focus.clone();
-                            let active_tab = state_read.ui.active_detail_tab.clone();
-                            let edit_mode = state_read.request.edit_mode.clone();
+                    }
+                    // pick a link from the response to follow
+                    KeyCode::Char('f') => {
+                        if is_editing(&state) {
+                            let mut s = state.write_lock();
+                            s.request.param_edit_buffer.push('f');
+                        } else if navigation::on_response_tab(&state) {
+                            modals::handle_link_picker_open(state.clone(), base_url.as_deref());
+                        }
+                    }
+                    // export the response table to CSV, or save a binary body
+                    KeyCode::Char('X') => {
+                        if is_editing(&state) {
+                            let mut s = state.write_lock();
+                            s.request.param_edit_buffer.push('X');
+                        } else if navigation::on_response_tab(&state) {
+                            modals::handle_export_dialog(self.selected_index, state.clone());
+                        } else {
+                            modals::handle_script_export_dialog(self.selected_index, state.clone());
+                        }
+                    }
+                    // sort the response table by the next column / flip the order
+                    KeyCode::Char('s') => {
+                        if is_editing(&state) {
+                            let mut s = state.write_lock();
+                            s.request.param_edit_buffer.push('s');
+                        } else if navigation::on_response_tab(&state) {
+                            navigation::handle_cycle_table_sort(state.clone());
+                        }
+                    }
+                    KeyCode::Char('o') => {
+                        if is_editing(&state) {
+                            let mut s = state.write_lock();
+                            s.request.param_edit_buffer.push('o');
+                        } else if navigation::on_response_tab(&state) {
+                            apply(state.clone(), AppAction::ToggleTableSortOrder);
+                        }
+                    }
+                    // visual line selection in the Response tab
+                    KeyCode::Char('v') => {
+                        if is_editing(&state) {
+                            let mut s = state.write_lock();
+                            s.request.param_edit_buffer.push('v');
+                        } else {
+                            let state_read = state.read_lock();
+                            let in_response = state_read.ui.panel_focus == PanelFocus::Details
+                                && state_read.ui.active_detail_tab == DetailTab::Response
+                                && state_read.request.current_response.is_some()
+                                && state_read.response_table().is_none();
                             drop(state_read);
 
-                            use crate::types::PanelFocus;
+                            if in_response {
+                                apply(state.clone(), AppAction::ToggleResponseVisual);
+                            }
+                        }
+                    }
+                    // yank (copy) the JSONPath of the current response line
+                    KeyCode::Char('p') => {
+                        if is_editing(&state) {
+                            let mut s = state.write_lock();
+                            s.request.param_edit_buffer.push('p');
+                        } else {
+                            let state_read = state.read_lock();
+                            let in_response = state_read.ui.panel_focus == PanelFocus::Details
+                                && state_read.ui.active_detail_tab == DetailTab::Response
+                                && state_read.response_table().is_none();
+                            drop(state_read);
 
-                            // ONLY handle if on Request tab and in Editing mode
-                            if panel == PanelFocus::Details
-                                && active_tab == DetailTab::Request
-                                && matches!(edit_mode, RequestEditMode::Editing(_))
-                            {
-                                apply(state.clone(), AppAction::BackspaceParamBuffer);
+                            if in_response {
+                                yank::handle_yank_json_path(state.clone());
                             }
                         }
-                        // esc - cancel param edit
-                        KeyCode::Esc => {
-                            let state_read = state.read().unwrap();
+                    }
+                    // yank (copy) current line or visual selection
+                    KeyCode::Char('y') => {
+                        if is_editing(&state) {
+                            let mut s = state.write_lock();
+                            s.request.param_edit_buffer.push('y');
+                        } else {
+                            let state_read = state.read_lock();
                             let panel = state_read.ui.panel_focus.clone();
                             let active_tab = state_read.ui.active_detail_tab.clone();
-                            let edit_mode = state_read.request.edit_mode.clone();
                             drop(state_read);
 
-                            use crate::types::PanelFocus;
+                            if panel == PanelFocus::Details && active_tab == DetailTab::Response {
+                                let (table_view, visual) = {
+                                    let s = state.read_lock();
+                                    (s.response_table().is_some(), s.ui.response_visual_anchor)
+                                };
+                                if table_view {
+                                    yank::handle_yank_table_row(state.clone());
+                                } else if visual.is_some() {
+                                    yank::handle_yank_response_selection(state.clone());
+                                } else {
+                                    yank::handle_yank_response_line(state.clone());
+                                }
+                            }
+                        }
+                    }
+                    // switch to endpoints panel
+                    KeyCode::Char('1') => {
+                        apply_or_char(
+                            state.clone(),
+                            '1',
+                            AppAction::NavigateToPanel(PanelFocus::EndpointsList),
+                        );
+                    }
+                    // switch to details panel
+                    KeyCode::Char('2') => {
+                        apply_or_char(
+                            state.clone(),
+                            '2',
+                            AppAction::NavigateToPanel(PanelFocus::Details),
+                        );
+                    }
 
-                            // ONLY handle if on Request tab and in Editing mode
-                            if panel == PanelFocus::Details
-                                && active_tab == DetailTab::Request
-                                && matches!(edit_mode, RequestEditMode::Editing(_))
+                    // ctrl + modifiers
+                    // retry after a failed spec load, else redo the last undone edit
+                    KeyCode::Char('r')
+                        if key
+                            .modifiers
+                            .contains(crossterm::event::KeyModifiers::CONTROL) =>
+                    {
+                        should_fetch = execution::handle_retry(state.clone());
+                        if !should_fetch && !is_editing(&state) {
+                            apply(state.clone(), AppAction::Redo);
+                        }
+                    }
+
+                    // Ctrl+l: Clear search filter
+                    KeyCode::Char('l')
+                        if key
+                            .modifiers
+                            .contains(crossterm::event::KeyModifiers::CONTROL) =>
+                    {
+                        search::handle_search_clear(
+                            &mut self.selected_index,
+                            state.clone(),
+                            list_state,
+                        );
+                    }
+
+                    // Ctrl+T / Ctrl+W: open a new workspace tab / close the current one
+                    KeyCode::Char(c @ ('t' | 'w'))
+                        if key.modifiers.contains(KeyModifiers::CONTROL) && !is_editing(&state) =>
+                    {
+                        let command = if c == 't' {
+                            WorkspaceCommand::Open
+                        } else {
+                            WorkspaceCommand::Close
+                        };
+                        apply(state.clone(), AppAction::ChangeWorkspace(command));
+                    }
+
+                    // Ctrl+U / Ctrl+N / Ctrl+T: insert generated UUID / int / timestamp
+                    KeyCode::Char(c @ ('u' | 'n' | 't'))
+                        if key.modifiers.contains(KeyModifiers::CONTROL) && is_editing(&state) =>
+                    {
+                        let generator = match c {
+                            'u' => Generator::Uuid,
+                            'n' => Generator::Int,
+                            _ => Generator::Timestamp,
+                        };
+                        parameters::handle_generate_value(
+                            self.selected_index,
+                            state.clone(),
+                            generator,
+                        );
+                    }
+
+                    // Special keys --
+                    // tab navigation (accepts a value suggestion while editing)
+                    KeyCode::Tab => {
+                        let has_suggestion =
+                            state.read_lock().request.selected_suggestion().is_some();
+                        if has_suggestion {
+                            apply(state.clone(), AppAction::AcceptParamSuggestion);
+                        } else {
+                            apply(state.clone(), AppAction::NavigateTabForward);
+                        }
+                    }
+                    // Shift+Tab (BackTab) - move left
+                    KeyCode::BackTab => {
+                        apply(state.clone(), AppAction::NavigateTabBackward);
+                    }
+                    // space  - execute & expand
+                    KeyCode::Char(' ') => {
+                        if is_editing(&state) {
+                            let mut s = state.write_lock();
+                            s.request.param_edit_buffer.push(' ');
+                        } else {
+                            let state_read = state.read_lock();
+                            let on_request_tab = state_read.ui.panel_focus == PanelFocus::Details
+                                && state_read.ui.active_detail_tab == DetailTab::Request;
+                            drop(state_read);
+
+                            // Boolean params take Space as a toggle, enum params cycle their values
+                            if !(on_request_tab
+                                && (parameters::handle_bool_toggle(
+                                    self.selected_index,
+                                    state.clone(),
+                                ) || parameters::handle_enum_cycle(
+                                    self.selected_index,
+                                    state.clone(),
+                                )))
                             {
-                                apply(state.clone(), AppAction::CancelParameterEdit);
+                                execution::handle_enter(
+                                    &mut self.selected_index,
+                                    state.clone(),
+                                    list_state,
+                                    base_url.clone(),
+                                );
                             }
                         }
+                    }
+                    // enter - param confirm
+                    KeyCode::Enter => {
+                        let state_read = state.read_lock();
+                        let panel = state_read.ui.panel_focus.clone();
+                        let active_tab = state_read.ui.active_detail_tab.clone();
+                        let edit_mode = state_read.request.edit_mode.clone();
+                        drop(state_read);
 
-                        // keep arrow keys for accessibility (optional)
-                        KeyCode::Up => {
-                            if !is_editing(&state) {
-                                let state_read = state.read().unwrap();
-                                let panel = state_read.ui.panel_focus.clone();
-                                let active_tab = state_read.ui.active_detail_tab.clone();
-                                drop(state_read);
+                        use crate::types::PanelFocus;
 
-                                use crate::types::PanelFocus;
-                                match panel {
-                                    PanelFocus::EndpointsList => {
-                                        navigation::handle_up(
-                                            &mut self.selected_index,
-                                            state.clone(),
-                                            list_state,
-                                        );
-                                    }
-                                    PanelFocus::Details => {
-                                        if active_tab == DetailTab::Request {
-                                            navigation::handle_request_param_up(state.clone());
-                                        }
-                                    }
+                        // ONLY handle if on Request tab and in Editing mode
+                        if panel == PanelFocus::Details
+                            && active_tab == DetailTab::Request
+                            && matches!(edit_mode, RequestEditMode::Editing(_))
+                        {
+                            parameters::handle_request_param_confirm(
+                                self.selected_index,
+                                state.clone(),
+                            );
+                        } else if panel == PanelFocus::Details && active_tab == DetailTab::Request {
+                            parameters::handle_bool_toggle(self.selected_index, state.clone());
+                        }
+                    }
+                    // backspace - param edit
+                    KeyCode::Backspace => {
+                        let state_read = state.read_lock();
+                        let panel = state_read.ui.panel_focus.clone();
+                        let active_tab = state_read.ui.active_detail_tab.clone();
+                        let edit_mode = state_read.request.edit_mode.clone();
+                        drop(state_read);
+
+                        use crate::types::PanelFocus;
+
+                        // ONLY handle if on Request tab and in Editing mode
+                        if panel == PanelFocus::Details
+                            && active_tab == DetailTab::Request
+                            && matches!(edit_mode, RequestEditMode::Editing(_))
+                        {
+                            apply(state.clone(), AppAction::BackspaceParamBuffer);
+                        }
+                    }
+                    // esc - dismiss the error banner first
+                    KeyCode::Esc if state.read_lock().ui.error_banner.is_some() => {
+                        apply(state.clone(), AppAction::DismissError);
+                    }
+                    // esc - cancel param edit
+                    KeyCode::Esc => {
+                        let state_read = state.read_lock();
+                        let panel = state_read.ui.panel_focus.clone();
+                        let active_tab = state_read.ui.active_detail_tab.clone();
+                        let edit_mode = state_read.request.edit_mode.clone();
+                        drop(state_read);
+
+                        use crate::types::PanelFocus;
+
+                        // ONLY handle if on Request tab and in Editing mode
+                        if panel == PanelFocus::Details
+                            && active_tab == DetailTab::Request
+                            && matches!(edit_mode, RequestEditMode::Editing(_))
+                        {
+                            apply(state.clone(), AppAction::CancelParameterEdit);
+                        }
+                        if panel == PanelFocus::Details && active_tab == DetailTab::Response {
+                            apply(state.clone(), AppAction::ExitResponseVisual);
+                        }
+                    }
+
+                    // keep arrow keys for accessibility (optional)
+                    KeyCode::Up if !is_editing(&state) => {
+                        let state_read = state.read_lock();
+                        let panel = state_read.ui.panel_focus.clone();
+                        let active_tab = state_read.ui.active_detail_tab.clone();
+                        drop(state_read);
+
+                        use crate::types::PanelFocus;
+                        match panel {
+                            PanelFocus::EndpointsList => {
+                                navigation::handle_up(
+                                    &mut self.selected_index,
+                                    state.clone(),
+                                    list_state,
+                                );
+                            }
+                            PanelFocus::Details => {
+                                if active_tab == DetailTab::Request {
+                                    navigation::handle_request_param_up(state.clone());
                                 }
                             }
                         }
+                    }
 
-                        KeyCode::Down => {
-                            if !is_editing(&state) {
-                                let state_read = state.read().unwrap();
-                                let panel = state_read.ui.panel_focus.clone();
-                                let active_tab = state_read.ui.active_detail_tab.clone();
-                                drop(state_read);
+                    KeyCode::Down if !is_editing(&state) => {
+                        let state_read = state.read_lock();
+                        let panel = state_read.ui.panel_focus.clone();
+                        let active_tab = state_read.ui.active_detail_tab.clone();
+                        drop(state_read);
 
-                                use crate::types::PanelFocus;
-                                match panel {
-                                    PanelFocus::EndpointsList => {
-                                        navigation::handle_down(
-                                            &mut self.selected_index,
-                                            state.clone(),
-                                            list_state,
-                                        );
-                                    }
-                                    PanelFocus::Details => {
-                                        if active_tab == DetailTab::Request {
-                                            navigation::handle_request_param_down(
-                                                self.selected_index,
-                                                state.clone(),
-                                            );
-                                        }
-                                    }
+                        use crate::types::PanelFocus;
+                        match panel {
+                            PanelFocus::EndpointsList => {
+                                navigation::handle_down(
+                                    &mut self.selected_index,
+                                    state.clone(),
+                                    list_state,
+                                );
+                            }
+                            PanelFocus::Details => {
+                                if active_tab == DetailTab::Request {
+                                    navigation::handle_request_param_down(
+                                        self.selected_index,
+                                        state.clone(),
+                                    );
                                 }
                             }
                         }
+                    }
 
-                        KeyCode::Char(c)
-                            if !key.modifiers.contains(KeyModifiers::CONTROL) && c != ' ' =>
-                        {
-                            let state_read = state.read().unwrap();
-                            let panel = state_read.ui.panel_focus.clone();
-                            let active_tab = state_read.ui.active_detail_tab.clone();
-                            let edit_mode = state_read.request.edit_mode.clone();
-                            drop(state_read);
+                    // arrows cycle value suggestions while editing
+                    KeyCode::Up => {
+                        apply(state.clone(), AppAction::PrevParamSuggestion);
+                    }
+                    KeyCode::Down => {
+                        apply(state.clone(), AppAction::NextParamSuggestion);
+                    }
 
-                            use crate::types::PanelFocus;
+                    // date helper: now, ±1 day, ±1 hour
+                    // (plain keys only; Shift is allowed as some layouts need it for '+')
+                    KeyCode::Char(c @ ('n' | '+' | '-' | '[' | ']'))
+                        if key.modifiers.difference(KeyModifiers::SHIFT).is_empty()
+                            && !is_editing(&state) =>
+                    {
+                        let state_read = state.read_lock();
+                        let on_request_tab = state_read.ui.panel_focus == PanelFocus::Details
+                            && state_read.ui.active_detail_tab == DetailTab::Request;
+                        drop(state_read);
 
-                            if panel == PanelFocus::Details
-                                && active_tab == DetailTab::Request
-                                && matches!(edit_mode, RequestEditMode::Editing(_))
-                            {
-                                let mut s = state.write().unwrap();
-                                s.request.param_edit_buffer.push(c);
-                                log_debug(&format!(
-                                    "Added char, buffer now: {}",
-                                    s.request.param_edit_buffer
-                                ));
-                            } else {
-                                log_debug("Conditions not met for character input");
-                            }
+                        let adjust = match c {
+                            'n' => DateAdjust::Now,
+                            '+' => DateAdjust::Days(1),
+                            '-' => DateAdjust::Days(-1),
+                            ']' => DateAdjust::Hours(1),
+                            _ => DateAdjust::Hours(-1),
+                        };
+                        if on_request_tab {
+                            parameters::handle_date_adjust(
+                                self.selected_index,
+                                state.clone(),
+                                adjust,
+                            );
+                        } else if c == 'n' && navigation::on_response_tab(&state) {
+                            // next response search match
+                            apply(
+                                state.clone(),
+                                AppAction::JumpToResponseMatch { forward: true },
+                            );
                         }
+                    }
 
-                        _ => {}
-                    },
-                }
+                    KeyCode::Char(c)
+                        if !key.modifiers.contains(KeyModifiers::CONTROL) && c != ' ' =>
+                    {
+                        let state_read = state.read_lock();
+                        let panel = state_read.ui.panel_focus.clone();
+                        let active_tab = state_read.ui.active_detail_tab.clone();
+                        let edit_mode = state_read.request.edit_mode.clone();
+                        drop(state_read);
+
+                        use crate::types::PanelFocus;
+
+                        if panel == PanelFocus::Details
+                            && active_tab == DetailTab::Request
+                            && matches!(edit_mode, RequestEditMode::Editing(_))
+                        {
+                            let mut s = state.write_lock();
+                            s.request.param_edit_buffer.push(c);
+                            log_debug(&format!(
+                                "Added char, buffer now: {}",
+                                s.request.param_edit_buffer
+                            ));
+                        } else {
+                            log_debug("Conditions not met for character input");
+                        }
+                    }
+
+                    _ => {}
+                },
             }
         }
         Ok((should_fetch, url_submitted))
@@ -6,13 +6,27 @@
 //! - Confirmation dialogs
 
 use super::helpers::{apply, apply_many, collect_paste_batch, log_debug};
-use crate::actions::AppAction;
+use crate::actions::{apply_action, AppAction};
+use crate::codegen::{self, ScriptRequest};
 use crate::config;
-use crate::state::AppState;
-use crate::types::{InputMode, UrlInputField, UrlSubmission};
+use crate::content::{body_file_name, BodyKind};
+use crate::diagnostics;
+use crate::discovery;
+use crate::editor::{self, ContentType};
+use crate::generators::{self, Generator};
+use crate::links;
+use crate::request;
+use crate::servers::ServerForm;
+use crate::state::{is_partial_json_object, AppState, LockRecover};
+use crate::table::csv_file_name;
+use crate::tasks;
+use crate::traffic;
+use crate::types::{InputMode, UrlInputField, UrlSubmission, WorkspaceCommand};
+use crate::utils::{write_export, write_script};
 use color_eyre::Result;
 use crossterm::event::KeyCode;
 use std::sync::{Arc, RwLock};
+use std::time::Instant;
 
 /// Handle URL dialog activation
 pub fn handle_url_dialog(
@@ -38,10 +52,16 @@ pub fn handle_url_input(
     use crossterm::event::KeyModifiers;
 
     match key.code {
+        KeyCode::Tab | KeyCode::Right if state.read_lock().input.url_completion().is_some() => {
+            // Accept the inline suggestion from URL history
+            apply(state, AppAction::AcceptUrlCompletion);
+            log_debug("Accepted URL completion from history");
+        }
+
         KeyCode::Tab => {
             // Switch between fields
             let current_field = {
-                let s = state.read().unwrap();
+                let s = state.read_lock();
                 s.input.active_url_field.clone()
             };
 
@@ -54,52 +74,48 @@ pub fn handle_url_input(
         }
 
         KeyCode::Enter => {
-            let mut s = state.write().unwrap();
-            let swagger_url = s.input.url_input.trim().to_string();
+            let mut s = state.write_lock();
+            let swagger_url = config::normalize_spec_url(s.input.url_input.trim());
             let base_url = s.input.base_url_input.trim().to_string();
 
-            if !swagger_url.is_empty() {
-                // Validate both URLs
-                match config::validate_url(&swagger_url) {
-                    Ok(_) => {
-                        // Also validate base URL if provided
-                        if !base_url.is_empty() {
-                            if let Err(e) = config::validate_url(&base_url) {
-                                log_debug(&format!("Invalid base URL: {e}"));
-                                // Keep modal open
-                                return Ok(None);
-                            }
-                        }
-
-                        s.input.mode = InputMode::Normal;
-
-                        let submission = UrlSubmission {
-                            swagger_url: swagger_url.clone(),
-                            base_url: if base_url.is_empty() {
-                                None
-                            } else {
-                                Some(base_url.clone())
-                            },
-                        };
-
-                        s.input.url_input.clear();
-                        s.input.base_url_input.clear();
-                        s.input.active_url_field = UrlInputField::SwaggerUrl;
-
-                        log_debug(&format!(
-                            "URLs submitted - Swagger: {}, Base: {:?}",
-                            submission.swagger_url, submission.base_url
-                        ));
+            // Validate both URLs, keeping the modal open on error
+            if let Err(e) = config::validate_spec_url(&swagger_url) {
+                log_debug(&format!("Invalid swagger URL: {e}"));
+                apply_action(AppAction::SetUrlError(UrlInputField::SwaggerUrl, e), &mut s);
+                return Ok(None);
+            }
 
-                        return Ok(Some(submission));
-                    }
-                    Err(e) => {
-                        log_debug(&format!("Invalid swagger URL: {e}"));
-                    }
+            // Base URL is optional, but must be valid if provided
+            if !base_url.is_empty() {
+                if let Err(e) = config::validate_url(&base_url) {
+                    log_debug(&format!("Invalid base URL: {e}"));
+                    apply_action(AppAction::SetUrlError(UrlInputField::BaseUrl, e), &mut s);
+                    return Ok(None);
                 }
-            } else {
-                log_debug("Empty swagger URL, not submitting");
             }
+
+            s.input.mode = InputMode::Normal;
+
+            let submission = UrlSubmission {
+                swagger_url: swagger_url.clone(),
+                base_url: if base_url.is_empty() {
+                    None
+                } else {
+                    Some(base_url.clone())
+                },
+            };
+
+            s.input.url_input.clear();
+            s.input.base_url_input.clear();
+            s.input.active_url_field = UrlInputField::SwaggerUrl;
+            s.input.url_error = None;
+
+            log_debug(&format!(
+                "URLs submitted - Swagger: {}, Base: {:?}",
+                submission.swagger_url, submission.base_url
+            ));
+
+            return Ok(Some(submission));
         }
 
         KeyCode::Esc => {
@@ -107,9 +123,19 @@ pub fn handle_url_input(
             log_debug("URL input cancelled");
         }
 
+        // Up/Down on the base URL field: fill in a server of the loaded spec
+        KeyCode::Up | KeyCode::Down
+            if state.read_lock().input.active_url_field == UrlInputField::BaseUrl =>
+        {
+            apply(
+                state,
+                AppAction::CycleBaseUrlServer(key.code == KeyCode::Down),
+            );
+        }
+
         KeyCode::Backspace => {
             let active_field = {
-                let s = state.read().unwrap();
+                let s = state.read_lock();
                 s.input.active_url_field.clone()
             };
 
@@ -126,7 +152,7 @@ pub fn handle_url_input(
         KeyCode::Char('w') if key.modifiers.contains(KeyModifiers::CONTROL) => {
             // Ctrl+W: Delete word backwards
             let active_field = {
-                let s = state.read().unwrap();
+                let s = state.read_lock();
                 s.input.active_url_field.clone()
             };
 
@@ -140,10 +166,15 @@ pub fn handle_url_input(
             }
         }
 
+        KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            // Ctrl+D: Pick a spec from running Docker containers and Kubernetes services
+            handle_spec_discovery_open(state);
+        }
+
         KeyCode::Char('l') if key.modifiers.contains(KeyModifiers::CONTROL) => {
             // Ctrl+L: Clear current field (matching search behavior)
             let active_field = {
-                let s = state.read().unwrap();
+                let s = state.read_lock();
                 s.input.active_url_field.clone()
             };
 
@@ -163,7 +194,7 @@ pub fn handle_url_input(
             let (batch_str, char_count) = collect_paste_batch(c);
 
             let active_field = {
-                let s = state.read().unwrap();
+                let s = state.read_lock();
                 s.input.active_url_field.clone()
             };
 
@@ -205,7 +236,7 @@ pub fn handle_token_input(
     match key.code {
         KeyCode::Enter => {
             let token = {
-                let s = state.read().unwrap();
+                let s = state.read_lock();
                 s.input.token_input.trim().to_string()
             };
 
@@ -227,6 +258,9 @@ pub fn handle_token_input(
             apply(state, AppAction::ExitTokenInputMode);
             log_debug("Token input cancelled");
         }
+        KeyCode::Tab => {
+            apply(state, AppAction::NextAuthScheme);
+        }
         KeyCode::Backspace => {
             apply(state, AppAction::BackspaceTokenInput);
         }
@@ -237,6 +271,9 @@ pub fn handle_token_input(
         KeyCode::Char('w') if key.modifiers.contains(KeyModifiers::CONTROL) => {
             apply(state, AppAction::DeleteWordTokenInput);
         }
+        KeyCode::Char('r') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            apply(state, AppAction::ToggleSecretReveal);
+        }
         KeyCode::Char(c) => {
             let (batch_str, char_count) = collect_paste_batch(c);
 
@@ -251,6 +288,83 @@ pub fn handle_token_input(
     Ok(())
 }
 
+/// Handle bulk parameter form input (with paste batching support)
+pub fn handle_param_form_input(
+    key: crossterm::event::KeyEvent,
+    state: Arc<RwLock<AppState>>,
+) -> Result<()> {
+    use crossterm::event::KeyModifiers;
+
+    match key.code {
+        KeyCode::Enter => {
+            apply(state, AppAction::SubmitParamForm);
+            log_debug("Parameter form saved");
+        }
+        KeyCode::Esc => {
+            apply(state, AppAction::CancelParamForm);
+            log_debug("Parameter form cancelled");
+        }
+        KeyCode::Tab | KeyCode::Down => {
+            apply(state, AppAction::ParamFormNextField);
+        }
+        KeyCode::BackTab | KeyCode::Up => {
+            apply(state, AppAction::ParamFormPrevField);
+        }
+        KeyCode::Backspace => {
+            apply(state, AppAction::BackspaceParamForm);
+        }
+        KeyCode::Char('l') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            apply(state, AppAction::ClearParamFormField);
+        }
+        KeyCode::Char('w') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            apply(state, AppAction::DeleteWordParamForm);
+        }
+        KeyCode::Char('r') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            apply(state, AppAction::ToggleSecretReveal);
+        }
+        KeyCode::Char(' ') if active_param_form_field_is_bool(&state) => {
+            apply(state, AppAction::CycleParamFormBool);
+        }
+        KeyCode::Char(c @ ('u' | 'n' | 't')) if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            let generator = match c {
+                'u' => Generator::Uuid,
+                'n' => Generator::Int,
+                _ => Generator::Timestamp,
+            };
+            let value = {
+                let s = state.read_lock();
+                let form = &s.input.param_form;
+                let param = form.fields.get(form.active_field).and_then(|field| {
+                    s.get_endpoint_by_key(&form.endpoint_key)
+                        .and_then(|ep| ep.parameters.iter().find(|p| p.name == field.name))
+                });
+                generators::generate(generator, param)
+            };
+            apply(state, AppAction::AppendToParamForm(value));
+        }
+        KeyCode::Char(c) => {
+            let (batch_str, char_count) = collect_paste_batch(c);
+
+            apply(state, AppAction::AppendToParamForm(batch_str));
+
+            if char_count > 1 {
+                log_debug(&format!("Batched {char_count} characters (paste detected)"));
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Whether the focused field of the parameter form is a boolean toggle
+fn active_param_form_field_is_bool(state: &Arc<RwLock<AppState>>) -> bool {
+    let s = state.read_lock();
+    let form = &s.input.param_form;
+    form.fields
+        .get(form.active_field)
+        .is_some_and(|field| field.is_boolean())
+}
+
 /// Handle clear token confirmation dialog
 pub fn handle_clear_confirmation(
     key: crossterm::event::KeyEvent,
@@ -276,19 +390,596 @@ pub fn handle_clear_confirmation(
     Ok(())
 }
 
-/// Handle auth dialog activation
-pub fn handle_auth_dialog(state: Arc<RwLock<AppState>>) {
-    // Pre-fill with current token if exists
-    let current_token = {
-        let s = state.read().unwrap();
-        s.request.auth.token.clone().unwrap_or_default()
+/// Handle reset request config confirmation dialog
+pub fn handle_reset_confirmation(
+    key: crossterm::event::KeyEvent,
+    state: Arc<RwLock<AppState>>,
+    endpoint_key: String,
+) -> Result<()> {
+    match key.code {
+        KeyCode::Char('y') | KeyCode::Char('Y') => {
+            log_debug(&format!("Request config reset for {endpoint_key}"));
+            apply_many(
+                state,
+                vec![
+                    AppAction::ResetRequestConfig { endpoint_key },
+                    AppAction::ExitConfirmResetMode,
+                ],
+            );
+        }
+        KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+            apply(state, AppAction::ExitConfirmResetMode);
+            log_debug("Request config reset cancelled");
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Handle snapshot name input modal (save the current response under a name)
+pub fn handle_snapshot_name_input(
+    key: crossterm::event::KeyEvent,
+    state: Arc<RwLock<AppState>>,
+    selected_index: usize,
+) -> Result<()> {
+    match key.code {
+        KeyCode::Enter => {
+            let (name, endpoint) = {
+                let s = state.read_lock();
+                (
+                    s.input.snapshot_name_input.trim().to_string(),
+                    s.get_selected_endpoint(selected_index),
+                )
+            };
+
+            match (name.is_empty(), endpoint) {
+                (false, Some(endpoint)) => {
+                    log_debug(&format!("Saved snapshot '{name}' for {}", endpoint.key()));
+                    apply_many(
+                        state,
+                        vec![
+                            AppAction::SaveSnapshot {
+                                endpoint_key: endpoint.key(),
+                                name,
+                            },
+                            AppAction::ExitSnapshotNameMode,
+                        ],
+                    );
+                }
+                _ => apply(state, AppAction::ExitSnapshotNameMode),
+            }
+        }
+        KeyCode::Esc => {
+            apply(state, AppAction::ExitSnapshotNameMode);
+        }
+        KeyCode::Backspace => {
+            apply(state, AppAction::BackspaceSnapshotName);
+        }
+        KeyCode::Char(c) => {
+            let (batch_str, _) = collect_paste_batch(c);
+            apply(state, AppAction::AppendToSnapshotName(batch_str));
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Handle variant name input modal (save the current config under a name)
+/// Handle input in the modal taking a JSON object or query string for the parameters
+///
+/// Enter inside an unfinished JSON object continues it, so multi-line pastes arrive whole.
+pub fn handle_param_paste_input(
+    key: crossterm::event::KeyEvent,
+    state: Arc<RwLock<AppState>>,
+) -> Result<()> {
+    match key.code {
+        KeyCode::Enter => {
+            let partial = is_partial_json_object(&state.read_lock().input.param_paste_input);
+            if partial {
+                apply(state, AppAction::AppendToParamPaste(" ".to_string()));
+            } else {
+                apply(state, AppAction::SubmitParamPaste);
+            }
+        }
+        KeyCode::Esc => {
+            apply(state, AppAction::ExitParamPasteMode);
+        }
+        KeyCode::Backspace => {
+            apply(state, AppAction::BackspaceParamPaste);
+        }
+        KeyCode::Char(c) => {
+            let (batch_str, _) = collect_paste_batch(c);
+            apply(state, AppAction::AppendToParamPaste(batch_str));
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+pub fn handle_variant_name_input(
+    key: crossterm::event::KeyEvent,
+    state: Arc<RwLock<AppState>>,
+    selected_index: usize,
+) -> Result<()> {
+    match key.code {
+        KeyCode::Enter => {
+            let (name, endpoint) = {
+                let s = state.read_lock();
+                (
+                    s.input.variant_name_input.trim().to_string(),
+                    s.get_selected_endpoint(selected_index),
+                )
+            };
+
+            match (name.is_empty(), endpoint) {
+                (false, Some(endpoint)) => {
+                    log_debug(&format!("Saved variant '{name}' for {}", endpoint.key()));
+                    apply_many(
+                        state,
+                        vec![
+                            AppAction::SaveVariant {
+                                endpoint_key: endpoint.key(),
+                                name,
+                            },
+                            AppAction::ExitVariantNameMode,
+                        ],
+                    );
+                }
+                _ => apply(state, AppAction::ExitVariantNameMode),
+            }
+        }
+        KeyCode::Esc => {
+            apply(state, AppAction::ExitVariantNameMode);
+        }
+        KeyCode::Backspace => {
+            apply(state, AppAction::BackspaceVariantName);
+        }
+        KeyCode::Char(c) => {
+            let (batch_str, _) = collect_paste_batch(c);
+            apply(state, AppAction::AppendToVariantName(batch_str));
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Handle endpoint note input modal
+pub fn handle_note_input(
+    key: crossterm::event::KeyEvent,
+    state: Arc<RwLock<AppState>>,
+    selected_index: usize,
+) -> Result<()> {
+    use crossterm::event::KeyModifiers;
+
+    match key.code {
+        KeyCode::Enter => {
+            let endpoint = state.read_lock().get_selected_endpoint(selected_index);
+            match endpoint {
+                Some(endpoint) => apply(
+                    state,
+                    AppAction::SaveNote {
+                        endpoint_key: endpoint.key(),
+                    },
+                ),
+                None => apply(state, AppAction::ExitNoteMode),
+            }
+        }
+        KeyCode::Esc => {
+            apply(state, AppAction::ExitNoteMode);
+        }
+        KeyCode::Backspace => {
+            apply(state, AppAction::BackspaceNote);
+        }
+        KeyCode::Char('l') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            apply(state, AppAction::ClearNote);
+        }
+        KeyCode::Char('w') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            apply(state, AppAction::DeleteWordNote);
+        }
+        KeyCode::Char(c) => {
+            let (batch_str, _) = collect_paste_batch(c);
+            apply(state, AppAction::AppendToNote(batch_str));
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Open the export modal: CSV for the table view, the raw body for binary responses
+pub fn handle_export_dialog(selected_index: usize, state: Arc<RwLock<AppState>>) {
+    let action = {
+        let s = state.read_lock();
+        let endpoint_path = s
+            .get_selected_endpoint(selected_index)
+            .map(|endpoint| endpoint.path)
+            .unwrap_or_default();
+
+        if s.response_table().is_some() {
+            AppAction::EnterCsvExportMode(csv_file_name(&endpoint_path))
+        } else if let (BodyKind::Binary, Some(response)) =
+            (s.request.body_kind, &s.request.current_response)
+        {
+            AppAction::EnterSaveBodyMode(body_file_name(&endpoint_path, response))
+        } else {
+            log_debug("Export needs the table view of an array response or a binary body");
+            return;
+        }
+    };
+
+    apply(state, action);
+}
+
+/// Open the script export modal for the selected endpoint
+pub fn handle_script_export_dialog(selected_index: usize, state: Arc<RwLock<AppState>>) {
+    let endpoint = state.read_lock().get_selected_endpoint(selected_index);
+    match endpoint {
+        Some(endpoint) => apply(
+            state,
+            AppAction::EnterScriptExportMode(codegen::script_file_name(&endpoint)),
+        ),
+        None => log_debug("No endpoint selected to export"),
+    }
+}
+
+/// Open the traffic export modal, if any request was sent this session
+pub fn handle_traffic_export_dialog(state: Arc<RwLock<AppState>>) {
+    if state.read_lock().request.traffic.is_empty() {
+        log_debug("No requests sent yet to export");
+        return;
+    }
+    apply(
+        state,
+        AppAction::EnterTrafficExportMode("traffic.har".to_string()),
+    );
+}
+
+/// The selected request, or every saved variant in spec order, as a shell script
+fn export_script(s: &AppState, selected_index: usize, base_url: &str) -> Option<String> {
+    let selected = s.get_selected_endpoint(selected_index);
+    let requests: Vec<ScriptRequest> = if s.input.export_collection {
+        codegen::saved_requests(&s.data.endpoints, &s.request.variants)
+    } else {
+        let endpoint = selected.as_ref()?;
+        vec![ScriptRequest {
+            endpoint,
+            config: s.request.configs.get(&endpoint.key()),
+            variant: None,
+        }]
+    };
+    (!requests.is_empty())
+        .then(|| codegen::shell_script(&requests, base_url, &s.data.security_schemes))
+}
+
+/// Handle input in the export modal; Enter writes the CSV table, the raw body or the script
+pub fn handle_export_input(
+    key: crossterm::event::KeyEvent,
+    state: Arc<RwLock<AppState>>,
+    selected_index: usize,
+    base_url: Option<&str>,
+) -> Result<()> {
+    use crossterm::event::KeyModifiers;
+
+    match key.code {
+        KeyCode::Enter => {
+            let (written, path) = {
+                let s = state.read_lock();
+                let path = s.input.export_path_input.trim().to_string();
+                let written = match s.input.mode {
+                    _ if path.is_empty() => None,
+                    InputMode::SavingBody => s
+                        .request
+                        .current_response
+                        .as_ref()
+                        .map(|response| write_export(&path, response.body.bytes())),
+                    InputMode::ExportingTraffic => Some(traffic::export(&s.request.traffic, &path)),
+                    InputMode::ExportingDiagnostics => Some(diagnostics::export(&s, &path)),
+                    InputMode::ExportingScript => Some(
+                        export_script(&s, selected_index, base_url.unwrap_or_default())
+                            .ok_or_else(|| std::io::Error::other("no saved requests yet"))
+                            .and_then(|script| write_script(&path, &script)),
+                    ),
+                    _ => s.response_table().map(|table| table.write_csv(&path)),
+                };
+                (written, path)
+            };
+            match written {
+                _ if path.is_empty() => {
+                    apply(
+                        state,
+                        AppAction::ExportFailed("Enter a file path".to_string()),
+                    );
+                }
+                Some(Ok(written)) => {
+                    log_debug(&format!("Exported response to {}", written.display()));
+                    apply(state, AppAction::ExitExportMode);
+                }
+                Some(Err(e)) => apply(
+                    state,
+                    AppAction::ExportFailed(format!("Export failed: {e}")),
+                ),
+                None => apply(state, AppAction::ExitExportMode),
+            }
+        }
+        KeyCode::Tab if matches!(state.read_lock().input.mode, InputMode::ExportingScript) => {
+            apply(state, AppAction::ToggleScriptScope);
+        }
+        KeyCode::Esc => {
+            apply(state, AppAction::ExitExportMode);
+        }
+        KeyCode::Backspace => {
+            apply(state, AppAction::BackspaceExportPath);
+        }
+        KeyCode::Char('l') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            apply(state, AppAction::ClearExportPath);
+        }
+        KeyCode::Char(c) => {
+            let (batch_str, _) = collect_paste_batch(c);
+            apply(state, AppAction::AppendToExportPath(batch_str));
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Open the discovered spec picker and look for specs in the background
+fn handle_spec_discovery_open(state: Arc<RwLock<AppState>>) {
+    apply(state.clone(), AppAction::StartSpecDiscovery);
+    let watched = Arc::clone(&state);
+    let task = async move {
+        let specs = discovery::discover().await;
+        log_debug(&format!("Discovered {} specs", specs.len()));
+        apply(state, AppAction::SpecDiscoveryFinished(specs));
+    };
+    tasks::spawn(watched, task, |state, message| {
+        apply_action(AppAction::SpecDiscoveryFinished(Vec::new()), state);
+        state.ui.toast = Some((format!("Discovery failed: {message}"), Instant::now()));
+    });
+}
+
+/// Handle input in the discovered spec picker; Enter fills the URL modal with the spec
+pub fn handle_spec_discovery_input(key: crossterm::event::KeyEvent, state: Arc<RwLock<AppState>>) {
+    match key.code {
+        KeyCode::Down | KeyCode::Char('j') => apply(state, AppAction::NextDiscoveredSpec),
+        KeyCode::Up | KeyCode::Char('k') => apply(state, AppAction::PrevDiscoveredSpec),
+        KeyCode::Enter => apply(state, AppAction::PickDiscoveredSpec),
+        KeyCode::Char('r') => handle_spec_discovery_open(state),
+        KeyCode::Esc => apply(state, AppAction::CloseSpecDiscovery),
+        _ => {}
+    }
+}
+
+/// Open the link picker with the links found in the current response
+pub fn handle_link_picker_open(state: Arc<RwLock<AppState>>, base_url: Option<&str>) {
+    let links = {
+        let s = state.read_lock();
+        s.request
+            .current_response
+            .as_ref()
+            .map(|response| links::extract_links(response, base_url))
+            .unwrap_or_default()
+    };
+
+    if links.is_empty() {
+        log_debug("No links found in the response");
+        return;
+    }
+    apply(state, AppAction::OpenLinkPicker(links));
+}
+
+/// Handle input in the link picker; Enter follows the highlighted link
+pub fn handle_link_picker_input(
+    key: crossterm::event::KeyEvent,
+    state: Arc<RwLock<AppState>>,
+    selected_index: usize,
+    base_url: Option<String>,
+) -> Result<()> {
+    match key.code {
+        KeyCode::Down | KeyCode::Char('j') => apply(state, AppAction::NextLink),
+        KeyCode::Up | KeyCode::Char('k') => apply(state, AppAction::PrevLink),
+        KeyCode::Enter => {
+            let target = {
+                let s = state.read_lock();
+                let link = s.input.links.get(s.input.link_index).cloned();
+                let endpoint = s
+                    .get_selected_endpoint(selected_index)
+                    .map(|endpoint| (endpoint.key(), endpoint.request_base_url(base_url)));
+                link.zip(endpoint)
+            };
+            apply(state.clone(), AppAction::CloseLinkPicker);
+
+            if let Some((link, (endpoint_key, base_url))) = target {
+                log_debug(&format!("Following link: {}", link.url));
+                request::follow_link_background(state, endpoint_key, link.url, base_url);
+            }
+        }
+        KeyCode::Esc => apply(state, AppAction::CloseLinkPicker),
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Handle input in the console; Enter evaluates the line, Esc closes the console
+pub fn handle_console_input(key: crossterm::event::KeyEvent, state: Arc<RwLock<AppState>>) {
+    match key.code {
+        KeyCode::Enter => apply(state, AppAction::SubmitConsole),
+        KeyCode::Esc => apply(state, AppAction::CloseConsole),
+        KeyCode::Backspace => apply(state, AppAction::BackspaceConsole),
+        KeyCode::Char(c) => {
+            let (batch_str, _) = collect_paste_batch(c);
+            apply(state, AppAction::AppendToConsole(batch_str));
+        }
+        _ => {}
+    }
+}
+
+/// Open the plugin command picker, if any plugin offers commands
+pub fn handle_plugin_commands_open(state: Arc<RwLock<AppState>>) {
+    let commands = state.read_lock().request.plugins.commands();
+    if commands.is_empty() {
+        log_debug("No plugin commands available");
+        return;
+    }
+    apply(state, AppAction::OpenPluginCommands(commands));
+}
+
+/// Handle input in the spec switcher; Enter shows the highlighted spec in its workspace tab
+pub fn handle_spec_switcher_input(key: crossterm::event::KeyEvent, state: Arc<RwLock<AppState>>) {
+    match key.code {
+        KeyCode::Down | KeyCode::Char('j') => apply(state, AppAction::NextListedSpec),
+        KeyCode::Up | KeyCode::Char('k') => apply(state, AppAction::PrevListedSpec),
+        KeyCode::Enter => {
+            let index = state.read_lock().input.spec_list_index;
+            apply_many(
+                state,
+                vec![
+                    AppAction::CloseSpecSwitcher,
+                    AppAction::ChangeWorkspace(WorkspaceCommand::OpenSpec(index)),
+                ],
+            );
+        }
+        KeyCode::Esc | KeyCode::Char('q') => apply(state, AppAction::CloseSpecSwitcher),
+        _ => {}
+    }
+}
+
+/// Handle input in the plugin command picker; Enter runs the highlighted command
+///
+/// The command runs in the background for the selected endpoint and its output
+/// replaces the output shown in the picker.
+pub fn handle_plugin_commands_input(
+    key: crossterm::event::KeyEvent,
+    state: Arc<RwLock<AppState>>,
+    selected_index: usize,
+) {
+    match key.code {
+        KeyCode::Down | KeyCode::Char('j') => apply(state, AppAction::NextPluginCommand),
+        KeyCode::Up | KeyCode::Char('k') => apply(state, AppAction::PrevPluginCommand),
+        KeyCode::Enter => {
+            let (command, endpoint, plugins) = {
+                let s = state.read_lock();
+                (
+                    s.input
+                        .plugin_commands
+                        .get(s.input.plugin_command_index)
+                        .cloned(),
+                    s.get_selected_endpoint(selected_index),
+                    s.request.plugins.clone(),
+                )
+            };
+            let Some((plugin, command)) = command else {
+                return;
+            };
+
+            apply(
+                state.clone(),
+                AppAction::SetPluginOutput(format!("Running {plugin}: {}...", command.name)),
+            );
+            tokio::task::spawn_blocking(move || {
+                let output = match plugins.run_command(&plugin, &command.name, endpoint.as_ref()) {
+                    Ok(output) => output,
+                    Err(e) => format!("Error: {e}"),
+                };
+                apply(state, AppAction::SetPluginOutput(output));
+            });
+        }
+        KeyCode::Esc | KeyCode::Char('q') => apply(state, AppAction::ClosePluginCommands),
+        _ => {}
+    }
+}
+
+/// Handle input in the jobs popup; `x` cancels the highlighted request, `X` all of them
+pub fn handle_jobs_input(key: crossterm::event::KeyEvent, state: Arc<RwLock<AppState>>) {
+    match key.code {
+        KeyCode::Down | KeyCode::Char('j') => apply(state, AppAction::NextJob),
+        KeyCode::Up | KeyCode::Char('k') => apply(state, AppAction::PrevJob),
+        KeyCode::Char('x') => apply(state, AppAction::CancelJob),
+        KeyCode::Char('X') => apply(state, AppAction::CancelAllJobs),
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('J') => {
+            apply(state, AppAction::CloseJobs)
+        }
+        _ => {}
+    }
+}
+
+/// Open the server form for the spec's servers, if it declares any
+pub fn handle_server_form_open(state: Arc<RwLock<AppState>>) {
+    let servers = state.read_lock().data.servers.clone();
+    if servers.is_empty() {
+        state
+            .write_lock()
+            .show_error("No base URL: the spec declares no servers, set one with ,".to_string());
+        return;
+    }
+    apply(state, AppAction::OpenServerForm(ServerForm::new(servers)));
+}
+
+/// Handle input in the server form; ←/→ step through servers and enum values
+pub fn handle_server_form_input(key: crossterm::event::KeyEvent, state: Arc<RwLock<AppState>>) {
+    match key.code {
+        KeyCode::Enter => apply(state, AppAction::SubmitServerForm),
+        KeyCode::Esc => apply(state, AppAction::CancelServerForm),
+        KeyCode::Tab | KeyCode::Down => apply(state, AppAction::ServerFormNextField),
+        KeyCode::BackTab | KeyCode::Up => apply(state, AppAction::ServerFormPrevField),
+        KeyCode::Right => apply(state, AppAction::CycleServerFormChoice { forward: true }),
+        KeyCode::Left => apply(state, AppAction::CycleServerFormChoice { forward: false }),
+        KeyCode::Backspace => apply(state, AppAction::BackspaceServerForm),
+        KeyCode::Char(c) => apply(state, AppAction::AppendToServerForm(c.to_string())),
+        _ => {}
+    }
+}
+
+/// Handle input in the spec info view; any of Esc, Enter, q or i closes it
+pub fn handle_spec_info_input(key: crossterm::event::KeyEvent, state: Arc<RwLock<AppState>>) {
+    if matches!(
+        key.code,
+        KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') | KeyCode::Char('i')
+    ) {
+        apply(state, AppAction::CloseSpecInfo);
+    }
+}
+
+/// Handle input in the spec changes view; Esc, q or A closes it
+pub fn handle_spec_changes_input(key: crossterm::event::KeyEvent, state: Arc<RwLock<AppState>>) {
+    match key.code {
+        KeyCode::Down | KeyCode::Char('j') => apply(state, AppAction::ScrollSpecChangesDown),
+        KeyCode::Up | KeyCode::Char('k') => apply(state, AppAction::ScrollSpecChangesUp),
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('A') => {
+            apply(state, AppAction::CloseSpecChanges)
+        }
+        _ => {}
+    }
+}
+
+/// Handle input in the usage stats view; Tab switches the order, Esc, q or T closes it
+pub fn handle_stats_input(key: crossterm::event::KeyEvent, state: Arc<RwLock<AppState>>) {
+    match key.code {
+        KeyCode::Down | KeyCode::Char('j') => apply(state, AppAction::ScrollStatsDown),
+        KeyCode::Up | KeyCode::Char('k') => apply(state, AppAction::ScrollStatsUp),
+        KeyCode::Tab | KeyCode::Char('s') => apply(state, AppAction::ToggleStatsSort),
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('T') => {
+            apply(state, AppAction::CloseStats)
+        }
+        _ => {}
+    }
+}
+
+/// Handle auth dialog activation, offering the security schemes of the selected endpoint first
+pub fn handle_auth_dialog(state: Arc<RwLock<AppState>>, selected_index: usize) {
+    let schemes: Vec<String> = {
+        let s = state.read_lock();
+        let endpoint = s.get_selected_endpoint(selected_index);
+        s.auth_schemes_for(endpoint.as_ref())
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect()
     };
 
+    // The current token is filled in when the dialog opens on its scheme
     apply_many(
         state,
         vec![
             AppAction::EnterTokenInputMode,
-            AppAction::AppendToTokenInput(current_token),
+            AppAction::OfferAuthSchemes(schemes),
         ],
     );
     log_debug("Entering token input mode");
@@ -297,22 +988,42 @@ pub fn handle_auth_dialog(state: Arc<RwLock<AppState>>) {
 /// Handle body dialog activation
 pub fn handle_body_dialog(state: Arc<RwLock<AppState>>, selected_index: usize) {
     // Pre-fill with current body if exists
-    let (current_body, endpoint_path) = {
-        let s = state.read().unwrap();
+    let (endpoint_key, current_body, content_type) = {
+        let s = state.read_lock();
         let endpoint = s.get_selected_endpoint(selected_index);
-        let path = endpoint.as_ref().map(|ep| ep.path.clone());
-        let body = path
+        let endpoint_key = endpoint.as_ref().map(|ep| ep.key());
+        let config = endpoint
             .as_ref()
-            .and_then(|p| s.request.configs.get(p))
+            .and_then(|ep| s.request.configs.get(&ep.key()));
+        let content_type = endpoint
+            .as_ref()
+            .map(|ep| ContentType::for_media_type(&ep.body_content_type(config)));
+        let body = config
             .and_then(|c| c.body.clone())
-            .unwrap_or_else(|| "{}".to_string());
-        (body, path)
+            .unwrap_or_else(|| match content_type {
+                Some(ContentType::Json) => "{}".to_string(),
+                _ => String::new(),
+            });
+        // Form bodies are kept as a JSON object but edited as `name=value` lines
+        let body = match content_type {
+            Some(ContentType::Form) => editor::form_lines(&body),
+            _ => body,
+        };
+        (endpoint_key, body, content_type)
     };
 
-    if endpoint_path.is_some() {
+    if let (Some(endpoint_key), Some(content_type)) = (endpoint_key, content_type) {
         // Set the editor content directly instead of using AppendToBodyInput
-        let mut s = state.write().unwrap();
-        s.input.body_editor.set_content(current_body.clone());
+        let mut s = state.write_lock();
+        // An edit left unsaved when the app last exited takes the saved body's place
+        let draft = s.request.body_drafts.get(&endpoint_key).cloned();
+        s.input.body_draft_restored = draft.is_some();
+        s.input
+            .body_editor
+            .set_content(draft.unwrap_or_else(|| current_body.clone()));
+        s.input.body_editor.set_content_type(content_type);
+        s.input.body_saved = current_body.clone();
+        s.input.body_endpoint = Some(endpoint_key);
         s.input.mode = InputMode::EnteringBody;
         log_debug(&format!(
             "Entering body input mode with initial content: {current_body:?}"
@@ -342,7 +1053,7 @@ pub fn handle_body_input(
     match key.code {
         // Ctrl+N: Insert newline (N for Newline)
         KeyCode::Char('n') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-            let mut s = state.write().unwrap();
+            let mut s = state.write_lock();
             s.input.body_validation_error = None;
             s.input.body_editor.insert_newline();
             log_debug("Inserted newline at cursor position (Ctrl+N)");
@@ -354,50 +1065,55 @@ pub fn handle_body_input(
                 "Enter pressed for save (modifiers: {:?})",
                 key.modifiers
             ));
-            let state_read = state.read().unwrap();
+            let state_read = state.read_lock();
 
-            // Get the current endpoint path
-            let endpoint_path = state_read
+            // Get the current endpoint key
+            let endpoint_key = state_read
                 .get_selected_endpoint(selected_index)
-                .map(|ep| ep.path.clone());
+                .map(|ep| ep.key());
 
             drop(state_read);
 
-            if let Some(path) = endpoint_path {
-                let mut s = state.write().unwrap();
+            if let Some(key) = endpoint_key {
+                let mut s = state.write_lock();
 
                 // Log the original content before formatting
                 let original_body = s.input.body_editor.content().to_string();
                 log_debug(&format!("Original body: {original_body}"));
 
-                // Validate JSON before accepting
-                let validation_result = s.input.body_editor.validate_json();
+                // Validate JSON before accepting, form lines are kept as a JSON object
+                // and other content types are saved as written
+                let content_type = s.input.body_editor.content_type().clone();
+                let validation_result = match content_type {
+                    ContentType::Json => s
+                        .input
+                        .body_editor
+                        .format_json()
+                        .map(|_| s.input.body_editor.content()),
+                    ContentType::Form => editor::form_object(&original_body),
+                    _ => Ok(original_body.clone()),
+                };
 
                 match validation_result {
-                    Ok(_) => {
-                        // Valid JSON - format and save
-                        let _ = s.input.body_editor.format_json();
-                        let formatted_body = s.input.body_editor.content().to_string();
-
+                    Ok(formatted_body) => {
                         log_debug(&format!("Formatted JSON successfully: {formatted_body}"));
 
                         // Save formatted body to config
-                        let config = s.get_or_create_request_config_by_path(&path);
-                        config.body = if formatted_body.trim().is_empty() {
-                            None
-                        } else {
-                            Some(formatted_body.clone())
-                        };
+                        s.edit_config(&key, "body edit", |s| {
+                            s.get_or_create_request_config_by_key(&key).body =
+                                if formatted_body.trim().is_empty() {
+                                    None
+                                } else {
+                                    Some(formatted_body.clone())
+                                };
+                        });
 
                         log_debug(&format!(
-                            "Saved body to config for path '{}': {:?}",
-                            path, config.body
+                            "Saved body to config for '{key}': {formatted_body:?}"
                         ));
 
                         // Close modal and clear error
-                        s.input.mode = InputMode::Normal;
-                        s.input.body_editor.clear();
-                        s.input.body_validation_error = None;
+                        s.close_body_editor();
 
                         log_debug("Body editor modal closed");
                     }
@@ -410,24 +1126,62 @@ pub fn handle_body_input(
             }
         }
 
-        KeyCode::Esc => {
-            let mut s = state.write().unwrap();
-            s.input.mode = InputMode::Normal;
-            s.input.body_editor.clear();
+        // Ctrl+G: Replace content with sample data generated from the body schema
+        KeyCode::Char('g') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            let mut s = state.write_lock();
+            let schema = s.get_selected_endpoint(selected_index).and_then(|ep| {
+                let media_type = ep.body_content_type(s.request.configs.get(&ep.key()));
+                ep.request_body
+                    .as_ref()
+                    .and_then(|body| body.schema_for(&media_type).cloned())
+            });
+
+            match schema {
+                Some(schema) => {
+                    let sample = generators::sample_from_schema(&schema);
+                    let content = serde_json::to_string_pretty(&sample).unwrap_or_default();
+                    let content = match s.input.body_editor.content_type() {
+                        ContentType::Form => editor::form_lines(&content),
+                        _ => content,
+                    };
+                    s.input.body_editor.set_content(content);
+                    s.input.body_validation_error = None;
+                    log_debug("Filled body with sample data");
+                }
+                None => {
+                    s.input.body_validation_error =
+                        Some("No JSON schema for this request body".to_string());
+                }
+            }
+        }
+
+        // Ctrl+R: Go back from a restored draft to the saved body
+        KeyCode::Char('r')
+            if key.modifiers.contains(KeyModifiers::CONTROL)
+                && state.read_lock().input.body_draft_restored =>
+        {
+            let mut s = state.write_lock();
+            let saved = s.input.body_saved.clone();
+            s.input.body_editor.set_content(saved);
+            s.input.body_draft_restored = false;
             s.input.body_validation_error = None;
+        }
+
+        KeyCode::Esc => {
+            state.write_lock().close_body_editor();
             log_debug("Body input cancelled");
         }
 
         KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
-            // Use the editor's built-in paste batching
-            let mut s = state.write().unwrap();
+            let (batch_str, _) = collect_paste_batch(c);
+            let mut s = state.write_lock();
 
             // Clear validation error when user starts typing
             s.input.body_validation_error = None;
 
-            let char_count = s.input.body_editor.handle_paste_batch(c);
+            let char_count = s.input.body_editor.handle_paste_batch(&batch_str);
 
-            if char_count > 1 {
+            if char_count > 1 && *s.input.body_editor.content_type() == ContentType::Json {
                 log_debug(&format!("Batched {char_count} characters (paste detected)"));
 
                 // Log content before format
@@ -488,7 +1242,7 @@ pub fn handle_body_input(
 
         _ => {
             // Delegate all other key events to the editor
-            let mut s = state.write().unwrap();
+            let mut s = state.write_lock();
 
             // Clear validation error when user edits
             s.input.body_validation_error = None;
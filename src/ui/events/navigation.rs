@@ -5,12 +5,14 @@
 //! - Parameter navigation (j/k in request params)
 //! - Response line navigation (j/k in response viewer)
 //! - View mode toggling (flat vs grouped)
+//! - Grouping strategy switching (tag vs path prefix)
+//! - Response table view and sorting
 
 use super::helpers::{apply, log_debug};
 use crate::actions::AppAction;
-use crate::state::AppState;
-use crate::types::{RequestEditMode, ViewMode};
-use crate::ui::draw::try_format_json;
+use crate::snapshot::diff_lines;
+use crate::state::{AppState, LockRecover};
+use crate::types::{DetailTab, PanelFocus, RequestEditMode, ViewMode};
 use ratatui::widgets::ListState;
 use std::sync::{Arc, RwLock};
 
@@ -24,11 +26,15 @@ pub fn handle_up(
         *selected_index -= 1;
         list_state.select(Some(*selected_index));
 
-        // Reset parameter selection and response scroll when changing endpoints
-        let mut s = state.write().unwrap();
+        // Reset parameter selection and restore the endpoint's scroll position
+        let mut s = state.write_lock();
         s.ui.selected_param_index = 0;
-        s.ui.response_scroll = 0;
-        s.ui.response_selected_line = 0;
+        s.store_view_position();
+        let endpoint_key = s
+            .get_selected_endpoint(*selected_index)
+            .map(|endpoint| endpoint.key());
+        s.load_view_position(endpoint_key);
+        s.remember_selection(*selected_index);
         drop(s);
 
         ensure_request_config_for_selected(*selected_index, state);
@@ -41,7 +47,7 @@ pub fn handle_down(
     state: Arc<RwLock<AppState>>,
     list_state: &mut ListState,
 ) {
-    let state_guard = state.read().unwrap();
+    let state_guard = state.read_lock();
 
     let max_index = match state_guard.ui.view_mode {
         ViewMode::Flat => state_guard.data.endpoints.len().saturating_sub(1),
@@ -53,11 +59,15 @@ pub fn handle_down(
         *selected_index += 1;
         list_state.select(Some(*selected_index));
 
-        // Reset parameter selection and response scroll when changing endpoints
-        let mut s = state.write().unwrap();
+        // Reset parameter selection and restore the endpoint's scroll position
+        let mut s = state.write_lock();
         s.ui.selected_param_index = 0;
-        s.ui.response_scroll = 0;
-        s.ui.response_selected_line = 0;
+        s.store_view_position();
+        let endpoint_key = s
+            .get_selected_endpoint(*selected_index)
+            .map(|endpoint| endpoint.key());
+        s.load_view_position(endpoint_key);
+        s.remember_selection(*selected_index);
         drop(s);
 
         ensure_request_config_for_selected(*selected_index, state);
@@ -66,7 +76,7 @@ pub fn handle_down(
 
 /// Navigate up in request parameters
 pub fn handle_request_param_up(state: Arc<RwLock<AppState>>) {
-    let mut s = state.write().unwrap();
+    let mut s = state.write_lock();
 
     // Only navigate if in Viewing mode
     if matches!(s.request.edit_mode, RequestEditMode::Viewing) && s.ui.selected_param_index > 0 {
@@ -76,7 +86,7 @@ pub fn handle_request_param_up(state: Arc<RwLock<AppState>>) {
 
 /// Navigate down in request parameters
 pub fn handle_request_param_down(selected_index: usize, state: Arc<RwLock<AppState>>) {
-    let state_read = state.read().unwrap();
+    let state_read = state.read_lock();
 
     // Only navigate if in Viewing mode
     if !matches!(state_read.request.edit_mode, RequestEditMode::Viewing) {
@@ -87,12 +97,10 @@ pub fn handle_request_param_down(selected_index: usize, state: Arc<RwLock<AppSta
     let selected_endpoint = state_read.get_selected_endpoint(selected_index);
 
     if let Some(endpoint) = selected_endpoint {
-        let path_param_count = endpoint.path_params().len();
-        let query_param_count = endpoint.query_params().len();
-        let total_param_count = path_param_count + query_param_count;
+        let total_param_count = endpoint.request_params().len();
 
         drop(state_read);
-        let mut s = state.write().unwrap();
+        let mut s = state.write_lock();
 
         if s.ui.selected_param_index < total_param_count.saturating_sub(1) {
             s.ui.selected_param_index += 1;
@@ -106,19 +114,43 @@ pub fn handle_toggle_view(
     state: Arc<RwLock<AppState>>,
     list_state: &mut ListState,
 ) {
+    let selected_key = state
+        .read_lock()
+        .get_selected_endpoint(*selected_index)
+        .map(|endpoint| endpoint.key());
+
     apply(state.clone(), AppAction::ToggleViewMode);
 
-    // Reset selection to top
+    // Keep the selected endpoint, opening its group; a group header goes back to the top
+    let index = selected_key
+        .and_then(|key| state.write_lock().locate_endpoint(&key))
+        .unwrap_or(0);
+    *selected_index = index;
+    list_state.select(Some(index));
+
+    let view_mode = state.read_lock().ui.view_mode.clone();
+    log_debug(&format!("Switched to {view_mode:?} mode"));
+}
+
+/// Switch the grouping strategy of the grouped view (tags vs path prefix)
+pub fn handle_cycle_group_by(
+    selected_index: &mut usize,
+    state: Arc<RwLock<AppState>>,
+    list_state: &mut ListState,
+) {
+    apply(state.clone(), AppAction::CycleGroupBy);
+
+    // Group names change, so the old index points somewhere unrelated
     *selected_index = 0;
     list_state.select(Some(0));
 
-    let view_mode = state.read().unwrap().ui.view_mode.clone();
-    log_debug(&format!("Switched to {view_mode:?} mode"));
+    let group_by = state.read_lock().ui.group_by;
+    log_debug(&format!("Grouping endpoints by {}", group_by.label()));
 }
 
 /// Navigate up in response lines
 pub fn handle_response_line_up(state: Arc<RwLock<AppState>>) {
-    let mut s = state.write().unwrap();
+    let mut s = state.write_lock();
 
     if s.ui.response_selected_line > 0 {
         s.ui.response_selected_line -= 1;
@@ -132,14 +164,23 @@ pub fn handle_response_line_up(state: Arc<RwLock<AppState>>) {
 
 /// Navigate down in response lines
 pub fn handle_response_line_down(state: Arc<RwLock<AppState>>) {
-    let state_read = state.read().unwrap();
-
-    // Count total lines in response
-    let total_lines = if let Some(ref response) = state_read.request.current_response {
+    let state_read = state.read_lock();
+
+    let snapshot = state_read
+        .ui
+        .snapshot_diff
+        .as_ref()
+        .and_then(|(key, _)| state_read.active_snapshot(key));
+
+    // Count total lines in response (rows in table view, diff lines against a snapshot)
+    let total_lines = if let Some(snapshot) = snapshot {
+        2 + diff_lines(&snapshot.lines, &state_read.request.response_lines).len()
+    } else if let Some(table) = state_read.response_table() {
+        table.rows.len()
+    } else if let Some(ref response) = state_read.request.current_response {
         if !response.is_error {
-            // Count lines in formatted JSON (status + empty + body lines)
-            let formatted_body = try_format_json(&response.body);
-            2 + formatted_body.lines().count()
+            // Count shown lines of formatted JSON (status + empty + body lines)
+            2 + state_read.visible_response_lines().len()
         } else {
             0
         }
@@ -148,13 +189,13 @@ pub fn handle_response_line_down(state: Arc<RwLock<AppState>>) {
     };
 
     drop(state_read);
-    let mut s = state.write().unwrap();
+    let mut s = state.write_lock();
 
     if total_lines > 0 && s.ui.response_selected_line < total_lines - 1 {
         s.ui.response_selected_line += 1;
 
-        // Auto-scroll down to keep selection visible (assume 20 line viewport for now)
-        let viewport_height = 20;
+        // Auto-scroll down to keep selection visible
+        let viewport_height = s.response_viewport_height();
         let scroll_bottom = s.ui.response_scroll + viewport_height;
         if s.ui.response_selected_line >= scroll_bottom {
             s.ui.response_scroll =
@@ -164,9 +205,52 @@ pub fn handle_response_line_down(state: Arc<RwLock<AppState>>) {
     }
 }
 
+/// Scroll the Headers tab by one line
+pub fn handle_headers_scroll(state: Arc<RwLock<AppState>>, down: bool) {
+    let mut s = state.write_lock();
+    let header_count = s
+        .request
+        .current_response
+        .as_ref()
+        .map_or(0, |response| response.headers.len());
+
+    if down {
+        if s.ui.headers_scroll + 1 < header_count {
+            s.ui.headers_scroll += 1;
+        }
+    } else {
+        s.ui.headers_scroll = s.ui.headers_scroll.saturating_sub(1);
+    }
+}
+
+/// Whether the Response tab of the details panel has focus
+pub fn on_response_tab(state: &Arc<RwLock<AppState>>) -> bool {
+    let state_read = state.read_lock();
+    state_read.ui.panel_focus == PanelFocus::Details
+        && state_read.ui.active_detail_tab == DetailTab::Response
+}
+
+/// Switch the Response tab between raw JSON and the table view
+pub fn handle_toggle_response_table(state: Arc<RwLock<AppState>>) {
+    apply(state.clone(), AppAction::ToggleResponseTable);
+
+    let s = state.read_lock();
+    if s.ui.response_table && s.response_table().is_none() {
+        log_debug("Response is not an array of objects, showing JSON");
+    }
+}
+
+/// Sort the response table by the next column
+pub fn handle_cycle_table_sort(state: Arc<RwLock<AppState>>) {
+    let columns = state.read_lock().response_table().map(|t| t.columns.len());
+    if let Some(columns) = columns {
+        apply(state, AppAction::CycleTableSort { columns });
+    }
+}
+
 /// Ensure request config exists for selected endpoint
 fn ensure_request_config_for_selected(selected_index: usize, state: Arc<RwLock<AppState>>) {
-    let state_read = state.read().unwrap();
+    let state_read = state.read_lock();
 
     // Get currently selected endpoint
     let selected_endpoint = state_read.get_selected_endpoint(selected_index);
@@ -175,7 +259,7 @@ fn ensure_request_config_for_selected(selected_index: usize, state: Arc<RwLock<A
         let endpoint = endpoint.clone();
         drop(state_read);
 
-        let mut s = state.write().unwrap();
+        let mut s = state.write_lock();
         s.get_or_create_request_config(&endpoint);
     }
 }
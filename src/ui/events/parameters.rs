@@ -3,19 +3,33 @@
 //! This module handles editing of request parameters:
 //! - Entering edit mode for a selected parameter
 //! - Confirming parameter edits
+//! - Cycling boolean parameters
+//! - Adjusting date/date-time parameters
+//! - Inserting generated values while editing
+//! - Opening the bulk parameter form
+//! - Filling empty parameters from spec defaults/examples
 //! - Ensuring request configs exist
 
 use super::helpers::{apply, log_debug};
 use crate::actions::AppAction;
-use crate::state::AppState;
-use crate::types::RequestEditMode;
+use crate::datetime::DateAdjust;
+use crate::generators::{self, Generator};
+use crate::state::{AppState, LockRecover};
+use crate::types::{DetailTab, PanelFocus, ParamForm, RequestEditMode};
 use std::sync::{Arc, RwLock};
 
+/// Whether the Request tab of the details panel has focus
+pub fn on_request_tab(state: &Arc<RwLock<AppState>>) -> bool {
+    let state_read = state.read_lock();
+    state_read.ui.panel_focus == PanelFocus::Details
+        && state_read.ui.active_detail_tab == DetailTab::Request
+}
+
 /// Enter edit mode for the currently selected parameter
 pub fn handle_request_param_edit(selected_index: usize, state: Arc<RwLock<AppState>>) {
     // First, gather all the data we need while holding read lock
     let edit_data = {
-        let state_read = state.read().unwrap();
+        let state_read = state.read_lock();
 
         // Only enter edit mode if currently in Viewing mode
         if !matches!(state_read.request.edit_mode, RequestEditMode::Viewing) {
@@ -26,36 +40,19 @@ pub fn handle_request_param_edit(selected_index: usize, state: Arc<RwLock<AppSta
         let selected_endpoint = state_read.get_selected_endpoint(selected_index);
 
         if let Some(endpoint) = selected_endpoint {
-            // Get both path and query parameters
-            let path_params: Vec<_> = endpoint.path_params();
-            let query_params: Vec<_> = endpoint.query_params();
-
-            let path_param_count = path_params.len();
-            let selected_idx = state_read.ui.selected_param_index;
-
-            // Determine if we're editing a path or query param
-            let param = if selected_idx < path_param_count {
-                // We're in the path params section
-                path_params.get(selected_idx)
-            } else {
-                // We're in the query params section
-                let query_idx = selected_idx - path_param_count;
-                query_params.get(query_idx)
-            };
-
-            if let Some(param) = param {
+            if let Some((param, _)) = endpoint.param_at(state_read.ui.selected_param_index) {
                 let param_name = param.name.clone();
-                let endpoint_path = endpoint.path.clone();
+                let endpoint_key = endpoint.key();
 
                 // Get current value from the appropriate HashMap
                 let current_value = state_read
                     .request
                     .configs
-                    .get(&endpoint_path)
+                    .get(&endpoint_key)
                     .and_then(|config| config.get_param_value(&param_name).map(|s| s.to_string()))
                     .unwrap_or_default();
 
-                Some((param_name, endpoint_path, current_value))
+                Some((param_name, endpoint_key, current_value))
             } else {
                 None
             }
@@ -65,11 +62,11 @@ pub fn handle_request_param_edit(selected_index: usize, state: Arc<RwLock<AppSta
     }; // state_read is dropped here
 
     // Now we can safely acquire write lock with the data we collected
-    if let Some((param_name, endpoint_path, _current_value)) = edit_data {
+    if let Some((param_name, endpoint_key, _current_value)) = edit_data {
         // Ensure config exists
         {
-            let mut s = state.write().unwrap();
-            s.request.configs.entry(endpoint_path.clone()).or_default();
+            let mut s = state.write_lock();
+            s.get_or_create_request_config_by_key(&endpoint_key);
         }
 
         // Enter edit mode using action
@@ -77,7 +74,7 @@ pub fn handle_request_param_edit(selected_index: usize, state: Arc<RwLock<AppSta
             state.clone(),
             AppAction::StartEditingParameter {
                 param_name: param_name.clone(),
-                endpoint_path,
+                endpoint_key,
             },
         );
 
@@ -87,32 +84,30 @@ pub fn handle_request_param_edit(selected_index: usize, state: Arc<RwLock<AppSta
 
 /// Confirm parameter edit and save the value
 pub fn handle_request_param_confirm(selected_index: usize, state: Arc<RwLock<AppState>>) {
-    let (is_editing, endpoint_path) = {
-        let state_read = state.read().unwrap();
+    let (is_editing, endpoint_key) = {
+        let state_read = state.read_lock();
 
         // Check if we're editing
         let is_editing = matches!(state_read.request.edit_mode, RequestEditMode::Editing(_));
 
         // Get currently selected endpoint path
-        let endpoint_path = state_read
+        let endpoint_key = state_read
             .get_selected_endpoint(selected_index)
-            .map(|endpoint| endpoint.path.clone());
+            .map(|endpoint| endpoint.key());
 
-        (is_editing, endpoint_path)
+        (is_editing, endpoint_key)
     };
 
     if is_editing {
-        if let Some(path) = endpoint_path {
+        if let Some(endpoint_key) = endpoint_key {
             // Confirm the edit using action
             apply(
                 state.clone(),
-                AppAction::ConfirmParameterEdit {
-                    endpoint_path: path,
-                },
+                AppAction::ConfirmParameterEdit { endpoint_key },
             );
 
             let param_info = {
-                let s = state.read().unwrap();
+                let s = state.read_lock();
                 format!(
                     "Confirmed parameter edit (now viewing mode: {})",
                     matches!(s.request.edit_mode, RequestEditMode::Viewing)
@@ -123,3 +118,194 @@ pub fn handle_request_param_confirm(selected_index: usize, state: Arc<RwLock<App
         }
     }
 }
+
+/// Open the bulk parameter form for the selected endpoint
+pub fn handle_param_form_open(selected_index: usize, state: Arc<RwLock<AppState>>) {
+    let form = {
+        let state_read = state.read_lock();
+
+        if !matches!(state_read.request.edit_mode, RequestEditMode::Viewing) {
+            return;
+        }
+
+        state_read
+            .get_selected_endpoint(selected_index)
+            .map(|endpoint| {
+                ParamForm::for_endpoint(&endpoint, state_read.request.configs.get(&endpoint.key()))
+            })
+    };
+
+    match form {
+        Some(form) if !form.fields.is_empty() => {
+            log_debug(&format!(
+                "Opening parameter form for {} ({} fields)",
+                form.endpoint_key,
+                form.fields.len()
+            ));
+            apply(state, AppAction::OpenParamForm(form));
+        }
+        _ => log_debug("No parameters to edit"),
+    }
+}
+
+/// Open the modal taking a JSON object or query string for the selected endpoint's parameters
+pub fn handle_param_paste_open(selected_index: usize, state: Arc<RwLock<AppState>>) {
+    let endpoint_key = {
+        let state_read = state.read_lock();
+
+        if !matches!(state_read.request.edit_mode, RequestEditMode::Viewing) {
+            return;
+        }
+
+        state_read
+            .get_selected_endpoint(selected_index)
+            .filter(|endpoint| !endpoint.request_params().is_empty())
+            .map(|endpoint| endpoint.key())
+    };
+
+    match endpoint_key {
+        Some(endpoint_key) => apply(state, AppAction::EnterParamPasteMode(endpoint_key)),
+        None => log_debug("No parameters to paste into"),
+    }
+}
+
+/// Fill all empty parameters of the selected endpoint from spec defaults/examples
+pub fn handle_fill_spec_defaults(selected_index: usize, state: Arc<RwLock<AppState>>) {
+    let endpoint_key = {
+        let state_read = state.read_lock();
+
+        if !matches!(state_read.request.edit_mode, RequestEditMode::Viewing) {
+            return;
+        }
+
+        state_read
+            .get_selected_endpoint(selected_index)
+            .map(|endpoint| endpoint.key())
+    };
+
+    if let Some(endpoint_key) = endpoint_key {
+        log_debug(&format!("Filling spec defaults for {endpoint_key}"));
+        apply(state, AppAction::FillSpecDefaults { endpoint_key });
+    }
+}
+
+/// Cycle the selected parameter if it is a boolean
+///
+/// Returns true when the key was consumed by the toggle.
+pub fn handle_bool_toggle(selected_index: usize, state: Arc<RwLock<AppState>>) -> bool {
+    let endpoint_key = {
+        let state_read = state.read_lock();
+
+        if !matches!(state_read.request.edit_mode, RequestEditMode::Viewing) {
+            return false;
+        }
+
+        state_read
+            .get_selected_endpoint(selected_index)
+            .filter(|endpoint| {
+                endpoint
+                    .param_at(state_read.ui.selected_param_index)
+                    .is_some_and(|(param, _)| param.is_boolean())
+            })
+            .map(|endpoint| endpoint.key())
+    };
+
+    match endpoint_key {
+        Some(endpoint_key) => {
+            apply(state, AppAction::CycleBoolParam { endpoint_key });
+            true
+        }
+        None => false,
+    }
+}
+
+/// Cycle the selected parameter through its allowed values if it has an `enum`
+///
+/// Returns true when the key was consumed by the cycle.
+pub fn handle_enum_cycle(selected_index: usize, state: Arc<RwLock<AppState>>) -> bool {
+    let endpoint_key = {
+        let state_read = state.read_lock();
+
+        if !matches!(state_read.request.edit_mode, RequestEditMode::Viewing) {
+            return false;
+        }
+
+        state_read
+            .get_selected_endpoint(selected_index)
+            .filter(|endpoint| {
+                endpoint
+                    .param_at(state_read.ui.selected_param_index)
+                    .is_some_and(|(param, _)| !param.allowed_values().is_empty())
+            })
+            .map(|endpoint| endpoint.key())
+    };
+
+    match endpoint_key {
+        Some(endpoint_key) => {
+            apply(state, AppAction::CycleEnumParam { endpoint_key });
+            true
+        }
+        None => false,
+    }
+}
+
+/// Apply a date helper edit to the selected parameter if it is date-formatted
+pub fn handle_date_adjust(selected_index: usize, state: Arc<RwLock<AppState>>, adjust: DateAdjust) {
+    let endpoint_key = {
+        let state_read = state.read_lock();
+
+        if !matches!(state_read.request.edit_mode, RequestEditMode::Viewing) {
+            return;
+        }
+
+        state_read
+            .get_selected_endpoint(selected_index)
+            .filter(|endpoint| {
+                endpoint
+                    .param_at(state_read.ui.selected_param_index)
+                    .is_some_and(|(param, _)| param.date_format().is_some())
+            })
+            .map(|endpoint| endpoint.key())
+    };
+
+    if let Some(endpoint_key) = endpoint_key {
+        log_debug(&format!("Adjusting date param: {adjust:?}"));
+        apply(
+            state,
+            AppAction::AdjustDateParam {
+                endpoint_key,
+                adjust,
+            },
+        );
+    }
+}
+
+/// Insert a generated value into the parameter being edited
+pub fn handle_generate_value(
+    selected_index: usize,
+    state: Arc<RwLock<AppState>>,
+    generator: Generator,
+) {
+    let value = {
+        let state_read = state.read_lock();
+
+        let RequestEditMode::Editing(param_name) = &state_read.request.edit_mode else {
+            return;
+        };
+
+        let param = state_read
+            .get_selected_endpoint(selected_index)
+            .and_then(|endpoint| {
+                endpoint
+                    .parameters
+                    .iter()
+                    .find(|p| &p.name == param_name)
+                    .cloned()
+            });
+
+        generators::generate(generator, param.as_ref())
+    };
+
+    log_debug(&format!("Generated {generator:?} value: {value}"));
+    apply(state, AppAction::AppendToParamBuffer(value));
+}
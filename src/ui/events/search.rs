@@ -4,20 +4,66 @@
 //! - Activating search mode
 //! - Handling search input
 //! - Clearing search filters
+//! - Hiding deprecated endpoints
+//! - Finding text in the response body
 
-use super::helpers::log_debug;
+use super::helpers::{apply, collect_paste_batch, log_debug};
 use crate::actions::{apply_action, AppAction};
-use crate::state::AppState;
+use crate::state::{AppState, LockRecover};
 use crate::types::InputMode;
 use color_eyre::Result;
 use crossterm::event::KeyCode;
 use ratatui::widgets::ListState;
 use std::sync::{Arc, RwLock};
+use std::time::Instant;
 
-/// Activate search mode
-pub fn handle_search_activate(state: Arc<RwLock<AppState>>) {
-    let mut s = state.write().unwrap();
+/// Activate search mode over all endpoints
+pub fn handle_search_activate(
+    selected_index: &mut usize,
+    state: Arc<RwLock<AppState>>,
+    list_state: &mut ListState,
+) {
+    let mut s = state.write_lock();
     apply_action(AppAction::EnterSearchMode, &mut s);
+    *selected_index = s.set_search_scope(None, *selected_index);
+    list_state.select(Some(*selected_index));
+}
+
+/// Show or hide the endpoints the spec marks as deprecated
+pub fn handle_toggle_deprecated(
+    selected_index: &mut usize,
+    state: Arc<RwLock<AppState>>,
+    list_state: &mut ListState,
+) {
+    let mut s = state.write_lock();
+    let hide = !s.search.hide_deprecated;
+    *selected_index = s.set_hide_deprecated(hide, *selected_index);
+    list_state.select(Some(*selected_index));
+
+    let count = s.data.endpoints.iter().filter(|ep| ep.deprecated).count();
+    let message = match (hide, count) {
+        (_, 0) => "The spec marks no endpoints as deprecated".to_string(),
+        (true, count) => format!("Hiding {count} deprecated endpoint(s)"),
+        (false, count) => format!("Showing {count} deprecated endpoint(s)"),
+    };
+    s.ui.toast = Some((message, Instant::now()));
+}
+
+/// Activate search mode within the group of the selection (grouped view only)
+pub fn handle_scoped_search_activate(
+    selected_index: &mut usize,
+    state: Arc<RwLock<AppState>>,
+    list_state: &mut ListState,
+) {
+    let mut s = state.write_lock();
+    let Some(group) = s.selected_group(*selected_index) else {
+        log_debug("Scoped search needs a group selected in the grouped view");
+        return;
+    };
+    log_debug(&format!("Searching within group '{group}'"));
+    apply_action(AppAction::EnterSearchMode, &mut s);
+    *selected_index = s.set_search_scope(Some(group), *selected_index);
+    list_state.select(Some(*selected_index));
 }
 
 /// Handle search input
@@ -32,56 +78,46 @@ pub fn handle_search_input(
     match key.code {
         KeyCode::Enter => {
             // Exit search mode and keep the filter active
-            let mut s = state.write().unwrap();
+            let mut s = state.write_lock();
             s.input.mode = InputMode::Normal;
             log_debug("Exiting search mode (keeping filter)");
         }
         KeyCode::Esc => {
             // Exit search mode and clear the filter
-            let mut s = state.write().unwrap();
+            let mut s = state.write_lock();
             s.input.mode = InputMode::Normal;
-            s.search.query.clear();
-            s.update_filtered_endpoints();
+            s.search.scope = None;
+            *selected_index = s.set_search_query(String::new(), *selected_index);
             log_debug("Exiting search mode (cleared filter)");
 
-            drop(s);
-            *selected_index = 0;
-            list_state.select(Some(0));
+            list_state.select(Some(*selected_index));
         }
         KeyCode::Backspace => {
-            let mut s = state.write().unwrap();
-            s.search.query.pop();
-            s.update_filtered_endpoints();
+            let mut s = state.write_lock();
+            let mut query = s.search.query.clone();
+            query.pop();
+            *selected_index = s.set_search_query(query, *selected_index);
 
             log_debug(&format!("Search query: '{}'", s.search.query));
-
-            // Reset selection to top
-            drop(s);
-            *selected_index = 0;
-            list_state.select(Some(0));
+            list_state.select(Some(*selected_index));
         }
         KeyCode::Char('l') if key.modifiers.contains(KeyModifiers::CONTROL) => {
             // Ctrl+L: Clear search (consistent with other inputs)
-            let mut s = state.write().unwrap();
-            s.search.query.clear();
-            s.update_filtered_endpoints();
+            let mut s = state.write_lock();
+            s.search.scope = None;
+            *selected_index = s.set_search_query(String::new(), *selected_index);
             log_debug("Cleared search query");
 
-            drop(s);
-            *selected_index = 0;
-            list_state.select(Some(0));
+            list_state.select(Some(*selected_index));
         }
         KeyCode::Char(c) => {
-            let mut s = state.write().unwrap();
-            s.search.query.push(c);
-            s.update_filtered_endpoints();
+            let mut s = state.write_lock();
+            let query = format!("{}{c}", s.search.query);
+            // Keep the selected endpoint while it still matches, so refining doesn't lose it
+            *selected_index = s.set_search_query(query, *selected_index);
 
             log_debug(&format!("Search query: '{}'", s.search.query));
-
-            // Reset selection to top when search changes
-            drop(s);
-            *selected_index = 0;
-            list_state.select(Some(0));
+            list_state.select(Some(*selected_index));
         }
         _ => {}
     }
@@ -94,14 +130,40 @@ pub fn handle_search_clear(
     state: Arc<RwLock<AppState>>,
     list_state: &mut ListState,
 ) {
-    let mut s = state.write().unwrap();
+    let mut s = state.write_lock();
+    s.search.scope = None;
     if !s.search.query.is_empty() {
-        s.search.query.clear();
-        s.update_filtered_endpoints();
+        *selected_index = s.set_search_query(String::new(), *selected_index);
         log_debug("Cleared search filter");
 
-        drop(s);
-        *selected_index = 0;
-        list_state.select(Some(0));
+        list_state.select(Some(*selected_index));
+    }
+}
+
+/// Handle input while typing a response search; Enter jumps to the first match
+pub fn handle_response_search_input(
+    key: crossterm::event::KeyEvent,
+    state: Arc<RwLock<AppState>>,
+) -> Result<()> {
+    match key.code {
+        KeyCode::Enter => {
+            apply(
+                state.clone(),
+                AppAction::ExitResponseSearchMode { clear: false },
+            );
+            apply(state, AppAction::JumpToResponseMatch { forward: true });
+        }
+        KeyCode::Esc => {
+            apply(state, AppAction::ExitResponseSearchMode { clear: true });
+        }
+        KeyCode::Backspace => {
+            apply(state, AppAction::BackspaceResponseSearch);
+        }
+        KeyCode::Char(c) => {
+            let (batch_str, _) = collect_paste_batch(c);
+            apply(state, AppAction::AppendToResponseSearch(batch_str));
+        }
+        _ => {}
     }
+    Ok(())
 }
@@ -1,19 +1,27 @@
 //! Yank (copy) handlers
 //!
-//! This module handles copying content to the system clipboard.
-//! Currently supports line-based yanking from Response tab.
+//! This module handles copying content to the system clipboard, or to the
+//! terminal's clipboard with an OSC 52 escape sequence over SSH and when no
+//! system clipboard is available (see `[clipboard]` in the config).
+//! Supports yanking a single line, a visual selection, the JSONPath of the
+//! selected line, or a table row from the Response tab, and client code for
+//! the selected endpoint.
 
-use super::helpers::log_debug;
-use crate::state::AppState;
-use crate::ui::draw::try_format_json;
+use super::helpers::{apply, log_debug};
+use crate::actions::AppAction;
+use crate::codegen;
+use crate::config::Osc52Mode;
+use crate::state::{AppState, LockRecover};
 use arboard::Clipboard;
+use base64::Engine;
+use std::io::Write;
 use std::sync::{Arc, RwLock};
 use std::time::Duration;
 
 /// Yank the currently selected line from the Response tab to clipboard
 pub fn handle_yank_response_line(state: Arc<RwLock<AppState>>) {
     log_debug("=== Yank handler called ===");
-    let state_read = state.read().unwrap();
+    let state_read = state.read_lock();
 
     // Get the response if available
     if let Some(ref response) = state_read.request.current_response {
@@ -23,8 +31,7 @@ pub fn handle_yank_response_line(state: Arc<RwLock<AppState>>) {
         }
 
         // Get formatted body
-        let formatted_body = try_format_json(&response.body);
-        let lines: Vec<&str> = formatted_body.lines().collect();
+        let lines = state_read.visible_response_lines();
 
         // The selected line index includes the status line (2 lines at top)
         let selected_line_idx = state_read.ui.response_selected_line;
@@ -57,7 +64,7 @@ pub fn handle_yank_response_line(state: Arc<RwLock<AppState>>) {
             return;
         }
 
-        let line_content = lines[body_line_idx];
+        let line_content = &lines[body_line_idx];
         log_debug(&format!("Line content: '{line_content}'"));
 
         // Try to extract just the value if this is a JSON key-value pair
@@ -66,39 +73,289 @@ pub fn handle_yank_response_line(state: Arc<RwLock<AppState>>) {
 
         drop(state_read);
 
-        // Copy to clipboard
-        match Clipboard::new() {
-            Ok(mut clipboard) => match clipboard.set_text(value_to_copy.clone()) {
-                Ok(_) => {
-                    log_debug(&format!("✓ Successfully yanked: {value_to_copy}"));
-
-                    // Set flash flag
-                    {
-                        let mut state_write = state.write().unwrap();
-                        state_write.ui.yank_flash = true;
-                    }
-
-                    // Spawn task to clear flash after delay
-                    let state_clone = state.clone();
-                    tokio::spawn(async move {
-                        tokio::time::sleep(Duration::from_millis(200)).await;
-                        let mut s = state_clone.write().unwrap();
-                        s.ui.yank_flash = false;
-                    });
-                }
-                Err(e) => {
-                    log_debug(&format!("✗ Failed to copy to clipboard: {e}"));
-                }
-            },
-            Err(e) => {
-                log_debug(&format!("✗ Failed to access clipboard: {e}"));
+        copy_to_clipboard(state, value_to_copy);
+    } else {
+        log_debug("No response available to yank");
+    }
+}
+
+/// Yank all lines of the visual selection in the Response tab, then leave visual mode
+pub fn handle_yank_response_selection(state: Arc<RwLock<AppState>>) {
+    let selection = {
+        let state_read = state.read_lock();
+        let range = state_read.response_visual_range();
+        match (&state_read.request.current_response, range) {
+            (Some(response), Some((start, end))) if !response.is_error => {
+                // Response lines start with the status line and an empty line
+                select_lines(
+                    state_read.visible_response_lines(),
+                    start.saturating_sub(2),
+                    end.saturating_sub(2),
+                )
+            }
+            _ => None,
+        }
+    };
+
+    apply(state.clone(), AppAction::ExitResponseVisual);
+
+    match selection {
+        Some(text) => {
+            log_debug(&format!("Yanking {} selected lines", text.lines().count()));
+            copy_to_clipboard(state, text);
+        }
+        None => log_debug("Nothing selected to yank"),
+    }
+}
+
+/// Yank the selected row of the response table as a JSON object
+pub fn handle_yank_table_row(state: Arc<RwLock<AppState>>) {
+    let row = {
+        let state_read = state.read_lock();
+        state_read.response_table().and_then(|table| {
+            table
+                .rows
+                .get(state_read.ui.response_selected_line)
+                .cloned()
+        })
+    };
+
+    match row {
+        Some(row) => {
+            let text =
+                serde_json::to_string_pretty(&serde_json::Value::Object(row)).unwrap_or_default();
+            copy_to_clipboard(state, text);
+        }
+        None => log_debug("No table row to yank"),
+    }
+}
+
+/// Yank a Rust reqwest function for the selected endpoint, with its current params and body
+pub fn handle_yank_rust_client(
+    selected_index: usize,
+    state: Arc<RwLock<AppState>>,
+    base_url: Option<String>,
+) {
+    let code = {
+        let state_read = state.read_lock();
+        state_read
+            .get_selected_endpoint(selected_index)
+            .map(|endpoint| {
+                let config = state_read.request.configs.get(&endpoint.key());
+                let base_url = endpoint.request_base_url(base_url).unwrap_or_default();
+                codegen::rust_reqwest(
+                    &endpoint,
+                    config,
+                    &base_url,
+                    &state_read.data.security_schemes,
+                )
+            })
+    };
+
+    match code {
+        Some(code) => copy_to_clipboard(state, code),
+        None => log_debug("No endpoint selected to generate code for"),
+    }
+}
+
+/// Yank the JSONPath (e.g. `$.items[3].address.city`) of the selected response line
+pub fn handle_yank_json_path(state: Arc<RwLock<AppState>>) {
+    let path = {
+        let state_read = state.read_lock();
+        let selected_line_idx = state_read.ui.response_selected_line;
+        match &state_read.request.current_response {
+            // Lines in response: [Status line, Empty line, ...body lines...]
+            Some(response) if !response.is_error && selected_line_idx >= 2 => {
+                // Paths only make sense for bodies that are valid JSON
+                serde_json::from_str::<serde_json::Value>(&response.body)
+                    .ok()
+                    .and_then(|_| {
+                        json_path_at(&state_read.request.response_lines, selected_line_idx - 2)
+                    })
+            }
+            _ => None,
+        }
+    };
+
+    match path {
+        Some(path) => copy_to_clipboard(state, path),
+        None => log_debug("No JSON path for the selected line"),
+    }
+}
+
+/// Copy text to the clipboard and flash the selection on success
+fn copy_to_clipboard(state: Arc<RwLock<AppState>>, text: String) {
+    let mode = state.read_lock().ui.osc52;
+    let copied = match mode {
+        Osc52Mode::Always => copy_with_osc52(&text),
+        Osc52Mode::Never => copy_with_system_clipboard(&text),
+        Osc52Mode::Auto if is_remote_session() => copy_with_osc52(&text),
+        Osc52Mode::Auto => copy_with_system_clipboard(&text).or_else(|e| {
+            log_debug(&format!("✗ {e}, falling back to OSC 52"));
+            copy_with_osc52(&text)
+        }),
+    };
+
+    match copied {
+        Ok(()) => {
+            log_debug(&format!("✓ Successfully yanked: {text}"));
+
+            // Set flash flag
+            {
+                let mut state_write = state.write_lock();
+                state_write.ui.yank_flash = true;
             }
+
+            // Spawn task to clear flash after delay
+            let state_clone = state.clone();
+            tokio::spawn(async move {
+                tokio::time::sleep(Duration::from_millis(200)).await;
+                let mut s = state_clone.write_lock();
+                s.ui.yank_flash = false;
+            });
         }
+        Err(e) => state.write_lock().show_error(format!("Copy failed: {e}")),
+    }
+}
+
+fn copy_with_system_clipboard(text: &str) -> Result<(), String> {
+    let mut clipboard = Clipboard::new().map_err(|e| format!("Failed to access clipboard: {e}"))?;
+    clipboard
+        .set_text(text)
+        .map_err(|e| format!("Failed to copy to clipboard: {e}"))
+}
+
+/// Hand the text to the terminal, which sets the clipboard of the machine it runs on
+fn copy_with_osc52(text: &str) -> Result<(), String> {
+    let sequence = osc52_sequence(text, std::env::var_os("TMUX").is_some());
+    let mut stdout = std::io::stdout();
+    stdout
+        .write_all(sequence.as_bytes())
+        .and_then(|_| stdout.flush())
+        .map_err(|e| format!("Failed to write OSC 52 sequence: {e}"))
+}
+
+/// Whether the terminal is on another machine, where the system clipboard is not the user's
+fn is_remote_session() -> bool {
+    std::env::var_os("SSH_TTY").is_some() || std::env::var_os("SSH_CONNECTION").is_some()
+}
+
+/// The OSC 52 sequence setting the clipboard to `text`
+///
+/// Inside tmux it is wrapped in a passthrough sequence so it reaches the outer terminal.
+fn osc52_sequence(text: &str, in_tmux: bool) -> String {
+    let encoded = base64::engine::general_purpose::STANDARD.encode(text);
+    let sequence = format!("\x1b]52;c;{encoded}\x07");
+    if in_tmux {
+        format!("\x1bPtmux;{}\x1b\\", sequence.replace('\x1b', "\x1b\x1b"))
     } else {
-        log_debug("No response available to yank");
+        sequence
     }
 }
 
+/// Join the body lines in the inclusive range, clamped to the body
+fn select_lines<S: AsRef<str>>(lines: &[S], start: usize, end: usize) -> Option<String> {
+    if start >= lines.len() {
+        return None;
+    }
+    let end = end.min(lines.len() - 1);
+    let selected: Vec<&str> = lines[start..=end].iter().map(AsRef::as_ref).collect();
+    Some(selected.join("\n"))
+}
+
+/// A container open above the current line of pretty-printed JSON
+enum PathFrame {
+    Object(Option<String>),
+    Array(Option<usize>),
+}
+
+/// Compute the JSONPath of a line in pretty-printed JSON (`serde_json::to_string_pretty`)
+///
+/// Closing brackets map to the container they close.
+fn json_path_at<S: AsRef<str>>(lines: &[S], target: usize) -> Option<String> {
+    let mut stack: Vec<PathFrame> = Vec::new();
+
+    for (idx, line) in lines.iter().enumerate().take(target + 1) {
+        let trimmed = line.as_ref().trim().trim_end_matches(',');
+
+        if trimmed.starts_with(['}', ']']) {
+            if idx == target {
+                stack.pop();
+                return Some(format_json_path(&stack));
+            }
+            stack.pop();
+            continue;
+        }
+
+        // Each line inside a container starts a new member or element
+        let value = match stack.last_mut() {
+            Some(PathFrame::Object(key)) => {
+                let (name, value) = split_json_key(trimmed)?;
+                *key = Some(name);
+                value
+            }
+            Some(PathFrame::Array(index)) => {
+                *index = Some(index.map_or(0, |i| i + 1));
+                trimmed
+            }
+            None => trimmed,
+        };
+
+        if idx == target {
+            return Some(format_json_path(&stack));
+        }
+        match value {
+            "{" => stack.push(PathFrame::Object(None)),
+            "[" => stack.push(PathFrame::Array(None)),
+            _ => {}
+        }
+    }
+
+    None
+}
+
+/// Split `"key": value` into the unescaped key and the value text
+fn split_json_key(line: &str) -> Option<(String, &str)> {
+    let mut escaped = false;
+    let end = line
+        .char_indices()
+        .skip(1)
+        .find(|&(_, c)| {
+            let is_end = c == '"' && !escaped;
+            escaped = c == '\\' && !escaped;
+            is_end
+        })
+        .map(|(i, _)| i)?;
+
+    let key: String = serde_json::from_str(&line[..=end]).ok()?;
+    let value = line[end + 1..].trim_start().strip_prefix(':')?.trim_start();
+    Some((key, value))
+}
+
+fn format_json_path(stack: &[PathFrame]) -> String {
+    let mut path = String::from("$");
+    for frame in stack {
+        match frame {
+            PathFrame::Object(Some(key)) => {
+                let is_identifier = key
+                    .chars()
+                    .next()
+                    .is_some_and(|c| c.is_alphabetic() || c == '_')
+                    && key.chars().all(|c| c.is_alphanumeric() || c == '_');
+                if is_identifier {
+                    path.push('.');
+                    path.push_str(key);
+                } else {
+                    path.push_str(&format!("['{}']", key.replace('\'', "\\'")));
+                }
+            }
+            PathFrame::Array(Some(index)) => path.push_str(&format!("[{index}]")),
+            PathFrame::Object(None) | PathFrame::Array(None) => {}
+        }
+    }
+    path
+}
+
 /// Extract the value portion from a JSON line
 /// Examples:
 ///   "  "access_token": "abc123"," -> "abc123"
@@ -133,6 +390,15 @@ fn extract_json_value(line: &str) -> String {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_osc52_sequence() {
+        assert_eq!(osc52_sequence("hi", false), "\x1b]52;c;aGk=\x07");
+        assert_eq!(
+            osc52_sequence("hi", true),
+            "\x1bPtmux;\x1b\x1b]52;c;aGk=\x07\x1b\\"
+        );
+    }
+
     #[test]
     fn test_extract_json_value_simple() {
         assert_eq!(
@@ -154,6 +420,44 @@ mod tests {
         assert_eq!(extract_json_value("  \"enabled\": false"), "false");
     }
 
+    #[test]
+    fn test_select_lines_clamps_to_body() {
+        let lines = ["{", "  \"id\": 1,", "  \"name\": \"x\"", "}"];
+        assert_eq!(
+            select_lines(&lines, 1, 2).as_deref(),
+            Some("  \"id\": 1,\n  \"name\": \"x\"")
+        );
+        assert_eq!(
+            select_lines(&lines, 2, 10).as_deref(),
+            Some("  \"name\": \"x\"\n}")
+        );
+        assert_eq!(select_lines(&lines, 4, 5), None);
+    }
+
+    #[test]
+    fn test_json_path_at() {
+        let body = serde_json::to_string_pretty(&serde_json::json!({
+            "items": [
+                { "id": 1 },
+                { "address": { "city": "Oslo" }, "tags": ["a", "b"] }
+            ],
+            "page size": 2
+        }))
+        .unwrap();
+        let lines: Vec<&str> = body.lines().collect();
+        let path_of = |needle: &str| {
+            let idx = lines.iter().position(|l| l.contains(needle)).unwrap();
+            json_path_at(&lines, idx).unwrap()
+        };
+
+        assert_eq!(json_path_at(&lines, 0).unwrap(), "$");
+        assert_eq!(path_of("\"id\""), "$.items[0].id");
+        assert_eq!(path_of("\"city\""), "$.items[1].address.city");
+        assert_eq!(path_of("\"b\""), "$.items[1].tags[1]");
+        assert_eq!(path_of("page size"), "$['page size']");
+        assert_eq!(json_path_at(&lines, lines.len() - 1).unwrap(), "$");
+    }
+
     #[test]
     fn test_extract_json_value_no_colon() {
         assert_eq!(extract_json_value("  123"), "123");
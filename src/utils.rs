@@ -1,11 +1,158 @@
-pub fn mask_token(token: &str) -> String {
-    let len = token.len();
-    if len <= 15 {
-        // Too short to safely show, just show dots
-        return "●".repeat(len);
+use std::borrow::Cow;
+use std::fs;
+use std::path::PathBuf;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// Whether `url` is under `prefix`: the same scheme, host and port, and a path under
+/// the prefix's path by whole segments; a prefix of just `scheme://` covers the whole scheme
+///
+/// Unlike a string prefix, `https://api.example.com` doesn't cover
+/// `https://api.example.com.evil.io` or `https://api.example.com:8443`.
+pub fn url_under(prefix: &str, url: &str) -> bool {
+    let Ok(url) = url::Url::parse(url) else {
+        return false;
+    };
+    if let Some(scheme) = prefix.strip_suffix("://") {
+        return url.scheme().eq_ignore_ascii_case(scheme);
+    }
+    let Ok(prefix) = url::Url::parse(prefix) else {
+        return false;
+    };
+    let prefix_path = prefix.path().trim_end_matches('/');
+    prefix.scheme() == url.scheme()
+        && prefix.host_str() == url.host_str()
+        && prefix.port_or_known_default() == url.port_or_known_default()
+        && url
+            .path()
+            .strip_prefix(prefix_path)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+}
+
+/// Shorten text to at most `max_width` terminal columns by replacing its middle with `…`
+///
+/// Both ends usually matter, e.g. the resource and the last segment of a path.
+pub fn truncate_middle(text: &str, max_width: usize) -> Cow<'_, str> {
+    if text.width() <= max_width {
+        return Cow::Borrowed(text);
+    }
+    let Some(budget) = max_width.checked_sub(1) else {
+        return Cow::Borrowed("");
+    };
+
+    let take = |chars: &mut dyn Iterator<Item = char>, limit: usize| {
+        let mut width = 0;
+        let mut taken = Vec::new();
+        for c in chars {
+            width += c.width().unwrap_or(0);
+            if width > limit {
+                break;
+            }
+            taken.push(c);
+        }
+        taken
+    };
+    let head = take(&mut text.chars(), budget - budget / 2);
+    let head_width: usize = head.iter().map(|c| c.width().unwrap_or(0)).sum();
+    let mut tail = take(&mut text.chars().rev(), budget - head_width);
+    tail.reverse();
+
+    Cow::Owned(
+        head.into_iter()
+            .chain(std::iter::once('…'))
+            .chain(tail)
+            .collect(),
+    )
+}
+
+/// Render values as a one-line sparkline scaled between their min and max
+pub fn sparkline(values: &[u128]) -> String {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+    let (Some(min), Some(max)) = (values.iter().min(), values.iter().max()) else {
+        return String::new();
+    };
+    let range = (max - min).max(1);
+    values
+        .iter()
+        .map(|value| BARS[((value - min) * (BARS.len() as u128 - 1) / range) as usize])
+        .collect()
+}
+
+/// Suggested export file name for an endpoint path, e.g. `users-id-posts.csv`
+pub fn export_file_name(endpoint_path: &str, extension: &str) -> String {
+    let stem: Vec<String> = endpoint_path
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .map(str::to_lowercase)
+        .collect();
+
+    if stem.is_empty() {
+        format!("response.{extension}")
+    } else {
+        format!("{}.{extension}", stem.join("-"))
     }
+}
+
+/// Write an exported file, expanding a leading `~/`; returns the written path
+pub fn write_export(path: &str, contents: &[u8]) -> std::io::Result<PathBuf> {
+    let path = match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(path),
+    };
+    fs::write(&path, contents)?;
+    Ok(path)
+}
 
-    let first = &token[..7];
-    let last = &token[len - 6..];
-    format!("{first}...{last}")
+/// Write an exported shell script and make it executable
+pub fn write_script(path: &str, script: &str) -> std::io::Result<PathBuf> {
+    let path = write_export(path, script.as_bytes())?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755))?;
+    }
+    Ok(path)
+}
+
+/// Attempts to pretty-print JSON, returns original string if not valid JSON
+pub fn try_format_json(body: &str) -> String {
+    // Try to parse as JSON
+    match serde_json::from_str::<serde_json::Value>(body) {
+        Ok(json) => {
+            // Successfully parsed, pretty-print it
+            serde_json::to_string_pretty(&json).unwrap_or_else(|_| body.to_string())
+        }
+        Err(_) => {
+            // Not valid JSON, return as-is
+            body.to_string()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_truncate_middle() {
+        assert!(matches!(
+            truncate_middle("/users/{id}", 20),
+            Cow::Borrowed("/users/{id}")
+        ));
+        assert_eq!(
+            truncate_middle("/organizations/{orgId}/members/{memberId}", 15),
+            "/organi…mberId}"
+        );
+        // Wide characters count two columns
+        assert_eq!(truncate_middle("日本語のパス", 7), "日…パス");
+        assert_eq!(truncate_middle("/users", 1), "…");
+        assert_eq!(truncate_middle("/users", 0), "");
+    }
+
+    #[test]
+    fn test_sparkline() {
+        assert_eq!(sparkline(&[]), "");
+        assert_eq!(sparkline(&[5, 5]), "▁▁");
+        assert_eq!(sparkline(&[100, 450, 800]), "▁▄█");
+    }
 }
@@ -0,0 +1,106 @@
+//! Opening responses outside the TUI
+//!
+//! The response is written to a temp file and shown in `$PAGER` (the TUI is
+//! suspended until the pager exits) or handed to the system's default opener,
+//! which usually means a browser for HTML and JSON.
+
+use crate::types::ApiResponse;
+use color_eyre::Result;
+use ratatui::DefaultTerminal;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+/// External programs a response can be opened in
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ExternalViewer {
+    Pager,
+    Browser,
+}
+
+/// Write the response to a temp file whose extension matches its content type
+///
+/// JSON is written pretty-printed (`formatted_lines`), anything else as received.
+pub fn write_response_file(response: &ApiResponse, formatted_lines: &[String]) -> Result<PathBuf> {
+    let content_type = response
+        .headers
+        .get("content-type")
+        .map(String::as_str)
+        .unwrap_or_default();
+    let extension = file_extension(content_type, &response.body);
+
+    let path = std::env::temp_dir().join(format!("lazy-swagger-tui-response.{extension}"));
+    if extension == "json" {
+        std::fs::write(&path, formatted_lines.join("\n"))?;
+    } else {
+        std::fs::write(&path, response.body.bytes())?;
+    }
+    Ok(path)
+}
+
+/// Show a file in `$PAGER` (default `less`), suspending the TUI until the pager exits
+pub fn open_in_pager(terminal: DefaultTerminal, path: &PathBuf) -> Result<DefaultTerminal> {
+    let pager = std::env::var("PAGER").unwrap_or_else(|_| "less".to_string());
+    let mut parts = pager.split_whitespace();
+    let program = parts.next().unwrap_or("less");
+
+    drop(terminal);
+    ratatui::restore();
+    // A missing pager just returns to the TUI, it must not end the session
+    let _ = Command::new(program).args(parts).arg(path).status();
+    let mut terminal = ratatui::init();
+    terminal.clear()?;
+
+    Ok(terminal)
+}
+
+/// Open a file with the system's default application, without waiting for it
+pub fn open_in_browser(path: &PathBuf) -> Result<()> {
+    let mut command = if cfg!(target_os = "macos") {
+        Command::new("open")
+    } else if cfg!(target_os = "windows") {
+        let mut command = Command::new("cmd");
+        command.args(["/C", "start", ""]);
+        command
+    } else {
+        Command::new("xdg-open")
+    };
+
+    command
+        .arg(path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+    Ok(())
+}
+
+/// File extension for a response, from its content type or, failing that, its body
+fn file_extension(content_type: &str, body: &str) -> &'static str {
+    let content_type = content_type.to_lowercase();
+    if content_type.contains("html") {
+        "html"
+    } else if content_type.contains("json")
+        || serde_json::from_str::<serde_json::Value>(body).is_ok()
+    {
+        "json"
+    } else if content_type.contains("xml") {
+        "xml"
+    } else {
+        "txt"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_extension() {
+        assert_eq!(file_extension("text/html; charset=utf-8", ""), "html");
+        assert_eq!(file_extension("application/problem+json", "{}"), "json");
+        assert_eq!(file_extension("", r#"{"id": 1}"#), "json");
+        assert_eq!(file_extension("application/xml", "<a/>"), "xml");
+        assert_eq!(file_extension("text/plain", "hello"), "txt");
+    }
+}